@@ -12,7 +12,10 @@
 
 use std::sync::Arc;
 use stellar_insights_backend::database::Database;
-use stellar_insights_backend::services::contract::{ContractConfig, ContractService};
+use stellar_insights_backend::network::NetworkConfig;
+use stellar_insights_backend::services::contract::{
+    ContractAddresses, ContractConfig, ContractService, ContractSettings,
+};
 use stellar_insights_backend::services::snapshot::SnapshotService;
 use tracing::{info, Level};
 use tracing_subscriber;
@@ -38,7 +41,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize contract service (optional)
     let contract_service = if std::env::var("SNAPSHOT_CONTRACT_ID").is_ok() {
         info!("Contract service configured - will submit to blockchain");
-        Some(Arc::new(ContractService::from_env()?))
+        let settings = ContractSettings {
+            addresses: ContractAddresses {
+                fallback: std::env::var("SNAPSHOT_CONTRACT_ID").ok(),
+                ..ContractAddresses::default()
+            },
+            rpc_url: std::env::var("SOROBAN_RPC_URL").ok(),
+            source_secret_key: std::env::var("STELLAR_SOURCE_SECRET_KEY").ok(),
+        };
+        Some(Arc::new(ContractService::from_settings(
+            &settings,
+            NetworkConfig::from_env(),
+        )?))
     } else {
         info!("Contract service not configured - will only generate hash");
         None
@@ -0,0 +1,6 @@
+-- Ledgers whose writes were fully committed by the ingestion service.
+-- Consulted before processing so restarts and re-runs skip completed ledgers.
+CREATE TABLE IF NOT EXISTS processed_ledgers (
+    sequence INTEGER PRIMARY KEY,
+    processed_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,19 @@
+-- Path payments whose transaction failed, recorded during ledger ingestion
+CREATE TABLE IF NOT EXISTS path_payment_failures (
+    operation_id TEXT PRIMARY KEY,
+    ledger_sequence INTEGER NOT NULL,
+    transaction_hash TEXT NOT NULL,
+    operation_type TEXT NOT NULL,
+    corridor_key TEXT NOT NULL,
+    source_asset_code TEXT NOT NULL,
+    source_asset_issuer TEXT NOT NULL,
+    destination_asset_code TEXT NOT NULL,
+    destination_asset_issuer TEXT NOT NULL,
+    amount REAL NOT NULL DEFAULT 0,
+    failure_reason TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    FOREIGN KEY (ledger_sequence) REFERENCES ledgers(sequence)
+);
+
+CREATE INDEX IF NOT EXISTS idx_path_payment_failures_corridor ON path_payment_failures(corridor_key);
+CREATE INDEX IF NOT EXISTS idx_path_payment_failures_created_at ON path_payment_failures(created_at);
@@ -0,0 +1,11 @@
+-- Holder concentration per asset, computed from a sample of holder balances.
+-- The metrics are NULL when they could not be computed; concentration_note says why.
+ALTER TABLE trustline_stats ADD COLUMN top10_holder_share REAL;
+ALTER TABLE trustline_stats ADD COLUMN gini_coefficient REAL;
+ALTER TABLE trustline_stats ADD COLUMN concentration_holders INTEGER;
+ALTER TABLE trustline_stats ADD COLUMN concentration_note TEXT;
+
+ALTER TABLE trustline_snapshots ADD COLUMN top10_holder_share REAL;
+ALTER TABLE trustline_snapshots ADD COLUMN gini_coefficient REAL;
+ALTER TABLE trustline_snapshots ADD COLUMN concentration_holders INTEGER;
+ALTER TABLE trustline_snapshots ADD COLUMN concentration_note TEXT;
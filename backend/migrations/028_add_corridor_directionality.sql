@@ -0,0 +1,3 @@
+-- Directional corridors keep source -> destination order; all others are
+-- stored pair-normalized (assets sorted) so A/B and B/A share one row
+ALTER TABLE corridors ADD COLUMN is_directional INTEGER NOT NULL DEFAULT 0;
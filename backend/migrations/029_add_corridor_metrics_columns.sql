@@ -0,0 +1,50 @@
+-- corridor_metrics was created without the id, latency and liquidity columns
+-- that CorridorMetrics and snapshot aggregation read. SQLite cannot add a
+-- column with a generated default, so rebuild the table; existing rows get a
+-- random UUID v4 id.
+CREATE TABLE corridor_metrics_new (
+    id TEXT PRIMARY KEY NOT NULL DEFAULT (
+        lower(hex(randomblob(4))) || '-' ||
+        lower(hex(randomblob(2))) || '-4' ||
+        substr(lower(hex(randomblob(2))), 2) || '-' ||
+        substr('89ab', 1 + abs(random() % 4), 1) ||
+        substr(lower(hex(randomblob(2))), 2) || '-' ||
+        lower(hex(randomblob(6)))
+    ),
+    corridor_key TEXT NOT NULL,
+    asset_a_code TEXT NOT NULL,
+    asset_a_issuer TEXT NOT NULL,
+    asset_b_code TEXT NOT NULL,
+    asset_b_issuer TEXT NOT NULL,
+    date TEXT NOT NULL,
+    total_transactions INTEGER DEFAULT 0,
+    successful_transactions INTEGER DEFAULT 0,
+    failed_transactions INTEGER DEFAULT 0,
+    success_rate REAL DEFAULT 0,
+    volume_usd REAL DEFAULT 0,
+    avg_settlement_latency_ms INTEGER,
+    median_settlement_latency_ms INTEGER,
+    liquidity_depth_usd REAL NOT NULL DEFAULT 0,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    updated_at TEXT DEFAULT CURRENT_TIMESTAMP,
+    UNIQUE (corridor_key, date)
+);
+
+INSERT INTO corridor_metrics_new (
+    corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer, date,
+    total_transactions, successful_transactions, failed_transactions, success_rate,
+    volume_usd, created_at, updated_at
+)
+SELECT
+    corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer, date,
+    total_transactions, successful_transactions, failed_transactions, success_rate,
+    volume_usd, created_at, updated_at
+FROM corridor_metrics;
+
+DROP TABLE corridor_metrics;
+ALTER TABLE corridor_metrics_new RENAME TO corridor_metrics;
+
+CREATE INDEX IF NOT EXISTS idx_corridor_metrics_key_date
+    ON corridor_metrics(corridor_key, date DESC);
+CREATE INDEX IF NOT EXISTS idx_corridor_metrics_date
+    ON corridor_metrics(date DESC);
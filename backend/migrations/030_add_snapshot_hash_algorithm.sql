@@ -0,0 +1,3 @@
+-- Algorithm used to compute snapshots.hash; rows written before this column
+-- existed were hashed with SHA-256.
+ALTER TABLE snapshots ADD COLUMN hash_algorithm TEXT NOT NULL DEFAULT 'sha256';
@@ -0,0 +1,15 @@
+-- Accounts re-created by create_account at the address of an earlier account merge
+CREATE TABLE IF NOT EXISTS account_churn (
+    recreate_operation_id TEXT PRIMARY KEY,
+    account TEXT NOT NULL,
+    merge_operation_id TEXT NOT NULL,
+    merge_ledger_sequence INTEGER NOT NULL,
+    merged_into TEXT NOT NULL,
+    recreate_ledger_sequence INTEGER NOT NULL,
+    funder TEXT,
+    recreated_at DATETIME NOT NULL,
+    FOREIGN KEY (merge_operation_id) REFERENCES account_merges(operation_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_account_churn_account ON account_churn(account);
+CREATE INDEX IF NOT EXISTS idx_account_churn_recreated_at ON account_churn(recreated_at DESC);
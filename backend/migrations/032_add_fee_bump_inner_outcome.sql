@@ -0,0 +1,4 @@
+-- Whether the inner transaction sponsored by a fee bump succeeded
+ALTER TABLE fee_bump_transactions ADD COLUMN inner_successful BOOLEAN NOT NULL DEFAULT 1;
+
+CREATE INDEX IF NOT EXISTS idx_fee_bump_inner_successful ON fee_bump_transactions(inner_successful);
@@ -0,0 +1,15 @@
+-- One analytics snapshot per epoch. Keep the most recent row of any epoch
+-- that was stored more than once before adding the constraint.
+DELETE FROM snapshots
+WHERE entity_type = 'analytics_snapshot'
+  AND id NOT IN (
+    SELECT id FROM (
+        SELECT id, ROW_NUMBER() OVER (PARTITION BY epoch ORDER BY created_at DESC) AS row_num
+        FROM snapshots
+        WHERE entity_type = 'analytics_snapshot'
+    )
+    WHERE row_num = 1
+  );
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_snapshots_analytics_epoch
+    ON snapshots(epoch) WHERE entity_type = 'analytics_snapshot';
@@ -0,0 +1,9 @@
+-- Settlement latency percentiles per corridor over the latest aggregation window
+CREATE TABLE IF NOT EXISTS corridor_latency_percentiles (
+    corridor_key TEXT PRIMARY KEY,
+    sample_count INTEGER NOT NULL,
+    p50_ms INTEGER,
+    p90_ms INTEGER,
+    p99_ms INTEGER,
+    updated_at DATETIME NOT NULL
+);
@@ -0,0 +1,18 @@
+-- Migration: Record the request id of audited admin actions and make the
+-- audit log append-only
+
+ALTER TABLE admin_audit_log ADD COLUMN request_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_admin_audit_request_id ON admin_audit_log(request_id);
+
+CREATE TRIGGER IF NOT EXISTS admin_audit_log_no_update
+BEFORE UPDATE ON admin_audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'admin_audit_log is append-only');
+END;
+
+CREATE TRIGGER IF NOT EXISTS admin_audit_log_no_delete
+BEFORE DELETE ON admin_audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'admin_audit_log is append-only');
+END;
@@ -0,0 +1,3 @@
+-- Corridors are soft-deleted: deleted_at is set and status becomes 'deleted',
+-- so their history stays in place but listings leave them out
+ALTER TABLE corridors ADD COLUMN deleted_at TEXT;
@@ -0,0 +1,2 @@
+-- Set once the anchor's home domain lists its stellar account in stellar.toml
+ALTER TABLE anchors ADD COLUMN domain_verified BOOLEAN NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- Consecutive failed home domain re-checks; domain_verified is only cleared
+-- once this reaches the configured threshold
+ALTER TABLE anchors ADD COLUMN domain_check_failures INTEGER NOT NULL DEFAULT 0;
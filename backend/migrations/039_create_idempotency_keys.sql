@@ -0,0 +1,14 @@
+-- Attempt state of side-effecting operations, keyed by idempotency key, so
+-- a retried operation that already completed is not run again
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    key TEXT PRIMARY KEY,
+    -- in_progress, failed or completed
+    status TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 1,
+    -- JSON result of the completed operation
+    result TEXT,
+    last_error TEXT,
+    -- Unix seconds of the latest claim, to reclaim attempts that never finished
+    claimed_at INTEGER NOT NULL,
+    updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,4 @@
+-- Look up analytics snapshots by hash, to spot one hash stored for several
+-- epochs
+CREATE INDEX IF NOT EXISTS idx_snapshots_analytics_hash
+    ON snapshots(hash) WHERE entity_type = 'analytics_snapshot';
@@ -0,0 +1,10 @@
+-- Per-resource write grants. Once an anchor or corridor has any grant, only
+-- the users granted on it (and admins) may mutate it.
+CREATE TABLE IF NOT EXISTS resource_permissions (
+    -- anchor or corridor
+    resource_type TEXT NOT NULL,
+    resource_id TEXT NOT NULL,
+    user_id TEXT NOT NULL,
+    granted_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (resource_type, resource_id, user_id)
+);
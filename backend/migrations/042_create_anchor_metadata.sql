@@ -0,0 +1,11 @@
+-- Display metadata read from each anchor's stellar.toml
+CREATE TABLE IF NOT EXISTS anchor_metadata (
+    anchor_id TEXT PRIMARY KEY REFERENCES anchors(id) ON DELETE CASCADE,
+    organization_name TEXT,
+    organization_logo TEXT,
+    documentation_url TEXT,
+    -- JSON array of currency codes listed under CURRENCIES
+    supported_currencies TEXT NOT NULL DEFAULT '[]',
+    -- Unix seconds of the stellar.toml fetch the row came from
+    fetched_at INTEGER NOT NULL
+);
@@ -0,0 +1,4 @@
+-- Reserves exactly as Horizon reports them, with seven decimal places; the
+-- REAL columns cannot hold every Stellar amount. NULL until a pool is synced
+ALTER TABLE liquidity_pools ADD COLUMN reserve_a_amount_exact TEXT;
+ALTER TABLE liquidity_pools ADD COLUMN reserve_b_amount_exact TEXT;
@@ -0,0 +1,11 @@
+-- Asset a payment was sent in; it differs from the received asset only for
+-- path payments. NULL code and issuer mean the native asset, as for the
+-- received asset. Existing rows were all stored by their received asset
+ALTER TABLE payments ADD COLUMN source_asset_code TEXT;
+ALTER TABLE payments ADD COLUMN source_asset_issuer TEXT;
+
+UPDATE payments
+SET source_asset_code = asset_code, source_asset_issuer = asset_issuer;
+
+CREATE INDEX IF NOT EXISTS idx_payments_corridor
+    ON payments(source_asset_code, source_asset_issuer, asset_code, asset_issuer);
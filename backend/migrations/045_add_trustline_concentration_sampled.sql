@@ -0,0 +1,4 @@
+-- Whether the concentration metrics cover only the first holders fetched
+-- because the asset has more than the fetch limit
+ALTER TABLE trustline_stats ADD COLUMN concentration_sampled INTEGER;
+ALTER TABLE trustline_snapshots ADD COLUMN concentration_sampled INTEGER;
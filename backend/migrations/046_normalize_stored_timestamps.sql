@@ -0,0 +1,40 @@
+-- Rewrite timestamps stored before ingestion normalized them into the
+-- canonical `YYYY-MM-DDTHH:MM:SSZ` form. Earlier rows hold RFC 3339 with an
+-- offset and possibly a fraction (`2024-01-15T10:30:00.5+00:00`) or SQLite's
+-- `YYYY-MM-DD HH:MM:SS`. Values SQLite cannot read are left as they are.
+
+UPDATE ledgers
+SET close_time = strftime('%Y-%m-%dT%H:%M:%SZ', close_time)
+WHERE close_time GLOB '*-*'
+  AND strftime('%Y-%m-%dT%H:%M:%SZ', close_time) <> close_time;
+
+-- Close times reported as unix seconds
+UPDATE ledgers
+SET close_time = strftime('%Y-%m-%dT%H:%M:%SZ', close_time, 'unixepoch')
+WHERE close_time <> ''
+  AND close_time NOT GLOB '*[^0-9]*';
+
+UPDATE payments
+SET created_at = strftime('%Y-%m-%dT%H:%M:%SZ', created_at)
+WHERE created_at GLOB '*-*'
+  AND strftime('%Y-%m-%dT%H:%M:%SZ', created_at) <> created_at;
+
+UPDATE path_payment_failures
+SET created_at = strftime('%Y-%m-%dT%H:%M:%SZ', created_at)
+WHERE created_at GLOB '*-*'
+  AND strftime('%Y-%m-%dT%H:%M:%SZ', created_at) <> created_at;
+
+UPDATE account_merges
+SET created_at = strftime('%Y-%m-%dT%H:%M:%SZ', created_at)
+WHERE created_at GLOB '*-*'
+  AND strftime('%Y-%m-%dT%H:%M:%SZ', created_at) <> created_at;
+
+UPDATE account_churn
+SET recreated_at = strftime('%Y-%m-%dT%H:%M:%SZ', recreated_at)
+WHERE recreated_at GLOB '*-*'
+  AND strftime('%Y-%m-%dT%H:%M:%SZ', recreated_at) <> recreated_at;
+
+UPDATE fee_bump_transactions
+SET created_at = strftime('%Y-%m-%dT%H:%M:%SZ', created_at)
+WHERE created_at GLOB '*-*'
+  AND strftime('%Y-%m-%dT%H:%M:%SZ', created_at) <> created_at;
@@ -0,0 +1,50 @@
+-- Merge non-directional corridors stored in reversed asset order, from before
+-- corridors were pair-normalized, into their normalized row.
+--
+-- A reversed row whose normalized counterpart exists is deleted, keeping the
+-- higher reliability score and repointing its metrics; otherwise it is
+-- rewritten in normalized order. Directional corridors, and reversed rows
+-- whose normalized slot is taken by one, are left alone.
+
+CREATE TEMP TABLE reversed_corridors AS
+SELECT r.id AS id, c.id AS canonical_id, r.reliability_score AS reliability_score
+FROM corridors r
+LEFT JOIN corridors c
+  ON c.source_asset_code = r.destination_asset_code
+ AND c.source_asset_issuer = r.destination_asset_issuer
+ AND c.destination_asset_code = r.source_asset_code
+ AND c.destination_asset_issuer = r.source_asset_issuer
+WHERE r.is_directional = 0
+  AND r.source_asset_code || ':' || r.source_asset_issuer
+    > r.destination_asset_code || ':' || r.destination_asset_issuer
+  AND COALESCE(c.is_directional, 0) = 0;
+
+UPDATE corridors
+SET reliability_score = MAX(
+        COALESCE(reliability_score, 0),
+        (SELECT COALESCE(MAX(d.reliability_score), 0)
+         FROM reversed_corridors d
+         WHERE d.canonical_id = corridors.id)
+    ),
+    updated_at = CURRENT_TIMESTAMP
+WHERE id IN (SELECT canonical_id FROM reversed_corridors);
+
+UPDATE metrics
+SET entity_id = (
+    SELECT d.canonical_id FROM reversed_corridors d WHERE d.id = metrics.entity_id
+)
+WHERE entity_type = 'corridor'
+  AND entity_id IN (SELECT id FROM reversed_corridors WHERE canonical_id IS NOT NULL);
+
+DELETE FROM corridors
+WHERE id IN (SELECT id FROM reversed_corridors WHERE canonical_id IS NOT NULL);
+
+UPDATE corridors
+SET source_asset_code = destination_asset_code,
+    source_asset_issuer = destination_asset_issuer,
+    destination_asset_code = source_asset_code,
+    destination_asset_issuer = source_asset_issuer,
+    updated_at = CURRENT_TIMESTAMP
+WHERE id IN (SELECT id FROM reversed_corridors WHERE canonical_id IS NULL);
+
+DROP TABLE reversed_corridors;
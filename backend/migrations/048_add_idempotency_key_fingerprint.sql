@@ -0,0 +1,7 @@
+-- Fingerprint of the input an idempotency key was used with, so reusing a
+-- key for different input is refused instead of returning the old result
+ALTER TABLE idempotency_keys ADD COLUMN fingerprint TEXT;
+
+-- Expired keys are pruned by age
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_updated_at
+    ON idempotency_keys(updated_at);
@@ -1,20 +1,79 @@
 use anyhow::Result;
-use chrono::Utc;
-use sqlx::SqlitePool;
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::request::Parts;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
+use std::convert::Infallible;
+use std::net::SocketAddr;
 use uuid::Uuid;
 
-#[derive(Debug, Clone)]
+use crate::auth_middleware::AuthUser;
+use crate::request_id::RequestId;
+
+/// Status recorded for an admin action that completed
+pub const STATUS_SUCCESS: &str = "success";
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
 pub struct AdminAuditLogEntry {
     pub id: String,
-    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub timestamp: DateTime<Utc>,
     pub action: String,
+    /// Target of the action, e.g. `anchor:<id>`
     pub resource: String,
+    /// Actor: the authenticated user id, or `ip:<addr>` on IP-whitelisted routes
     pub user_id: String,
     pub status: String,
-    pub details: serde_json::Value,
+    /// JSON-encoded details of the action
+    pub details: Option<String>,
+    pub request_id: Option<String>,
     pub hash: String,
 }
 
+/// Who performed an admin action and the request it arrived on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditContext {
+    pub actor: String,
+    pub request_id: Option<String>,
+}
+
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for AuditContext
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let actor = if let Some(user) = parts.extensions.get::<AuthUser>() {
+            user.user_id.clone()
+        } else if let Some(ConnectInfo(addr)) = parts.extensions.get::<ConnectInfo<SocketAddr>>() {
+            format!("ip:{}", addr.ip())
+        } else {
+            "unknown".to_string()
+        };
+        let request_id = parts
+            .extensions
+            .get::<RequestId>()
+            .map(|id| id.as_str().to_string());
+
+        Ok(Self { actor, request_id })
+    }
+}
+
+/// Filters for querying the audit log; unset fields match everything
+#[derive(Debug, Clone, Default)]
+pub struct AdminAuditFilter {
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    pub resource: Option<String>,
+    pub request_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub limit: i64,
+    pub offset: i64,
+}
+
 pub struct AdminAuditLogger {
     pool: SqlitePool,
 }
@@ -24,6 +83,36 @@ impl AdminAuditLogger {
         Self { pool }
     }
 
+    /// Record a completed admin action. Failures are logged rather than
+    /// returned, since the action itself has already taken effect.
+    pub async fn record(
+        &self,
+        context: &AuditContext,
+        action: &str,
+        resource: &str,
+        details: serde_json::Value,
+    ) {
+        if let Err(e) = self
+            .log_action(
+                action,
+                resource,
+                &context.actor,
+                STATUS_SUCCESS,
+                details,
+                context.request_id.as_deref(),
+            )
+            .await
+        {
+            tracing::error!(
+                action = action,
+                resource = resource,
+                actor = %context.actor,
+                "Failed to record admin audit entry: {}",
+                e
+            );
+        }
+    }
+
     /// Record an admin action with tamper-proof hash chaining
     pub async fn log_action(
         &self,
@@ -32,33 +121,43 @@ impl AdminAuditLogger {
         user_id: &str,
         status: &str,
         details: serde_json::Value,
-        prev_hash: Option<&str>,
+        request_id: Option<&str>,
     ) -> Result<()> {
         let timestamp = Utc::now();
         let id = Uuid::new_v4().to_string();
-        // The original format string for `data` was correct in terms of placeholder count.
-        // If "Fix placeholders in audit log" implies changing how `details` is serialized,
-        // `details.to_string()` is a common way to include JSON in a string hash.
+        let details = details.to_string();
         let data = format!(
-            "{}|{}|{}|{}|{}|{}|{}",
+            "{}|{}|{}|{}|{}|{}|{}|{}",
             id,
             timestamp,
             action,
             resource,
             user_id,
             status,
-            details.to_string()
+            details,
+            request_id.unwrap_or_default()
         );
+
+        // Read the previous hash and append in one transaction so the chain
+        // has no forks
+        let mut tx = self.pool.begin().await?;
+        let prev_hash: Option<String> = sqlx::query_scalar(
+            "SELECT hash FROM admin_audit_log ORDER BY timestamp DESC, rowid DESC LIMIT 1",
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
         let hash_input = match prev_hash {
             Some(h) => format!("{}|{}", h, data),
-            None => data.clone(),
+            None => data,
         };
         let hash = format!("{:x}", md5::compute(hash_input));
 
         sqlx::query(
             r#"
-            INSERT INTO admin_audit_log (id, timestamp, action, resource, user_id, status, details, hash)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO admin_audit_log (
+                id, timestamp, action, resource, user_id, status, details, request_id, hash
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&id)
@@ -68,10 +167,49 @@ impl AdminAuditLogger {
         .bind(user_id)
         .bind(status)
         .bind(details)
+        .bind(request_id)
         .bind(&hash)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         Ok(())
     }
+
+    /// Audit entries matching `filter`, newest first
+    pub async fn query(&self, filter: &AdminAuditFilter) -> Result<Vec<AdminAuditLogEntry>> {
+        let mut query: QueryBuilder<Sqlite> = QueryBuilder::new(
+            "SELECT id, timestamp, action, resource, user_id, status, details, request_id, hash \
+             FROM admin_audit_log WHERE 1 = 1",
+        );
+        if let Some(actor) = &filter.actor {
+            query.push(" AND user_id = ").push_bind(actor);
+        }
+        if let Some(action) = &filter.action {
+            query.push(" AND action = ").push_bind(action);
+        }
+        if let Some(resource) = &filter.resource {
+            query.push(" AND resource = ").push_bind(resource);
+        }
+        if let Some(request_id) = &filter.request_id {
+            query.push(" AND request_id = ").push_bind(request_id);
+        }
+        if let Some(since) = filter.since {
+            query.push(" AND timestamp >= ").push_bind(since);
+        }
+        if let Some(until) = filter.until {
+            query.push(" AND timestamp <= ").push_bind(until);
+        }
+        query
+            .push(" ORDER BY timestamp DESC, rowid DESC LIMIT ")
+            .push_bind(filter.limit)
+            .push(" OFFSET ")
+            .push_bind(filter.offset);
+
+        let entries = query
+            .build_query_as::<AdminAuditLogEntry>()
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(entries)
+    }
 }
@@ -1,6 +1,7 @@
 use crate::models::{AnchorMetrics, AnchorStatus};
 
 pub mod corridor;
+pub mod health;
 
 /// Performance metrics for an anchor's individual asset
 #[derive(Debug, Clone)]
@@ -0,0 +1,70 @@
+use crate::models::AnchorStatus;
+
+/// Green/yellow/red health band derived from a success rate
+pub type HealthStatus = AnchorStatus;
+
+/// Success rate (percent) at or above which an entity is green by default
+pub const DEFAULT_GREEN_THRESHOLD: f64 = 99.0;
+
+/// Success rate (percent) at or above which an entity is yellow by default
+pub const DEFAULT_YELLOW_THRESHOLD: f64 = 95.0;
+
+/// Success rate thresholds separating the health bands
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthThresholds {
+    /// Minimum success rate for green
+    pub green: f64,
+    /// Minimum success rate for yellow; anything lower is red
+    pub yellow: f64,
+}
+
+impl Default for HealthThresholds {
+    fn default() -> Self {
+        Self {
+            green: DEFAULT_GREEN_THRESHOLD,
+            yellow: DEFAULT_YELLOW_THRESHOLD,
+        }
+    }
+}
+
+impl HealthThresholds {
+    /// Band for a success rate given in percent
+    pub fn classify(&self, success_rate: f64) -> HealthStatus {
+        if success_rate >= self.green {
+            HealthStatus::Green
+        } else if success_rate >= self.yellow {
+            HealthStatus::Yellow
+        } else {
+            HealthStatus::Red
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_band_boundaries() {
+        let thresholds = HealthThresholds::default();
+        assert_eq!(thresholds.classify(94.9), HealthStatus::Red);
+        assert_eq!(thresholds.classify(95.0), HealthStatus::Yellow);
+        assert_eq!(thresholds.classify(97.9), HealthStatus::Yellow);
+        assert_eq!(thresholds.classify(98.0), HealthStatus::Yellow);
+        assert_eq!(thresholds.classify(98.9), HealthStatus::Yellow);
+        assert_eq!(thresholds.classify(99.0), HealthStatus::Green);
+        assert_eq!(thresholds.classify(100.0), HealthStatus::Green);
+        assert_eq!(thresholds.classify(0.0), HealthStatus::Red);
+    }
+
+    #[test]
+    fn test_custom_thresholds() {
+        let thresholds = HealthThresholds {
+            green: 98.0,
+            yellow: 90.0,
+        };
+        assert_eq!(thresholds.classify(97.9), HealthStatus::Yellow);
+        assert_eq!(thresholds.classify(98.0), HealthStatus::Green);
+        assert_eq!(thresholds.classify(89.9), HealthStatus::Red);
+    }
+}
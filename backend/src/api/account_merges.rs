@@ -7,7 +7,8 @@ use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::services::account_merge_detector::{
-    AccountMergeDetector, AccountMergeEvent, AccountMergeStats, DestinationAccountPattern,
+    AccountChurnEvent, AccountMergeDetector, AccountMergeEvent, AccountMergeStats,
+    DestinationAccountPattern,
 };
 
 #[derive(Deserialize)]
@@ -35,6 +36,7 @@ pub fn routes(detector: Arc<AccountMergeDetector>) -> Router {
         .route("/stats", get(get_account_merge_stats))
         .route("/recent", get(get_recent_account_merges))
         .route("/destinations", get(get_destination_patterns))
+        .route("/churn", get(get_account_churn))
         .with_state(detector)
 }
 
@@ -74,3 +76,12 @@ async fn get_destination_patterns(
         .unwrap_or_default();
     Json(patterns)
 }
+
+async fn get_account_churn(
+    State(detector): State<Arc<AccountMergeDetector>>,
+    Query(params): Query<RecentMergesParams>,
+) -> Json<Vec<AccountChurnEvent>> {
+    let limit = params.limit.clamp(1, 200);
+    let churn = detector.get_recent_churn(limit).await.unwrap_or_default();
+    Json(churn)
+}
@@ -0,0 +1,80 @@
+use axum::{
+    extract::{Query, State},
+    routing::get,
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::admin_audit_log::{AdminAuditFilter, AdminAuditLogEntry};
+use crate::api::pagination::PaginationConfig;
+use crate::database::Database;
+use crate::error::{ApiError, ApiResult};
+
+/// Most audit entries returned by one request
+pub const MAX_AUDIT_LIMIT: i64 = 500;
+
+#[derive(Debug, Deserialize)]
+pub struct AdminAuditQuery {
+    /// Authenticated user id, or `ip:<addr>` for IP-whitelisted routes
+    pub actor: Option<String>,
+    pub action: Option<String>,
+    /// Target of the action, e.g. `anchor:<id>`
+    pub target: Option<String>,
+    pub request_id: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+    #[serde(default)]
+    pub offset: i64,
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminAuditResponse {
+    pub entries: Vec<AdminAuditLogEntry>,
+}
+
+pub fn routes(db: Arc<Database>) -> Router {
+    Router::new()
+        .route("/api/admin/audit", get(list_admin_audit))
+        .with_state(db)
+}
+
+/// Admin audit entries, newest first
+///
+/// GET /api/admin/audit?actor=&action=&target=&request_id=&since=&until=&limit=&offset=
+pub async fn list_admin_audit(
+    State(db): State<Arc<Database>>,
+    Extension(pagination): Extension<PaginationConfig>,
+    Query(query): Query<AdminAuditQuery>,
+) -> ApiResult<Json<AdminAuditResponse>> {
+    pagination.check_offset(query.offset, None)?;
+    if let (Some(since), Some(until)) = (query.since, query.until) {
+        if since > until {
+            return Err(ApiError::bad_request(
+                "INVALID_TIME_RANGE",
+                "since must not be after until",
+            ));
+        }
+    }
+
+    let filter = AdminAuditFilter {
+        actor: query.actor,
+        action: query.action,
+        resource: query.target,
+        request_id: query.request_id,
+        since: query.since,
+        until: query.until,
+        limit: query.limit.clamp(1, MAX_AUDIT_LIMIT),
+        offset: query.offset.max(0),
+    };
+    let entries = db.admin_audit_logger.query(&filter).await?;
+
+    Ok(Json(AdminAuditResponse { entries }))
+}
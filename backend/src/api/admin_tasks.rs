@@ -0,0 +1,79 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::task_registry::{TaskRegistry, TaskStatus};
+
+#[derive(Debug, Serialize)]
+pub struct TaskSummaryResponse {
+    /// `healthy`, or `degraded` when any task is unhealthy
+    pub status: &'static str,
+    pub tasks: Vec<TaskStatus>,
+}
+
+pub fn routes(registry: Arc<TaskRegistry>) -> Router {
+    Router::new()
+        .route("/api/admin/tasks", get(get_task_summary))
+        .with_state(registry)
+}
+
+/// Status of every background task; 503 when any of them is unhealthy
+///
+/// GET /api/admin/tasks
+pub async fn get_task_summary(State(registry): State<Arc<TaskRegistry>>) -> Response {
+    let tasks = registry.statuses();
+    let healthy = tasks.iter().all(|task| task.healthy);
+    let (status_code, status) = if healthy {
+        (StatusCode::OK, "healthy")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "degraded")
+    };
+
+    (status_code, Json(TaskSummaryResponse { status, tasks })).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use std::time::Duration;
+    use tower::util::ServiceExt;
+
+    async fn summary(registry: Arc<TaskRegistry>) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .uri("/api/admin/tasks")
+            .body(Body::empty())
+            .unwrap();
+        let response = routes(registry).oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_summary_reports_stopped_task() {
+        let registry = Arc::new(TaskRegistry::new());
+        registry
+            .register("ledger_ingestion", Some(Duration::from_secs(60)))
+            .success();
+
+        let (status, body) = summary(Arc::clone(&registry)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "healthy");
+        assert_eq!(body["tasks"][0]["name"], "ledger_ingestion");
+
+        registry.register("webhook_dispatcher", None).stopped();
+        let (status, body) = summary(registry).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "degraded");
+        assert_eq!(body["tasks"][1]["name"], "webhook_dispatcher");
+        assert_eq!(body["tasks"][1]["healthy"], false);
+    }
+}
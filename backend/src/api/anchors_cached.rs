@@ -2,18 +2,23 @@ use axum::{
     extract::{Query, State},
     http::{HeaderMap, StatusCode},
     response::{IntoResponse, Response},
-    Json,
+    Extension, Json,
 };
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex, OnceLock};
 use utoipa::{IntoParams, ToSchema};
 
+use crate::api::field_selection::{parse_fields, select_fields, ANCHOR_FIELDS};
+use crate::api::freshness::{is_stale, latest_timestamp};
+use crate::api::pagination::{PagedResource, PaginationConfig};
 use crate::cache::{keys, CacheManager};
 use crate::cache_middleware::CacheAware;
 use crate::database::Database;
-use crate::error::ApiResult;
+use crate::error::{ApiError, ApiResult};
+use crate::models::AnchorCursor;
 use crate::rpc::{
-    circuit_breaker::{CircuitBreaker, CircuitBreakerConfig},
+    circuit_breaker::{CircuitBreaker, EndpointGroup},
     error::{with_retry, RetryConfig, RpcError},
     StellarRpcClient,
 };
@@ -22,26 +27,32 @@ use crate::services::price_feed::PriceFeedClient;
 #[derive(Debug, Deserialize, IntoParams)]
 #[into_params(parameter_in = Query)]
 pub struct ListAnchorsQuery {
-    /// Maximum number of results to return (default: 50)
-    #[serde(default = "default_limit")]
+    /// Maximum number of results to return (default: 50, at most 200, per
+    /// `PAGINATION_PAGE_SIZES`)
+    #[serde(default)]
     #[param(example = 50)]
-    pub limit: i64,
-    /// Pagination offset (default: 0)
+    pub limit: Option<i64>,
+    /// Pagination offset (default: 0, at most `MAX_PAGINATION_OFFSET`)
     #[serde(default)]
     #[param(example = 0)]
     pub offset: i64,
+    /// `next_cursor` of the previous page; takes precedence over `offset`
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Comma-separated anchor fields to return (default: all)
+    #[param(example = "name,reliability_score")]
+    pub fields: Option<String>,
 }
 
-fn default_limit() -> i64 {
-    50
-}
-
-fn rpc_circuit_breaker() -> Arc<CircuitBreaker> {
+/// Breaker with the settings of `rpc_client`'s accounts group breaker
+fn rpc_circuit_breaker(rpc_client: &StellarRpcClient) -> Arc<CircuitBreaker> {
     static CIRCUIT_BREAKER: OnceLock<Arc<CircuitBreaker>> = OnceLock::new();
     CIRCUIT_BREAKER
         .get_or_init(|| {
             Arc::new(CircuitBreaker::new(
-                CircuitBreakerConfig::default(),
+                rpc_client
+                    .circuit_breaker_config(EndpointGroup::Accounts)
+                    .clone(),
                 "horizon",
             ))
         })
@@ -80,6 +91,12 @@ pub struct AnchorMetricsResponse {
     /// Health status (green, yellow, red)
     #[schema(example = "green")]
     pub status: String,
+    /// When the anchor's underlying data was last updated
+    #[schema(example = "2024-01-15T10:30:00Z")]
+    pub data_as_of: DateTime<Utc>,
+    /// Whether `data_as_of` is older than the staleness threshold
+    #[schema(example = false)]
+    pub stale: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
@@ -89,6 +106,19 @@ pub struct AnchorsResponse {
     /// Total number of anchors
     #[schema(example = 25)]
     pub total: usize,
+    /// Cursor for the next page, absent on the last page
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+impl AnchorsResponse {
+    /// Re-evaluate `stale` now; cached responses keep their `data_as_of`
+    fn with_current_staleness(mut self, stale_after: Duration) -> Self {
+        for anchor in &mut self.anchors {
+            anchor.stale = is_stale(anchor.data_as_of, stale_after);
+        }
+        self
+    }
 }
 
 /// List all anchors with key metrics
@@ -105,6 +135,7 @@ pub struct AnchorsResponse {
     params(ListAnchorsQuery),
     responses(
         (status = 200, description = "List of anchors retrieved successfully", body = AnchorsResponse),
+        (status = 400, description = "Unknown field requested, offset too large or invalid cursor"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Anchors"
@@ -116,22 +147,48 @@ pub async fn get_anchors(
         Arc<StellarRpcClient>,
         Arc<PriceFeedClient>,
     )>,
+    Extension(pagination): Extension<PaginationConfig>,
     Query(params): Query<ListAnchorsQuery>,
     headers: HeaderMap,
 ) -> ApiResult<Response> {
-    let cache_key = keys::anchor_list(params.limit, params.offset);
+    let cursor = match params.cursor.as_deref() {
+        Some(raw) => Some(AnchorCursor::decode(raw).ok_or_else(|| {
+            ApiError::bad_request("INVALID_CURSOR", format!("Invalid anchor cursor '{}'", raw))
+        })?),
+        None => {
+            pagination.check_offset(params.offset, Some("cursor"))?;
+            None
+        }
+    };
+    let fields = parse_fields(params.fields.as_deref(), &[ANCHOR_FIELDS])?;
+    let limit = pagination.page_limit(PagedResource::Anchors, params.limit);
+    let cache_key = match &params.cursor {
+        Some(raw) => keys::anchor_list_after(limit, raw),
+        None => keys::anchor_list(limit, params.offset),
+    };
 
-    let response = <()>::get_or_fetch(&cache, &cache_key, cache.config.get_ttl("anchor"), async {
+    let ttl = cache.config.get_ttl("anchor");
+    let stale_after = cache.config.stale_after();
+    let (response, cache_status) = <()>::get_or_fetch_with_status(&cache, &cache_key, ttl, async {
         // Get anchor metadata from database (names, accounts, etc.)
-        let anchors = db.list_anchors(params.limit, params.offset).await?;
+        let anchors = match &cursor {
+            Some(cursor) => db.list_anchors_after(cursor, limit).await?,
+            None => db.list_anchors(limit, params.offset).await?,
+        };
 
         if anchors.is_empty() {
             return Ok(AnchorsResponse {
                 anchors: vec![],
                 total: 0,
+                next_cursor: None,
             });
         }
 
+        // A full page may be followed by more anchors
+        let next_cursor = (anchors.len() as i64 == limit)
+            .then(|| anchors.last().map(|a| AnchorCursor::after(a).encode()))
+            .flatten();
+
         // OPTIMIZATION: Batch fetch all assets for these anchors (1 query instead of N)
         let anchor_ids: Vec<uuid::Uuid> = anchors
             .iter()
@@ -143,7 +200,7 @@ pub async fn get_anchors(
             .await
             .unwrap_or_default();
 
-        let circuit_breaker = rpc_circuit_breaker();
+        let circuit_breaker = rpc_circuit_breaker(&rpc_client);
         let mut anchor_responses = Vec::new();
 
         // Process anchors with pre-fetched data
@@ -198,14 +255,13 @@ pub async fn get_anchors(
                 anchor.reliability_score
             };
 
-            let status = if reliability_score >= 99.0 {
-                "green".to_string()
-            } else if reliability_score >= 95.0 {
-                "yellow".to_string()
-            } else {
-                "red".to_string()
-            };
+            let status = db
+                .health_thresholds()
+                .classify(reliability_score)
+                .as_str()
+                .to_string();
 
+            let data_as_of = anchor_data_as_of(anchor.updated_at, &payments);
             let anchor_response = AnchorMetricsResponse {
                 id: anchor.id.to_string(),
                 name: anchor.name,
@@ -217,6 +273,8 @@ pub async fn get_anchors(
                 successful_transactions,
                 failed_transactions,
                 status,
+                data_as_of,
+                stale: is_stale(data_as_of, stale_after),
             };
 
             anchor_responses.push(anchor_response);
@@ -227,15 +285,34 @@ pub async fn get_anchors(
         Ok(AnchorsResponse {
             anchors: anchor_responses,
             total,
+            next_cursor,
         })
     })
     .await?;
+    let response = response.with_current_staleness(stale_after);
 
-    let ttl = cache.config.get_ttl("anchor");
-    let response = crate::http_cache::cached_json_response(&headers, &cache_key, &response, ttl)?;
+    let max_age = cache.config.http_max_age("anchor");
+    let mut response = match fields {
+        Some(fields) => {
+            let mut payload = serde_json::to_value(&response).map_err(anyhow::Error::from)?;
+            if let Some(anchors) = payload.get_mut("anchors") {
+                *anchors = select_fields(anchors.take(), &fields);
+            }
+            let resource_key = format!("{}:fields={}", cache_key, fields.join(","));
+            crate::http_cache::cached_json_response(&headers, &resource_key, &payload, max_age)?
+        }
+        None => crate::http_cache::cached_json_response(&headers, &cache_key, &response, max_age)?,
+    };
+    cache_status.apply(&mut response);
     Ok(response)
 }
 
+/// Latest of the anchor record's update and its most recent payment
+fn anchor_data_as_of(updated_at: DateTime<Utc>, payments: &[crate::rpc::Payment]) -> DateTime<Utc> {
+    latest_timestamp(payments.iter().map(|p| p.created_at.as_str()))
+        .map_or(updated_at, |latest| latest.max(updated_at))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -259,10 +336,34 @@ mod tests {
             successful_transactions: 950,
             failed_transactions: 50,
             status: "green".to_string(),
+            data_as_of: Utc::now(),
+            stale: false,
         };
 
         assert_eq!(response.name, "Test Anchor");
         assert_eq!(response.reliability_score, 95.5);
         assert_eq!(response.asset_coverage, 3);
     }
+
+    #[test]
+    fn test_anchor_data_as_of_prefers_latest_update() {
+        let updated_at: DateTime<Utc> = "2026-01-15T10:00:00Z".parse().unwrap();
+        assert_eq!(anchor_data_as_of(updated_at, &[]), updated_at);
+
+        let payment: crate::rpc::Payment = serde_json::from_value(serde_json::json!({
+            "id": "1",
+            "paging_token": "1",
+            "transaction_hash": "hash",
+            "source_account": "GSRC",
+            "destination": "GDEST",
+            "asset_type": "native",
+            "amount": "10.0",
+            "created_at": "2026-01-15T11:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(
+            anchor_data_as_of(updated_at, &[payment]),
+            "2026-01-15T11:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
 }
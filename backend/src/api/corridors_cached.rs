@@ -2,19 +2,28 @@ use axum::{
     extract::{Path, Query, State},
     http::HeaderMap,
     response::Response,
-    Json,
+    Extension,
 };
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, OnceLock};
 use utoipa::{IntoParams, ToSchema};
 
+use crate::api::field_selection::{
+    detail_response, parse_fields, select_fields, FieldsQuery, CORRIDOR_DETAIL_SECTIONS,
+    CORRIDOR_FIELDS,
+};
+use crate::api::freshness::{is_stale_or_unknown, latest_timestamp};
+use crate::api::pagination::PaginationConfig;
 use crate::cache::{keys, CacheManager};
-use crate::cache_middleware::CacheAware;
+use crate::cache_middleware::{CacheAware, CacheStatus};
 use crate::database::Database;
 use crate::error::{ApiError, ApiResult};
+use crate::models::asset::normalize_asset_code;
+use crate::models::corridor::{Corridor, LatencyPercentiles};
 use crate::models::SortBy;
-use crate::rpc::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use crate::rpc::circuit_breaker::{CircuitBreaker, EndpointGroup};
 use crate::rpc::error::{with_retry, RetryConfig, RpcError};
 use crate::rpc::StellarRpcClient;
 use crate::services::price_feed::PriceFeedClient;
@@ -47,7 +56,9 @@ fn extract_asset_pair_from_payment(payment: &crate::rpc::Payment) -> Option<Asse
                 } else {
                     format!(
                         "{}:{}",
-                        payment.source_asset_code.as_deref().unwrap_or("UNKNOWN"),
+                        normalize_asset_code(
+                            payment.source_asset_code.as_deref().unwrap_or("UNKNOWN")
+                        ),
                         payment.source_asset_issuer.as_deref().unwrap_or("unknown")
                     )
                 }
@@ -60,7 +71,7 @@ fn extract_asset_pair_from_payment(payment: &crate::rpc::Payment) -> Option<Asse
             } else {
                 format!(
                     "{}:{}",
-                    payment.get_asset_code().as_deref().unwrap_or("UNKNOWN"),
+                    normalize_asset_code(payment.get_asset_code().as_deref().unwrap_or("UNKNOWN")),
                     payment.get_asset_issuer().as_deref().unwrap_or("unknown")
                 )
             };
@@ -77,7 +88,7 @@ fn extract_asset_pair_from_payment(payment: &crate::rpc::Payment) -> Option<Asse
             } else {
                 format!(
                     "{}:{}",
-                    payment.get_asset_code().as_deref().unwrap_or("UNKNOWN"),
+                    normalize_asset_code(payment.get_asset_code().as_deref().unwrap_or("UNKNOWN")),
                     payment.get_asset_issuer().as_deref().unwrap_or("unknown")
                 )
             };
@@ -140,6 +151,20 @@ pub struct CorridorResponse {
     /// Last update timestamp
     #[schema(example = "2024-01-15T10:30:00Z")]
     pub last_updated: String,
+    /// Time of the most recent payment the metrics were derived from,
+    /// absent when no payment time could be read
+    #[schema(example = "2024-01-15T10:29:41Z")]
+    pub data_as_of: Option<DateTime<Utc>>,
+    /// Whether `data_as_of` is older than the staleness threshold or unknown
+    #[schema(example = false)]
+    pub stale: bool,
+}
+
+impl CorridorResponse {
+    /// Re-evaluate `stale` now; cached responses keep their `data_as_of`
+    fn refresh_staleness(&mut self, stale_after: Duration) {
+        self.stale = is_stale_or_unknown(self.data_as_of, stale_after);
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -193,6 +218,10 @@ pub struct CorridorDetailResponse {
     pub liquidity_trends: Vec<LiquidityDataPoint>,
     /// Related corridors
     pub related_corridors: Option<Vec<CorridorResponse>>,
+    /// Settlement latency percentiles from the latest aggregation window;
+    /// `null` when no latency samples were recorded
+    #[serde(default)]
+    pub settlement_latency_percentiles: Option<LatencyPercentiles>,
 }
 
 #[derive(Debug, Deserialize, IntoParams)]
@@ -202,7 +231,7 @@ pub struct ListCorridorsQuery {
     #[serde(default = "default_limit")]
     #[param(example = 50)]
     pub limit: i64,
-    /// Pagination offset (default: 0)
+    /// Pagination offset (default: 0, at most `MAX_PAGINATION_OFFSET`)
     #[serde(default)]
     #[param(example = 0)]
     pub offset: i64,
@@ -227,6 +256,9 @@ pub struct ListCorridorsQuery {
     /// Time period for metrics (24h, 7d, 30d)
     #[param(example = "24h")]
     pub time_period: Option<String>,
+    /// Comma-separated corridor fields to return (default: all)
+    #[param(example = "id,success_rate")]
+    pub fields: Option<String>,
 }
 
 fn default_limit() -> i64 {
@@ -265,12 +297,15 @@ fn get_liquidity_trend(volume_usd: f64) -> String {
     }
 }
 
-fn rpc_circuit_breaker() -> Arc<CircuitBreaker> {
+/// Breaker with the settings of `rpc_client`'s core group breaker
+fn rpc_circuit_breaker(rpc_client: &StellarRpcClient) -> Arc<CircuitBreaker> {
     static CIRCUIT_BREAKER: OnceLock<Arc<CircuitBreaker>> = OnceLock::new();
     CIRCUIT_BREAKER
         .get_or_init(|| {
             Arc::new(CircuitBreaker::new(
-                CircuitBreakerConfig::default(),
+                rpc_client
+                    .circuit_breaker_config(EndpointGroup::Core)
+                    .clone(),
                 "horizon",
             ))
         })
@@ -307,11 +342,12 @@ fn generate_corridor_list_cache_key(params: &ListCorridorsQuery) -> String {
     params(ListCorridorsQuery),
     responses(
         (status = 200, description = "List of corridors retrieved successfully", body = Vec<CorridorResponse>),
+        (status = 400, description = "Unknown field requested or offset too large"),
         (status = 500, description = "Internal server error")
     ),
     tag = "Corridors"
 )]
-#[tracing::instrument(skip(_db, cache, rpc_client, price_feed, params))]
+#[tracing::instrument(skip(_db, cache, rpc_client, price_feed, pagination, params))]
 pub async fn list_corridors(
     State((_db, cache, rpc_client, price_feed)): State<(
         Arc<Database>,
@@ -319,17 +355,21 @@ pub async fn list_corridors(
         Arc<StellarRpcClient>,
         Arc<PriceFeedClient>,
     )>,
+    Extension(pagination): Extension<PaginationConfig>,
     Query(params): Query<ListCorridorsQuery>,
     headers: HeaderMap,
 ) -> ApiResult<Response> {
+    pagination.check_offset(params.offset, None)?;
+    let fields = parse_fields(params.fields.as_deref(), &[CORRIDOR_FIELDS])?;
     let cache_key = generate_corridor_list_cache_key(&params);
+    let stale_after = cache.config.stale_after();
 
-    let corridors = <()>::get_or_fetch(
+    let (mut corridors, cache_status) = <()>::get_or_fetch_with_status(
         &cache,
         &cache_key,
         cache.config.get_ttl("corridor"),
         async {
-            let circuit_breaker = rpc_circuit_breaker();
+            let circuit_breaker = rpc_circuit_breaker(&rpc_client);
 
             // **RPC DATA**: Fetch recent payments to identify active corridors
             let payments = with_retry(
@@ -445,6 +485,7 @@ pub async fn list_corridors(
                 let health_score = calculate_health_score(success_rate, total_attempts, volume_usd);
                 let liquidity_trend = get_liquidity_trend(volume_usd);
                 let avg_latency = 400.0 + (success_rate * 2.0);
+                let data_as_of = corridor_data_as_of(corridor_payments);
 
                 let corridor_response = CorridorResponse {
                     id: corridor_key.clone(),
@@ -463,6 +504,8 @@ pub async fn list_corridors(
                     liquidity_trend,
                     health_score,
                     last_updated: chrono::Utc::now().to_rfc3339(),
+                    data_as_of,
+                    stale: is_stale_or_unknown(data_as_of, stale_after),
                 };
 
                 corridor_responses.push(corridor_response);
@@ -512,10 +555,25 @@ pub async fn list_corridors(
     )
     .await?;
 
+    corridors
+        .iter_mut()
+        .for_each(|corridor| corridor.refresh_staleness(stale_after));
+
     crate::observability::metrics::set_corridors_tracked(corridors.len() as i64);
 
-    let ttl = cache.config.get_ttl("corridor");
-    let response = crate::http_cache::cached_json_response(&headers, &cache_key, &corridors, ttl)?;
+    let max_age = cache.config.http_max_age("corridor");
+    let mut response = match fields {
+        Some(fields) => {
+            let payload = select_fields(
+                serde_json::to_value(&corridors).map_err(anyhow::Error::from)?,
+                &fields,
+            );
+            let resource_key = format!("{}:fields={}", cache_key, fields.join(","));
+            crate::http_cache::cached_json_response(&headers, &resource_key, &payload, max_age)?
+        }
+        None => crate::http_cache::cached_json_response(&headers, &cache_key, &corridors, max_age)?,
+    };
+    cache_status.apply(&mut response);
     Ok(response)
 }
 
@@ -649,6 +707,11 @@ fn calculate_liquidity_trends(
     data_points
 }
 
+/// Time of the most recent payment in a corridor, if any parse
+fn corridor_data_as_of(corridor_payments: &[&crate::rpc::Payment]) -> Option<DateTime<Utc>> {
+    latest_timestamp(corridor_payments.iter().map(|p| p.created_at.as_str()))
+}
+
 /// Find related corridors (same source or destination asset)
 fn find_related_corridors(
     target_corridor_key: &str,
@@ -689,10 +752,12 @@ fn find_related_corridors(
     get,
     path = "/api/corridors/{corridor_key}",
     params(
-        ("corridor_key" = String, Path, description = "Corridor identifier (e.g., USDC:native->XLM:native)")
+        ("corridor_key" = String, Path, description = "Corridor identifier (e.g., USDC:native->XLM:native)"),
+        FieldsQuery
     ),
     responses(
         (status = 200, description = "Corridor details retrieved successfully", body = CorridorDetailResponse),
+        (status = 400, description = "Invalid corridor key or unknown field requested"),
         (status = 404, description = "Corridor not found"),
         (status = 500, description = "Internal server error")
     ),
@@ -707,9 +772,15 @@ pub async fn get_corridor_detail(
         Arc<PriceFeedClient>,
     )>,
     Path(corridor_key): Path<String>,
-) -> ApiResult<Json<CorridorDetailResponse>> {
+    Query(selection): Query<FieldsQuery>,
+) -> ApiResult<Response> {
     use std::collections::HashMap;
 
+    let fields = parse_fields(
+        selection.fields.as_deref(),
+        &[CORRIDOR_FIELDS, CORRIDOR_DETAIL_SECTIONS],
+    )?;
+
     // Validate corridor_key format
     let parts: Vec<&str> = corridor_key.split("->").collect();
     if parts.len() != 2 {
@@ -735,17 +806,28 @@ pub async fn get_corridor_detail(
 
     // Check cache first
     let cache_key = keys::corridor_detail(&corridor_key);
-    if let Some(cached) = cache
-        .get::<CorridorDetailResponse>(&cache_key)
-        .await
-        .ok()
-        .flatten()
-    {
-        return Ok(Json(cached));
-    }
+    let stale_after = cache.config.stale_after();
+    let cache_status = match cache.get::<CorridorDetailResponse>(&cache_key).await {
+        Ok(Some(mut cached)) => {
+            cached.corridor.refresh_staleness(stale_after);
+            cached
+                .related_corridors
+                .iter_mut()
+                .flatten()
+                .for_each(|corridor| corridor.refresh_staleness(stale_after));
+            let mut response = detail_response(&cached, "corridor", fields.as_deref())?;
+            CacheStatus::Hit.apply(&mut response);
+            return Ok(response);
+        }
+        Ok(None) => CacheStatus::Miss,
+        Err(e) => {
+            tracing::warn!("Bypassing cache for {}: {}", cache_key, e);
+            CacheStatus::Bypass
+        }
+    };
 
     // Fetch payments from RPC
-    let circuit_breaker = rpc_circuit_breaker();
+    let circuit_breaker = rpc_circuit_breaker(&rpc_client);
 
     let payments = with_retry(
         || async {
@@ -827,6 +909,7 @@ pub async fn get_corridor_detail(
         let health_score = calculate_health_score(success_rate, total_attempts, volume_usd);
         let liquidity_trend = get_liquidity_trend(volume_usd);
         let avg_latency = 400.0 + (success_rate * 2.0);
+        let data_as_of = corridor_data_as_of(corr_payments);
 
         all_corridors.push(CorridorResponse {
             id: key.clone(),
@@ -845,6 +928,8 @@ pub async fn get_corridor_detail(
             liquidity_trend,
             health_score,
             last_updated: chrono::Utc::now().to_rfc3339(),
+            data_as_of,
+            stale: is_stale_or_unknown(data_as_of, stale_after),
         });
     }
 
@@ -871,6 +956,7 @@ pub async fn get_corridor_detail(
     let health_score = calculate_health_score(success_rate, total_attempts, volume_usd);
     let liquidity_trend = get_liquidity_trend(volume_usd);
     let avg_latency = 400.0 + (success_rate * 2.0);
+    let data_as_of = corridor_data_as_of(&corridor_payments);
 
     let corridor = CorridorResponse {
         id: corridor_key.clone(),
@@ -889,6 +975,8 @@ pub async fn get_corridor_detail(
         liquidity_trend,
         health_score,
         last_updated: chrono::Utc::now().to_rfc3339(),
+        data_as_of,
+        stale: is_stale_or_unknown(data_as_of, stale_after),
     };
 
     // Calculate historical metrics
@@ -899,22 +987,46 @@ pub async fn get_corridor_detail(
     // Find related corridors
     let related_corridors = find_related_corridors(&corridor_key, &all_corridors);
 
+    let percentiles_key = Corridor::new(
+        source_parts[0].to_string(),
+        source_parts[1].to_string(),
+        dest_parts[0].to_string(),
+        dest_parts[1].to_string(),
+    )
+    .to_string_key();
+    let settlement_latency_percentiles = db
+        .get_corridor_latency_percentiles(&percentiles_key)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::warn!(
+                "Failed to load latency percentiles for {}: {}",
+                corridor_key,
+                e
+            );
+            None
+        });
+
     let response = CorridorDetailResponse {
         corridor,
         historical_success_rate,
         latency_distribution,
         liquidity_trends,
         related_corridors,
+        settlement_latency_percentiles,
     };
 
-    // Cache the response with 5-minute TTL
-    let _ = cache
-        .set(
-            &cache_key, &response, 300, // 5 minutes
-        )
-        .await;
+    // Cache the response with 5-minute TTL, unless the cache just failed
+    if cache_status == CacheStatus::Miss {
+        let _ = cache
+            .set(
+                &cache_key, &response, 300, // 5 minutes
+            )
+            .await;
+    }
 
-    Ok(Json(response))
+    let mut response = detail_response(&response, "corridor", fields.as_deref())?;
+    cache_status.apply(&mut response);
+    Ok(response)
 }
 
 #[cfg(test)]
@@ -1209,6 +1321,8 @@ mod tests {
                 liquidity_trend: "stable".to_string(),
                 health_score: 95.0,
                 last_updated: "2026-01-15T10:00:00Z".to_string(),
+                data_as_of: "2026-01-15T10:00:00Z".parse().ok(),
+                stale: false,
             },
             CorridorResponse {
                 id: "USDC:GISSUER->EUR:GEURISSUER".to_string(),
@@ -1227,6 +1341,8 @@ mod tests {
                 liquidity_trend: "stable".to_string(),
                 health_score: 94.0,
                 last_updated: "2026-01-15T10:00:00Z".to_string(),
+                data_as_of: "2026-01-15T10:00:00Z".parse().ok(),
+                stale: false,
             },
         ];
 
@@ -1235,4 +1351,80 @@ mod tests {
         let related_corridors = related.unwrap();
         assert!(related_corridors.len() >= 2); // At least target and one related
     }
+
+    #[test]
+    fn test_corridor_data_as_of_is_latest_payment() {
+        let payment = |created_at: &str| crate::rpc::Payment {
+            id: created_at.to_string(),
+            paging_token: created_at.to_string(),
+            transaction_hash: "hash".to_string(),
+            source_account: "GTEST".to_string(),
+            destination: "GDEST".to_string(),
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+            amount: "10.0".to_string(),
+            created_at: created_at.to_string(),
+            operation_type: Some("payment".to_string()),
+            source_asset_type: None,
+            source_asset_code: None,
+            source_asset_issuer: None,
+            source_amount: None,
+            from: Some("GTEST".to_string()),
+            to: Some("GDEST".to_string()),
+            asset_balance_changes: None,
+        };
+        let payments = [
+            payment("2026-01-15T09:00:00Z"),
+            payment("2026-01-15T10:30:00Z"),
+            payment("2026-01-15T10:00:00Z"),
+        ];
+        let refs: Vec<&crate::rpc::Payment> = payments.iter().collect();
+
+        assert_eq!(
+            corridor_data_as_of(&refs),
+            "2026-01-15T10:30:00Z".parse::<DateTime<Utc>>().ok()
+        );
+
+        // Unreadable payment times leave the age unknown rather than now
+        let unreadable = payment("yesterday");
+        assert_eq!(corridor_data_as_of(&[&unreadable]), None);
+    }
+
+    #[test]
+    fn test_refresh_staleness() {
+        let mut corridor = CorridorResponse {
+            id: "USDC:GISSUER->XLM:native".to_string(),
+            source_asset: "USDC".to_string(),
+            destination_asset: "XLM".to_string(),
+            success_rate: 100.0,
+            total_attempts: 1,
+            successful_payments: 1,
+            failed_payments: 0,
+            average_latency_ms: 400.0,
+            median_latency_ms: 300.0,
+            p95_latency_ms: 1000.0,
+            p99_latency_ms: 1200.0,
+            liquidity_depth_usd: 10.0,
+            liquidity_volume_24h_usd: 1.0,
+            liquidity_trend: "decreasing".to_string(),
+            health_score: 60.0,
+            last_updated: Utc::now().to_rfc3339(),
+            data_as_of: Some(Utc::now()),
+            stale: true,
+        };
+        let stale_after = Duration::minutes(30);
+
+        corridor.refresh_staleness(stale_after);
+        assert!(!corridor.stale);
+
+        // A cached response whose data has since aged past the threshold
+        corridor.data_as_of = Some(Utc::now() - stale_after * 2);
+        corridor.refresh_staleness(stale_after);
+        assert!(corridor.stale);
+
+        corridor.data_as_of = None;
+        corridor.refresh_staleness(stale_after);
+        assert!(corridor.stale);
+    }
 }
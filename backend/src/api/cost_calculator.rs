@@ -11,6 +11,9 @@ use std::sync::Arc;
 use utoipa::ToSchema;
 
 use crate::http_cache::cached_json_response;
+use crate::services::fee_estimator::{FeeEstimate, FeeEstimator};
+use crate::services::pair_registry::PairRegistry;
+use crate::services::path_finder::{parse_asset, PathEstimate, PathFinder, PathFinderError};
 use crate::services::price_feed::PriceFeedClient;
 
 const DEFAULT_CACHE_TTL_SECONDS: usize = 60;
@@ -92,10 +95,35 @@ pub struct CostCalculationResponse {
     pub source_usd_rate: f64,
     pub destination_usd_rate: f64,
     pub mid_market_rate: f64,
+    /// Fee estimation strategy used for network fees (e.g. `static`, `p90`)
+    pub network_fee_strategy: String,
+    /// Estimated per-operation network fee in stroops
+    pub network_fee_stroops: u64,
     pub best_route: RouteEstimate,
     pub routes: Vec<RouteEstimate>,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PathEstimateRequest {
+    #[schema(example = "XLM:native")]
+    pub source_asset: String,
+    #[schema(example = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN")]
+    pub destination_asset: String,
+    #[schema(example = 1000.0)]
+    pub source_amount: f64,
+}
+
+/// Shared state for cost calculator handlers
+#[derive(Clone)]
+pub struct CostCalculatorState {
+    pub price_feed: Arc<PriceFeedClient>,
+    pub fee_estimator: Arc<FeeEstimator>,
+    /// Order-book path search; path estimates are unavailable when unset
+    pub path_finder: Option<Arc<PathFinder>>,
+    /// Pairs estimates are served for
+    pub pair_registry: Arc<PairRegistry>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct ErrorResponse {
     pub error: String,
@@ -152,7 +180,7 @@ impl RouteFees {
     tag = "Cost Calculator"
 )]
 pub async fn estimate_costs(
-    State(price_feed): State<Arc<PriceFeedClient>>,
+    State(state): State<CostCalculatorState>,
     request_headers: HeaderMap,
     Json(request): Json<CostCalculationRequest>,
 ) -> Response {
@@ -166,6 +194,13 @@ pub async fn estimate_costs(
         );
     }
 
+    if let Err(e) = state
+        .pair_registry
+        .check(&source_currency, &destination_currency)
+    {
+        return error_response(StatusCode::BAD_REQUEST, &e.to_string());
+    }
+
     if request.source_amount <= 0.0 || !request.source_amount.is_finite() {
         return error_response(
             StatusCode::BAD_REQUEST,
@@ -191,12 +226,13 @@ pub async fn estimate_costs(
         return error_response(StatusCode::BAD_REQUEST, "at least one route is required");
     }
 
-    let source_usd_rate = match resolve_usd_rate(&price_feed, &source_currency).await {
+    let price_feed = &state.price_feed;
+    let source_usd_rate = match resolve_usd_rate(price_feed, &source_currency).await {
         Ok(rate) => rate,
         Err(error) => return error_response(StatusCode::BAD_REQUEST, &error),
     };
 
-    let destination_usd_rate = match resolve_usd_rate(&price_feed, &destination_currency).await {
+    let destination_usd_rate = match resolve_usd_rate(price_feed, &destination_currency).await {
         Ok(rate) => rate,
         Err(error) => return error_response(StatusCode::BAD_REQUEST, &error),
     };
@@ -206,6 +242,7 @@ pub async fn estimate_costs(
     }
 
     let mid_market_rate = source_usd_rate / destination_usd_rate;
+    let fee_estimate = state.fee_estimator.estimate().await;
 
     let mut route_estimates: Vec<RouteEstimate> = unique_routes
         .into_iter()
@@ -215,6 +252,7 @@ pub async fn estimate_costs(
                 request.source_amount,
                 request.destination_amount,
                 mid_market_rate,
+                &fee_estimate,
             )
         })
         .collect();
@@ -241,6 +279,8 @@ pub async fn estimate_costs(
         source_usd_rate,
         destination_usd_rate,
         mid_market_rate,
+        network_fee_strategy: fee_estimate.strategy.label(),
+        network_fee_stroops: fee_estimate.fee_per_operation_stroops,
         best_route,
         routes: route_estimates,
     };
@@ -253,12 +293,13 @@ pub async fn estimate_costs(
         .join(",");
 
     let resource_key = format!(
-        "cost-calculator:{}:{}:{:.8}:{}:{:?}",
+        "cost-calculator:{}:{}:{:.8}:{}:{:?}:{}",
         source_currency,
         destination_currency,
         request.source_amount,
         route_key,
-        request.destination_amount
+        request.destination_amount,
+        response.network_fee_stroops
     );
 
     match cached_json_response(
@@ -275,13 +316,85 @@ pub async fn estimate_costs(
     }
 }
 
+/// Find the cheapest multi-hop DEX path between two assets using live order books.
+#[utoipa::path(
+    post,
+    path = "/api/cost-calculator/path",
+    request_body = PathEstimateRequest,
+    responses(
+        (status = 200, description = "Cheapest path found", body = PathEstimate),
+        (status = 400, description = "Invalid request", body = ErrorResponse),
+        (status = 404, description = "No viable path", body = ErrorResponse),
+        (status = 502, description = "Order book source unavailable", body = ErrorResponse),
+        (status = 503, description = "Path estimation unavailable", body = ErrorResponse)
+    ),
+    tag = "Cost Calculator"
+)]
+pub async fn estimate_path(
+    State(state): State<CostCalculatorState>,
+    Json(request): Json<PathEstimateRequest>,
+) -> Response {
+    let Some(path_finder) = state.path_finder.as_ref() else {
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "path estimation is not configured",
+        );
+    };
+
+    let (Some(source), Some(destination)) = (
+        parse_asset(&request.source_asset),
+        parse_asset(&request.destination_asset),
+    ) else {
+        return error_response(StatusCode::BAD_REQUEST, "assets must be XLM or CODE:ISSUER");
+    };
+
+    if let Err(e) = state
+        .pair_registry
+        .check(&request.source_asset, &request.destination_asset)
+    {
+        return error_response(StatusCode::BAD_REQUEST, &e.to_string());
+    }
+
+    if request.source_amount <= 0.0 || !request.source_amount.is_finite() {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            "source_amount must be a positive number",
+        );
+    }
+
+    let fee_estimate = state.fee_estimator.estimate().await;
+
+    match path_finder
+        .cheapest_path(
+            &source,
+            &destination,
+            request.source_amount,
+            fee_estimate.fee_per_operation_stroops,
+        )
+        .await
+    {
+        Ok(estimate) => Json(estimate).into_response(),
+        Err(error) => {
+            let status = match error {
+                PathFinderError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+                PathFinderError::NoPath { .. } => StatusCode::NOT_FOUND,
+                PathFinderError::Upstream(_) => StatusCode::BAD_GATEWAY,
+            };
+            error_response(status, &error.to_string())
+        }
+    }
+}
+
 fn estimate_route(
     route: PaymentRoute,
     source_amount: f64,
     destination_target: Option<f64>,
     mid_market_rate: f64,
+    fee_estimate: &FeeEstimate,
 ) -> RouteEstimate {
-    let fees = RouteFees::for_route(route);
+    let mut fees = RouteFees::for_route(route);
+    // Route network fees are calibrated at the base fee; scale with congestion
+    fees.network_fee_source *= fee_estimate.congestion_multiplier();
     let slippage_bps = (fees.slippage_base_bps
         + (source_amount / 10_000.0) * fees.slippage_per_10k_bps)
         .min(200.0);
@@ -441,14 +554,25 @@ fn error_response(status: StatusCode, message: &str) -> Response {
 }
 
 pub fn routes(price_feed: Arc<PriceFeedClient>) -> Router {
+    routes_with_state(CostCalculatorState {
+        price_feed,
+        fee_estimator: Arc::new(FeeEstimator::static_fee()),
+        path_finder: None,
+        pair_registry: Arc::new(PairRegistry::unrestricted()),
+    })
+}
+
+pub fn routes_with_state(state: CostCalculatorState) -> Router {
     Router::new()
         .route("/estimate", post(estimate_costs))
-        .with_state(price_feed)
+        .route("/path", post(estimate_path))
+        .with_state(state)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::fee_estimator::{estimate_from_samples, FeeEstimationStrategy};
 
     #[test]
     fn test_normalize_currency() {
@@ -467,11 +591,36 @@ mod tests {
             1_000.0,
             Some(1_500_000.0),
             1_538.0,
+            &estimate_from_samples(FeeEstimationStrategy::Static, &[]),
         );
         assert!(estimate.breakdown.total_fees_source > 0.0);
         assert!(estimate.breakdown.estimated_destination_amount > 0.0);
     }
 
+    #[test]
+    fn test_fee_strategies_produce_different_network_fees() {
+        let fee_data = [100, 100, 150, 200, 400, 800, 1_600, 3_200, 6_400, 12_800];
+        let network_fee = |strategy| {
+            estimate_route(
+                PaymentRoute::StellarDex,
+                1_000.0,
+                None,
+                1.0,
+                &estimate_from_samples(strategy, &fee_data),
+            )
+            .breakdown
+            .network_fee_source
+        };
+
+        let static_fee = network_fee(FeeEstimationStrategy::Static);
+        let median_fee = network_fee(FeeEstimationStrategy::NetworkMedian);
+        let p90_fee = network_fee(FeeEstimationStrategy::Percentile(90));
+
+        assert!((static_fee - 0.12).abs() < 1e-9);
+        assert!((median_fee - 0.12 * 4.0).abs() < 1e-9);
+        assert!((p90_fee - 0.12 * 64.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_fallback_rates_cover_common_assets() {
         assert_eq!(fallback_usd_rate("USD"), Some(1.0));
@@ -1,28 +1,26 @@
 use axum::{
     extract::{Query, State},
     routing::get,
-    Json, Router,
+    Extension, Json, Router,
 };
 use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::api::pagination::{PagedResource, PaginationConfig};
 use crate::models::{FeeBumpStats, FeeBumpTransaction};
 use crate::services::fee_bump_tracker::FeeBumpTrackerService;
 
 #[derive(Deserialize)]
 pub struct RecentFeeBumpsParams {
-    #[serde(default = "default_limit")]
-    limit: i64,
-}
-
-fn default_limit() -> i64 {
-    50
+    #[serde(default)]
+    limit: Option<i64>,
 }
 
 pub fn routes(fee_bump_service: Arc<FeeBumpTrackerService>) -> Router {
     Router::new()
         .route("/stats", get(get_fee_bump_stats))
         .route("/recent", get(get_recent_fee_bumps))
+        .route("/sponsored-failures", get(get_sponsored_failures))
         .with_state(fee_bump_service)
 }
 
@@ -39,15 +37,17 @@ async fn get_fee_bump_stats(
             max_fee_charged: 0,
             min_fee_charged: 0,
             unique_fee_sources: 0,
+            sponsored_failed_count: 0,
         });
     Json(stats)
 }
 
 async fn get_recent_fee_bumps(
     State(service): State<Arc<FeeBumpTrackerService>>,
+    Extension(pagination): Extension<PaginationConfig>,
     Query(params): Query<RecentFeeBumpsParams>,
 ) -> Json<Vec<FeeBumpTransaction>> {
-    let limit = params.limit.clamp(1, 100);
+    let limit = pagination.page_limit(PagedResource::FeeBumps, params.limit);
     // In a real app, handle error properly
     let transactions = service
         .get_recent_fee_bumps(limit)
@@ -55,3 +55,16 @@ async fn get_recent_fee_bumps(
         .unwrap_or_default();
     Json(transactions)
 }
+
+async fn get_sponsored_failures(
+    State(service): State<Arc<FeeBumpTrackerService>>,
+    Extension(pagination): Extension<PaginationConfig>,
+    Query(params): Query<RecentFeeBumpsParams>,
+) -> Json<Vec<FeeBumpTransaction>> {
+    let limit = pagination.page_limit(PagedResource::FeeBumps, params.limit);
+    let transactions = service
+        .get_sponsored_failures(limit)
+        .await
+        .unwrap_or_default();
+    Json(transactions)
+}
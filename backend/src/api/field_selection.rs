@@ -0,0 +1,243 @@
+use axum::{
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use utoipa::IntoParams;
+
+use crate::error::{ApiError, ApiResult};
+
+/// Fields of an anchor in the anchor list
+pub const ANCHOR_FIELDS: &[&str] = &[
+    "id",
+    "name",
+    "stellar_account",
+    "reliability_score",
+    "asset_coverage",
+    "failure_rate",
+    "total_transactions",
+    "successful_transactions",
+    "failed_transactions",
+    "status",
+    "data_as_of",
+    "stale",
+];
+
+/// Fields of the `anchor` object in an anchor detail response
+pub const ANCHOR_DETAIL_FIELDS: &[&str] = &[
+    "id",
+    "name",
+    "stellar_account",
+    "home_domain",
+    "total_transactions",
+    "successful_transactions",
+    "failed_transactions",
+    "total_volume_usd",
+    "avg_settlement_time_ms",
+    "reliability_score",
+    "status",
+    "created_at",
+    "updated_at",
+];
+
+/// Sections of an anchor detail response besides the anchor itself
+pub const ANCHOR_DETAIL_SECTIONS: &[&str] = &["assets", "metrics_history"];
+
+/// Fields of a corridor, in lists and in the `corridor` object of a detail response
+pub const CORRIDOR_FIELDS: &[&str] = &[
+    "id",
+    "source_asset",
+    "destination_asset",
+    "success_rate",
+    "total_attempts",
+    "successful_payments",
+    "failed_payments",
+    "average_latency_ms",
+    "median_latency_ms",
+    "p95_latency_ms",
+    "p99_latency_ms",
+    "liquidity_depth_usd",
+    "liquidity_volume_24h_usd",
+    "liquidity_trend",
+    "health_score",
+    "last_updated",
+    "data_as_of",
+    "stale",
+];
+
+/// Sections of a corridor detail response besides the corridor itself
+pub const CORRIDOR_DETAIL_SECTIONS: &[&str] = &[
+    "historical_success_rate",
+    "latency_distribution",
+    "liquidity_trends",
+    "related_corridors",
+    "settlement_latency_percentiles",
+];
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FieldsQuery {
+    /// Comma-separated list of fields to return (default: all)
+    #[param(example = "name,reliability_score")]
+    pub fields: Option<String>,
+}
+
+/// Parse a `fields` query value against the known field names.
+///
+/// Returns `None` when no selection was requested, and a 400 naming every
+/// unknown field otherwise.
+pub fn parse_fields(raw: Option<&str>, known: &[&[&str]]) -> ApiResult<Option<Vec<String>>> {
+    let Some(raw) = raw.filter(|r| !r.trim().is_empty()) else {
+        return Ok(None);
+    };
+
+    let mut fields: Vec<String> = Vec::new();
+    let mut unknown: Vec<String> = Vec::new();
+    for field in raw.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+        if !known.iter().any(|set| set.contains(&field)) {
+            unknown.push(field.to_string());
+        } else if !fields.iter().any(|f| f == field) {
+            fields.push(field.to_string());
+        }
+    }
+
+    if !unknown.is_empty() {
+        let mut details = HashMap::new();
+        details.insert("unknown_fields".to_string(), serde_json::json!(unknown));
+        details.insert(
+            "allowed_fields".to_string(),
+            serde_json::json!(known.concat()),
+        );
+        return Err(ApiError::bad_request_with_details(
+            "INVALID_FIELDS",
+            format!("Unknown fields requested: {}", unknown.join(", ")),
+            details,
+        ));
+    }
+
+    Ok(Some(fields))
+}
+
+/// Keep only `fields` of an object, or of every object in an array
+pub fn select_fields(value: Value, fields: &[String]) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .filter(|(key, _)| fields.contains(key))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| select_fields(item, fields))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Project a detail response: the `entity_key` object keeps the selected
+/// fields, and other sections are kept only if they were selected
+pub fn select_detail_fields(value: Value, entity_key: &str, fields: &[String]) -> Value {
+    let Value::Object(map) = value else {
+        return value;
+    };
+
+    let mut selected = Map::new();
+    for (key, section) in map {
+        if key == entity_key {
+            selected.insert(key, select_fields(section, fields));
+        } else if fields.contains(&key) {
+            selected.insert(key, section);
+        }
+    }
+    Value::Object(selected)
+}
+
+/// JSON response for a detail payload, projected when fields were selected
+pub fn detail_response<T: Serialize>(
+    payload: &T,
+    entity_key: &str,
+    fields: Option<&[String]>,
+) -> ApiResult<Response> {
+    let Some(fields) = fields else {
+        return Ok(Json(payload).into_response());
+    };
+
+    let value = serde_json::to_value(payload)
+        .map_err(|e| ApiError::internal("SERIALIZATION_ERROR", e.to_string()))?;
+    Ok(Json(select_detail_fields(value, entity_key, fields)).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        assert_eq!(parse_fields(None, &[ANCHOR_FIELDS]).unwrap(), None);
+        assert_eq!(parse_fields(Some(" "), &[ANCHOR_FIELDS]).unwrap(), None);
+        assert_eq!(
+            parse_fields(Some("name, reliability_score,name"), &[ANCHOR_FIELDS]).unwrap(),
+            Some(fields(&["name", "reliability_score"]))
+        );
+    }
+
+    #[test]
+    fn test_unknown_field_is_rejected() {
+        let err = parse_fields(Some("name,secret,bogus"), &[ANCHOR_FIELDS]).unwrap_err();
+        match err {
+            ApiError::BadRequest { code, message, .. } => {
+                assert_eq!(code, "INVALID_FIELDS");
+                assert!(message.contains("secret, bogus"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_select_fields_on_list() {
+        let anchors = json!([
+            {"id": "1", "name": "A", "reliability_score": 99.0, "status": "green"},
+            {"id": "2", "name": "B", "reliability_score": 95.0, "status": "yellow"}
+        ]);
+
+        let selected = select_fields(anchors, &fields(&["name", "reliability_score"]));
+        assert_eq!(
+            selected,
+            json!([
+                {"name": "A", "reliability_score": 99.0},
+                {"name": "B", "reliability_score": 95.0}
+            ])
+        );
+    }
+
+    #[test]
+    fn test_select_detail_fields() {
+        let detail = json!({
+            "corridor": {"id": "USDC->XLM", "success_rate": 99.0, "health_score": 90.0},
+            "latency_distribution": [],
+            "related_corridors": null
+        });
+
+        let selected = select_detail_fields(
+            detail,
+            "corridor",
+            &fields(&["success_rate", "latency_distribution"]),
+        );
+        assert_eq!(
+            selected,
+            json!({
+                "corridor": {"success_rate": 99.0},
+                "latency_distribution": []
+            })
+        );
+    }
+}
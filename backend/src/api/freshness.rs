@@ -0,0 +1,58 @@
+use chrono::{DateTime, Duration, Utc};
+
+/// Data older than this is flagged stale unless `DATA_STALE_AFTER_SECONDS` is set
+pub const DEFAULT_STALE_AFTER_SECONDS: i64 = 3600;
+
+/// Whether data last updated at `data_as_of` is older than `stale_after` now
+pub fn is_stale(data_as_of: DateTime<Utc>, stale_after: Duration) -> bool {
+    is_stale_at(data_as_of, Utc::now(), stale_after)
+}
+
+/// Whether data last updated at `data_as_of` is older than `stale_after` now;
+/// data of unknown age always is
+pub fn is_stale_or_unknown(data_as_of: Option<DateTime<Utc>>, stale_after: Duration) -> bool {
+    data_as_of.is_none_or(|as_of| is_stale(as_of, stale_after))
+}
+
+/// Whether data last updated at `data_as_of` is older than `stale_after` at `now`
+pub fn is_stale_at(data_as_of: DateTime<Utc>, now: DateTime<Utc>, stale_after: Duration) -> bool {
+    now - data_as_of > stale_after
+}
+
+/// Most recent of a set of RFC 3339 timestamps, ignoring unparseable ones
+pub fn latest_timestamp<'a>(
+    timestamps: impl IntoIterator<Item = &'a str>,
+) -> Option<DateTime<Utc>> {
+    timestamps
+        .into_iter()
+        .filter_map(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_timestamp() {
+        let latest = latest_timestamp([
+            "2026-01-01T00:00:00Z",
+            "not-a-timestamp",
+            "2026-01-03T12:00:00Z",
+            "2026-01-02T00:00:00Z",
+        ]);
+        assert_eq!(latest, "2026-01-03T12:00:00Z".parse().ok());
+        assert_eq!(latest_timestamp([]), None);
+    }
+
+    #[test]
+    fn test_stale_past_threshold() {
+        let now: DateTime<Utc> = "2026-01-01T12:00:00Z".parse().unwrap();
+        let threshold = Duration::minutes(30);
+
+        assert!(!is_stale_at(now - Duration::minutes(29), now, threshold));
+        assert!(!is_stale_at(now - threshold, now, threshold));
+        assert!(is_stale_at(now - Duration::minutes(31), now, threshold));
+    }
+}
@@ -1,11 +1,12 @@
 use axum::{
     extract::{Path, Query, State},
     routing::get,
-    Json, Router,
+    Extension, Json, Router,
 };
 use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::api::pagination::{PagedResource, PaginationConfig};
 use crate::models::{LiquidityPool, LiquidityPoolSnapshot, LiquidityPoolStats};
 use crate::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
 
@@ -13,18 +14,14 @@ use crate::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
 pub struct RankingsParams {
     #[serde(default = "default_sort")]
     sort_by: String,
-    #[serde(default = "default_limit")]
-    limit: i64,
+    #[serde(default)]
+    limit: Option<i64>,
 }
 
 fn default_sort() -> String {
     "apy".to_string()
 }
 
-fn default_limit() -> i64 {
-    20
-}
-
 #[derive(Deserialize)]
 pub struct SnapshotParams {
     #[serde(default = "default_snapshot_limit")]
@@ -73,9 +70,10 @@ async fn get_pool_stats(
 
 async fn get_pool_rankings(
     State(analyzer): State<Arc<LiquidityPoolAnalyzer>>,
+    Extension(pagination): Extension<PaginationConfig>,
     Query(params): Query<RankingsParams>,
 ) -> Json<Vec<LiquidityPool>> {
-    let limit = params.limit.clamp(1, 100);
+    let limit = pagination.page_limit(PagedResource::LiquidityPools, params.limit);
     let pools = analyzer
         .get_pool_rankings(&params.sort_by, limit)
         .await
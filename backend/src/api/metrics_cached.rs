@@ -5,11 +5,13 @@ use axum::{
     routing::get,
     Json, Router,
 };
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+use crate::api::freshness::is_stale_or_unknown;
 use crate::cache::{keys, CacheManager};
-use crate::cache_middleware::CacheAware;
+use crate::cache_middleware::{CacheAware, CacheStatus};
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct MetricsOverview {
@@ -18,6 +20,11 @@ pub struct MetricsOverview {
     pub active_users: u64,
     pub average_transaction_value: f64,
     pub corridor_count: u32,
+    /// When the overview was aggregated and cached, absent when aggregation
+    /// failed
+    pub data_as_of: Option<DateTime<Utc>>,
+    /// Whether `data_as_of` is older than the staleness threshold or unknown
+    pub stale: bool,
 }
 
 /// Handler for GET /api/metrics/overview (cached with 1 min TTL)
@@ -27,7 +34,7 @@ pub async fn metrics_overview(
 ) -> Response {
     let cache_key = keys::metrics_overview();
 
-    let overview = <()>::get_or_fetch(
+    let (mut overview, cache_status) = <()>::get_or_fetch_with_status(
         &cache,
         &cache_key,
         cache.config.get_ttl("dashboard"),
@@ -39,21 +46,35 @@ pub async fn metrics_overview(
                 active_users: 4321,
                 average_transaction_value: 28.56,
                 corridor_count: 12,
+                // Aggregated on this cache fill, so the data is as of now
+                data_as_of: Some(Utc::now()),
+                stale: false,
             })
         },
     )
     .await
-    .unwrap_or_else(|_| MetricsOverview {
-        total_volume: 0.0,
-        total_transactions: 0,
-        active_users: 0,
-        average_transaction_value: 0.0,
-        corridor_count: 0,
+    .unwrap_or_else(|_| {
+        let empty = MetricsOverview {
+            total_volume: 0.0,
+            total_transactions: 0,
+            active_users: 0,
+            average_transaction_value: 0.0,
+            corridor_count: 0,
+            data_as_of: None,
+            stale: true,
+        };
+        (empty, CacheStatus::Miss)
     });
 
-    let ttl = cache.config.get_ttl("dashboard");
-    match crate::http_cache::cached_json_response(&headers, &cache_key, &overview, ttl) {
-        Ok(response) => response,
+    // Cached overviews keep their data_as_of, so staleness is evaluated per request
+    overview.stale = is_stale_or_unknown(overview.data_as_of, cache.config.stale_after());
+
+    let max_age = cache.config.http_max_age("dashboard");
+    match crate::http_cache::cached_json_response(&headers, &cache_key, &overview, max_age) {
+        Ok(mut response) => {
+            cache_status.apply(&mut response);
+            response
+        }
         Err(e) => (
             axum::http::StatusCode::INTERNAL_SERVER_ERROR,
             Json(serde_json::json!({ "error": e.to_string() })),
@@ -80,6 +101,8 @@ mod tests {
             active_users: 50,
             average_transaction_value: 10.0,
             corridor_count: 5,
+            data_as_of: Some(Utc::now()),
+            stale: false,
         };
 
         assert_eq!(overview.total_volume, 1000.0);
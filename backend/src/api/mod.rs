@@ -1,4 +1,6 @@
 pub mod account_merges;
+pub mod admin_audit;
+pub mod admin_tasks;
 pub mod achievements;
 pub mod alerts;
 pub mod anchors;
@@ -13,15 +15,20 @@ pub mod cost_calculator;
 // pub mod digest;  // Commented out - depends on email module
 pub mod api_analytics;
 pub mod fee_bump;
+pub mod field_selection;
+pub mod freshness;
 pub mod governance;
 pub mod liquidity_pools;
 pub mod metrics;
 pub mod metrics_cached;
 pub mod network;
 pub mod oauth;
+pub mod overview;
+pub mod pagination;
 pub mod prediction;
 pub mod price_feed;
 pub mod replay_handlers;
+pub mod resource_permissions;
 pub mod sep10;
 pub mod sep24_proxy;
 pub mod sep31_proxy;
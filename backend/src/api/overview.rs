@@ -0,0 +1,222 @@
+use axum::{extract::State, http::HeaderMap, response::Response, routing::get, Router};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::api::freshness::is_stale_or_unknown;
+use crate::cache::{keys, CacheManager};
+use crate::cache_middleware::CacheAware;
+use crate::database::{Database, EntityCounts};
+use crate::error::ApiResult;
+use crate::rpc::StellarRpcClient;
+
+/// Anchors and corridors listed in each section of the overview
+pub const OVERVIEW_TOP_N: i64 = 5;
+
+pub type OverviewState = (Arc<Database>, Arc<CacheManager>, Arc<StellarRpcClient>);
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OverviewAnchor {
+    #[schema(example = "550e8400-e29b-41d4-a716-446655440000")]
+    pub id: String,
+    #[schema(example = "MoneyGram Access")]
+    pub name: String,
+    #[schema(example = 99.5)]
+    pub reliability_score: f64,
+    #[schema(example = "green")]
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OverviewCorridor {
+    pub id: String,
+    /// Source asset as `CODE:ISSUER`
+    #[schema(example = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN")]
+    pub source_asset: String,
+    /// Destination asset as `CODE:ISSUER`
+    #[schema(example = "EURC:GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2")]
+    pub destination_asset: String,
+    #[schema(example = 97.2)]
+    pub reliability_score: f64,
+    #[schema(example = "active")]
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OverviewNetworkHealth {
+    #[schema(example = "healthy")]
+    pub status: String,
+    #[schema(example = 51583040)]
+    pub latest_ledger: u64,
+    #[schema(example = 17280)]
+    pub ledger_retention_window: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OverviewSnapshot {
+    #[schema(example = 42)]
+    pub epoch: i64,
+    pub hash: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OverviewCounts {
+    #[schema(example = 25)]
+    pub anchors: i64,
+    #[schema(example = 120)]
+    pub corridors: i64,
+    #[schema(example = 80)]
+    pub assets: i64,
+    #[schema(example = 42)]
+    pub snapshots: i64,
+}
+
+impl From<EntityCounts> for OverviewCounts {
+    fn from(counts: EntityCounts) -> Self {
+        Self {
+            anchors: counts.anchors,
+            corridors: counts.corridors,
+            assets: counts.assets,
+            snapshots: counts.snapshots,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OverviewResponse {
+    /// Anchors with the highest reliability scores
+    pub top_anchors: Vec<OverviewAnchor>,
+    /// Corridors with the highest reliability scores
+    pub top_corridors: Vec<OverviewCorridor>,
+    /// RPC health, absent when the RPC could not be reached
+    pub network_health: Option<OverviewNetworkHealth>,
+    /// Most recent snapshot, absent before the first one
+    pub latest_snapshot: Option<OverviewSnapshot>,
+    pub counts: OverviewCounts,
+    /// Close time of the latest ingested ledger, absent before the first one
+    pub data_as_of: Option<DateTime<Utc>>,
+    /// Whether `data_as_of` is older than the staleness threshold or unknown
+    pub stale: bool,
+}
+
+async fn build_overview(
+    db: &Database,
+    rpc_client: &StellarRpcClient,
+    stale_after: Duration,
+) -> anyhow::Result<OverviewResponse> {
+    let top_anchors = db
+        .list_anchors(OVERVIEW_TOP_N, 0)
+        .await?
+        .into_iter()
+        .map(|anchor| OverviewAnchor {
+            id: anchor.id,
+            name: anchor.name,
+            reliability_score: anchor.reliability_score,
+            status: anchor.status,
+        })
+        .collect();
+
+    let top_corridors = db
+        .list_top_corridors(OVERVIEW_TOP_N)
+        .await?
+        .into_iter()
+        .map(|corridor| OverviewCorridor {
+            id: corridor.id,
+            source_asset: format!(
+                "{}:{}",
+                corridor.source_asset_code, corridor.source_asset_issuer
+            ),
+            destination_asset: format!(
+                "{}:{}",
+                corridor.destination_asset_code, corridor.destination_asset_issuer
+            ),
+            reliability_score: corridor.reliability_score,
+            status: corridor.status,
+        })
+        .collect();
+
+    // The rest of the overview is still useful while the RPC is down
+    let network_health = match rpc_client.check_health().await {
+        Ok(health) => Some(OverviewNetworkHealth {
+            status: health.status,
+            latest_ledger: health.latest_ledger,
+            ledger_retention_window: health.ledger_retention_window,
+        }),
+        Err(e) => {
+            tracing::warn!("Network health unavailable for overview: {}", e);
+            None
+        }
+    };
+
+    let latest_snapshot = db
+        .list_snapshots(None, 1, 0)
+        .await?
+        .into_iter()
+        .next()
+        .and_then(|snapshot| {
+            Some(OverviewSnapshot {
+                epoch: snapshot.epoch?,
+                hash: snapshot.hash,
+                timestamp: snapshot.timestamp,
+            })
+        });
+
+    let data_as_of = db.latest_ledger_close_time().await?;
+    Ok(OverviewResponse {
+        top_anchors,
+        top_corridors,
+        network_health,
+        latest_snapshot,
+        counts: db.count_entities().await?.into(),
+        data_as_of,
+        stale: is_stale_or_unknown(data_as_of, stale_after),
+    })
+}
+
+/// Dashboard overview
+///
+/// Top anchors and corridors, network health, the latest snapshot and entity
+/// counts in one response, cached with the short dashboard TTL.
+#[utoipa::path(
+    get,
+    path = "/api/overview",
+    responses(
+        (status = 200, description = "Overview assembled successfully", body = OverviewResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    tag = "Metrics"
+)]
+pub async fn get_overview(
+    State((db, cache, rpc_client)): State<OverviewState>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let cache_key = keys::dashboard_overview();
+    let (mut overview, cache_status) = <()>::get_or_fetch_with_status(
+        &cache,
+        &cache_key,
+        cache.config.get_ttl("dashboard"),
+        build_overview(&db, &rpc_client, cache.config.stale_after()),
+    )
+    .await?;
+
+    // Cached overviews keep their data_as_of, so staleness is evaluated per request
+    overview.stale = is_stale_or_unknown(overview.data_as_of, cache.config.stale_after());
+
+    let max_age = cache.config.http_max_age("dashboard");
+    let mut response =
+        crate::http_cache::cached_json_response(&headers, &cache_key, &overview, max_age)?;
+    cache_status.apply(&mut response);
+    Ok(response)
+}
+
+pub fn routes(
+    db: Arc<Database>,
+    cache: Arc<CacheManager>,
+    rpc_client: Arc<StellarRpcClient>,
+) -> Router {
+    Router::new()
+        .route("/api/overview", get(get_overview))
+        .with_state((db, cache, rpc_client))
+}
@@ -0,0 +1,270 @@
+//! Page sizes and limits on offset pagination.
+//!
+//! Each paged resource has a default page size, used when a request gives no
+//! `limit`, and a maximum that larger limits are clamped to.
+//!
+//! SQLite steps over every skipped row to honour `OFFSET`, so deep offsets
+//! are slow; past `PaginationConfig::max_offset` callers have to page with a
+//! cursor. Handlers receive the configured `PaginationConfig` as a request
+//! extension.
+
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::error::{ApiError, ApiResult};
+
+/// Resources whose listings are paged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PagedResource {
+    Anchors,
+    Corridors,
+    Snapshots,
+    LiquidityPools,
+    Trustlines,
+    FeeBumps,
+    Payments,
+    /// Records proxied from Horizon and Stellar RPC
+    RpcRecords,
+}
+
+impl PagedResource {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "anchors" => Some(Self::Anchors),
+            "corridors" => Some(Self::Corridors),
+            "snapshots" => Some(Self::Snapshots),
+            "liquidity_pools" => Some(Self::LiquidityPools),
+            "trustlines" => Some(Self::Trustlines),
+            "fee_bumps" => Some(Self::FeeBumps),
+            "payments" => Some(Self::Payments),
+            "rpc" | "rpc_records" => Some(Self::RpcRecords),
+            _ => None,
+        }
+    }
+}
+
+/// Default and largest page size of a resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageSize {
+    pub default: i64,
+    pub max: i64,
+}
+
+impl PageSize {
+    pub const fn new(default: i64, max: i64) -> Self {
+        Self { default, max }
+    }
+
+    /// Page size for a requested `limit`: the default when none was given,
+    /// otherwise the limit clamped to `1..=max`
+    pub fn resolve(&self, limit: Option<i64>) -> i64 {
+        limit.unwrap_or(self.default).clamp(1, self.max)
+    }
+}
+
+/// Page sizes of every paged resource
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaginationDefaults {
+    pub anchors: PageSize,
+    pub corridors: PageSize,
+    pub snapshots: PageSize,
+    pub liquidity_pools: PageSize,
+    pub trustlines: PageSize,
+    pub fee_bumps: PageSize,
+    pub payments: PageSize,
+    pub rpc_records: PageSize,
+}
+
+impl Default for PaginationDefaults {
+    fn default() -> Self {
+        Self {
+            anchors: PageSize::new(50, 200),
+            corridors: PageSize::new(50, 200),
+            snapshots: PageSize::new(50, 100),
+            liquidity_pools: PageSize::new(20, 100),
+            trustlines: PageSize::new(50, 200),
+            fee_bumps: PageSize::new(50, 100),
+            payments: PageSize::new(50, 200),
+            // Horizon returns at most 200 records per page
+            rpc_records: PageSize::new(20, 200),
+        }
+    }
+}
+
+impl PaginationDefaults {
+    /// One `<resource>=<default>:<max>` entry of `PAGINATION_PAGE_SIZES`,
+    /// e.g. `anchors=25:100`; the default must be between 1 and the max
+    pub fn parse_entry(entry: &str) -> Option<(PagedResource, PageSize)> {
+        let (resource, sizes) = entry.split_once('=')?;
+        let (default, max) = sizes.split_once(':')?;
+        let size = PageSize::new(default.trim().parse().ok()?, max.trim().parse().ok()?);
+        (size.default >= 1 && size.default <= size.max)
+            .then_some((PagedResource::parse(resource)?, size))
+    }
+
+    pub fn with_page_size(mut self, resource: PagedResource, size: PageSize) -> Self {
+        *self.page_size_mut(resource) = size;
+        self
+    }
+
+    fn page_size_mut(&mut self, resource: PagedResource) -> &mut PageSize {
+        match resource {
+            PagedResource::Anchors => &mut self.anchors,
+            PagedResource::Corridors => &mut self.corridors,
+            PagedResource::Snapshots => &mut self.snapshots,
+            PagedResource::LiquidityPools => &mut self.liquidity_pools,
+            PagedResource::Trustlines => &mut self.trustlines,
+            PagedResource::FeeBumps => &mut self.fee_bumps,
+            PagedResource::Payments => &mut self.payments,
+            PagedResource::RpcRecords => &mut self.rpc_records,
+        }
+    }
+
+    pub fn page_size(&self, resource: PagedResource) -> PageSize {
+        match resource {
+            PagedResource::Anchors => self.anchors,
+            PagedResource::Corridors => self.corridors,
+            PagedResource::Snapshots => self.snapshots,
+            PagedResource::LiquidityPools => self.liquidity_pools,
+            PagedResource::Trustlines => self.trustlines,
+            PagedResource::FeeBumps => self.fee_bumps,
+            PagedResource::Payments => self.payments,
+            PagedResource::RpcRecords => self.rpc_records,
+        }
+    }
+}
+
+/// Largest `offset` accepted by list endpoints by default
+pub const DEFAULT_MAX_OFFSET: i64 = 10_000;
+
+/// Page sizes and offset limit applied by list endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaginationConfig {
+    pub page_sizes: PaginationDefaults,
+    /// Largest `offset` accepted; deeper pages need a cursor
+    pub max_offset: i64,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            page_sizes: PaginationDefaults::default(),
+            max_offset: DEFAULT_MAX_OFFSET,
+        }
+    }
+}
+
+impl PaginationConfig {
+    /// Page size of `resource` for a requested `limit`
+    pub fn page_limit(&self, resource: PagedResource, limit: Option<i64>) -> i64 {
+        self.page_sizes.page_size(resource).resolve(limit)
+    }
+
+    /// Reject an `offset` beyond the configured maximum
+    pub fn check_offset(&self, offset: i64, cursor_param: Option<&str>) -> ApiResult<()> {
+        check_offset_against(offset, self.max_offset, cursor_param)
+    }
+}
+
+/// Reject an `offset` beyond `max_offset` with a 400 pointing at cursor
+/// pagination. `cursor_param` names the endpoint's cursor parameter, if any.
+pub fn check_offset_against(
+    offset: i64,
+    max_offset: i64,
+    cursor_param: Option<&str>,
+) -> ApiResult<()> {
+    if offset <= max_offset {
+        return Ok(());
+    }
+
+    let guidance = match cursor_param {
+        Some(param) => format!("page with the `{}` cursor parameter instead", param),
+        None => "use cursor pagination instead of deep offsets".to_string(),
+    };
+
+    let mut details = HashMap::new();
+    details.insert("offset".to_string(), json!(offset));
+    details.insert("max_offset".to_string(), json!(max_offset));
+    details.insert("suggestion".to_string(), json!("cursor"));
+    if let Some(param) = cursor_param {
+        details.insert("cursor_param".to_string(), json!(param));
+    }
+
+    Err(ApiError::bad_request_with_details(
+        "OFFSET_TOO_LARGE",
+        format!(
+            "Offset {} exceeds the maximum of {}; {}",
+            offset, max_offset, guidance
+        ),
+        details,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_defaults_and_clamps() {
+        let size = PageSize::new(50, 200);
+        assert_eq!(size.resolve(None), 50);
+        assert_eq!(size.resolve(Some(75)), 75);
+        assert_eq!(size.resolve(Some(10_000)), 200);
+        assert_eq!(size.resolve(Some(0)), 1);
+        assert_eq!(size.resolve(Some(-5)), 1);
+    }
+
+    #[test]
+    fn test_parse_page_size_entry() {
+        assert_eq!(
+            PaginationDefaults::parse_entry("anchors=25:100"),
+            Some((PagedResource::Anchors, PageSize::new(25, 100)))
+        );
+        assert_eq!(
+            PaginationDefaults::parse_entry("rpc= 10 : 50"),
+            Some((PagedResource::RpcRecords, PageSize::new(10, 50)))
+        );
+
+        assert_eq!(PaginationDefaults::parse_entry("bogus=1:2"), None);
+        assert_eq!(PaginationDefaults::parse_entry("anchors=25"), None);
+        // A default above its max, or below one, is rejected
+        assert_eq!(PaginationDefaults::parse_entry("snapshots=90:10"), None);
+        assert_eq!(PaginationDefaults::parse_entry("snapshots=0:10"), None);
+    }
+
+    #[test]
+    fn test_configured_page_limit() {
+        let config = PaginationConfig {
+            page_sizes: PaginationDefaults::default()
+                .with_page_size(PagedResource::Anchors, PageSize::new(25, 100)),
+            ..PaginationConfig::default()
+        };
+        assert_eq!(config.page_limit(PagedResource::Anchors, None), 25);
+        assert_eq!(config.page_limit(PagedResource::Anchors, Some(500)), 100);
+        assert_eq!(config.page_limit(PagedResource::Corridors, None), 50);
+    }
+
+    #[test]
+    fn test_offset_within_limit() {
+        assert!(check_offset_against(0, 100, None).is_ok());
+        assert!(check_offset_against(100, 100, None).is_ok());
+    }
+
+    #[test]
+    fn test_excessive_offset_suggests_cursor() {
+        match check_offset_against(101, 100, Some("cursor")).unwrap_err() {
+            ApiError::BadRequest {
+                code,
+                message,
+                details,
+            } => {
+                assert_eq!(code, "OFFSET_TOO_LARGE");
+                assert!(message.contains("`cursor`"));
+                let details = details.unwrap();
+                assert_eq!(details["max_offset"], 100);
+                assert_eq!(details["suggestion"], "cursor");
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+}
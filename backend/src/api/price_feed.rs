@@ -46,6 +46,21 @@ pub struct PriceResponse {
     /// Price in USD
     #[schema(example = 0.12)]
     pub price_usd: f64,
+    /// Price provider the value came from
+    #[schema(example = "CoinGecko")]
+    pub source: String,
+    /// Seconds since the price was fetched
+    #[schema(example = 120)]
+    pub age_seconds: u64,
+    /// Age after which this asset's price is stale
+    #[schema(example = 3600)]
+    pub staleness_threshold_seconds: u64,
+    /// Whether the price is older than its staleness threshold
+    #[schema(example = false)]
+    pub stale: bool,
+    /// Latest fetched price, when it was rejected for jumping too far from
+    /// `price_usd`
+    pub rejected_price_usd: Option<f64>,
     /// Timestamp of the response
     #[schema(example = "2024-01-15T10:30:00Z")]
     pub timestamp: String,
@@ -117,11 +132,16 @@ pub async fn get_price(
     State(price_feed): State<Arc<PriceFeedClient>>,
     Query(params): Query<GetPriceQuery>,
 ) -> impl IntoResponse {
-    match price_feed.get_price(&params.asset).await {
-        Ok(price) => {
+    match price_feed.get_price_status(&params.asset).await {
+        Ok(status) => {
             let response = PriceResponse {
                 asset: params.asset,
-                price_usd: price,
+                price_usd: status.price.price_usd,
+                source: status.price.source,
+                age_seconds: status.age_seconds,
+                staleness_threshold_seconds: status.staleness_threshold_seconds,
+                stale: status.stale,
+                rejected_price_usd: status.rejected_price_usd,
                 timestamp: chrono::Utc::now().to_rfc3339(),
             };
             (StatusCode::OK, Json(response)).into_response()
@@ -0,0 +1,109 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, put},
+    Json, Router,
+};
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use crate::admin_audit_log::{AdminAuditLogger, AuditContext};
+use crate::error::{ApiError, ApiResult};
+use crate::services::resource_acl::{ResourceAcl, ResourceType};
+
+#[derive(Debug, Serialize)]
+pub struct ResourceGrantsResponse {
+    pub resource_type: String,
+    pub resource_id: String,
+    /// Users allowed to mutate the resource; empty when only the write scope
+    /// applies
+    pub grantees: Vec<String>,
+}
+
+pub fn routes(db: SqlitePool) -> Router {
+    Router::new()
+        .route(
+            "/api/admin/permissions/:resource_type/:resource_id",
+            get(list_grants),
+        )
+        .route(
+            "/api/admin/permissions/:resource_type/:resource_id/:user_id",
+            put(grant_permission).delete(revoke_permission),
+        )
+        .with_state(db)
+}
+
+fn parse_resource_type(value: &str) -> ApiResult<ResourceType> {
+    value
+        .parse()
+        .map_err(|msg: String| ApiError::bad_request("INVALID_RESOURCE_TYPE", msg))
+}
+
+/// Users granted write access to an anchor or corridor
+///
+/// GET /api/admin/permissions/:resource_type/:resource_id
+pub async fn list_grants(
+    State(db): State<SqlitePool>,
+    Path((resource_type, resource_id)): Path<(String, String)>,
+) -> ApiResult<Json<ResourceGrantsResponse>> {
+    let resource = parse_resource_type(&resource_type)?;
+    let grantees = ResourceAcl::new(db)
+        .grantees(resource, &resource_id)
+        .await?;
+
+    Ok(Json(ResourceGrantsResponse {
+        resource_type: resource.to_string(),
+        resource_id,
+        grantees,
+    }))
+}
+
+/// Grant a user write access to an anchor or corridor, restricting it to its
+/// grantees
+///
+/// PUT /api/admin/permissions/:resource_type/:resource_id/:user_id
+pub async fn grant_permission(
+    State(db): State<SqlitePool>,
+    audit: AuditContext,
+    Path((resource_type, resource_id, user_id)): Path<(String, String, String)>,
+) -> ApiResult<StatusCode> {
+    let resource = parse_resource_type(&resource_type)?;
+    ResourceAcl::new(db.clone())
+        .grant(resource, &resource_id, &user_id)
+        .await?;
+    AdminAuditLogger::new(db)
+        .record(
+            &audit,
+            "permission.grant",
+            &format!("{}:{}", resource, resource_id),
+            serde_json::json!({ "user_id": user_id }),
+        )
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Revoke a user's write access; revoking the last grant lifts the
+/// restriction
+///
+/// DELETE /api/admin/permissions/:resource_type/:resource_id/:user_id
+pub async fn revoke_permission(
+    State(db): State<SqlitePool>,
+    audit: AuditContext,
+    Path((resource_type, resource_id, user_id)): Path<(String, String, String)>,
+) -> ApiResult<StatusCode> {
+    let resource = parse_resource_type(&resource_type)?;
+    ResourceAcl::new(db.clone())
+        .revoke(resource, &resource_id, &user_id)
+        .await?;
+    AdminAuditLogger::new(db)
+        .record(
+            &audit,
+            "permission.revoke",
+            &format!("{}:{}", resource, resource_id),
+            serde_json::json!({ "user_id": user_id }),
+        )
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
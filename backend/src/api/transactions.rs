@@ -10,7 +10,10 @@ use uuid::Uuid;
 
 use crate::{
     database::Database,
-    models::{PendingTransaction, PendingTransactionWithSignatures, Signature, TransactionResult},
+    models::{
+        transaction_envelope::EnvelopeError, PendingTransaction, PendingTransactionWithSignatures,
+        Signature, TransactionResult,
+    },
     state::AppState,
 };
 
@@ -47,6 +50,9 @@ pub async fn create_transaction(
         .create_pending_transaction(&req.source_account, &req.xdr, req.required_signatures)
         .await
         .map_err(|e| {
+            if let Some(invalid) = e.downcast_ref::<EnvelopeError>() {
+                return (StatusCode::BAD_REQUEST, invalid.to_string());
+            }
             tracing::error!("Failed to create transaction: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
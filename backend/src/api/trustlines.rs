@@ -3,11 +3,12 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     routing::get,
-    Json, Router,
+    Extension, Json, Router,
 };
 use serde::Deserialize;
 use std::sync::Arc;
 
+use crate::api::pagination::{PagedResource, PaginationConfig};
 use crate::models::{TrustlineMetrics, TrustlineSnapshot, TrustlineStat};
 use crate::services::trustline_analyzer::TrustlineAnalyzer;
 
@@ -35,12 +36,8 @@ impl From<anyhow::Error> for ApiError {
 
 #[derive(Deserialize)]
 pub struct RankingsParams {
-    #[serde(default = "default_limit")]
-    limit: i64,
-}
-
-fn default_limit() -> i64 {
-    50
+    #[serde(default)]
+    limit: Option<i64>,
 }
 
 #[derive(Deserialize)]
@@ -77,9 +74,10 @@ async fn get_trustline_metrics(
 
 async fn get_trustline_rankings(
     State(analyzer): State<Arc<TrustlineAnalyzer>>,
+    Extension(pagination): Extension<PaginationConfig>,
     Query(params): Query<RankingsParams>,
 ) -> ApiResult<Json<Vec<TrustlineStat>>> {
-    let limit = params.limit.clamp(1, 200);
+    let limit = pagination.page_limit(PagedResource::Trustlines, params.limit);
     let rankings = analyzer
         .get_trustline_rankings(limit)
         .await
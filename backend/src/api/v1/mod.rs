@@ -1,3 +1,4 @@
+use crate::api::pagination::PaginationConfig;
 use crate::api::{
     account_merges, anchors_cached, cache_stats, corridors_cached, cost_calculator, fee_bump,
     liquidity_pools, metrics_cached, oauth, price_feed as price_feed_api, webhooks,
@@ -14,10 +15,11 @@ use crate::services::fee_bump_tracker::FeeBumpTrackerService;
 use crate::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
 use crate::services::price_feed::PriceFeedClient;
 use crate::state::AppState;
+use crate::webhooks::WebhookLimits;
 use axum::{
     middleware,
     routing::{get, put},
-    Router,
+    Extension, Router,
 };
 use std::sync::Arc;
 use tower::ServiceBuilder;
@@ -40,6 +42,8 @@ pub fn routes(
     cors: CorsLayer,
     pool: sqlx::SqlitePool,
     cache: Arc<CacheManager>,
+    webhook_limits: WebhookLimits,
+    pagination: PaginationConfig,
 ) -> Router {
     // 1. Cached routes
     let cached_routes = Router::new()
@@ -72,6 +76,7 @@ pub fn routes(
             axum::routing::post(create_anchor_asset),
         )
         .route("/corridors", axum::routing::post(create_corridor))
+        .route("/corridors/:id", axum::routing::delete(delete_corridor))
         .route(
             "/corridors/:id/metrics-from-transactions",
             put(update_corridor_metrics_from_transactions),
@@ -80,7 +85,7 @@ pub fn routes(
         .layer(middleware::from_fn(auth_middleware));
 
     let protected_webhook_routes = Router::new()
-        .nest("/webhooks", webhooks::routes(pool.clone()))
+        .nest("/webhooks", webhooks::routes(pool.clone(), webhook_limits))
         .layer(middleware::from_fn(auth_middleware));
 
     // 4. RPC routes
@@ -121,6 +126,7 @@ pub fn routes(
         .merge(rpc_routes)
         .merge(service_routes)
         .merge(oauth_routes)
+        .layer(Extension(pagination))
         .layer(
             ServiceBuilder::new()
                 .layer(middleware::from_fn_with_state(
@@ -14,7 +14,9 @@ use tracing::info;
 
 use crate::auth::sep10_middleware::{sep10_auth_middleware, Sep10User};
 use crate::auth::sep10_simple::Sep10Service;
-use crate::services::verification_rewards::{VerificationRewardsService, VerifySnapshotRequest};
+use crate::services::verification_rewards::{
+    BatchClaimRequest, VerificationRewardsService, VerifySnapshotRequest,
+};
 
 /// Build verification rewards routes
 pub fn routes(
@@ -23,6 +25,7 @@ pub fn routes(
 ) -> Router {
     Router::new()
         .route("/verify", post(verify_snapshot))
+        .route("/verify/batch", post(claim_batch))
         .route("/stats", get(get_user_stats))
         .route("/history", get(get_user_verifications))
         .layer(middleware::from_fn_with_state(
@@ -64,11 +67,20 @@ pub struct ErrorResponse {
 
 /// POST /api/verifications/verify
 /// Verify a snapshot hash and earn rewards
+///
+/// Rewards are only granted to the account proven by the caller's SEP-10
+/// session; requests without a verified session are rejected.
 pub async fn verify_snapshot(
     State(service): State<Arc<VerificationRewardsService>>,
-    sep10_user: axum::Extension<Sep10User>,
+    sep10_user: Option<axum::Extension<Sep10User>>,
     Json(request): Json<VerifySnapshotRequest>,
 ) -> Result<Response, VerificationError> {
+    let Some(axum::Extension(sep10_user)) = sep10_user else {
+        return Err(VerificationError::Unauthorized(
+            "A verified SEP-10 session is required to claim verification rewards".to_string(),
+        ));
+    };
+
     info!(
         "Verification request from user {} for snapshot {}",
         sep10_user.account, request.snapshot_id
@@ -82,6 +94,37 @@ pub async fn verify_snapshot(
     Ok((StatusCode::OK, Json(response)).into_response())
 }
 
+/// POST /api/verifications/verify/batch
+/// Claim rewards for several `{epoch, hash, signature}` entries at once
+///
+/// Every entry is claimed for the account of the caller's SEP-10 session,
+/// must be signed by that account's key, and gets its own result; valid
+/// entries are rewarded even if others are not.
+pub async fn claim_batch(
+    State(service): State<Arc<VerificationRewardsService>>,
+    sep10_user: Option<axum::Extension<Sep10User>>,
+    Json(request): Json<BatchClaimRequest>,
+) -> Result<Response, VerificationError> {
+    let Some(axum::Extension(sep10_user)) = sep10_user else {
+        return Err(VerificationError::Unauthorized(
+            "A verified SEP-10 session is required to claim verification rewards".to_string(),
+        ));
+    };
+
+    info!(
+        "Batch claim of {} entries from user {}",
+        request.entries.len(),
+        sep10_user.account
+    );
+
+    let response = service
+        .claim_batch(&sep10_user.account, request)
+        .await
+        .map_err(|e| VerificationError::VerificationFailed(e.to_string()))?;
+
+    Ok((StatusCode::OK, Json(response)).into_response())
+}
+
 /// GET /api/verifications/stats
 /// Get current user's reward statistics
 pub async fn get_user_stats(
@@ -9,12 +9,15 @@ use axum::{
 use serde_json::json;
 use sqlx::SqlitePool;
 
-use crate::auth_middleware::AuthUser;
-use crate::webhooks::{CreateWebhookRequest, WebhookResponse, WebhookService};
+use crate::admin_audit_log::{AdminAuditLogger, AuditContext};
+use crate::auth_middleware::{scopes, AuthUser};
+use crate::webhooks::{
+    CreateWebhookRequest, WebhookError, WebhookLimits, WebhookResponse, WebhookService,
+};
 
 /// POST /api/webhooks - Register a new webhook
 pub async fn register_webhook(
-    State(db): State<SqlitePool>,
+    State((db, limits)): State<(SqlitePool, WebhookLimits)>,
     auth_user: AuthUser,
     Json(request): Json<CreateWebhookRequest>,
 ) -> Result<Response, WebhookApiError> {
@@ -79,18 +82,18 @@ pub async fn register_webhook(
         ));
     }
 
-    let service = WebhookService::new(db);
+    let service = WebhookService::new(db).with_limits(limits);
     let response = service
         .register_webhook(&auth_user.user_id, request)
         .await
-        .map_err(|e| WebhookApiError::ServerError(e.to_string()))?;
+        .map_err(WebhookApiError::from_service)?;
 
     Ok((StatusCode::CREATED, Json(response)).into_response())
 }
 
 /// GET /api/webhooks - List webhooks for authenticated user
 pub async fn list_webhooks(
-    State(db): State<SqlitePool>,
+    State((db, _)): State<(SqlitePool, WebhookLimits)>,
     auth_user: AuthUser,
 ) -> Result<Response, WebhookApiError> {
     let service = WebhookService::new(db);
@@ -118,14 +121,29 @@ pub async fn list_webhooks(
 }
 
 /// DELETE /api/webhooks/:id - Delete/deactivate webhook
+///
+/// Admins may delete other users' webhooks; those deletions are audited.
 pub async fn delete_webhook(
-    State(db): State<SqlitePool>,
+    State((db, _)): State<(SqlitePool, WebhookLimits)>,
     auth_user: AuthUser,
+    audit: AuditContext,
     Path(webhook_id): Path<String>,
 ) -> Result<Response, WebhookApiError> {
-    let service = WebhookService::new(db);
+    let service = WebhookService::new(db.clone());
+
+    let mut owner_id = auth_user.user_id.clone();
+    if auth_user.has_scope(scopes::ADMIN) {
+        if let Some(webhook) = service
+            .get_webhook(&webhook_id)
+            .await
+            .map_err(|e| WebhookApiError::ServerError(e.to_string()))?
+        {
+            owner_id = webhook.user_id;
+        }
+    }
+
     let deleted = service
-        .delete_webhook(&webhook_id, &auth_user.user_id)
+        .delete_webhook(&webhook_id, &owner_id)
         .await
         .map_err(|e| WebhookApiError::ServerError(e.to_string()))?;
 
@@ -133,6 +151,17 @@ pub async fn delete_webhook(
         return Err(WebhookApiError::NotFound("Webhook not found".to_string()));
     }
 
+    if owner_id != auth_user.user_id {
+        AdminAuditLogger::new(db)
+            .record(
+                &audit,
+                "webhook.delete",
+                &format!("webhook:{}", webhook_id),
+                json!({ "owner_id": owner_id }),
+            )
+            .await;
+    }
+
     Ok((
         StatusCode::OK,
         Json(json!({"message": "Webhook deleted successfully"})),
@@ -142,7 +171,7 @@ pub async fn delete_webhook(
 
 /// POST /api/webhooks/:id/test - Send test payload to webhook
 pub async fn test_webhook(
-    State(db): State<SqlitePool>,
+    State((db, _)): State<(SqlitePool, WebhookLimits)>,
     auth_user: AuthUser,
     Path(webhook_id): Path<String>,
 ) -> Result<Response, WebhookApiError> {
@@ -189,19 +218,36 @@ pub async fn test_webhook(
 pub enum WebhookApiError {
     NotFound(String),
     BadRequest(String),
+    Conflict(String),
     Forbidden,
+    PayloadTooLarge(String),
     ServerError(String),
 }
 
+impl WebhookApiError {
+    /// Map a webhook service failure, keeping the status of typed errors
+    fn from_service(e: anyhow::Error) -> Self {
+        match e.downcast_ref::<WebhookError>() {
+            Some(limit @ WebhookError::LimitExceeded { .. }) => Self::Conflict(limit.to_string()),
+            Some(too_large @ WebhookError::PayloadTooLarge { .. }) => {
+                Self::PayloadTooLarge(too_large.to_string())
+            }
+            None => Self::ServerError(e.to_string()),
+        }
+    }
+}
+
 impl IntoResponse for WebhookApiError {
     fn into_response(self) -> Response {
         let (status, message) = match self {
             WebhookApiError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
             WebhookApiError::BadRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            WebhookApiError::Conflict(msg) => (StatusCode::CONFLICT, msg),
             WebhookApiError::Forbidden => (
                 StatusCode::FORBIDDEN,
                 "You don't have permission to access this webhook".to_string(),
             ),
+            WebhookApiError::PayloadTooLarge(msg) => (StatusCode::PAYLOAD_TOO_LARGE, msg),
             WebhookApiError::ServerError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
         };
 
@@ -209,11 +255,43 @@ impl IntoResponse for WebhookApiError {
     }
 }
 
-/// Create webhook routes
-pub fn routes(db: SqlitePool) -> Router {
+/// Create webhook routes; registrations are held to `limits`
+pub fn routes(db: SqlitePool, limits: WebhookLimits) -> Router {
     Router::new()
         .route("/api/webhooks", post(register_webhook).get(list_webhooks))
         .route("/api/webhooks/:id", delete(delete_webhook))
         .route("/api/webhooks/:id/test", post(test_webhook))
-        .with_state(db)
+        .with_state((db, limits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_errors_keep_their_status() {
+        let status = |e: WebhookError| {
+            WebhookApiError::from_service(e.into())
+                .into_response()
+                .status()
+        };
+
+        assert_eq!(
+            status(WebhookError::LimitExceeded { limit: 10 }),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            status(WebhookError::PayloadTooLarge {
+                size: 2_048,
+                limit: 1_024
+            }),
+            StatusCode::PAYLOAD_TOO_LARGE
+        );
+        assert_eq!(
+            WebhookApiError::from_service(anyhow::anyhow!("database is locked"))
+                .into_response()
+                .status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
 }
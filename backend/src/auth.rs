@@ -24,6 +24,9 @@ const REFRESH_TOKEN_EXPIRY_DAYS: i64 = 7;
 pub struct User {
     pub id: String,
     pub username: String,
+    /// Scopes granted to the user, e.g. `anchor:write`
+    #[serde(default)]
+    pub scopes: Vec<String>,
 }
 
 /// Login request
@@ -68,6 +71,8 @@ pub struct Claims {
     pub exp: i64,           // Expiry timestamp
     pub iat: i64,           // Issued at timestamp
     pub token_type: String, // "access" or "refresh"
+    #[serde(default)]
+    pub scopes: Vec<String>, // Granted scopes, see `auth_middleware::scopes`
 }
 
 /// Authentication service
@@ -91,6 +96,12 @@ impl AuthService {
         }
     }
 
+    /// Secret tokens are signed with, shared with the auth middleware that
+    /// verifies them
+    pub fn jwt_secret(&self) -> &str {
+        &self.jwt_secret
+    }
+
     /// Authenticate user with credentials
     /// TODO: Implement database-backed user store with bcrypt/argon2 password hashing
     pub fn authenticate(&self, _username: &str, _password: &str) -> Result<User> {
@@ -115,6 +126,7 @@ impl AuthService {
             exp: expiration,
             iat: Utc::now().timestamp(),
             token_type: "access".to_string(),
+            scopes: user.scopes.clone(),
         };
 
         encode(
@@ -138,6 +150,7 @@ impl AuthService {
             exp: expiration,
             iat: Utc::now().timestamp(),
             token_type: "refresh".to_string(),
+            scopes: user.scopes.clone(),
         };
 
         encode(
@@ -257,6 +270,7 @@ impl AuthService {
         let user = User {
             id: claims.sub,
             username: claims.username,
+            scopes: claims.scopes,
         };
 
         // Generate new access token
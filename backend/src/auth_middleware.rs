@@ -1,13 +1,27 @@
 use axum::{
-    extract::{Extension, Request},
+    extract::{Extension, Path, Request, State},
     http::{header, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
 use serde_json::json;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::auth::Claims;
+use crate::services::resource_acl::{ResourceAcl, ResourceType};
+
+/// Scopes checked by [`require_scope`]
+pub mod scopes {
+    /// Create and update anchors and their assets
+    pub const ANCHOR_WRITE: &str = "anchor:write";
+    /// Create corridors and recompute their metrics
+    pub const CORRIDOR_WRITE: &str = "corridor:write";
+    /// Register, list and delete webhooks
+    pub const WEBHOOK_MANAGE: &str = "webhook:manage";
+    /// Satisfies every other scope
+    pub const ADMIN: &str = "admin";
+}
 
 /// JWT secret shared via extension
 #[derive(Clone)]
@@ -18,6 +32,14 @@ pub struct JwtSecret(pub Arc<str>);
 pub struct AuthUser {
     pub user_id: String,
     pub username: String,
+    pub scopes: Vec<String>,
+}
+
+impl AuthUser {
+    /// Whether the user was granted `scope`, directly or through `admin`
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.iter().any(|s| s == scope || s == scopes::ADMIN)
+    }
 }
 
 #[axum::async_trait]
@@ -64,12 +86,74 @@ pub async fn auth_middleware(
     let auth_user = AuthUser {
         user_id: claims.sub,
         username: claims.username,
+        scopes: claims.scopes,
     };
     req.extensions_mut().insert(auth_user);
 
     Ok(next.run(req).await)
 }
 
+/// Scope a route requires, used as state for [`require_scope`]
+#[derive(Debug, Clone, Copy)]
+pub struct RequiredScope(pub &'static str);
+
+/// Scope middleware - must run after [`auth_middleware`]; rejects users
+/// lacking the required scope with 403
+pub async fn require_scope(
+    State(RequiredScope(scope)): State<RequiredScope>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AuthError> {
+    let user = req
+        .extensions()
+        .get::<AuthUser>()
+        .ok_or(AuthError::MissingToken)?;
+
+    if !user.has_scope(scope) {
+        return Err(AuthError::InsufficientScope(scope));
+    }
+
+    Ok(next.run(req).await)
+}
+
+/// Resource a route mutates, used as state for
+/// [`require_resource_permission`]; the resource id is the route's `:id`
+#[derive(Clone)]
+pub struct ResourcePermission {
+    pub acl: ResourceAcl,
+    pub resource: ResourceType,
+}
+
+/// Per-resource permission middleware - must run after [`auth_middleware`];
+/// rejects users not permitted on the `:id` resource with 403. Routes without
+/// an `:id` are left to their scope.
+pub async fn require_resource_permission(
+    State(ResourcePermission { acl, resource }): State<ResourcePermission>,
+    params: Option<Path<HashMap<String, String>>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, AuthError> {
+    let Some(id) = params.and_then(|Path(mut params)| params.remove("id")) else {
+        return Ok(next.run(req).await);
+    };
+    let user = req
+        .extensions()
+        .get::<AuthUser>()
+        .ok_or(AuthError::MissingToken)?;
+
+    if user.has_scope(scopes::ADMIN) {
+        return Ok(next.run(req).await);
+    }
+    match acl.can_write(resource, &id, &user.user_id).await {
+        Ok(true) => Ok(next.run(req).await),
+        Ok(false) => Err(AuthError::ResourceForbidden { resource, id }),
+        Err(e) => {
+            tracing::error!("Resource permission check failed: {:#}", e);
+            Err(AuthError::PermissionCheckFailed)
+        }
+    }
+}
+
 /// Validate access token
 fn validate_access_token(token: &str, secret: &str) -> Result<Claims, AuthError> {
     use jsonwebtoken::{decode, DecodingKey, Validation};
@@ -96,13 +180,37 @@ fn validate_access_token(token: &str, secret: &str) -> Result<Claims, AuthError>
 pub enum AuthError {
     MissingToken,
     InvalidToken,
+    InsufficientScope(&'static str),
+    ResourceForbidden { resource: ResourceType, id: String },
+    PermissionCheckFailed,
 }
 
 impl IntoResponse for AuthError {
     fn into_response(self) -> Response {
         let (status, message) = match self {
-            AuthError::MissingToken => (StatusCode::UNAUTHORIZED, "Missing authentication token"),
-            AuthError::InvalidToken => (StatusCode::UNAUTHORIZED, "Invalid or expired token"),
+            AuthError::MissingToken => (
+                StatusCode::UNAUTHORIZED,
+                "Missing authentication token".to_string(),
+            ),
+            AuthError::InvalidToken => (
+                StatusCode::UNAUTHORIZED,
+                "Invalid or expired token".to_string(),
+            ),
+            AuthError::InsufficientScope(scope) => {
+                let body = json!({
+                    "error": "Insufficient scope",
+                    "reason": format!("missing required scope '{}'", scope),
+                });
+                return (StatusCode::FORBIDDEN, axum::Json(body)).into_response();
+            }
+            AuthError::ResourceForbidden { resource, id } => (
+                StatusCode::FORBIDDEN,
+                format!("No write permission on {} '{}'", resource, id),
+            ),
+            AuthError::PermissionCheckFailed => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Permission check unavailable".to_string(),
+            ),
         };
 
         let body = json!({
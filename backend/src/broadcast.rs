@@ -1,7 +1,9 @@
 use crate::models::corridor::Corridor;
 use crate::models::Anchor;
+use crate::services::event_bus::{DomainEvent, SampledReceiver};
 use crate::websocket::{WsMessage, WsState};
 use std::sync::Arc;
+use tokio::sync::broadcast;
 
 /// Broadcast an anchor update to all WebSocket clients
 pub fn broadcast_anchor_update(ws_state: &Arc<WsState>, anchor: &Anchor) {
@@ -29,6 +31,62 @@ pub fn broadcast_corridor_update(ws_state: &Arc<WsState>, corridor: &Corridor) {
     ws_state.broadcast(message);
 }
 
+/// Broadcast a domain event from the event bus to all WebSocket clients
+pub fn broadcast_domain_event(ws_state: &Arc<WsState>, event: &DomainEvent) {
+    let timestamp = chrono::Utc::now().to_rfc3339();
+    let message = match event {
+        DomainEvent::AnchorStatusChanged(e) => WsMessage::AnchorUpdate {
+            anchor_id: e.anchor_id.clone(),
+            name: e.name.clone(),
+            reliability_score: e.reliability_score,
+            status: e.new_status.clone(),
+        },
+        DomainEvent::CorridorHealthDegraded(e) => WsMessage::HealthAlert {
+            corridor_id: e.corridor_key.clone(),
+            severity: e.severity.clone(),
+            message: e.changes.join("; "),
+            timestamp,
+        },
+        DomainEvent::CorridorLiquidityDropped(e) => WsMessage::HealthAlert {
+            corridor_id: e.corridor_key.clone(),
+            severity: e.severity.clone(),
+            message: format!(
+                "Liquidity dropped to ${:.0} (threshold ${:.0})",
+                e.liquidity_depth_usd, e.threshold
+            ),
+            timestamp,
+        },
+        DomainEvent::PaymentCreated(e) => WsMessage::NewPayment {
+            corridor_id: format!("{}:{}", e.asset_code, e.asset_issuer),
+            amount: e.amount,
+            successful: true,
+            timestamp: e.timestamp.clone(),
+        },
+        // Backend connectivity and price feed health are for operators, not
+        // dashboard clients
+        DomainEvent::RpcDegraded(_) | DomainEvent::RpcRecovered(_) | DomainEvent::PriceStale(_) => {
+            return
+        }
+    };
+    ws_state.broadcast(message);
+}
+
+/// Forward events published on the event bus to WebSocket clients
+pub async fn run_domain_event_forwarder(ws_state: Arc<WsState>, mut rx: SampledReceiver) {
+    loop {
+        match rx.recv().await {
+            Ok(event) => broadcast_domain_event(&ws_state, &event),
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                tracing::warn!(
+                    "WebSocket event forwarder lagged, skipped {} events",
+                    skipped
+                );
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -51,6 +109,8 @@ mod tests {
             status: "active".to_string(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            domain_verified: false,
+            domain_check_failures: 0,
         };
 
         // Should not panic
@@ -1,3 +1,4 @@
+use crate::api::freshness::DEFAULT_STALE_AFTER_SECONDS;
 use redis::aio::MultiplexedConnection;
 use serde::{de::DeserializeOwned, Serialize};
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -29,6 +30,13 @@ pub struct CacheConfig {
     pub corridor_metrics_ttl: usize, // 5 minutes
     pub anchor_data_ttl: usize,      // 10 minutes
     pub dashboard_stats_ttl: usize,  // 1 minute
+    /// `Cache-Control: max-age` sent with cached responses, per cache type;
+    /// `None` uses the server-side TTL
+    pub corridor_http_max_age: Option<usize>,
+    pub anchor_http_max_age: Option<usize>,
+    pub dashboard_http_max_age: Option<usize>,
+    /// Seconds after which cached analytics data is flagged stale
+    pub data_stale_after: usize,
 }
 
 impl CacheConfig {
@@ -40,6 +48,22 @@ impl CacheConfig {
             _ => 300,
         }
     }
+
+    /// Seconds clients and CDNs may cache a response, the TTL unless overridden
+    pub fn http_max_age(&self, cache_type: &str) -> usize {
+        let max_age = match cache_type {
+            "corridor" => self.corridor_http_max_age,
+            "anchor" => self.anchor_http_max_age,
+            "dashboard" => self.dashboard_http_max_age,
+            _ => None,
+        };
+        max_age.unwrap_or_else(|| self.get_ttl(cache_type))
+    }
+
+    /// Age past which analytics data is reported as stale
+    pub fn stale_after(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.data_stale_after as i64)
+    }
 }
 
 impl Default for CacheConfig {
@@ -48,6 +72,10 @@ impl Default for CacheConfig {
             corridor_metrics_ttl: 300, // 5 minutes
             anchor_data_ttl: 600,      // 10 minutes
             dashboard_stats_ttl: 60,   // 1 minute
+            corridor_http_max_age: None,
+            anchor_http_max_age: None,
+            dashboard_http_max_age: None,
+            data_stale_after: DEFAULT_STALE_AFTER_SECONDS as usize,
         }
     }
 }
@@ -65,8 +93,12 @@ impl CacheManager {
     pub async fn new(config: CacheConfig) -> anyhow::Result<Self> {
         let redis_url =
             std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
+        Self::connect(config, &redis_url).await
+    }
 
-        let connection = if let Ok(client) = redis::Client::open(redis_url.as_str()) {
+    /// Like `new`, with an explicit Redis URL instead of `REDIS_URL`
+    pub async fn connect(config: CacheConfig, redis_url: &str) -> anyhow::Result<Self> {
+        let connection = if let Ok(client) = redis::Client::open(redis_url) {
             match client.get_multiplexed_tokio_connection().await {
                 Ok(conn) => {
                     tracing::info!("Connected to Redis for caching");
@@ -91,7 +123,14 @@ impl CacheManager {
         })
     }
 
-    /// Get value from cache, returns None if not found or Redis unavailable
+    /// Whether Redis is connected; without it every lookup misses
+    pub async fn is_connected(&self) -> bool {
+        self.redis_connection.read().await.is_some()
+    }
+
+    /// Get value from cache, returns None if not found or Redis unavailable.
+    /// Fails when Redis is connected but the lookup itself errors, so callers
+    /// can bypass the cache rather than treat it as a miss.
     pub async fn get<T: DeserializeOwned>(&self, key: &str) -> anyhow::Result<Option<T>> {
         if let Some(conn) = self.redis_connection.read().await.as_ref() {
             let mut conn = conn.clone();
@@ -119,10 +158,9 @@ impl CacheManager {
                     Ok(None)
                 }
                 Err(e) => {
-                    tracing::warn!("Redis GET error for {}: {}", key, e);
                     self.misses.fetch_add(1, Ordering::Relaxed);
                     crate::observability::metrics::record_cache_lookup(false);
-                    Ok(None)
+                    Err(anyhow::anyhow!("Redis GET error for {}: {}", key, e))
                 }
             }
         } else {
@@ -299,6 +337,10 @@ pub mod keys {
         format!("anchor:list:{}:{}", limit, offset)
     }
 
+    pub fn anchor_list_after(limit: i64, cursor: &str) -> String {
+        format!("anchor:list:{}:after:{}", limit, cursor)
+    }
+
     pub fn anchor_detail(id: &str) -> String {
         format!("anchor:detail:{}", id)
     }
@@ -327,6 +369,10 @@ pub mod keys {
         "metrics:overview".to_string()
     }
 
+    pub fn dashboard_overview() -> String {
+        "dashboard:overview".to_string()
+    }
+
     /// Pattern for invalidating all anchor-related caches
     pub fn anchor_pattern() -> String {
         "anchor:*".to_string()
@@ -1,6 +1,37 @@
 use crate::cache::CacheManager;
+use axum::http::{HeaderName, HeaderValue};
+use axum::response::Response;
 use std::sync::Arc;
 
+/// Response header reporting how the cache served a request
+pub const X_CACHE: HeaderName = HeaderName::from_static("x-cache");
+
+/// How a cached endpoint got its data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheStatus {
+    Hit,
+    Miss,
+    /// The cache lookup failed, so the source of truth was read directly
+    Bypass,
+}
+
+impl CacheStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheStatus::Hit => "HIT",
+            CacheStatus::Miss => "MISS",
+            CacheStatus::Bypass => "BYPASS",
+        }
+    }
+
+    /// Set `X-Cache` on `response`
+    pub fn apply(self, response: &mut Response) {
+        response
+            .headers_mut()
+            .insert(X_CACHE, HeaderValue::from_static(self.as_str()));
+    }
+}
+
 /// Helper trait for cache-aware operations
 pub trait CacheAware {
     fn get_or_fetch<T, F>(
@@ -12,6 +43,19 @@ pub trait CacheAware {
     where
         T: serde::Serialize + serde::de::DeserializeOwned,
         F: std::future::Future<Output = anyhow::Result<T>>;
+
+    /// Like `get_or_fetch`, also reporting whether the cache was hit, missed
+    /// or bypassed. A failing cache lookup is logged and `fetch_fn` runs
+    /// instead of failing the request.
+    fn get_or_fetch_with_status<T, F>(
+        cache: &Arc<CacheManager>,
+        key: &str,
+        ttl: usize,
+        fetch_fn: F,
+    ) -> impl std::future::Future<Output = anyhow::Result<(T, CacheStatus)>>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+        F: std::future::Future<Output = anyhow::Result<T>>;
 }
 
 /// Implement for unit type to provide static methods
@@ -22,23 +66,47 @@ impl CacheAware for () {
         ttl: usize,
         fetch_fn: F,
     ) -> impl std::future::Future<Output = anyhow::Result<T>>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned,
+        F: std::future::Future<Output = anyhow::Result<T>>,
+    {
+        async move {
+            let (data, _) = Self::get_or_fetch_with_status(cache, key, ttl, fetch_fn).await?;
+            Ok(data)
+        }
+    }
+
+    fn get_or_fetch_with_status<T, F>(
+        cache: &Arc<CacheManager>,
+        key: &str,
+        ttl: usize,
+        fetch_fn: F,
+    ) -> impl std::future::Future<Output = anyhow::Result<(T, CacheStatus)>>
     where
         T: serde::Serialize + serde::de::DeserializeOwned,
         F: std::future::Future<Output = anyhow::Result<T>>,
     {
         async move {
             // Try to get from cache first
-            if let Ok(Some(cached)) = cache.get::<T>(key).await {
-                return Ok(cached);
-            }
+            let status = match cache.get::<T>(key).await {
+                Ok(Some(cached)) => return Ok((cached, CacheStatus::Hit)),
+                Ok(None) => CacheStatus::Miss,
+                Err(e) => {
+                    tracing::warn!("Bypassing cache for {}: {}", key, e);
+                    CacheStatus::Bypass
+                }
+            };
 
             // Cache miss or error, fetch from source
             let data = fetch_fn.await?;
 
-            // Store in cache (ignore errors, cache is optional)
-            let _ = cache.set(key, &data, ttl).await;
+            // Store in cache (ignore errors, cache is optional); a cache that
+            // just failed to read is not written to
+            if status == CacheStatus::Miss {
+                let _ = cache.set(key, &data, ttl).await;
+            }
 
-            Ok(data)
+            Ok((data, status))
         }
     }
 }
@@ -0,0 +1,99 @@
+//! Load shedding for expensive route groups.
+//!
+//! Each group gets a fixed number of in-flight request slots. A request that
+//! finds every slot taken is rejected straight away with `503` and a
+//! `Retry-After` header instead of queueing, independently of the per-client
+//! rate limiter.
+
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+use crate::observability::metrics as obs_metrics;
+
+/// Default in-flight request cap for the RPC route group
+pub const DEFAULT_RPC_MAX_CONCURRENT_REQUESTS: usize = 32;
+
+/// Default `Retry-After` sent with shed requests, in seconds
+pub const DEFAULT_RETRY_AFTER_SECONDS: u64 = 1;
+
+/// Concurrency limits per route group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcurrencyLimitConfig {
+    pub rpc_max_concurrent_requests: usize,
+    pub retry_after_seconds: u64,
+}
+
+impl Default for ConcurrencyLimitConfig {
+    fn default() -> Self {
+        Self {
+            rpc_max_concurrent_requests: DEFAULT_RPC_MAX_CONCURRENT_REQUESTS,
+            retry_after_seconds: DEFAULT_RETRY_AFTER_SECONDS,
+        }
+    }
+}
+
+/// In-flight request slots shared by every route of one group
+#[derive(Debug)]
+pub struct ConcurrencyLimiter {
+    group: &'static str,
+    max_in_flight: usize,
+    retry_after_seconds: u64,
+    slots: Arc<Semaphore>,
+}
+
+impl ConcurrencyLimiter {
+    pub fn new(group: &'static str, max_in_flight: usize, retry_after_seconds: u64) -> Self {
+        Self {
+            group,
+            max_in_flight,
+            retry_after_seconds,
+            slots: Arc::new(Semaphore::new(max_in_flight)),
+        }
+    }
+
+    /// Number of requests currently holding a slot
+    pub fn in_flight(&self) -> usize {
+        self.max_in_flight - self.slots.available_permits()
+    }
+
+    fn shed(&self) -> Response {
+        obs_metrics::record_request_shed(self.group);
+        tracing::warn!(
+            "Shedding request to {} routes: {} requests in flight",
+            self.group,
+            self.max_in_flight
+        );
+
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(header::RETRY_AFTER, self.retry_after_seconds.to_string())],
+            Json(json!({
+                "error": "Service overloaded",
+                "max_concurrent_requests": self.max_in_flight,
+                "retry_after": self.retry_after_seconds,
+            })),
+        )
+            .into_response()
+    }
+}
+
+/// Run the request if a slot of `limiter` is free, otherwise shed it
+pub async fn concurrency_limit_middleware(
+    State(limiter): State<Arc<ConcurrencyLimiter>>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let Ok(_permit) = limiter.slots.clone().try_acquire_owned() else {
+        return limiter.shed();
+    };
+
+    next.run(req).await
+}
@@ -0,0 +1,71 @@
+//! Cross-origin request policy.
+//!
+//! `CORS_ALLOWED_ORIGINS` is a comma-separated origin list, or `*` to allow
+//! every origin (development only). Responses carry `Vary` on the request
+//! headers the CORS decision depends on, so shared caches never serve one
+//! origin's `Access-Control-Allow-Origin` to another.
+
+use axum::http::{header, HeaderValue, Method};
+use std::time::Duration;
+use tower_http::cors::{Any, CorsLayer};
+
+/// Default time browsers may cache a preflight response
+pub const DEFAULT_CORS_MAX_AGE: Duration = Duration::from_secs(3600);
+
+const ALLOWED_METHODS: [Method; 7] = [
+    Method::GET,
+    Method::POST,
+    Method::PUT,
+    Method::DELETE,
+    Method::OPTIONS,
+    Method::PATCH,
+    Method::HEAD,
+];
+
+/// Build the CORS layer for `allowed_origins`, caching preflights for `max_age`
+///
+/// Fails when the list holds no valid origin rather than falling back to
+/// allowing every origin.
+pub fn cors_layer(allowed_origins: &str, max_age: Duration) -> anyhow::Result<CorsLayer> {
+    let base = CorsLayer::new()
+        .allow_methods(ALLOWED_METHODS)
+        .allow_headers(Any)
+        .max_age(max_age)
+        .vary([
+            header::ORIGIN,
+            header::ACCESS_CONTROL_REQUEST_METHOD,
+            header::ACCESS_CONTROL_REQUEST_HEADERS,
+        ]);
+
+    if allowed_origins.trim() == "*" {
+        tracing::warn!(
+            "CORS configured to allow ALL origins (*). \
+             This is insecure and should not be used in production."
+        );
+        return Ok(base.allow_origin(Any));
+    }
+
+    let origins: Vec<HeaderValue> = allowed_origins
+        .split(',')
+        .filter_map(|o| {
+            let trimmed = o.trim();
+            trimmed
+                .parse::<HeaderValue>()
+                .map_err(|e| {
+                    tracing::warn!("Skipping invalid CORS origin '{}': {}", trimmed, e);
+                })
+                .ok()
+        })
+        .collect();
+
+    if origins.is_empty() {
+        anyhow::bail!(
+            "CORS_ALLOWED_ORIGINS contains no valid origins. \
+             Set valid origins or use '*' explicitly for development. \
+             Refusing to fall back to allow-all. (SEC-011)"
+        );
+    }
+
+    tracing::info!("CORS restricted to {} specific origin(s)", origins.len());
+    Ok(base.allow_origin(origins))
+}
@@ -1,19 +1,33 @@
 use crate::admin_audit_log::AdminAuditLogger;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use sqlx::SqlitePool;
+use sqlx::pool::PoolConnection;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
+use std::str::FromStr;
 use std::time::Duration;
 use std::time::Instant;
 use uuid::Uuid;
 
 use crate::analytics::compute_anchor_metrics;
+use crate::analytics::health::HealthThresholds;
+use crate::db::aggregation::AggregationDb;
+use crate::db::metrics_buffer::PendingMetric;
+use crate::db::query_timeout::{with_query_timeout, QueryTimeout, DEFAULT_QUERY_TIMEOUT};
+use crate::db::retry::{retry_transient, RetryPolicy};
 use crate::models::api_key::{
     generate_api_key, hash_api_key, ApiKey, ApiKeyInfo, CreateApiKeyRequest, CreateApiKeyResponse,
 };
+use crate::models::asset::{AssetId, NATIVE_ASSET_ISSUER};
+use crate::models::timestamp::{format_timestamp, parse_timestamp};
+use crate::models::transaction_envelope::validate_envelope;
 use crate::models::{
-    Anchor, AnchorDetailResponse, AnchorMetricsHistory, Asset, CorridorRecord, CreateAnchorRequest,
-    MetricRecord, MuxedAccountAnalytics, MuxedAccountUsage, SnapshotRecord,
+    Anchor, AnchorCursor, AnchorDetailResponse, AnchorMetricsHistory, Asset, CorridorPayment,
+    CorridorRecord, CreateAnchorRequest, MetricRecord, MuxedAccountAnalytics, MuxedAccountUsage,
+    PaymentCursor, SnapshotCursor, SnapshotRecord,
 };
+use crate::muxed::DustThresholds;
+use crate::request_deadline::{remaining_budget, DeadlineExceeded};
 
 /// Configuration for database connection pool
 #[derive(Debug, Clone)]
@@ -23,6 +37,8 @@ pub struct PoolConfig {
     pub connect_timeout_seconds: u64,
     pub idle_timeout_seconds: u64,
     pub max_lifetime_seconds: u64,
+    /// Limit for heavy read queries; also bounds SQLite's busy wait
+    pub query_timeout_seconds: u64,
 }
 
 impl Default for PoolConfig {
@@ -33,46 +49,31 @@ impl Default for PoolConfig {
             connect_timeout_seconds: 30,
             idle_timeout_seconds: 600,
             max_lifetime_seconds: 1800,
+            query_timeout_seconds: DEFAULT_QUERY_TIMEOUT.as_secs(),
         }
     }
 }
 
 impl PoolConfig {
-    /// Load pool configuration from environment variables
-    pub fn from_env() -> Self {
-        Self {
-            max_connections: std::env::var("DB_POOL_MAX_CONNECTIONS")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(10),
-            min_connections: std::env::var("DB_POOL_MIN_CONNECTIONS")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(2),
-            connect_timeout_seconds: std::env::var("DB_POOL_CONNECT_TIMEOUT_SECONDS")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(30),
-            idle_timeout_seconds: std::env::var("DB_POOL_IDLE_TIMEOUT_SECONDS")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(600),
-            max_lifetime_seconds: std::env::var("DB_POOL_MAX_LIFETIME_SECONDS")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(1800),
-        }
+    pub fn query_timeout(&self) -> Duration {
+        Duration::from_secs(self.query_timeout_seconds)
     }
 
     /// Create a configured SQLite pool with these settings
+    ///
+    /// SQLite's busy timeout is set a little past the query timeout, so a
+    /// statement stuck on a lock when its query times out gives up shortly
+    /// after even if the interrupt does not reach it.
     pub async fn create_pool(&self, database_url: &str) -> Result<SqlitePool> {
+        let options = SqliteConnectOptions::from_str(database_url)?
+            .busy_timeout(self.query_timeout() + BUSY_TIMEOUT_GRACE);
         let pool = sqlx::sqlite::SqlitePoolOptions::new()
             .max_connections(self.max_connections)
             .min_connections(self.min_connections)
             .acquire_timeout(Duration::from_secs(self.connect_timeout_seconds))
             .idle_timeout(Some(Duration::from_secs(self.idle_timeout_seconds)))
             .max_lifetime(Some(Duration::from_secs(self.max_lifetime_seconds)))
-            .connect(database_url)
+            .connect_with(options)
             .await?;
 
         Ok(pool)
@@ -104,6 +105,19 @@ pub struct AnchorMetricsParams {
     pub volume_usd: Option<f64>,
 }
 
+/// Metrics history rows kept per anchor by default
+pub const DEFAULT_ANCHOR_METRICS_HISTORY_LIMIT: i64 = 1000;
+
+/// Daily volume (USD) below which corridors are hidden from listings by
+/// default
+pub const DEFAULT_MIN_CORRIDOR_VOLUME_USD: f64 = 1.0;
+
+/// Upper bound on a database health check round trip
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How much longer than the query timeout SQLite waits on a locked database
+const BUSY_TIMEOUT_GRACE: Duration = Duration::from_secs(1);
+
 /// Connection pool metrics
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct PoolMetrics {
@@ -111,8 +125,31 @@ pub struct PoolMetrics {
     pub idle: usize,
 }
 
+/// Row counts of the main entities
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, sqlx::FromRow,
+)]
+pub struct EntityCounts {
+    pub anchors: i64,
+    pub corridors: i64,
+    pub assets: i64,
+    pub snapshots: i64,
+}
+
 pub struct Database {
     pool: SqlitePool,
+    query_timeout: Duration,
+    /// Retries for writes that find the database busy or locked
+    retry_policy: RetryPolicy,
+    /// Metrics history rows kept per anchor; older rows are trimmed on insert
+    metrics_history_limit: i64,
+    /// Payments below their asset's threshold are dust and left out of
+    /// muxed analytics
+    muxed_dust_thresholds: DustThresholds,
+    /// Success rates separating green, yellow and red anchors
+    health_thresholds: HealthThresholds,
+    /// Default volume filter of corridor listings; zero lists every corridor
+    min_corridor_volume_usd: f64,
     pub admin_audit_logger: AdminAuditLogger,
 }
 
@@ -121,10 +158,98 @@ impl Database {
         let admin_audit_logger = AdminAuditLogger::new(pool.clone());
         Self {
             pool,
+            query_timeout: DEFAULT_QUERY_TIMEOUT,
+            retry_policy: RetryPolicy::default(),
+            metrics_history_limit: DEFAULT_ANCHOR_METRICS_HISTORY_LIMIT,
+            muxed_dust_thresholds: DustThresholds::default(),
+            health_thresholds: HealthThresholds::default(),
+            min_corridor_volume_usd: DEFAULT_MIN_CORRIDOR_VOLUME_USD,
             admin_audit_logger,
         }
     }
 
+    /// Limit for the heavier read queries (history, listings, analytics)
+    pub fn with_query_timeout(mut self, timeout: Duration) -> Self {
+        self.query_timeout = timeout;
+        self
+    }
+
+    /// Retries for writes that hit a busy or locked database
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Metrics history rows kept per anchor (at least one)
+    pub fn with_metrics_history_limit(mut self, limit: i64) -> Self {
+        self.metrics_history_limit = limit.max(1);
+        self
+    }
+
+    /// Smallest payment amount per asset counted by muxed analytics
+    pub fn with_muxed_dust_thresholds(mut self, thresholds: DustThresholds) -> Self {
+        self.muxed_dust_thresholds = thresholds;
+        self
+    }
+
+    /// Success rates at which anchors turn green or yellow
+    pub fn with_health_thresholds(mut self, thresholds: HealthThresholds) -> Self {
+        self.health_thresholds = thresholds;
+        self
+    }
+
+    /// Health bands anchor statuses are derived with
+    pub fn health_thresholds(&self) -> HealthThresholds {
+        self.health_thresholds
+    }
+
+    /// Latest daily volume (USD) corridor listings require by default
+    pub fn with_min_corridor_volume_usd(mut self, min_volume_usd: f64) -> Self {
+        self.min_corridor_volume_usd = min_volume_usd;
+        self
+    }
+
+    /// Default volume filter of corridor listings
+    pub fn min_corridor_volume_usd(&self) -> f64 {
+        self.min_corridor_volume_usd
+    }
+
+    /// Run a heavy read on its own connection under the query timeout, cut
+    /// short to what is left of the current request's budget
+    async fn timed<T, E, F, Fut>(&self, query: &'static str, run: F) -> Result<T>
+    where
+        F: FnOnce(PoolConnection<Sqlite>) -> Fut,
+        Fut: std::future::Future<Output = std::result::Result<T, E>>,
+        E: Into<anyhow::Error>,
+    {
+        let remaining = match remaining_budget() {
+            Some(remaining) if remaining < self.query_timeout => remaining,
+            _ => return with_query_timeout(query, self.query_timeout, &self.pool, run).await,
+        };
+        if remaining.is_zero() {
+            tracing::warn!("Skipping query '{}': request deadline exceeded", query);
+            return Err(DeadlineExceeded { query }.into());
+        }
+
+        with_query_timeout(query, remaining, &self.pool, run)
+            .await
+            .map_err(|e| match e.downcast_ref::<QueryTimeout>() {
+                Some(_) => DeadlineExceeded { query }.into(),
+                None => e,
+            })
+    }
+
+    /// Run a write, retrying it while SQLite reports the database busy or
+    /// locked. `op` builds the statement afresh for every attempt.
+    async fn retrying<T, E, F, Fut>(&self, operation: &'static str, op: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = std::result::Result<T, E>>,
+        E: Into<anyhow::Error>,
+    {
+        retry_transient(&self.retry_policy, operation, op).await
+    }
+
     pub fn pool(&self) -> &SqlitePool {
         &self.pool
     }
@@ -133,6 +258,27 @@ impl Database {
         crate::db::aggregates::CorridorAggregates::new(self.pool.clone())
     }
 
+    /// Round-trip a `SELECT 1` and return its latency.
+    ///
+    /// Bounded by `HEALTH_CHECK_TIMEOUT` so a hung connection fails the check
+    /// instead of stalling the caller.
+    pub async fn health_check(&self) -> Result<Duration> {
+        let start = Instant::now();
+        tokio::time::timeout(
+            HEALTH_CHECK_TIMEOUT,
+            sqlx::query("SELECT 1").execute(&self.pool),
+        )
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "Database health check timed out after {:?}",
+                HEALTH_CHECK_TIMEOUT
+            )
+        })??;
+
+        Ok(start.elapsed())
+    }
+
     /// Get connection pool metrics
     pub fn pool_metrics(&self) -> PoolMetrics {
         PoolMetrics {
@@ -144,23 +290,57 @@ impl Database {
     // Anchor operations
     pub async fn create_anchor(&self, req: CreateAnchorRequest) -> Result<Anchor> {
         let id = Uuid::new_v4().to_string();
-        let anchor = sqlx::query_as::<_, Anchor>(
-            r#"
-            INSERT INTO anchors (id, name, stellar_account, home_domain)
-            VALUES ($1, $2, $3, $4)
-            RETURNING *
-            "#,
-        )
-        .bind(id)
-        .bind(&req.name)
-        .bind(&req.stellar_account)
-        .bind(&req.home_domain)
-        .fetch_one(&self.pool)
-        .await?;
+        let anchor = self
+            .retrying("create_anchor", || {
+                sqlx::query_as::<_, Anchor>(
+                    r#"
+                    INSERT INTO anchors (id, name, stellar_account, home_domain)
+                    VALUES ($1, $2, $3, $4)
+                    RETURNING *
+                    "#,
+                )
+                .bind(&id)
+                .bind(&req.name)
+                .bind(&req.stellar_account)
+                .bind(&req.home_domain)
+                .fetch_one(&self.pool)
+            })
+            .await?;
 
         Ok(anchor)
     }
 
+    /// Insert anchors in a single transaction. An entry is `None` when its
+    /// stellar account is already registered.
+    pub async fn create_anchors_in_transaction(
+        &self,
+        reqs: &[CreateAnchorRequest],
+    ) -> Result<Vec<Option<Anchor>>> {
+        let mut tx = self.pool.begin().await?;
+        let mut created = Vec::with_capacity(reqs.len());
+
+        for req in reqs {
+            let anchor = sqlx::query_as::<_, Anchor>(
+                r#"
+                INSERT INTO anchors (id, name, stellar_account, home_domain)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (stellar_account) DO NOTHING
+                RETURNING *
+                "#,
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(&req.name)
+            .bind(&req.stellar_account)
+            .bind(&req.home_domain)
+            .fetch_optional(&mut *tx)
+            .await?;
+            created.push(anchor);
+        }
+
+        tx.commit().await?;
+        Ok(created)
+    }
+
     pub async fn get_anchor_by_id(&self, id: Uuid) -> Result<Option<Anchor>> {
         let anchor = sqlx::query_as::<_, Anchor>(
             r#"
@@ -174,6 +354,112 @@ impl Database {
         Ok(anchor)
     }
 
+    pub async fn set_anchor_domain_verified(&self, id: Uuid, verified: bool) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE anchors
+            SET domain_verified = $1, updated_at = $2
+            WHERE id = $3
+            "#,
+        )
+        .bind(verified)
+        .bind(Utc::now())
+        .bind(id.to_string())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn set_anchor_domain_check_failures(&self, id: Uuid, failures: i64) -> Result<()> {
+        sqlx::query("UPDATE anchors SET domain_check_failures = $1 WHERE id = $2")
+            .bind(failures)
+            .bind(id.to_string())
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Anchors with a home domain to verify
+    pub async fn list_anchors_with_home_domain(&self) -> Result<Vec<Anchor>> {
+        let anchors = sqlx::query_as::<_, Anchor>(
+            r#"
+            SELECT * FROM anchors
+            WHERE home_domain IS NOT NULL AND TRIM(home_domain) != ''
+            ORDER BY id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(anchors)
+    }
+
+    /// Store the stellar.toml metadata of an anchor, replacing what was there
+    pub async fn upsert_anchor_metadata(
+        &self,
+        anchor_id: Uuid,
+        metadata: &crate::services::stellar_toml::AnchorMetadata,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO anchor_metadata (
+                anchor_id, organization_name, organization_logo, documentation_url,
+                supported_currencies, fetched_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (anchor_id) DO UPDATE SET
+                organization_name = excluded.organization_name,
+                organization_logo = excluded.organization_logo,
+                documentation_url = excluded.documentation_url,
+                supported_currencies = excluded.supported_currencies,
+                fetched_at = excluded.fetched_at
+            "#,
+        )
+        .bind(anchor_id.to_string())
+        .bind(&metadata.organization_name)
+        .bind(&metadata.organization_logo)
+        .bind(&metadata.documentation_url)
+        .bind(serde_json::to_string(&metadata.supported_currencies)?)
+        .bind(metadata.fetched_at)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Stored stellar.toml metadata of an anchor, if it was ever enriched
+    pub async fn get_anchor_metadata(
+        &self,
+        anchor_id: Uuid,
+    ) -> Result<Option<crate::services::stellar_toml::AnchorMetadata>> {
+        let row: Option<(Option<String>, Option<String>, Option<String>, String, i64)> =
+            sqlx::query_as(
+                r#"
+                SELECT organization_name, organization_logo, documentation_url,
+                       supported_currencies, fetched_at
+                FROM anchor_metadata WHERE anchor_id = $1
+                "#,
+            )
+            .bind(anchor_id.to_string())
+            .fetch_optional(&self.pool)
+            .await?;
+
+        row.map(
+            |(organization_name, organization_logo, documentation_url, currencies, fetched_at)| {
+                Ok(crate::services::stellar_toml::AnchorMetadata {
+                    organization_name,
+                    organization_logo,
+                    documentation_url,
+                    supported_currencies: serde_json::from_str(&currencies)?,
+                    fetched_at,
+                })
+            },
+        )
+        .transpose()
+    }
+
     pub async fn get_anchor_by_stellar_account(
         &self,
         stellar_account: &str,
@@ -190,19 +476,27 @@ impl Database {
         Ok(anchor)
     }
 
+    /// Anchors by reliability, most recently updated first among equals.
+    ///
+    /// `updated_at` holds both `CURRENT_TIMESTAMP` and RFC 3339 text, so it is
+    /// compared through `julianday`; `id` breaks any remaining tie so pages
+    /// never overlap or skip rows.
     pub async fn list_anchors(&self, limit: i64, offset: i64) -> Result<Vec<Anchor>> {
         let start = Instant::now();
-        let anchors = sqlx::query_as::<_, Anchor>(
+        let query = sqlx::query_as::<_, Anchor>(
             r#"
             SELECT * FROM anchors
-            ORDER BY reliability_score DESC, updated_at DESC
+            ORDER BY reliability_score DESC, julianday(updated_at) DESC, id DESC
             LIMIT $1 OFFSET $2
             "#,
         )
         .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await?;
+        .bind(offset);
+        let anchors = self
+            .timed("list_anchors", |mut conn| async move {
+                query.fetch_all(&mut *conn).await
+            })
+            .await?;
 
         crate::observability::metrics::observe_db_query(
             "list_anchors",
@@ -212,6 +506,36 @@ impl Database {
         Ok(anchors)
     }
 
+    /// Anchors following `cursor` in `list_anchors` order; keyset
+    /// pagination over the full sort key
+    pub async fn list_anchors_after(
+        &self,
+        cursor: &AnchorCursor,
+        limit: i64,
+    ) -> Result<Vec<Anchor>> {
+        let query = sqlx::query_as::<_, Anchor>(
+            r#"
+            SELECT * FROM anchors
+            WHERE reliability_score < $1
+               OR (reliability_score = $1 AND julianday(updated_at) < julianday($2))
+               OR (reliability_score = $1 AND julianday(updated_at) = julianday($2) AND id < $3)
+            ORDER BY reliability_score DESC, julianday(updated_at) DESC, id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(cursor.reliability_score)
+        .bind(cursor.updated_at)
+        .bind(&cursor.id)
+        .bind(limit);
+        let anchors = self
+            .timed("list_anchors_after", |mut conn| async move {
+                query.fetch_all(&mut *conn).await
+            })
+            .await?;
+
+        Ok(anchors)
+    }
+
     pub async fn update_anchor_metrics(
         &self,
         anchor_id: Uuid,
@@ -230,32 +554,35 @@ impl Database {
         );
 
         // Update anchor
-        let anchor = sqlx::query_as::<_, Anchor>(
-            r#"
-            UPDATE anchors
-            SET total_transactions = $1,
-                successful_transactions = $2,
-                failed_transactions = $3,
-                avg_settlement_time_ms = $4,
-                reliability_score = $5,
-                status = $6,
-                total_volume_usd = COALESCE($7, total_volume_usd),
-                updated_at = $8
-            WHERE id = $9
-            RETURNING *
-            "#,
-        )
-        .bind(total_transactions)
-        .bind(successful_transactions)
-        .bind(failed_transactions)
-        .bind(avg_settlement_time_ms.unwrap_or(0))
-        .bind(metrics.reliability_score)
-        .bind(metrics.status.as_str())
-        .bind(volume_usd.unwrap_or(0.0))
-        .bind(Utc::now())
-        .bind(anchor_id.to_string())
-        .fetch_one(&self.pool)
-        .await?;
+        let anchor = self
+            .retrying("update_anchor_metrics", || {
+                sqlx::query_as::<_, Anchor>(
+                    r#"
+                    UPDATE anchors
+                    SET total_transactions = $1,
+                        successful_transactions = $2,
+                        failed_transactions = $3,
+                        avg_settlement_time_ms = $4,
+                        reliability_score = $5,
+                        status = $6,
+                        total_volume_usd = COALESCE($7, total_volume_usd),
+                        updated_at = $8
+                    WHERE id = $9
+                    RETURNING *
+                    "#,
+                )
+                .bind(total_transactions)
+                .bind(successful_transactions)
+                .bind(failed_transactions)
+                .bind(avg_settlement_time_ms.unwrap_or(0))
+                .bind(metrics.reliability_score)
+                .bind(metrics.status.as_str())
+                .bind(volume_usd.unwrap_or(0.0))
+                .bind(Utc::now())
+                .bind(anchor_id.to_string())
+                .fetch_one(&self.pool)
+            })
+            .await?;
 
         // Record metrics history
         self.record_anchor_metrics_history(AnchorMetricsParams {
@@ -302,6 +629,31 @@ impl Database {
         Ok(asset)
     }
 
+    /// Add an asset to an anchor unless the asset is already registered,
+    /// under this anchor or another. Returns whether a row was inserted.
+    pub async fn create_asset_if_missing(
+        &self,
+        anchor_id: Uuid,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO assets (id, anchor_id, asset_code, asset_issuer)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (asset_code, asset_issuer) DO NOTHING
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(anchor_id.to_string())
+        .bind(asset_code)
+        .bind(asset_issuer)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn get_assets_by_anchor(&self, anchor_id: Uuid) -> Result<Vec<Asset>> {
         let assets = sqlx::query_as::<_, Asset>(
             r#"
@@ -371,30 +723,32 @@ impl Database {
 
     // Update anchor metrics from RPC ingestion
     pub async fn update_anchor_from_rpc(&self, params: AnchorRpcUpdate) -> Result<()> {
-        sqlx::query(
-            r#"
-            UPDATE anchors
-            SET total_transactions = $1,
-                successful_transactions = $2,
-                failed_transactions = $3,
-                total_volume_usd = $4,
-                avg_settlement_time_ms = $5,
-                reliability_score = $6,
-                status = $7,
-                updated_at = $8
-            WHERE stellar_account = $9
-            "#,
-        )
-        .bind(params.total_transactions)
-        .bind(params.successful_transactions)
-        .bind(params.failed_transactions)
-        .bind(params.total_volume_usd)
-        .bind(params.avg_settlement_time_ms)
-        .bind(params.reliability_score)
-        .bind(&params.status)
-        .bind(Utc::now())
-        .bind(&params.stellar_account)
-        .execute(&self.pool)
+        self.retrying("update_anchor_from_rpc", || {
+            sqlx::query(
+                r#"
+                UPDATE anchors
+                SET total_transactions = $1,
+                    successful_transactions = $2,
+                    failed_transactions = $3,
+                    total_volume_usd = $4,
+                    avg_settlement_time_ms = $5,
+                    reliability_score = $6,
+                    status = $7,
+                    updated_at = $8
+                WHERE stellar_account = $9
+                "#,
+            )
+            .bind(params.total_transactions)
+            .bind(params.successful_transactions)
+            .bind(params.failed_transactions)
+            .bind(params.total_volume_usd)
+            .bind(params.avg_settlement_time_ms)
+            .bind(params.reliability_score)
+            .bind(&params.status)
+            .bind(Utc::now())
+            .bind(&params.stellar_account)
+            .execute(&self.pool)
+        })
         .await?;
 
         Ok(())
@@ -406,6 +760,8 @@ impl Database {
         params: AnchorMetricsParams,
     ) -> Result<AnchorMetricsHistory> {
         let id = Uuid::new_v4().to_string();
+        let anchor_id = params.anchor_id.to_string();
+        let mut tx = self.pool.begin().await?;
         let history = sqlx::query_as::<_, AnchorMetricsHistory>(
             r#"
             INSERT INTO anchor_metrics_history (
@@ -418,7 +774,7 @@ impl Database {
             "#,
         )
         .bind(id)
-        .bind(params.anchor_id.to_string())
+        .bind(&anchor_id)
         .bind(Utc::now())
         .bind(params.success_rate)
         .bind(params.failure_rate)
@@ -428,9 +784,27 @@ impl Database {
         .bind(params.failed_transactions)
         .bind(params.avg_settlement_time_ms.unwrap_or(0))
         .bind(params.volume_usd.unwrap_or(0.0))
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await?;
 
+        // Keep only the newest rows so history stays bounded
+        sqlx::query(
+            r#"
+            DELETE FROM anchor_metrics_history
+            WHERE anchor_id = $1 AND id NOT IN (
+                SELECT id FROM anchor_metrics_history
+                WHERE anchor_id = $1
+                ORDER BY timestamp DESC, rowid DESC
+                LIMIT $2
+            )
+            "#,
+        )
+        .bind(&anchor_id)
+        .bind(self.metrics_history_limit)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
         Ok(history)
     }
 
@@ -439,7 +813,7 @@ impl Database {
         anchor_id: Uuid,
         limit: i64,
     ) -> Result<Vec<AnchorMetricsHistory>> {
-        let history = sqlx::query_as::<_, AnchorMetricsHistory>(
+        let query = sqlx::query_as::<_, AnchorMetricsHistory>(
             r#"
             SELECT * FROM anchor_metrics_history
             WHERE anchor_id = $1
@@ -448,9 +822,12 @@ impl Database {
             "#,
         )
         .bind(anchor_id.to_string())
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
+        .bind(limit);
+        let history = self
+            .timed("get_anchor_metrics_history", |mut conn| async move {
+                query.fetch_all(&mut *conn).await
+            })
+            .await?;
 
         Ok(history)
     }
@@ -476,7 +853,12 @@ impl Database {
         &self,
         req: crate::models::CreateCorridorRequest,
     ) -> Result<crate::models::corridor::Corridor> {
-        let corridor = crate::models::corridor::Corridor::new(
+        let build = if req.directional {
+            crate::models::corridor::Corridor::directional
+        } else {
+            crate::models::corridor::Corridor::new
+        };
+        let corridor = build(
             req.source_asset_code,
             req.source_asset_issuer,
             req.dest_asset_code,
@@ -488,11 +870,11 @@ impl Database {
             r#"
             INSERT INTO corridors (
                 id, source_asset_code, source_asset_issuer,
-                destination_asset_code, destination_asset_issuer
+                destination_asset_code, destination_asset_issuer, is_directional
             )
-            VALUES ($1, $2, $3, $4, $5)
+            VALUES ($1, $2, $3, $4, $5, $6)
             ON CONFLICT (source_asset_code, source_asset_issuer, destination_asset_code, destination_asset_issuer)
-            DO UPDATE SET updated_at = CURRENT_TIMESTAMP
+            DO UPDATE SET status = 'active', deleted_at = NULL, updated_at = CURRENT_TIMESTAMP
             "#,
         )
         .bind(Uuid::new_v4().to_string())
@@ -500,6 +882,7 @@ impl Database {
         .bind(&corridor.asset_a_issuer)
         .bind(&corridor.asset_b_code)
         .bind(&corridor.asset_b_issuer)
+        .bind(corridor.directional)
         .execute(&self.pool)
         .await?;
 
@@ -511,28 +894,88 @@ impl Database {
         limit: i64,
         offset: i64,
     ) -> Result<Vec<crate::models::corridor::Corridor>> {
-        let start = Instant::now();
-        let records = sqlx::query_as::<_, CorridorRecord>(
-            r#"
-            SELECT * FROM corridors ORDER BY reliability_score DESC LIMIT $1 OFFSET $2
-            "#,
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await?;
+        self.list_corridors_min_volume(limit, offset, None).await
+    }
 
-        let corridors = records
-            .into_iter()
-            .map(|r| {
-                crate::models::corridor::Corridor::new(
-                    r.source_asset_code,
-                    r.source_asset_issuer,
-                    r.destination_asset_code,
-                    r.destination_asset_issuer,
+    /// Live corridors whose latest daily volume is at least `min_volume_usd`;
+    /// corridors without metrics count as zero volume. `None` lists every
+    /// corridor.
+    ///
+    /// Volumes are matched on `Corridor::to_string_key`, the key metrics are
+    /// stored under, so the filter pages in Rust rather than in SQL.
+    pub async fn list_corridors_min_volume(
+        &self,
+        limit: i64,
+        offset: i64,
+        min_volume_usd: Option<f64>,
+    ) -> Result<Vec<crate::models::corridor::Corridor>> {
+        let start = Instant::now();
+        let corridors = match min_volume_usd {
+            None => {
+                let query = sqlx::query_as::<_, CorridorRecord>(
+                    r#"
+                    SELECT * FROM corridors
+                    WHERE deleted_at IS NULL
+                    ORDER BY reliability_score DESC
+                    LIMIT $1 OFFSET $2
+                    "#,
                 )
-            })
-            .collect::<Vec<_>>();
+                .bind(limit)
+                .bind(offset);
+                self.timed("list_corridors", |mut conn| async move {
+                    query.fetch_all(&mut *conn).await
+                })
+                .await?
+                .iter()
+                .map(CorridorRecord::get_corridor)
+                .collect()
+            }
+            Some(min_volume_usd) => {
+                let records = sqlx::query_as::<_, CorridorRecord>(
+                    r#"
+                    SELECT * FROM corridors
+                    WHERE deleted_at IS NULL
+                    ORDER BY reliability_score DESC
+                    "#,
+                );
+                let records = self
+                    .timed("list_corridors", |mut conn| async move {
+                        records.fetch_all(&mut *conn).await
+                    })
+                    .await?;
+                let latest_volumes = sqlx::query_as::<_, (String, Option<f64>)>(
+                    r#"
+                    SELECT m.corridor_key, m.volume_usd FROM corridor_metrics m
+                    WHERE m.date = (
+                        SELECT MAX(date) FROM corridor_metrics
+                        WHERE corridor_key = m.corridor_key
+                    )
+                    "#,
+                );
+                let latest_volumes: std::collections::HashMap<String, Option<f64>> = self
+                    .timed("list_corridors", |mut conn| async move {
+                        latest_volumes.fetch_all(&mut *conn).await
+                    })
+                    .await?
+                    .into_iter()
+                    .collect();
+
+                records
+                    .iter()
+                    .map(CorridorRecord::get_corridor)
+                    .filter(|corridor| {
+                        let volume = latest_volumes
+                            .get(&corridor.to_string_key())
+                            .copied()
+                            .flatten()
+                            .unwrap_or(0.0);
+                        volume >= min_volume_usd
+                    })
+                    .skip(offset.max(0) as usize)
+                    .take(limit.max(0) as usize)
+                    .collect()
+            }
+        };
         crate::observability::metrics::observe_db_query(
             "list_corridors",
             "success",
@@ -541,27 +984,64 @@ impl Database {
         Ok(corridors)
     }
 
+    /// Live corridors with the highest reliability scores
+    pub async fn list_top_corridors(&self, limit: i64) -> Result<Vec<CorridorRecord>> {
+        let query = sqlx::query_as::<_, CorridorRecord>(
+            r#"
+            SELECT * FROM corridors
+            WHERE deleted_at IS NULL
+            ORDER BY reliability_score DESC, id
+            LIMIT $1
+            "#,
+        )
+        .bind(limit);
+        let corridors = self
+            .timed("list_top_corridors", |mut conn| async move {
+                query.fetch_all(&mut *conn).await
+            })
+            .await?;
+
+        Ok(corridors)
+    }
+
     pub async fn get_corridor_by_id(
         &self,
         id: Uuid,
     ) -> Result<Option<crate::models::corridor::Corridor>> {
         let record = sqlx::query_as::<_, CorridorRecord>(
             r#"
-            SELECT * FROM corridors WHERE id = $1
+            SELECT * FROM corridors WHERE id = $1 AND deleted_at IS NULL
             "#,
         )
         .bind(id.to_string())
         .fetch_optional(&self.pool)
         .await?;
 
-        Ok(record.map(|r| {
-            crate::models::corridor::Corridor::new(
-                r.source_asset_code,
-                r.source_asset_issuer,
-                r.destination_asset_code,
-                r.destination_asset_issuer,
-            )
-        }))
+        Ok(record.map(|r| r.get_corridor()))
+    }
+
+    /// Soft-delete a corridor, leaving it out of listings and snapshots.
+    /// Returns the deleted corridor, or `None` if there is no live corridor
+    /// with this id. Creating the corridor again restores it.
+    pub async fn delete_corridor(
+        &self,
+        id: Uuid,
+    ) -> Result<Option<crate::models::corridor::Corridor>> {
+        let record = sqlx::query_as::<_, CorridorRecord>(
+            r#"
+            UPDATE corridors
+            SET status = 'deleted',
+                deleted_at = CURRENT_TIMESTAMP,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE id = $1 AND deleted_at IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(record.map(|r| r.get_corridor()))
     }
 
     pub async fn update_corridor_metrics(
@@ -583,12 +1063,7 @@ impl Database {
         .fetch_one(&self.pool)
         .await?;
 
-        Ok(crate::models::corridor::Corridor::new(
-            record.source_asset_code,
-            record.source_asset_issuer,
-            record.destination_asset_code,
-            record.destination_asset_issuer,
-        ))
+        Ok(record.get_corridor())
     }
 
     // Generic Metric operations
@@ -600,25 +1075,59 @@ impl Database {
         entity_type: Option<String>,
     ) -> Result<MetricRecord> {
         let id = Uuid::new_v4().to_string();
-        let metric = sqlx::query_as::<_, MetricRecord>(
-            r#"
-            INSERT INTO metrics (id, name, value, entity_id, entity_type, timestamp)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            RETURNING *
-            "#,
-        )
-        .bind(id)
-        .bind(name)
-        .bind(value)
-        .bind(entity_id)
-        .bind(entity_type)
-        .bind(Utc::now())
-        .fetch_one(&self.pool)
-        .await?;
+        let metric = self
+            .retrying("record_metric", || {
+                sqlx::query_as::<_, MetricRecord>(
+                    r#"
+                    INSERT INTO metrics (id, name, value, entity_id, entity_type, timestamp)
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    RETURNING *
+                    "#,
+                )
+                .bind(&id)
+                .bind(name)
+                .bind(value)
+                .bind(&entity_id)
+                .bind(&entity_type)
+                .bind(Utc::now())
+                .fetch_one(&self.pool)
+            })
+            .await?;
 
         Ok(metric)
     }
 
+    /// Insert buffered metrics in a single transaction, returning the rows
+    /// written
+    pub async fn record_metrics(&self, metrics: &[PendingMetric]) -> Result<u64> {
+        if metrics.is_empty() {
+            return Ok(0);
+        }
+
+        self.retrying("record_metrics", || async move {
+            let mut tx = self.pool.begin().await?;
+            for metric in metrics {
+                sqlx::query(
+                    r#"
+                    INSERT INTO metrics (id, name, value, entity_id, entity_type, timestamp)
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    "#,
+                )
+                .bind(Uuid::new_v4().to_string())
+                .bind(&metric.name)
+                .bind(metric.value)
+                .bind(&metric.entity_id)
+                .bind(&metric.entity_type)
+                .bind(metric.timestamp)
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+            Ok::<_, sqlx::Error>(metrics.len() as u64)
+        })
+        .await
+    }
+
     // Snapshot operations
     pub async fn create_snapshot(
         &self,
@@ -629,22 +1138,26 @@ impl Database {
         epoch: Option<i64>,
     ) -> Result<SnapshotRecord> {
         let id = Uuid::new_v4().to_string();
-        let snapshot = sqlx::query_as::<_, SnapshotRecord>(
-            r#"
-            INSERT INTO snapshots (id, entity_id, entity_type, data, hash, epoch, timestamp)
-            VALUES ($1, $2, $3, $4, $5, $6, $7)
-            RETURNING *
-            "#,
-        )
-        .bind(id)
-        .bind(entity_id)
-        .bind(entity_type)
-        .bind(data.to_string())
-        .bind(hash)
-        .bind(epoch)
-        .bind(Utc::now())
-        .fetch_one(&self.pool)
-        .await?;
+        let data = data.to_string();
+        let snapshot = self
+            .retrying("create_snapshot", || {
+                sqlx::query_as::<_, SnapshotRecord>(
+                    r#"
+                    INSERT INTO snapshots (id, entity_id, entity_type, data, hash, epoch, timestamp)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7)
+                    RETURNING *
+                    "#,
+                )
+                .bind(&id)
+                .bind(entity_id)
+                .bind(entity_type)
+                .bind(&data)
+                .bind(&hash)
+                .bind(epoch)
+                .bind(Utc::now())
+                .fetch_one(&self.pool)
+            })
+            .await?;
 
         Ok(snapshot)
     }
@@ -662,24 +1175,97 @@ impl Database {
         Ok(snapshot)
     }
 
-    pub async fn list_snapshots(&self, limit: i64, offset: i64) -> Result<Vec<SnapshotRecord>> {
-        let snapshots = sqlx::query_as::<_, SnapshotRecord>(
+    /// Snapshots newest epoch first, optionally of one entity type
+    pub async fn list_snapshots(
+        &self,
+        entity_type: Option<&str>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<SnapshotRecord>> {
+        let query = sqlx::query_as::<_, SnapshotRecord>(
             r#"
             SELECT * FROM snapshots
-            WHERE epoch IS NOT NULL
-            ORDER BY epoch DESC
-            LIMIT $1 OFFSET $2
+            WHERE epoch IS NOT NULL AND ($1 IS NULL OR entity_type = $1)
+            ORDER BY epoch DESC, id DESC
+            LIMIT $2 OFFSET $3
             "#,
         )
+        .bind(entity_type)
         .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await?;
+        .bind(offset);
+        let snapshots = self
+            .timed("list_snapshots", |mut conn| async move {
+                query.fetch_all(&mut *conn).await
+            })
+            .await?;
+
+        Ok(snapshots)
+    }
+
+    /// Snapshots after `cursor` in `list_snapshots` order; keyset pagination
+    /// on `(epoch, id)`, so epochs holding several snapshots page correctly
+    pub async fn list_snapshots_after(
+        &self,
+        entity_type: Option<&str>,
+        cursor: &SnapshotCursor,
+        limit: i64,
+    ) -> Result<Vec<SnapshotRecord>> {
+        let query = sqlx::query_as::<_, SnapshotRecord>(
+            r#"
+            SELECT * FROM snapshots
+            WHERE epoch IS NOT NULL AND ($1 IS NULL OR entity_type = $1)
+              AND (epoch < $2 OR (epoch = $2 AND id < $3))
+            ORDER BY epoch DESC, id DESC
+            LIMIT $4
+            "#,
+        )
+        .bind(entity_type)
+        .bind(cursor.epoch)
+        .bind(cursor.id.as_str())
+        .bind(limit);
+        let snapshots = self
+            .timed("list_snapshots_after", |mut conn| async move {
+                query.fetch_all(&mut *conn).await
+            })
+            .await?;
 
         Ok(snapshots)
     }
 
     // Ingestion methods
+    pub async fn count_entities(&self) -> Result<EntityCounts> {
+        let query = sqlx::query_as::<_, EntityCounts>(
+            r#"
+            SELECT
+                (SELECT COUNT(*) FROM anchors) AS anchors,
+                (SELECT COUNT(*) FROM corridors WHERE deleted_at IS NULL) AS corridors,
+                (SELECT COUNT(*) FROM assets) AS assets,
+                (SELECT COUNT(*) FROM snapshots WHERE epoch IS NOT NULL) AS snapshots
+            "#,
+        );
+        let counts = self
+            .timed("count_entities", |mut conn| async move {
+                query.fetch_one(&mut *conn).await
+            })
+            .await?;
+
+        Ok(counts)
+    }
+
+    /// Close time of the most recently ingested ledger, if any
+    pub async fn latest_ledger_close_time(&self) -> Result<Option<DateTime<Utc>>> {
+        let query = sqlx::query_scalar::<_, String>(
+            "SELECT close_time FROM ledgers ORDER BY sequence DESC LIMIT 1",
+        );
+        let close_time = self
+            .timed("latest_ledger_close_time", |mut conn| async move {
+                query.fetch_optional(&mut *conn).await
+            })
+            .await?;
+
+        Ok(close_time.map(|t| parse_timestamp(&t)).transpose()?)
+    }
+
     pub async fn get_ingestion_cursor(&self, task_name: &str) -> Result<Option<String>> {
         let state = sqlx::query_as::<_, crate::models::IngestionState>(
             r#"
@@ -694,19 +1280,21 @@ impl Database {
     }
 
     pub async fn update_ingestion_cursor(&self, task_name: &str, last_cursor: &str) -> Result<()> {
-        sqlx::query(
-            r#"
-            INSERT INTO ingestion_state (task_name, last_cursor, updated_at)
-            VALUES ($1, $2, $3)
-            ON CONFLICT (task_name) DO UPDATE SET
-                last_cursor = EXCLUDED.last_cursor,
-                updated_at = EXCLUDED.updated_at
-            "#,
-        )
-        .bind(task_name)
-        .bind(last_cursor)
-        .bind(Utc::now())
-        .execute(&self.pool)
+        self.retrying("update_ingestion_cursor", || {
+            sqlx::query(
+                r#"
+                INSERT INTO ingestion_state (task_name, last_cursor, updated_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (task_name) DO UPDATE SET
+                    last_cursor = EXCLUDED.last_cursor,
+                    updated_at = EXCLUDED.updated_at
+                "#,
+            )
+            .bind(task_name)
+            .bind(last_cursor)
+            .bind(Utc::now())
+            .execute(&self.pool)
+        })
         .await?;
 
         Ok(())
@@ -715,13 +1303,27 @@ impl Database {
     pub async fn save_payments(&self, payments: Vec<crate::models::PaymentRecord>) -> Result<()> {
         let start = Instant::now();
         for payment in payments {
+            // Stored like the received asset: NULL code and issuer for the
+            // native asset, and the received asset when none is given
+            let (source_asset_code, source_asset_issuer) =
+                if payment.source_asset_issuer == NATIVE_ASSET_ISSUER {
+                    (None, None)
+                } else if payment.source_asset_code.is_empty() {
+                    (payment.asset_code.clone(), payment.asset_issuer.clone())
+                } else {
+                    (
+                        Some(payment.source_asset_code.clone()),
+                        Some(payment.source_asset_issuer.clone()),
+                    )
+                };
             sqlx::query(
                 r#"
                 INSERT INTO payments (
                     id, transaction_hash, source_account, destination_account,
+                    source_asset_code, source_asset_issuer,
                     asset_type, asset_code, asset_issuer, amount, created_at
                 )
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                 ON CONFLICT (id) DO NOTHING
                 "#,
             )
@@ -729,11 +1331,13 @@ impl Database {
             .bind(&payment.transaction_hash)
             .bind(&payment.source_account)
             .bind(&payment.destination_account)
+            .bind(source_asset_code)
+            .bind(source_asset_issuer)
             .bind(&payment.asset_type)
             .bind(&payment.asset_code)
             .bind(&payment.asset_issuer)
             .bind(payment.amount)
-            .bind(payment.created_at)
+            .bind(format_timestamp(&payment.created_at))
             .execute(&self.pool)
             .await?;
         }
@@ -745,9 +1349,63 @@ impl Database {
         Ok(())
     }
 
+    /// Stored payments of the corridor `source -> destination` created at or
+    /// after `since`, newest first, following `after` when given.
+    ///
+    /// A payment belongs to the corridor from the asset it was sent in to
+    /// the asset it delivered; both are the same unless it is a path payment.
+    pub async fn list_corridor_payments(
+        &self,
+        source: &AssetId,
+        destination: &AssetId,
+        since: DateTime<Utc>,
+        after: Option<&PaymentCursor>,
+        limit: i64,
+    ) -> Result<Vec<CorridorPayment>> {
+        // Native assets are stored with NULL code and issuer, which `IS`
+        // matches while still using the corridor index
+        let stored = |asset: &AssetId| {
+            (!asset.is_native()).then(|| (asset.code.clone(), asset.issuer.clone()))
+        };
+        let (source_code, source_issuer) = stored(source).unzip();
+        let (destination_code, destination_issuer) = stored(destination).unzip();
+
+        let query = sqlx::query_as::<_, CorridorPayment>(
+            r#"
+            SELECT id, transaction_hash, source_account, destination_account,
+                   source_asset_code, source_asset_issuer,
+                   asset_type, asset_code, asset_issuer, amount, created_at
+            FROM payments
+            WHERE source_asset_code IS $1 AND source_asset_issuer IS $2
+              AND asset_code IS $3 AND asset_issuer IS $4
+              AND julianday(created_at) >= julianday($5)
+              AND ($6 IS NULL
+                   OR julianday(created_at) < julianday($6)
+                   OR (julianday(created_at) = julianday($6) AND id < $7))
+            ORDER BY julianday(created_at) DESC, id DESC
+            LIMIT $8
+            "#,
+        )
+        .bind(source_code)
+        .bind(source_issuer)
+        .bind(destination_code)
+        .bind(destination_issuer)
+        .bind(format_timestamp(&since))
+        .bind(after.map(|cursor| cursor.created_at))
+        .bind(after.map(|cursor| cursor.id.as_str()))
+        .bind(limit);
+        let payments = self
+            .timed("list_corridor_payments", |mut conn| async move {
+                query.fetch_all(&mut *conn).await
+            })
+            .await?;
+
+        Ok(payments)
+    }
+
     // Aggregation methods
-    pub fn aggregation_db(&self) -> crate::db::aggregation::AggregationDb {
-        crate::db::aggregation::AggregationDb::new(self.pool.clone())
+    pub fn aggregation_db(&self) -> AggregationDb {
+        AggregationDb::new(self.pool.clone())
     }
 
     pub async fn fetch_payments_by_timerange(
@@ -756,9 +1414,28 @@ impl Database {
         end_time: chrono::DateTime<chrono::Utc>,
         limit: i64,
     ) -> Result<Vec<crate::models::corridor::PaymentRecord>> {
-        self.aggregation_db()
-            .fetch_payments_by_timerange(start_time, end_time, limit)
-            .await
+        self.timed("fetch_payments_by_timerange", |mut conn| async move {
+            AggregationDb::fetch_payments_by_timerange(&mut conn, start_time, end_time, limit).await
+        })
+        .await
+    }
+
+    pub async fn fetch_path_payment_failures_by_timerange(
+        &self,
+        start_time: chrono::DateTime<chrono::Utc>,
+        end_time: chrono::DateTime<chrono::Utc>,
+        limit: i64,
+    ) -> Result<Vec<crate::models::corridor::PaymentRecord>> {
+        self.timed(
+            "fetch_path_payment_failures_by_timerange",
+            |mut conn| async move {
+                AggregationDb::fetch_path_payment_failures_by_timerange(
+                    &mut conn, start_time, end_time, limit,
+                )
+                .await
+            },
+        )
+        .await
     }
 
     pub async fn upsert_hourly_corridor_metric(
@@ -770,14 +1447,35 @@ impl Database {
             .await
     }
 
+    pub async fn upsert_corridor_latency_percentiles(
+        &self,
+        corridor_key: &str,
+        percentiles: &crate::models::corridor::LatencyPercentiles,
+    ) -> Result<()> {
+        self.aggregation_db()
+            .upsert_corridor_latency_percentiles(corridor_key, percentiles)
+            .await
+    }
+
+    pub async fn get_corridor_latency_percentiles(
+        &self,
+        corridor_key: &str,
+    ) -> Result<Option<crate::models::corridor::LatencyPercentiles>> {
+        self.timed("get_corridor_latency_percentiles", |mut conn| async move {
+            AggregationDb::get_corridor_latency_percentiles(&mut conn, corridor_key).await
+        })
+        .await
+    }
+
     pub async fn fetch_hourly_metrics_by_timerange(
         &self,
         start_time: chrono::DateTime<chrono::Utc>,
         end_time: chrono::DateTime<chrono::Utc>,
     ) -> Result<Vec<crate::services::aggregation::HourlyCorridorMetrics>> {
-        self.aggregation_db()
-            .fetch_hourly_metrics_by_timerange(start_time, end_time)
-            .await
+        self.timed("fetch_hourly_metrics_by_timerange", |mut conn| async move {
+            AggregationDb::fetch_hourly_metrics_by_timerange(&mut conn, start_time, end_time).await
+        })
+        .await
     }
 
     pub async fn create_aggregation_job(&self, job_id: &str, job_type: &str) -> Result<()> {
@@ -813,22 +1511,65 @@ impl Database {
             .await
     }
 
+    /// Payments counted by muxed analytics, as the CTE `counted`: those at
+    /// or above their asset's dust threshold
+    fn counted_payments(&self) -> QueryBuilder<'static, Sqlite> {
+        let mut query = QueryBuilder::new(
+            "WITH counted AS (SELECT source_account, destination_account FROM payments",
+        );
+        if !self.muxed_dust_thresholds.is_empty() {
+            query.push(" WHERE amount >= CASE");
+            for threshold in self.muxed_dust_thresholds.entries() {
+                if threshold.native {
+                    query.push(" WHEN asset_type = 'native' THEN ");
+                } else {
+                    query
+                        .push(" WHEN asset_type != 'native' AND asset_code = ")
+                        .push_bind(threshold.code.to_string());
+                    if let Some(issuer) = threshold.issuer {
+                        query
+                            .push(" AND asset_issuer = ")
+                            .push_bind(issuer.to_string());
+                    }
+                    query.push(" THEN ");
+                }
+                query.push_bind(threshold.min_amount);
+            }
+            query.push(" ELSE 0 END");
+        }
+        query.push(") ");
+        query
+    }
+
     /// Muxed account analytics: counts and top addresses from payments table.
-    /// Uses M-address detection (starts with 'M', length 69).
+    /// Uses M-address detection (starts with 'M', length 69). Payments below
+    /// their asset's dust threshold are not counted.
     pub async fn get_muxed_analytics(&self, top_limit: i64) -> Result<MuxedAccountAnalytics> {
         use crate::muxed;
         const MUXED_LEN: i64 = 69;
 
-        let total_muxed_payments = sqlx::query_scalar::<_, i64>(
-            r#"
-            SELECT COUNT(*) FROM payments
-            WHERE (source_account LIKE 'M%' AND LENGTH(source_account) = ?1)
-               OR (destination_account LIKE 'M%' AND LENGTH(destination_account) = ?1)
-            "#,
-        )
-        .bind(MUXED_LEN)
-        .fetch_one(&self.pool)
-        .await?;
+        let mut query = self.counted_payments();
+        query
+            .push(
+                r#"
+            SELECT COUNT(*) FROM counted
+            WHERE (source_account LIKE 'M%' AND LENGTH(source_account) = "#,
+            )
+            .push_bind(MUXED_LEN)
+            .push(
+                r#")
+               OR (destination_account LIKE 'M%' AND LENGTH(destination_account) = "#,
+            )
+            .push_bind(MUXED_LEN)
+            .push(")");
+        let total_muxed_payments = self
+            .timed("get_muxed_analytics", |mut conn| async move {
+                query
+                    .build_query_scalar::<i64>()
+                    .fetch_one(&mut *conn)
+                    .await
+            })
+            .await?;
 
         #[derive(sqlx::FromRow)]
         struct AddrCount {
@@ -836,33 +1577,47 @@ impl Database {
             cnt: i64,
         }
 
-        let source_counts: Vec<AddrCount> = sqlx::query_as(
-            r#"
-            SELECT source_account AS addr, COUNT(*) AS cnt FROM payments
-            WHERE source_account LIKE 'M%' AND LENGTH(source_account) = ?1
+        let mut query = self.counted_payments();
+        query
+            .push(
+                r#"
+            SELECT source_account AS addr, COUNT(*) AS cnt FROM counted
+            WHERE source_account LIKE 'M%' AND LENGTH(source_account) = "#,
+            )
+            .push_bind(MUXED_LEN)
+            .push(
+                r#"
             GROUP BY source_account
             ORDER BY cnt DESC
-            LIMIT ?2
-            "#,
-        )
-        .bind(MUXED_LEN)
-        .bind(top_limit)
-        .fetch_all(&self.pool)
-        .await?;
+            LIMIT "#,
+            )
+            .push_bind(top_limit);
+        let source_counts: Vec<AddrCount> = self
+            .timed("get_muxed_analytics", |mut conn| async move {
+                query.build_query_as().fetch_all(&mut *conn).await
+            })
+            .await?;
 
-        let dest_counts: Vec<AddrCount> = sqlx::query_as(
-            r#"
-            SELECT destination_account AS addr, COUNT(*) AS cnt FROM payments
-            WHERE destination_account LIKE 'M%' AND LENGTH(destination_account) = ?1
+        let mut query = self.counted_payments();
+        query
+            .push(
+                r#"
+            SELECT destination_account AS addr, COUNT(*) AS cnt FROM counted
+            WHERE destination_account LIKE 'M%' AND LENGTH(destination_account) = "#,
+            )
+            .push_bind(MUXED_LEN)
+            .push(
+                r#"
             GROUP BY destination_account
             ORDER BY cnt DESC
-            LIMIT ?2
-            "#,
-        )
-        .bind(MUXED_LEN)
-        .bind(top_limit)
-        .fetch_all(&self.pool)
-        .await?;
+            LIMIT "#,
+            )
+            .push_bind(top_limit);
+        let dest_counts: Vec<AddrCount> = self
+            .timed("get_muxed_analytics", |mut conn| async move {
+                query.build_query_as().fetch_all(&mut *conn).await
+            })
+            .await?;
 
         let mut by_addr: std::collections::HashMap<String, (i64, i64)> =
             std::collections::HashMap::new();
@@ -891,18 +1646,31 @@ impl Database {
         top_muxed_by_activity.sort_by(|a, b| b.total_payments.cmp(&a.total_payments));
         top_muxed_by_activity.truncate(top_limit as usize);
 
-        let unique_muxed_addresses = sqlx::query_scalar::<_, i64>(
-            r#"
+        let mut query = self.counted_payments();
+        query
+            .push(
+                r#"
             SELECT COUNT(DISTINCT addr) FROM (
-                SELECT source_account AS addr FROM payments WHERE source_account LIKE 'M%' AND LENGTH(source_account) = ?1
+                SELECT source_account AS addr FROM counted
+                WHERE source_account LIKE 'M%' AND LENGTH(source_account) = "#,
+            )
+            .push_bind(MUXED_LEN)
+            .push(
+                r#"
                 UNION
-                SELECT destination_account AS addr FROM payments WHERE destination_account LIKE 'M%' AND LENGTH(destination_account) = ?1
+                SELECT destination_account AS addr FROM counted
+                WHERE destination_account LIKE 'M%' AND LENGTH(destination_account) = "#,
             )
-            "#,
-        )
-        .bind(MUXED_LEN)
-        .fetch_one(&self.pool)
-        .await?;
+            .push_bind(MUXED_LEN)
+            .push(")");
+        let unique_muxed_addresses = self
+            .timed("get_muxed_analytics", |mut conn| async move {
+                query
+                    .build_query_scalar::<i64>()
+                    .fetch_one(&mut *conn)
+                    .await
+            })
+            .await?;
 
         let base_accounts_with_muxed: Vec<String> = top_muxed_by_activity
             .iter()
@@ -926,12 +1694,16 @@ impl Database {
     // Transaction Builder Methods
     // =========================
 
+    /// Store a transaction for signature collection. Fails with
+    /// `EnvelopeError` unless `xdr` is an envelope from `source_account`
+    /// that `required_signatures` signatures can cover.
     pub async fn create_pending_transaction(
         &self,
         source_account: &str,
         xdr: &str,
         required_signatures: i32,
     ) -> Result<crate::models::PendingTransaction> {
+        validate_envelope(xdr, source_account, required_signatures)?;
         let id = Uuid::new_v4().to_string();
         let status = "pending";
 
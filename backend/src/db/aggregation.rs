@@ -1,7 +1,8 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use sqlx::SqlitePool;
+use sqlx::{SqliteConnection, SqlitePool};
 
+use crate::models::corridor::LatencyPercentiles;
 use crate::services::aggregation::HourlyCorridorMetrics;
 
 pub struct AggregationDb {
@@ -15,7 +16,7 @@ impl AggregationDb {
 
     /// Fetch payments within a time range
     pub async fn fetch_payments_by_timerange(
-        &self,
+        conn: &mut SqliteConnection,
         start_time: DateTime<Utc>,
         end_time: DateTime<Utc>,
         limit: i64,
@@ -41,7 +42,7 @@ impl AggregationDb {
         .bind(start_time.to_rfc3339())
         .bind(end_time.to_rfc3339())
         .bind(limit)
-        .fetch_all(&self.pool)
+        .fetch_all(conn)
         .await
         .context("Failed to fetch payments by timerange")?;
 
@@ -81,6 +82,58 @@ impl AggregationDb {
         Ok(payment_records)
     }
 
+    /// Fetch failed path payments within a time range as unsuccessful payment records
+    pub async fn fetch_path_payment_failures_by_timerange(
+        conn: &mut SqliteConnection,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<crate::models::corridor::PaymentRecord>> {
+        let rows = sqlx::query_as::<_, PathPaymentFailureRow>(
+            r#"
+            SELECT
+                source_asset_code,
+                source_asset_issuer,
+                destination_asset_code,
+                destination_asset_issuer,
+                amount,
+                created_at
+            FROM path_payment_failures
+            WHERE created_at >= ? AND created_at <= ?
+            ORDER BY created_at ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(start_time.to_rfc3339())
+        .bind(end_time.to_rfc3339())
+        .bind(limit)
+        .fetch_all(conn)
+        .await
+        .context("Failed to fetch path payment failures by timerange")?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                let timestamp = DateTime::parse_from_rfc3339(&row.created_at)
+                    .ok()?
+                    .with_timezone(&Utc);
+
+                Some(crate::models::corridor::PaymentRecord {
+                    id: uuid::Uuid::new_v4(),
+                    source_asset_code: row.source_asset_code,
+                    source_asset_issuer: row.source_asset_issuer,
+                    destination_asset_code: row.destination_asset_code,
+                    destination_asset_issuer: row.destination_asset_issuer,
+                    amount: row.amount,
+                    successful: false,
+                    timestamp,
+                    submission_time: None,
+                    confirmation_time: None,
+                })
+            })
+            .collect())
+    }
+
     /// Upsert hourly corridor metric
     pub async fn upsert_hourly_corridor_metric(
         &self,
@@ -150,9 +203,61 @@ impl AggregationDb {
         Ok(())
     }
 
+    /// Replace a corridor's settlement latency percentiles
+    pub async fn upsert_corridor_latency_percentiles(
+        &self,
+        corridor_key: &str,
+        percentiles: &LatencyPercentiles,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO corridor_latency_percentiles (
+                corridor_key, sample_count, p50_ms, p90_ms, p99_ms, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(corridor_key) DO UPDATE SET
+                sample_count = excluded.sample_count,
+                p50_ms = excluded.p50_ms,
+                p90_ms = excluded.p90_ms,
+                p99_ms = excluded.p99_ms,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(corridor_key)
+        .bind(percentiles.sample_count)
+        .bind(percentiles.p50_ms)
+        .bind(percentiles.p90_ms)
+        .bind(percentiles.p99_ms)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await
+        .context("Failed to upsert corridor latency percentiles")?;
+
+        Ok(())
+    }
+
+    /// Settlement latency percentiles of a corridor, if any were recorded
+    pub async fn get_corridor_latency_percentiles(
+        conn: &mut SqliteConnection,
+        corridor_key: &str,
+    ) -> Result<Option<LatencyPercentiles>> {
+        let percentiles = sqlx::query_as::<_, LatencyPercentiles>(
+            r#"
+            SELECT sample_count, p50_ms, p90_ms, p99_ms
+            FROM corridor_latency_percentiles
+            WHERE corridor_key = ?
+            "#,
+        )
+        .bind(corridor_key)
+        .fetch_optional(conn)
+        .await
+        .context("Failed to fetch corridor latency percentiles")?;
+
+        Ok(percentiles)
+    }
+
     /// Fetch hourly metrics by time range
     pub async fn fetch_hourly_metrics_by_timerange(
-        &self,
+        conn: &mut SqliteConnection,
         start_time: DateTime<Utc>,
         end_time: DateTime<Utc>,
     ) -> Result<Vec<HourlyCorridorMetrics>> {
@@ -181,7 +286,7 @@ impl AggregationDb {
         )
         .bind(start_time.to_rfc3339())
         .bind(end_time.to_rfc3339())
-        .fetch_all(&self.pool)
+        .fetch_all(conn)
         .await
         .context("Failed to fetch hourly metrics by timerange")?;
 
@@ -350,6 +455,16 @@ struct PaymentRecordRow {
     created_at: String,
 }
 
+#[derive(sqlx::FromRow)]
+struct PathPaymentFailureRow {
+    source_asset_code: String,
+    source_asset_issuer: String,
+    destination_asset_code: String,
+    destination_asset_issuer: String,
+    amount: f64,
+    created_at: String,
+}
+
 #[derive(sqlx::FromRow)]
 struct HourlyCorridorMetricsRow {
     id: String,
@@ -0,0 +1,170 @@
+//! Buffered metric recording.
+//!
+//! `Database::record_metric` writes one row per call. For high-frequency
+//! metrics, [`BufferedMetricsWriter`] queues rows in memory and writes them
+//! in one transaction once `max_batch_size` rows are pending or
+//! `flush_interval` has passed, and once more when shutdown is triggered.
+//! At most `max_pending` rows are held; past that the oldest are dropped and
+//! counted in `buffered_metrics_dropped_total`.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex};
+use tokio::task::JoinHandle;
+
+use crate::database::Database;
+use crate::observability::metrics as obs_metrics;
+
+/// Default pending rows that trigger a flush
+pub const DEFAULT_METRICS_BATCH_SIZE: usize = 100;
+
+/// Default longest time a row waits in the buffer
+pub const DEFAULT_METRICS_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default most rows held while writes are failing
+pub const DEFAULT_METRICS_MAX_PENDING: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetricsBufferConfig {
+    /// Pending rows that trigger an immediate flush
+    pub max_batch_size: usize,
+    pub flush_interval: Duration,
+    /// Rows held before the oldest are dropped
+    pub max_pending: usize,
+}
+
+impl Default for MetricsBufferConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: DEFAULT_METRICS_BATCH_SIZE,
+            flush_interval: DEFAULT_METRICS_FLUSH_INTERVAL,
+            max_pending: DEFAULT_METRICS_MAX_PENDING,
+        }
+    }
+}
+
+/// A metric row waiting to be written
+#[derive(Debug, Clone, PartialEq)]
+pub struct PendingMetric {
+    pub name: String,
+    pub value: f64,
+    pub entity_id: Option<String>,
+    pub entity_type: Option<String>,
+    /// When the metric was recorded, not when it was flushed
+    pub timestamp: DateTime<Utc>,
+}
+
+pub struct BufferedMetricsWriter {
+    db: Arc<Database>,
+    config: MetricsBufferConfig,
+    pending: Mutex<VecDeque<PendingMetric>>,
+}
+
+/// Drop the oldest rows past `max_pending`, counting them
+fn drop_oldest(pending: &mut VecDeque<PendingMetric>, max_pending: usize) {
+    let excess = pending.len().saturating_sub(max_pending);
+    if excess > 0 {
+        pending.drain(..excess);
+        obs_metrics::record_buffered_metrics_dropped(excess as u64);
+        tracing::warn!("Dropped {} buffered metrics over the buffer limit", excess);
+    }
+}
+
+impl BufferedMetricsWriter {
+    pub fn new(db: Arc<Database>, config: MetricsBufferConfig) -> Self {
+        Self {
+            db,
+            config,
+            pending: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Queue a metric, flushing when the batch size is reached
+    ///
+    /// When the buffer is full the oldest queued row is dropped.
+    pub async fn record(
+        &self,
+        name: &str,
+        value: f64,
+        entity_id: Option<String>,
+        entity_type: Option<String>,
+    ) -> Result<()> {
+        let full = {
+            let mut pending = self.pending.lock().await;
+            pending.push_back(PendingMetric {
+                name: name.to_string(),
+                value,
+                entity_id,
+                entity_type,
+                timestamp: Utc::now(),
+            });
+            drop_oldest(&mut pending, self.config.max_pending);
+            pending.len() >= self.config.max_batch_size
+        };
+
+        if full {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Rows queued but not yet written
+    pub async fn pending_len(&self) -> usize {
+        self.pending.lock().await.len()
+    }
+
+    /// Write every pending row, returning how many were written
+    ///
+    /// On failure the rows are put back so the next flush retries them,
+    /// keeping the newest `max_pending`.
+    pub async fn flush(&self) -> Result<u64> {
+        let batch: Vec<PendingMetric> = self.pending.lock().await.drain(..).collect();
+        match self.db.record_metrics(&batch).await {
+            Ok(written) => Ok(written),
+            Err(e) => {
+                let mut pending = self.pending.lock().await;
+                let newer = std::mem::replace(&mut *pending, batch.into());
+                pending.extend(newer);
+                drop_oldest(&mut pending, self.config.max_pending);
+                Err(e)
+            }
+        }
+    }
+
+    /// Flush every `flush_interval` until shutdown, then flush once more
+    pub fn spawn_flusher(
+        self: &Arc<Self>,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) -> JoinHandle<()> {
+        let writer = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(writer.config.flush_interval);
+            interval.tick().await;
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        if let Err(e) = writer.flush().await {
+                            tracing::error!("Failed to flush buffered metrics: {}", e);
+                        }
+                    }
+                    _ = shutdown_rx.recv() => {
+                        match writer.flush().await {
+                            Ok(written) => tracing::info!(
+                                "Flushed {} buffered metrics on shutdown",
+                                written
+                            ),
+                            Err(e) => tracing::error!(
+                                "Failed to flush buffered metrics on shutdown: {}",
+                                e
+                            ),
+                        }
+                        break;
+                    }
+                }
+            }
+        })
+    }
+}
@@ -1,4 +1,7 @@
 pub mod aggregates;
 pub mod aggregation;
+pub mod metrics_buffer;
+pub mod query_timeout;
+pub mod retry;
 pub mod schema;
 pub mod alerts;
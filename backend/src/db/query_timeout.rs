@@ -0,0 +1,167 @@
+//! Upper bound on how long a database read may hold a pool connection.
+
+use anyhow::Result;
+use sqlx::pool::PoolConnection;
+use sqlx::{Sqlite, SqlitePool};
+use std::future::Future;
+use std::ptr::NonNull;
+use std::time::{Duration, Instant};
+
+use crate::observability::metrics as obs_metrics;
+
+/// Default limit for the heavier read queries
+pub const DEFAULT_QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A query did not finish within its time limit
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Query '{query}' timed out after {timeout:?}")]
+pub struct QueryTimeout {
+    pub query: &'static str,
+    pub timeout: Duration,
+}
+
+/// Raw handle of a connection, kept to interrupt the statement it runs
+struct InterruptHandle(NonNull<libsqlite3_sys::sqlite3>);
+
+// SAFETY: `sqlite3_interrupt` may be called from any thread while the
+// connection is open
+unsafe impl Send for InterruptHandle {}
+
+impl InterruptHandle {
+    /// Make the statement running on the connection fail with
+    /// `SQLITE_INTERRUPT`. The connection must still be open.
+    fn interrupt(&self) {
+        // SAFETY: callers hold the connection alive across this call
+        unsafe { libsqlite3_sys::sqlite3_interrupt(self.0.as_ptr()) }
+    }
+}
+
+/// Run `run` on a connection from `pool`, failing with [`QueryTimeout`] if
+/// acquiring it and running the query take longer than `timeout`.
+///
+/// On timeout the statement is interrupted before the connection is given
+/// up, so SQLite stops executing it and the connection goes back to the pool
+/// instead of staying busy until the query would have finished. A query
+/// waiting on a lock is also bounded by the pool's `busy_timeout`.
+pub async fn with_query_timeout<T, E, F, Fut>(
+    query: &'static str,
+    timeout: Duration,
+    pool: &SqlitePool,
+    run: F,
+) -> Result<T>
+where
+    F: FnOnce(PoolConnection<Sqlite>) -> Fut,
+    Fut: Future<Output = std::result::Result<T, E>>,
+    E: Into<anyhow::Error>,
+{
+    let start = Instant::now();
+    let deadline = tokio::time::Instant::now() + timeout;
+    let timed_out = || {
+        obs_metrics::observe_db_query(query, "timeout", start.elapsed().as_secs_f64());
+        tracing::warn!("Query '{}' timed out after {:?}", query, timeout);
+        anyhow::Error::from(QueryTimeout { query, timeout })
+    };
+
+    let mut conn = match tokio::time::timeout_at(deadline, pool.acquire()).await {
+        Ok(conn) => conn?,
+        Err(_) => return Err(timed_out()),
+    };
+    let handle = InterruptHandle(conn.lock_handle().await?.as_raw_handle());
+
+    // `fut` owns the connection, so it stays open until the interrupt is sent
+    let fut = run(conn);
+    tokio::pin!(fut);
+    tokio::select! {
+        result = &mut fut => result.map_err(Into::into),
+        _ = tokio::time::sleep_until(deadline) => {
+            handle.interrupt();
+            Err(timed_out())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    /// Counts forever; only an interrupt ends it
+    const ENDLESS_QUERY: &str =
+        "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c) SELECT count(*) FROM c";
+
+    async fn single_connection_pool() -> SqlitePool {
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_secs(2))
+            .connect("sqlite::memory:")
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_fast_query_passes_through() {
+        let pool = single_connection_pool().await;
+        let value = with_query_timeout(
+            "fast",
+            Duration::from_secs(1),
+            &pool,
+            |mut conn| async move {
+                sqlx::query_scalar::<_, i64>("SELECT 42")
+                    .fetch_one(&mut *conn)
+                    .await
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(value, 42);
+    }
+
+    #[tokio::test]
+    async fn test_slow_query_times_out() {
+        let pool = single_connection_pool().await;
+        let err = with_query_timeout(
+            "slow",
+            Duration::from_millis(10),
+            &pool,
+            |mut conn| async move {
+                sqlx::query_scalar::<_, i64>(ENDLESS_QUERY)
+                    .fetch_one(&mut *conn)
+                    .await
+            },
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<QueryTimeout>(),
+            Some(&QueryTimeout {
+                query: "slow",
+                timeout: Duration::from_millis(10),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_statement_releases_its_connection() {
+        let pool = single_connection_pool().await;
+        with_query_timeout(
+            "slow",
+            Duration::from_millis(50),
+            &pool,
+            |mut conn| async move {
+                sqlx::query_scalar::<_, i64>(ENDLESS_QUERY)
+                    .fetch_one(&mut *conn)
+                    .await
+            },
+        )
+        .await
+        .unwrap_err();
+
+        // The only connection is free again rather than still counting
+        let value: i64 = sqlx::query_scalar("SELECT 1")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(value, 1);
+    }
+}
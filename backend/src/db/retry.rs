@@ -0,0 +1,126 @@
+//! Retries for database operations that hit a transient SQLite lock.
+//!
+//! `SQLITE_BUSY` and `SQLITE_LOCKED` only mean another connection held the
+//! lock at that moment, so the operation is retried with a short exponential
+//! backoff. Anything else, constraint violations included, is returned
+//! straight away.
+
+use anyhow::Result;
+use std::future::Future;
+use std::time::Duration;
+
+/// Default retries after the first attempt
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Default wait before the first retry; doubled on each further retry
+pub const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(50);
+
+/// Primary SQLite result codes; extended codes keep these in the low byte
+const SQLITE_BUSY: i32 = 5;
+const SQLITE_LOCKED: i32 = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; zero disables retrying
+    pub max_retries: u32,
+    pub backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRIES,
+            backoff: DEFAULT_RETRY_BACKOFF,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Wait before retry number `retry`, counting from zero
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.backoff.saturating_mul(2u32.saturating_pow(retry))
+    }
+}
+
+/// Whether `err` is a busy or locked database rather than a failed statement
+pub fn is_transient(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<sqlx::Error>() {
+        Some(sqlx::Error::Database(db_err)) => db_err
+            .code()
+            .and_then(|code| code.parse::<i32>().ok())
+            .is_some_and(|code| matches!(code & 0xff, SQLITE_BUSY | SQLITE_LOCKED)),
+        _ => false,
+    }
+}
+
+/// Run `op`, retrying it under `policy` while it fails with a transient error
+pub async fn retry_transient<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    operation: &'static str,
+    mut op: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = std::result::Result<T, E>>,
+    E: Into<anyhow::Error>,
+{
+    let mut retry = 0;
+    loop {
+        let err = match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) => e.into(),
+        };
+        if retry >= policy.max_retries || !is_transient(&err) {
+            return Err(err);
+        }
+
+        let delay = policy.delay(retry);
+        retry += 1;
+        tracing::warn!(
+            "Database busy during '{}', retry {} of {} in {:?}: {}",
+            operation,
+            retry,
+            policy.max_retries,
+            delay,
+            err
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_doubles() {
+        let policy = RetryPolicy {
+            max_retries: 3,
+            backoff: Duration::from_millis(10),
+        };
+        assert_eq!(policy.delay(0), Duration::from_millis(10));
+        assert_eq!(policy.delay(2), Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn test_non_database_errors_are_not_retried() {
+        let mut attempts = 0;
+        let err = retry_transient(&RetryPolicy::default(), "op", || {
+            attempts += 1;
+            async { Err::<(), _>(sqlx::Error::RowNotFound) }
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(attempts, 1);
+        assert!(!is_transient(&err));
+    }
+}
@@ -5,52 +5,904 @@
 //! if critical configuration is missing.
 
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::SigningKey;
 use std::env;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
 
-/// Required environment variables that must be set
-const REQUIRED_VARS: &[&str] = &["DATABASE_URL", "ENCRYPTION_KEY", "JWT_SECRET"];
+use crate::analytics::health::HealthThresholds;
+use crate::api::freshness::DEFAULT_STALE_AFTER_SECONDS;
+use crate::api::pagination::{PaginationConfig, PaginationDefaults, DEFAULT_MAX_OFFSET};
+use crate::cache::CacheConfig;
+use crate::concurrency_limit::ConcurrencyLimitConfig;
+use crate::database::{
+    PoolConfig, DEFAULT_ANCHOR_METRICS_HISTORY_LIMIT, DEFAULT_MIN_CORRIDOR_VOLUME_USD,
+};
+use crate::db::retry::RetryPolicy;
+use crate::ingestion::anchor_activity::AnchorMetricsSource;
+use crate::ingestion::asset_filter::AssetFilter;
+use crate::ingestion::ledger::{BatchSizeConfig, FollowConfig};
+use crate::ingestion::ReliabilityScoreConfig;
+use crate::jobs::anchor_domain_recheck::DEFAULT_FAILURE_THRESHOLD;
+use crate::muxed::DustThresholds;
+use crate::request_deadline::{RequestDeadlineConfig, DEFAULT_MAX_REQUEST_DURATION};
+use crate::request_decompression::DEFAULT_MAX_REQUEST_BODY_BYTES;
+use crate::response_compression::{
+    CompressionAlgorithms, CompressionQuality, ResponseCompressionConfig,
+    DEFAULT_EXCLUDED_CONTENT_TYPES,
+};
+use crate::rpc::circuit_breaker::{
+    CircuitBreakerConfig, EndpointGroup, GroupCircuitBreakerConfigs,
+};
+use crate::rpc::data_source::DataSourceConfig;
+use crate::rpc::latency::{LatencyWindowConfig, DEFAULT_SAMPLE_CAPACITY, DEFAULT_WINDOW_SECONDS};
+use crate::rpc::recording::{RecordingConfig, RecordingMode, DEFAULT_FIXTURES_DIR};
+use crate::rpc::stellar::DEFAULT_MAX_RECORDS_CEILING;
+use crate::services::contract::{ContractAddresses, ContractSettings};
+use crate::services::event_bus::{
+    parse_sampling_entry, EventSamplingConfig, DEFAULT_EVENT_BUS_CAPACITY,
+};
+use crate::services::fee_estimator::{
+    FeeEstimationConfig, FeeEstimationStrategy, DEFAULT_FEE_PERCENTILE,
+};
+use crate::services::pair_registry::PairRegistry;
+use crate::services::path_finder::{parse_asset, PathFinderConfig, MAX_PATH_HOPS};
+use crate::services::price_feed::{PriceFeedConfig, PROVIDER_NAMES};
+use crate::services::rpc_health_monitor::{RpcHealthMonitorConfig, DEFAULT_RECOVERY_DEBOUNCE};
+use crate::services::snapshot::{
+    ConfirmationConfig, HashAlgorithm, IntegrityAuditConfig, SnapshotSizeLimits,
+};
+use crate::services::webhook_dispatcher::WebhookDispatcherConfig;
+use crate::snapshot::{EpochCalendar, DEFAULT_EPOCH_DURATION_SECS};
+use crate::webhooks::{OversizedPayloadPolicy, WebhookLimits};
 
-/// Environment variables that should be validated if present
-const VALIDATED_VARS: &[(&str, fn(&str) -> bool)] = &[
-    ("SERVER_PORT", validate_port),
-    ("DB_POOL_MAX_CONNECTIONS", validate_positive_number),
-    ("DB_POOL_MIN_CONNECTIONS", validate_positive_number),
-    ("RPC_MAX_RECORDS_PER_REQUEST", validate_positive_number),
-    ("RPC_MAX_TOTAL_RECORDS", validate_positive_number),
-    ("RPC_PAGINATION_DELAY_MS", validate_positive_number),
-];
+/// Required secrets that must be set; they are read by their own subsystems
+const REQUIRED_VARS: &[&str] = &["ENCRYPTION_KEY", "JWT_SECRET"];
 
-/// Validates all required environment variables are set
-pub fn validate_env() -> Result<()> {
-    let mut errors = Vec::new();
+/// Application settings parsed and validated once at startup
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub database_url: String,
+    pub server_host: String,
+    pub server_port: u16,
+    pub pool: PoolConfig,
+    /// Retries for writes that find the database busy or locked
+    pub db_retry: RetryPolicy,
+    pub rpc_mock_mode: bool,
+    /// Recording or replay of RPC and Horizon traffic
+    pub rpc_recording: RecordingConfig,
+    /// Largest per-call override of the total records a paged RPC fetch returns
+    pub rpc_max_records_ceiling: u32,
+    /// How long a breaker must stay closed before RPC recovery is announced
+    pub rpc_health_monitor: RpcHealthMonitorConfig,
+    /// Horizon or Soroban RPC as the source of each operation both can serve
+    pub rpc_data_sources: DataSourceConfig,
+    /// Samples kept and the window averaged for `/api/rpc/health` latency
+    pub rpc_latency_window: LatencyWindowConfig,
+    /// Circuit breaker thresholds and timeout of each RPC endpoint group
+    pub rpc_circuit_breakers: GroupCircuitBreakerConfigs,
+    pub redis_url: String,
+    /// Cache TTLs, the `Cache-Control` max-ages of cached responses and the
+    /// age past which their data is flagged stale
+    pub cache: CacheConfig,
+    pub cors_allowed_origins: String,
+    /// Seconds browsers may cache a CORS preflight response
+    pub cors_max_age_seconds: u64,
+    pub compression_min_size: u16,
+    pub compression_algorithms: CompressionAlgorithms,
+    pub compression_level: CompressionQuality,
+    /// Content type prefixes sent uncompressed
+    pub compression_excluded_content_types: Vec<String>,
+    /// Largest accepted request body, measured after decompression
+    pub max_request_body_bytes: usize,
+    /// In-flight request caps of the load-shed route groups
+    pub concurrency_limit: ConcurrencyLimitConfig,
+    /// Time budget for a request's database reads
+    pub request_deadline: RequestDeadlineConfig,
+    pub sep10_server_public_key: String,
+    pub sep10_home_domain: String,
+    /// `LEDGER_INGESTION_MODE=follow` tracks the ledger head instead of polling in batches
+    pub ledger_follow_mode: bool,
+    /// Batch size, poll interval and backoff of follow mode
+    pub ledger_follow: FollowConfig,
+    /// Adaptive batch size bounds and timeout of polling ingestion
+    pub ledger_batch_size: BatchSizeConfig,
+    /// Assets whose payments are stored during ingestion
+    pub ingestion_asset_filter: AssetFilter,
+    /// Write snapshots with every monetary field as an exact decimal string
+    pub snapshot_exact_amounts: bool,
+    /// Algorithm stored snapshot hashes are computed with
+    pub snapshot_hash_algorithm: HashAlgorithm,
+    /// Largest snapshot stored or submitted
+    pub snapshot_size_limits: SnapshotSizeLimits,
+    /// Startup audit of the hashes of recently stored snapshots
+    pub snapshot_integrity_audit: IntegrityAuditConfig,
+    /// When and how often a submitted snapshot is checked on chain
+    pub snapshot_confirmation: ConfirmationConfig,
+    /// Price providers and their keys, failover and change limits, cache
+    /// TTL and staleness thresholds
+    pub price_feed: PriceFeedConfig,
+    /// Pairs the order-book and cost comparison endpoints serve
+    pub supported_asset_pairs: PairRegistry,
+    /// How the cost calculator estimates network fees
+    pub fee_estimation: FeeEstimationConfig,
+    /// Hop limit and intermediate assets of the cost calculator's path search
+    pub path_finder: PathFinderConfig,
+    /// ed25519 key that signs exported snapshot proof bundles
+    pub snapshot_proof_signing_key: Option<SigningKey>,
+    /// Snapshot contract addresses, Soroban RPC endpoint and source account
+    pub snapshot_contract: ContractSettings,
+    /// Per-asset amounts below which payments are dust to muxed analytics
+    pub muxed_dust_thresholds: DustThresholds,
+    /// Weights of the anchor reliability score
+    pub reliability_score: ReliabilityScoreConfig,
+    /// Whether anchor metrics come from payments or every operation
+    pub anchor_metrics_source: AnchorMetricsSource,
+    /// Consecutive failed checks before a verified anchor domain is flagged
+    pub anchor_domain_failure_threshold: u32,
+    /// Webhook delivery concurrency and dispatch batch size
+    pub webhook_dispatcher: WebhookDispatcherConfig,
+    /// Limits on webhook registrations and queued event payloads
+    pub webhook_limits: WebhookLimits,
+    /// Events buffered per event bus subscriber before it lags
+    pub event_bus_capacity: usize,
+    /// Sampling of high-volume events for the webhook and WebSocket consumers
+    pub event_sampling: EventSamplingConfig,
+    /// Assigns and labels snapshot epochs by the periods they cover; `None`
+    /// leaves epochs numbered sequentially and unlabelled
+    pub snapshot_epoch_calendar: Option<EpochCalendar>,
+    /// Success rates at which anchors turn green or yellow
+    pub health_thresholds: HealthThresholds,
+    /// Latest daily volume (USD) below which corridors are hidden from
+    /// listings; zero lists every corridor
+    pub min_corridor_volume_usd: f64,
+    /// Metrics history rows kept per anchor; older rows are trimmed on insert
+    pub anchor_metrics_history_limit: u32,
+    /// Page sizes and the offset limit of list endpoints
+    pub pagination: PaginationConfig,
+}
+
+/// Every missing or invalid variable found while loading `AppConfig`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    pub errors: Vec<String>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Environment configuration errors:\n  - {}",
+            self.errors.join("\n  - ")
+        )
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl AppConfig {
+    /// Load from the process environment
+    pub fn from_env() -> std::result::Result<Self, ConfigError> {
+        Self::from_lookup(|name| env::var(name).ok())
+    }
+
+    /// Load from an arbitrary variable source, collecting every error
+    /// instead of stopping at the first one
+    pub fn from_lookup<F>(lookup: F) -> std::result::Result<Self, ConfigError>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut vars = EnvReader {
+            lookup,
+            errors: Vec::new(),
+        };
 
-    // Check required variables
-    for var in REQUIRED_VARS {
-        if env::var(var).is_err() {
-            errors.push(format!("Missing required environment variable: {}", var));
+        for var in REQUIRED_VARS {
+            vars.required(var);
+        }
+        // Only validated here; the RPC client reads these itself
+        for var in [
+            "RPC_MAX_RECORDS_PER_REQUEST",
+            "RPC_MAX_TOTAL_RECORDS",
+            "RPC_PAGINATION_DELAY_MS",
+        ] {
+            vars.validated(var, validate_positive_number);
+        }
+
+        let sep10_server_public_key = match vars.required("SEP10_SERVER_PUBLIC_KEY") {
+            Some(key) if !validate_stellar_public_key(&key) => {
+                vars.invalid("SEP10_SERVER_PUBLIC_KEY", &key);
+                String::new()
+            }
+            key => key.unwrap_or_default(),
+        };
+
+        let pool_defaults = PoolConfig::default();
+        let concurrency_defaults = ConcurrencyLimitConfig::default();
+        let retry_defaults = RetryPolicy::default();
+        let audit_defaults = IntegrityAuditConfig::default();
+        let size_defaults = SnapshotSizeLimits::default();
+        let confirmation_defaults = ConfirmationConfig::default();
+        let price_feed_defaults = PriceFeedConfig::default();
+        let reliability_defaults = ReliabilityScoreConfig::default();
+        let health_defaults = HealthThresholds::default();
+        let webhook_defaults = WebhookDispatcherConfig::default();
+        let webhook_limit_defaults = WebhookLimits::default();
+        let fee_estimation_defaults = FeeEstimationConfig::default();
+        let path_finder_defaults = PathFinderConfig::default();
+        let follow_defaults = FollowConfig::default();
+        let batch_defaults = BatchSizeConfig::default();
+        let config = Self {
+            database_url: vars.required("DATABASE_URL").unwrap_or_default(),
+            server_host: vars.string("SERVER_HOST", "127.0.0.1"),
+            server_port: vars
+                .validated("SERVER_PORT", validate_port)
+                .map_or(8080, |v| v.parse().unwrap_or(8080)),
+            pool: PoolConfig {
+                max_connections: vars
+                    .positive("DB_POOL_MAX_CONNECTIONS", pool_defaults.max_connections),
+                min_connections: vars
+                    .positive("DB_POOL_MIN_CONNECTIONS", pool_defaults.min_connections),
+                connect_timeout_seconds: vars.parsed(
+                    "DB_POOL_CONNECT_TIMEOUT_SECONDS",
+                    pool_defaults.connect_timeout_seconds,
+                ),
+                idle_timeout_seconds: vars.parsed(
+                    "DB_POOL_IDLE_TIMEOUT_SECONDS",
+                    pool_defaults.idle_timeout_seconds,
+                ),
+                max_lifetime_seconds: vars.parsed(
+                    "DB_POOL_MAX_LIFETIME_SECONDS",
+                    pool_defaults.max_lifetime_seconds,
+                ),
+                query_timeout_seconds: vars.parsed(
+                    "DB_QUERY_TIMEOUT_SECONDS",
+                    pool_defaults.query_timeout_seconds,
+                ),
+            },
+            db_retry: RetryPolicy {
+                max_retries: vars.parsed("DB_BUSY_RETRIES", retry_defaults.max_retries),
+                backoff: Duration::from_millis(vars.parsed(
+                    "DB_BUSY_RETRY_BACKOFF_MS",
+                    retry_defaults.backoff.as_millis() as u64,
+                )),
+            },
+            rpc_mock_mode: vars.parsed("RPC_MOCK_MODE", false),
+            rpc_recording: RecordingConfig::new(
+                vars.parsed("RPC_RECORDING_MODE", RecordingMode::Off),
+                vars.string("RPC_FIXTURES_DIR", DEFAULT_FIXTURES_DIR),
+            ),
+            rpc_max_records_ceiling: vars
+                .positive("RPC_MAX_RECORDS_CEILING", DEFAULT_MAX_RECORDS_CEILING),
+            rpc_health_monitor: RpcHealthMonitorConfig {
+                recovery_debounce: Duration::from_secs(vars.parsed(
+                    "RPC_RECOVERY_DEBOUNCE_SECS",
+                    DEFAULT_RECOVERY_DEBOUNCE.as_secs(),
+                )),
+            },
+            rpc_data_sources: vars
+                .parsed_list("RPC_DATA_SOURCES", DataSourceConfig::parse_entry)
+                .into_iter()
+                .fold(
+                    DataSourceConfig::default(),
+                    |config, (operation, source)| config.with_source(operation, source),
+                ),
+            rpc_circuit_breakers: vars.circuit_breakers(),
+            rpc_latency_window: LatencyWindowConfig {
+                capacity: vars.positive(
+                    "RPC_LATENCY_SAMPLE_CAPACITY",
+                    DEFAULT_SAMPLE_CAPACITY as u32,
+                ) as usize,
+                window: Duration::from_secs(
+                    vars.positive("RPC_LATENCY_WINDOW_SECONDS", DEFAULT_WINDOW_SECONDS as u32)
+                        .into(),
+                ),
+            },
+            redis_url: vars.string("REDIS_URL", "redis://127.0.0.1:6379"),
+            cache: CacheConfig {
+                corridor_http_max_age: vars.optional_parsed("HTTP_CACHE_MAX_AGE_CORRIDORS"),
+                anchor_http_max_age: vars.optional_parsed("HTTP_CACHE_MAX_AGE_ANCHORS"),
+                dashboard_http_max_age: vars.optional_parsed("HTTP_CACHE_MAX_AGE_METRICS"),
+                data_stale_after: vars.positive(
+                    "DATA_STALE_AFTER_SECONDS",
+                    DEFAULT_STALE_AFTER_SECONDS as u32,
+                ) as usize,
+                ..CacheConfig::default()
+            },
+            cors_allowed_origins: vars.string(
+                "CORS_ALLOWED_ORIGINS",
+                "http://localhost:3000,http://localhost:3001",
+            ),
+            cors_max_age_seconds: vars.parsed(
+                "CORS_MAX_AGE_SECONDS",
+                crate::cors::DEFAULT_CORS_MAX_AGE.as_secs(),
+            ),
+            compression_min_size: vars.parsed("COMPRESSION_MIN_SIZE", 1024),
+            compression_algorithms: vars
+                .parsed("COMPRESSION_ALGORITHMS", CompressionAlgorithms::default()),
+            compression_level: vars.parsed("COMPRESSION_LEVEL", CompressionQuality::default()),
+            compression_excluded_content_types: vars
+                .string(
+                    "COMPRESSION_EXCLUDED_CONTENT_TYPES",
+                    &DEFAULT_EXCLUDED_CONTENT_TYPES.join(","),
+                )
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            max_request_body_bytes: vars
+                .parsed("MAX_REQUEST_BODY_BYTES", DEFAULT_MAX_REQUEST_BODY_BYTES),
+            concurrency_limit: ConcurrencyLimitConfig {
+                rpc_max_concurrent_requests: vars.positive(
+                    "RPC_MAX_CONCURRENT_REQUESTS",
+                    concurrency_defaults.rpc_max_concurrent_requests as u32,
+                ) as usize,
+                retry_after_seconds: vars.parsed(
+                    "CONCURRENCY_LIMIT_RETRY_AFTER_SECONDS",
+                    concurrency_defaults.retry_after_seconds,
+                ),
+            },
+            request_deadline: RequestDeadlineConfig {
+                max_request_duration: vars
+                    .millis("MAX_REQUEST_DURATION_MS", DEFAULT_MAX_REQUEST_DURATION),
+            },
+            sep10_server_public_key,
+            sep10_home_domain: vars.string("SEP10_HOME_DOMAIN", "stellar-insights.local"),
+            ledger_follow_mode: vars
+                .string("LEDGER_INGESTION_MODE", "batch")
+                .eq_ignore_ascii_case("follow"),
+            ledger_follow: FollowConfig {
+                batch_size: vars.positive("LEDGER_FOLLOW_BATCH_SIZE", follow_defaults.batch_size),
+                poll_interval: vars.millis("LEDGER_FOLLOW_POLL_MS", follow_defaults.poll_interval),
+                max_backoff: vars
+                    .millis("LEDGER_FOLLOW_MAX_BACKOFF_MS", follow_defaults.max_backoff),
+            },
+            ledger_batch_size: BatchSizeConfig {
+                initial: vars.positive("LEDGER_INGESTION_BATCH_SIZE", batch_defaults.initial),
+                min: vars.positive("LEDGER_INGESTION_MIN_BATCH_SIZE", batch_defaults.min),
+                max: vars.positive("LEDGER_INGESTION_MAX_BATCH_SIZE", batch_defaults.max),
+                timeout: Duration::from_secs(u64::from(vars.positive(
+                    "LEDGER_INGESTION_BATCH_TIMEOUT_SECONDS",
+                    batch_defaults.timeout.as_secs() as u32,
+                ))),
+            },
+            ingestion_asset_filter: {
+                let filter = vars
+                    .parsed_list("INGESTION_ASSET_ALLOWLIST", AssetFilter::parse_entry)
+                    .into_iter()
+                    .fold(AssetFilter::default(), |filter, (code, issuer)| {
+                        filter.with_allowed(code, issuer)
+                    });
+                vars.parsed_list("INGESTION_ASSET_DENYLIST", AssetFilter::parse_entry)
+                    .into_iter()
+                    .fold(filter, |filter, (code, issuer)| {
+                        filter.with_denied(code, issuer)
+                    })
+            },
+            snapshot_exact_amounts: vars.parsed("SNAPSHOT_EXACT_AMOUNTS", false),
+            snapshot_hash_algorithm: vars
+                .parsed("SNAPSHOT_HASH_ALGORITHM", HashAlgorithm::default()),
+            snapshot_size_limits: SnapshotSizeLimits {
+                max_metrics: vars.positive("SNAPSHOT_MAX_METRICS", size_defaults.max_metrics as u32)
+                    as usize,
+                max_bytes: vars.positive("SNAPSHOT_MAX_BYTES", size_defaults.max_bytes as u32)
+                    as usize,
+            },
+            snapshot_integrity_audit: IntegrityAuditConfig {
+                enabled: vars.parsed("SNAPSHOT_STARTUP_AUDIT", audit_defaults.enabled),
+                sample_size: vars.positive(
+                    "SNAPSHOT_AUDIT_SAMPLE_SIZE",
+                    audit_defaults.sample_size as u32,
+                ) as usize,
+                max_corruption_ratio: vars.ratio(
+                    "SNAPSHOT_AUDIT_MAX_CORRUPTION_RATIO",
+                    audit_defaults.max_corruption_ratio,
+                ),
+            },
+            snapshot_confirmation: ConfirmationConfig {
+                min_delay: Duration::from_millis(vars.parsed(
+                    "SNAPSHOT_CONFIRMATION_DELAY_MS",
+                    confirmation_defaults.min_delay.as_millis() as u64,
+                )),
+                max_checks: vars.positive(
+                    "SNAPSHOT_CONFIRMATION_MAX_CHECKS",
+                    confirmation_defaults.max_checks,
+                ),
+                backoff: Duration::from_millis(vars.parsed(
+                    "SNAPSHOT_CONFIRMATION_BACKOFF_MS",
+                    confirmation_defaults.backoff.as_millis() as u64,
+                )),
+            },
+            price_feed: PriceFeedConfig {
+                provider: vars
+                    .string("PRICE_FEED_PROVIDER", &price_feed_defaults.provider)
+                    .trim()
+                    .to_lowercase(),
+                api_key: vars.optional("PRICE_FEED_API_KEY"),
+                cache_ttl_seconds: vars.parsed(
+                    "PRICE_FEED_CACHE_TTL_SECONDS",
+                    price_feed_defaults.cache_ttl_seconds,
+                ),
+                request_timeout_seconds: vars
+                    .positive(
+                        "PRICE_FEED_REQUEST_TIMEOUT_SECONDS",
+                        price_feed_defaults.request_timeout_seconds as u32,
+                    )
+                    .into(),
+                coincap_api_key: vars.optional("COINCAP_API_KEY"),
+                fallback_providers: vars
+                    .list("PRICE_FEED_FALLBACK_PROVIDERS")
+                    .into_iter()
+                    .map(|p| p.to_lowercase())
+                    .collect(),
+                max_deviation_percent: vars.percent("PRICE_FEED_MAX_DEVIATION_PERCENT"),
+                max_price_change_percent: vars.percent("PRICE_FEED_MAX_PRICE_CHANGE_PERCENT"),
+                staleness_seconds: vars
+                    .positive(
+                        "PRICE_FEED_STALENESS_SECONDS",
+                        price_feed_defaults.staleness_seconds as u32,
+                    )
+                    .into(),
+                asset_staleness_seconds: vars
+                    .parsed_list(
+                        "PRICE_FEED_ASSET_STALENESS_SECONDS",
+                        PriceFeedConfig::parse_staleness_entry,
+                    )
+                    .into_iter()
+                    .collect(),
+            },
+            supported_asset_pairs: vars
+                .optional_parsed("SUPPORTED_ASSET_PAIRS")
+                .unwrap_or_default(),
+            fee_estimation: FeeEstimationConfig {
+                strategy: vars.fee_estimation_strategy(),
+                sample_ledgers: vars.positive(
+                    "FEE_ESTIMATION_SAMPLE_LEDGERS",
+                    fee_estimation_defaults.sample_ledgers,
+                ),
+                ..FeeEstimationConfig::default()
+            },
+            path_finder: PathFinderConfig {
+                max_hops: vars
+                    .positive("PATH_FINDER_MAX_HOPS", path_finder_defaults.max_hops as u32)
+                    as usize,
+                intermediates: Some(
+                    vars.parsed_list("PATH_FINDER_INTERMEDIATE_ASSETS", parse_asset),
+                )
+                .filter(|assets| !assets.is_empty())
+                .unwrap_or(path_finder_defaults.intermediates),
+            },
+            snapshot_proof_signing_key: vars.signing_key("SNAPSHOT_PROOF_SIGNING_KEY"),
+            snapshot_contract: ContractSettings {
+                addresses: ContractAddresses {
+                    mainnet: vars.optional("SNAPSHOT_CONTRACT_ID_MAINNET"),
+                    testnet: vars.optional("SNAPSHOT_CONTRACT_ID_TESTNET"),
+                    fallback: vars.optional("SNAPSHOT_CONTRACT_ID"),
+                },
+                rpc_url: vars.optional("SOROBAN_RPC_URL"),
+                source_secret_key: vars.stellar_secret("STELLAR_SOURCE_SECRET_KEY"),
+            },
+            muxed_dust_thresholds: vars
+                .optional_parsed("MUXED_MIN_PAYMENT_AMOUNTS")
+                .unwrap_or_default(),
+            reliability_score: ReliabilityScoreConfig {
+                base_weight: vars
+                    .non_negative("RELIABILITY_BASE_WEIGHT", reliability_defaults.base_weight),
+                penalty_per_failure: vars.non_negative(
+                    "RELIABILITY_PENALTY_PER_FAILURE",
+                    reliability_defaults.penalty_per_failure,
+                ),
+                max_penalty: vars
+                    .non_negative("RELIABILITY_MAX_PENALTY", reliability_defaults.max_penalty),
+            },
+            anchor_metrics_source: vars
+                .parsed("ANCHOR_METRICS_SOURCE", AnchorMetricsSource::default()),
+            anchor_domain_failure_threshold: vars
+                .positive("ANCHOR_DOMAIN_FAILURE_THRESHOLD", DEFAULT_FAILURE_THRESHOLD),
+            webhook_dispatcher: WebhookDispatcherConfig {
+                max_concurrent_deliveries: vars.positive(
+                    "WEBHOOK_MAX_CONCURRENT_DELIVERIES",
+                    webhook_defaults.max_concurrent_deliveries as u32,
+                ) as usize,
+                batch_size: vars.positive(
+                    "WEBHOOK_DISPATCH_BATCH_SIZE",
+                    webhook_defaults.batch_size as u32,
+                ) as usize,
+            },
+            webhook_limits: WebhookLimits {
+                max_active_per_user: vars
+                    .positive(
+                        "MAX_ACTIVE_WEBHOOKS_PER_USER",
+                        webhook_limit_defaults.max_active_per_user as u32,
+                    )
+                    .into(),
+                max_payload_bytes: vars.positive(
+                    "MAX_WEBHOOK_PAYLOAD_BYTES",
+                    webhook_limit_defaults.max_payload_bytes as u32,
+                ) as usize,
+                oversized_payload_policy: vars.parsed(
+                    "WEBHOOK_OVERSIZED_PAYLOAD_POLICY",
+                    webhook_limit_defaults.oversized_payload_policy,
+                ),
+            },
+            event_bus_capacity: vars
+                .positive("EVENT_BUS_CAPACITY", DEFAULT_EVENT_BUS_CAPACITY as u32)
+                as usize,
+            event_sampling: vars
+                .parsed_list("EVENT_SAMPLING", parse_sampling_entry)
+                .into_iter()
+                .fold(
+                    EventSamplingConfig::default(),
+                    |config, (event_type, rule)| config.with_rule(event_type, rule),
+                ),
+            snapshot_epoch_calendar: vars.epoch_calendar(),
+            health_thresholds: HealthThresholds {
+                green: vars.percentage("HEALTH_GREEN_THRESHOLD", health_defaults.green),
+                yellow: vars.percentage("HEALTH_YELLOW_THRESHOLD", health_defaults.yellow),
+            },
+            min_corridor_volume_usd: vars
+                .non_negative("CORRIDOR_MIN_VOLUME_USD", DEFAULT_MIN_CORRIDOR_VOLUME_USD),
+            anchor_metrics_history_limit: vars.positive(
+                "ANCHOR_METRICS_HISTORY_LIMIT",
+                DEFAULT_ANCHOR_METRICS_HISTORY_LIMIT as u32,
+            ),
+            pagination: PaginationConfig {
+                page_sizes: vars
+                    .parsed_list("PAGINATION_PAGE_SIZES", PaginationDefaults::parse_entry)
+                    .into_iter()
+                    .fold(PaginationDefaults::default(), |sizes, (resource, size)| {
+                        sizes.with_page_size(resource, size)
+                    }),
+                max_offset: vars
+                    .parsed("MAX_PAGINATION_OFFSET", DEFAULT_MAX_OFFSET as u32)
+                    .into(),
+            },
+        };
+
+        if !PROVIDER_NAMES.contains(&config.price_feed.provider.as_str()) {
+            vars.invalid("PRICE_FEED_PROVIDER", &config.price_feed.provider);
+        }
+        for provider in &config.price_feed.fallback_providers {
+            if !PROVIDER_NAMES.contains(&provider.as_str()) {
+                vars.invalid("PRICE_FEED_FALLBACK_PROVIDERS", provider);
+            }
+        }
+
+        if config.pool.min_connections > config.pool.max_connections {
+            vars.errors.push(format!(
+                "DB_POOL_MIN_CONNECTIONS ({}) exceeds DB_POOL_MAX_CONNECTIONS ({})",
+                config.pool.min_connections, config.pool.max_connections
+            ));
+        }
+
+        let batch = &config.ledger_batch_size;
+        if batch.min > batch.initial || batch.initial > batch.max {
+            vars.errors.push(format!(
+                "LEDGER_INGESTION_BATCH_SIZE ({}) must lie between \
+                 LEDGER_INGESTION_MIN_BATCH_SIZE ({}) and LEDGER_INGESTION_MAX_BATCH_SIZE ({})",
+                batch.initial, batch.min, batch.max
+            ));
+        }
+
+        if config.ledger_follow.poll_interval > config.ledger_follow.max_backoff {
+            vars.errors.push(format!(
+                "LEDGER_FOLLOW_POLL_MS ({}) exceeds LEDGER_FOLLOW_MAX_BACKOFF_MS ({})",
+                config.ledger_follow.poll_interval.as_millis(),
+                config.ledger_follow.max_backoff.as_millis()
+            ));
+        }
+
+        if config.path_finder.max_hops > MAX_PATH_HOPS {
+            vars.errors.push(format!(
+                "PATH_FINDER_MAX_HOPS ({}) exceeds the limit of {}",
+                config.path_finder.max_hops, MAX_PATH_HOPS
+            ));
+        }
+
+        if config.health_thresholds.yellow > config.health_thresholds.green {
+            vars.errors.push(format!(
+                "HEALTH_YELLOW_THRESHOLD ({}) exceeds HEALTH_GREEN_THRESHOLD ({})",
+                config.health_thresholds.yellow, config.health_thresholds.green
+            ));
+        }
+
+        if vars.errors.is_empty() {
+            Ok(config)
+        } else {
+            Err(ConfigError {
+                errors: vars.errors,
+            })
         }
     }
 
-    // Validate format of present variables
-    for (var, validator) in VALIDATED_VARS {
-        if let Ok(value) = env::var(var) {
-            if !validator(&value) {
-                errors.push(format!(
-                    "Invalid value for environment variable {}: '{}'",
-                    var, value
-                ));
+    /// Settings for the response compression layer
+    pub fn response_compression(&self) -> ResponseCompressionConfig {
+        ResponseCompressionConfig {
+            algorithms: self.compression_algorithms,
+            quality: self.compression_level,
+            min_size: self.compression_min_size,
+            excluded_content_types: self.compression_excluded_content_types.clone(),
+        }
+    }
+
+    /// `host:port` the server binds to
+    pub fn server_addr(&self) -> String {
+        format!("{}:{}", self.server_host, self.server_port)
+    }
+}
+
+/// Reads variables through a lookup function, recording errors as it goes
+struct EnvReader<F> {
+    lookup: F,
+    errors: Vec<String>,
+}
+
+impl<F: Fn(&str) -> Option<String>> EnvReader<F> {
+    fn invalid(&mut self, name: &str, value: &str) {
+        self.errors.push(format!(
+            "Invalid value for environment variable {}: '{}'",
+            name, value
+        ));
+    }
+
+    fn required(&mut self, name: &str) -> Option<String> {
+        let value = (self.lookup)(name);
+        if value.is_none() {
+            self.errors
+                .push(format!("Missing required environment variable: {}", name));
+        }
+        value
+    }
+
+    fn string(&self, name: &str, default: &str) -> String {
+        (self.lookup)(name).unwrap_or_else(|| default.to_string())
+    }
+
+    /// Non-empty value, or `None` when unset or blank
+    fn optional(&self, name: &str) -> Option<String> {
+        (self.lookup)(name).filter(|v| !v.trim().is_empty())
+    }
+
+    /// Parsed non-empty value; `None` when unset, blank or invalid
+    fn optional_parsed<T: FromStr>(&mut self, name: &str) -> Option<T> {
+        let value = self.optional(name)?;
+        match value.trim().parse() {
+            Ok(parsed) => Some(parsed),
+            Err(_) => {
+                self.invalid(name, &value);
+                None
             }
         }
     }
 
-    if !errors.is_empty() {
-        anyhow::bail!(
-            "Environment configuration errors:\n  - {}",
-            errors.join("\n  - ")
-        );
+    /// Comma-separated entries, trimmed, with empty entries dropped
+    fn list(&self, name: &str) -> Vec<String> {
+        (self.lookup)(name)
+            .map(|v| {
+                v.split(',')
+                    .map(|e| e.trim().to_string())
+                    .filter(|e| !e.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Comma-separated entries parsed by `parse`, recording each entry it
+    /// rejects
+    fn parsed_list<T>(&mut self, name: &str, parse: fn(&str) -> Option<T>) -> Vec<T> {
+        let mut parsed = Vec::new();
+        for entry in self.list(name) {
+            match parse(&entry) {
+                Some(value) => parsed.push(value),
+                None => self.invalid(name, &entry),
+            }
+        }
+        parsed
+    }
+
+    fn validated(&mut self, name: &str, validator: fn(&str) -> bool) -> Option<String> {
+        let value = (self.lookup)(name)?;
+        if validator(&value) {
+            Some(value)
+        } else {
+            self.invalid(name, &value);
+            None
+        }
+    }
+
+    fn parsed<T: FromStr>(&mut self, name: &str, default: T) -> T {
+        match (self.lookup)(name) {
+            Some(value) => value.trim().parse().unwrap_or_else(|_| {
+                self.invalid(name, &value);
+                default
+            }),
+            None => default,
+        }
+    }
+
+    fn positive(&mut self, name: &str, default: u32) -> u32 {
+        self.validated(name, validate_positive_number)
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(default)
+    }
+
+    /// Positive percentage; unset disables the check it configures
+    fn percent(&mut self, name: &str) -> Option<f64> {
+        let value = (self.lookup)(name)?;
+        match value.trim().parse::<f64>() {
+            Ok(percent) if percent.is_finite() && percent > 0.0 => Some(percent),
+            _ => {
+                self.invalid(name, &value);
+                None
+            }
+        }
+    }
+
+    /// Finite number of at least zero
+    fn non_negative(&mut self, name: &str, default: f64) -> f64 {
+        let Some(value) = (self.lookup)(name) else {
+            return default;
+        };
+        match value.trim().parse::<f64>() {
+            Ok(number) if number.is_finite() && number >= 0.0 => number,
+            _ => {
+                self.invalid(name, &value);
+                default
+            }
+        }
     }
 
-    Ok(())
+    /// Percentage between 0 and 100 inclusive
+    fn percentage(&mut self, name: &str, default: f64) -> f64 {
+        let Some(value) = (self.lookup)(name) else {
+            return default;
+        };
+        match value.trim().parse::<f64>() {
+            Ok(percent) if (0.0..=100.0).contains(&percent) => percent,
+            _ => {
+                self.invalid(name, &value);
+                default
+            }
+        }
+    }
+
+    /// Fraction between 0 and 1 inclusive
+    fn ratio(&mut self, name: &str, default: f64) -> f64 {
+        let Some(value) = (self.lookup)(name) else {
+            return default;
+        };
+        match value.trim().parse::<f64>() {
+            Ok(ratio) if (0.0..=1.0).contains(&ratio) => ratio,
+            _ => {
+                self.invalid(name, &value);
+                default
+            }
+        }
+    }
+
+    /// ed25519 signing key given as a hex-encoded 32-byte seed
+    fn signing_key(&mut self, name: &str) -> Option<SigningKey> {
+        let value = self.optional(name)?;
+        match hex::decode(value.trim()).map(<[u8; 32]>::try_from) {
+            Ok(Ok(seed)) => Some(SigningKey::from_bytes(&seed)),
+            _ => {
+                // Never echo the secret back into the error
+                self.invalid(name, "[REDACTED]");
+                None
+            }
+        }
+    }
+
+    /// `RPC_CIRCUIT_BREAKER_*` settings, each overridden for one endpoint
+    /// group by its `RPC_CIRCUIT_BREAKER_<GROUP>_*` counterpart
+    fn circuit_breakers(&mut self) -> GroupCircuitBreakerConfigs {
+        let base = self.circuit_breaker("RPC_CIRCUIT_BREAKER", &CircuitBreakerConfig::default());
+        EndpointGroup::ALL.into_iter().fold(
+            GroupCircuitBreakerConfigs::uniform(base.clone()),
+            |configs, group| {
+                let prefix = format!("RPC_CIRCUIT_BREAKER_{}", group.label().to_ascii_uppercase());
+                let config = self.circuit_breaker(&prefix, &base);
+                configs.with_group(group, config)
+            },
+        )
+    }
+
+    fn circuit_breaker(
+        &mut self,
+        prefix: &str,
+        defaults: &CircuitBreakerConfig,
+    ) -> CircuitBreakerConfig {
+        let name = |setting: &str| format!("{}_{}", prefix, setting);
+        CircuitBreakerConfig {
+            failure_threshold: self
+                .positive(&name("FAILURE_THRESHOLD"), defaults.failure_threshold),
+            success_threshold: self
+                .positive(&name("SUCCESS_THRESHOLD"), defaults.success_threshold),
+            timeout_duration: Duration::from_secs(
+                self.positive(
+                    &name("TIMEOUT_SECONDS"),
+                    defaults.timeout_duration.as_secs() as u32,
+                )
+                .into(),
+            ),
+            half_open_max_calls: self
+                .positive(&name("HALF_OPEN_MAX_CALLS"), defaults.half_open_max_calls),
+        }
+    }
+
+    /// Stellar secret seed (`S` followed by 55 base32 characters)
+    fn stellar_secret(&mut self, name: &str) -> Option<String> {
+        let value = self.optional(name)?.trim().to_string();
+        let well_formed = value.len() == 56
+            && value.starts_with('S')
+            && value
+                .bytes()
+                .all(|b| matches!(b, b'A'..=b'Z' | b'2'..=b'7'));
+        if well_formed {
+            Some(value)
+        } else {
+            // Never echo the secret back into the error
+            self.invalid(name, "[REDACTED]");
+            None
+        }
+    }
+
+    /// `SNAPSHOT_EPOCH_GENESIS` (RFC 3339) and `SNAPSHOT_EPOCH_DURATION_SECS`;
+    /// a duration without a genesis is an error rather than ignored
+    fn epoch_calendar(&mut self) -> Option<EpochCalendar> {
+        let genesis = self.optional_parsed::<DateTime<Utc>>("SNAPSHOT_EPOCH_GENESIS");
+        let duration_secs = self.optional_parsed::<u64>("SNAPSHOT_EPOCH_DURATION_SECS");
+        let Some(genesis) = genesis else {
+            if duration_secs.is_some() && self.optional("SNAPSHOT_EPOCH_GENESIS").is_none() {
+                self.errors.push(
+                    "SNAPSHOT_EPOCH_DURATION_SECS is set without SNAPSHOT_EPOCH_GENESIS"
+                        .to_string(),
+                );
+            }
+            return None;
+        };
+        let duration_secs = duration_secs.unwrap_or(DEFAULT_EPOCH_DURATION_SECS);
+        match EpochCalendar::new(genesis, duration_secs) {
+            Ok(calendar) => Some(calendar),
+            Err(_) => {
+                self.invalid("SNAPSHOT_EPOCH_DURATION_SECS", &duration_secs.to_string());
+                None
+            }
+        }
+    }
+
+    /// `FEE_ESTIMATION_STRATEGY`, with `FEE_ESTIMATION_PERCENTILE` (1-100)
+    /// for the percentile strategy
+    fn fee_estimation_strategy(&mut self) -> FeeEstimationStrategy {
+        let mut percentile = self.parsed("FEE_ESTIMATION_PERCENTILE", DEFAULT_FEE_PERCENTILE);
+        if !(1..=100).contains(&percentile) {
+            self.invalid("FEE_ESTIMATION_PERCENTILE", &percentile.to_string());
+            percentile = DEFAULT_FEE_PERCENTILE;
+        }
+        let Some(name) = self.optional("FEE_ESTIMATION_STRATEGY") else {
+            return FeeEstimationStrategy::Static;
+        };
+        FeeEstimationStrategy::parse(&name, percentile).unwrap_or_else(|| {
+            self.invalid("FEE_ESTIMATION_STRATEGY", &name);
+            FeeEstimationStrategy::Static
+        })
+    }
+
+    /// Positive duration given in milliseconds
+    fn millis(&mut self, name: &str, default: Duration) -> Duration {
+        self.validated(name, validate_positive_number)
+            .and_then(|v| v.parse().ok())
+            .map_or(default, Duration::from_millis)
+    }
+}
+
+/// Validates the environment and returns the parsed configuration.
+///
+/// Fails with every missing or invalid variable listed, not just the first.
+pub fn validate_env() -> Result<AppConfig> {
+    Ok(AppConfig::from_env()?)
 }
 
 /// Logs all configured environment variables (without sensitive values)
@@ -84,6 +936,7 @@ pub fn log_env_config() {
     log_var("DB_POOL_CONNECT_TIMEOUT_SECONDS");
     log_var("DB_POOL_IDLE_TIMEOUT_SECONDS");
     log_var("DB_POOL_MAX_LIFETIME_SECONDS");
+    log_var("DB_QUERY_TIMEOUT_SECONDS");
 
     // CORS
     log_var("CORS_ALLOWED_ORIGINS");
@@ -99,6 +952,15 @@ pub fn log_env_config() {
     if env::var("PRICE_FEED_API_KEY").is_ok() {
         tracing::info!("  PRICE_FEED_API_KEY: [REDACTED]");
     }
+    log_var("PRICE_FEED_FALLBACK_PROVIDERS");
+    if env::var("COINCAP_API_KEY").is_ok() {
+        tracing::info!("  COINCAP_API_KEY: [REDACTED]");
+    }
+
+    // Snapshot proofs
+    if env::var("SNAPSHOT_PROOF_SIGNING_KEY").is_ok() {
+        tracing::info!("  SNAPSHOT_PROOF_SIGNING_KEY: [REDACTED]");
+    }
 
     // RPC Pagination
     log_var("RPC_MAX_RECORDS_PER_REQUEST");
@@ -182,6 +1044,11 @@ fn validate_stellar_public_key(value: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::api::pagination::PagedResource;
+    use crate::db::query_timeout::DEFAULT_QUERY_TIMEOUT;
+    use crate::rpc::data_source::{DataOperation, DataSource};
+    use crate::services::event_bus::SamplingRule;
+    use crate::services::path_finder::{asset_id, DEFAULT_MAX_HOPS};
 
     #[test]
     fn test_sanitize_sqlite_url() {
@@ -224,4 +1091,1063 @@ mod tests {
         assert!(!validate_positive_number("-1"));
         assert!(!validate_positive_number("abc"));
     }
+
+    const SERVER_KEY: &str = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+
+    fn load(vars: &[(&str, &str)]) -> std::result::Result<AppConfig, ConfigError> {
+        let vars: std::collections::HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        AppConfig::from_lookup(|name| vars.get(name).cloned())
+    }
+
+    fn required_vars() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("DATABASE_URL", "sqlite::memory:"),
+            ("ENCRYPTION_KEY", "key"),
+            ("JWT_SECRET", "secret"),
+            ("SEP10_SERVER_PUBLIC_KEY", SERVER_KEY),
+        ]
+    }
+
+    #[test]
+    fn test_app_config_valid_env() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("SERVER_PORT", "9000"),
+            ("DB_POOL_MAX_CONNECTIONS", "20"),
+            ("RPC_MOCK_MODE", "true"),
+            ("LEDGER_INGESTION_MODE", "follow"),
+        ]);
+
+        let config = load(&vars).unwrap();
+        assert_eq!(config.database_url, "sqlite::memory:");
+        assert_eq!(config.server_addr(), "127.0.0.1:9000");
+        assert_eq!(config.pool.max_connections, 20);
+        assert_eq!(config.pool.min_connections, 2);
+        assert_eq!(config.db_retry, RetryPolicy::default());
+        assert!(config.rpc_mock_mode);
+        assert_eq!(config.rpc_recording.mode, RecordingMode::Off);
+        assert_eq!(config.rpc_max_records_ceiling, DEFAULT_MAX_RECORDS_CEILING);
+        assert_eq!(config.rpc_health_monitor, RpcHealthMonitorConfig::default());
+        assert_eq!(config.rpc_data_sources, DataSourceConfig::default());
+        assert_eq!(config.rpc_latency_window, LatencyWindowConfig::default());
+        assert_eq!(
+            config.rpc_circuit_breakers,
+            GroupCircuitBreakerConfigs::default()
+        );
+        assert!(config.ledger_follow_mode);
+        assert_eq!(config.ledger_follow, FollowConfig::default());
+        assert_eq!(config.ledger_batch_size, BatchSizeConfig::default());
+        assert_eq!(config.ingestion_asset_filter, AssetFilter::default());
+        assert_eq!(config.sep10_server_public_key, SERVER_KEY);
+        assert_eq!(config.compression_min_size, 1024);
+        assert_eq!(
+            config.response_compression(),
+            ResponseCompressionConfig::default()
+        );
+        assert_eq!(config.cors_max_age_seconds, 3600);
+        assert_eq!(config.cache.http_max_age("anchor"), 600);
+        assert_eq!(
+            config.cache.stale_after(),
+            chrono::Duration::seconds(DEFAULT_STALE_AFTER_SECONDS)
+        );
+        assert_eq!(config.request_deadline, RequestDeadlineConfig::default());
+        assert_eq!(config.concurrency_limit, ConcurrencyLimitConfig::default());
+        assert!(!config.snapshot_exact_amounts);
+        assert_eq!(config.snapshot_hash_algorithm, HashAlgorithm::Sha256);
+        assert_eq!(
+            config.snapshot_integrity_audit,
+            IntegrityAuditConfig::default()
+        );
+        assert_eq!(config.snapshot_confirmation, ConfirmationConfig::default());
+        assert_eq!(config.price_feed.provider, "coingecko");
+        assert_eq!(config.price_feed.api_key, None);
+        assert_eq!(config.price_feed.cache_ttl_seconds, 900);
+        assert_eq!(config.price_feed.request_timeout_seconds, 10);
+        assert!(config.price_feed.fallback_providers.is_empty());
+        assert_eq!(config.price_feed.max_deviation_percent, None);
+        assert_eq!(config.price_feed.max_price_change_percent, None);
+        assert_eq!(config.price_feed.coincap_api_key, None);
+        assert_eq!(config.price_feed.staleness_seconds, 3600);
+        assert!(config.price_feed.asset_staleness_seconds.is_empty());
+        assert!(!config.supported_asset_pairs.is_restricted());
+        assert_eq!(config.fee_estimation, FeeEstimationConfig::default());
+        assert_eq!(config.path_finder.max_hops, DEFAULT_MAX_HOPS);
+        assert_eq!(config.path_finder.intermediates.len(), 3);
+        assert!(config.snapshot_proof_signing_key.is_none());
+        assert_eq!(config.snapshot_contract, ContractSettings::default());
+        assert!(config.muxed_dust_thresholds.is_empty());
+        assert!(config.snapshot_epoch_calendar.is_none());
+        assert_eq!(config.reliability_score, ReliabilityScoreConfig::default());
+        assert_eq!(config.anchor_metrics_source, AnchorMetricsSource::Payments);
+        assert_eq!(
+            config.anchor_domain_failure_threshold,
+            DEFAULT_FAILURE_THRESHOLD
+        );
+        assert_eq!(
+            config.webhook_dispatcher,
+            WebhookDispatcherConfig::default()
+        );
+        assert_eq!(config.webhook_limits, WebhookLimits::default());
+        assert_eq!(config.snapshot_size_limits, SnapshotSizeLimits::default());
+        assert_eq!(config.event_bus_capacity, DEFAULT_EVENT_BUS_CAPACITY);
+        assert!(config.event_sampling.is_empty());
+        assert_eq!(config.health_thresholds, HealthThresholds::default());
+        assert_eq!(
+            config.min_corridor_volume_usd,
+            DEFAULT_MIN_CORRIDOR_VOLUME_USD
+        );
+        assert_eq!(
+            config.anchor_metrics_history_limit as i64,
+            DEFAULT_ANCHOR_METRICS_HISTORY_LIMIT
+        );
+        assert_eq!(config.pagination, PaginationConfig::default());
+    }
+
+    #[test]
+    fn test_app_config_snapshot_proof_signing_key() {
+        let seed = "07".repeat(32);
+        let mut vars = required_vars();
+        vars.push(("SNAPSHOT_PROOF_SIGNING_KEY", seed.as_str()));
+        let key = load(&vars).unwrap().snapshot_proof_signing_key.unwrap();
+        assert_eq!(key.to_bytes(), [7u8; 32]);
+
+        let mut vars = required_vars();
+        vars.push(("SNAPSHOT_PROOF_SIGNING_KEY", "not-a-key"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("SNAPSHOT_PROOF_SIGNING_KEY"));
+        assert!(!err.errors[0].contains("not-a-key"));
+    }
+
+    #[test]
+    fn test_app_config_snapshot_contract() {
+        let secret = format!("S{}", "A".repeat(55));
+        let mut vars = required_vars();
+        vars.extend([
+            ("SNAPSHOT_CONTRACT_ID_TESTNET", "CTESTNET"),
+            ("SNAPSHOT_CONTRACT_ID", "CFALLBACK"),
+            ("SOROBAN_RPC_URL", "https://soroban.example"),
+            ("STELLAR_SOURCE_SECRET_KEY", secret.as_str()),
+        ]);
+        let settings = load(&vars).unwrap().snapshot_contract;
+        assert_eq!(settings.addresses.mainnet, None);
+        assert_eq!(settings.addresses.testnet.as_deref(), Some("CTESTNET"));
+        assert_eq!(settings.addresses.fallback.as_deref(), Some("CFALLBACK"));
+        assert_eq!(settings.rpc_url.as_deref(), Some("https://soroban.example"));
+        assert_eq!(settings.source_secret_key, Some(secret.clone()));
+
+        let mut vars = required_vars();
+        vars.push(("STELLAR_SOURCE_SECRET_KEY", "not-a-secret"));
+        let err = load(&vars).unwrap_err();
+        assert_eq!(err.errors.len(), 1);
+        assert!(err.errors[0].contains("STELLAR_SOURCE_SECRET_KEY"));
+        assert!(!err.errors[0].contains("not-a-secret"));
+    }
+
+    #[test]
+    fn test_app_config_price_feed_failover() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("PRICE_FEED_FALLBACK_PROVIDERS", "CoinCap, coingecko,"),
+            ("PRICE_FEED_MAX_DEVIATION_PERCENT", "5"),
+            ("PRICE_FEED_MAX_PRICE_CHANGE_PERCENT", "50"),
+            ("COINCAP_API_KEY", "coincap-key"),
+        ]);
+        let config = load(&vars).unwrap().price_feed;
+        assert_eq!(
+            config.fallback_providers,
+            vec!["coincap".to_string(), "coingecko".to_string()]
+        );
+        assert_eq!(config.max_deviation_percent, Some(5.0));
+        assert_eq!(config.max_price_change_percent, Some(50.0));
+        assert_eq!(config.coincap_api_key.as_deref(), Some("coincap-key"));
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("PRICE_FEED_FALLBACK_PROVIDERS", "coinmarketcap"),
+            ("PRICE_FEED_MAX_DEVIATION_PERCENT", "-1"),
+            ("PRICE_FEED_MAX_PRICE_CHANGE_PERCENT", "fifty"),
+        ]);
+        let err = load(&vars).unwrap_err();
+        assert_eq!(err.errors.len(), 3);
+    }
+
+    #[test]
+    fn test_app_config_price_feed() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("PRICE_FEED_PROVIDER", "CoinCap"),
+            ("PRICE_FEED_API_KEY", "price-key"),
+            ("PRICE_FEED_CACHE_TTL_SECONDS", "600"),
+            ("PRICE_FEED_REQUEST_TIMEOUT_SECONDS", "15"),
+            ("PRICE_FEED_STALENESS_SECONDS", "1800"),
+            (
+                "PRICE_FEED_ASSET_STALENESS_SECONDS",
+                "XLM:native=600, AQUA:GAQUA=86400",
+            ),
+        ]);
+        let config = load(&vars).unwrap().price_feed;
+        assert_eq!(config.provider, "coincap");
+        assert_eq!(config.api_key.as_deref(), Some("price-key"));
+        assert_eq!(config.cache_ttl_seconds, 600);
+        assert_eq!(config.request_timeout_seconds, 15);
+        assert_eq!(config.staleness_seconds, 1800);
+        assert_eq!(config.asset_staleness_seconds.len(), 2);
+        assert_eq!(config.asset_staleness_seconds["AQUA:GAQUA"], 86400);
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("PRICE_FEED_PROVIDER", "coinmarketcap"),
+            ("PRICE_FEED_CACHE_TTL_SECONDS", "15m"),
+            ("PRICE_FEED_REQUEST_TIMEOUT_SECONDS", "0"),
+            ("PRICE_FEED_STALENESS_SECONDS", "-1"),
+            (
+                "PRICE_FEED_ASSET_STALENESS_SECONDS",
+                "XLM:native=600,USDC=x",
+            ),
+        ]);
+        let err = load(&vars).unwrap_err();
+        assert_eq!(err.errors.len(), 5);
+        assert!(err.errors.iter().all(|e| e.contains("PRICE_FEED_")));
+    }
+
+    #[test]
+    fn test_app_config_supported_asset_pairs() {
+        let mut vars = required_vars();
+        vars.push(("SUPPORTED_ASSET_PAIRS", "XLM/USDC, USDC/NGN"));
+        let pairs = load(&vars).unwrap().supported_asset_pairs;
+        assert_eq!(pairs.pairs(), ["XLM/USDC", "USDC/NGN"]);
+
+        // A typo must not silently serve every pair
+        let mut vars = required_vars();
+        vars.push(("SUPPORTED_ASSET_PAIRS", "XLM/USDC,USDCNGN"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("SUPPORTED_ASSET_PAIRS"));
+    }
+
+    #[test]
+    fn test_app_config_ledger_follow() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("LEDGER_FOLLOW_BATCH_SIZE", "10"),
+            ("LEDGER_FOLLOW_POLL_MS", "500"),
+            ("LEDGER_FOLLOW_MAX_BACKOFF_MS", "5000"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().ledger_follow,
+            FollowConfig {
+                batch_size: 10,
+                poll_interval: Duration::from_millis(500),
+                max_backoff: Duration::from_secs(5),
+            }
+        );
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("LEDGER_FOLLOW_BATCH_SIZE", "0"),
+            ("LEDGER_FOLLOW_POLL_MS", "1s"),
+            ("LEDGER_FOLLOW_MAX_BACKOFF_MS", "0"),
+        ]);
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 3);
+
+        // Backoff starts at the poll interval, so it cannot be capped below it
+        let mut vars = required_vars();
+        vars.extend([
+            ("LEDGER_FOLLOW_POLL_MS", "20000"),
+            ("LEDGER_FOLLOW_MAX_BACKOFF_MS", "10000"),
+        ]);
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("LEDGER_FOLLOW_POLL_MS"));
+    }
+
+    #[test]
+    fn test_app_config_ingestion_asset_filter() {
+        let issuer = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        let allowlist = format!("USDC:{}, EURC", issuer);
+        let mut vars = required_vars();
+        vars.extend([
+            ("INGESTION_ASSET_ALLOWLIST", allowlist.as_str()),
+            ("INGESTION_ASSET_DENYLIST", "SPAM"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().ingestion_asset_filter,
+            AssetFilter::new(&allowlist, "SPAM")
+        );
+
+        // A malformed code stops startup instead of being dropped
+        let mut vars = required_vars();
+        vars.extend([
+            ("INGESTION_ASSET_ALLOWLIST", "USDC,US DC"),
+            ("INGESTION_ASSET_DENYLIST", "SPAM:"),
+        ]);
+        let err = load(&vars).unwrap_err();
+        assert_eq!(err.errors.len(), 2);
+        assert!(err.errors[0].contains("INGESTION_ASSET_ALLOWLIST"));
+        assert!(err.errors[1].contains("INGESTION_ASSET_DENYLIST"));
+    }
+
+    #[test]
+    fn test_app_config_ledger_batch_size() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("LEDGER_INGESTION_BATCH_SIZE", "10"),
+            ("LEDGER_INGESTION_MIN_BATCH_SIZE", "2"),
+            ("LEDGER_INGESTION_MAX_BATCH_SIZE", "100"),
+            ("LEDGER_INGESTION_BATCH_TIMEOUT_SECONDS", "60"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().ledger_batch_size,
+            BatchSizeConfig {
+                initial: 10,
+                min: 2,
+                max: 100,
+                timeout: Duration::from_secs(60),
+            }
+        );
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("LEDGER_INGESTION_MIN_BATCH_SIZE", "0"),
+            ("LEDGER_INGESTION_BATCH_TIMEOUT_SECONDS", "30s"),
+        ]);
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 2);
+
+        // Out-of-order bounds are an error rather than clamped
+        for (name, value) in [
+            ("LEDGER_INGESTION_BATCH_SIZE", "80"),
+            ("LEDGER_INGESTION_MIN_BATCH_SIZE", "10"),
+            ("LEDGER_INGESTION_MAX_BATCH_SIZE", "4"),
+        ] {
+            let mut vars = required_vars();
+            vars.push((name, value));
+            let err = load(&vars).unwrap_err();
+            assert!(
+                err.errors[0].contains("LEDGER_INGESTION_BATCH_SIZE"),
+                "{}={}",
+                name,
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_app_config_fee_estimation() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("FEE_ESTIMATION_STRATEGY", "percentile"),
+            ("FEE_ESTIMATION_PERCENTILE", "75"),
+            ("FEE_ESTIMATION_SAMPLE_LEDGERS", "5"),
+        ]);
+        let config = load(&vars).unwrap().fee_estimation;
+        assert_eq!(config.strategy, FeeEstimationStrategy::Percentile(75));
+        assert_eq!(config.sample_ledgers, 5);
+
+        let mut vars = required_vars();
+        vars.push(("FEE_ESTIMATION_STRATEGY", "Median"));
+        assert_eq!(
+            load(&vars).unwrap().fee_estimation.strategy,
+            FeeEstimationStrategy::NetworkMedian
+        );
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("FEE_ESTIMATION_STRATEGY", "surge"),
+            ("FEE_ESTIMATION_PERCENTILE", "0"),
+            ("FEE_ESTIMATION_SAMPLE_LEDGERS", "0"),
+        ]);
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 3);
+    }
+
+    #[test]
+    fn test_app_config_path_finder() {
+        let usdc = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        let mut vars = required_vars();
+        let intermediates = format!("XLM, {}", usdc);
+        vars.extend([
+            ("PATH_FINDER_MAX_HOPS", "2"),
+            ("PATH_FINDER_INTERMEDIATE_ASSETS", intermediates.as_str()),
+        ]);
+        let config = load(&vars).unwrap().path_finder;
+        assert_eq!(config.max_hops, 2);
+        let ids: Vec<String> = config.intermediates.iter().map(asset_id).collect();
+        assert_eq!(ids, ["XLM:native", usdc]);
+
+        // A typo must not silently drop an intermediate
+        let mut vars = required_vars();
+        vars.extend([
+            ("PATH_FINDER_MAX_HOPS", "7"),
+            ("PATH_FINDER_INTERMEDIATE_ASSETS", "XLM,USDC"),
+        ]);
+        let err = load(&vars).unwrap_err();
+        assert_eq!(err.errors.len(), 2, "{}", err);
+        assert!(err.to_string().contains("'USDC'"));
+        assert!(err.to_string().contains("PATH_FINDER_MAX_HOPS"));
+    }
+
+    #[test]
+    fn test_app_config_muxed_dust_thresholds() {
+        let mut vars = required_vars();
+        vars.push(("MUXED_MIN_PAYMENT_AMOUNTS", "XLM=1, USDC=0.5"));
+        let thresholds = load(&vars).unwrap().muxed_dust_thresholds;
+        assert_eq!(thresholds, "XLM=1,USDC=0.5".parse().unwrap());
+
+        for invalid in ["XLM", "XLM=-1", "XLM=abc"] {
+            let mut vars = required_vars();
+            vars.push(("MUXED_MIN_PAYMENT_AMOUNTS", invalid));
+            let err = load(&vars).unwrap_err();
+            assert!(
+                err.errors[0].contains("MUXED_MIN_PAYMENT_AMOUNTS"),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_app_config_reliability_score() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("RELIABILITY_BASE_WEIGHT", "0.8"),
+            ("RELIABILITY_PENALTY_PER_FAILURE", "0.05"),
+            ("RELIABILITY_MAX_PENALTY", "0.3"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().reliability_score,
+            ReliabilityScoreConfig {
+                base_weight: 0.8,
+                penalty_per_failure: 0.05,
+                max_penalty: 0.3,
+            }
+        );
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("RELIABILITY_BASE_WEIGHT", "-1"),
+            ("RELIABILITY_PENALTY_PER_FAILURE", "often"),
+            ("RELIABILITY_MAX_PENALTY", "inf"),
+        ]);
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 3);
+    }
+
+    #[test]
+    fn test_app_config_health_thresholds() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("HEALTH_GREEN_THRESHOLD", "98"),
+            ("HEALTH_YELLOW_THRESHOLD", "90.5"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().health_thresholds,
+            HealthThresholds {
+                green: 98.0,
+                yellow: 90.5,
+            }
+        );
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("HEALTH_GREEN_THRESHOLD", "101"),
+            ("HEALTH_YELLOW_THRESHOLD", "high"),
+        ]);
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 2);
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("HEALTH_GREEN_THRESHOLD", "90"),
+            ("HEALTH_YELLOW_THRESHOLD", "95"),
+        ]);
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("HEALTH_YELLOW_THRESHOLD"));
+    }
+
+    #[test]
+    fn test_app_config_min_corridor_volume() {
+        let mut vars = required_vars();
+        vars.push(("CORRIDOR_MIN_VOLUME_USD", "0"));
+        assert_eq!(load(&vars).unwrap().min_corridor_volume_usd, 0.0);
+
+        let mut vars = required_vars();
+        vars.push(("CORRIDOR_MIN_VOLUME_USD", "-5"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("CORRIDOR_MIN_VOLUME_USD"));
+    }
+
+    #[test]
+    fn test_app_config_anchor_metrics_history_limit() {
+        let mut vars = required_vars();
+        vars.push(("ANCHOR_METRICS_HISTORY_LIMIT", "250"));
+        assert_eq!(load(&vars).unwrap().anchor_metrics_history_limit, 250);
+
+        for invalid in ["0", "-10", "all"] {
+            let mut vars = required_vars();
+            vars.push(("ANCHOR_METRICS_HISTORY_LIMIT", invalid));
+            let err = load(&vars).unwrap_err();
+            assert_eq!(err.errors.len(), 1);
+            assert!(err.errors[0].contains("ANCHOR_METRICS_HISTORY_LIMIT"));
+        }
+    }
+
+    #[test]
+    fn test_app_config_pagination() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("MAX_PAGINATION_OFFSET", "0"),
+            ("PAGINATION_PAGE_SIZES", "anchors=25:100, rpc=10:50"),
+        ]);
+        let pagination = load(&vars).unwrap().pagination;
+        assert_eq!(pagination.max_offset, 0);
+        assert_eq!(pagination.page_limit(PagedResource::Anchors, None), 25);
+        assert_eq!(
+            pagination.page_limit(PagedResource::RpcRecords, Some(80)),
+            50
+        );
+        assert_eq!(pagination.page_limit(PagedResource::Snapshots, None), 50);
+
+        for invalid in ["-1", "10k"] {
+            let mut vars = required_vars();
+            vars.push(("MAX_PAGINATION_OFFSET", invalid));
+            let err = load(&vars).unwrap_err();
+            assert_eq!(err.errors.len(), 1);
+            assert!(err.errors[0].contains("MAX_PAGINATION_OFFSET"));
+        }
+
+        let mut vars = required_vars();
+        vars.push((
+            "PAGINATION_PAGE_SIZES",
+            "anchors=25:100,bogus=1:2,snapshots=90:10",
+        ));
+        let err = load(&vars).unwrap_err();
+        assert_eq!(err.errors.len(), 2);
+        assert!(err
+            .errors
+            .iter()
+            .all(|e| e.contains("PAGINATION_PAGE_SIZES")));
+    }
+
+    #[test]
+    fn test_app_config_anchor_metrics_source() {
+        let mut vars = required_vars();
+        vars.push(("ANCHOR_METRICS_SOURCE", "Operations"));
+        assert_eq!(
+            load(&vars).unwrap().anchor_metrics_source,
+            AnchorMetricsSource::Operations
+        );
+
+        let mut vars = required_vars();
+        vars.push(("ANCHOR_METRICS_SOURCE", "trades"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("ANCHOR_METRICS_SOURCE"));
+    }
+
+    #[test]
+    fn test_app_config_anchor_domain_failure_threshold() {
+        let mut vars = required_vars();
+        vars.push(("ANCHOR_DOMAIN_FAILURE_THRESHOLD", "5"));
+        assert_eq!(load(&vars).unwrap().anchor_domain_failure_threshold, 5);
+
+        let mut vars = required_vars();
+        vars.push(("ANCHOR_DOMAIN_FAILURE_THRESHOLD", "0"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("ANCHOR_DOMAIN_FAILURE_THRESHOLD"));
+    }
+
+    #[test]
+    fn test_app_config_webhook_dispatcher() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("WEBHOOK_MAX_CONCURRENT_DELIVERIES", "16"),
+            ("WEBHOOK_DISPATCH_BATCH_SIZE", "50"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().webhook_dispatcher,
+            WebhookDispatcherConfig {
+                max_concurrent_deliveries: 16,
+                batch_size: 50,
+            }
+        );
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("WEBHOOK_MAX_CONCURRENT_DELIVERIES", "0"),
+            ("WEBHOOK_DISPATCH_BATCH_SIZE", "ten"),
+        ]);
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 2);
+    }
+
+    #[test]
+    fn test_app_config_webhook_limits() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("MAX_ACTIVE_WEBHOOKS_PER_USER", "25"),
+            ("MAX_WEBHOOK_PAYLOAD_BYTES", "4096"),
+            ("WEBHOOK_OVERSIZED_PAYLOAD_POLICY", "Reject"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().webhook_limits,
+            WebhookLimits {
+                max_active_per_user: 25,
+                max_payload_bytes: 4096,
+                oversized_payload_policy: OversizedPayloadPolicy::Reject,
+            }
+        );
+
+        for invalid in ["0", "-1", "many"] {
+            let mut vars = required_vars();
+            vars.push(("MAX_ACTIVE_WEBHOOKS_PER_USER", invalid));
+            let err = load(&vars).unwrap_err();
+            assert_eq!(err.errors.len(), 1);
+            assert!(err.errors[0].contains("MAX_ACTIVE_WEBHOOKS_PER_USER"));
+        }
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("MAX_WEBHOOK_PAYLOAD_BYTES", "256KiB"),
+            ("WEBHOOK_OVERSIZED_PAYLOAD_POLICY", "drop"),
+        ]);
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 2);
+    }
+
+    #[test]
+    fn test_app_config_event_bus() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("EVENT_BUS_CAPACITY", "1024"),
+            ("EVENT_SAMPLING", "payment.created=min:1000"),
+        ]);
+        let config = load(&vars).unwrap();
+        assert_eq!(config.event_bus_capacity, 1024);
+        assert_eq!(
+            config
+                .event_sampling
+                .rule(&crate::webhooks::WebhookEventType::PaymentCreated),
+            Some(SamplingRule::MinAmount(1000.0))
+        );
+
+        // A zero capacity would panic when the channel is created
+        let mut vars = required_vars();
+        vars.push(("EVENT_BUS_CAPACITY", "0"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("EVENT_BUS_CAPACITY"));
+
+        // Malformed rules and rules for alert events are errors, not skipped
+        let mut vars = required_vars();
+        vars.push((
+            "EVENT_SAMPLING",
+            "payment.created=1/0,rpc.degraded=1/10,payment.created=1/5",
+        ));
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 2);
+    }
+
+    #[test]
+    fn test_app_config_snapshot_epoch_calendar() {
+        let mut vars = required_vars();
+        vars.push(("SNAPSHOT_EPOCH_GENESIS", "2025-12-29T00:00:00Z"));
+        let calendar = load(&vars).unwrap().snapshot_epoch_calendar.unwrap();
+        assert_eq!(calendar.label(2).as_deref(), Some("2026-W03"));
+
+        for (name, value) in [
+            ("SNAPSHOT_EPOCH_GENESIS", "next monday"),
+            ("SNAPSHOT_EPOCH_DURATION_SECS", "0"),
+            ("SNAPSHOT_EPOCH_DURATION_SECS", "weekly"),
+        ] {
+            let mut vars = required_vars();
+            vars.extend([
+                ("SNAPSHOT_EPOCH_GENESIS", "2025-12-29T00:00:00Z"),
+                (name, value),
+            ]);
+            let err = load(&vars).unwrap_err();
+            assert!(err.errors[0].contains(name), "{}={}", name, value);
+        }
+
+        // A duration alone would silently leave epochs unlabelled
+        let mut vars = required_vars();
+        vars.push(("SNAPSHOT_EPOCH_DURATION_SECS", "86400"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("SNAPSHOT_EPOCH_GENESIS"));
+    }
+
+    #[test]
+    fn test_app_config_snapshot_exact_amounts() {
+        let mut vars = required_vars();
+        vars.push(("SNAPSHOT_EXACT_AMOUNTS", "true"));
+        assert!(load(&vars).unwrap().snapshot_exact_amounts);
+
+        let mut vars = required_vars();
+        vars.push(("SNAPSHOT_EXACT_AMOUNTS", "sometimes"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("SNAPSHOT_EXACT_AMOUNTS"));
+    }
+
+    #[test]
+    fn test_app_config_snapshot_hash_algorithm() {
+        let mut vars = required_vars();
+        vars.push(("SNAPSHOT_HASH_ALGORITHM", "sha-512"));
+        assert_eq!(
+            load(&vars).unwrap().snapshot_hash_algorithm,
+            HashAlgorithm::Sha512
+        );
+
+        let mut vars = required_vars();
+        vars.push(("SNAPSHOT_HASH_ALGORITHM", "md5"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("SNAPSHOT_HASH_ALGORITHM"));
+    }
+
+    #[test]
+    fn test_app_config_snapshot_size_limits() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("SNAPSHOT_MAX_METRICS", "500"),
+            ("SNAPSHOT_MAX_BYTES", "65536"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().snapshot_size_limits,
+            SnapshotSizeLimits {
+                max_metrics: 500,
+                max_bytes: 65536,
+            }
+        );
+
+        let mut vars = required_vars();
+        vars.extend([("SNAPSHOT_MAX_METRICS", "0"), ("SNAPSHOT_MAX_BYTES", "4MB")]);
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 2);
+    }
+
+    #[test]
+    fn test_app_config_snapshot_confirmation() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("SNAPSHOT_CONFIRMATION_DELAY_MS", "500"),
+            ("SNAPSHOT_CONFIRMATION_MAX_CHECKS", "3"),
+            ("SNAPSHOT_CONFIRMATION_BACKOFF_MS", "0"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().snapshot_confirmation,
+            ConfirmationConfig {
+                min_delay: Duration::from_millis(500),
+                max_checks: 3,
+                backoff: Duration::ZERO,
+            }
+        );
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("SNAPSHOT_CONFIRMATION_DELAY_MS", "2s"),
+            ("SNAPSHOT_CONFIRMATION_MAX_CHECKS", "0"),
+            ("SNAPSHOT_CONFIRMATION_BACKOFF_MS", "-1"),
+        ]);
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 3);
+    }
+
+    #[test]
+    fn test_app_config_snapshot_integrity_audit() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("SNAPSHOT_STARTUP_AUDIT", "true"),
+            ("SNAPSHOT_AUDIT_SAMPLE_SIZE", "20"),
+            ("SNAPSHOT_AUDIT_MAX_CORRUPTION_RATIO", "0.1"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().snapshot_integrity_audit,
+            IntegrityAuditConfig {
+                enabled: true,
+                sample_size: 20,
+                max_corruption_ratio: 0.1,
+            }
+        );
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("SNAPSHOT_STARTUP_AUDIT", "sometimes"),
+            ("SNAPSHOT_AUDIT_SAMPLE_SIZE", "0"),
+            ("SNAPSHOT_AUDIT_MAX_CORRUPTION_RATIO", "1.5"),
+        ]);
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 3);
+    }
+
+    #[test]
+    fn test_app_config_pool() {
+        let config = load(&required_vars()).unwrap();
+        assert_eq!(config.pool.query_timeout(), DEFAULT_QUERY_TIMEOUT);
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("DB_POOL_MAX_CONNECTIONS", "15"),
+            ("DB_POOL_CONNECT_TIMEOUT_SECONDS", "45"),
+            ("DB_QUERY_TIMEOUT_SECONDS", "3"),
+        ]);
+        let config = load(&vars).unwrap();
+        assert_eq!(config.pool.max_connections, 15);
+        assert_eq!(config.pool.min_connections, 2);
+        assert_eq!(config.pool.connect_timeout_seconds, 45);
+        assert_eq!(config.pool.idle_timeout_seconds, 600);
+        assert_eq!(config.pool.query_timeout(), Duration::from_secs(3));
+
+        let mut vars = required_vars();
+        vars.push(("DB_QUERY_TIMEOUT_SECONDS", "slow"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("DB_QUERY_TIMEOUT_SECONDS"));
+    }
+
+    #[test]
+    fn test_app_config_db_retry() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("DB_BUSY_RETRIES", "0"),
+            ("DB_BUSY_RETRY_BACKOFF_MS", "200"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().db_retry,
+            RetryPolicy {
+                max_retries: 0,
+                backoff: Duration::from_millis(200),
+            }
+        );
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("DB_BUSY_RETRIES", "-1"),
+            ("DB_BUSY_RETRY_BACKOFF_MS", "fast"),
+        ]);
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 2);
+    }
+
+    #[test]
+    fn test_app_config_rpc_recording() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("RPC_RECORDING_MODE", "replay"),
+            ("RPC_FIXTURES_DIR", "fixtures"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().rpc_recording,
+            RecordingConfig::new(RecordingMode::Replay, "fixtures")
+        );
+
+        let mut vars = required_vars();
+        vars.push(("RPC_RECORDING_MODE", "rewind"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("RPC_RECORDING_MODE"));
+    }
+
+    #[test]
+    fn test_app_config_rpc_max_records_ceiling() {
+        let mut vars = required_vars();
+        vars.push(("RPC_MAX_RECORDS_CEILING", "100000"));
+        assert_eq!(load(&vars).unwrap().rpc_max_records_ceiling, 100_000);
+
+        let mut vars = required_vars();
+        vars.push(("RPC_MAX_RECORDS_CEILING", "0"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("RPC_MAX_RECORDS_CEILING"));
+    }
+
+    #[test]
+    fn test_app_config_rpc_recovery_debounce() {
+        let mut vars = required_vars();
+        vars.push(("RPC_RECOVERY_DEBOUNCE_SECS", "0"));
+        assert_eq!(
+            load(&vars).unwrap().rpc_health_monitor.recovery_debounce,
+            Duration::ZERO
+        );
+
+        let mut vars = required_vars();
+        vars.push(("RPC_RECOVERY_DEBOUNCE_SECS", "1m"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("RPC_RECOVERY_DEBOUNCE_SECS"));
+    }
+
+    #[test]
+    fn test_app_config_rpc_data_sources() {
+        let mut vars = required_vars();
+        vars.push((
+            "RPC_DATA_SOURCES",
+            "latest_ledger=rpc, transactions=horizon",
+        ));
+        let sources = load(&vars).unwrap().rpc_data_sources;
+        assert_eq!(
+            sources.source_for(DataOperation::LatestLedger),
+            DataSource::Rpc
+        );
+        assert_eq!(
+            sources.source_for(DataOperation::LedgerTransactions),
+            DataSource::Horizon
+        );
+
+        let mut vars = required_vars();
+        vars.push((
+            "RPC_DATA_SOURCES",
+            "latest_ledger=rpc,bogus=rpc,transactions=archive",
+        ));
+        let err = load(&vars).unwrap_err();
+        assert_eq!(err.errors.len(), 2);
+        assert!(err.errors.iter().all(|e| e.contains("RPC_DATA_SOURCES")));
+    }
+
+    #[test]
+    fn test_app_config_rpc_circuit_breakers() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("RPC_CIRCUIT_BREAKER_FAILURE_THRESHOLD", "8"),
+            ("RPC_CIRCUIT_BREAKER_TIMEOUT_SECONDS", "45"),
+            ("RPC_CIRCUIT_BREAKER_MARKETS_FAILURE_THRESHOLD", "20"),
+        ]);
+        let breakers = load(&vars).unwrap().rpc_circuit_breakers;
+        let core = breakers.get(EndpointGroup::Core);
+        assert_eq!(core.failure_threshold, 8);
+        assert_eq!(core.timeout_duration, Duration::from_secs(45));
+        assert_eq!(breakers.get(EndpointGroup::Accounts), core);
+        let markets = breakers.get(EndpointGroup::Markets);
+        assert_eq!(markets.failure_threshold, 20);
+        assert_eq!(markets.timeout_duration, Duration::from_secs(45));
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("RPC_CIRCUIT_BREAKER_SUCCESS_THRESHOLD", "two"),
+            ("RPC_CIRCUIT_BREAKER_ACCOUNTS_HALF_OPEN_MAX_CALLS", "0"),
+        ]);
+        let err = load(&vars).unwrap_err();
+        assert_eq!(err.errors.len(), 2);
+        assert!(err.errors[0].contains("RPC_CIRCUIT_BREAKER_SUCCESS_THRESHOLD"));
+        assert!(err.errors[1].contains("RPC_CIRCUIT_BREAKER_ACCOUNTS_HALF_OPEN_MAX_CALLS"));
+    }
+
+    #[test]
+    fn test_app_config_rpc_latency_window() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("RPC_LATENCY_SAMPLE_CAPACITY", "64"),
+            ("RPC_LATENCY_WINDOW_SECONDS", "30"),
+        ]);
+        let latency = load(&vars).unwrap().rpc_latency_window;
+        assert_eq!(latency.capacity, 64);
+        assert_eq!(latency.window, Duration::from_secs(30));
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("RPC_LATENCY_SAMPLE_CAPACITY", "0"),
+            ("RPC_LATENCY_WINDOW_SECONDS", "soon"),
+        ]);
+        let err = load(&vars).unwrap_err();
+        assert_eq!(err.errors.len(), 2);
+        assert!(err.errors[0].contains("RPC_LATENCY_SAMPLE_CAPACITY"));
+        assert!(err.errors[1].contains("RPC_LATENCY_WINDOW_SECONDS"));
+    }
+
+    #[test]
+    fn test_app_config_http_cache_max_age() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("HTTP_CACHE_MAX_AGE_CORRIDORS", "30"),
+            ("HTTP_CACHE_MAX_AGE_METRICS", "0"),
+        ]);
+        let cache = load(&vars).unwrap().cache;
+        assert_eq!(cache.http_max_age("corridor"), 30);
+        assert_eq!(cache.http_max_age("anchor"), 600);
+        assert_eq!(cache.http_max_age("dashboard"), 0);
+
+        let mut vars = required_vars();
+        vars.push(("HTTP_CACHE_MAX_AGE_ANCHORS", "10m"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("HTTP_CACHE_MAX_AGE_ANCHORS"));
+    }
+
+    #[test]
+    fn test_app_config_data_stale_after() {
+        let mut vars = required_vars();
+        vars.push(("DATA_STALE_AFTER_SECONDS", "900"));
+        let cache = load(&vars).unwrap().cache;
+        assert_eq!(cache.stale_after(), chrono::Duration::minutes(15));
+
+        for invalid in ["0", "1h"] {
+            let mut vars = required_vars();
+            vars.push(("DATA_STALE_AFTER_SECONDS", invalid));
+            let err = load(&vars).unwrap_err();
+            assert_eq!(err.errors.len(), 1);
+            assert!(err.errors[0].contains("DATA_STALE_AFTER_SECONDS"));
+        }
+    }
+
+    #[test]
+    fn test_app_config_concurrency_limit() {
+        let mut vars = required_vars();
+        vars.extend([
+            ("RPC_MAX_CONCURRENT_REQUESTS", "64"),
+            ("CONCURRENCY_LIMIT_RETRY_AFTER_SECONDS", "5"),
+        ]);
+        assert_eq!(
+            load(&vars).unwrap().concurrency_limit,
+            ConcurrencyLimitConfig {
+                rpc_max_concurrent_requests: 64,
+                retry_after_seconds: 5,
+            }
+        );
+
+        let mut vars = required_vars();
+        vars.extend([
+            ("RPC_MAX_CONCURRENT_REQUESTS", "0"),
+            ("CONCURRENCY_LIMIT_RETRY_AFTER_SECONDS", "soon"),
+        ]);
+        assert_eq!(load(&vars).unwrap_err().errors.len(), 2);
+    }
+
+    #[test]
+    fn test_app_config_request_deadline() {
+        let mut vars = required_vars();
+        vars.push(("MAX_REQUEST_DURATION_MS", "1500"));
+        let config = load(&vars).unwrap();
+        assert_eq!(
+            config.request_deadline.max_request_duration,
+            Duration::from_millis(1500)
+        );
+
+        let mut vars = required_vars();
+        vars.push(("MAX_REQUEST_DURATION_MS", "0"));
+        let err = load(&vars).unwrap_err();
+        assert!(err.errors[0].contains("MAX_REQUEST_DURATION_MS"));
+    }
+
+    #[test]
+    fn test_app_config_reports_every_invalid_value() {
+        let mut vars = required_vars();
+        vars.retain(|(k, _)| *k != "JWT_SECRET");
+        vars.extend([
+            ("SERVER_PORT", "0"),
+            ("DB_POOL_MAX_CONNECTIONS", "abc"),
+            ("RPC_MOCK_MODE", "maybe"),
+            ("COMPRESSION_MIN_SIZE", "-5"),
+            ("COMPRESSION_ALGORITHMS", "gzip,zstd"),
+        ]);
+
+        let err = load(&vars).unwrap_err();
+        assert_eq!(err.errors.len(), 6, "{}", err);
+
+        let message = err.to_string();
+        for var in [
+            "JWT_SECRET",
+            "SERVER_PORT",
+            "DB_POOL_MAX_CONNECTIONS",
+            "RPC_MOCK_MODE",
+            "COMPRESSION_MIN_SIZE",
+            "COMPRESSION_ALGORITHMS",
+        ] {
+            assert!(message.contains(var), "missing {} in {}", var, message);
+        }
+    }
+
+    #[test]
+    fn test_app_config_rejects_placeholder_server_key() {
+        let mut vars = required_vars();
+        vars.retain(|(k, _)| *k != "SEP10_SERVER_PUBLIC_KEY");
+        vars.push((
+            "SEP10_SERVER_PUBLIC_KEY",
+            "GXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX",
+        ));
+
+        let err = load(&vars).unwrap_err();
+        assert_eq!(err.errors.len(), 1);
+        assert!(err.errors[0].contains("SEP10_SERVER_PUBLIC_KEY"));
+    }
 }
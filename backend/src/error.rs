@@ -1,8 +1,9 @@
 use axum::{
     extract::Request,
-    http::StatusCode,
+    http::{header, StatusCode},
+    middleware::{self, Next},
     response::{IntoResponse, Response},
-    Json,
+    Json, Router,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -49,6 +50,11 @@ pub enum ApiError {
         message: String,
         details: Option<HashMap<String, serde_json::Value>>,
     },
+    Timeout {
+        code: String,
+        message: String,
+        details: Option<HashMap<String, serde_json::Value>>,
+    },
 }
 
 impl ApiError {
@@ -115,13 +121,23 @@ impl ApiError {
         }
     }
 
+    /// Create a Timeout error
+    pub fn timeout(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Timeout {
+            code: code.into(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
     /// Add details to any error variant
     pub fn with_details(mut self, details: HashMap<String, serde_json::Value>) -> Self {
         match &mut self {
             Self::NotFound { details: d, .. }
             | Self::BadRequest { details: d, .. }
             | Self::InternalError { details: d, .. }
-            | Self::Unauthorized { details: d, .. } => {
+            | Self::Unauthorized { details: d, .. }
+            | Self::Timeout { details: d, .. } => {
                 *d = Some(details);
             }
         }
@@ -135,6 +151,7 @@ impl ApiError {
             Self::BadRequest { .. } => StatusCode::BAD_REQUEST,
             Self::InternalError { .. } => StatusCode::INTERNAL_SERVER_ERROR,
             Self::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
+            Self::Timeout { .. } => StatusCode::GATEWAY_TIMEOUT,
         }
     }
 
@@ -168,6 +185,11 @@ impl ApiError {
                 code,
                 message,
                 details,
+            }
+            | Self::Timeout {
+                code,
+                message,
+                details,
             } => (code.clone(), message.clone(), details.clone(), None),
         };
 
@@ -202,9 +224,92 @@ pub fn error_response_with_request_id(error: ApiError, req: &Request) -> Respons
     (status, Json(error_response)).into_response()
 }
 
+/// Standard error envelope for a response produced outside any handler
+fn routing_error(
+    status: StatusCode,
+    code: &str,
+    message: String,
+    request_id: Option<String>,
+) -> Response {
+    let body = ErrorResponse {
+        error: ErrorDetail {
+            code: code.to_string(),
+            message,
+            details: None,
+            request_id,
+            stack_trace: None,
+        },
+    };
+    (status, Json(body)).into_response()
+}
+
+fn request_id_of(req: &Request) -> Option<String> {
+    req.extensions()
+        .get::<crate::request_id::RequestId>()
+        .map(|id| id.0.clone())
+}
+
+/// Fallback for requests that match no route
+pub async fn route_not_found(req: Request) -> Response {
+    routing_error(
+        StatusCode::NOT_FOUND,
+        "ROUTE_NOT_FOUND",
+        format!("No route for {} {}", req.method(), req.uri().path()),
+        request_id_of(&req),
+    )
+}
+
+/// Replace the router's empty 405 responses with the JSON error envelope,
+/// keeping the `Allow` header
+pub async fn method_not_allowed_json(req: Request, next: Next) -> Response {
+    let request_id = request_id_of(&req);
+    let message = format!(
+        "Method {} is not allowed for {}",
+        req.method(),
+        req.uri().path()
+    );
+
+    let response = next.run(req).await;
+    if response.status() != StatusCode::METHOD_NOT_ALLOWED
+        || response.headers().contains_key(header::CONTENT_TYPE)
+    {
+        return response;
+    }
+
+    let allow = response.headers().get(header::ALLOW).cloned();
+    let mut json = routing_error(
+        StatusCode::METHOD_NOT_ALLOWED,
+        "METHOD_NOT_ALLOWED",
+        message,
+        request_id,
+    );
+    if let Some(allow) = allow {
+        json.headers_mut().insert(header::ALLOW, allow);
+    }
+    json
+}
+
+/// Answer unmatched routes and disallowed methods with JSON errors. Apply
+/// inside the request ID layer so the errors carry the request ID.
+pub fn with_json_fallbacks(router: Router) -> Router {
+    router
+        .fallback(route_not_found)
+        .layer(middleware::from_fn(method_not_allowed_json))
+}
+
 /// Convert from anyhow::Error
 impl From<anyhow::Error> for ApiError {
     fn from(err: anyhow::Error) -> Self {
+        if err
+            .downcast_ref::<crate::request_deadline::DeadlineExceeded>()
+            .is_some()
+        {
+            return Self::timeout(
+                "REQUEST_DEADLINE_EXCEEDED",
+                "The request took too long to complete",
+            );
+        }
+
         Self::InternalError {
             code: "INTERNAL_ERROR".to_string(),
             message: "An internal error occurred".to_string(),
@@ -271,6 +376,15 @@ mod tests {
         assert_eq!(error.status_code(), StatusCode::UNAUTHORIZED);
     }
 
+    #[test]
+    fn test_deadline_exceeded_is_timeout() {
+        let err = anyhow::Error::new(crate::request_deadline::DeadlineExceeded {
+            query: "list_corridors",
+        });
+        let error = ApiError::from(err);
+        assert_eq!(error.status_code(), StatusCode::GATEWAY_TIMEOUT);
+    }
+
     #[test]
     fn test_error_with_details() {
         let mut details = HashMap::new();
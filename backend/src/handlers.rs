@@ -1,31 +1,36 @@
 use axum::{
     extract::{Path, Query, State},
-    response::IntoResponse,
-    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+use crate::admin_audit_log::AuditContext;
+use crate::api::field_selection::{
+    detail_response, parse_fields, FieldsQuery, ANCHOR_DETAIL_FIELDS, ANCHOR_DETAIL_SECTIONS,
+};
+use crate::api::pagination::{PagedResource, PaginationConfig};
 use crate::broadcast::{broadcast_anchor_update, broadcast_corridor_update};
 use crate::error::{ApiError, ApiResult};
+use crate::models::asset::{validate_asset, AssetId};
 use crate::models::corridor::Corridor;
-use crate::models::{AnchorDetailResponse, CreateAnchorRequest, CreateCorridorRequest};
+use crate::models::{CorridorPayment, CreateAnchorRequest, CreateCorridorRequest, PaymentCursor};
 use crate::services::analytics::{compute_corridor_metrics, CorridorTransaction};
+use crate::services::event_bus::DomainEvent;
 use crate::state::AppState;
+use crate::webhooks::events::AnchorStatusChangedEvent;
 
 #[derive(Debug, Deserialize)]
 pub struct ListAnchorsQuery {
-    #[serde(default = "default_limit")]
-    pub limit: i64,
+    #[serde(default)]
+    pub limit: Option<i64>,
     #[serde(default)]
     pub offset: i64,
 }
 
-fn default_limit() -> i64 {
-    50
-}
-
 #[derive(Debug, Serialize)]
 pub struct ListAnchorsResponse {
     pub anchors: Vec<crate::models::Anchor>,
@@ -34,10 +39,16 @@ pub struct ListAnchorsResponse {
 
 #[derive(Debug, Deserialize)]
 pub struct ListCorridorsQuery {
-    #[serde(default = "default_limit")]
-    pub limit: i64,
+    #[serde(default)]
+    pub limit: Option<i64>,
     #[serde(default)]
     pub offset: i64,
+    /// Hide corridors whose latest daily volume is below this, in USD;
+    /// defaults to `Database::min_corridor_volume_usd`
+    pub min_volume_usd: Option<f64>,
+    /// List every corridor, dust included
+    #[serde(default)]
+    pub include_all: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -49,22 +60,31 @@ pub struct ListCorridorsResponse {
 /// GET /api/anchors - List all anchors with their metrics
 pub async fn list_anchors(
     State(app_state): State<AppState>,
+    Extension(pagination): Extension<PaginationConfig>,
     Query(params): Query<ListAnchorsQuery>,
 ) -> ApiResult<Json<ListAnchorsResponse>> {
-    let anchors = app_state
-        .db
-        .list_anchors(params.limit, params.offset)
-        .await?;
+    pagination.check_offset(params.offset, None)?;
+    let limit = pagination.page_limit(PagedResource::Anchors, params.limit);
+    let anchors = app_state.db.list_anchors(limit, params.offset).await?;
     let total = anchors.len();
 
     Ok(Json(ListAnchorsResponse { anchors, total }))
 }
 
 /// GET /api/anchors/:id - Get detailed anchor information
+///
+/// `?fields=` selects fields of the anchor and which of `assets` and
+/// `metrics_history` to include.
 pub async fn get_anchor(
     State(app_state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> ApiResult<Json<AnchorDetailResponse>> {
+    Query(selection): Query<FieldsQuery>,
+) -> ApiResult<Response> {
+    let fields = parse_fields(
+        selection.fields.as_deref(),
+        &[ANCHOR_DETAIL_FIELDS, ANCHOR_DETAIL_SECTIONS],
+    )?;
+
     let anchor_detail = app_state.db.get_anchor_detail(id).await?.ok_or_else(|| {
         let mut details = HashMap::new();
         details.insert("anchor_id".to_string(), serde_json::json!(id.to_string()));
@@ -75,7 +95,7 @@ pub async fn get_anchor(
         )
     })?;
 
-    Ok(Json(anchor_detail))
+    detail_response(&anchor_detail, "anchor", fields.as_deref())
 }
 
 /// GET /api/anchors/account/:stellar_account - Get anchor by Stellar account (G- or M-address)
@@ -134,28 +154,200 @@ pub async fn get_muxed_analytics(
 /// POST /api/anchors - Create a new anchor
 pub async fn create_anchor(
     State(app_state): State<AppState>,
+    audit: AuditContext,
     Json(req): Json<CreateAnchorRequest>,
 ) -> ApiResult<Json<crate::models::Anchor>> {
+    validate_anchor_request(&req).map_err(|msg| ApiError::bad_request("INVALID_INPUT", msg))?;
+
+    let anchor = app_state.db.create_anchor(req).await?;
+    app_state
+        .db
+        .admin_audit_logger
+        .record(
+            &audit,
+            "anchor.create",
+            &format!("anchor:{}", anchor.id),
+            serde_json::json!({
+                "name": anchor.name,
+                "stellar_account": anchor.stellar_account,
+            }),
+        )
+        .await;
+
+    // Broadcast the new anchor to WebSocket clients
+    broadcast_anchor_update(&app_state.ws_state, &anchor);
+
+    Ok(Json(anchor))
+}
+
+fn validate_anchor_request(req: &CreateAnchorRequest) -> Result<(), &'static str> {
     if req.name.is_empty() {
+        return Err("Name cannot be empty");
+    }
+    if req.stellar_account.is_empty() {
+        return Err("Stellar account cannot be empty");
+    }
+    Ok(())
+}
+
+/// Most anchors accepted by a single bulk import
+pub const MAX_BULK_ANCHORS: usize = 1000;
+
+/// Anchors inserted per database transaction during a bulk import
+const BULK_ANCHOR_CHUNK_SIZE: usize = 100;
+
+/// Outcome of one item of a bulk anchor import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkAnchorStatus {
+    Created,
+    Invalid,
+    Duplicate,
+    Failed,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkAnchorResult {
+    /// Position of the item in the request array
+    pub index: usize,
+    pub status: BulkAnchorStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BulkAnchorResult {
+    fn rejected(index: usize, status: BulkAnchorStatus, error: impl Into<String>) -> Self {
+        Self {
+            index,
+            status,
+            id: None,
+            error: Some(error.into()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkAnchorImportResponse {
+    pub total: usize,
+    pub created: usize,
+    pub failed: usize,
+    pub results: Vec<BulkAnchorResult>,
+}
+
+/// POST /api/anchors/bulk - Create many anchors, reporting each item's outcome.
+///
+/// Valid items are inserted one transaction per chunk; invalid items,
+/// duplicates and items of a chunk whose transaction fails are reported
+/// without affecting the rest of the import.
+pub async fn create_anchors_bulk(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    Json(reqs): Json<Vec<CreateAnchorRequest>>,
+) -> ApiResult<Json<BulkAnchorImportResponse>> {
+    if reqs.is_empty() {
         return Err(ApiError::bad_request(
             "INVALID_INPUT",
-            "Name cannot be empty",
+            "At least one anchor is required",
         ));
     }
-
-    if req.stellar_account.is_empty() {
+    if reqs.len() > MAX_BULK_ANCHORS {
         return Err(ApiError::bad_request(
-            "INVALID_INPUT",
-            "Stellar account cannot be empty",
+            "BATCH_TOO_LARGE",
+            format!(
+                "At most {} anchors can be imported at once",
+                MAX_BULK_ANCHORS
+            ),
         ));
     }
 
-    let anchor = app_state.db.create_anchor(req).await?;
+    let total = reqs.len();
+    let mut results = Vec::with_capacity(total);
+    let mut valid: Vec<(usize, CreateAnchorRequest)> = Vec::new();
+    let mut seen_accounts = HashSet::new();
+
+    for (index, req) in reqs.into_iter().enumerate() {
+        if let Err(msg) = validate_anchor_request(&req) {
+            results.push(BulkAnchorResult::rejected(
+                index,
+                BulkAnchorStatus::Invalid,
+                msg,
+            ));
+        } else if !seen_accounts.insert(req.stellar_account.clone()) {
+            results.push(BulkAnchorResult::rejected(
+                index,
+                BulkAnchorStatus::Duplicate,
+                "Stellar account appears earlier in this batch",
+            ));
+        } else {
+            valid.push((index, req));
+        }
+    }
 
-    // Broadcast the new anchor to WebSocket clients
-    broadcast_anchor_update(&app_state.ws_state, &anchor);
+    for chunk in valid.chunks(BULK_ANCHOR_CHUNK_SIZE) {
+        let chunk_reqs: Vec<CreateAnchorRequest> = chunk.iter().map(|(_, r)| r.clone()).collect();
+
+        match app_state
+            .db
+            .create_anchors_in_transaction(&chunk_reqs)
+            .await
+        {
+            Ok(created) => {
+                for ((index, _), anchor) in chunk.iter().zip(created) {
+                    match anchor {
+                        Some(anchor) => {
+                            broadcast_anchor_update(&app_state.ws_state, &anchor);
+                            results.push(BulkAnchorResult {
+                                index: *index,
+                                status: BulkAnchorStatus::Created,
+                                id: Some(anchor.id),
+                                error: None,
+                            });
+                        }
+                        None => results.push(BulkAnchorResult::rejected(
+                            *index,
+                            BulkAnchorStatus::Duplicate,
+                            "Stellar account is already registered",
+                        )),
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Bulk anchor import chunk failed: {}", e);
+                results.extend(chunk.iter().map(|(index, _)| {
+                    BulkAnchorResult::rejected(*index, BulkAnchorStatus::Failed, e.to_string())
+                }));
+            }
+        }
+    }
 
-    Ok(Json(anchor))
+    results.sort_by_key(|r| r.index);
+    let created = results
+        .iter()
+        .filter(|r| r.status == BulkAnchorStatus::Created)
+        .count();
+
+    let created_ids: Vec<&str> = results.iter().filter_map(|r| r.id.as_deref()).collect();
+    if !created_ids.is_empty() {
+        app_state
+            .db
+            .admin_audit_logger
+            .record(
+                &audit,
+                "anchor.bulk_create",
+                "anchors",
+                serde_json::json!({ "total": total, "created": created_ids }),
+            )
+            .await;
+    }
+
+    Ok(Json(BulkAnchorImportResponse {
+        total,
+        created,
+        failed: total - created,
+        results,
+    }))
 }
 
 /// PUT /api/anchors/:id/metrics - Update anchor metrics
@@ -171,10 +363,11 @@ pub struct UpdateMetricsRequest {
 pub async fn update_anchor_metrics(
     State(app_state): State<AppState>,
     Path(id): Path<Uuid>,
+    audit: AuditContext,
     Json(req): Json<UpdateMetricsRequest>,
 ) -> ApiResult<Json<crate::models::Anchor>> {
     // Verify anchor exists
-    if app_state.db.get_anchor_by_id(id).await?.is_none() {
+    let Some(previous) = app_state.db.get_anchor_by_id(id).await? else {
         let mut details = HashMap::new();
         details.insert("anchor_id".to_string(), serde_json::json!(id.to_string()));
         return Err(ApiError::not_found_with_details(
@@ -182,7 +375,7 @@ pub async fn update_anchor_metrics(
             format!("Anchor with id {} not found", id),
             details,
         ));
-    }
+    };
 
     let anchor = app_state
         .db
@@ -196,8 +389,39 @@ pub async fn update_anchor_metrics(
         )
         .await?;
 
-    // Broadcast the anchor update to WebSocket clients
-    broadcast_anchor_update(&app_state.ws_state, &anchor);
+    app_state
+        .db
+        .admin_audit_logger
+        .record(
+            &audit,
+            "anchor.update_metrics",
+            &format!("anchor:{}", anchor.id),
+            serde_json::json!({
+                "total_transactions": req.total_transactions,
+                "successful_transactions": req.successful_transactions,
+                "failed_transactions": req.failed_transactions,
+                "avg_settlement_time_ms": req.avg_settlement_time_ms,
+                "volume_usd": req.volume_usd,
+            }),
+        )
+        .await;
+
+    if previous.status == anchor.status {
+        // Broadcast the anchor update to WebSocket clients
+        broadcast_anchor_update(&app_state.ws_state, &anchor);
+    } else {
+        // Status transitions go through the event bus so webhooks fire too
+        app_state
+            .event_bus
+            .publish(DomainEvent::AnchorStatusChanged(AnchorStatusChangedEvent {
+                anchor_id: anchor.id.clone(),
+                name: anchor.name.clone(),
+                old_status: previous.status,
+                new_status: anchor.status.clone(),
+                reliability_score: anchor.reliability_score,
+                failed_txn_count: anchor.failed_transactions,
+            }));
+    }
 
     Ok(Json(anchor))
 }
@@ -233,6 +457,7 @@ pub struct CreateAssetRequest {
 pub async fn create_anchor_asset(
     State(app_state): State<AppState>,
     Path(id): Path<Uuid>,
+    audit: AuditContext,
     Json(req): Json<CreateAssetRequest>,
 ) -> ApiResult<Json<crate::models::Asset>> {
     // Verify anchor exists
@@ -246,10 +471,25 @@ pub async fn create_anchor_asset(
         ));
     }
 
+    let asset = validate_asset(&req.asset_code, Some(&req.asset_issuer))
+        .map_err(|e| ApiError::bad_request("INVALID_ASSET", e.to_string()))?;
     let asset = app_state
         .db
-        .create_asset(id, req.asset_code, req.asset_issuer)
+        .create_asset(id, asset.code, asset.issuer)
         .await?;
+    app_state
+        .db
+        .admin_audit_logger
+        .record(
+            &audit,
+            "anchor.add_asset",
+            &format!("anchor:{}", id),
+            serde_json::json!({
+                "asset_code": asset.asset_code,
+                "asset_issuer": asset.asset_issuer,
+            }),
+        )
+        .await;
 
     Ok(Json(asset))
 }
@@ -268,43 +508,217 @@ pub async fn health_check() -> impl IntoResponse {
     }))
 }
 
+/// Readiness probe: fails with 503 when the database does not answer
+pub async fn ready_check(State(state): State<AppState>) -> impl IntoResponse {
+    match state.db.health_check().await {
+        Ok(latency) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "status": "ready",
+                "database": {
+                    "status": "up",
+                    "latency_ms": latency.as_secs_f64() * 1000.0
+                }
+            })),
+        ),
+        Err(e) => {
+            tracing::warn!("Readiness check failed: {}", e);
+            (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({
+                    "status": "not_ready",
+                    "database": {
+                        "status": "down",
+                        "error": e.to_string()
+                    }
+                })),
+            )
+        }
+    }
+}
+
 /// Database pool metrics endpoint
 pub async fn pool_metrics(State(state): State<AppState>) -> impl IntoResponse {
     let metrics = state.db.pool_metrics();
     Json(metrics)
 }
 
-/// GET /api/corridors - List all corridors
+/// GET /api/corridors - List corridors, hiding dust corridors unless
+/// `include_all=true`
 pub async fn list_corridors(
     State(app_state): State<AppState>,
+    Extension(pagination): Extension<PaginationConfig>,
     Query(params): Query<ListCorridorsQuery>,
 ) -> ApiResult<Json<ListCorridorsResponse>> {
+    pagination.check_offset(params.offset, None)?;
+    let limit = pagination.page_limit(PagedResource::Corridors, params.limit);
+    if let Some(min) = params.min_volume_usd {
+        if !min.is_finite() || min < 0.0 {
+            return Err(ApiError::bad_request(
+                "INVALID_MIN_VOLUME",
+                format!("min_volume_usd must be a non-negative number, got {}", min),
+            ));
+        }
+    }
+    let min_volume_usd = if params.include_all {
+        None
+    } else {
+        Some(
+            params
+                .min_volume_usd
+                .unwrap_or_else(|| app_state.db.min_corridor_volume_usd()),
+        )
+        .filter(|v| *v > 0.0)
+    };
     let corridors = app_state
         .db
-        .list_corridors(params.limit, params.offset)
+        .list_corridors_min_volume(limit, params.offset, min_volume_usd)
         .await?;
     let total = corridors.len();
     Ok(Json(ListCorridorsResponse { corridors, total }))
 }
 
+/// Window of corridor payments used when a request gives none
+pub const DEFAULT_PAYMENT_WINDOW: &str = "24h";
+/// Longest window of corridor payments a request may ask for
+const MAX_PAYMENT_WINDOW_DAYS: i64 = 90;
+
+#[derive(Debug, Deserialize)]
+pub struct CorridorPaymentsQuery {
+    /// How far back to look, e.g. `30m`, `24h` or `7d`
+    pub window: Option<String>,
+    pub limit: Option<i64>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CorridorPaymentsResponse {
+    pub corridor_key: String,
+    pub window: String,
+    pub payments: Vec<CorridorPayment>,
+    /// Cursor for the next page, absent on the last one
+    pub next_cursor: Option<String>,
+}
+
+/// Parse a window such as `30m`, `24h` or `7d`, up to 90 days
+fn parse_payment_window(window: &str) -> Option<chrono::Duration> {
+    let window = window.trim();
+    let unit = window.chars().last()?;
+    let amount = window[..window.len() - unit.len_utf8()]
+        .parse::<i64>()
+        .ok()
+        .filter(|n| *n > 0 && *n <= MAX_PAYMENT_WINDOW_DAYS * 24 * 60)?;
+    let duration = match unit {
+        'm' => chrono::Duration::minutes(amount),
+        'h' => chrono::Duration::hours(amount),
+        'd' => chrono::Duration::days(amount),
+        _ => return None,
+    };
+    (duration <= chrono::Duration::days(MAX_PAYMENT_WINDOW_DAYS)).then_some(duration)
+}
+
+/// Source and destination assets of a `CODE:ISSUER->CODE:ISSUER` key
+fn parse_corridor_key(corridor_key: &str) -> ApiResult<(AssetId, AssetId)> {
+    let parse_asset = |asset: &str| {
+        let (code, issuer) = asset.split_once(':').ok_or_else(|| {
+            ApiError::bad_request("INVALID_ASSET_FORMAT", "Asset format must be 'CODE:ISSUER'")
+        })?;
+        validate_asset(code, Some(issuer))
+            .map_err(|e| ApiError::bad_request("INVALID_ASSET", e.to_string()))
+    };
+    let (source, destination) = corridor_key.split_once("->").ok_or_else(|| {
+        ApiError::bad_request(
+            "INVALID_CORRIDOR_FORMAT",
+            "Corridor key must be in format 'ASSET1:ISSUER1->ASSET2:ISSUER2'",
+        )
+    })?;
+    Ok((parse_asset(source)?, parse_asset(destination)?))
+}
+
+/// GET /api/corridors/:corridor_key/payments - Payments behind a corridor's
+/// metrics within `window`, newest first, paged with `cursor`
+pub async fn get_corridor_payments(
+    State(app_state): State<AppState>,
+    Extension(pagination): Extension<PaginationConfig>,
+    Path(corridor_key): Path<String>,
+    Query(params): Query<CorridorPaymentsQuery>,
+) -> ApiResult<Json<CorridorPaymentsResponse>> {
+    let (source, destination) = parse_corridor_key(&corridor_key)?;
+    let window = params
+        .window
+        .unwrap_or_else(|| DEFAULT_PAYMENT_WINDOW.to_string());
+    let duration = parse_payment_window(&window).ok_or_else(|| {
+        ApiError::bad_request(
+            "INVALID_WINDOW",
+            format!(
+                "Invalid window '{}': expected e.g. 30m, 24h or 7d, at most {}d",
+                window, MAX_PAYMENT_WINDOW_DAYS
+            ),
+        )
+    })?;
+    let cursor = params
+        .cursor
+        .as_deref()
+        .map(|raw| {
+            PaymentCursor::decode(raw).ok_or_else(|| {
+                ApiError::bad_request(
+                    "INVALID_CURSOR",
+                    format!("Invalid payment cursor '{}'", raw),
+                )
+            })
+        })
+        .transpose()?;
+    let limit = pagination.page_limit(PagedResource::Payments, params.limit);
+
+    let payments = app_state
+        .db
+        .list_corridor_payments(
+            &source,
+            &destination,
+            chrono::Utc::now() - duration,
+            cursor.as_ref(),
+            limit,
+        )
+        .await?;
+    let next_cursor = (payments.len() as i64 == limit)
+        .then(|| payments.last().map(|p| PaymentCursor::after(p).encode()))
+        .flatten();
+
+    Ok(Json(CorridorPaymentsResponse {
+        corridor_key,
+        window,
+        payments,
+        next_cursor,
+    }))
+}
+
 /// POST /api/corridors - Create a new corridor
 pub async fn create_corridor(
     State(app_state): State<AppState>,
     Json(req): Json<CreateCorridorRequest>,
 ) -> ApiResult<Json<Corridor>> {
-    if req.source_asset_code.is_empty() || req.dest_asset_code.is_empty() {
-        return Err(ApiError::bad_request(
-            "INVALID_INPUT",
-            "Asset codes cannot be empty",
-        ));
-    }
     if req.source_asset_issuer.is_empty() || req.dest_asset_issuer.is_empty() {
         return Err(ApiError::bad_request(
             "INVALID_INPUT",
             "Asset issuers cannot be empty",
         ));
     }
-    let corridor = app_state.db.create_corridor(req).await?;
+    let source = validate_asset(&req.source_asset_code, Some(&req.source_asset_issuer));
+    let dest = validate_asset(&req.dest_asset_code, Some(&req.dest_asset_issuer));
+    let (source, dest) = source
+        .and_then(|source| Ok((source, dest?)))
+        .map_err(|e| ApiError::bad_request("INVALID_ASSET", e.to_string()))?;
+    let corridor = app_state
+        .db
+        .create_corridor(CreateCorridorRequest {
+            source_asset_code: source.code,
+            source_asset_issuer: source.issuer,
+            dest_asset_code: dest.code,
+            dest_asset_issuer: dest.issuer,
+            ..req
+        })
+        .await?;
 
     // Broadcast the new corridor to WebSocket clients
     broadcast_corridor_update(&app_state.ws_state, &corridor);
@@ -312,6 +726,36 @@ pub async fn create_corridor(
     Ok(Json(corridor))
 }
 
+/// DELETE /api/corridors/:id - Soft-delete a corridor
+pub async fn delete_corridor(
+    State(app_state): State<AppState>,
+    audit: AuditContext,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let Some(corridor) = app_state.db.delete_corridor(id).await? else {
+        let mut details = HashMap::new();
+        details.insert("corridor_id".to_string(), serde_json::json!(id.to_string()));
+        return Err(ApiError::not_found_with_details(
+            "CORRIDOR_NOT_FOUND",
+            format!("Corridor with id {} not found", id),
+            details,
+        ));
+    };
+
+    app_state
+        .db
+        .admin_audit_logger
+        .record(
+            &audit,
+            "corridor.delete",
+            &format!("corridor:{}", id),
+            serde_json::json!({ "corridor_key": corridor.to_string_key() }),
+        )
+        .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 /// PUT /api/corridors/:id/metrics-from-transactions - Compute metrics from transactions and persist
 #[derive(Debug, Deserialize)]
 pub struct UpdateCorridorMetricsFromTxns {
@@ -0,0 +1,204 @@
+//! Anchor activity derived from an account's full operation stream.
+//!
+//! Payments are only part of what an anchor does: issuing and accepting
+//! trustlines and market making on the DEX also show it is active. Each
+//! operation is classified by its Horizon type and weighted by category, so a
+//! failed payment weighs more on the success rate than a failed offer.
+
+use crate::rpc::HorizonOperation;
+
+/// Where anchor metrics are derived from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnchorMetricsSource {
+    /// The anchor's payments only
+    #[default]
+    Payments,
+    /// Every operation of the anchor's account
+    Operations,
+}
+
+impl AnchorMetricsSource {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "payments" => Some(Self::Payments),
+            "operations" => Some(Self::Operations),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for AnchorMetricsSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| format!("unknown anchor metrics source '{}'", s))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OperationCategory {
+    /// Value moved: payments, path payments, account creation, claimable
+    /// balances
+    Payment,
+    /// Trustlines opened, changed or authorized
+    Trustline,
+    /// DEX offers
+    Offer,
+    /// Anything else, e.g. `set_options` or `manage_data`
+    Other,
+}
+
+impl OperationCategory {
+    pub fn classify(operation_type: &str) -> Self {
+        match operation_type {
+            "payment"
+            | "path_payment_strict_receive"
+            | "path_payment_strict_send"
+            | "path_payment"
+            | "create_account"
+            | "create_claimable_balance"
+            | "claim_claimable_balance" => Self::Payment,
+            "change_trust" | "allow_trust" | "set_trust_line_flags" => Self::Trustline,
+            "manage_sell_offer"
+            | "manage_buy_offer"
+            | "create_passive_sell_offer"
+            | "manage_offer"
+            | "create_passive_offer" => Self::Offer,
+            _ => Self::Other,
+        }
+    }
+
+    /// Weight of one operation of this category in the activity metrics
+    pub fn weight(&self) -> f64 {
+        match self {
+            Self::Payment => 1.0,
+            Self::Offer => 0.5,
+            Self::Trustline => 0.5,
+            Self::Other => 0.25,
+        }
+    }
+}
+
+/// Classified operations of one account
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OperationActivity {
+    pub payments: u64,
+    pub trustlines: u64,
+    pub offers: u64,
+    pub other: u64,
+    /// Operations whose transaction failed, of any category
+    pub failed: u64,
+    /// Summed payment amounts of successful payment operations
+    pub payment_volume: f64,
+    /// Summed category weights of all operations
+    pub weighted_total: f64,
+    /// Summed category weights of successful operations
+    pub weighted_successful: f64,
+}
+
+impl OperationActivity {
+    pub fn from_operations(operations: &[HorizonOperation]) -> Self {
+        let mut activity = Self::default();
+        for op in operations {
+            let category = OperationCategory::classify(&op.operation_type);
+            match category {
+                OperationCategory::Payment => activity.payments += 1,
+                OperationCategory::Trustline => activity.trustlines += 1,
+                OperationCategory::Offer => activity.offers += 1,
+                OperationCategory::Other => activity.other += 1,
+            }
+
+            activity.weighted_total += category.weight();
+            // Horizon omits the flag when failed transactions are excluded
+            if op.transaction_successful == Some(false) {
+                activity.failed += 1;
+                continue;
+            }
+            activity.weighted_successful += category.weight();
+            if category == OperationCategory::Payment {
+                activity.payment_volume += op
+                    .amount
+                    .as_deref()
+                    .and_then(|a| a.parse::<f64>().ok())
+                    .unwrap_or(0.0);
+            }
+        }
+        activity
+    }
+
+    pub fn total(&self) -> u64 {
+        self.payments + self.trustlines + self.offers + self.other
+    }
+
+    pub fn successful(&self) -> u64 {
+        self.total() - self.failed
+    }
+
+    /// Weighted success rate in percent, 0.0 without operations
+    pub fn success_rate(&self) -> f64 {
+        if self.weighted_total > 0.0 {
+            self.weighted_successful / self.weighted_total * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn operation(operation_type: &str, amount: Option<&str>, successful: bool) -> HorizonOperation {
+        HorizonOperation {
+            id: "1".to_string(),
+            paging_token: "1".to_string(),
+            transaction_hash: "tx".to_string(),
+            source_account: "GANCHOR".to_string(),
+            operation_type: operation_type.to_string(),
+            created_at: "2026-01-22T10:30:00Z".to_string(),
+            account: None,
+            into: None,
+            amount: amount.map(str::to_string),
+            funder: None,
+            transaction_successful: Some(successful),
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            OperationCategory::classify("path_payment_strict_send"),
+            OperationCategory::Payment
+        );
+        assert_eq!(
+            OperationCategory::classify("change_trust"),
+            OperationCategory::Trustline
+        );
+        assert_eq!(
+            OperationCategory::classify("manage_buy_offer"),
+            OperationCategory::Offer
+        );
+        assert_eq!(
+            OperationCategory::classify("set_options"),
+            OperationCategory::Other
+        );
+    }
+
+    #[test]
+    fn test_failed_operations_are_weighted_by_category() {
+        let activity = OperationActivity::from_operations(&[
+            operation("payment", Some("10.5"), true),
+            operation("payment", Some("99"), false),
+            operation("manage_sell_offer", None, true),
+            operation("change_trust", None, false),
+        ]);
+        assert_eq!(activity.payments, 2);
+        assert_eq!(activity.failed, 2);
+        assert_eq!(activity.successful(), 2);
+        assert_eq!(activity.payment_volume, 10.5);
+        // 1.5 of 3.0 weighted operations succeeded
+        assert!((activity.success_rate() - 50.0).abs() < 1e-9);
+
+        assert_eq!(OperationActivity::from_operations(&[]).success_rate(), 0.0);
+    }
+}
@@ -0,0 +1,185 @@
+//! Asset allowlist and denylist consulted while ingesting payments.
+//!
+//! Entries are `CODE:ISSUER` for one asset or a bare `CODE` for every issuer
+//! of that code. With an allowlist set only matching assets are stored; the
+//! denylist is applied on top of it. Lumens are always stored.
+
+use std::collections::HashSet;
+use tracing::warn;
+
+use crate::models::asset::{
+    normalize_asset_code, MAX_ASSET_CODE_LEN, NATIVE_ASSET_CODE, NATIVE_ASSET_ISSUER,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetFilter {
+    /// `None` allows every asset not denied
+    allow: Option<AssetRules>,
+    deny: AssetRules,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct AssetRules {
+    /// Codes matched for any issuer
+    codes: HashSet<String>,
+    /// `(code, issuer)` pairs
+    assets: HashSet<(String, String)>,
+}
+
+impl AssetRules {
+    fn parse(list: &str) -> Self {
+        let mut rules = Self::default();
+        for entry in list.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            match AssetFilter::parse_entry(entry) {
+                Some((code, issuer)) => rules.insert(code, issuer),
+                None => warn!("Ignoring invalid asset filter entry '{}'", entry),
+            }
+        }
+        rules
+    }
+
+    fn insert(&mut self, code: String, issuer: Option<String>) {
+        match issuer {
+            Some(issuer) => self.assets.insert((code, issuer)),
+            None => self.codes.insert(code),
+        };
+    }
+
+    fn matches(&self, code: &str, issuer: &str) -> bool {
+        self.codes.contains(code)
+            || self
+                .assets
+                .contains(&(code.to_string(), issuer.to_string()))
+    }
+}
+
+impl AssetFilter {
+    /// Filter from comma-separated lists; an empty allowlist allows everything
+    pub fn new(allowlist: &str, denylist: &str) -> Self {
+        let allow = AssetRules::parse(allowlist);
+        Self {
+            allow: (allow != AssetRules::default()).then_some(allow),
+            deny: AssetRules::parse(denylist),
+        }
+    }
+
+    /// Parse a `CODE:ISSUER` or bare `CODE` list entry; `None` when the code
+    /// is not 1-12 ASCII letters and digits or the issuer is empty
+    pub fn parse_entry(entry: &str) -> Option<(String, Option<String>)> {
+        let (code, issuer) = match entry.split_once(':') {
+            Some((code, issuer)) => (normalize_asset_code(code), Some(issuer.trim())),
+            None => (normalize_asset_code(entry), None),
+        };
+        let valid_code = !code.is_empty()
+            && code.len() <= MAX_ASSET_CODE_LEN
+            && code.chars().all(|c| c.is_ascii_alphanumeric());
+        if !valid_code || issuer == Some("") {
+            return None;
+        }
+        Some((code, issuer.map(str::to_string)))
+    }
+
+    /// Add an asset to the allowlist; from then on only allowed assets pass
+    pub fn with_allowed(mut self, code: String, issuer: Option<String>) -> Self {
+        self.allow
+            .get_or_insert_with(AssetRules::default)
+            .insert(code, issuer);
+        self
+    }
+
+    /// Add an asset to the denylist
+    pub fn with_denied(mut self, code: String, issuer: Option<String>) -> Self {
+        self.deny.insert(code, issuer);
+        self
+    }
+
+    /// Whether payments in this asset should be stored
+    pub fn allows(&self, code: Option<&str>, issuer: Option<&str>) -> bool {
+        let code = code.map(normalize_asset_code).unwrap_or_default();
+        let issuer = issuer.map(str::trim).unwrap_or_default();
+        let is_native = issuer.is_empty() || issuer == NATIVE_ASSET_ISSUER;
+        if is_native && (code.is_empty() || code.eq_ignore_ascii_case(NATIVE_ASSET_CODE)) {
+            return true;
+        }
+
+        if self.deny.matches(&code, issuer) {
+            return false;
+        }
+        self.allow
+            .as_ref()
+            .is_none_or(|allow| allow.matches(&code, issuer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CIRCLE: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+    const SPAMMER: &str = "GSPAMXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX";
+
+    #[test]
+    fn test_empty_filter_allows_everything() {
+        let filter = AssetFilter::default();
+        assert!(filter.allows(Some("USDC"), Some(CIRCLE)));
+        assert!(filter.allows(Some("SPAM"), Some(SPAMMER)));
+        assert_eq!(AssetFilter::new(" , ", ""), filter);
+    }
+
+    #[test]
+    fn test_allowlist() {
+        let filter = AssetFilter::new(&format!(" USDC:{}, EURC", CIRCLE), "");
+        assert!(filter.allows(Some("USDC"), Some(CIRCLE)));
+        assert!(!filter.allows(Some("USDC"), Some(SPAMMER)));
+        // Codes are case-sensitive, so `usdc` is another asset
+        assert!(!filter.allows(Some("usdc"), Some(CIRCLE)));
+        assert!(filter.allows(Some("EURC"), Some(SPAMMER)));
+        assert!(!filter.allows(Some("SPAM"), Some(SPAMMER)));
+    }
+
+    #[test]
+    fn test_denylist_overrides_allowlist() {
+        let filter = AssetFilter::new("USDC", &format!("USDC:{},SPAM", SPAMMER));
+        assert!(filter.allows(Some("USDC"), Some(CIRCLE)));
+        assert!(!filter.allows(Some("USDC"), Some(SPAMMER)));
+        assert!(!filter.allows(Some("SPAM"), Some(CIRCLE)));
+        assert!(filter.allows(Some("spam"), Some(CIRCLE)));
+
+        let filter = AssetFilter::new("", "SPAM");
+        assert!(filter.allows(Some("BRL"), Some(CIRCLE)));
+        assert!(!filter.allows(Some("SPAM"), Some(SPAMMER)));
+    }
+
+    #[test]
+    fn test_parse_entry() {
+        assert_eq!(
+            AssetFilter::parse_entry(&format!(" USDC : {} ", CIRCLE)),
+            Some(("USDC".to_string(), Some(CIRCLE.to_string())))
+        );
+        assert_eq!(
+            AssetFilter::parse_entry("native"),
+            Some(("XLM".to_string(), None))
+        );
+        for entry in ["", "USD-C", "TOOLONGASSETCODE", "USDC:", ":GABC"] {
+            assert_eq!(AssetFilter::parse_entry(entry), None, "{}", entry);
+        }
+
+        let filter = AssetFilter::default()
+            .with_allowed("USDC".to_string(), None)
+            .with_denied("USDC".to_string(), Some(SPAMMER.to_string()));
+        assert_eq!(
+            filter,
+            AssetFilter::new("USDC", &format!("USDC:{}", SPAMMER))
+        );
+    }
+
+    #[test]
+    fn test_native_is_always_allowed() {
+        let filter = AssetFilter::new("USDC", "XLM,native");
+        assert!(filter.allows(None, None));
+        assert!(filter.allows(Some("XLM"), Some("native")));
+        assert!(filter.allows(Some("native"), None));
+        // An issued asset named XLM is not lumens
+        assert!(!filter.allows(Some("XLM"), Some(SPAMMER)));
+    }
+}
@@ -1,19 +1,207 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, TimeZone, Utc};
-use sqlx::SqlitePool;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tracing::{info, warn};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::{debug, error, info, warn};
 
+use crate::ingestion::asset_filter::AssetFilter;
+use crate::ingestion::path_payment_failures::{
+    detect_path_payment_failures, insert_path_payment_failure,
+};
+use crate::models::timestamp::{format_timestamp, parse_timestamp};
+use crate::observability::metrics as obs_metrics;
 use crate::rpc::{GetLedgersResult, RpcLedger, StellarRpcClient};
 use crate::services::account_merge_detector::AccountMergeDetector;
 use crate::services::fee_bump_tracker::FeeBumpTrackerService;
 
+/// Ledgers fetched per request while backfilling
+const BACKFILL_BATCH_SIZE: u32 = 50;
+
 /// Ledger ingestion service that fetches and persists ledgers sequentially
 pub struct LedgerIngestionService {
     rpc_client: Arc<StellarRpcClient>,
     fee_bump_tracker: Arc<FeeBumpTrackerService>,
     account_merge_detector: Arc<AccountMergeDetector>,
     pool: SqlitePool,
+    asset_filter: AssetFilter,
+    lag: AtomicU64,
+}
+
+/// Batch sizing for polling ingestion
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchSizeConfig {
+    pub initial: u32,
+    pub min: u32,
+    pub max: u32,
+    /// A batch taking longer than this counts as a failure
+    pub timeout: Duration,
+}
+
+impl Default for BatchSizeConfig {
+    fn default() -> Self {
+        Self {
+            initial: 5,
+            min: 1,
+            max: 50,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Batch size that doubles while ingestion is behind the head and halves
+/// after errors or timeouts, bounded by the configured min/max
+#[derive(Debug, Clone)]
+pub struct AdaptiveBatchSize {
+    current: u32,
+    min: u32,
+    max: u32,
+}
+
+impl AdaptiveBatchSize {
+    pub fn new(config: &BatchSizeConfig) -> Self {
+        let min = config.min.max(1);
+        let max = config.max.max(min);
+        let sizer = Self {
+            current: config.initial.clamp(min, max),
+            min,
+            max,
+        };
+        sizer.publish();
+        sizer
+    }
+
+    pub fn current(&self) -> u32 {
+        self.current
+    }
+
+    /// Grow after a successful batch that left ingestion behind the head
+    pub fn record_success(&mut self, progress: &IngestionProgress) {
+        if !progress.caught_up() {
+            self.current = self.current.saturating_mul(2).min(self.max);
+            self.publish();
+        }
+    }
+
+    /// Shrink after a failed or timed-out batch
+    pub fn record_failure(&mut self) {
+        self.current = (self.current / 2).max(self.min);
+        self.publish();
+    }
+
+    fn publish(&self) {
+        obs_metrics::set_ledger_ingestion_batch_size(i64::from(self.current));
+    }
+}
+
+/// Tuning for follow mode, which tracks the ledger head as ledgers close
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FollowConfig {
+    pub batch_size: u32,
+    /// Poll interval once caught up to the network head
+    pub poll_interval: Duration,
+    /// Upper bound for backoff while the head is not advancing or RPC fails
+    pub max_backoff: Duration,
+}
+
+impl Default for FollowConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 20,
+            poll_interval: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl FollowConfig {
+    /// Delay before the next batch. Batches run back to back only while
+    /// ledgers are being ingested or skipped behind the head; a batch that
+    /// advances past no ledger backs off even when lagging, so a stalled RPC
+    /// node is not polled in a tight loop.
+    fn next_delay(&self, progress: &IngestionProgress, backoff: &mut Duration) -> Duration {
+        if !progress.advanced() {
+            let delay = *backoff;
+            *backoff = (*backoff * 2).min(self.max_backoff);
+            return delay;
+        }
+
+        *backoff = self.poll_interval;
+        if progress.caught_up() {
+            self.poll_interval
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+/// Outcome of a single ingestion batch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IngestionProgress {
+    /// Ledgers processed in this batch
+    pub ingested: u64,
+    /// Ledgers skipped because they were already processed
+    pub skipped: u64,
+    /// Last ledger ingested so far, if any
+    pub last_ledger: Option<u64>,
+    /// Latest ledger known to the RPC node
+    pub latest_ledger: u64,
+    /// Ledgers between the last ingested ledger and the head
+    pub lag: u64,
+}
+
+impl IngestionProgress {
+    pub fn caught_up(&self) -> bool {
+        self.lag == 0
+    }
+
+    /// Whether the batch moved past any ledgers, processing or skipping them
+    pub fn advanced(&self) -> bool {
+        self.ingested + self.skipped > 0
+    }
+}
+
+/// Rows written while processing a single ledger
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct LedgerWrites {
+    payments: u64,
+    fee_bumps: u64,
+    merges: u64,
+    account_churn: u64,
+    path_payment_failures: u64,
+}
+
+/// Outcome of processing one fetched page of ledgers
+struct LedgerBatch {
+    /// Ledgers newly processed
+    processed: u64,
+    /// Ledgers skipped because they were already processed
+    skipped: u64,
+    /// Ledger that failed and stopped the batch, with its error
+    failure: Option<(u64, anyhow::Error)>,
+}
+
+/// Summary of a historical backfill
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct BackfillReport {
+    pub start_ledger: u64,
+    pub end_ledger: u64,
+    /// Ledgers processed during this run
+    pub ledgers_processed: u64,
+    /// Ledgers skipped because they were already processed
+    pub ledgers_skipped: u64,
+    /// Ledgers that failed and can be retried by re-running the backfill
+    pub ledgers_failed: u64,
+    pub payments: u64,
+    pub fee_bumps: u64,
+    pub merges: u64,
+    /// Accounts re-created after an earlier merge
+    pub account_churn: u64,
+    pub path_payment_failures: u64,
 }
 
 /// Represents a payment operation extracted from a ledger
@@ -41,11 +229,98 @@ impl LedgerIngestionService {
             fee_bump_tracker,
             account_merge_detector,
             pool,
+            asset_filter: AssetFilter::default(),
+            lag: AtomicU64::new(0),
         }
     }
 
+    /// Store only the payments `filter` allows instead of every asset
+    pub fn with_asset_filter(mut self, filter: AssetFilter) -> Self {
+        self.asset_filter = filter;
+        self
+    }
+
+    /// Ledgers behind the network head as of the last batch
+    pub fn lag_behind_head(&self) -> u64 {
+        self.lag.load(Ordering::Relaxed)
+    }
+
     /// I'm running the main ingestion loop - fetches ledgers and persists them
     pub async fn run_ingestion(&self, batch_size: u32) -> Result<u64> {
+        Ok(self.ingest_batch(batch_size).await?.ingested)
+    }
+
+    /// Ingest one batch sized by `sizer`, adjusting it from the outcome
+    pub async fn ingest_adaptive(
+        &self,
+        sizer: &mut AdaptiveBatchSize,
+        timeout: Duration,
+    ) -> Result<IngestionProgress> {
+        let batch_size = sizer.current();
+        match tokio::time::timeout(timeout, self.ingest_batch(batch_size)).await {
+            Ok(Ok(progress)) => {
+                sizer.record_success(&progress);
+                Ok(progress)
+            }
+            Ok(Err(e)) => {
+                sizer.record_failure();
+                Err(e)
+            }
+            Err(_) => {
+                sizer.record_failure();
+                Err(anyhow::anyhow!(
+                    "Ingesting {} ledgers timed out after {:?}",
+                    batch_size,
+                    timeout
+                ))
+            }
+        }
+    }
+
+    /// Follow the ledger head: ingest back-to-back while behind, poll at
+    /// `poll_interval` once caught up, and back off exponentially (up to
+    /// `max_backoff`) while the head is idle or RPC calls fail.
+    pub async fn run_follow(&self, config: &FollowConfig, mut shutdown: broadcast::Receiver<()>) {
+        info!(
+            "Starting ledger ingestion in follow mode (batch size {})",
+            config.batch_size
+        );
+        let mut backoff = config.poll_interval;
+
+        loop {
+            let delay = tokio::select! {
+                result = self.ingest_batch(config.batch_size) => match result {
+                    Ok(progress) => {
+                        obs_metrics::record_background_job("ledger_ingestion", "success");
+                        config.next_delay(&progress, &mut backoff)
+                    }
+                    Err(e) => {
+                        error!("Ledger ingestion failed: {}", e);
+                        obs_metrics::record_background_job("ledger_ingestion", "error");
+                        let delay = backoff;
+                        backoff = (backoff * 2).min(config.max_backoff);
+                        delay
+                    }
+                },
+                _ = shutdown.recv() => break,
+            };
+
+            if delay.is_zero() {
+                tokio::task::yield_now().await;
+                continue;
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown.recv() => break,
+            }
+        }
+
+        info!("Ledger follow mode stopped");
+    }
+
+    /// Ingest the next batch of ledgers and report progress against the head
+    pub async fn ingest_batch(&self, batch_size: u32) -> Result<IngestionProgress> {
         let cursor = self.get_cursor().await?;
         let start_ledger = match self.get_last_ledger().await? {
             Some(l) => Some(l + 1),
@@ -70,106 +345,265 @@ impl LedgerIngestionService {
             .await
             .context("Failed to fetch ledgers")?;
 
-        let count = self.process_ledgers(&result).await?;
+        let batch = self.process_ledgers(&result).await;
+        let (last_ledger, new_cursor) = match &batch.failure {
+            // Stop just before the failed ledger so the next batch retries it;
+            // getLedgers cursors are ledger sequences
+            Some((failed, _)) => {
+                let last = failed.checked_sub(1);
+                (last, last.map(|l| l.to_string()))
+            }
+            None => (
+                result
+                    .ledgers
+                    .last()
+                    .map(|l| l.sequence)
+                    .or_else(|| start_ledger.and_then(|s| s.checked_sub(1))),
+                result.cursor.clone(),
+            ),
+        };
 
         // I'm saving cursor for restart safety
-        if let Some(new_cursor) = &result.cursor {
-            self.save_cursor(new_cursor, result.ledgers.last().map(|l| l.sequence))
-                .await?;
+        if let Some(new_cursor) = &new_cursor {
+            self.save_cursor(new_cursor, last_ledger).await?;
+        }
+
+        let lag = result
+            .latest_ledger
+            .saturating_sub(last_ledger.unwrap_or_default());
+        self.lag.store(lag, Ordering::Relaxed);
+        obs_metrics::set_ledger_ingestion_lag(i64::try_from(lag).unwrap_or(i64::MAX));
+
+        if let Some((failed, e)) = batch.failure {
+            return Err(e.context(format!(
+                "Failed to process ledger {} after ingesting {} ledgers",
+                failed, batch.processed
+            )));
         }
 
-        Ok(count)
+        Ok(IngestionProgress {
+            ingested: batch.processed,
+            skipped: batch.skipped,
+            last_ledger,
+            latest_ledger: result.latest_ledger,
+            lag,
+        })
     }
 
-    /// I'm processing and persisting fetched ledgers
-    async fn process_ledgers(&self, result: &GetLedgersResult) -> Result<u64> {
-        let mut count = 0u64;
+    /// I'm processing and persisting fetched ledgers in order, skipping ones
+    /// already processed and stopping at the first failure
+    async fn process_ledgers(&self, result: &GetLedgersResult) -> LedgerBatch {
+        let mut batch = LedgerBatch {
+            processed: 0,
+            skipped: 0,
+            failure: None,
+        };
 
         for ledger in &result.ledgers {
-            if let Err(e) = self.persist_ledger(ledger).await {
-                warn!("Failed to persist ledger {}: {}", ledger.sequence, e);
-                continue;
-            }
-
-            // Fetch real payments from Horizon
-            match self
-                .rpc_client
-                .fetch_payments_for_ledger(ledger.sequence)
-                .await
-            {
-                Ok(payments) => {
-                    for payment in payments {
-                        // Convert RPC Payment to ExtractedPayment
-                        // Uses helper methods to support both old and new Horizon formats
-                        let extracted = ExtractedPayment {
-                            ledger_sequence: ledger.sequence,
-                            transaction_hash: payment.transaction_hash.clone(),
-                            operation_type: "payment".to_string(), // Horizon 'payments' endpoint returns payments
-                            source_account: payment.source_account.clone(),
-                            destination: payment.get_destination().unwrap_or_default(),
-                            asset_code: payment.get_asset_code(),
-                            asset_issuer: payment.get_asset_issuer(),
-                            amount: payment.get_amount(),
-                        };
-
-                        if let Err(e) = self.persist_payment(&extracted).await {
-                            warn!("Failed to persist payment: {}", e);
-                        }
-                    }
-                }
+            match self.process_ledger(ledger).await {
+                Ok(Some(_)) => batch.processed += 1,
+                Ok(None) => batch.skipped += 1,
                 Err(e) => {
-                    warn!(
-                        "Failed to fetch payments for ledger {}: {}",
-                        ledger.sequence, e
-                    );
-                    // Non-fatal, continue ingesting ledgers
+                    warn!("Failed to process ledger {}: {}", ledger.sequence, e);
+                    batch.failure = Some((ledger.sequence, e));
+                    break;
                 }
             }
+        }
 
-            // Fetch and process transactions for fee bumps
-            match self
-                .rpc_client
-                .fetch_transactions_for_ledger(ledger.sequence)
-                .await
-            {
-                Ok(transactions) => {
-                    if let Err(e) = self
-                        .fee_bump_tracker
-                        .process_transactions(&transactions)
-                        .await
-                    {
-                        warn!("Failed to process transactions for fee bumps: {}", e);
-                    }
-                }
-                Err(e) => {
-                    warn!(
-                        "Failed to fetch transactions for ledger {}: {}",
-                        ledger.sequence, e
-                    );
-                }
+        info!(
+            "Processed {} ledgers, skipped {} already processed",
+            batch.processed, batch.skipped
+        );
+        batch
+    }
+
+    /// Process one ledger, committing all of its writes together with the
+    /// processed-ledger marker. Returns `None` if it was already processed.
+    async fn process_ledger(&self, ledger: &RpcLedger) -> Result<Option<LedgerWrites>> {
+        if self.is_ledger_processed(ledger.sequence).await? {
+            debug!("Ledger {} already processed, skipping", ledger.sequence);
+            return Ok(None);
+        }
+
+        // Fetch everything up front so the database transaction stays short
+        let payments = self
+            .rpc_client
+            .fetch_payments_for_ledger(ledger.sequence)
+            .await
+            .context("Failed to fetch payments")?;
+        let transactions = self
+            .rpc_client
+            .fetch_transactions_for_ledger(ledger.sequence)
+            .await
+            .context("Failed to fetch transactions")?;
+        let activity = self
+            .account_merge_detector
+            .detect_ledger_activity(ledger.sequence)
+            .await
+            .context("Failed to fetch account merge operations")?;
+
+        let failures = detect_path_payment_failures(ledger.sequence, &payments, &transactions);
+        let failed_hashes: HashSet<&str> = transactions
+            .iter()
+            .filter(|tx| !tx.successful)
+            .map(|tx| tx.hash.as_str())
+            .collect();
+
+        let mut db_tx = self.pool.begin().await?;
+
+        Self::persist_ledger(&mut db_tx, ledger).await?;
+
+        let mut writes = LedgerWrites::default();
+
+        // Failed operations are recorded separately below, not as payments
+        for payment in payments
+            .iter()
+            .filter(|p| !failed_hashes.contains(p.transaction_hash.as_str()))
+            .filter(|p| {
+                self.asset_filter.allows(
+                    p.get_asset_code().as_deref(),
+                    p.get_asset_issuer().as_deref(),
+                )
+            })
+        {
+            // Convert RPC Payment to ExtractedPayment
+            // Uses helper methods to support both old and new Horizon formats
+            let extracted = ExtractedPayment {
+                ledger_sequence: ledger.sequence,
+                transaction_hash: payment.transaction_hash.clone(),
+                operation_type: "payment".to_string(), // Horizon 'payments' endpoint returns payments
+                source_account: payment.source_account.clone(),
+                destination: payment.get_destination().unwrap_or_default(),
+                asset_code: payment.get_asset_code(),
+                asset_issuer: payment.get_asset_issuer(),
+                amount: payment.get_amount(),
+            };
+            Self::persist_payment(&mut db_tx, &extracted).await?;
+            writes.payments += 1;
+        }
+
+        for failure in &failures {
+            if insert_path_payment_failure(&mut db_tx, failure).await? {
+                writes.path_payment_failures += 1;
             }
+        }
+
+        writes.fee_bumps = self
+            .fee_bump_tracker
+            .persist_transactions_in(&mut db_tx, &transactions)
+            .await?;
+
+        for merge in &activity.merges {
+            if AccountMergeDetector::insert_merge_event(&mut *db_tx, merge).await? {
+                writes.merges += 1;
+            }
+        }
+
+        // After the merges, so an account merged and re-created in this ledger is caught
+        for creation in &activity.creations {
+            if AccountMergeDetector::record_churn(&mut *db_tx, creation).await? {
+                writes.account_churn += 1;
+            }
+        }
+
+        sqlx::query("INSERT INTO processed_ledgers (sequence) VALUES ($1)")
+            .bind(ledger.sequence as i64)
+            .execute(&mut *db_tx)
+            .await?;
+
+        db_tx.commit().await?;
+        Ok(Some(writes))
+    }
+
+    /// Reprocess the inclusive ledger range `start..=end` without moving the
+    /// ingestion cursor. Ledgers already processed are skipped, so re-running
+    /// a backfill is safe and only retries ledgers that previously failed.
+    pub async fn backfill_range(&self, start: u64, end: u64) -> Result<BackfillReport> {
+        if start > end {
+            anyhow::bail!("Invalid backfill range: start {} > end {}", start, end);
+        }
 
-            if let Err(e) = self
-                .account_merge_detector
-                .process_ledger_operations(ledger.sequence)
+        info!("Backfilling ledgers {}..={}", start, end);
+        let mut report = BackfillReport {
+            start_ledger: start,
+            end_ledger: end,
+            ..BackfillReport::default()
+        };
+
+        let mut next = start;
+        while next <= end {
+            let limit = u32::try_from(end - next + 1)
+                .unwrap_or(u32::MAX)
+                .min(BACKFILL_BATCH_SIZE);
+            let result = self
+                .rpc_client
+                .fetch_ledgers(Some(next), limit, None)
                 .await
-            {
+                .with_context(|| format!("Failed to fetch ledgers from {}", next))?;
+
+            let ledgers: Vec<&RpcLedger> = result
+                .ledgers
+                .iter()
+                .filter(|l| l.sequence >= next && l.sequence <= end)
+                .collect();
+            let Some(last) = ledgers.last().map(|l| l.sequence) else {
                 warn!(
-                    "Failed to process account merge operations for ledger {}: {}",
-                    ledger.sequence, e
+                    "No ledgers returned from {} (latest available: {}), stopping backfill",
+                    next, result.latest_ledger
                 );
+                break;
+            };
+
+            for ledger in ledgers {
+                match self.process_ledger(ledger).await {
+                    Ok(Some(writes)) => {
+                        report.ledgers_processed += 1;
+                        report.payments += writes.payments;
+                        report.fee_bumps += writes.fee_bumps;
+                        report.merges += writes.merges;
+                        report.account_churn += writes.account_churn;
+                        report.path_payment_failures += writes.path_payment_failures;
+                    }
+                    Ok(None) => report.ledgers_skipped += 1,
+                    Err(e) => {
+                        warn!("Failed to backfill ledger {}: {}", ledger.sequence, e);
+                        report.ledgers_failed += 1;
+                    }
+                }
             }
 
-            count += 1;
+            next = last + 1;
         }
 
-        info!("Processed {} ledgers", count);
-        Ok(count)
+        info!(
+            "Backfill {}..={} complete: {} processed, {} skipped, {} failed",
+            start, end, report.ledgers_processed, report.ledgers_skipped, report.ledgers_failed
+        );
+        Ok(report)
+    }
+
+    /// Whether a ledger's writes have already been committed
+    pub async fn is_ledger_processed(&self, sequence: u64) -> Result<bool> {
+        let row: Option<(i64,)> =
+            sqlx::query_as("SELECT sequence FROM processed_ledgers WHERE sequence = $1")
+                .bind(sequence as i64)
+                .fetch_optional(&self.pool)
+                .await?;
+        Ok(row.is_some())
+    }
+
+    /// Highest fully-processed ledger sequence
+    pub async fn last_processed_ledger(&self) -> Result<Option<u64>> {
+        let row: (Option<i64>,) = sqlx::query_as("SELECT MAX(sequence) FROM processed_ledgers")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.0.map(|s| s as u64))
     }
 
     /// I'm persisting a single ledger to the database
-    async fn persist_ledger(&self, ledger: &RpcLedger) -> Result<()> {
-        let close_time = self.parse_ledger_time(&ledger.ledger_close_time)?;
+    async fn persist_ledger(conn: &mut SqliteConnection, ledger: &RpcLedger) -> Result<()> {
+        let close_time = Self::parse_ledger_time(&ledger.ledger_close_time)?;
 
         sqlx::query(
             r#"
@@ -180,10 +614,10 @@ impl LedgerIngestionService {
         )
         .bind(ledger.sequence as i64)
         .bind(&ledger.hash)
-        .bind(close_time)
+        .bind(format_timestamp(&close_time))
         .bind(0i32) // I'd get real counts from XDR parsing
         .bind(0i32)
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
 
         // I'm also storing a placeholder transaction for the ledger
@@ -201,14 +635,17 @@ impl LedgerIngestionService {
         .bind(100i64)
         .bind(1i32)
         .bind(true)
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
 
         Ok(())
     }
 
     /// I'm persisting an extracted payment to the database
-    async fn persist_payment(&self, payment: &ExtractedPayment) -> Result<()> {
+    async fn persist_payment(
+        conn: &mut SqliteConnection,
+        payment: &ExtractedPayment,
+    ) -> Result<()> {
         sqlx::query(
             r#"
             INSERT INTO ledger_payments (ledger_sequence, transaction_hash, operation_type, source_account, destination, asset_code, asset_issuer, amount)
@@ -223,7 +660,7 @@ impl LedgerIngestionService {
         .bind(&payment.asset_code)
         .bind(&payment.asset_issuer)
         .bind(&payment.amount)
-        .execute(&self.pool)
+        .execute(conn)
         .await?;
 
         Ok(())
@@ -267,9 +704,75 @@ impl LedgerIngestionService {
         Ok(())
     }
 
-    fn parse_ledger_time(&self, timestamp_str: &str) -> Result<DateTime<Utc>> {
-        // I'm parsing unix timestamp string to DateTime
-        let ts: i64 = timestamp_str.parse().unwrap_or(0);
-        Ok(Utc.timestamp_opt(ts, 0).single().unwrap_or_else(Utc::now))
+    fn parse_ledger_time(timestamp_str: &str) -> Result<DateTime<Utc>> {
+        // Close times are unix seconds from the RPC, RFC 3339 from Horizon
+        parse_timestamp(timestamp_str).context("Failed to parse ledger close time")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(ingested: u64, lag: u64) -> IngestionProgress {
+        IngestionProgress {
+            ingested,
+            skipped: 0,
+            last_ledger: Some(100),
+            latest_ledger: 100 + lag,
+            lag,
+        }
+    }
+
+    #[test]
+    fn test_follow_runs_back_to_back_while_catching_up() {
+        let config = FollowConfig::default();
+        let mut backoff = config.max_backoff;
+
+        assert_eq!(
+            config.next_delay(&progress(20, 50), &mut backoff),
+            Duration::ZERO
+        );
+        assert_eq!(backoff, config.poll_interval);
+    }
+
+    #[test]
+    fn test_follow_runs_back_to_back_while_skipping_processed_ledgers() {
+        let config = FollowConfig::default();
+        let mut backoff = config.max_backoff;
+        let skipping = IngestionProgress {
+            skipped: 20,
+            ..progress(0, 50)
+        };
+
+        assert_eq!(config.next_delay(&skipping, &mut backoff), Duration::ZERO);
+        assert_eq!(backoff, config.poll_interval);
+    }
+
+    #[test]
+    fn test_follow_polls_once_caught_up() {
+        let config = FollowConfig::default();
+        let mut backoff = config.poll_interval;
+
+        assert_eq!(
+            config.next_delay(&progress(3, 0), &mut backoff),
+            config.poll_interval
+        );
+    }
+
+    #[test]
+    fn test_follow_backs_off_when_nothing_ingested_behind_head() {
+        let config = FollowConfig::default();
+        let mut backoff = config.poll_interval;
+
+        let first = config.next_delay(&progress(0, 50), &mut backoff);
+        let second = config.next_delay(&progress(0, 50), &mut backoff);
+        assert_eq!(first, config.poll_interval);
+        assert_eq!(second, config.poll_interval * 2);
+
+        for _ in 0..10 {
+            config.next_delay(&progress(0, 50), &mut backoff);
+        }
+        assert_eq!(backoff, config.max_backoff);
     }
 }
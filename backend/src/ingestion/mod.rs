@@ -1,52 +1,155 @@
+pub mod anchor_activity;
+pub mod asset_filter;
 // I'm exporting the ledger ingestion module as required by issue #2
 pub mod ledger;
+pub mod path_payment_failures;
 
 use anyhow::{Context, Result};
 use serde::Serialize;
 use std::sync::Arc;
 use tracing::{info, warn};
 
-use crate::database::Database;
+use self::anchor_activity::{AnchorMetricsSource, OperationActivity};
+use crate::database::{AnchorRpcUpdate, Database};
 use crate::rpc::StellarRpcClient;
 
+/// Share of anchors failing a sync above which the sync is treated as degraded
+pub const SYNC_FAILURE_ALERT_RATIO: f64 = 0.5;
+
+/// Per-anchor outcome of a metrics sync
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncReport {
+    /// Number of anchors whose metrics were updated
+    pub succeeded: usize,
+    /// Anchors that failed, with the error for each
+    pub failed: Vec<(String, String)>,
+}
+
+impl SyncReport {
+    pub fn total(&self) -> usize {
+        self.succeeded + self.failed.len()
+    }
+
+    /// Fraction of anchors that failed, 0.0 when nothing was synced
+    pub fn failure_ratio(&self) -> f64 {
+        if self.total() == 0 {
+            0.0
+        } else {
+            self.failed.len() as f64 / self.total() as f64
+        }
+    }
+}
+
+/// Weights of the anchor reliability score:
+/// `success_rate / 100 * base_weight - min(failures * penalty_per_failure, max_penalty)`,
+/// clamped to `[0, 1]`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReliabilityScoreConfig {
+    /// Multiplier applied to the success rate fraction
+    pub base_weight: f64,
+    /// Penalty subtracted for each failed transaction
+    pub penalty_per_failure: f64,
+    /// Upper bound on the total failure penalty
+    pub max_penalty: f64,
+}
+
+impl Default for ReliabilityScoreConfig {
+    fn default() -> Self {
+        Self {
+            base_weight: 1.0,
+            penalty_per_failure: 0.01,
+            max_penalty: 0.2,
+        }
+    }
+}
+
+impl ReliabilityScoreConfig {
+    /// Score in `[0, 1]` for a success rate given in percent
+    pub fn score(&self, success_rate: f64, failed_count: i64) -> f64 {
+        let base_score = success_rate / 100.0 * self.base_weight;
+        let penalty = (failed_count as f64 * self.penalty_per_failure).min(self.max_penalty);
+        (base_score - penalty).clamp(0.0, 1.0)
+    }
+}
+
 pub struct DataIngestionService {
     rpc_client: Arc<StellarRpcClient>,
     db: Arc<Database>,
+    reliability: ReliabilityScoreConfig,
+    metrics_source: AnchorMetricsSource,
 }
 
 impl DataIngestionService {
     pub fn new(rpc_client: Arc<StellarRpcClient>, db: Arc<Database>) -> Self {
-        Self { rpc_client, db }
+        Self {
+            rpc_client,
+            db,
+            reliability: ReliabilityScoreConfig::default(),
+            metrics_source: AnchorMetricsSource::default(),
+        }
+    }
+
+    /// Use `config` instead of the default reliability formula
+    pub fn with_reliability_config(mut self, config: ReliabilityScoreConfig) -> Self {
+        self.reliability = config;
+        self
     }
 
-    /// Sync all metrics from Stellar network
-    pub async fn sync_all_metrics(&self) -> Result<()> {
+    /// Derive anchor metrics from `source` instead of payments only
+    pub fn with_metrics_source(mut self, source: AnchorMetricsSource) -> Self {
+        self.metrics_source = source;
+        self
+    }
+
+    /// Sync all metrics from Stellar network.
+    ///
+    /// Only fails if the sync cannot start; per-anchor failures are listed
+    /// in the returned report.
+    pub async fn sync_all_metrics(&self) -> Result<SyncReport> {
         info!("Starting metrics synchronization");
 
-        self.sync_anchor_metrics().await?;
+        let report = self.sync_anchor_metrics().await?;
 
-        info!("Metrics synchronization completed");
-        Ok(())
+        info!(
+            "Metrics synchronization completed: {} succeeded, {} failed",
+            report.succeeded,
+            report.failed.len()
+        );
+        Ok(report)
     }
 
     /// Fetch and process anchor metrics from RPC
-    pub async fn sync_anchor_metrics(&self) -> Result<()> {
+    pub async fn sync_anchor_metrics(&self) -> Result<SyncReport> {
         info!("Syncing anchor metrics from Stellar network");
 
-        let anchors = self.db.list_anchors(0, 100).await?;
+        let anchors = self.db.list_anchors(100, 0).await?;
+        let mut report = SyncReport::default();
 
         for anchor in anchors {
             match self.process_anchor_metrics(&anchor.stellar_account).await {
-                Ok(_) => info!("Updated metrics for anchor: {}", anchor.name),
-                Err(e) => warn!("Failed to update anchor {}: {}", anchor.name, e),
+                Ok(_) => {
+                    info!("Updated metrics for anchor: {}", anchor.name);
+                    report.succeeded += 1;
+                }
+                Err(e) => {
+                    warn!("Failed to update anchor {}: {}", anchor.name, e);
+                    report.failed.push((anchor.name, e.to_string()));
+                }
             }
         }
 
-        Ok(())
+        Ok(report)
     }
 
     /// Process metrics for a single anchor
     async fn process_anchor_metrics(&self, account_id: &str) -> Result<()> {
+        match self.metrics_source {
+            AnchorMetricsSource::Payments => self.process_anchor_payments(account_id).await,
+            AnchorMetricsSource::Operations => self.process_anchor_operations(account_id).await,
+        }
+    }
+
+    async fn process_anchor_payments(&self, account_id: &str) -> Result<()> {
         let payments = self
             .rpc_client
             .fetch_account_payments(account_id, 100)
@@ -76,7 +179,7 @@ impl DataIngestionService {
             0.0
         };
 
-        let reliability_score = self.calculate_reliability_score(success_rate, failed as i64);
+        let reliability_score = self.reliability.score(success_rate, failed as i64);
 
         let avg_settlement_time = if !settlement_times.is_empty() {
             settlement_times.iter().sum::<i32>() / settlement_times.len() as i32
@@ -84,16 +187,10 @@ impl DataIngestionService {
             1000
         };
 
-        let status = if success_rate >= 98.0 {
-            "green"
-        } else if success_rate >= 95.0 {
-            "yellow"
-        } else {
-            "red"
-        };
+        let status = self.db.health_thresholds().classify(success_rate);
 
         self.db
-            .update_anchor_from_rpc(crate::database::AnchorRpcUpdate {
+            .update_anchor_from_rpc(AnchorRpcUpdate {
                 stellar_account: account_id.to_string(),
                 total_transactions,
                 successful_transactions: successful as i64,
@@ -101,17 +198,50 @@ impl DataIngestionService {
                 total_volume_usd: total_volume,
                 avg_settlement_time_ms: avg_settlement_time,
                 reliability_score,
-                status: status.to_string(),
+                status: status.as_str().to_string(),
             })
             .await?;
 
         Ok(())
     }
 
-    fn calculate_reliability_score(&self, success_rate: f64, failed_count: i64) -> f64 {
-        let base_score = success_rate / 100.0;
-        let penalty = (failed_count as f64 * 0.01).min(0.2);
-        (base_score - penalty).clamp(0.0, 1.0)
+    /// Metrics from every operation of the anchor's account, with the success
+    /// rate weighted by operation category
+    async fn process_anchor_operations(&self, account_id: &str) -> Result<()> {
+        let operations = self
+            .rpc_client
+            .fetch_account_operations(account_id, 200)
+            .await
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+        if operations.is_empty() {
+            return Ok(());
+        }
+
+        let activity = OperationActivity::from_operations(&operations);
+        let success_rate = activity.success_rate();
+        let failed = activity.failed as i64;
+
+        self.db
+            .update_anchor_from_rpc(AnchorRpcUpdate {
+                stellar_account: account_id.to_string(),
+                total_transactions: activity.total() as i64,
+                successful_transactions: activity.successful() as i64,
+                failed_transactions: failed,
+                total_volume_usd: activity.payment_volume,
+                // Operations carry no settlement timing either
+                avg_settlement_time_ms: 1000,
+                reliability_score: self.reliability.score(success_rate, failed),
+                status: self
+                    .db
+                    .health_thresholds()
+                    .classify(success_rate)
+                    .as_str()
+                    .to_string(),
+            })
+            .await?;
+
+        Ok(())
     }
 
     /// Get current network health status
@@ -168,3 +298,38 @@ impl DataIngestionService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_default_reliability_score() {
+        let config = ReliabilityScoreConfig::default();
+        assert_close(config.score(100.0, 0), 1.0);
+        assert_close(config.score(95.0, 5), 0.9);
+        assert_close(config.score(90.0, 50), 0.7);
+        assert_close(config.score(10.0, 30), 0.0);
+    }
+
+    #[test]
+    fn test_custom_reliability_score() {
+        let config = ReliabilityScoreConfig {
+            base_weight: 0.8,
+            penalty_per_failure: 0.05,
+            max_penalty: 0.3,
+        };
+        assert_close(config.score(100.0, 0), 0.8);
+        assert_close(config.score(100.0, 2), 0.7);
+        assert_close(config.score(50.0, 100), 0.1);
+    }
+}
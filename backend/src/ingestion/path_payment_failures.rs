@@ -0,0 +1,256 @@
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{SqliteConnection, SqlitePool};
+use std::collections::HashMap;
+use stellar_xdr::curr::{
+    InnerTransactionResultResult, Limits, OperationResult, OperationResultTr, ReadXdr,
+    TransactionResult, TransactionResultResult,
+};
+
+use crate::models::asset::normalize_asset_code;
+use crate::models::corridor::Corridor;
+use crate::models::timestamp::{format_timestamp, parse_ingested_timestamp};
+use crate::rpc::{HorizonTransaction, Payment};
+
+/// Reason recorded when a failed transaction carries no decodable result
+const UNKNOWN_FAILURE_REASON: &str = "tx_failed";
+
+/// A path payment whose transaction failed on-chain
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PathPaymentFailure {
+    pub operation_id: String,
+    pub ledger_sequence: u64,
+    pub transaction_hash: String,
+    pub operation_type: String,
+    pub corridor_key: String,
+    pub source_asset_code: String,
+    pub source_asset_issuer: String,
+    pub destination_asset_code: String,
+    pub destination_asset_issuer: String,
+    pub amount: f64,
+    pub failure_reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Failed path payment count for a corridor
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct CorridorPathPaymentFailures {
+    pub corridor_key: String,
+    pub failure_count: i64,
+}
+
+fn is_path_payment(payment: &Payment) -> bool {
+    payment
+        .operation_type
+        .as_deref()
+        .is_some_and(|t| t.starts_with("path_payment"))
+}
+
+/// Correlate a ledger's payments with their transactions and return the path
+/// payments whose transaction was not successful.
+pub fn detect_path_payment_failures(
+    ledger_sequence: u64,
+    payments: &[Payment],
+    transactions: &[HorizonTransaction],
+) -> Vec<PathPaymentFailure> {
+    let failed: HashMap<&str, &HorizonTransaction> = transactions
+        .iter()
+        .filter(|tx| !tx.successful)
+        .map(|tx| (tx.hash.as_str(), tx))
+        .collect();
+
+    payments
+        .iter()
+        .filter(|p| is_path_payment(p))
+        .filter_map(|payment| {
+            let tx = failed.get(payment.transaction_hash.as_str())?;
+
+            let (source_asset_code, source_asset_issuer) = asset_parts(
+                payment.source_asset_type.as_deref(),
+                payment.source_asset_code.clone(),
+                payment.source_asset_issuer.clone(),
+            );
+            let (destination_asset_code, destination_asset_issuer) = asset_parts(
+                Some(payment.asset_type.as_str()),
+                payment.get_asset_code(),
+                payment.get_asset_issuer(),
+            );
+            let corridor_key = Corridor::new(
+                source_asset_code.clone(),
+                source_asset_issuer.clone(),
+                destination_asset_code.clone(),
+                destination_asset_issuer.clone(),
+            )
+            .to_string_key();
+
+            Some(PathPaymentFailure {
+                operation_id: payment.id.clone(),
+                ledger_sequence,
+                transaction_hash: payment.transaction_hash.clone(),
+                operation_type: payment.operation_type.clone().unwrap_or_default(),
+                corridor_key,
+                source_asset_code,
+                source_asset_issuer,
+                destination_asset_code,
+                destination_asset_issuer,
+                amount: payment.get_amount().parse().unwrap_or(0.0),
+                failure_reason: failure_reason(tx),
+                created_at: parse_ingested_timestamp(&payment.created_at, "path_payment_failure")?,
+            })
+        })
+        .collect()
+}
+
+/// Asset code/issuer pair with the code normalized, using `XLM`/`native`
+/// for lumens
+fn asset_parts(
+    asset_type: Option<&str>,
+    code: Option<String>,
+    issuer: Option<String>,
+) -> (String, String) {
+    match (asset_type, code, issuer) {
+        (Some("native"), _, _) | (_, None, _) => ("XLM".to_string(), "native".to_string()),
+        (_, Some(code), issuer) => (normalize_asset_code(&code), issuer.unwrap_or_default()),
+    }
+}
+
+/// Failure reason decoded from the transaction's `result_xdr`, e.g.
+/// `path_payment_strict_send:TooFewOffers` or `tx:TxBadSeq`
+pub fn failure_reason(tx: &HorizonTransaction) -> String {
+    tx.result_xdr
+        .as_deref()
+        .and_then(decode_failure_reason)
+        .unwrap_or_else(|| UNKNOWN_FAILURE_REASON.to_string())
+}
+
+fn decode_failure_reason(result_xdr: &str) -> Option<String> {
+    let bytes = BASE64.decode(result_xdr).ok()?;
+    let result = TransactionResult::from_xdr(bytes, Limits::none()).ok()?;
+
+    let operations = match &result.result {
+        TransactionResultResult::TxFailed(ops) => ops.as_slice(),
+        TransactionResultResult::TxFeeBumpInnerFailed(pair) => match &pair.result.result {
+            InnerTransactionResultResult::TxFailed(ops) => ops.as_slice(),
+            other => return Some(format!("tx:{}", other.name())),
+        },
+        other => return Some(format!("tx:{}", other.name())),
+    };
+
+    operations.iter().find_map(|op| match op {
+        OperationResult::OpInner(OperationResultTr::PathPaymentStrictSend(r)) => {
+            failed_operation("path_payment_strict_send", r.name())
+        }
+        OperationResult::OpInner(OperationResultTr::PathPaymentStrictReceive(r)) => {
+            failed_operation("path_payment_strict_receive", r.name())
+        }
+        OperationResult::OpInner(_) => None,
+        other => Some(format!("op:{}", other.name())),
+    })
+}
+
+fn failed_operation(kind: &str, result: &str) -> Option<String> {
+    (result != "Success").then(|| format!("{}:{}", kind, result))
+}
+
+/// Record a failure; returns whether a new row was inserted
+pub async fn insert_path_payment_failure(
+    conn: &mut SqliteConnection,
+    failure: &PathPaymentFailure,
+) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO path_payment_failures (
+            operation_id, ledger_sequence, transaction_hash, operation_type, corridor_key,
+            source_asset_code, source_asset_issuer, destination_asset_code,
+            destination_asset_issuer, amount, failure_reason, created_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        ON CONFLICT (operation_id) DO NOTHING
+        "#,
+    )
+    .bind(&failure.operation_id)
+    .bind(failure.ledger_sequence as i64)
+    .bind(&failure.transaction_hash)
+    .bind(&failure.operation_type)
+    .bind(&failure.corridor_key)
+    .bind(&failure.source_asset_code)
+    .bind(&failure.source_asset_issuer)
+    .bind(&failure.destination_asset_code)
+    .bind(&failure.destination_asset_issuer)
+    .bind(failure.amount)
+    .bind(&failure.failure_reason)
+    .bind(format_timestamp(&failure.created_at))
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Failed path payment counts per corridor, most failures first
+pub async fn failures_by_corridor(pool: &SqlitePool) -> Result<Vec<CorridorPathPaymentFailures>> {
+    let rows = sqlx::query_as::<_, CorridorPathPaymentFailures>(
+        r#"
+        SELECT corridor_key, COUNT(*) AS failure_count
+        FROM path_payment_failures
+        GROUP BY corridor_key
+        ORDER BY failure_count DESC, corridor_key ASC
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::{PathPaymentStrictSendResult, TransactionResultExt, VecM, WriteXdr};
+
+    fn failed_result_xdr() -> String {
+        let result = TransactionResult {
+            fee_charged: 100,
+            result: TransactionResultResult::TxFailed(
+                VecM::try_from(vec![OperationResult::OpInner(
+                    OperationResultTr::PathPaymentStrictSend(
+                        PathPaymentStrictSendResult::UnderDestmin,
+                    ),
+                )])
+                .unwrap(),
+            ),
+            ext: TransactionResultExt::V0,
+        };
+        BASE64.encode(result.to_xdr(Limits::none()).unwrap())
+    }
+
+    #[test]
+    fn test_decodes_path_payment_failure_reason() {
+        assert_eq!(
+            decode_failure_reason(&failed_result_xdr()).as_deref(),
+            Some("path_payment_strict_send:UnderDestmin")
+        );
+    }
+
+    #[test]
+    fn test_undecodable_result_falls_back() {
+        assert_eq!(decode_failure_reason("not-xdr"), None);
+    }
+
+    #[test]
+    fn test_native_asset_parts() {
+        assert_eq!(
+            asset_parts(Some("native"), None, None),
+            ("XLM".to_string(), "native".to_string())
+        );
+        assert_eq!(
+            asset_parts(
+                Some("credit_alphanum4"),
+                Some("USDC".to_string()),
+                Some("GISSUER".to_string())
+            ),
+            ("USDC".to_string(), "GISSUER".to_string())
+        );
+    }
+}
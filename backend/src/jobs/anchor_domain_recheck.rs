@@ -0,0 +1,132 @@
+//! Periodic re-verification of anchor home domains.
+//!
+//! A verified anchor only loses its `domain_verified` flag after its
+//! stellar.toml has failed to list it, or been unreachable, on several checks
+//! in a row, so a transient outage does not flap the flag. Every flip is
+//! published as an `anchor.status_changed` event.
+
+use anyhow::Result;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::models::Anchor;
+use crate::services::anchor_domain_verifier::AnchorDomainVerifier;
+use crate::services::event_bus::{DomainEvent, EventBus};
+use crate::webhooks::events::AnchorStatusChangedEvent;
+
+/// Status reported in `anchor.status_changed` events for a verified domain
+pub const DOMAIN_VERIFIED_STATUS: &str = "domain_verified";
+/// Status reported in `anchor.status_changed` events for an unverified domain
+pub const DOMAIN_UNVERIFIED_STATUS: &str = "domain_unverified";
+
+/// Default consecutive failed checks before a verified domain is flagged
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+
+/// Outcome of one re-check cycle
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DomainRecheckStats {
+    pub checked: usize,
+    pub failed: usize,
+    pub verified: usize,
+    pub unverified: usize,
+}
+
+pub struct AnchorDomainRecheckJob {
+    db: Arc<Database>,
+    verifier: Arc<AnchorDomainVerifier>,
+    event_bus: Arc<EventBus>,
+    failure_threshold: u32,
+}
+
+impl AnchorDomainRecheckJob {
+    pub fn new(
+        db: Arc<Database>,
+        verifier: Arc<AnchorDomainVerifier>,
+        event_bus: Arc<EventBus>,
+    ) -> Self {
+        Self {
+            db,
+            verifier,
+            event_bus,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+        }
+    }
+
+    pub fn with_failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self
+    }
+
+    /// Re-check every anchor with a home domain once
+    pub async fn run_once(&self) -> Result<DomainRecheckStats> {
+        let mut stats = DomainRecheckStats::default();
+        for anchor in self.db.list_anchors_with_home_domain().await? {
+            let Ok(id) = Uuid::parse_str(&anchor.id) else {
+                warn!(
+                    "Skipping domain re-check of anchor with invalid id {}",
+                    anchor.id
+                );
+                continue;
+            };
+
+            stats.checked += 1;
+            let result = self.verifier.check_anchor(&anchor).await;
+            if result.is_verified() {
+                if anchor.domain_check_failures != 0 {
+                    self.db.set_anchor_domain_check_failures(id, 0).await?;
+                }
+                if !anchor.domain_verified {
+                    self.db.set_anchor_domain_verified(id, true).await?;
+                    self.publish_change(&anchor, true);
+                    stats.verified += 1;
+                }
+                continue;
+            }
+
+            stats.failed += 1;
+            let failures = anchor.domain_check_failures + 1;
+            self.db
+                .set_anchor_domain_check_failures(id, failures)
+                .await?;
+            if anchor.domain_verified && failures >= i64::from(self.failure_threshold) {
+                warn!(
+                    "Anchor {} failed {} domain checks in a row ({:?}), clearing verification",
+                    anchor.id, failures, result
+                );
+                self.db.set_anchor_domain_verified(id, false).await?;
+                self.publish_change(&anchor, false);
+                stats.unverified += 1;
+            } else {
+                info!(
+                    "Anchor {} failed domain check {} of {}: {:?}",
+                    anchor.id, failures, self.failure_threshold, result
+                );
+            }
+        }
+
+        info!(
+            "Anchor domain re-check: {} checked, {} failed, {} verified, {} unverified",
+            stats.checked, stats.failed, stats.verified, stats.unverified
+        );
+        Ok(stats)
+    }
+
+    fn publish_change(&self, anchor: &Anchor, verified: bool) {
+        let (old_status, new_status) = if verified {
+            (DOMAIN_UNVERIFIED_STATUS, DOMAIN_VERIFIED_STATUS)
+        } else {
+            (DOMAIN_VERIFIED_STATUS, DOMAIN_UNVERIFIED_STATUS)
+        };
+        self.event_bus
+            .publish(DomainEvent::AnchorStatusChanged(AnchorStatusChangedEvent {
+                anchor_id: anchor.id.clone(),
+                name: anchor.name.clone(),
+                old_status: old_status.to_string(),
+                new_status: new_status.to_string(),
+                reliability_score: anchor.reliability_score,
+                failed_txn_count: anchor.failed_transactions,
+            }));
+    }
+}
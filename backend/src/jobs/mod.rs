@@ -1,5 +1,7 @@
+pub mod anchor_domain_recheck;
 pub mod asset_revalidation;
 pub mod scheduler;
 
+pub use anchor_domain_recheck::{AnchorDomainRecheckJob, DomainRecheckStats};
 pub use asset_revalidation::{AssetRevalidationJob, RevalidationConfig, RevalidationStats};
 pub use scheduler::{JobConfig, JobScheduler};
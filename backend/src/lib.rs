@@ -13,6 +13,8 @@ pub mod broadcast;
 pub mod cache;
 pub mod cache_invalidation;
 pub mod cache_middleware;
+pub mod concurrency_limit;
+pub mod cors;
 pub mod crypto;
 pub mod database;
 pub mod db;
@@ -37,12 +39,17 @@ pub mod observability;
 pub mod openapi;
 pub mod rate_limit;
 pub mod replay;
+pub mod request_deadline;
+pub mod request_decompression;
 pub mod request_id;
+pub mod response_compression;
 pub mod services;
 pub mod shutdown;
 pub mod snapshot;
 pub mod snapshot_handlers;
 pub mod state;
+pub mod supervisor;
+pub mod task_registry;
 pub mod vault;
 pub mod webhooks;
 pub mod websocket;
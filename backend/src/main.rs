@@ -4,22 +4,21 @@ use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
 use axum::extract::State;
 use axum::response::{Html, IntoResponse};
 use axum::{
-    http::Method,
     routing::{get, post, put},
-    Router,
+    Extension, Router,
 };
 use dotenv::dotenv;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::task::JoinHandle;
-use tower_http::compression::{predicate::SizeAbove, CompressionLayer};
-use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 
 use stellar_insights_backend::alerts::AlertManager;
 use stellar_insights_backend::api::account_merges;
+use stellar_insights_backend::api::admin_audit;
+use stellar_insights_backend::api::admin_tasks;
 use stellar_insights_backend::api::anchors_cached::get_anchors;
 use stellar_insights_backend::api::api_analytics;
 use stellar_insights_backend::api::api_keys;
@@ -31,44 +30,72 @@ use stellar_insights_backend::api::fee_bump;
 use stellar_insights_backend::api::liquidity_pools;
 use stellar_insights_backend::api::metrics_cached;
 use stellar_insights_backend::api::oauth;
+use stellar_insights_backend::api::overview;
+use stellar_insights_backend::api::resource_permissions;
 use stellar_insights_backend::api::verification_rewards;
 use stellar_insights_backend::api::webhooks;
 use stellar_insights_backend::auth::AuthService;
-use stellar_insights_backend::auth_middleware::auth_middleware;
-use stellar_insights_backend::cache::{CacheConfig, CacheManager};
+use stellar_insights_backend::auth_middleware::{
+    auth_middleware, require_resource_permission, require_scope, scopes, JwtSecret, RequiredScope,
+    ResourcePermission,
+};
+use stellar_insights_backend::broadcast::run_domain_event_forwarder;
+use stellar_insights_backend::cache::CacheManager;
 use stellar_insights_backend::cache_invalidation::CacheInvalidationService;
+use stellar_insights_backend::concurrency_limit::{
+    concurrency_limit_middleware, ConcurrencyLimiter,
+};
+use stellar_insights_backend::cors;
 use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::metrics_buffer::{BufferedMetricsWriter, MetricsBufferConfig};
+use stellar_insights_backend::error::with_json_fallbacks;
 // use stellar_insights_backend::graphql::{build_schema, AppSchema};
 // use stellar_insights_backend::gdpr::{GdprService, handlers as gdpr_handlers};
 use stellar_insights_backend::handlers::*;
-use stellar_insights_backend::ingestion::ledger::LedgerIngestionService;
-use stellar_insights_backend::ingestion::DataIngestionService;
+use stellar_insights_backend::ingestion::ledger::{AdaptiveBatchSize, LedgerIngestionService};
+use stellar_insights_backend::ingestion::{DataIngestionService, SYNC_FAILURE_ALERT_RATIO};
 use stellar_insights_backend::ip_whitelist_middleware::{
     ip_whitelist_middleware, IpWhitelistConfig,
 };
-use stellar_insights_backend::jobs::JobScheduler;
+use stellar_insights_backend::jobs::{AnchorDomainRecheckJob, JobConfig, JobScheduler};
 use stellar_insights_backend::monitor::CorridorMonitor;
 use stellar_insights_backend::network::NetworkConfig;
 use stellar_insights_backend::observability::{metrics as obs_metrics, tracing as obs_tracing};
 use stellar_insights_backend::openapi::ApiDoc;
 use stellar_insights_backend::rate_limit::{rate_limit_middleware, ClientRateLimits, RateLimitConfig, RateLimiter};
+use stellar_insights_backend::request_deadline::request_deadline_middleware;
+use stellar_insights_backend::request_decompression::with_request_decompression;
 use stellar_insights_backend::request_id::request_id_middleware;
+use stellar_insights_backend::response_compression::compression_layer;
+use stellar_insights_backend::rpc::circuit_breaker::CircuitBreakers;
 use stellar_insights_backend::rpc::StellarRpcClient;
 use stellar_insights_backend::rpc_handlers;
 use stellar_insights_backend::services::account_merge_detector::AccountMergeDetector;
+use stellar_insights_backend::services::anchor_asset_discovery::AnchorAssetDiscovery;
+use stellar_insights_backend::services::anchor_domain_verifier::AnchorDomainVerifier;
+use stellar_insights_backend::services::contract::ContractService;
+use stellar_insights_backend::services::event_bus::EventBus;
 use stellar_insights_backend::services::fee_bump_tracker::FeeBumpTrackerService;
+use stellar_insights_backend::services::fee_estimator::FeeEstimator;
+use stellar_insights_backend::services::idempotency::IdempotencyStore;
 use stellar_insights_backend::services::liquidity_pool_analyzer::LiquidityPoolAnalyzer;
-use stellar_insights_backend::services::price_feed::{
-    default_asset_mapping, PriceFeedClient, PriceFeedConfig,
-};
+use stellar_insights_backend::services::path_finder::{OrderBookSource, PathFinder};
+use stellar_insights_backend::services::price_feed::{default_asset_mapping, PriceFeedClient};
 use stellar_insights_backend::services::realtime_broadcaster::RealtimeBroadcaster;
+use stellar_insights_backend::services::resource_acl::{ResourceAcl, ResourceType};
+use stellar_insights_backend::services::rpc_health_monitor::RpcHealthMonitor;
+use stellar_insights_backend::services::snapshot::SnapshotService;
+use stellar_insights_backend::services::stellar_toml::{AnchorMetadataEnricher, StellarTomlClient};
 use stellar_insights_backend::services::trustline_analyzer::TrustlineAnalyzer;
 use stellar_insights_backend::services::webhook_dispatcher::WebhookDispatcher;
 use stellar_insights_backend::shutdown::{
     flush_cache, log_shutdown_summary, shutdown_background_tasks, shutdown_database,
     shutdown_websockets, wait_for_signal, ShutdownConfig, ShutdownCoordinator,
 };
+use stellar_insights_backend::snapshot_handlers::{self, SnapshotAppState};
 use stellar_insights_backend::state::AppState;
+use stellar_insights_backend::supervisor::{supervise, RestartPolicy};
+use stellar_insights_backend::task_registry::TaskRegistry;
 use stellar_insights_backend::telegram;
 use stellar_insights_backend::vault;
 use stellar_insights_backend::websocket::WsState;
@@ -88,7 +115,7 @@ async fn main() -> Result<()> {
     tracing::info!("Starting Stellar Insights Backend");
 
     // Validate environment configuration
-    stellar_insights_backend::env_config::validate_env()
+    let config = stellar_insights_backend::env_config::validate_env()
         .context("Environment configuration validation failed")?;
 
     // Log sanitized environment configuration
@@ -105,8 +132,7 @@ async fn main() -> Result<()> {
     let shutdown_coordinator = Arc::new(ShutdownCoordinator::new(shutdown_config.clone()));
 
     // Database connection
-    let database_url = std::env::var("DATABASE_URL")
-        .unwrap_or_else(|_| "sqlite:./stellar_insights.db".to_string());
+    let database_url = config.database_url.clone();
 
     // Log sanitized database URL to prevent credential leakage (SEC-016)
     let sanitized_db_url = if database_url.starts_with("sqlite:") {
@@ -126,16 +152,16 @@ async fn main() -> Result<()> {
     };
     tracing::info!("Connecting to database: {}", sanitized_db_url);
 
-    // Load pool configuration from environment
-    let pool_config = stellar_insights_backend::database::PoolConfig::from_env();
+    let pool_config = &config.pool;
     tracing::info!(
         "Database pool configuration: max_connections={}, min_connections={}, \
-         connect_timeout={}s, idle_timeout={}s, max_lifetime={}s",
+         connect_timeout={}s, idle_timeout={}s, max_lifetime={}s, query_timeout={}s",
         pool_config.max_connections,
         pool_config.min_connections,
         pool_config.connect_timeout_seconds,
         pool_config.idle_timeout_seconds,
-        pool_config.max_lifetime_seconds
+        pool_config.max_lifetime_seconds,
+        pool_config.query_timeout_seconds
     );
 
     let pool = pool_config.create_pool(&database_url).await?;
@@ -143,13 +169,18 @@ async fn main() -> Result<()> {
     tracing::info!("Running database migrations...");
     sqlx::migrate!("./migrations").run(&pool).await?;
 
-    let db = Arc::new(Database::new(pool.clone()));
+    let db = Arc::new(
+        Database::new(pool.clone())
+            .with_query_timeout(pool_config.query_timeout())
+            .with_retry_policy(config.db_retry)
+            .with_metrics_history_limit(config.anchor_metrics_history_limit.into())
+            .with_muxed_dust_thresholds(config.muxed_dust_thresholds.clone())
+            .with_health_thresholds(config.health_thresholds)
+            .with_min_corridor_volume_usd(config.min_corridor_volume_usd),
+    );
 
     // Initialize Stellar RPC Client
-    let mock_mode = std::env::var("RPC_MOCK_MODE")
-        .unwrap_or_else(|_| "false".to_string())
-        .parse::<bool>()
-        .unwrap_or(false);
+    let mock_mode = config.rpc_mock_mode;
 
     // Initialize Stellar RPC Client with network configuration
     let network_config = NetworkConfig::from_env();
@@ -160,27 +191,33 @@ async fn main() -> Result<()> {
     );
 
     let rpc_client = if mock_mode {
-        Arc::new(StellarRpcClient::new_with_network(
-            network_config.network,
-            true,
-        ))
+        StellarRpcClient::new_with_network(network_config.network, true)
     } else {
-        Arc::new(StellarRpcClient::new(
+        StellarRpcClient::new(
             network_config.rpc_url.clone(),
             network_config.horizon_url.clone(),
             false,
-        ))
+        )
+        .with_recording(config.rpc_recording.clone())
     };
+    let rpc_client = Arc::new(
+        rpc_client
+            .with_max_records_ceiling(config.rpc_max_records_ceiling)
+            .with_data_sources(config.rpc_data_sources)
+            .with_latency_window(config.rpc_latency_window)
+            .with_circuit_breakers(CircuitBreakers::configured(&config.rpc_circuit_breakers)),
+    );
 
     // Initialize WebSocket state
     let ws_state = Arc::new(WsState::new());
     tracing::info!("WebSocket state initialized");
 
     // Initialize Data Ingestion Service
-    let ingestion_service = Arc::new(DataIngestionService::new(
-        Arc::clone(&rpc_client),
-        Arc::clone(&db),
-    ));
+    let ingestion_service = Arc::new(
+        DataIngestionService::new(Arc::clone(&rpc_client), Arc::clone(&db))
+            .with_reliability_config(config.reliability_score)
+            .with_metrics_source(config.anchor_metrics_source),
+    );
 
     // Initialize Fee Bump Tracker Service
     let fee_bump_tracker = Arc::new(FeeBumpTrackerService::new(pool.clone()));
@@ -198,9 +235,8 @@ async fn main() -> Result<()> {
     ));
 
     // Initialize Price Feed Client
-    let price_feed_config = PriceFeedConfig::from_env();
     let asset_mapping = default_asset_mapping();
-    let price_feed = Arc::new(PriceFeedClient::new(price_feed_config, asset_mapping));
+    let price_feed = Arc::new(PriceFeedClient::new(config.price_feed.clone(), asset_mapping));
     tracing::info!("Price feed client initialized");
 
     // Initialize Trustline Analyzer
@@ -210,16 +246,18 @@ async fn main() -> Result<()> {
     ));
 
     // Initialize Ledger Ingestion Service
-    let ledger_ingestion_service = Arc::new(LedgerIngestionService::new(
-        Arc::clone(&rpc_client),
-        Arc::clone(&fee_bump_tracker),
-        Arc::clone(&account_merge_detector),
-        pool.clone(),
-    ));
+    let ledger_ingestion_service = Arc::new(
+        LedgerIngestionService::new(
+            Arc::clone(&rpc_client),
+            Arc::clone(&fee_bump_tracker),
+            Arc::clone(&account_merge_detector),
+            pool.clone(),
+        )
+        .with_asset_filter(config.ingestion_asset_filter.clone()),
+    );
 
     // Initialize Redis cache
-    let cache_config = CacheConfig::default();
-    let cache = Arc::new(CacheManager::new(cache_config).await?);
+    let cache = Arc::new(CacheManager::new(config.cache.clone()).await?);
     tracing::info!("Cache manager initialized");
 
     // Initialize cache invalidation service
@@ -246,14 +284,73 @@ async fn main() -> Result<()> {
     tracing::info!("RealtimeBroadcaster initialized");
 
     // Initialize Webhook Dispatcher
-    let webhook_dispatcher = WebhookDispatcher::new(pool.clone());
+    let webhook_dispatcher = Arc::new(
+        WebhookDispatcher::new(pool.clone())
+            .with_dispatch_config(config.webhook_dispatcher)
+            .with_webhook_limits(config.webhook_limits),
+    );
     tracing::info!("Webhook dispatcher initialized");
 
+    // Initialize domain event bus
+    let event_bus = Arc::new(EventBus::with_capacity(config.event_bus_capacity));
+    tracing::info!("Event bus initialized");
+
+    // Initialize snapshot service; snapshots are only submitted on-chain
+    // when the snapshot contract is configured
+    let contract_service = match ContractService::from_settings(
+        &config.snapshot_contract,
+        network_config.clone(),
+    ) {
+        Ok(service) => Some(Arc::new(
+            service.with_idempotency(IdempotencyStore::new(pool.clone())),
+        )),
+        Err(e) => {
+            tracing::info!(
+                "Snapshot contract not configured ({}), on-chain submission disabled",
+                e
+            );
+            None
+        }
+    };
+    let mut snapshot_service = SnapshotService::new(Arc::clone(&db), contract_service.clone())
+        .with_hash_algorithm(config.snapshot_hash_algorithm)
+        .with_confirmation_config(config.snapshot_confirmation)
+        .with_size_limits(config.snapshot_size_limits);
+    if let Some(calendar) = config.snapshot_epoch_calendar.clone() {
+        snapshot_service = snapshot_service.with_epoch_calendar(calendar);
+    }
+    if config.snapshot_exact_amounts {
+        snapshot_service = snapshot_service.with_exact_amounts();
+    }
+    if let Some(key) = config.snapshot_proof_signing_key.clone() {
+        snapshot_service = snapshot_service.with_proof_signing_key(key);
+    }
+    let outdated_snapshots = snapshot_service.migrate_stored_snapshots().await?;
+    if outdated_snapshots > 0 {
+        tracing::info!(
+            "{} stored snapshots use an earlier schema and are upgraded when loaded",
+            outdated_snapshots
+        );
+    }
+    // Optionally check stored snapshots before serving, refusing to start
+    // when too many of them are corrupted
+    if config.snapshot_integrity_audit.enabled {
+        snapshot_service
+            .run_startup_audit(&config.snapshot_integrity_audit)
+            .await?;
+    }
+    let snapshot_state = SnapshotAppState {
+        db: Arc::clone(&db),
+        contract_service,
+        snapshot_service: Arc::new(snapshot_service),
+    };
+
     // Create app state for handlers that need it
     let app_state = AppState::new(
         Arc::clone(&db),
         Arc::clone(&ws_state),
         Arc::clone(&ingestion_service),
+        Arc::clone(&event_bus),
     );
 
     // Create cached state tuple for cached API handlers
@@ -266,47 +363,78 @@ async fn main() -> Result<()> {
 
     // Track background tasks for graceful shutdown
     let mut background_tasks: Vec<JoinHandle<()>> = Vec::new();
+    // Periodic loops below are supervised and restarted if they panic
+    // Background task runs and liveness, reported at /api/admin/tasks
+    let task_registry = Arc::new(TaskRegistry::new());
 
     // Metrics synchronization task
     let ingestion_clone = Arc::clone(&ingestion_service);
     let cache_invalidation_clone = Arc::clone(&cache_invalidation);
-    let shutdown_rx1 = shutdown_coordinator.subscribe();
-    let task = tokio::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(300)); // 5 minutes
-        let mut shutdown_rx = shutdown_rx1;
-        loop {
-            tokio::select! {
-                _ = interval.tick() => {
-                    if let Err(e) = ingestion_clone.sync_all_metrics().await {
-                        tracing::error!("Metrics synchronization failed: {}", e);
-                        obs_metrics::record_background_job("metrics_sync", "error");
-                    } else {
-                        obs_metrics::record_background_job("metrics_sync", "success");
-                        // Invalidate caches after successful sync
-                        if let Err(e) = cache_invalidation_clone.invalidate_anchors().await {
-                            tracing::warn!("Failed to invalidate anchor caches: {}", e);
-                        }
-                        if let Err(e) = cache_invalidation_clone.invalidate_corridors().await {
-                            tracing::warn!("Failed to invalidate corridor caches: {}", e);
+    let metrics_sync_status =
+        task_registry.register("metrics_sync", Some(std::time::Duration::from_secs(300)));
+    let task = supervise(
+        "metrics_sync",
+        RestartPolicy::default(),
+        shutdown_coordinator.subscribe(),
+        move |shutdown_rx1| {
+            let ingestion_clone = Arc::clone(&ingestion_clone);
+            let cache_invalidation_clone = Arc::clone(&cache_invalidation_clone);
+            let metrics_sync_status = metrics_sync_status.clone();
+            async move {
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(300)); // 5 minutes
+                let mut shutdown_rx = shutdown_rx1;
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            let result = ingestion_clone.sync_all_metrics().await;
+                            metrics_sync_status.record(&result);
+                            match result {
+                                Err(e) => {
+                                    tracing::error!("Metrics synchronization failed: {}", e);
+                                    obs_metrics::record_background_job("metrics_sync", "error");
+                                }
+                                Ok(report) => {
+                                    for (anchor, error) in &report.failed {
+                                        tracing::warn!("Metrics sync failed for anchor {}: {}", anchor, error);
+                                        obs_metrics::record_background_job("anchor_metrics_sync", "error");
+                                    }
+                                    if report.failure_ratio() > SYNC_FAILURE_ALERT_RATIO {
+                                        tracing::error!(
+                                            "Metrics sync degraded: {} of {} anchors failed",
+                                            report.failed.len(),
+                                            report.total()
+                                        );
+                                        obs_metrics::record_background_job("metrics_sync", "degraded");
+                                    } else {
+                                        obs_metrics::record_background_job("metrics_sync", "success");
+                                    }
+                                    // Invalidate caches for the anchors that did update
+                                    if let Err(e) = cache_invalidation_clone.invalidate_anchors().await {
+                                        tracing::warn!("Failed to invalidate anchor caches: {}", e);
+                                    }
+                                    if let Err(e) = cache_invalidation_clone.invalidate_corridors().await {
+                                        tracing::warn!("Failed to invalidate corridor caches: {}", e);
+                                    }
+                                    if let Err(e) = cache_invalidation_clone.invalidate_metrics().await {
+                                        tracing::warn!("Failed to invalidate metrics caches: {}", e);
+                                    }
+                                }
+                            }
                         }
-                        if let Err(e) = cache_invalidation_clone.invalidate_metrics().await {
-                            tracing::warn!("Failed to invalidate metrics caches: {}", e);
+                        _ = shutdown_rx.recv() => {
+                            tracing::info!("Metrics synchronization task shutting down");
+                            break;
                         }
                     }
                 }
-                _ = shutdown_rx.recv() => {
-                    tracing::info!("Metrics synchronization task shutting down");
-                    break;
-                }
+                metrics_sync_status.stopped();
             }
-        }
-    });
+        },
+    );
     background_tasks.push(task);
 
     // Initialize Auth Service with its own Redis connection
-    let redis_url =
-        std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string());
-    let auth_redis_connection = if let Ok(client) = redis::Client::open(redis_url.as_str()) {
+    let auth_redis_connection = if let Ok(client) = redis::Client::open(config.redis_url.as_str()) {
         match client.get_multiplexed_tokio_connection().await {
             Ok(conn) => {
                 tracing::info!("Auth service connected to Redis");
@@ -329,20 +457,13 @@ async fn main() -> Result<()> {
     ))));
     tracing::info!("Auth service initialized");
 
+    let jwt_secret = JwtSecret(auth_service.jwt_secret().into());
+
     // Initialize SEP-10 Service for Stellar authentication
     let sep10_redis_connection = Arc::new(tokio::sync::RwLock::new(auth_redis_connection));
 
-    // Get and validate SEP-10 server public key (required for security)
-    let sep10_server_key = std::env::var("SEP10_SERVER_PUBLIC_KEY")
-        .context("SEP10_SERVER_PUBLIC_KEY environment variable is required for authentication")?;
-
-    // Additional validation: ensure it's not the placeholder value
-    if sep10_server_key == "GXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX" {
-        anyhow::bail!(
-            "SEP10_SERVER_PUBLIC_KEY is set to placeholder value. \
-             Please generate a valid Stellar keypair using: stellar keys generate --network testnet"
-        );
-    }
+    // The server public key was validated (and placeholders rejected) at startup
+    let sep10_server_key = &config.sep10_server_public_key;
 
     tracing::info!(
         "SEP-10 authentication enabled with server key: {}...",
@@ -351,12 +472,9 @@ async fn main() -> Result<()> {
 
     let sep10_service = Arc::new(
         stellar_insights_backend::auth::sep10_simple::Sep10Service::new(
-            std::env::var("SEP10_SERVER_PUBLIC_KEY").unwrap_or_else(|_| {
-                "GXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX".to_string()
-            }),
+            config.sep10_server_public_key.clone(),
             network_config.network_passphrase.clone(),
-            std::env::var("SEP10_HOME_DOMAIN")
-                .unwrap_or_else(|_| "stellar-insights.local".to_string()),
+            config.sep10_home_domain.clone(),
             sep10_redis_connection,
         )
         .context("Failed to initialize SEP-10 service")?,
@@ -397,106 +515,188 @@ async fn main() -> Result<()> {
     });
     */
 
+    // Per-batch ingestion metrics are written in batches rather than per row
+    let metrics_writer = Arc::new(BufferedMetricsWriter::new(
+        Arc::clone(&db),
+        MetricsBufferConfig::default(),
+    ));
+    background_tasks.push(metrics_writer.spawn_flusher(shutdown_coordinator.subscribe()));
+
     // Ledger ingestion task
     let ledger_ingestion_clone = Arc::clone(&ledger_ingestion_service);
-    let shutdown_rx2 = shutdown_coordinator.subscribe();
-    let task = tokio::spawn(async move {
-        tracing::info!("Starting ledger ingestion background task");
-        let mut shutdown_rx = shutdown_rx2;
-        loop {
-            tokio::select! {
-                result = ledger_ingestion_clone.run_ingestion(5) => {
-                    match result {
-                        Ok(count) => {
-                            obs_metrics::record_background_job("ledger_ingestion", "success");
-                            if count == 0 {
-                                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
-                            } else {
-                                tokio::task::yield_now().await;
+    let follow_mode = config.ledger_follow_mode;
+    let follow_config = config.ledger_follow.clone();
+    let batch_config = config.ledger_batch_size.clone();
+    // Follow mode runs its own loop, so only its liveness is tracked
+    let ledger_ingestion_status = task_registry.register(
+        "ledger_ingestion",
+        (!follow_mode).then(|| std::time::Duration::from_secs(60)),
+    );
+    let task = supervise(
+        "ledger_ingestion",
+        RestartPolicy::default(),
+        shutdown_coordinator.subscribe(),
+        move |shutdown_rx2| {
+            let ledger_ingestion_clone = Arc::clone(&ledger_ingestion_clone);
+            let ledger_ingestion_status = ledger_ingestion_status.clone();
+            let metrics_writer = Arc::clone(&metrics_writer);
+            let follow_config = follow_config.clone();
+            let batch_config = batch_config.clone();
+            async move {
+                tracing::info!("Starting ledger ingestion background task");
+                if follow_mode {
+                    ledger_ingestion_clone
+                        .run_follow(&follow_config, shutdown_rx2)
+                        .await;
+                    ledger_ingestion_status.stopped();
+                    return;
+                }
+                let mut batch_size = AdaptiveBatchSize::new(&batch_config);
+                let mut shutdown_rx = shutdown_rx2;
+                loop {
+                    tokio::select! {
+                        result = ledger_ingestion_clone.ingest_adaptive(&mut batch_size, batch_config.timeout) => {
+                            ledger_ingestion_status.record(&result);
+                            match result {
+                                Ok(progress) => {
+                                    obs_metrics::record_background_job("ledger_ingestion", "success");
+                                    for (name, count) in [
+                                        ("ledger_ingestion.ledgers_ingested", progress.ingested),
+                                        ("ledger_ingestion.ledgers_skipped", progress.skipped),
+                                    ] {
+                                        if let Err(e) = metrics_writer.record(name, count as f64, None, None).await {
+                                            tracing::warn!("Failed to record ingestion metrics: {}", e);
+                                        }
+                                    }
+                                    if !progress.advanced() {
+                                        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                                    } else {
+                                        tokio::task::yield_now().await;
+                                    }
+                                }
+                                Err(e) => {
+                                    tracing::error!("Ledger ingestion failed: {}", e);
+                                    obs_metrics::record_background_job("ledger_ingestion", "error");
+                                    tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+                                }
                             }
                         }
-                        Err(e) => {
-                            tracing::error!("Ledger ingestion failed: {}", e);
-                            obs_metrics::record_background_job("ledger_ingestion", "error");
-                            tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+                        _ = shutdown_rx.recv() => {
+                            tracing::info!("Ledger ingestion task shutting down");
+                            break;
                         }
                     }
                 }
-                _ = shutdown_rx.recv() => {
-                    tracing::info!("Ledger ingestion task shutting down");
-                    break;
-                }
+                ledger_ingestion_status.stopped();
             }
-        }
-    });
+        },
+    );
     background_tasks.push(task);
 
     // Liquidity pool sync background task
     let lp_analyzer_clone = Arc::clone(&lp_analyzer);
-    let shutdown_rx3 = shutdown_coordinator.subscribe();
-    let task = tokio::spawn(async move {
-        tracing::info!("Starting liquidity pool sync background task");
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(300)); // 5 minutes
-        let mut shutdown_rx = shutdown_rx3;
-        loop {
-            tokio::select! {
-                _ = interval.tick() => {
-                    if let Err(e) = lp_analyzer_clone.sync_pools().await {
-                        tracing::error!("Liquidity pool sync failed: {}", e);
-                        obs_metrics::record_background_job("liquidity_pool_sync", "error");
-                    } else {
-                        obs_metrics::record_background_job("liquidity_pool_sync", "success");
-                    }
-                    if let Err(e) = lp_analyzer_clone.take_snapshots().await {
-                        tracing::error!("Liquidity pool snapshot failed: {}", e);
-                        obs_metrics::record_background_job("liquidity_pool_snapshot", "error");
-                    } else {
-                        obs_metrics::record_background_job("liquidity_pool_snapshot", "success");
+    let lp_sync_status = task_registry.register(
+        "liquidity_pool_sync",
+        Some(std::time::Duration::from_secs(300)),
+    );
+    let task = supervise(
+        "liquidity_pool_sync",
+        RestartPolicy::default(),
+        shutdown_coordinator.subscribe(),
+        move |shutdown_rx3| {
+            let lp_analyzer_clone = Arc::clone(&lp_analyzer_clone);
+            let lp_sync_status = lp_sync_status.clone();
+            async move {
+                tracing::info!("Starting liquidity pool sync background task");
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(300)); // 5 minutes
+                let mut shutdown_rx = shutdown_rx3;
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            if let Err(e) = lp_analyzer_clone.sync_pools().await {
+                                tracing::error!("Liquidity pool sync failed: {}", e);
+                                obs_metrics::record_background_job("liquidity_pool_sync", "error");
+                                lp_sync_status.error(format!("sync: {}", e));
+                            } else {
+                                obs_metrics::record_background_job("liquidity_pool_sync", "success");
+                                lp_sync_status.success();
+                            }
+                            if let Err(e) = lp_analyzer_clone.take_snapshots().await {
+                                tracing::error!("Liquidity pool snapshot failed: {}", e);
+                                obs_metrics::record_background_job("liquidity_pool_snapshot", "error");
+                                lp_sync_status.error(format!("snapshot: {}", e));
+                            } else {
+                                obs_metrics::record_background_job("liquidity_pool_snapshot", "success");
+                            }
+                        }
+                        _ = shutdown_rx.recv() => {
+                            tracing::info!("Liquidity pool sync task shutting down");
+                            break;
+                        }
                     }
                 }
-                _ = shutdown_rx.recv() => {
-                    tracing::info!("Liquidity pool sync task shutting down");
-                    break;
-                }
+                lp_sync_status.stopped();
             }
-        }
-    });
+        },
+    );
     background_tasks.push(task);
 
     // Trustline stats sync background task
     let trustline_analyzer_clone = Arc::clone(&trustline_analyzer);
-    let shutdown_rx4 = shutdown_coordinator.subscribe();
-    let task = tokio::spawn(async move {
-        tracing::info!("Starting trustline stats sync background task");
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(900)); // 15 minutes
-        let mut shutdown_rx = shutdown_rx4;
-        loop {
-            tokio::select! {
-                _ = interval.tick() => {
-                    if let Err(e) = trustline_analyzer_clone.sync_assets().await {
-                        tracing::error!("Trustline sync failed: {}", e);
-                        obs_metrics::record_background_job("trustline_sync", "error");
-                    } else {
-                        obs_metrics::record_background_job("trustline_sync", "success");
-                    }
-                    if let Err(e) = trustline_analyzer_clone.take_snapshots().await {
-                        tracing::error!("Trustline snapshot failed: {}", e);
-                        obs_metrics::record_background_job("trustline_snapshot", "error");
-                    } else {
-                        obs_metrics::record_background_job("trustline_snapshot", "success");
+    let trustline_sync_status =
+        task_registry.register("trustline_sync", Some(std::time::Duration::from_secs(900)));
+    let task = supervise(
+        "trustline_sync",
+        RestartPolicy::default(),
+        shutdown_coordinator.subscribe(),
+        move |shutdown_rx4| {
+            let trustline_analyzer_clone = Arc::clone(&trustline_analyzer_clone);
+            let trustline_sync_status = trustline_sync_status.clone();
+            async move {
+                tracing::info!("Starting trustline stats sync background task");
+                let mut interval = tokio::time::interval(std::time::Duration::from_secs(900)); // 15 minutes
+                let mut shutdown_rx = shutdown_rx4;
+                loop {
+                    tokio::select! {
+                        _ = interval.tick() => {
+                            if let Err(e) = trustline_analyzer_clone.sync_assets().await {
+                                tracing::error!("Trustline sync failed: {}", e);
+                                obs_metrics::record_background_job("trustline_sync", "error");
+                                trustline_sync_status.error(format!("sync: {}", e));
+                            } else {
+                                obs_metrics::record_background_job("trustline_sync", "success");
+                                trustline_sync_status.success();
+                            }
+                            if let Err(e) = trustline_analyzer_clone.sync_concentration().await {
+                                tracing::error!("Trustline concentration sync failed: {}", e);
+                                obs_metrics::record_background_job("trustline_concentration", "error");
+                                trustline_sync_status.error(format!("concentration: {}", e));
+                            } else {
+                                obs_metrics::record_background_job("trustline_concentration", "success");
+                            }
+                            if let Err(e) = trustline_analyzer_clone.take_snapshots().await {
+                                tracing::error!("Trustline snapshot failed: {}", e);
+                                obs_metrics::record_background_job("trustline_snapshot", "error");
+                                trustline_sync_status.error(format!("snapshot: {}", e));
+                            } else {
+                                obs_metrics::record_background_job("trustline_snapshot", "success");
+                            }
+                        }
+                        _ = shutdown_rx.recv() => {
+                            tracing::info!("Trustline stats sync task shutting down");
+                            break;
+                        }
                     }
                 }
-                _ = shutdown_rx.recv() => {
-                    tracing::info!("Trustline stats sync task shutting down");
-                    break;
-                }
+                trustline_sync_status.stopped();
             }
-        }
-    });
+        },
+    );
     background_tasks.push(task);
 
     // Start RealtimeBroadcaster background task
     let shutdown_rx5 = shutdown_coordinator.subscribe();
+    let broadcaster_status = task_registry.register("realtime_broadcaster", None);
     let task = tokio::spawn(async move {
         tracing::info!("Starting RealtimeBroadcaster background task");
         let mut shutdown_rx = shutdown_rx5;
@@ -508,6 +708,7 @@ async fn main() -> Result<()> {
                 tracing::info!("RealtimeBroadcaster task shutting down");
             }
         }
+        broadcaster_status.stopped();
     });
     background_tasks.push(task);
 
@@ -548,26 +749,97 @@ async fn main() -> Result<()> {
     background_tasks.push(task);
     tracing::info!("Corridor monitor task started");
 
+    // Start domain event consumers (webhooks + WebSocket)
+    let event_sampling = config.event_sampling.clone();
+    if !event_sampling.is_empty() {
+        tracing::info!("Event sampling enabled: {:?}", event_sampling);
+    }
+    let dispatcher_clone = Arc::clone(&webhook_dispatcher);
+    let event_rx = event_bus.subscribe_sampled("webhooks", event_sampling.clone());
+    let shutdown_rx_events = shutdown_coordinator.subscribe();
+    let webhook_consumer_status = task_registry.register("webhook_event_consumer", None);
+    let task = tokio::spawn(async move {
+        let mut shutdown_rx = shutdown_rx_events;
+        tokio::select! {
+            _ = dispatcher_clone.run_event_consumer(event_rx) => {}
+            _ = shutdown_rx.recv() => {
+                tracing::info!("Webhook event consumer shutting down");
+            }
+        }
+        webhook_consumer_status.stopped();
+    });
+    background_tasks.push(task);
+
+    let ws_state_clone = Arc::clone(&ws_state);
+    let event_rx = event_bus.subscribe_sampled("websocket", event_sampling);
+    let shutdown_rx_events = shutdown_coordinator.subscribe();
+    let ws_forwarder_status = task_registry.register("websocket_event_forwarder", None);
+    let task = tokio::spawn(async move {
+        let mut shutdown_rx = shutdown_rx_events;
+        tokio::select! {
+            _ = run_domain_event_forwarder(ws_state_clone, event_rx) => {}
+            _ = shutdown_rx.recv() => {
+                tracing::info!("WebSocket event forwarder shutting down");
+            }
+        }
+        ws_forwarder_status.stopped();
+    });
+    background_tasks.push(task);
+
+    // Alert on RPC circuit breaker outages and recoveries
+    let rpc_health_monitor = RpcHealthMonitor::new(
+        Arc::clone(&event_bus),
+        "stellar_rpc",
+        config.rpc_health_monitor,
+    );
+    let circuit_rx = rpc_client.subscribe_circuit_transitions();
+    let shutdown_rx_rpc_health = shutdown_coordinator.subscribe();
+    let rpc_health_status = task_registry.register("rpc_health_monitor", None);
+    let task = tokio::spawn(async move {
+        rpc_health_monitor
+            .run(circuit_rx, shutdown_rx_rpc_health)
+            .await;
+        rpc_health_status.stopped();
+    });
+    background_tasks.push(task);
+
+    // Deliver stale price alerts to webhook subscribers
+    let price_feed_clone = Arc::clone(&price_feed);
+    let event_bus_clone = Arc::clone(&event_bus);
+    let shutdown_rx_price_alerts = shutdown_coordinator.subscribe();
+    let price_alerts_status = task_registry.register("stale_price_alert_forwarder", None);
+    let task = tokio::spawn(async move {
+        price_feed_clone
+            .run_stale_alert_forwarder(event_bus_clone, shutdown_rx_price_alerts)
+            .await;
+        price_alerts_status.stopped();
+    });
+    background_tasks.push(task);
+
     // Start Webhook Dispatcher background task
     let shutdown_rx6 = shutdown_coordinator.subscribe();
+    let dispatcher_status = task_registry.register("webhook_dispatcher", None);
     let task = tokio::spawn(async move {
         let mut shutdown_rx = shutdown_rx6;
         tokio::select! {
             result = webhook_dispatcher.run() => {
                 if let Err(e) = result {
                     tracing::error!("Webhook dispatcher encountered fatal error: {}", e);
+                    dispatcher_status.error(&e);
                 }
             }
             _ = shutdown_rx.recv() => {
                 tracing::info!("Webhook dispatcher task shutting down");
             }
         }
+        dispatcher_status.stopped();
     });
     background_tasks.push(task);
 
     // Start CorridorMonitor background task
     let monitor_clone = Arc::clone(&corridor_monitor);
     let shutdown_rx_monitor = shutdown_coordinator.subscribe();
+    let monitor_status = task_registry.register("corridor_monitor", None);
     let task = tokio::spawn(async move {
         let mut shutdown_rx = shutdown_rx_monitor;
         tokio::select! {
@@ -578,6 +850,7 @@ async fn main() -> Result<()> {
                 tracing::info!("CorridorMonitor task shutting down");
             }
         }
+        monitor_status.stopped();
     });
     background_tasks.push(task);
 
@@ -609,7 +882,7 @@ async fn main() -> Result<()> {
 
     // Start background job scheduler
     tracing::info!("Starting background job scheduler...");
-    let _job_scheduler = JobScheduler::start(
+    let mut job_scheduler = JobScheduler::start(
         Arc::clone(&db),
         Arc::clone(&cache),
         Arc::clone(&rpc_client),
@@ -617,6 +890,73 @@ async fn main() -> Result<()> {
         Arc::clone(&price_feed),
     )
     .await;
+
+    // Anchor home domain re-verification job
+    let toml_client = Arc::new(StellarTomlClient::new(
+        Arc::new(tokio::sync::RwLock::new(None)),
+        None,
+    )?);
+    let domain_verifier = Arc::new(AnchorDomainVerifier::new(
+        Arc::clone(&db),
+        Arc::clone(&toml_client),
+    ));
+    let domain_recheck = Arc::new(
+        AnchorDomainRecheckJob::new(Arc::clone(&db), domain_verifier, Arc::clone(&event_bus))
+            .with_failure_threshold(config.anchor_domain_failure_threshold),
+    );
+    job_scheduler.add_job(
+        JobConfig::from_env("anchor-domain-recheck", 6 * 3600),
+        move || {
+            let domain_recheck = Arc::clone(&domain_recheck);
+            Box::pin(async move {
+                domain_recheck.run_once().await?;
+                Ok(())
+            })
+        },
+    );
+
+    // Anchor display metadata from stellar.toml
+    let metadata_enricher = Arc::new(AnchorMetadataEnricher::new(Arc::clone(&db), toml_client));
+    job_scheduler.add_job(
+        JobConfig::from_env("anchor-metadata-enrichment", 24 * 3600),
+        move || {
+            let metadata_enricher = Arc::clone(&metadata_enricher);
+            Box::pin(async move {
+                metadata_enricher.enrich_all().await?;
+                Ok(())
+            })
+        },
+    );
+
+    // Assets issued by each anchor's account on Horizon
+    let asset_discovery = Arc::new(AnchorAssetDiscovery::new(
+        Arc::clone(&db),
+        Arc::clone(&rpc_client),
+    ));
+    job_scheduler.add_job(
+        JobConfig::from_env("anchor-asset-discovery", 24 * 3600),
+        move || {
+            let asset_discovery = Arc::clone(&asset_discovery);
+            Box::pin(async move {
+                asset_discovery.discover_all().await?;
+                Ok(())
+            })
+        },
+    );
+
+    // Idempotency keys past their TTL
+    let idempotency_store = Arc::new(IdempotencyStore::new(pool.clone()));
+    job_scheduler.add_job(
+        JobConfig::from_env("idempotency-key-cleanup", 3600),
+        move || {
+            let idempotency_store = Arc::clone(&idempotency_store);
+            Box::pin(async move {
+                let pruned = idempotency_store.prune_expired().await?;
+                tracing::debug!("Pruned {} expired idempotency keys", pruned);
+                Ok(())
+            })
+        },
+    );
     tracing::info!("Background job scheduler started");
 
     // Initialize rate limiter with database support for API key validation
@@ -639,21 +979,22 @@ async fn main() -> Result<()> {
         }
     };
 
-    // Configure rate limits for endpoints with per-client tiers
-    rate_limiter
-        .register_endpoint(
-            "/health".to_string(),
-            RateLimitConfig {
-                requests_per_minute: 1000,
-                whitelist_ips: vec!["127.0.0.1".to_string()],
-                client_limits: Some(ClientRateLimits {
-                    authenticated: 1000,
-                    premium: 5000,
-                    anonymous: 1000,
-                }),
-            },
-        )
-        .await;
+    // Configure rate limits for endpoints with per-client tiers; the liveness
+    // and readiness probes share one limit
+    let probe_rate_limit = RateLimitConfig {
+        requests_per_minute: 1000,
+        whitelist_ips: vec!["127.0.0.1".to_string()],
+        client_limits: Some(ClientRateLimits {
+            authenticated: 1000,
+            premium: 5000,
+            anonymous: 1000,
+        }),
+    };
+    for probe in ["/health", "/ready"] {
+        rate_limiter
+            .register_endpoint(probe.to_string(), probe_rate_limit.clone())
+            .await;
+    }
 
     rate_limiter
         .register_endpoint(
@@ -801,78 +1142,29 @@ async fn main() -> Result<()> {
     // Read comma-separated allowed origins from env.
     // Use "*" to allow all origins (development only).
     // Production example: CORS_ALLOWED_ORIGINS=https://stellar-insights.com
-    let cors_allowed_origins = std::env::var("CORS_ALLOWED_ORIGINS")
-        .unwrap_or_else(|_| "http://localhost:3000,http://localhost:3001".to_string());
+    let cors_allowed_origins = &config.cors_allowed_origins;
 
     tracing::info!(
         "Configuring CORS with allowed origins: {}",
         cors_allowed_origins
     );
 
-    let cors_methods = [
-        Method::GET,
-        Method::POST,
-        Method::PUT,
-        Method::DELETE,
-        Method::OPTIONS,
-        Method::PATCH,
-        Method::HEAD,
-    ];
-
-    let cors = {
-        let base = CorsLayer::new()
-            .allow_methods(cors_methods)
-            .allow_headers(Any)
-            .max_age(Duration::from_secs(3600));
-
-        if cors_allowed_origins.trim() == "*" {
-            tracing::warn!(
-                "CORS configured to allow ALL origins (*). \
-                 This is insecure and should not be used in production."
-            );
-            base.allow_origin(Any)
-        } else {
-            let origins: Vec<axum::http::HeaderValue> = cors_allowed_origins
-                .split(',')
-                .filter_map(|o| {
-                    let trimmed = o.trim();
-                    trimmed
-                        .parse::<axum::http::HeaderValue>()
-                        .map_err(|e| {
-                            tracing::warn!("Skipping invalid CORS origin '{}': {}", trimmed, e);
-                        })
-                        .ok()
-                })
-                .collect();
-
-            if origins.is_empty() {
-                panic!(
-                    "CORS_ALLOWED_ORIGINS contains no valid origins. \
-                     Set valid origins or use '*' explicitly for development. \
-                     Refusing to fall back to allow-all. (SEC-011)"
-                );
-            } else {
-                tracing::info!("CORS restricted to {} specific origin(s)", origins.len());
-                base.allow_origin(origins)
-            }
-        }
-    };
+    let cors = cors::cors_layer(
+        cors_allowed_origins,
+        Duration::from_secs(config.cors_max_age_seconds),
+    )?;
 
     // Compression configuration
-    // Only compress responses larger than 1KB to avoid overhead on small responses
-    let compression_min_size = std::env::var("COMPRESSION_MIN_SIZE")
-        .ok()
-        .and_then(|s| s.parse::<u16>().ok())
-        .unwrap_or(1024);
-
-    let compression = CompressionLayer::new()
-        .gzip(true)
-        .br(true)
-        .compress_when(SizeAbove::new(compression_min_size));
+    // Only compress responses above COMPRESSION_MIN_SIZE to avoid overhead on small responses
+    let compression_config = config.response_compression();
+    let compression = compression_layer(&compression_config);
 
     tracing::info!(
-        "Compression enabled (gzip, brotli) for responses > {} bytes",
-        compression_min_size
+        "Compression enabled ({}, level {:?}) for responses > {} bytes, except {}",
+        compression_config.algorithms,
+        compression_config.quality,
+        compression_config.min_size,
+        compression_config.excluded_content_types.join(", ")
     );
 
     // Import middleware
@@ -897,12 +1189,17 @@ async fn main() -> Result<()> {
     // Build non-cached anchor routes with app state
     let anchor_routes = Router::new()
         .route("/health", get(health_check))
+        .route("/ready", get(ready_check))
         .route("/api/anchors/:id", get(get_anchor))
         .route(
             "/api/anchors/account/:stellar_account",
             get(get_anchor_by_account),
         )
         .route("/api/anchors/:id/assets", get(get_anchor_assets))
+        .route(
+            "/api/corridors/:corridor_key/payments",
+            get(get_corridor_payments),
+        )
         .route("/api/analytics/muxed", get(get_muxed_analytics))
         .with_state(app_state.clone())
         .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
@@ -911,22 +1208,54 @@ async fn main() -> Result<()> {
         )))
         .layer(cors.clone());
 
-    // Build protected anchor routes (require authentication)
+    // Build protected anchor routes (require authentication, a write scope
+    // and, for a specific anchor or corridor, permission on it)
+    let resource_acl = ResourceAcl::new(pool.clone());
+    let protected_corridor_routes = Router::new()
+        .route("/api/corridors", axum::routing::post(create_corridor))
+        .route("/api/corridors/:id", axum::routing::delete(delete_corridor))
+        .route(
+            "/api/corridors/:id/metrics-from-transactions",
+            put(update_corridor_metrics_from_transactions),
+        )
+        .route_layer(middleware::from_fn_with_state(
+            ResourcePermission {
+                acl: resource_acl.clone(),
+                resource: ResourceType::Corridor,
+            },
+            require_resource_permission,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            RequiredScope(scopes::CORRIDOR_WRITE),
+            require_scope,
+        ));
     let protected_anchor_routes = Router::new()
         .route("/api/anchors", axum::routing::post(create_anchor))
+        .route(
+            "/api/anchors/bulk",
+            axum::routing::post(create_anchors_bulk),
+        )
         .route("/api/anchors/:id/metrics", put(update_anchor_metrics))
         .route(
             "/api/anchors/:id/assets",
             axum::routing::post(create_anchor_asset),
         )
-        .route("/api/corridors", axum::routing::post(create_corridor))
-        .route(
-            "/api/corridors/:id/metrics-from-transactions",
-            put(update_corridor_metrics_from_transactions),
-        )
+        .route_layer(middleware::from_fn_with_state(
+            ResourcePermission {
+                acl: resource_acl,
+                resource: ResourceType::Anchor,
+            },
+            require_resource_permission,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            RequiredScope(scopes::ANCHOR_WRITE),
+            require_scope,
+        ))
+        .merge(protected_corridor_routes)
         .with_state(app_state.clone())
         .layer(
             ServiceBuilder::new()
+                .layer(Extension(jwt_secret.clone()))
                 .layer(middleware::from_fn(auth_middleware))
                 .layer(middleware::from_fn_with_state(
                     rate_limiter.clone(),
@@ -940,10 +1269,15 @@ async fn main() -> Result<()> {
 
     // Build webhook routes (require authentication)
     let webhook_routes = Router::new()
-        .nest("/api/webhooks", webhooks::routes(pool.clone()))
+        .merge(webhooks::routes(pool.clone(), config.webhook_limits))
         .layer(
             ServiceBuilder::new()
+                .layer(Extension(jwt_secret.clone()))
                 .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    RequiredScope(scopes::WEBHOOK_MANAGE),
+                    require_scope,
+                ))
                 .layer(middleware::from_fn_with_state(
                     rate_limiter.clone(),
                     rate_limit_middleware,
@@ -953,6 +1287,24 @@ async fn main() -> Result<()> {
 
     // Build metrics routes (public)
     let metrics_routes = metrics_cached::routes(Arc::clone(&cache));
+    let overview_routes =
+        overview::routes(Arc::clone(&db), Arc::clone(&cache), Arc::clone(&rpc_client));
+
+    // Cap in-flight requests to the RPC routes, which fan out to Horizon
+    let rpc_concurrency_limiter = Arc::new(ConcurrencyLimiter::new(
+        "rpc",
+        config.concurrency_limit.rpc_max_concurrent_requests,
+        config.concurrency_limit.retry_after_seconds,
+    ));
+
+    // Pairs the order-book and cost comparison endpoints serve
+    let pair_registry = Arc::new(config.supported_asset_pairs.clone());
+    if pair_registry.is_restricted() {
+        tracing::info!(
+            "Serving {} supported asset pair(s)",
+            pair_registry.pairs().len()
+        );
+    }
 
     // Build RPC router
     let rpc_routes = Router::new()
@@ -968,7 +1320,12 @@ async fn main() -> Result<()> {
         )
         .route("/api/rpc/trades", get(rpc_handlers::get_trades))
         .route("/api/rpc/orderbook", get(rpc_handlers::get_order_book))
-        .with_state(rpc_client)
+        .with_state(Arc::clone(&rpc_client))
+        .layer(Extension(Arc::clone(&pair_registry)))
+        .layer(middleware::from_fn_with_state(
+            rpc_concurrency_limiter,
+            concurrency_limit_middleware,
+        ))
         .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
             rate_limiter.clone(),
             rate_limit_middleware,
@@ -1024,10 +1381,28 @@ async fn main() -> Result<()> {
         .layer(cors.clone());
 
     // Build cost calculator routes
+    let fee_estimation_config = config.fee_estimation.clone();
+    tracing::info!(
+        "Cost calculator fee estimation strategy: {}",
+        fee_estimation_config.strategy.label()
+    );
+    let fee_estimator = Arc::new(FeeEstimator::new(
+        fee_estimation_config,
+        Some(Arc::clone(&rpc_client)),
+    ));
+    let path_finder = Arc::new(PathFinder::new(
+        Arc::clone(&rpc_client) as Arc<dyn OrderBookSource>,
+        config.path_finder.clone(),
+    ));
     let cost_calculator_routes = Router::new()
         .nest(
             "/api/cost-calculator",
-            cost_calculator::routes(Arc::clone(&price_feed)),
+            cost_calculator::routes_with_state(cost_calculator::CostCalculatorState {
+                price_feed: Arc::clone(&price_feed),
+                fee_estimator,
+                path_finder: Some(path_finder),
+                pair_registry: Arc::clone(&pair_registry),
+            }),
         )
         .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
             rate_limiter.clone(),
@@ -1145,6 +1520,86 @@ async fn main() -> Result<()> {
         )
         .layer(cors.clone());
 
+    // Build background task status routes (ADMIN - IP whitelisted)
+    let admin_task_routes = admin_tasks::routes(Arc::clone(&task_registry))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    ip_whitelist_config.clone(),
+                    ip_whitelist_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build admin audit log routes (ADMIN - IP whitelisted, admin scope)
+    let admin_audit_routes = admin_audit::routes(Arc::clone(&db))
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    ip_whitelist_config.clone(),
+                    ip_whitelist_middleware,
+                ))
+                .layer(Extension(jwt_secret.clone()))
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    RequiredScope(scopes::ADMIN),
+                    require_scope,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build resource permission routes (ADMIN - IP whitelisted, admin scope)
+    let resource_permission_routes = resource_permissions::routes(pool.clone())
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    ip_whitelist_config.clone(),
+                    ip_whitelist_middleware,
+                ))
+                .layer(Extension(jwt_secret.clone()))
+                .layer(middleware::from_fn(auth_middleware))
+                .layer(middleware::from_fn_with_state(
+                    RequiredScope(scopes::ADMIN),
+                    require_scope,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
+    // Build public snapshot routes
+    let snapshot_routes = snapshot_handlers::routes(snapshot_state.clone())
+        .layer(ServiceBuilder::new().layer(middleware::from_fn_with_state(
+            rate_limiter.clone(),
+            rate_limit_middleware,
+        )))
+        .layer(cors.clone());
+
+    // Build manual snapshot routes (ADMIN - IP whitelisted)
+    let admin_snapshot_routes = snapshot_handlers::admin_routes(snapshot_state)
+        .layer(
+            ServiceBuilder::new()
+                .layer(middleware::from_fn_with_state(
+                    ip_whitelist_config.clone(),
+                    ip_whitelist_middleware,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    rate_limiter.clone(),
+                    rate_limit_middleware,
+                )),
+        )
+        .layer(cors.clone());
+
     // Build governance routes
     let governance_routes = Router::new()
         .nest(
@@ -1280,14 +1735,36 @@ async fn main() -> Result<()> {
         .merge(api_analytics_routes)
         .merge(cache_routes)
         .merge(metrics_routes)
+        .merge(overview_routes)
         // .merge(graphql_routes) // Add GraphQL routes
         .merge(admin_db_routes)
+        .merge(admin_audit_routes)
+        .merge(resource_permission_routes)
+        .merge(admin_task_routes)
+        .merge(snapshot_routes)
+        .merge(admin_snapshot_routes)
         .merge(verification_routes)
         .merge(asset_verification_routes)
         // .merge(gdpr_routes)
         .merge(api_key_routes)
         .merge(ws_routes)
-        .merge(alert_ws_routes)
+        .merge(alert_ws_routes);
+
+    // Database reads give up once a request has used its time budget
+    let deadline_config = config.request_deadline;
+    tracing::info!(
+        "Request deadline for database reads: {:?}",
+        deadline_config.max_request_duration
+    );
+
+    // Unmatched routes and disallowed methods answer with JSON errors
+    let app = with_json_fallbacks(app)
+        // List endpoints read their page limits from this extension
+        .layer(Extension(config.pagination))
+        .layer(middleware::from_fn_with_state(
+            deadline_config,
+            request_deadline_middleware,
+        ))
         .layer(middleware::from_fn_with_state(
             db.clone(),
             stellar_insights_backend::api_analytics_middleware::api_analytics_middleware,
@@ -1297,10 +1774,15 @@ async fn main() -> Result<()> {
         .layer(middleware::from_fn(request_id_middleware))
         .layer(compression); // Apply compression to all routes
 
+    // Accept gzip/brotli request bodies, size-limited after decompression
+    let app = with_request_decompression(app, config.max_request_body_bytes);
+    tracing::info!(
+        "Request decompression enabled (gzip, brotli), max body {} bytes",
+        config.max_request_body_bytes
+    );
+
     // Start server
-    let host = std::env::var("SERVER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
-    let port = std::env::var("SERVER_PORT").unwrap_or_else(|_| "8080".to_string());
-    let addr = format!("{}:{}", host, port);
+    let addr = config.server_addr();
 
     tracing::info!("Server starting on {}", addr);
     tracing::info!(
@@ -1348,6 +1830,7 @@ async fn main() -> Result<()> {
     // Graceful shutdown sequence
     tracing::info!("Step 1/4: Shutting down background tasks");
     shutdown_background_tasks(background_tasks, shutdown_config.background_task_timeout).await;
+    job_scheduler.shutdown().await;
 
     tracing::info!("Step 2/4: Closing WebSocket connections");
     shutdown_websockets(ws_state_for_shutdown, Duration::from_secs(5)).await;
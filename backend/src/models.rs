@@ -2,8 +2,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 pub mod api_key;
+pub mod asset;
 pub mod asset_verification;
 pub mod corridor;
+pub mod timestamp;
+pub mod transaction_envelope;
 pub mod alerts;
 
 
@@ -37,6 +40,76 @@ pub struct Anchor {
     pub status: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Whether the home domain's stellar.toml lists `stellar_account`
+    #[sqlx(default)]
+    pub domain_verified: bool,
+    /// Consecutive failed home domain re-checks
+    #[sqlx(default)]
+    pub domain_check_failures: i64,
+}
+
+/// Position just past an anchor in `list_anchors` order: reliability score,
+/// then last update, then id, all descending
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnchorCursor {
+    pub reliability_score: f64,
+    pub updated_at: DateTime<Utc>,
+    pub id: String,
+}
+
+impl AnchorCursor {
+    pub fn after(anchor: &Anchor) -> Self {
+        Self {
+            reliability_score: anchor.reliability_score,
+            updated_at: anchor.updated_at,
+            id: anchor.id.clone(),
+        }
+    }
+
+    /// Opaque, URL-safe form handed to clients
+    pub fn encode(&self) -> String {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+        let raw = format!(
+            "{}|{}|{}",
+            self.reliability_score,
+            self.updated_at
+                .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            self.id
+        );
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+        let raw = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+        let mut parts = raw.splitn(3, '|');
+        let reliability_score = parts.next()?.parse::<f64>().ok()?;
+        let updated_at = DateTime::parse_from_rfc3339(parts.next()?)
+            .ok()?
+            .with_timezone(&Utc);
+        let id = parts.next()?.to_string();
+        (reliability_score.is_finite() && !id.is_empty()).then_some(Self {
+            reliability_score,
+            updated_at,
+            id,
+        })
+    }
+}
+
+impl CorridorRecord {
+    pub fn get_corridor(&self) -> crate::models::corridor::Corridor {
+        let build = if self.is_directional {
+            crate::models::corridor::Corridor::directional
+        } else {
+            crate::models::corridor::Corridor::new
+        };
+        build(
+            self.source_asset_code.clone(),
+            self.source_asset_issuer.clone(),
+            self.destination_asset_code.clone(),
+            self.destination_asset_issuer.clone(),
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -119,6 +192,10 @@ pub struct CreateCorridorRequest {
     pub source_asset_issuer: String,
     pub dest_asset_code: String,
     pub dest_asset_issuer: String,
+    /// Keep source -> destination as its own corridor instead of
+    /// normalizing the asset pair
+    #[serde(default)]
+    pub directional: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -146,6 +223,9 @@ pub struct CorridorRecord {
     pub status: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Whether source -> destination is a distinct corridor from its reverse
+    #[sqlx(default)]
+    pub is_directional: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -171,6 +251,40 @@ pub struct SnapshotRecord {
     pub created_at: DateTime<Utc>,
 }
 
+/// Position just past a snapshot in `list_snapshots` order: epoch, then id,
+/// both descending
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotCursor {
+    pub epoch: i64,
+    pub id: String,
+}
+
+impl SnapshotCursor {
+    pub fn after(snapshot: &SnapshotRecord) -> Option<Self> {
+        Some(Self {
+            epoch: snapshot.epoch?,
+            id: snapshot.id.clone(),
+        })
+    }
+
+    /// Opaque, URL-safe form handed to clients
+    pub fn encode(&self) -> String {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+        URL_SAFE_NO_PAD.encode(format!("{}|{}", self.epoch, self.id))
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+        let raw = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+        let (epoch, id) = raw.split_once('|')?;
+        let epoch = epoch.parse::<i64>().ok()?;
+        (!id.is_empty()).then(|| Self {
+            epoch,
+            id: id.to_string(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct PaymentRecord {
     pub id: String,
@@ -227,6 +341,66 @@ impl PaymentRecord {
     }
 }
 
+/// Stored payment as listed under its corridor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+pub struct CorridorPayment {
+    pub id: String,
+    pub transaction_hash: String,
+    pub source_account: String,
+    pub destination_account: String,
+    /// Asset sent, absent for the native asset
+    pub source_asset_code: Option<String>,
+    pub source_asset_issuer: Option<String>,
+    /// Asset received
+    pub asset_type: String,
+    pub asset_code: Option<String>,
+    pub asset_issuer: Option<String>,
+    pub amount: f64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Position just past a payment in corridor payment order: creation time,
+/// then id, both descending
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: String,
+}
+
+impl PaymentCursor {
+    pub fn after(payment: &CorridorPayment) -> Self {
+        Self {
+            created_at: payment.created_at,
+            id: payment.id.clone(),
+        }
+    }
+
+    /// Opaque, URL-safe form handed to clients
+    pub fn encode(&self) -> String {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+        let raw = format!(
+            "{}|{}",
+            self.created_at
+                .to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true),
+            self.id
+        );
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    pub fn decode(cursor: &str) -> Option<Self> {
+        use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+        let raw = String::from_utf8(URL_SAFE_NO_PAD.decode(cursor).ok()?).ok()?;
+        let (created_at, id) = raw.split_once('|')?;
+        let created_at = DateTime::parse_from_rfc3339(created_at)
+            .ok()?
+            .with_timezone(&Utc);
+        (!id.is_empty()).then(|| Self {
+            created_at,
+            id: id.to_string(),
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct IngestionState {
     pub task_name: String,
@@ -245,6 +419,8 @@ pub struct FeeBumpTransaction {
     pub inner_max_fee: i64,
     pub signatures_count: i32,
     pub created_at: DateTime<Utc>,
+    /// Whether the sponsored inner transaction succeeded
+    pub inner_successful: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -254,6 +430,8 @@ pub struct FeeBumpStats {
     pub max_fee_charged: i64,
     pub min_fee_charged: i64,
     pub unique_fee_sources: i64,
+    /// Fee bumps whose fee was paid but whose inner transaction failed
+    pub sponsored_failed_count: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -378,6 +556,16 @@ pub struct TrustlineStat {
     pub total_supply: f64,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Share of sampled holdings held by the ten largest holders (0-1)
+    pub top10_holder_share: Option<f64>,
+    /// Gini coefficient of sampled holder balances (0 = even, 1 = one holder)
+    pub gini_coefficient: Option<f64>,
+    pub concentration_holders: Option<i64>,
+    /// Why the concentration metrics are null, if they are
+    pub concentration_note: Option<String>,
+    /// The metrics cover a sample of `concentration_holders` holders, not
+    /// every holder
+    pub concentration_sampled: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -390,6 +578,16 @@ pub struct TrustlineSnapshot {
     pub unauthorized_trustlines: i64,
     pub total_supply: f64,
     pub snapshot_at: DateTime<Utc>,
+    /// Share of sampled holdings held by the ten largest holders (0-1)
+    pub top10_holder_share: Option<f64>,
+    /// Gini coefficient of sampled holder balances (0 = even, 1 = one holder)
+    pub gini_coefficient: Option<f64>,
+    pub concentration_holders: Option<i64>,
+    /// Why the concentration metrics are null, if they are
+    pub concentration_note: Option<String>,
+    /// The metrics cover a sample of `concentration_holders` holders, not
+    /// every holder
+    pub concentration_sampled: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,194 @@
+//! Normalization and validation of Stellar asset identifiers.
+//!
+//! Everything that stores or keys an asset normalizes its code here, so the
+//! same asset always yields the same `CODE:ISSUER` key. Codes are
+//! case-sensitive on Stellar (`usdc` and `USDC` from one issuer are different
+//! assets), so normalization only trims them.
+
+/// Code used for lumens
+pub const NATIVE_ASSET_CODE: &str = "XLM";
+/// Issuer used for lumens, which have none
+pub const NATIVE_ASSET_ISSUER: &str = "native";
+/// Longest code of a credit asset (`credit_alphanum12`)
+pub const MAX_ASSET_CODE_LEN: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AssetError {
+    #[error("Asset code cannot be empty")]
+    EmptyCode,
+
+    #[error("Asset code '{0}' is longer than {MAX_ASSET_CODE_LEN} characters")]
+    CodeTooLong(String),
+
+    #[error("Asset code '{0}' may only contain letters and digits")]
+    InvalidCode(String),
+
+    #[error("Asset '{0}' has no issuer")]
+    MissingIssuer(String),
+}
+
+/// A validated asset with its code trimmed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetId {
+    pub code: String,
+    pub issuer: String,
+}
+
+impl AssetId {
+    pub fn native() -> Self {
+        Self {
+            code: NATIVE_ASSET_CODE.to_string(),
+            issuer: NATIVE_ASSET_ISSUER.to_string(),
+        }
+    }
+
+    pub fn is_native(&self) -> bool {
+        self.issuer == NATIVE_ASSET_ISSUER
+    }
+
+    /// Horizon asset type: `native`, `credit_alphanum4` or `credit_alphanum12`
+    pub fn asset_type(&self) -> &'static str {
+        if self.is_native() {
+            "native"
+        } else if self.code.len() <= 4 {
+            "credit_alphanum4"
+        } else {
+            "credit_alphanum12"
+        }
+    }
+
+    /// `CODE:ISSUER` key, `XLM:native` for lumens
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.code, self.issuer)
+    }
+}
+
+/// Trimmed asset code, case preserved; `native` is spelled `XLM`
+pub fn normalize_asset_code(code: &str) -> String {
+    let code = code.trim();
+    if code.eq_ignore_ascii_case(NATIVE_ASSET_ISSUER) {
+        return NATIVE_ASSET_CODE.to_string();
+    }
+    code.to_string()
+}
+
+/// Validate an asset code and issuer.
+///
+/// Lumens are `XLM` or `native` in any case, with no issuer or the issuer
+/// `native`.
+/// Credit assets need an issuer and a code of 1-12 ASCII letters and digits.
+pub fn validate_asset(code: &str, issuer: Option<&str>) -> Result<AssetId, AssetError> {
+    let code = normalize_asset_code(code);
+    let issuer = issuer
+        .map(str::trim)
+        .filter(|issuer| !issuer.is_empty() && !issuer.eq_ignore_ascii_case(NATIVE_ASSET_ISSUER));
+
+    let Some(issuer) = issuer else {
+        if code.eq_ignore_ascii_case(NATIVE_ASSET_CODE) {
+            return Ok(AssetId::native());
+        }
+        if code.is_empty() {
+            return Err(AssetError::EmptyCode);
+        }
+        return Err(AssetError::MissingIssuer(code));
+    };
+
+    if code.is_empty() {
+        return Err(AssetError::EmptyCode);
+    }
+    if code.len() > MAX_ASSET_CODE_LEN {
+        return Err(AssetError::CodeTooLong(code));
+    }
+    if !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(AssetError::InvalidCode(code));
+    }
+
+    Ok(AssetId {
+        code,
+        issuer: issuer.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    #[test]
+    fn test_native_asset() {
+        for (code, issuer) in [
+            ("XLM", None),
+            ("xlm", Some("native")),
+            (" native ", None),
+            ("XLM", Some("")),
+        ] {
+            let asset = validate_asset(code, issuer).unwrap();
+            assert!(asset.is_native());
+            assert_eq!(asset.asset_type(), "native");
+            assert_eq!(asset.key(), "XLM:native");
+        }
+    }
+
+    #[test]
+    fn test_alphanum4_asset() {
+        let asset = validate_asset(" USDC ", Some(ISSUER)).unwrap();
+        assert_eq!(asset.code, "USDC");
+        assert_eq!(asset.asset_type(), "credit_alphanum4");
+        assert_eq!(asset.key(), format!("USDC:{}", ISSUER));
+
+        // Codes are case-sensitive: `usdc` is a different asset
+        let lower = validate_asset("usdc", Some(ISSUER)).unwrap();
+        assert_eq!(lower.code, "usdc");
+        assert_ne!(lower, asset);
+
+        // An issued asset named XLM is not lumens
+        let asset = validate_asset("XLM", Some(ISSUER)).unwrap();
+        assert!(!asset.is_native());
+        assert_eq!(asset.asset_type(), "credit_alphanum4");
+    }
+
+    #[test]
+    fn test_alphanum12_asset() {
+        let asset = validate_asset("yUSDCoin2024", Some(ISSUER)).unwrap();
+        assert_eq!(asset.code, "yUSDCoin2024");
+        assert_eq!(asset.asset_type(), "credit_alphanum12");
+        assert_eq!(
+            validate_asset("EURCX", Some(ISSUER)).unwrap().asset_type(),
+            "credit_alphanum12"
+        );
+    }
+
+    #[test]
+    fn test_invalid_assets() {
+        assert_eq!(validate_asset("", Some(ISSUER)), Err(AssetError::EmptyCode));
+        assert_eq!(validate_asset("  ", None), Err(AssetError::EmptyCode));
+        assert_eq!(
+            validate_asset("ABCDEFGHIJKLM", Some(ISSUER)),
+            Err(AssetError::CodeTooLong("ABCDEFGHIJKLM".to_string()))
+        );
+        assert_eq!(
+            validate_asset("US-D", Some(ISSUER)),
+            Err(AssetError::InvalidCode("US-D".to_string()))
+        );
+        assert_eq!(
+            validate_asset("USDÇ", Some(ISSUER)),
+            Err(AssetError::InvalidCode("USDÇ".to_string()))
+        );
+        assert_eq!(
+            validate_asset("USDC", None),
+            Err(AssetError::MissingIssuer("USDC".to_string()))
+        );
+        assert_eq!(
+            validate_asset("USDC", Some("native")),
+            Err(AssetError::MissingIssuer("USDC".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_normalize_asset_code() {
+        assert_eq!(normalize_asset_code(" usdc "), "usdc");
+        assert_eq!(normalize_asset_code("Native"), "XLM");
+        assert_eq!(normalize_asset_code("EURC"), "EURC");
+    }
+}
@@ -1,16 +1,26 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use super::asset::normalize_asset_code;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, sqlx::FromRow)]
 pub struct Corridor {
     pub asset_a_code: String,
     pub asset_a_issuer: String,
     pub asset_b_code: String,
     pub asset_b_issuer: String,
+    /// Whether asset A -> asset B is a direction of its own. Non-directional
+    /// corridors are pair-normalized, so A/B and B/A are the same corridor.
+    #[serde(default)]
+    #[sqlx(default)]
+    pub directional: bool,
 }
 
 impl Corridor {
+    /// Pair-normalized corridor: the assets are sorted, so either order gives
+    /// the same corridor. Asset codes are normalized.
     pub fn new(
         asset_a_code: String,
         asset_a_issuer: String,
@@ -18,15 +28,33 @@ impl Corridor {
         asset_b_issuer: String,
     ) -> Self {
         let mut corridor = Corridor {
-            asset_a_code,
+            asset_a_code: normalize_asset_code(&asset_a_code),
             asset_a_issuer,
-            asset_b_code,
+            asset_b_code: normalize_asset_code(&asset_b_code),
             asset_b_issuer,
+            directional: false,
         };
         corridor.normalize_ordering();
         corridor
     }
 
+    /// Directional corridor from `asset_a` to `asset_b`, kept in the given
+    /// order. Asset codes are normalized.
+    pub fn directional(
+        asset_a_code: String,
+        asset_a_issuer: String,
+        asset_b_code: String,
+        asset_b_issuer: String,
+    ) -> Self {
+        Corridor {
+            asset_a_code: normalize_asset_code(&asset_a_code),
+            asset_a_issuer,
+            asset_b_code: normalize_asset_code(&asset_b_code),
+            asset_b_issuer,
+            directional: true,
+        }
+    }
+
     fn normalize_ordering(&mut self) {
         let asset_a_key = format!("{}:{}", self.asset_a_code, self.asset_a_issuer);
         let asset_b_key = format!("{}:{}", self.asset_b_code, self.asset_b_issuer);
@@ -43,6 +71,17 @@ impl Corridor {
             self.asset_a_code, self.asset_a_issuer, self.asset_b_code, self.asset_b_issuer
         )
     }
+
+    /// Canonical key of the asset pair, the same for both directions
+    pub fn pair_key(&self) -> String {
+        Self::new(
+            self.asset_a_code.clone(),
+            self.asset_a_issuer.clone(),
+            self.asset_b_code.clone(),
+            self.asset_b_issuer.clone(),
+        )
+        .to_string_key()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
@@ -147,6 +186,53 @@ pub fn compute_median(values: &mut [i64]) -> Option<i64> {
     }
 }
 
+/// Fewest latency samples for which p90 is reported
+pub const MIN_SAMPLES_FOR_P90: usize = 10;
+
+/// Fewest latency samples for which p99 is reported
+pub const MIN_SAMPLES_FOR_P99: usize = 100;
+
+/// Settlement latency percentiles of a corridor. High percentiles are `None`
+/// when there are too few samples for them to mean anything.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema, sqlx::FromRow)]
+pub struct LatencyPercentiles {
+    /// Number of latency samples the percentiles were computed from
+    #[schema(example = 250)]
+    pub sample_count: i64,
+    #[schema(example = 4200)]
+    pub p50_ms: Option<i32>,
+    #[schema(example = 6100)]
+    pub p90_ms: Option<i32>,
+    #[schema(example = 9800)]
+    pub p99_ms: Option<i32>,
+}
+
+/// Nearest-rank percentile of sorted values
+fn nearest_rank(sorted: &[i64], percentile: usize) -> i64 {
+    let rank = (percentile * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Computes p50/p90/p99 settlement latency using the nearest-rank method.
+///
+/// Returns `None` for an empty slice; p90 and p99 are omitted below
+/// `MIN_SAMPLES_FOR_P90` and `MIN_SAMPLES_FOR_P99` samples respectively.
+pub fn compute_latency_percentiles(values: &mut [i64]) -> Option<LatencyPercentiles> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let len = values.len();
+    let percentile = |p: usize| nearest_rank(values, p) as i32;
+
+    Some(LatencyPercentiles {
+        sample_count: len as i64,
+        p50_ms: Some(percentile(50)),
+        p90_ms: (len >= MIN_SAMPLES_FOR_P90).then(|| percentile(90)),
+        p99_ms: (len >= MIN_SAMPLES_FOR_P99).then(|| percentile(99)),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -202,6 +288,28 @@ mod tests {
         assert!(key.contains("->"));
     }
 
+    #[test]
+    fn test_directional_corridor_keeps_order() {
+        let usdc_to_eurc = Corridor::directional(
+            "USDC".to_string(),
+            "issuer1".to_string(),
+            "EURC".to_string(),
+            "issuer2".to_string(),
+        );
+        let eurc_to_usdc = Corridor::directional(
+            "EURC".to_string(),
+            "issuer2".to_string(),
+            "USDC".to_string(),
+            "issuer1".to_string(),
+        );
+
+        assert_ne!(usdc_to_eurc, eurc_to_usdc);
+        assert_eq!(usdc_to_eurc.to_string_key(), "USDC:issuer1->EURC:issuer2");
+        assert_eq!(eurc_to_usdc.to_string_key(), "EURC:issuer2->USDC:issuer1");
+        assert_eq!(usdc_to_eurc.pair_key(), eurc_to_usdc.pair_key());
+        assert_eq!(usdc_to_eurc.pair_key(), "EURC:issuer2->USDC:issuer1");
+    }
+
     #[test]
     fn test_payment_record_get_corridor() {
         let payment = PaymentRecord {
@@ -284,4 +392,41 @@ mod tests {
         let mut values = vec![5000];
         assert_eq!(compute_median(&mut values), Some(5000));
     }
+
+    #[test]
+    fn test_latency_percentiles_full_sample() {
+        // 1..=200 seconds, shuffled
+        let mut values: Vec<i64> = (1..=200).rev().map(|s| s * 1000).collect();
+        let percentiles = compute_latency_percentiles(&mut values).unwrap();
+        assert_eq!(
+            percentiles,
+            LatencyPercentiles {
+                sample_count: 200,
+                p50_ms: Some(100_000),
+                p90_ms: Some(180_000),
+                p99_ms: Some(198_000),
+            }
+        );
+    }
+
+    #[test]
+    fn test_latency_percentiles_omit_high_percentiles_for_small_samples() {
+        let mut values = vec![5000, 1000, 3000];
+        let percentiles = compute_latency_percentiles(&mut values).unwrap();
+        assert_eq!(percentiles.sample_count, 3);
+        assert_eq!(percentiles.p50_ms, Some(3000));
+        assert_eq!(percentiles.p90_ms, None);
+        assert_eq!(percentiles.p99_ms, None);
+
+        let mut values: Vec<i64> = (1..=10).map(|s| s * 100).collect();
+        let percentiles = compute_latency_percentiles(&mut values).unwrap();
+        assert_eq!(percentiles.p50_ms, Some(500));
+        assert_eq!(percentiles.p90_ms, Some(900));
+        assert_eq!(percentiles.p99_ms, None);
+    }
+
+    #[test]
+    fn test_latency_percentiles_empty() {
+        assert_eq!(compute_latency_percentiles(&mut []), None);
+    }
 }
@@ -0,0 +1,131 @@
+//! Parsing and formatting of timestamps.
+//!
+//! Horizon reports times as RFC 3339 strings, ledger close times arrive as
+//! unix seconds, and SQLite's `CURRENT_TIMESTAMP` writes `YYYY-MM-DD HH:MM:SS`.
+//! Ingestion parses all of them here into `DateTime<Utc>` and stores them in
+//! one canonical form, `YYYY-MM-DDTHH:MM:SSZ`, so stored times compare and sort
+//! as text.
+
+use chrono::{DateTime, NaiveDateTime, SecondsFormat, Utc};
+
+use crate::observability::metrics as obs_metrics;
+
+/// Layouts without a zone, read as UTC
+const NAIVE_FORMATS: [&str; 2] = ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"];
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unrecognized timestamp '{0}'")]
+pub struct TimestampError(pub String);
+
+/// Parse a timestamp from Horizon, the RPC or the database into UTC.
+///
+/// Accepts RFC 3339 with any offset or fractional seconds, `+hhmm` offsets,
+/// the same layouts without a zone (taken as UTC, with `T` or a space between
+/// date and time) and unix seconds.
+pub fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, TimestampError> {
+    let value = value.trim();
+    let invalid = || TimestampError(value.to_string());
+
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    if let Ok(dt) = DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z") {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    for format in NAIVE_FORMATS {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(value, format) {
+            return Ok(dt.and_utc());
+        }
+    }
+    if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+        let seconds = value.parse::<i64>().map_err(|_| invalid())?;
+        return DateTime::from_timestamp(seconds, 0).ok_or_else(invalid);
+    }
+
+    Err(invalid())
+}
+
+/// Parse the timestamp of an ingested `source` record, `None` when it is
+/// unreadable. The record should then be skipped; the failure is logged and
+/// counted in `unparseable_timestamps_total`.
+pub fn parse_ingested_timestamp(value: &str, source: &str) -> Option<DateTime<Utc>> {
+    match parse_timestamp(value) {
+        Ok(dt) => Some(dt),
+        Err(e) => {
+            tracing::warn!("Skipping {} record: {}", source, e);
+            obs_metrics::record_unparseable_timestamp(source);
+            None
+        }
+    }
+}
+
+/// Canonical form of a timestamp, e.g. `2024-01-01T00:00:00Z`.
+///
+/// Horizon reports whole seconds, so any fraction is dropped.
+pub fn format_timestamp(dt: &DateTime<Utc>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Canonical form of `value`, or `None` when it cannot be parsed
+pub fn normalize_timestamp(value: &str) -> Option<String> {
+    parse_timestamp(value).ok().map(|dt| format_timestamp(&dt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANONICAL: &str = "2024-01-15T10:30:00Z";
+
+    #[test]
+    fn test_horizon_variants_normalize_to_canonical_form() {
+        for value in [
+            "2024-01-15T10:30:00Z",
+            " 2024-01-15T10:30:00Z ",
+            "2024-01-15T10:30:00.000Z",
+            "2024-01-15T10:30:00.4Z",
+            "2024-01-15t10:30:00z",
+            "2024-01-15T10:30:00+00:00",
+            "2024-01-15T12:30:00+02:00",
+            "2024-01-15T05:30:00-05:00",
+            "2024-01-15T12:30:00+0200",
+            "2024-01-15T10:30:00",
+            "2024-01-15 10:30:00",
+            "2024-01-15 10:30:00.123",
+            "1705314600",
+        ] {
+            assert_eq!(
+                normalize_timestamp(value).as_deref(),
+                Some(CANONICAL),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_keeps_fractional_seconds() {
+        let dt = parse_timestamp("2024-01-15T10:30:00.250Z").unwrap();
+        assert_eq!(dt.timestamp_subsec_millis(), 250);
+        assert_eq!(format_timestamp(&dt), CANONICAL);
+    }
+
+    #[test]
+    fn test_invalid_timestamps() {
+        for value in ["", "yesterday", "2024-13-01T00:00:00Z", "2024-01-15", "-5"] {
+            assert_eq!(
+                parse_timestamp(value),
+                Err(TimestampError(value.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_unreadable_ingested_timestamp_is_skipped() {
+        assert_eq!(
+            parse_ingested_timestamp("2024-01-15T10:30:00Z", "test")
+                .map(|dt| format_timestamp(&dt)),
+            Some(CANONICAL.to_string())
+        );
+        assert_eq!(parse_ingested_timestamp("yesterday", "test"), None);
+    }
+}
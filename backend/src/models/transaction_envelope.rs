@@ -0,0 +1,199 @@
+//! Validation of transaction envelopes submitted for multisig signing.
+//!
+//! Pending transactions carry a base64 `TransactionEnvelope`. Before one is
+//! stored it must decode, and its source account and signer count are read
+//! from the envelope rather than trusted from the request.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use std::collections::BTreeSet;
+use stellar_xdr::curr::{
+    FeeBumpTransactionInnerTx, Limits, MuxedAccount, Operation, PublicKey, ReadXdr,
+    TransactionEnvelope, Uint256,
+};
+
+/// Deepest nesting accepted in a submitted envelope
+const XDR_MAX_DEPTH: u32 = 500;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum EnvelopeError {
+    #[error("Transaction XDR is not valid base64")]
+    NotBase64,
+    #[error("Transaction XDR does not decode to an envelope: {0}")]
+    Malformed(String),
+    #[error("Transaction source account is {actual}, not {expected}")]
+    SourceMismatch { expected: String, actual: String },
+    #[error("Transaction has {minimum} signers but requires only {required} signatures")]
+    TooFewSignatures { required: i32, minimum: i32 },
+}
+
+/// What a decoded envelope says about who must sign it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvelopeSummary {
+    /// `G...` source account of the transaction; for a fee bump, of the
+    /// inner transaction
+    pub source_account: String,
+    /// Distinct accounts that must sign: the source, operation sources and
+    /// any fee-bump fee source
+    pub signers: Vec<String>,
+}
+
+impl EnvelopeSummary {
+    /// Fewest signatures the transaction can be valid with
+    pub fn required_signatures(&self) -> i32 {
+        self.signers.len() as i32
+    }
+}
+
+/// Decode a base64 `TransactionEnvelope`
+pub fn parse_envelope(xdr: &str) -> Result<EnvelopeSummary, EnvelopeError> {
+    let bytes = BASE64
+        .decode(xdr.trim())
+        .map_err(|_| EnvelopeError::NotBase64)?;
+    // The envelope comes from the client; bound nesting and let nothing
+    // claim more bytes than were sent
+    let limits = Limits {
+        depth: XDR_MAX_DEPTH,
+        len: bytes.len(),
+    };
+    let envelope = TransactionEnvelope::from_xdr(&bytes, limits)
+        .map_err(|e| EnvelopeError::Malformed(e.to_string()))?;
+
+    let mut signers = BTreeSet::new();
+    let source_account = match &envelope {
+        TransactionEnvelope::TxV0(e) => {
+            let source = account(&e.tx.source_account_ed25519);
+            signers.extend(operation_sources(&e.tx.operations));
+            source
+        }
+        TransactionEnvelope::Tx(e) => {
+            signers.extend(operation_sources(&e.tx.operations));
+            muxed_account(&e.tx.source_account)
+        }
+        TransactionEnvelope::TxFeeBump(e) => {
+            signers.insert(muxed_account(&e.tx.fee_source));
+            let FeeBumpTransactionInnerTx::Tx(inner) = &e.tx.inner_tx;
+            signers.extend(operation_sources(&inner.tx.operations));
+            muxed_account(&inner.tx.source_account)
+        }
+    };
+    signers.insert(source_account.clone());
+
+    Ok(EnvelopeSummary {
+        source_account,
+        signers: signers.into_iter().collect(),
+    })
+}
+
+/// Check `xdr` is an envelope from `source_account` that `required_signatures`
+/// signatures can authorize
+pub fn validate_envelope(
+    xdr: &str,
+    source_account: &str,
+    required_signatures: i32,
+) -> Result<EnvelopeSummary, EnvelopeError> {
+    let summary = parse_envelope(xdr)?;
+    if summary.source_account != source_account.trim() {
+        return Err(EnvelopeError::SourceMismatch {
+            expected: source_account.trim().to_string(),
+            actual: summary.source_account,
+        });
+    }
+    if required_signatures < summary.required_signatures() {
+        return Err(EnvelopeError::TooFewSignatures {
+            required: required_signatures,
+            minimum: summary.required_signatures(),
+        });
+    }
+    Ok(summary)
+}
+
+fn account(key: &Uint256) -> String {
+    PublicKey::PublicKeyTypeEd25519(key.clone()).to_string()
+}
+
+/// Signing account behind a possibly muxed `M...` account
+fn muxed_account(muxed: &MuxedAccount) -> String {
+    match muxed {
+        MuxedAccount::Ed25519(key) => account(key),
+        MuxedAccount::MuxedEd25519(muxed) => account(&muxed.ed25519),
+    }
+}
+
+fn operation_sources(operations: &[Operation]) -> impl Iterator<Item = String> + '_ {
+    operations
+        .iter()
+        .filter_map(|op| op.source_account.as_ref())
+        .map(muxed_account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::{
+        BumpSequenceOp, Memo, OperationBody, Preconditions, SequenceNumber, Transaction,
+        TransactionExt, TransactionV1Envelope, VecM, WriteXdr,
+    };
+
+    fn envelope(source: u8, operation_sources: &[Option<u8>]) -> String {
+        let operations: Vec<Operation> = operation_sources
+            .iter()
+            .map(|source| Operation {
+                source_account: source.map(|s| MuxedAccount::Ed25519(Uint256([s; 32]))),
+                body: OperationBody::BumpSequence(BumpSequenceOp {
+                    bump_to: SequenceNumber(2),
+                }),
+            })
+            .collect();
+        let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx: Transaction {
+                source_account: MuxedAccount::Ed25519(Uint256([source; 32])),
+                fee: 200,
+                seq_num: SequenceNumber(1),
+                cond: Preconditions::None,
+                memo: Memo::None,
+                operations: operations.try_into().unwrap(),
+                ext: TransactionExt::V0,
+            },
+            signatures: VecM::default(),
+        });
+        BASE64.encode(envelope.to_xdr(Limits::none()).unwrap())
+    }
+
+    #[test]
+    fn test_valid_envelope() {
+        let source = account(&Uint256([1; 32]));
+        let summary = parse_envelope(&envelope(1, &[None, Some(2), Some(1), Some(2)])).unwrap();
+        assert_eq!(summary.source_account, source);
+        assert_eq!(summary.required_signatures(), 2);
+        assert!(summary.signers.contains(&account(&Uint256([2; 32]))));
+
+        assert!(validate_envelope(&envelope(1, &[None]), &source, 1).is_ok());
+        assert_eq!(
+            validate_envelope(&envelope(1, &[Some(2)]), &source, 1),
+            Err(EnvelopeError::TooFewSignatures {
+                required: 1,
+                minimum: 2
+            })
+        );
+        assert!(matches!(
+            validate_envelope(&envelope(3, &[None]), &source, 1),
+            Err(EnvelopeError::SourceMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_garbage_is_rejected() {
+        assert_eq!(
+            parse_envelope("not a transaction!"),
+            Err(EnvelopeError::NotBase64)
+        );
+        assert!(matches!(
+            parse_envelope(&BASE64.encode(b"garbage")),
+            Err(EnvelopeError::Malformed(_))
+        ));
+        assert!(matches!(
+            parse_envelope(""),
+            Err(EnvelopeError::Malformed(_))
+        ));
+    }
+}
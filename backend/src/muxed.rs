@@ -4,12 +4,15 @@
 //! sub-accounts via a 64-bit muxed ID. M-addresses are 69 characters and start with 'M'.
 //! See SEP-0023 and [Stellar Muxed Accounts FAQ](https://stellar.org/blog/developers/muxed-accounts-faq).
 
-use data_encoding::BASE32;
+use data_encoding::BASE32_NOPAD;
 use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 
-/// Stellar strkey version bytes
-const VERSION_ACCOUNT_ID: u8 = 6; // G-address
-const VERSION_MUXED_ACCOUNT: u8 = 12; // M-address
+use crate::services::pair_registry::AssetPattern;
+
+/// Stellar strkey version bytes; the version number sits in the top five bits
+const VERSION_ACCOUNT_ID: u8 = 6 << 3; // G-address
+const VERSION_MUXED_ACCOUNT: u8 = 12 << 3; // M-address
 
 /// Length of a Stellar M-address (MUXED_ACCOUNT strkey)
 pub const MUXED_ADDRESS_LEN: usize = 69;
@@ -66,6 +69,40 @@ pub fn is_stellar_account_address(addr: &str) -> bool {
     is_muxed_address(addr)
 }
 
+/// Decode a strkey with the given version byte, returning its payload.
+/// Returns None if the encoding, version or little-endian CRC-16 checksum is wrong.
+fn decode_strkey(addr: &str, version: u8) -> Option<Vec<u8>> {
+    let decoded = BASE32_NOPAD.decode(addr.as_bytes()).ok()?;
+    if decoded.len() < 3 || decoded[0] != version {
+        return None;
+    }
+    let (body, checksum) = decoded.split_at(decoded.len() - 2);
+    if crc16(body) != u16::from_le_bytes([checksum[0], checksum[1]]) {
+        return None;
+    }
+    Some(body[1..].to_vec())
+}
+
+fn encode_strkey(version: u8, payload: &[u8]) -> String {
+    let mut data = Vec::with_capacity(payload.len() + 3);
+    data.push(version);
+    data.extend_from_slice(payload);
+    let checksum = crc16(&data);
+    data.extend_from_slice(&checksum.to_le_bytes());
+    BASE32_NOPAD.encode(&data)
+}
+
+/// Returns true if the given string is a G-address with a valid version byte and checksum.
+pub fn is_valid_account_id(addr: &str) -> bool {
+    addr.len() == G_ADDRESS_LEN
+        && decode_strkey(addr, VERSION_ACCOUNT_ID).is_some_and(|key| key.len() == 32)
+}
+
+/// Returns true if the given string is a checksummed G-address or M-address.
+pub fn is_valid_account_strkey(addr: &str) -> bool {
+    is_valid_account_id(addr) || parse_muxed_address(addr).is_some()
+}
+
 /// Parse an M-address into base account (G) and muxed ID.
 /// Returns None if the input is not a valid M-address or decoding fails.
 pub fn parse_muxed_address(addr: &str) -> Option<MuxedAccountInfo> {
@@ -73,31 +110,13 @@ pub fn parse_muxed_address(addr: &str) -> Option<MuxedAccountInfo> {
         return None;
     }
 
-    let decoded = BASE32.decode(addr.as_bytes()).ok()?;
-    // Muxed: version(1) + account_id(32) + muxed_id(8) + checksum(2) = 43 bytes
-    if decoded.len() != 43 {
-        return None;
-    }
-    if decoded[0] != VERSION_MUXED_ACCOUNT {
-        return None;
-    }
-    let checksum = u16::from_be_bytes([decoded[41], decoded[42]]);
-    let payload = &decoded[0..41];
-    if crc16(payload) != checksum {
+    // Muxed payload: account_id(32) + muxed_id(8)
+    let payload = decode_strkey(addr, VERSION_MUXED_ACCOUNT)?;
+    if payload.len() != 40 {
         return None;
     }
-
-    let account_id: &[u8; 32] = decoded[1..33].try_into().ok()?;
-    let muxed_id = u64::from_be_bytes(decoded[33..41].try_into().ok()?);
-
-    // Encode 32-byte account ID as G-address
-    let mut g_payload = [0u8; 35];
-    g_payload[0] = VERSION_ACCOUNT_ID;
-    g_payload[1..33].copy_from_slice(account_id);
-    let c = crc16(&g_payload);
-    g_payload[33] = (c >> 8) as u8;
-    g_payload[34] = (c & 0xff) as u8;
-    let base_account = BASE32.encode(&g_payload);
+    let muxed_id = u64::from_be_bytes(payload[32..40].try_into().ok()?);
+    let base_account = encode_strkey(VERSION_ACCOUNT_ID, &payload[..32]);
 
     Some(MuxedAccountInfo {
         muxed_address: addr.to_string(),
@@ -123,6 +142,71 @@ pub fn normalize_account_input(addr: &str) -> Option<&str> {
     None
 }
 
+/// Per-asset dust thresholds for muxed analytics, from
+/// `MUXED_MIN_PAYMENT_AMOUNTS`.
+///
+/// Amounts are in each asset's own units, so one threshold cannot fit every
+/// asset. Entries are comma-separated `ASSET=AMOUNT`, where the asset is
+/// `XLM`, a code, or `CODE:ISSUER` (e.g. `XLM=1,USDC=0.5`); a bare code
+/// applies to that code from any issuer, and an issuer-specific entry wins
+/// over it. Assets without an entry count every payment.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DustThresholds {
+    entries: Vec<(AssetPattern, f64)>,
+}
+
+impl DustThresholds {
+    /// Thresholds with the issuer-specific entries first, which is the order
+    /// they must be matched in
+    pub fn entries(&self) -> impl Iterator<Item = DustThreshold<'_>> {
+        let specific = self.entries.iter().filter(|(a, _)| a.issuer.is_some());
+        let by_code = self.entries.iter().filter(|(a, _)| a.issuer.is_none());
+        specific
+            .chain(by_code)
+            .map(|(asset, min_amount)| DustThreshold {
+                native: *asset == AssetPattern::native(),
+                code: &asset.code,
+                issuer: asset.issuer.as_deref(),
+                min_amount: *min_amount,
+            })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Smallest counted payment of one asset, or of one code from any issuer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DustThreshold<'a> {
+    pub native: bool,
+    pub code: &'a str,
+    pub issuer: Option<&'a str>,
+    pub min_amount: f64,
+}
+
+impl FromStr for DustThresholds {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries = Vec::new();
+        for entry in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let threshold = entry.split_once('=').and_then(|(asset, amount)| {
+                let amount = amount.trim().parse::<f64>().ok();
+                Some((
+                    AssetPattern::parse(asset)?,
+                    amount.filter(|a| a.is_finite() && *a >= 0.0)?,
+                ))
+            });
+            match threshold {
+                Some(threshold) => entries.push(threshold),
+                None => return Err(format!("invalid dust threshold '{}'", entry)),
+            }
+        }
+        Ok(Self { entries })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,14 +240,58 @@ mod tests {
             parse_muxed_address("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ")
                 .is_none()
         );
-        // Valid M-address format: parse may succeed (if checksum/version match) or None
-        let m = "MAAAAAAAAAAAAAB7BQ2L7E5NBWMXDUCMZSIPOBKRDSBYVLMXGSSKF6YNPIB7Y77ITLVL6";
-        let info = parse_muxed_address(m);
-        if let Some(ref i) = info {
-            assert!(i.muxed_address == m);
-            assert!(i.base_account.as_ref().map_or(true, |g| g.starts_with('G')));
-        }
+        let m = "MA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJUAAAAAAAAAAE2JUG6";
+        let info = parse_muxed_address(m).unwrap();
+        assert_eq!(info.muxed_address, m);
+        assert_eq!(
+            info.base_account.as_deref(),
+            Some("GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ")
+        );
+        assert_eq!(info.muxed_id, Some(1234));
+        // Corrupted checksum
+        assert!(parse_muxed_address(&m.replace("JUG6", "JUG4")).is_none());
         // Too short M string
         assert!(parse_muxed_address("M").is_none());
     }
+
+    #[test]
+    fn test_is_valid_account_strkey() {
+        assert!(is_valid_account_id(
+            "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ"
+        ));
+        // Last character changed, so the checksum no longer matches
+        assert!(!is_valid_account_id(
+            "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGY"
+        ));
+        assert!(!is_valid_account_id(&"G".repeat(G_ADDRESS_LEN)));
+        assert!(is_valid_account_strkey(
+            "MAAAAAAAAAAAAAB7BQ2L7E5NBWMXDUCMZSIPOBKRDSBYVLMXGSSKF6YNPIB7Y77ITLVL6"
+        ));
+        assert!(!is_valid_account_strkey("invalid"));
+    }
+
+    #[test]
+    fn test_dust_thresholds_match_issuer_entries_first() {
+        let issuer = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        let thresholds: DustThresholds = format!("USDC=0.1, XLM=1, USDC:{}=2", issuer)
+            .parse()
+            .unwrap();
+        let entries: Vec<_> = thresholds
+            .entries()
+            .map(|t| (t.native, t.code, t.issuer, t.min_amount))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (false, "USDC", Some(issuer), 2.0),
+                (false, "USDC", None, 0.1),
+                (true, "XLM", None, 1.0),
+            ]
+        );
+
+        assert!("".parse::<DustThresholds>().unwrap().is_empty());
+        for invalid in ["XLM", "XLM=-1", "XLM=NaN", "=1", "USDC:=1"] {
+            assert!(invalid.parse::<DustThresholds>().is_err(), "{}", invalid);
+        }
+    }
 }
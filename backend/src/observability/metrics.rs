@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
 use std::sync::{Mutex, OnceLock};
 use std::time::Instant;
 
@@ -26,9 +26,17 @@ struct MetricsState {
     errors_total: Mutex<HashMap<String, u64>>,
     db_query_duration_seconds: Mutex<HashMap<String, DurationSeries>>,
     background_jobs_total: Mutex<HashMap<String, u64>>,
+    background_task_restarts_total: Mutex<HashMap<String, u64>>,
+    requests_shed_total: Mutex<HashMap<String, u64>>,
+    domain_events_total: Mutex<HashMap<String, u64>>,
+    domain_events_sampled_out_total: Mutex<HashMap<String, u64>>,
+    unparseable_timestamps_total: Mutex<HashMap<String, u64>>,
+    buffered_metrics_dropped_total: AtomicU64,
     active_connections: AtomicI64,
     corridors_tracked: AtomicI64,
     http_in_flight_requests: AtomicI64,
+    ledger_ingestion_lag: AtomicI64,
+    ledger_ingestion_batch_size: AtomicI64,
 }
 
 static METRICS: OnceLock<MetricsState> = OnceLock::new();
@@ -205,6 +213,71 @@ pub async fn metrics_handler() -> Response {
         ));
     }
 
+    out.push_str("# HELP background_task_restarts_total Background task restarts after a panic\n");
+    out.push_str("# TYPE background_task_restarts_total counter\n");
+    for (key, value) in snapshot_counters(&metrics.background_task_restarts_total) {
+        out.push_str(&format!(
+            "background_task_restarts_total{} {}\n",
+            key_to_prom_labels(&key),
+            value
+        ));
+    }
+
+    out.push_str("# HELP requests_shed_total Requests rejected by concurrency limits\n");
+    out.push_str("# TYPE requests_shed_total counter\n");
+    for (key, value) in snapshot_counters(&metrics.requests_shed_total) {
+        out.push_str(&format!(
+            "requests_shed_total{} {}\n",
+            key_to_prom_labels(&key),
+            value
+        ));
+    }
+
+    out.push_str("# HELP domain_events_total Domain events published on the event bus\n");
+    out.push_str("# TYPE domain_events_total counter\n");
+    for (key, value) in snapshot_counters(&metrics.domain_events_total) {
+        out.push_str(&format!(
+            "domain_events_total{} {}\n",
+            key_to_prom_labels(&key),
+            value
+        ));
+    }
+
+    out.push_str(
+        "# HELP domain_events_sampled_out_total Domain events dropped by subscriber sampling\n",
+    );
+    out.push_str("# TYPE domain_events_sampled_out_total counter\n");
+    for (key, value) in snapshot_counters(&metrics.domain_events_sampled_out_total) {
+        out.push_str(&format!(
+            "domain_events_sampled_out_total{} {}\n",
+            key_to_prom_labels(&key),
+            value
+        ));
+    }
+
+    out.push_str(
+        "# HELP unparseable_timestamps_total Ingested records skipped for an unreadable timestamp\n",
+    );
+    out.push_str("# TYPE unparseable_timestamps_total counter\n");
+    for (key, value) in snapshot_counters(&metrics.unparseable_timestamps_total) {
+        out.push_str(&format!(
+            "unparseable_timestamps_total{} {}\n",
+            key_to_prom_labels(&key),
+            value
+        ));
+    }
+
+    out.push_str(
+        "# HELP buffered_metrics_dropped_total Metric rows dropped from a full write buffer\n",
+    );
+    out.push_str("# TYPE buffered_metrics_dropped_total counter\n");
+    out.push_str(&format!(
+        "buffered_metrics_dropped_total {}\n",
+        metrics
+            .buffered_metrics_dropped_total
+            .load(Ordering::Relaxed)
+    ));
+
     out.push_str("# HELP active_connections Active websocket connections\n");
     out.push_str("# TYPE active_connections gauge\n");
     out.push_str(&format!(
@@ -219,6 +292,20 @@ pub async fn metrics_handler() -> Response {
         metrics.corridors_tracked.load(Ordering::Relaxed)
     ));
 
+    out.push_str("# HELP ledger_ingestion_lag Ledgers behind the network head\n");
+    out.push_str("# TYPE ledger_ingestion_lag gauge\n");
+    out.push_str(&format!(
+        "ledger_ingestion_lag {}\n",
+        metrics.ledger_ingestion_lag.load(Ordering::Relaxed)
+    ));
+
+    out.push_str("# HELP ledger_ingestion_batch_size Current ledger ingestion batch size\n");
+    out.push_str("# TYPE ledger_ingestion_batch_size gauge\n");
+    out.push_str(&format!(
+        "ledger_ingestion_batch_size {}\n",
+        metrics.ledger_ingestion_batch_size.load(Ordering::Relaxed)
+    ));
+
     out.push_str("# HELP http_in_flight_requests In-flight HTTP requests\n");
     out.push_str("# TYPE http_in_flight_requests gauge\n");
     out.push_str(&format!(
@@ -309,10 +396,64 @@ pub fn record_background_job(job: &str, status: &str) {
     );
 }
 
+pub fn record_task_restart(task: &str) {
+    inc_counter(
+        &state().background_task_restarts_total,
+        make_key(&[("task", task)]),
+    );
+}
+
+pub fn record_request_shed(group: &str) {
+    inc_counter(&state().requests_shed_total, make_key(&[("group", group)]));
+}
+
+pub fn record_domain_event(event_type: &str) {
+    inc_counter(
+        &state().domain_events_total,
+        make_key(&[("event_type", event_type)]),
+    );
+}
+
+pub fn record_event_sampled_out(subscriber: &str, event_type: &str) {
+    inc_counter(
+        &state().domain_events_sampled_out_total,
+        make_key(&[("subscriber", subscriber), ("event_type", event_type)]),
+    );
+}
+
+pub fn record_unparseable_timestamp(source: &str) {
+    inc_counter(
+        &state().unparseable_timestamps_total,
+        make_key(&[("source", source)]),
+    );
+}
+
+pub fn record_buffered_metrics_dropped(count: u64) {
+    state()
+        .buffered_metrics_dropped_total
+        .fetch_add(count, Ordering::Relaxed);
+}
+
+pub fn buffered_metrics_dropped() -> u64 {
+    state()
+        .buffered_metrics_dropped_total
+        .load(Ordering::Relaxed)
+}
+
 pub fn set_corridors_tracked(count: i64) {
     state().corridors_tracked.store(count, Ordering::Relaxed);
 }
 
+pub fn set_ledger_ingestion_lag(lag: i64) {
+    state().ledger_ingestion_lag.store(lag, Ordering::Relaxed);
+}
+
+pub fn set_ledger_ingestion_batch_size(size: i64) {
+    state()
+        .ledger_ingestion_batch_size
+        .store(size, Ordering::Relaxed);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
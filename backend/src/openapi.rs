@@ -28,6 +28,8 @@ use utoipa::OpenApi;
         crate::api::price_feed::convert_to_usd,
         crate::api::price_feed::get_cache_stats,
         crate::api::cost_calculator::estimate_costs,
+        crate::api::cost_calculator::estimate_path,
+        crate::api::overview::get_overview,
     ),
     components(
         schemas(
@@ -38,6 +40,7 @@ use utoipa::OpenApi;
             crate::api::corridors_cached::SuccessRateDataPoint,
             crate::api::corridors_cached::LatencyDataPoint,
             crate::api::corridors_cached::LiquidityDataPoint,
+            crate::models::corridor::LatencyPercentiles,
             crate::api::price_feed::PriceResponse,
             crate::api::price_feed::PricesResponse,
             crate::api::price_feed::ConvertResponse,
@@ -47,7 +50,16 @@ use utoipa::OpenApi;
             crate::api::cost_calculator::RouteCostBreakdown,
             crate::api::cost_calculator::RouteEstimate,
             crate::api::cost_calculator::CostCalculationResponse,
+            crate::api::cost_calculator::PathEstimateRequest,
+            crate::services::path_finder::PathHop,
+            crate::services::path_finder::PathEstimate,
             crate::api::cost_calculator::ErrorResponse,
+            crate::api::overview::OverviewResponse,
+            crate::api::overview::OverviewAnchor,
+            crate::api::overview::OverviewCorridor,
+            crate::api::overview::OverviewNetworkHealth,
+            crate::api::overview::OverviewSnapshot,
+            crate::api::overview::OverviewCounts,
         )
     ),
     tags(
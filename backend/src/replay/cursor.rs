@@ -0,0 +1,242 @@
+//! Reorg-Safe Event Cursor
+//!
+//! Tracks how far contract events have been indexed. The cursor remembers the
+//! last `depth` indexed events with their ledger sequence; when a fetch comes
+//! back with a ledger lower than the cursor has already indexed, the chain has
+//! reorganized, and the cursor rewinds to the oldest ledger it still
+//! remembers. Events are deduplicated on their id, so re-fetching from that
+//! checkpoint indexes only what was missed.
+
+use std::collections::{HashSet, VecDeque};
+use tracing::warn;
+
+use super::ContractEvent;
+
+/// Default number of indexed events the cursor remembers
+pub const DEFAULT_REORG_DEPTH: usize = 1000;
+
+/// Result of applying a fetched batch to the cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CursorUpdate {
+    /// Events not indexed before, in fetch order
+    Indexed(Vec<ContractEvent>),
+    /// The batch went backwards; nothing from it was indexed and fetching
+    /// should restart at `resume_from`
+    Reorg { resume_from: u64 },
+}
+
+#[derive(Debug, Clone)]
+pub struct EventCursor {
+    depth: usize,
+    /// Ledger indexing started at, the checkpoint before anything is evicted
+    start_ledger: u64,
+    /// Ledger to fetch from while nothing has been indexed since the start or
+    /// the last rewind
+    resume_ledger: u64,
+    /// Highest ledger fetched since then
+    last_ledger: Option<u64>,
+    /// Highest ledger whose events have been evicted from `recent`
+    evicted_through: Option<u64>,
+    /// Last indexed events as `(id, ledger)`, oldest first
+    recent: VecDeque<(String, u64)>,
+    seen: HashSet<String>,
+}
+
+impl EventCursor {
+    pub fn new(start_ledger: u64) -> Self {
+        Self::with_depth(start_ledger, DEFAULT_REORG_DEPTH)
+    }
+
+    pub fn with_depth(start_ledger: u64, depth: usize) -> Self {
+        Self {
+            depth: depth.max(1),
+            start_ledger,
+            resume_ledger: start_ledger,
+            last_ledger: None,
+            evicted_through: None,
+            recent: VecDeque::new(),
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Highest ledger indexed so far, `None` right after a rewind
+    pub fn last_ledger(&self) -> Option<u64> {
+        self.last_ledger
+    }
+
+    /// Ledger the next fetch should start at. The last indexed ledger is
+    /// fetched again, since it may hold events past the previous page.
+    pub fn next_ledger(&self) -> u64 {
+        self.last_ledger.unwrap_or(self.resume_ledger)
+    }
+
+    /// Oldest ledger whose events are all still remembered; re-indexing from
+    /// here cannot duplicate anything
+    pub fn safe_checkpoint(&self) -> u64 {
+        match self.evicted_through {
+            Some(ledger) => ledger + 1,
+            None => self.start_ledger,
+        }
+    }
+
+    /// Apply a fetched batch. `latest_ledger` is the chain head the RPC
+    /// reported with it.
+    pub fn apply(&mut self, latest_ledger: u64, events: Vec<ContractEvent>) -> CursorUpdate {
+        if let Some(last) = self.last_ledger {
+            let lowest = events.iter().map(|e| e.ledger_sequence).min();
+            if latest_ledger < last || lowest.is_some_and(|ledger| ledger < last) {
+                return CursorUpdate::Reorg {
+                    resume_from: self.rewind(latest_ledger.min(lowest.unwrap_or(u64::MAX))),
+                };
+            }
+        }
+
+        let mut indexed = Vec::new();
+        for event in events {
+            self.last_ledger = Some(
+                self.last_ledger
+                    .map_or(event.ledger_sequence, |l| l.max(event.ledger_sequence)),
+            );
+            if !self.seen.insert(event.id.clone()) {
+                continue;
+            }
+            self.recent
+                .push_back((event.id.clone(), event.ledger_sequence));
+            indexed.push(event);
+        }
+        self.evict();
+        CursorUpdate::Indexed(indexed)
+    }
+
+    /// Move back to the safe checkpoint after a fetch reached `ledger`, below
+    /// what was already indexed. Returns the ledger to resume from.
+    fn rewind(&mut self, ledger: u64) -> u64 {
+        let resume_from = self.safe_checkpoint();
+        warn!(
+            "Event fetch returned ledger {} below indexed ledger {:?}, re-indexing from {}",
+            ledger, self.last_ledger, resume_from
+        );
+        // Remembered ids stay, so events that survived the reorg are not
+        // indexed twice
+        self.last_ledger = None;
+        self.resume_ledger = resume_from;
+        resume_from
+    }
+
+    /// Forget events beyond `depth`, a whole ledger at a time so the safe
+    /// checkpoint never splits a ledger
+    fn evict(&mut self) {
+        while self.recent.len() > self.depth {
+            let Some((_, ledger)) = self.recent.front().cloned() else {
+                break;
+            };
+            while self.recent.front().is_some_and(|(_, l)| *l <= ledger) {
+                if let Some((id, _)) = self.recent.pop_front() {
+                    self.seen.remove(&id);
+                }
+            }
+            self.evicted_through = Some(ledger);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn event(id: &str, ledger: u64) -> ContractEvent {
+        ContractEvent {
+            id: id.to_string(),
+            ledger_sequence: ledger,
+            transaction_hash: format!("tx-{}", id),
+            contract_id: "contract-1".to_string(),
+            event_type: "snapshot_submitted".to_string(),
+            data: serde_json::json!({}),
+            timestamp: Utc::now(),
+            network: "testnet".to_string(),
+        }
+    }
+
+    fn ids(update: CursorUpdate) -> Vec<String> {
+        match update {
+            CursorUpdate::Indexed(events) => events.into_iter().map(|e| e.id).collect(),
+            CursorUpdate::Reorg { resume_from } => panic!("unexpected reorg to {}", resume_from),
+        }
+    }
+
+    /// Events of `chain` from `from` on, as a fetch starting there returns them
+    fn fetch(chain: &[ContractEvent], from: u64) -> Vec<ContractEvent> {
+        chain
+            .iter()
+            .filter(|e| e.ledger_sequence >= from)
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_overlapping_fetches_are_deduplicated() {
+        let mut cursor = EventCursor::new(100);
+        assert_eq!(
+            ids(cursor.apply(101, vec![event("a", 100), event("b", 101)])),
+            ["a", "b"]
+        );
+        assert_eq!(cursor.next_ledger(), 101);
+
+        // The next page starts at the last indexed ledger again
+        assert_eq!(
+            ids(cursor.apply(102, vec![event("b", 101), event("c", 102)])),
+            ["c"]
+        );
+        assert_eq!(cursor.last_ledger(), Some(102));
+    }
+
+    #[test]
+    fn test_reorg_reindexes_without_duplicates_or_gaps() {
+        let mut cursor = EventCursor::with_depth(100, 4);
+        let mut indexed = Vec::new();
+
+        let before: Vec<_> = (100..=105)
+            .map(|ledger| event(&format!("e{}", ledger), ledger))
+            .collect();
+        indexed.extend(ids(cursor.apply(105, fetch(&before, 100))));
+        assert_eq!(cursor.last_ledger(), Some(105));
+        // e100 and e101 have been forgotten
+        assert_eq!(cursor.safe_checkpoint(), 102);
+
+        // Ledgers 104 and 105 are replaced; the node now reports ledger 103
+        // as its head and returns the new 104 on the next fetch
+        let mut after = before[..4].to_vec();
+        after.push(event("e104-reorg", 104));
+        let update = cursor.apply(103, fetch(&after[..4], cursor.next_ledger()));
+        assert_eq!(update, CursorUpdate::Reorg { resume_from: 102 });
+        assert_eq!(cursor.next_ledger(), 102);
+
+        indexed.extend(ids(cursor.apply(104, fetch(&after, cursor.next_ledger()))));
+        assert_eq!(cursor.last_ledger(), Some(104));
+        after.push(event("e106", 106));
+        indexed.extend(ids(cursor.apply(106, fetch(&after, cursor.next_ledger()))));
+
+        let unique: HashSet<_> = indexed.iter().collect();
+        assert_eq!(unique.len(), indexed.len(), "duplicates in {:?}", indexed);
+        for e in &after {
+            assert!(indexed.contains(&e.id), "{} was missed", e.id);
+        }
+        assert_eq!(cursor.last_ledger(), Some(106));
+    }
+
+    #[test]
+    fn test_reorg_before_anything_is_evicted_restarts_at_start() {
+        let mut cursor = EventCursor::new(50);
+        ids(cursor.apply(52, vec![event("a", 50), event("b", 52)]));
+        assert_eq!(
+            cursor.apply(52, vec![event("a", 50)]),
+            CursorUpdate::Reorg { resume_from: 50 }
+        );
+        assert_eq!(cursor.next_ledger(), 50);
+        assert_eq!(
+            ids(cursor.apply(52, vec![event("a", 50)])),
+            Vec::<String>::new()
+        );
+    }
+}
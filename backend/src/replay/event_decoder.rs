@@ -0,0 +1,414 @@
+//! Event Decoder
+//!
+//! Decodes Soroban contract events as returned by RPC `getEvents`, whose
+//! topics and data are base64 `ScVal` XDR. Events from the snapshot and
+//! governance contracts become typed structs, keyed by their first topic
+//! symbol; their data may be a `#[contracttype]` struct (a symbol-keyed map)
+//! or a tuple. Anything else, including events whose data does not have the
+//! expected shape, is kept raw so nothing is lost.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde::{Deserialize, Serialize};
+use stellar_xdr::curr::{Limits, ReadXdr, ScVal};
+
+use super::ContractEvent;
+use crate::models::timestamp::{parse_timestamp, TimestampError};
+
+/// Deepest nesting accepted in an event value
+const XDR_MAX_DEPTH: u32 = 500;
+
+/// Contract event as returned by RPC `getEvents`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcContractEvent {
+    pub id: String,
+    pub ledger: u64,
+    pub ledger_closed_at: String,
+    pub contract_id: String,
+    pub tx_hash: String,
+    /// Base64 `ScVal` topics
+    pub topic: Vec<String>,
+    /// Base64 `ScVal` data
+    pub value: String,
+}
+
+impl RpcContractEvent {
+    pub fn decode(&self) -> DecodedEvent {
+        decode_event(&self.topic, &self.value)
+    }
+
+    /// Stored form of the event, with the decoded fields as its data
+    pub fn into_contract_event(self, network: &str) -> Result<ContractEvent, TimestampError> {
+        let decoded = self.decode();
+        Ok(ContractEvent {
+            timestamp: parse_timestamp(&self.ledger_closed_at)?,
+            id: self.id,
+            ledger_sequence: self.ledger,
+            transaction_hash: self.tx_hash,
+            contract_id: self.contract_id,
+            event_type: decoded.event_type(),
+            data: decoded.data(),
+            network: network.to_string(),
+        })
+    }
+}
+
+/// Snapshot hash recorded on-chain, `SNAP_SUB`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotSubmitted {
+    /// Hex-encoded SHA-256 hash
+    pub hash: String,
+    pub epoch: u64,
+    pub timestamp: u64,
+    /// Only in the struct form of the event
+    pub submitter: Option<String>,
+}
+
+/// Governance proposal opened, `PROP_CRT`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: String,
+    pub target_contract: String,
+    pub voting_ends_at: u64,
+}
+
+/// Vote on a governance proposal, `VOTE_CST`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoteCast {
+    pub proposal_id: u64,
+    pub voter: String,
+    pub choice: u32,
+}
+
+/// Governance proposal closed, `PROP_FIN`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposalFinalized {
+    pub proposal_id: u64,
+    pub status: u32,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub total_voters: u64,
+}
+
+/// Governance parameter changed, `CFG_CHG`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigChanged {
+    pub parameter: String,
+    pub old_value: u64,
+    pub new_value: u64,
+}
+
+/// Event that could not be decoded, as received
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawEvent {
+    /// Base64 `ScVal` topics
+    pub topics: Vec<String>,
+    /// Base64 `ScVal` data
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedEvent {
+    SnapshotSubmitted(SnapshotSubmitted),
+    ProposalCreated(ProposalCreated),
+    VoteCast(VoteCast),
+    ProposalFinalized(ProposalFinalized),
+    ConfigChanged(ConfigChanged),
+    Unknown(RawEvent),
+}
+
+impl DecodedEvent {
+    /// Stored event type, e.g. `snapshot_submitted`; raw events are
+    /// `unknown:<first topic symbol>`
+    pub fn event_type(&self) -> String {
+        match self {
+            Self::SnapshotSubmitted(_) => "snapshot_submitted".to_string(),
+            Self::ProposalCreated(_) => "proposal_created".to_string(),
+            Self::VoteCast(_) => "vote_cast".to_string(),
+            Self::ProposalFinalized(_) => "proposal_finalized".to_string(),
+            Self::ConfigChanged(_) => "config_changed".to_string(),
+            Self::Unknown(raw) => match raw.topics.first().and_then(|t| decode_scval(t)) {
+                Some(ScVal::Symbol(symbol)) => {
+                    format!("unknown:{}", symbol.0.to_utf8_string_lossy())
+                }
+                _ => "unknown".to_string(),
+            },
+        }
+    }
+
+    /// Event fields as stored JSON
+    pub fn data(&self) -> serde_json::Value {
+        let data = match self {
+            Self::SnapshotSubmitted(event) => serde_json::to_value(event),
+            Self::ProposalCreated(event) => serde_json::to_value(event),
+            Self::VoteCast(event) => serde_json::to_value(event),
+            Self::ProposalFinalized(event) => serde_json::to_value(event),
+            Self::ConfigChanged(event) => serde_json::to_value(event),
+            Self::Unknown(raw) => serde_json::to_value(raw),
+        };
+        data.unwrap_or_default()
+    }
+}
+
+/// Decode an event from its base64 topics and data
+pub fn decode_event(topics: &[String], value: &str) -> DecodedEvent {
+    decode_known(topics, value).unwrap_or_else(|| {
+        DecodedEvent::Unknown(RawEvent {
+            topics: topics.to_vec(),
+            value: value.to_string(),
+        })
+    })
+}
+
+fn decode_known(topics: &[String], value: &str) -> Option<DecodedEvent> {
+    let ScVal::Symbol(topic) = decode_scval(topics.first()?)? else {
+        return None;
+    };
+    let data = decode_scval(value)?;
+    // Tuple data lists the fields in the order given
+    let fields = |order: &'static [&'static str]| Fields { data: &data, order };
+
+    let event = match topic.0.to_utf8_string_lossy().as_str() {
+        "SNAP_SUB" => {
+            let f = fields(&["hash", "epoch", "timestamp", "submitter"]);
+            DecodedEvent::SnapshotSubmitted(SnapshotSubmitted {
+                hash: f.hex("hash")?,
+                epoch: f.u64("epoch")?,
+                timestamp: f.u64("timestamp")?,
+                submitter: f.address("submitter"),
+            })
+        }
+        "PROP_CRT" => {
+            let f = fields(&[
+                "proposal_id",
+                "proposer",
+                "target_contract",
+                "voting_ends_at",
+            ]);
+            DecodedEvent::ProposalCreated(ProposalCreated {
+                proposal_id: f.u64("proposal_id")?,
+                proposer: f.address("proposer")?,
+                target_contract: f.address("target_contract")?,
+                voting_ends_at: f.u64("voting_ends_at")?,
+            })
+        }
+        "VOTE_CST" => {
+            let f = fields(&["proposal_id", "voter", "choice"]);
+            DecodedEvent::VoteCast(VoteCast {
+                proposal_id: f.u64("proposal_id")?,
+                voter: f.address("voter")?,
+                choice: f.u32("choice")?,
+            })
+        }
+        "PROP_FIN" => {
+            let f = fields(&[
+                "proposal_id",
+                "status",
+                "votes_for",
+                "votes_against",
+                "total_voters",
+            ]);
+            DecodedEvent::ProposalFinalized(ProposalFinalized {
+                proposal_id: f.u64("proposal_id")?,
+                status: f.u32("status")?,
+                votes_for: f.u64("votes_for")?,
+                votes_against: f.u64("votes_against")?,
+                total_voters: f.u64("total_voters")?,
+            })
+        }
+        "CFG_CHG" => {
+            let f = fields(&["parameter", "old_value", "new_value"]);
+            DecodedEvent::ConfigChanged(ConfigChanged {
+                parameter: f.symbol("parameter")?,
+                old_value: f.u64("old_value")?,
+                new_value: f.u64("new_value")?,
+            })
+        }
+        _ => return None,
+    };
+    Some(event)
+}
+
+fn decode_scval(value: &str) -> Option<ScVal> {
+    let bytes = BASE64.decode(value.trim()).ok()?;
+    // Event values come from the network; bound nesting and let nothing
+    // claim more bytes than were received
+    let limits = Limits {
+        depth: XDR_MAX_DEPTH,
+        len: bytes.len(),
+    };
+    ScVal::from_xdr(&bytes, limits).ok()
+}
+
+/// Named fields of event data, from a symbol-keyed map or a tuple
+struct Fields<'a> {
+    data: &'a ScVal,
+    order: &'static [&'static str],
+}
+
+impl Fields<'_> {
+    fn get(&self, name: &str) -> Option<&ScVal> {
+        match self.data {
+            ScVal::Map(Some(map)) => map.0.iter().find_map(|entry| match &entry.key {
+                ScVal::Symbol(key) if key.0.as_slice() == name.as_bytes() => Some(&entry.val),
+                _ => None,
+            }),
+            ScVal::Vec(Some(items)) => {
+                let index = self.order.iter().position(|field| *field == name)?;
+                items.0.get(index)
+            }
+            _ => None,
+        }
+    }
+
+    fn u64(&self, name: &str) -> Option<u64> {
+        match self.get(name)? {
+            ScVal::U64(value) => Some(*value),
+            ScVal::U32(value) => Some(*value as u64),
+            ScVal::Timepoint(value) => Some(value.0),
+            _ => None,
+        }
+    }
+
+    fn u32(&self, name: &str) -> Option<u32> {
+        match self.get(name)? {
+            ScVal::U32(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn hex(&self, name: &str) -> Option<String> {
+        match self.get(name)? {
+            ScVal::Bytes(bytes) => Some(hex::encode(bytes.0.as_slice())),
+            _ => None,
+        }
+    }
+
+    fn address(&self, name: &str) -> Option<String> {
+        match self.get(name)? {
+            ScVal::Address(address) => Some(address.to_string()),
+            _ => None,
+        }
+    }
+
+    fn symbol(&self, name: &str) -> Option<String> {
+        match self.get(name)? {
+            ScVal::Symbol(symbol) => symbol.0.to_utf8_string().ok(),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `SNAP_SUB`/`SNAP_LFE` event for epoch 42 with its struct data
+    const SNAPSHOT_TOPICS: [&str; 2] = ["AAAADwAAAAhTTkFQX1NVQg==", "AAAADwAAAAhTTkFQX0xGRQ=="];
+    const SNAPSHOT_VALUE: &str = "AAAAEQAAAAEAAAAEAAAADwAAAAVlcG9jaAAAAAAAAAUAAAAAAAAAKgAAAA8AAAAEaGFzaAAAAA0AAAAgAAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8AAAAPAAAACXN1Ym1pdHRlcgAAAAAAABIAAAAAAAAAAAMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAAAADwAAAAl0aW1lc3RhbXAAAAAAAAAFAAAAAGlVuQA=";
+    /// The same event in the snapshot contract's tuple form, for epoch 7
+    const SNAPSHOT_TUPLE_VALUE: &str = "AAAAEAAAAAEAAAADAAAADQAAACAAAQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHwAAAAUAAAAAAAAABwAAAAUAAAAAaVW5AA==";
+    const SUBMITTER: &str = "GABQGAYDAMBQGAYDAMBQGAYDAMBQGAYDAMBQGAYDAMBQGAYDAMBQHGPC";
+    const HASH: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    fn topics(topics: &[&str]) -> Vec<String> {
+        topics.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn test_deeply_nested_value_is_rejected() {
+        use stellar_xdr::curr::{ScVec, WriteXdr};
+
+        let nested = |depth: u32| {
+            let mut value = ScVal::U32(1);
+            for _ in 0..depth {
+                value = ScVal::Vec(Some(ScVec(vec![value].try_into().unwrap())));
+            }
+            value.to_xdr_base64(Limits::none()).unwrap()
+        };
+
+        assert!(decode_scval(&nested(10)).is_some());
+        assert_eq!(decode_scval(&nested(XDR_MAX_DEPTH + 10)), None);
+    }
+
+    #[test]
+    fn test_decode_captured_snapshot_event() {
+        let event = RpcContractEvent {
+            id: "0000180388626432-0000000001".to_string(),
+            ledger: 42_000,
+            ledger_closed_at: "2026-01-01T00:00:00Z".to_string(),
+            contract_id: "CSNAPSHOT".to_string(),
+            tx_hash: "abc".to_string(),
+            topic: topics(&SNAPSHOT_TOPICS),
+            value: SNAPSHOT_VALUE.to_string(),
+        };
+
+        let expected = SnapshotSubmitted {
+            hash: HASH.to_string(),
+            epoch: 42,
+            timestamp: 1_767_225_600,
+            submitter: Some(SUBMITTER.to_string()),
+        };
+        assert_eq!(
+            event.decode(),
+            DecodedEvent::SnapshotSubmitted(expected.clone())
+        );
+
+        // Stored with the fields the snapshot processor reads
+        let stored = event.into_contract_event("testnet").unwrap();
+        assert_eq!(stored.event_type, "snapshot_submitted");
+        assert_eq!(stored.data["epoch"], 42);
+        assert_eq!(stored.data["hash"], HASH);
+        assert_eq!(stored.ledger_sequence, 42_000);
+        assert_eq!(stored.network, "testnet");
+
+        assert_eq!(
+            decode_event(&topics(&SNAPSHOT_TOPICS[..1]), SNAPSHOT_TUPLE_VALUE),
+            DecodedEvent::SnapshotSubmitted(SnapshotSubmitted {
+                epoch: 7,
+                submitter: None,
+                ..expected
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_governance_vote() {
+        let vote = decode_event(
+            &topics(&["AAAADwAAAAhWT1RFX0NTVA==", "AAAADwAAAAdHT1ZfTEZFAA=="]),
+            "AAAAEQAAAAEAAAADAAAADwAAAAZjaG9pY2UAAAAAAAMAAAABAAAADwAAAAtwcm9wb3NhbF9pZAAAAAAFAAAAAAAAAAUAAAAPAAAABXZvdGVyAAAAAAAAEgAAAAAAAAAABAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQ=",
+        );
+        let DecodedEvent::VoteCast(vote) = vote else {
+            panic!("not a vote: {:?}", vote);
+        };
+        assert_eq!(vote.proposal_id, 5);
+        assert_eq!(vote.choice, 1);
+        assert!(vote.voter.starts_with('G'));
+    }
+
+    #[test]
+    fn test_unknown_event_is_kept_raw() {
+        // `STOPPED` from the snapshot contract has no decoder
+        let stopped = topics(&["AAAADwAAAAdTVE9QUEVEAA=="]);
+        let value = "AAAAEAAAAAEAAAABAAAAEgAAAAAAAAAAAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwMDAwM=";
+        let decoded = decode_event(&stopped, value);
+        assert_eq!(
+            decoded,
+            DecodedEvent::Unknown(RawEvent {
+                topics: stopped.clone(),
+                value: value.to_string(),
+            })
+        );
+        assert_eq!(decoded.event_type(), "unknown:STOPPED");
+        assert_eq!(decoded.data()["value"], value);
+
+        // A known topic with data of the wrong shape is kept raw too
+        let malformed = decode_event(&topics(&SNAPSHOT_TOPICS), value);
+        assert!(matches!(malformed, DecodedEvent::Unknown(_)));
+        assert_eq!(malformed.event_type(), "unknown:SNAP_SUB");
+
+        let garbage = decode_event(&topics(&["not xdr"]), "");
+        assert_eq!(garbage.event_type(), "unknown");
+    }
+}
@@ -15,14 +15,18 @@
 
 pub mod checkpoint;
 pub mod config;
+pub mod cursor;
 pub mod engine;
+pub mod event_decoder;
 pub mod event_processor;
 pub mod state_builder;
 pub mod storage;
 
 pub use checkpoint::{Checkpoint, CheckpointManager};
 pub use config::{ReplayConfig, ReplayMode, ReplayRange};
+pub use cursor::{CursorUpdate, EventCursor};
 pub use engine::ReplayEngine;
+pub use event_decoder::{decode_event, DecodedEvent, RpcContractEvent};
 pub use event_processor::{EventProcessor, ProcessingContext, ProcessingResult};
 pub use state_builder::StateBuilder;
 pub use storage::{EventStorage, ReplayStorage};
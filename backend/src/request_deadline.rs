@@ -0,0 +1,103 @@
+//! Per-request time budget for database work.
+//!
+//! The middleware stamps each request with a deadline, stored in the request
+//! extensions and in a task-local for the handler's task. `Database` reads
+//! that go through its query timeout shrink their limit to what is left of
+//! the budget, and fail with [`DeadlineExceeded`] once it is spent, so a
+//! handler chaining many queries gives up instead of running long past the
+//! point where the client cares.
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+/// Default time a request may spend before its database reads are cut off
+pub const DEFAULT_MAX_REQUEST_DURATION: Duration = Duration::from_secs(30);
+
+tokio::task_local! {
+    static DEADLINE: Instant;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestDeadlineConfig {
+    pub max_request_duration: Duration,
+}
+
+impl Default for RequestDeadlineConfig {
+    fn default() -> Self {
+        Self {
+            max_request_duration: DEFAULT_MAX_REQUEST_DURATION,
+        }
+    }
+}
+
+/// Point in time by which a request should be answered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestDeadline(pub Instant);
+
+impl RequestDeadline {
+    pub fn after(budget: Duration) -> Self {
+        Self(Instant::now() + budget)
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.0.saturating_duration_since(Instant::now())
+    }
+}
+
+/// The request's time budget ran out before a query could finish
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Request deadline exceeded during query '{query}'")]
+pub struct DeadlineExceeded {
+    pub query: &'static str,
+}
+
+/// Run `fut` with `deadline` as the budget for the database reads it makes
+pub async fn with_deadline<F: Future>(deadline: RequestDeadline, fut: F) -> F::Output {
+    DEADLINE.scope(deadline.0, fut).await
+}
+
+/// Budget left for the current request, `None` outside of one
+pub fn remaining_budget() -> Option<Duration> {
+    DEADLINE
+        .try_with(|deadline| deadline.saturating_duration_since(Instant::now()))
+        .ok()
+}
+
+/// Middleware setting the request deadline from the configured duration
+pub async fn request_deadline_middleware(
+    State(config): State<RequestDeadlineConfig>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let deadline = RequestDeadline::after(config.max_request_duration);
+    req.extensions_mut().insert(deadline);
+    with_deadline(deadline, next.run(req)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_remaining_budget_is_scoped() {
+        assert_eq!(remaining_budget(), None);
+
+        let deadline = RequestDeadline::after(Duration::from_secs(60));
+        let remaining = with_deadline(deadline, async { remaining_budget() })
+            .await
+            .unwrap();
+        assert!(remaining > Duration::from_secs(59));
+        assert!(remaining <= Duration::from_secs(60));
+
+        let spent = RequestDeadline::after(Duration::ZERO);
+        assert_eq!(
+            with_deadline(spent, async { remaining_budget() }).await,
+            Some(Duration::ZERO)
+        );
+    }
+}
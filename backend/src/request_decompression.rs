@@ -0,0 +1,24 @@
+//! Decompression of `Content-Encoding: gzip` / `br` request bodies.
+//!
+//! The body size limit is enforced on the decompressed stream, so a small
+//! compressed payload cannot expand past it (zip bomb).
+
+use axum::{extract::DefaultBodyLimit, Router};
+use tower_http::decompression::RequestDecompressionLayer;
+
+/// Default cap on a request body after decompression
+pub const DEFAULT_MAX_REQUEST_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+/// Accept gzip and brotli request bodies on every route of `router`.
+///
+/// Bodies read through extractors are limited to `max_body_bytes` after
+/// decompression; exceeding it yields `413 Payload Too Large`. Other
+/// encodings are rejected with `415 Unsupported Media Type`.
+pub fn with_request_decompression<S>(router: Router<S>, max_body_bytes: usize) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        .layer(DefaultBodyLimit::max(max_body_bytes))
+        .layer(RequestDecompressionLayer::new().gzip(true).br(true))
+}
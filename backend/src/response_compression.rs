@@ -0,0 +1,223 @@
+//! Compression of response bodies.
+//!
+//! `COMPRESSION_ALGORITHMS` picks which encodings are negotiated with
+//! `Accept-Encoding` and `COMPRESSION_LEVEL` how hard they compress.
+//! Responses smaller than `COMPRESSION_MIN_SIZE`, or whose content type
+//! starts with an entry of `COMPRESSION_EXCLUDED_CONTENT_TYPES` (images and
+//! archives are already compressed), are sent as-is.
+
+use axum::body::HttpBody;
+use axum::http::{header, Response};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use tower_http::compression::predicate::{And, Predicate, SizeAbove};
+use tower_http::compression::CompressionLayer;
+use tower_http::CompressionLevel;
+
+/// Content types never compressed unless `COMPRESSION_EXCLUDED_CONTENT_TYPES`
+/// says otherwise
+pub const DEFAULT_EXCLUDED_CONTENT_TYPES: &[&str] = &[
+    "image/",
+    "audio/",
+    "video/",
+    "application/zip",
+    "application/gzip",
+    "application/grpc",
+    "text/event-stream",
+];
+
+/// Encodings offered to clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionAlgorithms {
+    pub gzip: bool,
+    pub br: bool,
+}
+
+impl Default for CompressionAlgorithms {
+    fn default() -> Self {
+        Self {
+            gzip: true,
+            br: true,
+        }
+    }
+}
+
+impl FromStr for CompressionAlgorithms {
+    type Err = String;
+
+    /// Comma-separated `gzip` / `br` (or `brotli`); `none` disables
+    /// compression
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut algorithms = Self {
+            gzip: false,
+            br: false,
+        };
+        for name in s.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+            match name.to_ascii_lowercase().as_str() {
+                "gzip" => algorithms.gzip = true,
+                "br" | "brotli" => algorithms.br = true,
+                "none" => {}
+                _ => return Err(format!("unsupported compression algorithm '{}'", name)),
+            }
+        }
+        Ok(algorithms)
+    }
+}
+
+impl fmt::Display for CompressionAlgorithms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.gzip, self.br) {
+            (true, true) => f.write_str("gzip, brotli"),
+            (true, false) => f.write_str("gzip"),
+            (false, true) => f.write_str("brotli"),
+            (false, false) => f.write_str("none"),
+        }
+    }
+}
+
+/// Quality level shared by every enabled algorithm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CompressionQuality {
+    Fastest,
+    #[default]
+    Default,
+    Best,
+    /// Algorithm-specific level, clamped to what each algorithm supports
+    /// (gzip 0-9, brotli 0-11)
+    Precise(i32),
+}
+
+impl FromStr for CompressionQuality {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "fastest" => Ok(Self::Fastest),
+            "default" => Ok(Self::Default),
+            "best" => Ok(Self::Best),
+            other => other
+                .parse::<u8>()
+                .map(|level| Self::Precise(level.into()))
+                .map_err(|_| format!("invalid compression level '{}'", s)),
+        }
+    }
+}
+
+impl From<CompressionQuality> for CompressionLevel {
+    fn from(quality: CompressionQuality) -> Self {
+        match quality {
+            CompressionQuality::Fastest => CompressionLevel::Fastest,
+            CompressionQuality::Default => CompressionLevel::Default,
+            CompressionQuality::Best => CompressionLevel::Best,
+            CompressionQuality::Precise(level) => CompressionLevel::Precise(level),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseCompressionConfig {
+    pub algorithms: CompressionAlgorithms,
+    pub quality: CompressionQuality,
+    /// Smallest response body compressed, in bytes
+    pub min_size: u16,
+    /// Content type prefixes sent uncompressed
+    pub excluded_content_types: Vec<String>,
+}
+
+impl Default for ResponseCompressionConfig {
+    fn default() -> Self {
+        Self {
+            algorithms: CompressionAlgorithms::default(),
+            quality: CompressionQuality::default(),
+            min_size: 1024,
+            excluded_content_types: DEFAULT_EXCLUDED_CONTENT_TYPES
+                .iter()
+                .map(|t| t.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Skips responses whose content type starts with an excluded prefix
+#[derive(Debug, Clone)]
+pub struct CompressibleContentType {
+    excluded: Arc<[String]>,
+}
+
+impl CompressibleContentType {
+    pub fn new(excluded: &[String]) -> Self {
+        Self {
+            excluded: excluded
+                .iter()
+                .map(|t| t.trim().to_ascii_lowercase())
+                .filter(|t| !t.is_empty())
+                .collect(),
+        }
+    }
+}
+
+impl Predicate for CompressibleContentType {
+    fn should_compress<B>(&self, response: &Response<B>) -> bool
+    where
+        B: HttpBody,
+    {
+        let Some(content_type) = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+        else {
+            return true;
+        };
+        let content_type = content_type.trim().to_ascii_lowercase();
+        !self
+            .excluded
+            .iter()
+            .any(|prefix| content_type.starts_with(prefix.as_str()))
+    }
+}
+
+pub type ResponseCompressionLayer = CompressionLayer<And<SizeAbove, CompressibleContentType>>;
+
+/// Build the response compression layer for `config`
+pub fn compression_layer(config: &ResponseCompressionConfig) -> ResponseCompressionLayer {
+    CompressionLayer::new()
+        .gzip(config.algorithms.gzip)
+        .br(config.algorithms.br)
+        .quality(config.quality.into())
+        .compress_when(
+            SizeAbove::new(config.min_size)
+                .and(CompressibleContentType::new(&config.excluded_content_types)),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_algorithms() {
+        let both: CompressionAlgorithms = "gzip, Brotli".parse().unwrap();
+        assert_eq!(both, CompressionAlgorithms::default());
+        assert_eq!(
+            "br".parse::<CompressionAlgorithms>().unwrap(),
+            CompressionAlgorithms {
+                gzip: false,
+                br: true
+            }
+        );
+        assert_eq!(
+            "none".parse::<CompressionAlgorithms>().unwrap().to_string(),
+            "none"
+        );
+        assert!("gzip,zstd".parse::<CompressionAlgorithms>().is_err());
+    }
+
+    #[test]
+    fn test_parse_quality() {
+        assert_eq!("best".parse(), Ok(CompressionQuality::Best));
+        assert_eq!(" 4 ".parse(), Ok(CompressionQuality::Precise(4)));
+        assert!("-1".parse::<CompressionQuality>().is_err());
+        assert!("max".parse::<CompressionQuality>().is_err());
+    }
+}
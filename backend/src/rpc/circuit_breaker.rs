@@ -2,20 +2,27 @@
 //!
 //! After a configurable number of failures, the circuit opens and requests
 //! fail fast. After a timeout, the circuit moves to half-open and allows
-//! a limited number of test requests; success closes the circuit.
+//! a limited number of test requests at a time; success closes the circuit.
+//! Opening and closing are published to [`CircuitBreaker::subscribe`] receivers.
+//!
+//! [`CircuitBreakers`] keeps one breaker per [`EndpointGroup`], so a flaky
+//! optional endpoint does not fail fast the calls of critical ones.
 
+use futures::stream::{self, BoxStream, StreamExt};
 use std::sync::Arc;
-use std::time::{Duration, Instant};
-use tokio::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::{broadcast, Mutex};
+use tokio::time::Instant;
 
 use crate::rpc::error::RpcError;
 use crate::rpc::metrics;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CircuitBreakerConfig {
     pub failure_threshold: u32,
     pub success_threshold: u32,
     pub timeout_duration: Duration,
+    /// Test requests let through at a time while half-open
     pub half_open_max_calls: u32,
 }
 
@@ -30,11 +37,27 @@ impl Default for CircuitBreakerConfig {
     }
 }
 
+/// A change of the circuit between letting requests through and failing fast
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitTransition {
+    /// The circuit opened after repeated failures, or reopened from half-open
+    Opened,
+    /// The circuit closed after enough successful half-open calls
+    Closed,
+}
+
+/// A transition of the breaker guarding `group`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GroupTransition {
+    pub group: EndpointGroup,
+    pub transition: CircuitTransition,
+}
+
 #[derive(Debug, Clone)]
 enum CircuitState {
     Closed { failure_count: u32 },
     Open { opened_at: Instant },
-    HalfOpen { success_count: u32 },
+    HalfOpen { success_count: u32, in_flight: u32 },
 }
 
 /// Circuit breaker for a single logical endpoint (e.g. Horizon API).
@@ -43,6 +66,7 @@ pub struct CircuitBreaker {
     state: Arc<Mutex<CircuitState>>,
     config: CircuitBreakerConfig,
     endpoint: String,
+    transitions: broadcast::Sender<CircuitTransition>,
 }
 
 impl CircuitBreaker {
@@ -51,9 +75,24 @@ impl CircuitBreaker {
             state: Arc::new(Mutex::new(CircuitState::Closed { failure_count: 0 })),
             config,
             endpoint: endpoint.into(),
+            transitions: broadcast::channel(16).0,
         }
     }
 
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    /// Receive every later transition of this circuit
+    pub fn subscribe(&self) -> broadcast::Receiver<CircuitTransition> {
+        self.transitions.subscribe()
+    }
+
+    fn notify(&self, transition: CircuitTransition) {
+        // No subscribers is fine
+        let _ = self.transitions.send(transition);
+    }
+
     /// Run an operation through the circuit breaker.
     /// Returns CircuitBreakerOpen if the circuit is open.
     pub async fn call<F, Fut, T>(&self, f: F) -> Result<T, RpcError>
@@ -75,27 +114,42 @@ impl CircuitBreaker {
             Err(e) if e.is_retryable() => {
                 self.on_failure().await;
             }
-            Err(_) => {}
+            Err(_) => {
+                self.on_neutral().await;
+            }
         }
 
         result
     }
 
+    /// Whether a call must fail fast; a call let through while half-open
+    /// takes one of the `half_open_max_calls` test slots
     async fn is_open(&self) -> bool {
         let mut state = self.state.lock().await;
         let now = Instant::now();
 
-        match &*state {
+        match &mut *state {
             CircuitState::Open { opened_at } => {
                 if now.duration_since(*opened_at) >= self.config.timeout_duration {
-                    *state = CircuitState::HalfOpen { success_count: 0 };
+                    *state = CircuitState::HalfOpen {
+                        success_count: 0,
+                        in_flight: 1,
+                    };
                     metrics::set_circuit_breaker_state(&self.endpoint, 2); // half-open
                     false
                 } else {
                     true
                 }
             }
-            _ => false,
+            CircuitState::HalfOpen { in_flight, .. } => {
+                if *in_flight >= self.config.half_open_max_calls.max(1) {
+                    true
+                } else {
+                    *in_flight += 1;
+                    false
+                }
+            }
+            CircuitState::Closed { .. } => false,
         }
     }
 
@@ -103,13 +157,18 @@ impl CircuitBreaker {
         let mut state = self.state.lock().await;
         let current = std::mem::replace(&mut *state, CircuitState::Closed { failure_count: 0 });
         *state = match current {
-            CircuitState::HalfOpen { success_count } => {
+            CircuitState::HalfOpen {
+                success_count,
+                in_flight,
+            } => {
                 if success_count + 1 >= self.config.success_threshold {
                     metrics::set_circuit_breaker_state(&self.endpoint, 0); // closed
+                    self.notify(CircuitTransition::Closed);
                     CircuitState::Closed { failure_count: 0 }
                 } else {
                     CircuitState::HalfOpen {
                         success_count: success_count + 1,
+                        in_flight: in_flight.saturating_sub(1),
                     }
                 }
             }
@@ -127,6 +186,7 @@ impl CircuitBreaker {
             CircuitState::Closed { failure_count } => {
                 if failure_count + 1 >= self.config.failure_threshold {
                     metrics::set_circuit_breaker_state(&self.endpoint, 1); // open
+                    self.notify(CircuitTransition::Opened);
                     CircuitState::Open {
                         opened_at: Instant::now(),
                     }
@@ -138,6 +198,7 @@ impl CircuitBreaker {
             }
             CircuitState::HalfOpen { .. } => {
                 metrics::set_circuit_breaker_state(&self.endpoint, 1);
+                self.notify(CircuitTransition::Opened);
                 CircuitState::Open {
                     opened_at: Instant::now(),
                 }
@@ -145,6 +206,125 @@ impl CircuitBreaker {
             other => other,
         };
     }
+
+    /// A call failed in a way that says nothing about the endpoint's health;
+    /// only its half-open test slot is released
+    async fn on_neutral(&self) {
+        if let CircuitState::HalfOpen { in_flight, .. } = &mut *self.state.lock().await {
+            *in_flight = in_flight.saturating_sub(1);
+        }
+    }
+}
+
+/// Group of RPC/Horizon endpoints sharing a circuit breaker
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndpointGroup {
+    /// Health, ledgers, payments, transactions, operations and submission
+    Core,
+    /// Per-account payment and operation history
+    Accounts,
+    /// Trades, order books, liquidity pools and assets
+    Markets,
+}
+
+impl EndpointGroup {
+    pub const ALL: [Self; 3] = [Self::Core, Self::Accounts, Self::Markets];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Core => "core",
+            Self::Accounts => "accounts",
+            Self::Markets => "markets",
+        }
+    }
+
+    /// Endpoint name the group's breaker reports metrics under; the core
+    /// group keeps the historical `rpc`
+    pub fn endpoint(&self) -> &'static str {
+        match self {
+            Self::Core => "rpc",
+            Self::Accounts => "rpc_accounts",
+            Self::Markets => "rpc_markets",
+        }
+    }
+}
+
+/// Circuit breaker settings of each [`EndpointGroup`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupCircuitBreakerConfigs {
+    configs: [CircuitBreakerConfig; 3],
+}
+
+impl GroupCircuitBreakerConfigs {
+    /// The same `config` for every group
+    pub fn uniform(config: CircuitBreakerConfig) -> Self {
+        Self {
+            configs: EndpointGroup::ALL.map(|_| config.clone()),
+        }
+    }
+
+    /// Use `config` for `group`
+    pub fn with_group(mut self, group: EndpointGroup, config: CircuitBreakerConfig) -> Self {
+        self.configs[group as usize] = config;
+        self
+    }
+
+    pub fn get(&self, group: EndpointGroup) -> &CircuitBreakerConfig {
+        &self.configs[group as usize]
+    }
+}
+
+/// One circuit breaker per [`EndpointGroup`]
+#[derive(Clone)]
+pub struct CircuitBreakers {
+    breakers: [Arc<CircuitBreaker>; 3],
+}
+
+impl CircuitBreakers {
+    /// Breakers configured by `config_for` each group
+    pub fn new(config_for: impl Fn(EndpointGroup) -> CircuitBreakerConfig) -> Self {
+        Self {
+            breakers: EndpointGroup::ALL
+                .map(|group| Arc::new(CircuitBreaker::new(config_for(group), group.endpoint()))),
+        }
+    }
+
+    /// The same `config` for every group
+    pub fn uniform(config: CircuitBreakerConfig) -> Self {
+        Self::new(|_| config.clone())
+    }
+
+    /// Breakers with the settings `configs` holds for each group
+    pub fn configured(configs: &GroupCircuitBreakerConfigs) -> Self {
+        Self::new(|group| configs.get(group).clone())
+    }
+
+    /// Breaker guarding `group`
+    pub fn get(&self, group: EndpointGroup) -> &Arc<CircuitBreaker> {
+        &self.breakers[group as usize]
+    }
+
+    /// Every later transition of every group's breaker, merged into one
+    /// stream that ends once all the breakers are dropped
+    pub fn subscribe(&self) -> BoxStream<'static, GroupTransition> {
+        stream::select_all(EndpointGroup::ALL.map(|group| {
+            stream::unfold(self.get(group).subscribe(), move |mut rx| async move {
+                loop {
+                    match rx.recv().await {
+                        Ok(transition) => return Some((GroupTransition { group, transition }, rx)),
+                        Err(broadcast::error::RecvError::Lagged(skipped)) => tracing::warn!(
+                            "Circuit transitions of the {} group lagged, skipped {}",
+                            group.label(),
+                            skipped
+                        ),
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+            })
+            .boxed()
+        }))
+        .boxed()
+    }
 }
 
 #[cfg(test)]
@@ -247,4 +427,123 @@ mod tests {
         let r3 = cb.call(|| async { Ok(3) }).await;
         assert_eq!(r3.unwrap(), 3);
     }
+
+    async fn fail(cb: &CircuitBreaker) {
+        let _: Result<(), _> = cb
+            .call(|| async {
+                Err(RpcError::ServerError {
+                    status: 503,
+                    message: "x".into(),
+                })
+            })
+            .await;
+    }
+
+    /// Whether the breaker fails a call fast. The probe ends in a neutral
+    /// error, so it neither resets nor adds to the failure count.
+    async fn is_rejected(cb: &CircuitBreaker) -> bool {
+        matches!(
+            cb.call(|| async { Err::<(), _>(RpcError::ParseError("probe".into())) })
+                .await,
+            Err(RpcError::CircuitBreakerOpen)
+        )
+    }
+
+    #[tokio::test]
+    async fn half_open_lets_through_limited_test_calls() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            success_threshold: 3,
+            timeout_duration: Duration::from_millis(10),
+            half_open_max_calls: 2,
+        };
+        let cb = CircuitBreaker::new(config, "test");
+        fail(&cb).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // Two slow test calls take both slots
+        let (release, released) = tokio::sync::watch::channel(false);
+        let probe = || {
+            let cb = cb.clone();
+            let mut released = released.clone();
+            tokio::spawn(async move {
+                cb.call(|| async move {
+                    released.wait_for(|r| *r).await.unwrap();
+                    Ok(())
+                })
+                .await
+            })
+        };
+        let first = probe();
+        tokio::task::yield_now().await;
+        let second = probe();
+        tokio::task::yield_now().await;
+        assert!(is_rejected(&cb).await);
+
+        release.send(true).unwrap();
+        first.await.unwrap().unwrap();
+        second.await.unwrap().unwrap();
+        // A finished test call frees its slot
+        assert!(!is_rejected(&cb).await);
+    }
+
+    #[tokio::test]
+    async fn group_with_higher_threshold_tolerates_more_failures() {
+        let breakers = CircuitBreakers::new(|group| CircuitBreakerConfig {
+            failure_threshold: if group == EndpointGroup::Markets {
+                4
+            } else {
+                2
+            },
+            ..test_config()
+        });
+        let core = breakers.get(EndpointGroup::Core);
+        let markets = breakers.get(EndpointGroup::Markets);
+
+        for _ in 0..2 {
+            fail(core).await;
+            fail(markets).await;
+        }
+        assert!(is_rejected(core).await);
+        // Markets failures are counted apart from the core ones
+        assert!(!is_rejected(markets).await);
+
+        // Markets opens on exactly its own fourth failure
+        fail(markets).await;
+        assert!(!is_rejected(markets).await);
+        fail(markets).await;
+        assert!(is_rejected(markets).await);
+        assert!(!is_rejected(breakers.get(EndpointGroup::Accounts)).await);
+    }
+
+    #[tokio::test]
+    async fn subscribe_reports_the_group_of_each_transition() {
+        let breakers = CircuitBreakers::uniform(CircuitBreakerConfig {
+            failure_threshold: 1,
+            ..test_config()
+        });
+        let mut transitions = breakers.subscribe();
+
+        fail(breakers.get(EndpointGroup::Markets)).await;
+        fail(breakers.get(EndpointGroup::Accounts)).await;
+
+        let mut groups = vec![
+            transitions.next().await.unwrap(),
+            transitions.next().await.unwrap(),
+        ];
+        groups.sort_by_key(|t| t.group.label());
+        assert_eq!(
+            groups,
+            [
+                GroupTransition {
+                    group: EndpointGroup::Accounts,
+                    transition: CircuitTransition::Opened,
+                },
+                GroupTransition {
+                    group: EndpointGroup::Markets,
+                    transition: CircuitTransition::Opened,
+                },
+            ]
+        );
+    }
 }
@@ -0,0 +1,278 @@
+//! Per-operation choice between Horizon and Soroban RPC as the data source.
+//!
+//! Operations that both APIs can serve may be routed to either, so that
+//! deployments without a full Horizon instance can still ingest data.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::{
+    FeeBumpTransactionInnerTx, Hash, Limits, MuxedAccount, ReadXdr, TransactionEnvelope,
+    TransactionResult, TransactionSignaturePayload, TransactionSignaturePayloadTaggedTransaction,
+    WriteXdr,
+};
+
+use super::stellar::{FeeBumpTransactionInfo, HorizonTransaction, InnerTransaction};
+
+/// Backend an operation's data is fetched from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataSource {
+    Horizon,
+    Rpc,
+}
+
+impl DataSource {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "horizon" => Some(Self::Horizon),
+            "rpc" | "soroban_rpc" => Some(Self::Rpc),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Horizon => "horizon",
+            Self::Rpc => "rpc",
+        }
+    }
+}
+
+/// Operations that both Horizon and Soroban RPC can serve
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataOperation {
+    /// Latest closed ledger (`/ledgers` vs `getLatestLedger`)
+    LatestLedger,
+    /// Transactions of one ledger (`/ledgers/:seq/transactions` vs `getTransactions`)
+    LedgerTransactions,
+}
+
+impl DataOperation {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "latest_ledger" => Some(Self::LatestLedger),
+            "ledger_transactions" | "transactions" => Some(Self::LedgerTransactions),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::LatestLedger => "latest_ledger",
+            Self::LedgerTransactions => "ledger_transactions",
+        }
+    }
+}
+
+/// Data source selected for each operation; everything defaults to Horizon
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DataSourceConfig {
+    pub latest_ledger: DataSource,
+    pub ledger_transactions: DataSource,
+}
+
+impl Default for DataSourceConfig {
+    fn default() -> Self {
+        Self {
+            latest_ledger: DataSource::Horizon,
+            ledger_transactions: DataSource::Horizon,
+        }
+    }
+}
+
+impl DataSourceConfig {
+    /// One `<operation>=<source>` entry of `RPC_DATA_SOURCES`, e.g.
+    /// `latest_ledger=rpc`
+    pub fn parse_entry(entry: &str) -> Option<(DataOperation, DataSource)> {
+        let (operation, source) = entry.split_once('=')?;
+        Some((DataOperation::parse(operation)?, DataSource::parse(source)?))
+    }
+
+    pub fn with_source(mut self, operation: DataOperation, source: DataSource) -> Self {
+        match operation {
+            DataOperation::LatestLedger => self.latest_ledger = source,
+            DataOperation::LedgerTransactions => self.ledger_transactions = source,
+        }
+        self
+    }
+
+    pub fn source_for(&self, operation: DataOperation) -> DataSource {
+        match operation {
+            DataOperation::LatestLedger => self.latest_ledger,
+            DataOperation::LedgerTransactions => self.ledger_transactions,
+        }
+    }
+}
+
+/// Transaction as returned by Soroban RPC `getTransactions`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransaction {
+    pub status: String,
+    /// Present from RPC v22; computed from the envelope otherwise
+    #[serde(default)]
+    pub tx_hash: Option<String>,
+    #[serde(default)]
+    pub application_order: u32,
+    pub envelope_xdr: String,
+    #[serde(default)]
+    pub result_xdr: Option<String>,
+    pub ledger: u64,
+    /// Ledger close time, in Unix seconds
+    pub created_at: i64,
+}
+
+/// Result of Soroban RPC `getTransactions`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetTransactionsResult {
+    #[serde(default)]
+    pub transactions: Vec<RpcTransaction>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+fn sha256_hex(
+    network_id: &Hash,
+    transaction: TransactionSignaturePayloadTaggedTransaction,
+) -> Option<String> {
+    let payload = TransactionSignaturePayload {
+        network_id: network_id.clone(),
+        tagged_transaction: transaction,
+    };
+    let bytes = payload.to_xdr(Limits::none()).ok()?;
+    Some(hex::encode(Sha256::digest(bytes)))
+}
+
+fn signatures(signatures: &[stellar_xdr::curr::DecoratedSignature]) -> Vec<String> {
+    signatures
+        .iter()
+        .map(|s| BASE64.encode(s.signature.as_slice()))
+        .collect()
+}
+
+/// Convert an RPC transaction into the Horizon shape used by ingestion.
+///
+/// Returns `None` when the envelope cannot be decoded or, for envelopes
+/// without a reported hash, the hash cannot be derived.
+pub fn rpc_transaction_to_horizon(
+    tx: &RpcTransaction,
+    network_passphrase: &str,
+) -> Option<HorizonTransaction> {
+    let envelope =
+        TransactionEnvelope::from_xdr(BASE64.decode(&tx.envelope_xdr).ok()?, Limits::none())
+            .ok()?;
+    let network_id = Hash(Sha256::digest(network_passphrase.as_bytes()).into());
+
+    let fee_charged = tx
+        .result_xdr
+        .as_deref()
+        .and_then(|xdr| BASE64.decode(xdr).ok())
+        .and_then(|bytes| TransactionResult::from_xdr(bytes, Limits::none()).ok())
+        .map(|result| result.fee_charged.to_string());
+
+    let (hash, source_account, fee_account, max_fee, operation_count, fee_bump, inner) =
+        match &envelope {
+            TransactionEnvelope::TxV0(v0) => (
+                tx.tx_hash.clone()?,
+                MuxedAccount::Ed25519(v0.tx.source_account_ed25519.clone()).to_string(),
+                None,
+                v0.tx.fee,
+                v0.tx.operations.len(),
+                None,
+                None,
+            ),
+            TransactionEnvelope::Tx(v1) => {
+                let hash = tx.tx_hash.clone().or_else(|| {
+                    sha256_hex(
+                        &network_id,
+                        TransactionSignaturePayloadTaggedTransaction::Tx(v1.tx.clone()),
+                    )
+                })?;
+                (
+                    hash,
+                    v1.tx.source_account.to_string(),
+                    None,
+                    v1.tx.fee,
+                    v1.tx.operations.len(),
+                    None,
+                    None,
+                )
+            }
+            TransactionEnvelope::TxFeeBump(outer) => {
+                let FeeBumpTransactionInnerTx::Tx(inner) = &outer.tx.inner_tx;
+                let hash = tx.tx_hash.clone().or_else(|| {
+                    sha256_hex(
+                        &network_id,
+                        TransactionSignaturePayloadTaggedTransaction::TxFeeBump(outer.tx.clone()),
+                    )
+                })?;
+                let inner_hash = sha256_hex(
+                    &network_id,
+                    TransactionSignaturePayloadTaggedTransaction::Tx(inner.tx.clone()),
+                )?;
+                (
+                    hash.clone(),
+                    inner.tx.source_account.to_string(),
+                    Some(outer.tx.fee_source.to_string()),
+                    u32::try_from(outer.tx.fee).unwrap_or(u32::MAX),
+                    inner.tx.operations.len(),
+                    Some(FeeBumpTransactionInfo {
+                        hash,
+                        signatures: signatures(&outer.signatures),
+                    }),
+                    Some(InnerTransaction {
+                        hash: inner_hash,
+                        max_fee: Some(inner.tx.fee.to_string()),
+                        signatures: signatures(&inner.signatures),
+                    }),
+                )
+            }
+        };
+
+    let created_at = DateTime::<Utc>::from_timestamp(tx.created_at, 0)
+        .unwrap_or_default()
+        .to_rfc3339();
+    // Horizon paging tokens are TOIDs: ledger, then application order
+    let paging_token = ((tx.ledger << 32) | (u64::from(tx.application_order) << 12)).to_string();
+
+    Some(HorizonTransaction {
+        id: hash.clone(),
+        fee_account: fee_account.or_else(|| Some(source_account.clone())),
+        hash,
+        ledger: tx.ledger,
+        created_at,
+        source_account,
+        fee_charged,
+        max_fee: Some(max_fee.to_string()),
+        operation_count: operation_count as u32,
+        successful: tx.status == "SUCCESS",
+        paging_token,
+        fee_bump_transaction: fee_bump,
+        inner_transaction: inner,
+        result_xdr: tx.result_xdr.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_data_source_entry() {
+        assert_eq!(
+            DataSourceConfig::parse_entry("latest_ledger=rpc"),
+            Some((DataOperation::LatestLedger, DataSource::Rpc))
+        );
+        assert_eq!(
+            DataSourceConfig::parse_entry("transactions=horizon"),
+            Some((DataOperation::LedgerTransactions, DataSource::Horizon))
+        );
+
+        assert_eq!(DataSourceConfig::parse_entry("bogus=rpc"), None);
+        assert_eq!(DataSourceConfig::parse_entry("latest_ledger=archive"), None);
+        assert_eq!(DataSourceConfig::parse_entry("latest_ledger"), None);
+    }
+}
@@ -123,15 +123,61 @@ where
                     return Err(e);
                 }
 
-                let delay = std::cmp::min(
-                    config
-                        .base_delay_ms
-                        .saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1))),
-                    config.max_delay_ms,
-                );
-
-                tokio::time::sleep(Duration::from_millis(delay)).await;
+                // A rate limit with a known reset is retried right after it,
+                // but never later than the longest backoff
+                let delay = match e {
+                    RpcError::RateLimitError {
+                        retry_after: Some(retry_after),
+                    } => retry_after.min(Duration::from_millis(config.max_delay_ms)),
+                    _ => Duration::from_millis(std::cmp::min(
+                        config
+                            .base_delay_ms
+                            .saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1))),
+                        config.max_delay_ms,
+                    )),
+                };
+
+                tokio::time::sleep(delay).await;
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::circuit_breaker::CircuitBreakerConfig;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[tokio::test]
+    async fn test_rate_limit_wait_is_capped_by_max_delay() {
+        let attempts = AtomicU32::new(0);
+        let breaker = Arc::new(CircuitBreaker::new(CircuitBreakerConfig::default(), "test"));
+        let config = RetryConfig {
+            max_attempts: 2,
+            base_delay_ms: 1,
+            max_delay_ms: 10,
+        };
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(5),
+            with_retry(
+                || async {
+                    if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                        Err(RpcError::RateLimitError {
+                            retry_after: Some(Duration::from_secs(3600)),
+                        })
+                    } else {
+                        Ok(())
+                    }
+                },
+                config,
+                breaker,
+            ),
+        )
+        .await;
+
+        assert!(matches!(result, Ok(Ok(()))));
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+}
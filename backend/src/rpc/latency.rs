@@ -0,0 +1,166 @@
+//! Rolling window of recent RPC latencies, reported by `/api/rpc/health`.
+//!
+//! Samples go into a fixed-size ring of atomics, so recording never takes a
+//! lock. Each slot packs the sample time and latency into one `u64`, which
+//! keeps a reader from seeing half of a sample.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_SAMPLE_CAPACITY: usize = 256;
+pub const DEFAULT_WINDOW_SECONDS: u64 = 300;
+
+/// Bits of a slot holding the latency in milliseconds
+const LATENCY_BITS: u32 = 24;
+const LATENCY_MASK: u64 = (1 << LATENCY_BITS) - 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatencyWindowConfig {
+    /// Samples kept; older ones are overwritten
+    pub capacity: usize,
+    /// Samples older than this are left out of the average
+    pub window: Duration,
+}
+
+impl Default for LatencyWindowConfig {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_SAMPLE_CAPACITY,
+            window: Duration::from_secs(DEFAULT_WINDOW_SECONDS),
+        }
+    }
+}
+
+/// Recent RPC responsiveness
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LatencySummary {
+    pub sample_count: usize,
+    /// `None` when no request completed within the window
+    pub average_ms: Option<f64>,
+    pub window_secs: u64,
+}
+
+/// Bounded store of the most recent RPC latency samples
+#[derive(Debug)]
+pub struct LatencyWindow {
+    /// `(ms since origin + 1) << LATENCY_BITS | latency ms`; 0 is an empty slot
+    slots: Box<[AtomicU64]>,
+    next: AtomicUsize,
+    origin: Instant,
+    window: Duration,
+}
+
+impl LatencyWindow {
+    pub fn new(config: LatencyWindowConfig) -> Self {
+        Self {
+            slots: (0..config.capacity.max(1))
+                .map(|_| AtomicU64::new(0))
+                .collect(),
+            next: AtomicUsize::new(0),
+            origin: Instant::now(),
+            window: config.window,
+        }
+    }
+
+    pub fn record(&self, latency: Duration) {
+        self.record_at(latency, Instant::now());
+    }
+
+    fn record_at(&self, latency: Duration, at: Instant) {
+        let since_origin = at.saturating_duration_since(self.origin).as_millis() as u64;
+        let latency_ms = (latency.as_millis() as u64).min(LATENCY_MASK);
+        let slot = self.next.fetch_add(1, Ordering::Relaxed) % self.slots.len();
+        self.slots[slot].store(
+            ((since_origin + 1) << LATENCY_BITS) | latency_ms,
+            Ordering::Relaxed,
+        );
+    }
+
+    pub fn summary(&self) -> LatencySummary {
+        self.summary_at(Instant::now())
+    }
+
+    fn summary_at(&self, now: Instant) -> LatencySummary {
+        let now_ms = now.saturating_duration_since(self.origin).as_millis() as u64;
+        let window_ms = self.window.as_millis() as u64;
+
+        let (count, total) = self
+            .slots
+            .iter()
+            .map(|slot| slot.load(Ordering::Relaxed))
+            .filter(|packed| *packed != 0)
+            .filter(|packed| {
+                let recorded_ms = (packed >> LATENCY_BITS) - 1;
+                now_ms.saturating_sub(recorded_ms) <= window_ms
+            })
+            .fold((0usize, 0u64), |(count, total), packed| {
+                (count + 1, total + (packed & LATENCY_MASK))
+            });
+
+        LatencySummary {
+            sample_count: count,
+            average_ms: (count > 0).then(|| total as f64 / count as f64),
+            window_secs: self.window.as_secs(),
+        }
+    }
+}
+
+impl Default for LatencyWindow {
+    fn default() -> Self {
+        Self::new(LatencyWindowConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(capacity: usize, window_secs: u64) -> LatencyWindow {
+        LatencyWindow::new(LatencyWindowConfig {
+            capacity,
+            window: Duration::from_secs(window_secs),
+        })
+    }
+
+    #[test]
+    fn test_rolling_average_uses_most_recent_samples() {
+        let latencies = window(3, 60);
+        let now = latencies.origin + Duration::from_secs(10);
+        assert_eq!(latencies.summary_at(now).average_ms, None);
+
+        for ms in [100, 200, 300] {
+            latencies.record_at(Duration::from_millis(ms), now);
+        }
+        let summary = latencies.summary_at(now);
+        assert_eq!(summary.sample_count, 3);
+        assert_eq!(summary.average_ms, Some(200.0));
+
+        // The oldest sample is overwritten once the ring is full
+        latencies.record_at(Duration::from_millis(700), now);
+        let summary = latencies.summary_at(now);
+        assert_eq!(summary.sample_count, 3);
+        assert_eq!(summary.average_ms, Some(400.0));
+    }
+
+    #[test]
+    fn test_old_samples_age_out() {
+        let latencies = window(10, 60);
+        let start = latencies.origin;
+        latencies.record_at(Duration::from_millis(1000), start);
+        let summary = latencies.summary_at(start + Duration::from_secs(30));
+        assert_eq!(summary.sample_count, 1);
+        assert_eq!(summary.average_ms, Some(1000.0));
+
+        latencies.record_at(Duration::from_millis(50), start + Duration::from_secs(50));
+        latencies.record_at(Duration::from_millis(150), start + Duration::from_secs(55));
+
+        let summary = latencies.summary_at(start + Duration::from_secs(90));
+        assert_eq!(summary.sample_count, 2);
+        assert_eq!(summary.average_ms, Some(100.0));
+
+        let summary = latencies.summary_at(start + Duration::from_secs(200));
+        assert_eq!(summary.sample_count, 0);
+        assert_eq!(summary.average_ms, None);
+    }
+}
@@ -1,14 +1,20 @@
 pub mod circuit_breaker;
 pub mod config;
+pub mod data_source;
 pub mod error;
+pub mod latency;
 pub mod metrics;
 pub mod rate_limiter;
+pub mod recording;
 pub mod stellar;
 
+pub use data_source::{DataOperation, DataSource, DataSourceConfig};
+pub use latency::{LatencySummary, LatencyWindow, LatencyWindowConfig};
 pub use rate_limiter::{RpcRateLimitConfig, RpcRateLimitMetrics, RpcRateLimiter};
+pub use recording::{RecordingConfig, RecordingMode};
 pub use stellar::{
-    Asset, FeeBumpTransactionInfo, GetLedgersResult, HealthResponse, HorizonAsset, HorizonEffect,
-    HorizonLiquidityPool, HorizonOperation, HorizonPoolReserve, HorizonTransaction,
-    InnerTransaction, LedgerInfo, OrderBook, OrderBookEntry, Payment, Price, RpcLedger,
-    StellarRpcClient, Trade,
+    Asset, FeeBumpTransactionInfo, GetLedgersResult, HealthResponse, HorizonAccount,
+    HorizonAccountBalance, HorizonAsset, HorizonEffect, HorizonLiquidityPool, HorizonOperation,
+    HorizonPoolReserve, HorizonTransaction, InnerTransaction, LedgerInfo, MaxRecordsExceeded,
+    OrderBook, OrderBookEntry, PaginatedResult, Payment, Price, RpcLedger, StellarRpcClient, Trade,
 };
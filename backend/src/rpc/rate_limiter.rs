@@ -84,6 +84,8 @@ struct TokenBucketState {
     capacity: f64,
     refill_rate_per_second: f64,
     last_refill: Instant,
+    /// Set after a 429: no request goes out before this
+    blocked_until: Option<Instant>,
 }
 
 #[derive(Clone)]
@@ -111,6 +113,7 @@ impl RpcRateLimiter {
                 capacity,
                 refill_rate_per_second,
                 last_refill: Instant::now(),
+                blocked_until: None,
             })),
             queue: Arc::new(Semaphore::new(config.queue_size)),
             total_requests: Arc::new(AtomicU64::new(0)),
@@ -133,7 +136,14 @@ impl RpcRateLimiter {
                 let mut state = self.state.lock().await;
                 Self::refill_locked(&mut state);
 
-                if state.tokens >= 1.0 {
+                let blocked_for = state
+                    .blocked_until
+                    .map(|until| until.saturating_duration_since(Instant::now()))
+                    .unwrap_or_default();
+                if !blocked_for.is_zero() {
+                    self.throttled_requests.fetch_add(1, Ordering::Relaxed);
+                    blocked_for
+                } else if state.tokens >= 1.0 {
                     state.tokens -= 1.0;
                     Duration::from_secs(0)
                 } else {
@@ -178,19 +188,24 @@ impl RpcRateLimiter {
         }
     }
 
-    pub async fn on_rate_limited(&self, headers: &HeaderMap) {
+    /// Record a 429 and hold back every request until the server's reset.
+    ///
+    /// The wait comes from the `Retry-After` header, then from Horizon's
+    /// problem+json `body`, then defaults to five seconds. Returns the wait so
+    /// the caller can delay its retry by it.
+    pub async fn on_rate_limited(&self, headers: &HeaderMap, body: &str) -> Duration {
         self.rate_limited_responses.fetch_add(1, Ordering::Relaxed);
 
-        let wait_seconds =
-            parse_retry_after_seconds(headers).unwrap_or(DEFAULT_RETRY_AFTER_SECONDS);
-
-        {
-            let mut state = self.state.lock().await;
-            state.tokens = 0.0;
-            state.last_refill = Instant::now();
-        }
+        let wait = parse_retry_after_seconds(headers)
+            .map(Duration::from_secs)
+            .or_else(|| parse_horizon_retry_after(body))
+            .unwrap_or(Duration::from_secs(DEFAULT_RETRY_AFTER_SECONDS));
 
-        tokio::time::sleep(Duration::from_secs(wait_seconds)).await;
+        let mut state = self.state.lock().await;
+        state.tokens = 0.0;
+        state.last_refill = Instant::now();
+        state.blocked_until = Some(Instant::now() + wait);
+        wait
     }
 
     pub fn metrics(&self) -> RpcRateLimitMetrics {
@@ -237,6 +252,29 @@ fn parse_retry_after_seconds(headers: &HeaderMap) -> Option<u64> {
     }
 }
 
+/// Wait requested by a Horizon rate-limit error body.
+///
+/// Horizon answers 429 with a problem+json document; the wait is read from
+/// `retry_after` (seconds to wait) or `reset` (seconds until the limit window
+/// resets), at the top level or under `extras`. Numbers may be fractional or
+/// given as strings.
+pub fn parse_horizon_retry_after(body: &str) -> Option<Duration> {
+    let problem: serde_json::Value = serde_json::from_str(body).ok()?;
+    let seconds = |value: &serde_json::Value| match value {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.trim().parse::<f64>().ok(),
+        _ => None,
+    };
+
+    let wait = [&problem, &problem["extras"]]
+        .into_iter()
+        .flat_map(|scope| [&scope["retry_after"], &scope["reset"]])
+        .find_map(seconds)
+        .filter(|s| s.is_finite() && *s >= 0.0)
+        .and_then(|s| Duration::try_from_secs_f64(s).ok());
+    wait
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -302,12 +340,54 @@ mod tests {
         let mut headers = HeaderMap::new();
         headers.insert("retry-after", HeaderValue::from_static("0"));
 
-        limiter.on_rate_limited(&headers).await;
+        let wait = limiter
+            .on_rate_limited(&headers, r#"{"extras": {"retry_after": 30}}"#)
+            .await;
 
+        // The header wins over the body
+        assert_eq!(wait, Duration::ZERO);
         let metrics = limiter.metrics();
         assert_eq!(metrics.rate_limited_responses, 1);
     }
 
+    #[tokio::test]
+    async fn rate_limited_body_reset_blocks_requests() {
+        let limiter = RpcRateLimiter::new(RpcRateLimitConfig::default());
+        let body = r#"{
+            "type": "https://stellar.org/horizon-errors/rate_limit_exceeded",
+            "title": "Rate Limit Exceeded",
+            "status": 429,
+            "extras": {"reset": "0.3"}
+        }"#;
+
+        let wait = limiter.on_rate_limited(&HeaderMap::new(), body).await;
+        assert_eq!(wait, Duration::from_millis(300));
+
+        let start = Instant::now();
+        limiter.acquire().await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(250));
+    }
+
+    #[test]
+    fn horizon_retry_after_parses_problem_body() {
+        let parse = parse_horizon_retry_after;
+        assert_eq!(
+            parse(r#"{"status": 429, "retry_after": 2}"#),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            parse(r#"{"status": 429, "extras": {"retry_after": "1.5"}}"#),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            parse(r#"{"status": 429, "extras": {"reset": 12}}"#),
+            Some(Duration::from_secs(12))
+        );
+        assert_eq!(parse(r#"{"status": 429, "detail": "slow down"}"#), None);
+        assert_eq!(parse(r#"{"extras": {"retry_after": -1}}"#), None);
+        assert_eq!(parse("Too Many Requests"), None);
+    }
+
     #[tokio::test]
     async fn queue_full_increments_rejected_metric() {
         let limiter = RpcRateLimiter::new(RpcRateLimitConfig {
@@ -0,0 +1,189 @@
+//! Recording and replay of Horizon and RPC traffic.
+//!
+//! In `record` mode the client makes real calls and writes each response to
+//! a fixture file; in `replay` mode it makes no network calls and answers from
+//! those fixtures instead, so tests can run deterministically against captured
+//! real data. Fixtures are keyed by the request method, URL and JSON body.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Default directory for recorded fixtures, relative to the working directory
+pub const DEFAULT_FIXTURES_DIR: &str = "tests/fixtures/rpc";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RecordingMode {
+    /// Plain network calls
+    #[default]
+    Off,
+    /// Network calls whose responses are saved as fixtures
+    Record,
+    /// No network calls; responses come from saved fixtures
+    Replay,
+}
+
+impl RecordingMode {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "off" | "" => Some(Self::Off),
+            "record" => Some(Self::Record),
+            "replay" => Some(Self::Replay),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for RecordingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| format!("unknown recording mode '{}'", s))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordingConfig {
+    pub mode: RecordingMode,
+    pub fixtures_dir: PathBuf,
+}
+
+impl RecordingConfig {
+    pub fn new(mode: RecordingMode, fixtures_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            mode,
+            fixtures_dir: fixtures_dir.into(),
+        }
+    }
+}
+
+/// One recorded exchange
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fixture {
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<serde_json::Value>,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+/// Fixture key for a request: its method, URL and body. JSON bodies are
+/// compared by value, so key order and whitespace do not matter.
+pub fn fixture_key(method: &str, url: &str, body: Option<&[u8]>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.to_uppercase().as_bytes());
+    hasher.update(b" ");
+    hasher.update(url.as_bytes());
+    if let Some(body) = body {
+        hasher.update(b"\n");
+        match serde_json::from_slice::<serde_json::Value>(body) {
+            Ok(json) => hasher.update(canonical_json(&json).as_bytes()),
+            Err(_) => hasher.update(body),
+        }
+    }
+    format!(
+        "{}-{}",
+        method.to_lowercase(),
+        &hex::encode(hasher.finalize())[..16]
+    )
+}
+
+/// `value` as JSON text with object keys sorted
+fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            let fields: Vec<String> = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    format!(
+                        "{}:{}",
+                        serde_json::Value::from(key.as_str()),
+                        canonical_json(value)
+                    )
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        serde_json::Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Fixture files in one directory, one `<key>.json` per request
+#[derive(Debug, Clone)]
+pub struct FixtureStore {
+    dir: PathBuf,
+}
+
+impl FixtureStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    /// The fixture recorded under `key`, `None` if there is none
+    pub async fn load(&self, key: &str) -> std::io::Result<Option<Fixture>> {
+        let contents = match tokio::fs::read(self.path(key)).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        serde_json::from_slice(&contents)
+            .map(Some)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Write `fixture` under `key`, replacing any earlier recording
+    pub async fn save(&self, key: &str, fixture: &Fixture) -> std::io::Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let contents = serde_json::to_vec_pretty(fixture)?;
+        tokio::fs::write(self.path(key), contents).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixture_key_follows_request_parameters() {
+        let url = "https://horizon.example/ledgers?limit=1";
+        let key = fixture_key("GET", url, None);
+        assert!(key.starts_with("get-"));
+        assert_eq!(key, fixture_key("get", url, None));
+        assert_ne!(
+            key,
+            fixture_key("GET", "https://horizon.example/ledgers?limit=2", None)
+        );
+
+        let body = br#"{"jsonrpc": "2.0", "method": "getHealth", "id": 1}"#;
+        let reordered = br#"{"id":1,"method":"getHealth","jsonrpc":"2.0"}"#;
+        let other = br#"{"jsonrpc": "2.0", "method": "getLatestLedger", "id": 1}"#;
+        let key = fixture_key("POST", url, Some(body));
+        assert_eq!(key, fixture_key("POST", url, Some(reordered)));
+        assert_ne!(key, fixture_key("POST", url, Some(other)));
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(RecordingMode::parse("Record"), Some(RecordingMode::Record));
+        assert_eq!(RecordingMode::parse("replay"), Some(RecordingMode::Replay));
+        assert_eq!(RecordingMode::parse(""), Some(RecordingMode::Off));
+        assert_eq!(RecordingMode::parse("rewind"), None);
+    }
+}
@@ -1,13 +1,20 @@
 use crate::network::{NetworkConfig, StellarNetwork};
-use crate::rpc::circuit_breaker::CircuitBreaker;
-use crate::rpc::config::{
-    circuit_breaker_config_from_env, initial_backoff_from_env, max_backoff_from_env,
-    max_retries_from_env,
+use crate::rpc::circuit_breaker::{
+    CircuitBreakerConfig, CircuitBreakers, EndpointGroup, GroupTransition,
+};
+use crate::rpc::config::{initial_backoff_from_env, max_backoff_from_env, max_retries_from_env};
+use crate::rpc::data_source::{
+    rpc_transaction_to_horizon, DataOperation, DataSource, DataSourceConfig, GetTransactionsResult,
 };
 use crate::rpc::error::{with_retry, RetryConfig, RpcError};
+use crate::rpc::latency::{LatencySummary, LatencyWindow, LatencyWindowConfig};
 use crate::rpc::metrics;
-use crate::rpc::rate_limiter::{RpcRateLimitConfig, RpcRateLimitMetrics, RpcRateLimiter};
+use crate::rpc::rate_limiter::{
+    parse_horizon_retry_after, RpcRateLimitConfig, RpcRateLimitMetrics, RpcRateLimiter,
+};
+use crate::rpc::recording::{fixture_key, Fixture, FixtureStore, RecordingConfig, RecordingMode};
 use anyhow::{anyhow, Context, Result};
+use futures::stream::BoxStream;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -38,6 +45,9 @@ pub struct HorizonAsset {
     pub contracts_amount: String,
     pub balances: AssetBalances,
     pub flags: AssetFlags,
+    /// Cursor for the page after this asset, `CODE_ISSUER_TYPE`
+    #[serde(default)]
+    pub paging_token: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +64,23 @@ pub struct AssetBalances {
     pub unauthorized: String,
 }
 
+/// An account from Horizon's `/accounts` endpoint, reduced to its balances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HorizonAccount {
+    pub id: String,
+    pub balances: Vec<HorizonAccountBalance>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HorizonAccountBalance {
+    pub balance: String,
+    pub asset_type: String,
+    #[serde(default)]
+    pub asset_code: Option<String>,
+    #[serde(default)]
+    pub asset_issuer: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetFlags {
     pub auth_required: bool,
@@ -62,6 +89,39 @@ pub struct AssetFlags {
     pub auth_clawback_enabled: bool,
 }
 
+/// Default largest per-request `max_records` override
+pub const DEFAULT_MAX_RECORDS_CEILING: u32 = 50_000;
+
+/// A `fetch_all_*` override asked for more records than the ceiling allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MaxRecordsExceeded {
+    pub requested: u32,
+    pub ceiling: u32,
+}
+
+impl std::fmt::Display for MaxRecordsExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Requested {} records, above the maximum of {} per paginated fetch",
+            self.requested, self.ceiling
+        )
+    }
+}
+
+impl std::error::Error for MaxRecordsExceeded {}
+
+/// Records from a paginated fetch and where it stopped
+#[derive(Debug, Clone)]
+pub struct PaginatedResult<T> {
+    pub items: Vec<T>,
+    pub fetched: u32,
+    /// The fetch stopped at `max_records`, so more records may exist
+    pub capped: bool,
+    /// Cursor of the last record, to resume from when `capped`
+    pub last_cursor: Option<String>,
+}
+
 #[derive(Clone)]
 pub struct StellarRpcClient {
     client: Client,
@@ -70,11 +130,13 @@ pub struct StellarRpcClient {
     network_config: NetworkConfig,
     mock_mode: bool,
     rate_limiter: RpcRateLimiter,
-    circuit_breaker: Arc<CircuitBreaker>,
+    circuit_breakers: CircuitBreakers,
     /// Maximum records per single request (default: 200)
     max_records_per_request: u32,
     /// Maximum total records across all paginated requests (default: 10000)
     max_total_records: u32,
+    /// Largest per-request `max_records` override accepted (default: 50000)
+    max_records_ceiling: u32,
     /// Delay between pagination requests in milliseconds (default: 100)
     pagination_delay_ms: u64,
     /// Maximum retries for RPC calls
@@ -83,6 +145,13 @@ pub struct StellarRpcClient {
     initial_backoff: Duration,
     /// Maximum backoff duration
     max_backoff: Duration,
+    /// Whether Horizon or RPC serves each operation both can answer
+    data_sources: DataSourceConfig,
+    /// Recent request latencies, reported by the health endpoint; shared by
+    /// clones so they all feed the same window
+    latency: Arc<LatencyWindow>,
+    /// Whether responses are recorded to or replayed from fixtures
+    recording: RecordingConfig,
 }
 
 // ============================================================================
@@ -257,6 +326,12 @@ pub struct HorizonOperation {
     pub account: Option<String>,
     pub into: Option<String>,
     pub amount: Option<String>,
+    /// Account that funded a `create_account` operation
+    pub funder: Option<String>,
+    /// Whether the enclosing transaction succeeded; only sent by Horizon when
+    /// failed transactions are included
+    #[serde(default)]
+    pub transaction_successful: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -289,6 +364,9 @@ pub struct HorizonTransaction {
     pub fee_bump_transaction: Option<FeeBumpTransactionInfo>,
     #[serde(rename = "inner_transaction")]
     pub inner_transaction: Option<InnerTransaction>,
+    /// Base64 `TransactionResult` XDR, used to explain failures
+    #[serde(default)]
+    pub result_xdr: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -423,7 +501,9 @@ fn status_to_rpc_error(
 ) -> RpcError {
     if status.as_u16() == 429 {
         return RpcError::RateLimitError {
-            retry_after: retry_after_secs.map(Duration::from_secs),
+            retry_after: retry_after_secs
+                .map(Duration::from_secs)
+                .or_else(|| parse_horizon_retry_after(&body)),
         };
     }
     if (500..=599).contains(&status.as_u16()) {
@@ -478,8 +558,7 @@ impl StellarRpcClient {
         };
 
         let network_config = NetworkConfig::for_network(network);
-        let cb_config = circuit_breaker_config_from_env();
-        let circuit_breaker = Arc::new(CircuitBreaker::new(cb_config, "rpc"));
+        let circuit_breakers = CircuitBreakers::uniform(CircuitBreakerConfig::default());
 
         // Load pagination config from environment or use defaults
         let max_records_per_request = std::env::var("RPC_MAX_RECORDS_PER_REQUEST")
@@ -504,13 +583,17 @@ impl StellarRpcClient {
             network_config,
             mock_mode,
             rate_limiter,
-            circuit_breaker,
+            circuit_breakers,
             max_records_per_request,
             max_total_records,
+            max_records_ceiling: DEFAULT_MAX_RECORDS_CEILING,
             pagination_delay_ms,
             max_retries: max_retries_from_env(),
             initial_backoff: initial_backoff_from_env(),
             max_backoff: max_backoff_from_env(),
+            data_sources: DataSourceConfig::default(),
+            latency: Arc::new(LatencyWindow::new(LatencyWindowConfig::default())),
+            recording: RecordingConfig::default(),
         }
     }
 
@@ -523,8 +606,7 @@ impl StellarRpcClient {
             .build()
             .expect("Failed to build HTTP client");
         let rate_limiter = RpcRateLimiter::new(RpcRateLimitConfig::from_env());
-        let cb_config = circuit_breaker_config_from_env();
-        let circuit_breaker = Arc::new(CircuitBreaker::new(cb_config, "rpc"));
+        let circuit_breakers = CircuitBreakers::uniform(CircuitBreakerConfig::default());
 
         // Load pagination config from environment or use defaults
         let max_records_per_request = std::env::var("RPC_MAX_RECORDS_PER_REQUEST")
@@ -549,13 +631,17 @@ impl StellarRpcClient {
             network_config,
             mock_mode,
             rate_limiter,
-            circuit_breaker,
+            circuit_breakers,
             max_records_per_request,
             max_total_records,
+            max_records_ceiling: DEFAULT_MAX_RECORDS_CEILING,
             pagination_delay_ms,
             max_retries: max_retries_from_env(),
             initial_backoff: initial_backoff_from_env(),
             max_backoff: max_backoff_from_env(),
+            data_sources: DataSourceConfig::default(),
+            latency: Arc::new(LatencyWindow::new(LatencyWindowConfig::default())),
+            recording: RecordingConfig::default(),
         }
     }
 
@@ -564,6 +650,73 @@ impl StellarRpcClient {
         Self::new_with_network(StellarNetwork::Mainnet, mock_mode)
     }
 
+    /// Fetch each operation from the source `data_sources` selects instead of Horizon
+    pub fn with_data_sources(mut self, data_sources: DataSourceConfig) -> Self {
+        self.data_sources = data_sources;
+        self
+    }
+
+    /// Use `config` instead of the default latency window
+    pub fn with_latency_window(mut self, config: LatencyWindowConfig) -> Self {
+        self.latency = Arc::new(LatencyWindow::new(config));
+        self
+    }
+
+    /// Use `breakers` instead of breakers with the default configuration
+    pub fn with_circuit_breakers(mut self, breakers: CircuitBreakers) -> Self {
+        self.circuit_breakers = breakers;
+        self
+    }
+
+    /// Record or replay traffic as `recording` says; clients make plain
+    /// network calls otherwise
+    pub fn with_recording(mut self, recording: RecordingConfig) -> Self {
+        self.recording = recording;
+        self
+    }
+
+    /// Accept `max_records` overrides up to `ceiling` instead of
+    /// `DEFAULT_MAX_RECORDS_CEILING`
+    pub fn with_max_records_ceiling(mut self, ceiling: u32) -> Self {
+        self.max_records_ceiling = ceiling;
+        self
+    }
+
+    /// Largest `max_records` override the `fetch_all_*` methods accept
+    pub fn max_records_ceiling(&self) -> u32 {
+        self.max_records_ceiling
+    }
+
+    /// Records a `fetch_all_*` call fetches: the override when given, else
+    /// `max_total_records`
+    fn resolve_max_records(&self, max_records: Option<u32>) -> Result<u32> {
+        match max_records {
+            None => Ok(self.max_total_records),
+            Some(requested) if requested > self.max_records_ceiling => Err(MaxRecordsExceeded {
+                requested,
+                ceiling: self.max_records_ceiling,
+            }
+            .into()),
+            Some(requested) => Ok(requested),
+        }
+    }
+
+    /// Receive the open and close transitions of every endpoint group's
+    /// circuit breaker
+    pub fn subscribe_circuit_transitions(&self) -> BoxStream<'static, GroupTransition> {
+        self.circuit_breakers.subscribe()
+    }
+
+    /// Settings of the circuit breaker guarding `group`
+    pub fn circuit_breaker_config(&self, group: EndpointGroup) -> &CircuitBreakerConfig {
+        self.circuit_breakers.get(group).config()
+    }
+
+    /// Data source used for `operation`
+    pub fn data_source(&self, operation: DataOperation) -> DataSource {
+        self.data_sources.source_for(operation)
+    }
+
     /// Get the current network configuration
     pub fn network_config(&self) -> &NetworkConfig {
         &self.network_config
@@ -589,7 +742,85 @@ impl StellarRpcClient {
         self.rate_limiter.metrics()
     }
 
-    async fn execute_with_retry<F, Fut, T>(&self, operation: F) -> Result<T, RpcError>
+    /// Average latency of the requests made within the latency window
+    pub fn recent_latency(&self) -> LatencySummary {
+        self.latency.summary()
+    }
+
+    /// Send `request`, recording its response or replaying a recorded one
+    /// depending on the recording mode
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, RpcError> {
+        let network_error = |e: reqwest::Error| RpcError::NetworkError(e.to_string());
+        if self.recording.mode == RecordingMode::Off {
+            return request.send().await.map_err(network_error);
+        }
+
+        let request = request.build().map_err(network_error)?;
+        let method = request.method().to_string();
+        let url = request.url().to_string();
+        let request_body = request
+            .body()
+            .and_then(|b| b.as_bytes())
+            .map(<[u8]>::to_vec);
+        let key = fixture_key(&method, &url, request_body.as_deref());
+        let store = FixtureStore::new(&self.recording.fixtures_dir);
+        let io_error = |e: std::io::Error| {
+            RpcError::NetworkError(format!("RPC fixture {} unavailable: {}", key, e))
+        };
+
+        let fixture = if self.recording.mode == RecordingMode::Replay {
+            store
+                .load(&key)
+                .await
+                .map_err(io_error)?
+                .ok_or_else(|| RpcError::ServerError {
+                    status: 404,
+                    message: format!(
+                        "No recorded fixture {} for {} {} in {}",
+                        key,
+                        method,
+                        url,
+                        store.dir().display()
+                    ),
+                })?
+        } else {
+            let response = self.client.execute(request).await.map_err(network_error)?;
+            let status = response.status().as_u16();
+            let content_type = response
+                .headers()
+                .get(reqwest::header::CONTENT_TYPE)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            let body = response.text().await.map_err(network_error)?;
+            let fixture = Fixture {
+                method,
+                url,
+                request_body: request_body.and_then(|b| serde_json::from_slice(&b).ok()),
+                status,
+                content_type,
+                body,
+            };
+            store.save(&key, &fixture).await.map_err(io_error)?;
+            debug!("Recorded RPC fixture {}", key);
+            fixture
+        };
+
+        let mut response = axum::http::Response::builder().status(fixture.status);
+        if let Some(content_type) = &fixture.content_type {
+            response = response.header(reqwest::header::CONTENT_TYPE, content_type);
+        }
+        let response = response
+            .body(fixture.body)
+            .map_err(|e| RpcError::ParseError(format!("Invalid RPC fixture {}: {}", key, e)))?;
+        Ok(reqwest::Response::from(response))
+    }
+
+    /// Run `operation` with retries, through the circuit breaker of `group`
+    async fn execute_with_retry<F, Fut, T>(
+        &self,
+        group: EndpointGroup,
+        operation: F,
+    ) -> Result<T, RpcError>
     where
         F: Fn() -> Fut,
         Fut: std::future::Future<Output = Result<T, RpcError>>,
@@ -600,7 +831,23 @@ impl StellarRpcClient {
             max_delay_ms: self.max_backoff.as_millis() as u64,
         };
 
-        with_retry(operation, retry_config, self.circuit_breaker.clone()).await
+        // Each attempt is a latency sample
+        let timed_operation = || {
+            let attempt = operation();
+            async move {
+                let start = Instant::now();
+                let result = attempt.await;
+                self.latency.record(start.elapsed());
+                result
+            }
+        };
+
+        with_retry(
+            timed_operation,
+            retry_config,
+            self.circuit_breakers.get(group).clone(),
+        )
+        .await
     }
 
     /// Check the health of the RPC endpoint
@@ -612,7 +859,7 @@ impl StellarRpcClient {
         info!("Checking RPC health at {}", self.rpc_url);
 
         let result = self
-            .execute_with_retry(|| self.check_health_internal())
+            .execute_with_retry(EndpointGroup::Core, || self.check_health_internal())
             .await;
 
         result.map_err(|e| {
@@ -629,12 +876,8 @@ impl StellarRpcClient {
         });
 
         let response = self
-            .client
-            .post(&self.rpc_url)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+            .send(self.client.post(&self.rpc_url).json(&payload))
+            .await?;
 
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
@@ -664,7 +907,7 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_latest_ledger_internal())
+            .execute_with_retry(EndpointGroup::Core, || self.fetch_latest_ledger_internal())
             .await;
 
         result.map_err(|e| {
@@ -674,13 +917,12 @@ impl StellarRpcClient {
     }
 
     async fn fetch_latest_ledger_internal(&self) -> Result<LedgerInfo, RpcError> {
+        if self.data_source(DataOperation::LatestLedger) == DataSource::Rpc {
+            return self.fetch_latest_ledger_rpc().await;
+        }
+
         let url = format!("{}/ledgers?order=desc&limit=1", self.horizon_url);
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -694,6 +936,118 @@ impl StellarRpcClient {
             .ok_or_else(|| RpcError::ParseError("No ledger data found".to_string()))
     }
 
+    /// Latest ledger via RPC `getLatestLedger`. RPC reports only the
+    /// sequence, hash and (from v22) close time; other fields are left empty.
+    async fn fetch_latest_ledger_rpc(&self) -> Result<LedgerInfo, RpcError> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct LatestLedger {
+            id: String,
+            sequence: u64,
+            #[serde(default)]
+            close_time: Option<String>,
+        }
+
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "getLatestLedger",
+            "id": 1
+        });
+        let latest: LatestLedger = self.rpc_call(&payload, "getLatestLedger").await?;
+
+        let closed_at = latest
+            .close_time
+            .and_then(|t| t.parse::<i64>().ok())
+            .and_then(|t| chrono::DateTime::<chrono::Utc>::from_timestamp(t, 0))
+            .map(|t| t.to_rfc3339())
+            .unwrap_or_default();
+
+        Ok(LedgerInfo {
+            sequence: latest.sequence,
+            hash: latest.id,
+            previous_hash: String::new(),
+            transaction_count: 0,
+            operation_count: 0,
+            closed_at,
+            total_coins: String::new(),
+            fee_pool: String::new(),
+            base_fee: 0,
+            base_reserve: String::new(),
+        })
+    }
+
+    /// POST a JSON-RPC request and return its result
+    async fn rpc_call<T: serde::de::DeserializeOwned>(
+        &self,
+        payload: &serde_json::Value,
+        method: &str,
+    ) -> Result<T, RpcError> {
+        let response = self
+            .send(self.client.post(&self.rpc_url).json(payload))
+            .await?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        let json_response: JsonRpcResponse<T> = response
+            .json()
+            .await
+            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        if let Some(error) = json_response.error {
+            return Err(RpcError::ServerError {
+                status: 500,
+                message: format!("RPC error: {} (code: {})", error.message, error.code),
+            });
+        }
+        json_response
+            .result
+            .ok_or_else(|| RpcError::ParseError(format!("No result in {} response", method)))
+    }
+
+    /// Submit a signed transaction envelope via RPC `sendTransaction` and
+    /// return its hash. Submissions are not retried.
+    pub async fn send_transaction(&self, envelope_xdr: &str) -> Result<String, RpcError> {
+        if self.mock_mode {
+            return Ok(Self::mock_transaction_hash(envelope_xdr));
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SendTransactionResult {
+            status: String,
+            hash: String,
+            #[serde(default)]
+            error_result_xdr: Option<String>,
+        }
+
+        let payload = json!({
+            "jsonrpc": "2.0",
+            "method": "sendTransaction",
+            "id": 1,
+            "params": { "transaction": envelope_xdr }
+        });
+        let result: SendTransactionResult = self
+            .rpc_call(&payload, "sendTransaction")
+            .await
+            .map_err(|e| {
+                metrics::record_rpc_error(e.error_type_label(), "stellar");
+                e
+            })?;
+
+        match result.status.as_str() {
+            "PENDING" | "DUPLICATE" => Ok(result.hash),
+            "TRY_AGAIN_LATER" => Err(RpcError::RateLimitError { retry_after: None }),
+            status => Err(RpcError::ServerError {
+                status: 400,
+                message: format!(
+                    "Transaction {} rejected with status {}: {}",
+                    result.hash,
+                    status,
+                    result.error_result_xdr.unwrap_or_default()
+                ),
+            }),
+        }
+    }
+
     /// I'm fetching ledgers via RPC getLedgers for sequential ingestion (issue #2)
     pub async fn fetch_ledgers(
         &self,
@@ -714,7 +1068,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_ledgers_internal(start_ledger, limit, cursor))
+            .execute_with_retry(EndpointGroup::Core, || {
+                self.fetch_ledgers_internal(start_ledger, limit, cursor)
+            })
             .await;
 
         result.map_err(|e| {
@@ -748,12 +1104,8 @@ impl StellarRpcClient {
             "params": params
         });
         let response = self
-            .client
-            .post(&self.rpc_url)
-            .json(&payload)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+            .send(self.client.post(&self.rpc_url).json(&payload))
+            .await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -785,7 +1137,9 @@ impl StellarRpcClient {
         info!("Fetching {} payments from Horizon API", limit);
 
         let result = self
-            .execute_with_retry(|| self.fetch_payments_internal(limit, cursor))
+            .execute_with_retry(EndpointGroup::Core, || {
+                self.fetch_payments_internal(limit, cursor)
+            })
             .await;
 
         result.map_err(|e| {
@@ -803,12 +1157,7 @@ impl StellarRpcClient {
         if let Some(c) = cursor {
             url.push_str(&format!("&cursor={}", c));
         }
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -833,7 +1182,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_trades_internal(limit, cursor))
+            .execute_with_retry(EndpointGroup::Markets, || {
+                self.fetch_trades_internal(limit, cursor)
+            })
             .await;
 
         result.map_err(|e| {
@@ -851,12 +1202,7 @@ impl StellarRpcClient {
         if let Some(c) = cursor {
             url.push_str(&format!("&cursor={}", c));
         }
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -882,7 +1228,7 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| {
+            .execute_with_retry(EndpointGroup::Markets, || {
                 self.fetch_order_book_internal(selling_asset, buying_asset, limit)
             })
             .await;
@@ -907,12 +1253,7 @@ impl StellarRpcClient {
             "{}/order_book?{}&{}&limit={}",
             self.horizon_url, selling_params, buying_params, limit
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -928,7 +1269,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_payments_for_ledger_internal(sequence))
+            .execute_with_retry(EndpointGroup::Core, || {
+                self.fetch_payments_for_ledger_internal(sequence)
+            })
             .await;
 
         result.map_err(|e| {
@@ -942,15 +1285,10 @@ impl StellarRpcClient {
         sequence: u64,
     ) -> Result<Vec<Payment>, RpcError> {
         let url = format!(
-            "{}/ledgers/{}/payments?limit=200",
+            "{}/ledgers/{}/payments?limit=200&include_failed=true",
             self.horizon_url, sequence
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -974,7 +1312,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_transactions_for_ledger_internal(sequence))
+            .execute_with_retry(EndpointGroup::Core, || {
+                self.fetch_transactions_for_ledger_internal(sequence)
+            })
             .await;
 
         result.map_err(|e| {
@@ -987,16 +1327,15 @@ impl StellarRpcClient {
         &self,
         sequence: u64,
     ) -> Result<Vec<HorizonTransaction>, RpcError> {
+        if self.data_source(DataOperation::LedgerTransactions) == DataSource::Rpc {
+            return self.fetch_transactions_for_ledger_rpc(sequence).await;
+        }
+
         let url = format!(
             "{}/ledgers/{}/transactions?limit=200&include_failed=true",
             self.horizon_url, sequence
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -1010,6 +1349,50 @@ impl StellarRpcClient {
             .unwrap_or_default())
     }
 
+    /// Transactions of one ledger via RPC `getTransactions`, converted to
+    /// the Horizon shape. Pages until a later ledger is reached.
+    async fn fetch_transactions_for_ledger_rpc(
+        &self,
+        sequence: u64,
+    ) -> Result<Vec<HorizonTransaction>, RpcError> {
+        const PAGE_SIZE: usize = 200;
+
+        let mut transactions = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = match &cursor {
+                Some(c) => json!({ "pagination": { "cursor": c, "limit": PAGE_SIZE } }),
+                None => json!({ "startLedger": sequence, "pagination": { "limit": PAGE_SIZE } }),
+            };
+            let payload = json!({
+                "jsonrpc": "2.0",
+                "method": "getTransactions",
+                "id": 1,
+                "params": params
+            });
+            let page: GetTransactionsResult = self.rpc_call(&payload, "getTransactions").await?;
+
+            let page_len = page.transactions.len();
+            let past_ledger = page.transactions.iter().any(|tx| tx.ledger > sequence);
+            for tx in page.transactions.iter().filter(|tx| tx.ledger == sequence) {
+                match rpc_transaction_to_horizon(tx, &self.network_config.network_passphrase) {
+                    Some(converted) => transactions.push(converted),
+                    None => warn!(
+                        "Skipping undecodable RPC transaction in ledger {}",
+                        sequence
+                    ),
+                }
+            }
+
+            if past_ledger || page_len < PAGE_SIZE || page.cursor.is_none() {
+                break;
+            }
+            cursor = page.cursor;
+        }
+
+        Ok(transactions)
+    }
+
     /// Fetch operations for a specific ledger
     pub async fn fetch_operations_for_ledger(
         &self,
@@ -1020,7 +1403,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_operations_for_ledger_internal(sequence))
+            .execute_with_retry(EndpointGroup::Core, || {
+                self.fetch_operations_for_ledger_internal(sequence)
+            })
             .await;
 
         result.map_err(|e| {
@@ -1037,12 +1422,7 @@ impl StellarRpcClient {
             "{}/ledgers/{}/operations?limit=200",
             self.horizon_url, sequence
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -1066,7 +1446,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_operation_effects_internal(operation_id))
+            .execute_with_retry(EndpointGroup::Core, || {
+                self.fetch_operation_effects_internal(operation_id)
+            })
             .await;
 
         result.map_err(|e| {
@@ -1083,12 +1465,7 @@ impl StellarRpcClient {
             "{}/operations/{}/effects?limit=200",
             self.horizon_url, operation_id
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -1113,7 +1490,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_account_payments_internal(account_id, limit))
+            .execute_with_retry(EndpointGroup::Accounts, || {
+                self.fetch_account_payments_internal(account_id, limit)
+            })
             .await;
 
         result.map_err(|e| {
@@ -1131,12 +1510,7 @@ impl StellarRpcClient {
             "{}/accounts/{}/payments?order=desc&limit={}",
             self.horizon_url, account_id, limit
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -1150,6 +1524,54 @@ impl StellarRpcClient {
             .unwrap_or_default())
     }
 
+    /// Fetch the most recent operations of an account, including those of
+    /// failed transactions
+    pub async fn fetch_account_operations(
+        &self,
+        account_id: &str,
+        limit: u32,
+    ) -> Result<Vec<HorizonOperation>, RpcError> {
+        if self.mock_mode {
+            let mut operations = Self::mock_operations_for_ledger(1);
+            operations.truncate(limit as usize);
+            return Ok(operations);
+        }
+
+        let result = self
+            .execute_with_retry(EndpointGroup::Accounts, || {
+                self.fetch_account_operations_internal(account_id, limit)
+            })
+            .await;
+
+        result.map_err(|e| {
+            metrics::record_rpc_error(e.error_type_label(), "stellar");
+            e
+        })
+    }
+
+    async fn fetch_account_operations_internal(
+        &self,
+        account_id: &str,
+        limit: u32,
+    ) -> Result<Vec<HorizonOperation>, RpcError> {
+        let url = format!(
+            "{}/accounts/{}/operations?order=desc&limit={}&include_failed=true",
+            self.horizon_url, account_id, limit
+        );
+        let response = self.send(self.client.get(&url)).await?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        let horizon_response: HorizonResponse<HorizonOperation> = response
+            .json()
+            .await
+            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        Ok(horizon_response
+            .embedded
+            .map(|e| e.records)
+            .unwrap_or_default())
+    }
+
     // ============================================================================
     // Paginated Fetch Methods
     // ============================================================================
@@ -1157,19 +1579,38 @@ impl StellarRpcClient {
     /// Fetch all payments with automatic pagination up to max_total_records
     ///
     /// # Arguments
-    /// * `max_records` - Optional maximum number of records to fetch (uses config default if None)
+    /// * `max_records` - Optional maximum number of records to fetch (uses config default if None);
+    ///   fails with [`MaxRecordsExceeded`] above the configured ceiling
     ///
     /// # Returns
     /// Vector of all fetched payments up to the limit
     pub async fn fetch_all_payments(&self, max_records: Option<u32>) -> Result<Vec<Payment>> {
+        Ok(self
+            .fetch_all_payments_paginated(max_records, None)
+            .await?
+            .items)
+    }
+
+    /// Like [`Self::fetch_all_payments`], starting after `cursor` and
+    /// reporting whether the limit cut the fetch short
+    pub async fn fetch_all_payments_paginated(
+        &self,
+        max_records: Option<u32>,
+        cursor: Option<&str>,
+    ) -> Result<PaginatedResult<Payment>> {
+        let max_records = self.resolve_max_records(max_records)?;
         if self.mock_mode {
-            let limit = max_records.unwrap_or(self.max_total_records);
-            return Ok(Self::mock_payments(limit));
+            let payments = Self::mock_payments(max_records);
+            return Ok(PaginatedResult {
+                fetched: payments.len() as u32,
+                capped: true,
+                last_cursor: payments.last().map(|p| p.paging_token.clone()),
+                items: payments,
+            });
         }
 
-        let max_records = max_records.unwrap_or(self.max_total_records);
         let mut all_payments = Vec::new();
-        let mut cursor: Option<String> = None;
+        let mut cursor: Option<String> = cursor.map(str::to_string);
         let mut fetched = 0;
 
         info!(
@@ -1219,25 +1660,49 @@ impl StellarRpcClient {
             "Completed pagination: fetched {} total payments",
             all_payments.len()
         );
-        Ok(all_payments)
+        Ok(PaginatedResult {
+            items: all_payments,
+            fetched,
+            capped: fetched >= max_records,
+            last_cursor: cursor,
+        })
     }
 
     /// Fetch all trades with automatic pagination up to max_total_records
     ///
     /// # Arguments
-    /// * `max_records` - Optional maximum number of records to fetch (uses config default if None)
+    /// * `max_records` - Optional maximum number of records to fetch (uses config default if None);
+    ///   fails with [`MaxRecordsExceeded`] above the configured ceiling
     ///
     /// # Returns
     /// Vector of all fetched trades up to the limit
     pub async fn fetch_all_trades(&self, max_records: Option<u32>) -> Result<Vec<Trade>> {
+        Ok(self
+            .fetch_all_trades_paginated(max_records, None)
+            .await?
+            .items)
+    }
+
+    /// Like [`Self::fetch_all_trades`], starting after `cursor` and
+    /// reporting whether the limit cut the fetch short
+    pub async fn fetch_all_trades_paginated(
+        &self,
+        max_records: Option<u32>,
+        cursor: Option<&str>,
+    ) -> Result<PaginatedResult<Trade>> {
+        let max_records = self.resolve_max_records(max_records)?;
         if self.mock_mode {
-            let limit = max_records.unwrap_or(self.max_total_records);
-            return Ok(Self::mock_trades(limit));
+            let trades = Self::mock_trades(max_records);
+            return Ok(PaginatedResult {
+                fetched: trades.len() as u32,
+                capped: true,
+                last_cursor: trades.last().map(|t| t.id.clone()),
+                items: trades,
+            });
         }
 
-        let max_records = max_records.unwrap_or(self.max_total_records);
         let mut all_trades = Vec::new();
-        let mut cursor: Option<String> = None;
+        let mut cursor: Option<String> = cursor.map(str::to_string);
         let mut fetched = 0;
 
         info!(
@@ -1289,14 +1754,20 @@ impl StellarRpcClient {
             "Completed pagination: fetched {} total trades",
             all_trades.len()
         );
-        Ok(all_trades)
+        Ok(PaginatedResult {
+            items: all_trades,
+            fetched,
+            capped: fetched >= max_records,
+            last_cursor: cursor,
+        })
     }
 
     /// Fetch all payments for a specific account with automatic pagination
     ///
     /// # Arguments
     /// * `account_id` - The Stellar account ID
-    /// * `max_records` - Optional maximum number of records to fetch (uses config default if None)
+    /// * `max_records` - Optional maximum number of records to fetch (uses config default if None);
+    ///   fails with [`MaxRecordsExceeded`] above the configured ceiling
     ///
     /// # Returns
     /// Vector of all fetched payments for the account up to the limit
@@ -1305,12 +1776,11 @@ impl StellarRpcClient {
         account_id: &str,
         max_records: Option<u32>,
     ) -> Result<Vec<Payment>> {
+        let max_records = self.resolve_max_records(max_records)?;
         if self.mock_mode {
-            let limit = max_records.unwrap_or(self.max_total_records);
-            return Ok(Self::mock_payments(limit));
+            return Ok(Self::mock_payments(max_records));
         }
 
-        let max_records = max_records.unwrap_or(self.max_total_records);
         let mut all_payments = Vec::new();
         let mut cursor: Option<String> = None;
         let mut fetched = 0;
@@ -1333,7 +1803,7 @@ impl StellarRpcClient {
             }
 
             let response = self
-                .retry_request(|| async { self.client.get(&url).send().await })
+                .retry_request(EndpointGroup::Accounts, || self.send(self.client.get(&url)))
                 .await
                 .context("Failed to fetch account payments page")?;
 
@@ -1413,11 +1883,16 @@ impl StellarRpcClient {
         }
     }
 
-    /// Retry a request with exponential backoff
-    async fn retry_request<F, Fut>(&self, request_fn: F) -> Result<reqwest::Response>
+    /// Retry a request with exponential backoff, through the circuit breaker
+    /// of `group`
+    async fn retry_request<F, Fut>(
+        &self,
+        group: EndpointGroup,
+        request_fn: F,
+    ) -> Result<reqwest::Response>
     where
         F: Fn() -> Fut,
-        Fut: std::future::Future<Output = Result<reqwest::Response, reqwest::Error>>,
+        Fut: std::future::Future<Output = Result<reqwest::Response, RpcError>>,
     {
         let retry_config = RetryConfig {
             max_attempts: MAX_RETRIES + 1,
@@ -1434,9 +1909,8 @@ impl StellarRpcClient {
                     .map_err(|_| RpcError::RateLimitError { retry_after: None })?;
 
                 let start_time = Instant::now();
-                let response = request_fn()
-                    .await
-                    .map_err(|e| RpcError::categorize(&e.to_string()))?;
+                let response = request_fn().await?;
+                self.latency.record(start_time.elapsed());
                 let elapsed = start_time.elapsed().as_millis();
                 let status = response.status();
                 let headers = response.headers().clone();
@@ -1449,10 +1923,6 @@ impl StellarRpcClient {
                     return Ok(response);
                 }
 
-                if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                    self.rate_limiter.on_rate_limited(&headers).await;
-                }
-
                 let error_text = response
                     .text()
                     .await
@@ -1464,12 +1934,13 @@ impl StellarRpcClient {
 
                 let msg = format!("HTTP {}: {}", status, error_text);
                 if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                    let retry_after = headers
-                        .get("Retry-After")
-                        .and_then(|v| v.to_str().ok())
-                        .and_then(|s| s.parse::<u64>().ok())
-                        .map(Duration::from_secs);
-                    Err(RpcError::RateLimitError { retry_after })
+                    let retry_after = self
+                        .rate_limiter
+                        .on_rate_limited(&headers, &error_text)
+                        .await;
+                    Err(RpcError::RateLimitError {
+                        retry_after: Some(retry_after),
+                    })
                 } else if status == reqwest::StatusCode::REQUEST_TIMEOUT
                     || status == reqwest::StatusCode::GATEWAY_TIMEOUT
                 {
@@ -1484,7 +1955,7 @@ impl StellarRpcClient {
                 }
             },
             retry_config,
-            self.circuit_breaker.clone(),
+            self.circuit_breakers.get(group).clone(),
         )
         .await
         .map_err(|e| {
@@ -1754,6 +2225,11 @@ impl StellarRpcClient {
         }
     }
 
+    fn mock_transaction_hash(envelope_xdr: &str) -> String {
+        use sha2::{Digest, Sha256};
+        hex::encode(Sha256::digest(envelope_xdr.as_bytes()))
+    }
+
     fn mock_transactions(limit: u32, ledger_sequence: u64) -> Vec<HorizonTransaction> {
         (0..limit)
             .map(|i| {
@@ -1787,6 +2263,7 @@ impl StellarRpcClient {
                     } else {
                         None
                     },
+                    result_xdr: None,
                 }
             })
             .collect()
@@ -1809,6 +2286,8 @@ impl StellarRpcClient {
                 account: Some(source_a),
                 into: Some(dest_a),
                 amount: None,
+                funder: None,
+                transaction_successful: None,
             },
             HorizonOperation {
                 id: format!("op_{}_1", sequence),
@@ -1821,6 +2300,8 @@ impl StellarRpcClient {
                 account: None,
                 into: None,
                 amount: Some("25.0000000".to_string()),
+                funder: None,
+                transaction_successful: None,
             },
             HorizonOperation {
                 id: format!("op_{}_2", sequence),
@@ -1832,6 +2313,8 @@ impl StellarRpcClient {
                 account: Some(source_b),
                 into: Some(dest_b),
                 amount: None,
+                funder: None,
+                transaction_successful: None,
             },
         ]
     }
@@ -1890,7 +2373,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_liquidity_pools_internal(limit, cursor))
+            .execute_with_retry(EndpointGroup::Markets, || {
+                self.fetch_liquidity_pools_internal(limit, cursor)
+            })
             .await;
 
         result.map_err(|e| {
@@ -1911,12 +2396,7 @@ impl StellarRpcClient {
         if let Some(c) = cursor {
             url.push_str(&format!("&cursor={}", c));
         }
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -1943,7 +2423,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_liquidity_pool_internal(pool_id))
+            .execute_with_retry(EndpointGroup::Markets, || {
+                self.fetch_liquidity_pool_internal(pool_id)
+            })
             .await;
 
         result.map_err(|e| {
@@ -1957,12 +2439,7 @@ impl StellarRpcClient {
         pool_id: &str,
     ) -> Result<HorizonLiquidityPool, RpcError> {
         let url = format!("{}/liquidity_pools/{}", self.horizon_url, pool_id);
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -1983,7 +2460,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_pool_trades_internal(pool_id, limit))
+            .execute_with_retry(EndpointGroup::Markets, || {
+                self.fetch_pool_trades_internal(pool_id, limit)
+            })
             .await;
 
         result.map_err(|e| {
@@ -2001,12 +2480,7 @@ impl StellarRpcClient {
             "{}/liquidity_pools/{}/trades?order=desc&limit={}",
             self.horizon_url, pool_id, limit
         );
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -2031,7 +2505,9 @@ impl StellarRpcClient {
         }
 
         let result = self
-            .execute_with_retry(|| self.fetch_assets_internal(limit, rating_sort))
+            .execute_with_retry(EndpointGroup::Markets, || {
+                self.fetch_assets_internal(limit, rating_sort)
+            })
             .await;
 
         result.map_err(|e| {
@@ -2051,12 +2527,112 @@ impl StellarRpcClient {
         } else {
             url.push_str("&order=desc");
         }
-        let response = self
-            .client
-            .get(&url)
-            .send()
+        let response = self.send(self.client.get(&url)).await?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        let horizon_response: HorizonResponse<HorizonAsset> = response
+            .json()
             .await
-            .map_err(|e| RpcError::NetworkError(e.to_string()))?;
+            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        Ok(horizon_response
+            .embedded
+            .map(|e| e.records)
+            .unwrap_or_default())
+    }
+
+    /// Fetch the assets issued by `issuer`, following the paging cursor up
+    /// to `max_records` (uses config default if None); fails with
+    /// [`MaxRecordsExceeded`] above the configured ceiling
+    pub async fn fetch_all_assets_by_issuer(
+        &self,
+        issuer: &str,
+        max_records: Option<u32>,
+    ) -> Result<PaginatedResult<HorizonAsset>> {
+        let max_records = self.resolve_max_records(max_records)?;
+
+        let mut all_assets = Vec::new();
+        let mut cursor: Option<String> = None;
+        let mut fetched = 0;
+
+        while fetched < max_records {
+            let limit = std::cmp::min(self.max_records_per_request, max_records - fetched);
+
+            let assets = self
+                .fetch_assets_by_issuer(issuer, limit, cursor.as_deref())
+                .await
+                .context("Failed to fetch issued assets page")?;
+            let page_len = assets.len() as u32;
+
+            fetched += page_len;
+
+            if let Some(last_asset) = assets.last() {
+                cursor = Some(last_asset.paging_token.clone());
+            }
+
+            all_assets.extend(assets);
+
+            // A short page is the last one
+            if page_len < limit || fetched >= max_records {
+                break;
+            }
+            if !self.mock_mode {
+                tokio::time::sleep(tokio::time::Duration::from_millis(self.pagination_delay_ms))
+                    .await;
+            }
+        }
+
+        Ok(PaginatedResult {
+            items: all_assets,
+            fetched,
+            capped: fetched >= max_records,
+            last_cursor: cursor,
+        })
+    }
+
+    /// Fetch one page of the assets issued by `issuer`, starting after
+    /// `cursor`
+    pub async fn fetch_assets_by_issuer(
+        &self,
+        issuer: &str,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<HorizonAsset>, RpcError> {
+        if self.mock_mode {
+            return Ok(Self::mock_assets(u32::MAX)
+                .into_iter()
+                .filter(|asset| asset.asset_issuer == issuer)
+                .skip_while(|asset| cursor.is_some_and(|c| asset.paging_token.as_str() <= c))
+                .take(limit as usize)
+                .collect());
+        }
+
+        let result = self
+            .execute_with_retry(EndpointGroup::Markets, || {
+                self.fetch_assets_by_issuer_internal(issuer, limit, cursor)
+            })
+            .await;
+
+        result.map_err(|e| {
+            metrics::record_rpc_error(e.error_type_label(), "stellar");
+            e
+        })
+    }
+
+    async fn fetch_assets_by_issuer_internal(
+        &self,
+        issuer: &str,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<HorizonAsset>, RpcError> {
+        let mut url = format!(
+            "{}/assets?asset_issuer={}&limit={}",
+            self.horizon_url, issuer, limit
+        );
+        if let Some(c) = cursor {
+            url.push_str(&format!("&cursor={}", c));
+        }
+        let response = self.send(self.client.get(&url)).await?;
         if !response.status().is_success() {
             return Err(map_response_error(response).await);
         }
@@ -2070,6 +2646,122 @@ impl StellarRpcClient {
             .unwrap_or_default())
     }
 
+    /// Fetch accounts holding a trustline to the given asset, following the
+    /// paging cursor up to `max_records` (uses config default if None);
+    /// fails with [`MaxRecordsExceeded`] above the configured ceiling
+    pub async fn fetch_all_asset_holders(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        max_records: Option<u32>,
+    ) -> Result<PaginatedResult<HorizonAccount>> {
+        let max_records = self.resolve_max_records(max_records)?;
+        if self.mock_mode {
+            let holders = Self::mock_asset_holders(asset_code, asset_issuer, max_records);
+            return Ok(PaginatedResult {
+                fetched: holders.len() as u32,
+                capped: holders.len() as u32 >= max_records,
+                last_cursor: holders.last().map(|h| h.id.clone()),
+                items: holders,
+            });
+        }
+
+        let mut all_holders = Vec::new();
+        let mut cursor: Option<String> = None;
+        let mut fetched = 0;
+
+        while fetched < max_records {
+            let limit = std::cmp::min(self.max_records_per_request, max_records - fetched);
+
+            let holders = self
+                .fetch_asset_holders(asset_code, asset_issuer, limit, cursor.as_deref())
+                .await
+                .context("Failed to fetch asset holders page")?;
+            let page_len = holders.len() as u32;
+
+            fetched += page_len;
+
+            // Horizon pages accounts by account id
+            if let Some(last_holder) = holders.last() {
+                cursor = Some(last_holder.id.clone());
+            }
+
+            all_holders.extend(holders);
+
+            // A short page is the last one
+            if page_len < limit || fetched >= max_records {
+                break;
+            }
+            tokio::time::sleep(tokio::time::Duration::from_millis(self.pagination_delay_ms)).await;
+        }
+
+        info!(
+            "Fetched {} holders of {}:{}",
+            all_holders.len(),
+            asset_code,
+            asset_issuer
+        );
+        Ok(PaginatedResult {
+            items: all_holders,
+            fetched,
+            capped: fetched >= max_records,
+            last_cursor: cursor,
+        })
+    }
+
+    /// Fetch one page of accounts holding a trustline to the given asset,
+    /// starting after `cursor`
+    pub async fn fetch_asset_holders(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<HorizonAccount>, RpcError> {
+        if self.mock_mode {
+            return Ok(Self::mock_asset_holders(asset_code, asset_issuer, limit));
+        }
+
+        let result = self
+            .execute_with_retry(EndpointGroup::Markets, || {
+                self.fetch_asset_holders_internal(asset_code, asset_issuer, limit, cursor)
+            })
+            .await;
+
+        result.map_err(|e| {
+            metrics::record_rpc_error(e.error_type_label(), "stellar");
+            e
+        })
+    }
+
+    async fn fetch_asset_holders_internal(
+        &self,
+        asset_code: &str,
+        asset_issuer: &str,
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<Vec<HorizonAccount>, RpcError> {
+        let mut url = format!(
+            "{}/accounts?asset={}:{}&limit={}",
+            self.horizon_url, asset_code, asset_issuer, limit
+        );
+        if let Some(c) = cursor {
+            url.push_str(&format!("&cursor={}", c));
+        }
+        let response = self.send(self.client.get(&url)).await?;
+        if !response.status().is_success() {
+            return Err(map_response_error(response).await);
+        }
+        let horizon_response: HorizonResponse<HorizonAccount> = response
+            .json()
+            .await
+            .map_err(|e| RpcError::ParseError(e.to_string()))?;
+        Ok(horizon_response
+            .embedded
+            .map(|e| e.records)
+            .unwrap_or_default())
+    }
+
     // ============================================================================
     // Liquidity Pool Mock Data
     // ============================================================================
@@ -2212,10 +2904,49 @@ impl StellarRpcClient {
                     auth_immutable: false,
                     auth_clawback_enabled: false,
                 },
+                paging_token: format!("{}_{}_credit_alphanum4", code, issuer),
             })
         }
         assets
     }
+
+    /// Mock holder distributions per asset: USDC evenly spread, AQUA held
+    /// mostly by one whale, yXLM with only a handful of holders and
+    /// everything else on a long tail
+    fn mock_asset_holders(asset_code: &str, asset_issuer: &str, limit: u32) -> Vec<HorizonAccount> {
+        let holders: u32 = match asset_code {
+            "yXLM" => 5,
+            _ => 50,
+        };
+
+        (0..holders.min(limit))
+            .map(|i| {
+                let balance = match asset_code {
+                    "USDC" => 1_000.0,
+                    "AQUA" if i == 0 => 1_000_000.0,
+                    "AQUA" => 10.0,
+                    _ => 10_000.0 / f64::from(i + 1),
+                };
+                HorizonAccount {
+                    id: format!("GHOLDER{:03}{}", i, asset_code),
+                    balances: vec![
+                        HorizonAccountBalance {
+                            balance: "100.0000000".to_string(),
+                            asset_type: "native".to_string(),
+                            asset_code: None,
+                            asset_issuer: None,
+                        },
+                        HorizonAccountBalance {
+                            balance: format!("{:.7}", balance),
+                            asset_type: "credit_alphanum4".to_string(),
+                            asset_code: Some(asset_code.to_string()),
+                            asset_issuer: Some(asset_issuer.to_string()),
+                        },
+                    ],
+                }
+            })
+            .collect()
+    }
 }
 
 // ============================================================================
@@ -2353,6 +3084,7 @@ mod tests {
         // Verify default pagination config is loaded
         assert_eq!(client.max_records_per_request, 200);
         assert_eq!(client.max_total_records, 10000);
+        assert_eq!(client.max_records_ceiling, DEFAULT_MAX_RECORDS_CEILING);
         assert_eq!(client.pagination_delay_ms, 100);
     }
 
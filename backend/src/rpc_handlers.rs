@@ -2,22 +2,34 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::rpc::{Asset, StellarRpcClient};
+use crate::api::pagination::{PagedResource, PaginationConfig};
+use crate::muxed::{is_valid_account_id, parse_muxed_address};
+use crate::rpc::{
+    Asset, HealthResponse, LatencySummary, OrderBook, OrderBookEntry, StellarRpcClient,
+};
+use crate::services::pair_registry::PairRegistry;
 
 #[derive(Debug, Deserialize)]
 pub struct PaginationQuery {
-    #[serde(default = "default_limit")]
-    pub limit: u32,
+    #[serde(default)]
+    pub limit: Option<i64>,
     pub cursor: Option<String>,
 }
 
-fn default_limit() -> u32 {
-    20
+impl PaginationQuery {
+    /// Requested page size within the configured RPC record limits
+    pub fn page_limit(&self, pagination: &PaginationConfig) -> u32 {
+        rpc_page_limit(pagination, self.limit)
+    }
+}
+
+fn rpc_page_limit(pagination: &PaginationConfig, limit: Option<i64>) -> u32 {
+    pagination.page_limit(PagedResource::RpcRecords, limit) as u32
 }
 
 #[derive(Debug, Deserialize)]
@@ -28,8 +40,8 @@ pub struct OrderBookQuery {
     pub buying_asset_type: String,
     pub buying_asset_code: Option<String>,
     pub buying_asset_issuer: Option<String>,
-    #[serde(default = "default_limit")]
-    pub limit: u32,
+    #[serde(default)]
+    pub limit: Option<i64>,
 }
 
 #[derive(Debug, Serialize)]
@@ -37,13 +49,60 @@ pub struct ErrorResponse {
     pub error: String,
 }
 
+fn bad_request(error: String) -> (StatusCode, Json<ErrorResponse>) {
+    (StatusCode::BAD_REQUEST, Json(ErrorResponse { error }))
+}
+
+/// Account to query Horizon with, or a 400 for anything other than a
+/// checksummed G-address or M-address. Horizon indexes payments by base
+/// account, so an M-address resolves to the G-address it multiplexes.
+fn horizon_account(account_id: &str) -> Result<String, (StatusCode, Json<ErrorResponse>)> {
+    let account_id = account_id.trim();
+    if is_valid_account_id(account_id) {
+        return Ok(account_id.to_string());
+    }
+    parse_muxed_address(account_id)
+        .and_then(|info| info.base_account)
+        .ok_or_else(|| {
+            bad_request(format!(
+                "Invalid Stellar account '{}': expected a G-address or M-address with a valid checksum",
+                account_id
+            ))
+        })
+}
+
+/// 400 unless `issuer` is absent or a checksummed G-address
+fn validate_issuer(
+    field: &str,
+    issuer: Option<&str>,
+) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+    match issuer {
+        Some(issuer) if !is_valid_account_id(issuer) => Err(bad_request(format!(
+            "Invalid {} '{}': expected a G-address with a valid checksum",
+            field, issuer
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// RPC health with the latency of recent requests
+#[derive(Debug, Serialize)]
+pub struct RpcHealthReport {
+    #[serde(flatten)]
+    pub health: HealthResponse,
+    pub recent_latency: LatencySummary,
+}
+
 /// Health check for Stellar RPC
 #[tracing::instrument(skip(client))]
 pub async fn rpc_health_check(
     State(client): State<Arc<StellarRpcClient>>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
     match client.check_health().await {
-        Ok(health) => Ok(Json(health)),
+        Ok(health) => Ok(Json(RpcHealthReport {
+            health,
+            recent_latency: client.recent_latency(),
+        })),
         Err(e) => Err((
             StatusCode::SERVICE_UNAVAILABLE,
             Json(ErrorResponse {
@@ -70,13 +129,17 @@ pub async fn get_latest_ledger(
 }
 
 /// Get recent payments
-#[tracing::instrument(skip(client))]
+#[tracing::instrument(skip(client, pagination))]
 pub async fn get_payments(
     State(client): State<Arc<StellarRpcClient>>,
+    Extension(pagination): Extension<PaginationConfig>,
     Query(params): Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
     let cursor = params.cursor.as_deref();
-    match client.fetch_payments(params.limit, cursor).await {
+    match client
+        .fetch_payments(params.page_limit(&pagination), cursor)
+        .await
+    {
         Ok(payments) => Ok(Json(payments)),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -88,14 +151,16 @@ pub async fn get_payments(
 }
 
 /// Get payments for a specific account
-#[tracing::instrument(skip(client))]
+#[tracing::instrument(skip(client, pagination))]
 pub async fn get_account_payments(
     State(client): State<Arc<StellarRpcClient>>,
+    Extension(pagination): Extension<PaginationConfig>,
     Path(account_id): Path<String>,
     Query(params): Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    let account_id = horizon_account(&account_id)?;
     match client
-        .fetch_account_payments(&account_id, params.limit)
+        .fetch_account_payments(&account_id, params.page_limit(&pagination))
         .await
     {
         Ok(payments) => Ok(Json(payments)),
@@ -109,13 +174,17 @@ pub async fn get_account_payments(
 }
 
 /// Get recent trades
-#[tracing::instrument(skip(client))]
+#[tracing::instrument(skip(client, pagination))]
 pub async fn get_trades(
     State(client): State<Arc<StellarRpcClient>>,
+    Extension(pagination): Extension<PaginationConfig>,
     Query(params): Query<PaginationQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
     let cursor = params.cursor.as_deref();
-    match client.fetch_trades(params.limit, cursor).await {
+    match client
+        .fetch_trades(params.page_limit(&pagination), cursor)
+        .await
+    {
         Ok(trades) => Ok(Json(trades)),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -126,12 +195,98 @@ pub async fn get_trades(
     }
 }
 
+/// Order book figures derived from its displayed offers.
+///
+/// Horizon reports bid amounts in the counter asset and ask amounts in the
+/// base asset; depths here are both in the base asset.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OrderBookSummary {
+    /// Midpoint of the best bid and ask; `None` if either side is empty
+    pub mid_price: Option<f64>,
+    /// `(bid_depth - ask_depth) / total_depth`, from -1 (all asks) to 1 (all
+    /// bids); `None` if either side is empty
+    pub imbalance: Option<f64>,
+    pub bid_depth: f64,
+    pub ask_depth: f64,
+    pub total_depth: f64,
+}
+
+fn entry_price(entry: &OrderBookEntry) -> Option<f64> {
+    let price = if entry.price_r.d != 0 {
+        entry.price_r.n as f64 / entry.price_r.d as f64
+    } else {
+        entry.price.parse().ok()?
+    };
+    (price > 0.0).then_some(price)
+}
+
+fn entry_amount(entry: &OrderBookEntry) -> f64 {
+    entry.amount.parse().unwrap_or(0.0)
+}
+
+impl OrderBookSummary {
+    pub fn from_book(book: &OrderBook) -> Self {
+        let bid_depth: f64 = book
+            .bids
+            .iter()
+            .filter_map(|bid| Some(entry_amount(bid) / entry_price(bid)?))
+            .sum();
+        let ask_depth: f64 = book.asks.iter().map(entry_amount).sum();
+        let total_depth = bid_depth + ask_depth;
+
+        let best_bid = book.bids.first().and_then(entry_price);
+        let best_ask = book.asks.first().and_then(entry_price);
+        let mid_price = best_bid.zip(best_ask).map(|(bid, ask)| (bid + ask) / 2.0);
+        let imbalance = (!book.bids.is_empty() && !book.asks.is_empty() && total_depth > 0.0)
+            .then(|| (bid_depth - ask_depth) / total_depth);
+
+        Self {
+            mid_price,
+            imbalance,
+            bid_depth,
+            ask_depth,
+            total_depth,
+        }
+    }
+}
+
+/// Order book with its derived figures
+#[derive(Debug, Serialize)]
+pub struct OrderBookResponse {
+    #[serde(flatten)]
+    pub order_book: OrderBook,
+    #[serde(flatten)]
+    pub summary: OrderBookSummary,
+}
+
+/// `XLM` or `CODE:ISSUER`, as the pair registry names assets
+fn registry_asset_id(asset: &Asset) -> String {
+    if asset.asset_type == "native" {
+        return "XLM".to_string();
+    }
+    format!(
+        "{}:{}",
+        asset.asset_code.as_deref().unwrap_or_default(),
+        asset.asset_issuer.as_deref().unwrap_or_default()
+    )
+}
+
 /// Get order book for a trading pair
-#[tracing::instrument(skip(client))]
+///
+/// Pairs outside the configured [`PairRegistry`] are rejected with 400.
+#[tracing::instrument(skip(client, pagination, pair_registry))]
 pub async fn get_order_book(
     State(client): State<Arc<StellarRpcClient>>,
+    Extension(pagination): Extension<PaginationConfig>,
+    pair_registry: Option<Extension<Arc<PairRegistry>>>,
     Query(params): Query<OrderBookQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+    validate_issuer(
+        "selling_asset_issuer",
+        params.selling_asset_issuer.as_deref(),
+    )?;
+    validate_issuer("buying_asset_issuer", params.buying_asset_issuer.as_deref())?;
+
     let selling_asset = Asset {
         asset_type: params.selling_asset_type,
         asset_code: params.selling_asset_code,
@@ -144,11 +299,27 @@ pub async fn get_order_book(
         asset_issuer: params.buying_asset_issuer,
     };
 
+    if let Some(Extension(registry)) = pair_registry {
+        registry
+            .check(
+                &registry_asset_id(&selling_asset),
+                &registry_asset_id(&buying_asset),
+            )
+            .map_err(|e| bad_request(e.to_string()))?;
+    }
+
     match client
-        .fetch_order_book(&selling_asset, &buying_asset, params.limit)
+        .fetch_order_book(
+            &selling_asset,
+            &buying_asset,
+            rpc_page_limit(&pagination, params.limit),
+        )
         .await
     {
-        Ok(order_book) => Ok(Json(order_book)),
+        Ok(order_book) => Ok(Json(OrderBookResponse {
+            summary: OrderBookSummary::from_book(&order_book),
+            order_book,
+        })),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
@@ -157,3 +328,128 @@ pub async fn get_order_book(
         )),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn asset(code: &str) -> Asset {
+        Asset {
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some(code.to_string()),
+            asset_issuer: Some("GISSUER".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mock_order_book_summary() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let book = client
+            .fetch_order_book(&asset("USDC"), &asset("EURC"), 10)
+            .await
+            .unwrap();
+        let summary = OrderBookSummary::from_book(&book);
+
+        // Best bid 0.995, best ask 1.005
+        assert!((summary.mid_price.unwrap() - 1.0).abs() < 1e-9);
+        assert!((summary.ask_depth - 8700.0).abs() < 1e-9);
+        let bid_depth = 1000.0 / 0.995 + 2500.0 / 0.99 + 5000.0 / 0.985;
+        assert!((summary.bid_depth - bid_depth).abs() < 1e-6);
+        assert!((summary.total_depth - (bid_depth + 8700.0)).abs() < 1e-6);
+        let imbalance = (bid_depth - 8700.0) / (bid_depth + 8700.0);
+        assert!((summary.imbalance.unwrap() - imbalance).abs() < 1e-9);
+        assert!(summary.imbalance.unwrap() < 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_one_sided_order_book_summary() {
+        let client = StellarRpcClient::new_with_defaults(true);
+        let mut book = client
+            .fetch_order_book(&asset("USDC"), &asset("EURC"), 10)
+            .await
+            .unwrap();
+        book.bids.clear();
+        let summary = OrderBookSummary::from_book(&book);
+
+        assert_eq!(summary.mid_price, None);
+        assert_eq!(summary.imbalance, None);
+        assert_eq!(summary.bid_depth, 0.0);
+        assert!((summary.total_depth - 8700.0).abs() < 1e-9);
+    }
+
+    const ACCOUNT: &str = "GA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJVSGZ";
+
+    async fn account_payments(account_id: &str) -> Result<(), StatusCode> {
+        let client = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let params = PaginationQuery {
+            limit: None,
+            cursor: None,
+        };
+        get_account_payments(State(client), Path(account_id.to_string()), Query(params))
+            .await
+            .map(|_| ())
+            .map_err(|(status, _)| status)
+    }
+
+    #[tokio::test]
+    async fn test_account_payments_accepts_valid_account() {
+        assert_eq!(account_payments(ACCOUNT).await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn test_account_payments_rejects_bad_checksum() {
+        let corrupted = format!("{}Y", &ACCOUNT[..ACCOUNT.len() - 1]);
+        assert_eq!(
+            account_payments(&corrupted).await,
+            Err(StatusCode::BAD_REQUEST)
+        );
+        assert_eq!(
+            account_payments("not-an-account").await,
+            Err(StatusCode::BAD_REQUEST)
+        );
+
+        let (_, Json(body)) = horizon_account(&corrupted).unwrap_err();
+        assert!(body.error.contains("valid checksum"), "{}", body.error);
+    }
+
+    async fn order_book(registry: &str, buying_code: &str) -> Result<(), StatusCode> {
+        let client = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let registry = Arc::new(PairRegistry::parse(registry).unwrap());
+        let params = OrderBookQuery {
+            selling_asset_type: "native".to_string(),
+            selling_asset_code: None,
+            selling_asset_issuer: None,
+            buying_asset_type: "credit_alphanum4".to_string(),
+            buying_asset_code: Some(buying_code.to_string()),
+            buying_asset_issuer: Some(ACCOUNT.to_string()),
+            limit: None,
+        };
+        get_order_book(
+            State(client),
+            Extension(PaginationConfig::default()),
+            Some(Extension(registry)),
+            Query(params),
+        )
+        .await
+        .map(|_| ())
+        .map_err(|(status, _)| status)
+    }
+
+    #[tokio::test]
+    async fn test_order_book_serves_registered_pair_only() {
+        let registry = format!("USDC:{}/XLM", ACCOUNT);
+        assert_eq!(order_book(&registry, "USDC").await, Ok(()));
+        assert_eq!(order_book("XLM/USDC", "USDC").await, Ok(()));
+        assert_eq!(
+            order_book(&registry, "EURC").await,
+            Err(StatusCode::BAD_REQUEST)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_account_payments_resolves_muxed_account() {
+        let muxed = "MA7QYNF7SOWQ3GLR2BGMZEHXAVIRZA4KVWLTJJFC7MGXUA74P7UJUAAAAAAAAAAE2JUG6";
+        assert_eq!(account_payments(muxed).await, Ok(()));
+        assert_eq!(horizon_account(muxed).unwrap(), ACCOUNT);
+    }
+}
@@ -1,10 +1,12 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
-use sqlx::{Pool, Sqlite};
+use sqlx::{Executor, Pool, Sqlite};
+use std::collections::HashSet;
 use std::sync::Arc;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
+use crate::models::timestamp::{format_timestamp, parse_ingested_timestamp};
 use crate::rpc::{HorizonOperation, StellarRpcClient};
 
 #[derive(Debug, Clone, Serialize, sqlx::FromRow)]
@@ -18,6 +20,38 @@ pub struct AccountMergeEvent {
     pub created_at: DateTime<Utc>,
 }
 
+/// An account opened by a `create_account` operation
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AccountCreation {
+    pub operation_id: String,
+    pub transaction_hash: String,
+    pub ledger_sequence: i64,
+    pub account: String,
+    pub funder: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An account re-created at the address of an account that was merged away
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct AccountChurnEvent {
+    pub account: String,
+    pub merge_operation_id: String,
+    pub merge_ledger_sequence: i64,
+    /// Account the original balance was merged into
+    pub merged_into: String,
+    pub recreate_operation_id: String,
+    pub recreate_ledger_sequence: i64,
+    pub funder: Option<String>,
+    pub recreated_at: DateTime<Utc>,
+}
+
+/// Merges and account creations found in one ledger
+#[derive(Debug, Clone, Default)]
+pub struct LedgerAccountActivity {
+    pub merges: Vec<AccountMergeEvent>,
+    pub creations: Vec<AccountCreation>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct AccountMergeStats {
     pub total_merges: i64,
@@ -44,41 +78,161 @@ impl AccountMergeDetector {
     }
 
     /// Fetches operations for a ledger, extracts account merges, and persists merge events.
+    /// Accounts re-created after an earlier merge are recorded as churn.
     pub async fn process_ledger_operations(&self, ledger_sequence: u64) -> Result<u64> {
-        let operations = self
-            .rpc_client
-            .fetch_operations_for_ledger(ledger_sequence)
-            .await?;
-
+        let activity = self.detect_ledger_activity(ledger_sequence).await?;
         let mut inserted = 0_u64;
 
-        for operation in operations
-            .iter()
-            .filter(|op| op.operation_type == "account_merge")
-        {
-            if self
-                .persist_merge_from_operation(ledger_sequence, operation)
-                .await?
-            {
+        for event in &activity.merges {
+            if Self::insert_merge_event(&self.pool, event).await? {
                 inserted += 1;
             }
         }
 
+        let mut churned = 0_u64;
+        for creation in &activity.creations {
+            if Self::record_churn(&self.pool, creation).await? {
+                churned += 1;
+            }
+        }
+
         if inserted > 0 {
             info!(
                 "Detected and stored {} account merge operations for ledger {}",
                 inserted, ledger_sequence
             );
         }
+        if churned > 0 {
+            info!(
+                "Detected {} re-created merged accounts in ledger {}",
+                churned, ledger_sequence
+            );
+        }
 
         Ok(inserted)
     }
 
-    async fn persist_merge_from_operation(
+    /// Fetches operations for a ledger and resolves its account merge events without persisting them.
+    pub async fn detect_ledger_merges(
+        &self,
+        ledger_sequence: u64,
+    ) -> Result<Vec<AccountMergeEvent>> {
+        Ok(self.detect_ledger_activity(ledger_sequence).await?.merges)
+    }
+
+    /// Fetches operations for a ledger and resolves its merges and account creations
+    /// without persisting them. Operations of failed transactions are skipped, since
+    /// nothing they did took effect.
+    pub async fn detect_ledger_activity(
+        &self,
+        ledger_sequence: u64,
+    ) -> Result<LedgerAccountActivity> {
+        let operations = self
+            .rpc_client
+            .fetch_operations_for_ledger(ledger_sequence)
+            .await?;
+        let failed_transactions = self.failed_transactions(ledger_sequence, &operations).await;
+        let operations = Self::successful_operations(operations, &failed_transactions);
+
+        let mut merges = Vec::new();
+        for operation in operations
+            .iter()
+            .filter(|op| op.operation_type == "account_merge")
+        {
+            if let Some(event) = self.merge_from_operation(ledger_sequence, operation).await {
+                merges.push(event);
+            }
+        }
+
+        Ok(LedgerAccountActivity {
+            merges,
+            creations: Self::account_creations(ledger_sequence, &operations),
+        })
+    }
+
+    /// Hashes of the ledger's failed transactions. Only fetched when an account merge
+    /// does not carry its transaction's status itself.
+    async fn failed_transactions(
+        &self,
+        ledger_sequence: u64,
+        operations: &[HorizonOperation],
+    ) -> HashSet<String> {
+        let status_unknown = operations
+            .iter()
+            .any(|op| op.operation_type == "account_merge" && op.transaction_successful.is_none());
+        if !status_unknown {
+            return HashSet::new();
+        }
+
+        match self
+            .rpc_client
+            .fetch_transactions_for_ledger(ledger_sequence)
+            .await
+        {
+            Ok(transactions) => transactions
+                .into_iter()
+                .filter(|tx| !tx.successful)
+                .map(|tx| tx.hash)
+                .collect(),
+            Err(error) => {
+                // Horizon leaves failed transactions out of ledger operations by default
+                warn!(
+                    "Failed to fetch transactions for ledger {} while checking merge status: {}",
+                    ledger_sequence, error
+                );
+                HashSet::new()
+            }
+        }
+    }
+
+    /// Drops operations whose transaction failed, by their own status or by
+    /// `failed_transactions`.
+    pub fn successful_operations(
+        operations: Vec<HorizonOperation>,
+        failed_transactions: &HashSet<String>,
+    ) -> Vec<HorizonOperation> {
+        operations
+            .into_iter()
+            .filter(|op| {
+                let failed = op.transaction_successful == Some(false)
+                    || failed_transactions.contains(&op.transaction_hash);
+                if failed {
+                    debug!(
+                        "Skipping {} operation {} of failed transaction {}",
+                        op.operation_type, op.id, op.transaction_hash
+                    );
+                }
+                !failed
+            })
+            .collect()
+    }
+
+    /// Extracts the accounts opened by `create_account` operations.
+    pub fn account_creations(
+        ledger_sequence: u64,
+        operations: &[HorizonOperation],
+    ) -> Vec<AccountCreation> {
+        operations
+            .iter()
+            .filter(|op| op.operation_type == "create_account")
+            .filter_map(|op| {
+                Some(AccountCreation {
+                    operation_id: op.id.clone(),
+                    transaction_hash: op.transaction_hash.clone(),
+                    ledger_sequence: ledger_sequence as i64,
+                    account: op.account.clone()?,
+                    funder: op.funder.clone(),
+                    created_at: parse_ingested_timestamp(&op.created_at, "account_creation")?,
+                })
+            })
+            .collect()
+    }
+
+    async fn merge_from_operation(
         &self,
         ledger_sequence: u64,
         operation: &HorizonOperation,
-    ) -> Result<bool> {
+    ) -> Option<AccountMergeEvent> {
         let destination_account = match operation.into.clone() {
             Some(account) => account,
             None => {
@@ -86,7 +240,7 @@ impl AccountMergeDetector {
                     "Skipping account_merge operation {} without destination account",
                     operation.id
                 );
-                return Ok(false);
+                return None;
             }
         };
 
@@ -99,11 +253,9 @@ impl AccountMergeDetector {
             .resolve_merged_balance(&operation.id, &destination_account)
             .await;
 
-        let created_at = DateTime::parse_from_rfc3339(&operation.created_at)
-            .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or_else(|_| Utc::now());
+        let created_at = parse_ingested_timestamp(&operation.created_at, "account_merge")?;
 
-        let event = AccountMergeEvent {
+        Some(AccountMergeEvent {
             operation_id: operation.id.clone(),
             transaction_hash: operation.transaction_hash.clone(),
             ledger_sequence: ledger_sequence as i64,
@@ -111,9 +263,7 @@ impl AccountMergeDetector {
             destination_account,
             merged_balance,
             created_at,
-        };
-
-        self.persist_merge_event(&event).await
+        })
     }
 
     async fn resolve_merged_balance(&self, operation_id: &str, destination: &str) -> f64 {
@@ -141,7 +291,11 @@ impl AccountMergeDetector {
         0.0
     }
 
-    async fn persist_merge_event(&self, event: &AccountMergeEvent) -> Result<bool> {
+    /// Persists a merge event using the given executor. Returns whether a new row was inserted.
+    pub async fn insert_merge_event<'e, E>(executor: E, event: &AccountMergeEvent) -> Result<bool>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
         let result = sqlx::query(
             r#"
             INSERT INTO account_merges (
@@ -163,13 +317,72 @@ impl AccountMergeDetector {
         .bind(&event.source_account)
         .bind(&event.destination_account)
         .bind(event.merged_balance)
-        .bind(event.created_at)
-        .execute(&self.pool)
+        .bind(format_timestamp(&event.created_at))
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Records churn if `creation` re-opens an account merged by an earlier operation,
+    /// correlating against the most recent such merge. Merges from the same ledger must
+    /// already be stored. Returns whether a new churn row was inserted.
+    pub async fn record_churn<'e, E>(executor: E, creation: &AccountCreation) -> Result<bool>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO account_churn (
+                recreate_operation_id,
+                account,
+                merge_operation_id,
+                merge_ledger_sequence,
+                merged_into,
+                recreate_ledger_sequence,
+                funder,
+                recreated_at
+            )
+            SELECT $1, source_account, operation_id, ledger_sequence, destination_account, $2, $3, $4
+            FROM account_merges
+            WHERE source_account = $5
+              AND (ledger_sequence < $6 OR (ledger_sequence = $7 AND operation_id < $8))
+            ORDER BY ledger_sequence DESC, operation_id DESC
+            LIMIT 1
+            ON CONFLICT (recreate_operation_id) DO NOTHING
+            "#,
+        )
+        .bind(&creation.operation_id)
+        .bind(creation.ledger_sequence)
+        .bind(&creation.funder)
+        .bind(format_timestamp(&creation.created_at))
+        .bind(&creation.account)
+        .bind(creation.ledger_sequence)
+        .bind(creation.ledger_sequence)
+        .bind(&creation.operation_id)
+        .execute(executor)
         .await?;
 
         Ok(result.rows_affected() > 0)
     }
 
+    pub async fn get_recent_churn(&self, limit: i64) -> Result<Vec<AccountChurnEvent>> {
+        let rows = sqlx::query_as::<_, AccountChurnEvent>(
+            r#"
+            SELECT account, merge_operation_id, merge_ledger_sequence, merged_into,
+                   recreate_operation_id, recreate_ledger_sequence, funder, recreated_at
+            FROM account_churn
+            ORDER BY recreated_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows)
+    }
+
     pub async fn get_recent_merges(&self, limit: i64) -> Result<Vec<AccountMergeEvent>> {
         let rows = sqlx::query_as::<_, AccountMergeEvent>(
             r#"
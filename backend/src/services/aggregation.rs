@@ -6,8 +6,10 @@ use tracing::{error, info, warn};
 use uuid::Uuid;
 
 use crate::database::Database;
-use crate::models::corridor::CorridorMetrics;
-use crate::services::analytics::compute_metrics_from_payments;
+use crate::models::corridor::{CorridorMetrics, PaymentRecord};
+use crate::services::analytics::{
+    compute_latency_percentiles_from_payments, compute_metrics_from_payments,
+};
 
 const MAX_RETRIES: i32 = 3;
 const RETRY_DELAY_SECS: u64 = 60;
@@ -115,12 +117,20 @@ impl AggregationService {
         );
 
         // Fetch payments from the time window
-        let payments = self
+        let mut payments = self
             .db
             .fetch_payments_by_timerange(start_time, end_time, self.config.batch_size)
             .await
             .context("Failed to fetch payments for aggregation")?;
 
+        // Failed path payments count against corridor success rates
+        let failed_path_payments = self
+            .db
+            .fetch_path_payment_failures_by_timerange(start_time, end_time, self.config.batch_size)
+            .await
+            .context("Failed to fetch path payment failures for aggregation")?;
+        payments.extend(failed_path_payments);
+
         if payments.is_empty() {
             info!("No payments found in time window");
             return Ok(0);
@@ -130,6 +140,7 @@ impl AggregationService {
 
         // Compute metrics for each corridor
         let corridor_metrics = compute_metrics_from_payments(&payments);
+        self.store_latency_percentiles(&payments).await?;
 
         if corridor_metrics.is_empty() {
             info!("No corridor metrics computed");
@@ -233,6 +244,17 @@ impl AggregationService {
         Ok(count)
     }
 
+    /// Store settlement latency percentiles of the corridors in this window
+    async fn store_latency_percentiles(&self, payments: &[PaymentRecord]) -> Result<()> {
+        for (corridor_key, percentiles) in compute_latency_percentiles_from_payments(payments) {
+            self.db
+                .upsert_corridor_latency_percentiles(&corridor_key, &percentiles)
+                .await
+                .context("Failed to store corridor latency percentiles")?;
+        }
+        Ok(())
+    }
+
     /// Truncate datetime to hour boundary
     fn truncate_to_hour(&self, dt: DateTime<Utc>) -> DateTime<Utc> {
         dt.with_minute(0)
@@ -1,4 +1,6 @@
-use crate::models::corridor::{compute_median, CorridorMetrics, PaymentRecord};
+use crate::models::corridor::{
+    compute_latency_percentiles, compute_median, CorridorMetrics, LatencyPercentiles, PaymentRecord,
+};
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -235,6 +237,27 @@ pub fn compute_metrics_by_window(
     compute_metrics_from_payments(&filtered)
 }
 
+/// Settlement latency percentiles per corridor key, from the successful
+/// payments that carry timing data. Corridors without samples are left out.
+pub fn compute_latency_percentiles_from_payments(
+    payments: &[PaymentRecord],
+) -> HashMap<String, LatencyPercentiles> {
+    let mut samples: HashMap<String, Vec<i64>> = HashMap::new();
+    for payment in payments.iter().filter(|p| p.successful) {
+        if let Some(latency_ms) = payment.settlement_latency_ms().filter(|ms| *ms >= 0) {
+            samples
+                .entry(payment.get_corridor().to_string_key())
+                .or_default()
+                .push(latency_ms);
+        }
+    }
+
+    samples
+        .into_iter()
+        .filter_map(|(key, mut values)| Some((key, compute_latency_percentiles(&mut values)?)))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -468,4 +491,39 @@ mod tests {
         assert_eq!(m.avg_settlement_latency_ms, Some(2000)); // (1000 + 3000) / 2
         assert_eq!(m.median_settlement_latency_ms, Some(2000)); // Median of [1000, 3000]
     }
+
+    #[test]
+    fn test_latency_percentiles_from_seeded_payments() {
+        let now = Utc::now();
+        let mut payments: Vec<PaymentRecord> = (1..=20)
+            .map(|i| create_test_payment_with_latency("USDC", "EURC", 10.0, true, now, i * 250))
+            .collect();
+        // Failed and negative-latency payments are not samples
+        payments.push(create_test_payment_with_latency(
+            "USDC", "EURC", 10.0, false, now, 999_999,
+        ));
+        payments.push(create_test_payment_with_latency(
+            "USDC", "EURC", 10.0, true, now, -500,
+        ));
+        payments.push(create_test_payment_with_latency(
+            "BTC", "ETH", 1.0, true, now, 3000,
+        ));
+        payments.push(create_test_payment_record("XLM", "USDC", 5.0, true, now));
+
+        let percentiles = compute_latency_percentiles_from_payments(&payments);
+        assert_eq!(percentiles.len(), 2);
+
+        let usdc_eurc = payments[0].get_corridor().to_string_key();
+        let usdc = &percentiles[&usdc_eurc];
+        assert_eq!(usdc.sample_count, 20);
+        assert_eq!(usdc.p50_ms, Some(2500));
+        assert_eq!(usdc.p90_ms, Some(4500));
+        assert_eq!(usdc.p99_ms, None);
+
+        let btc_eth = payments[22].get_corridor().to_string_key();
+        let btc = &percentiles[&btc_eth];
+        assert_eq!(btc.sample_count, 1);
+        assert_eq!(btc.p50_ms, Some(3000));
+        assert_eq!(btc.p90_ms, None);
+    }
 }
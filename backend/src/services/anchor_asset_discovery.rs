@@ -0,0 +1,140 @@
+//! Anchor asset discovery.
+//!
+//! Anchors' asset lists are maintained by hand through the anchor assets
+//! endpoint, so assets an anchor issues can be missing. Discovery asks Horizon
+//! for every asset issued by the anchor's `stellar_account` and adds the ones
+//! not yet registered, leaving manually added assets untouched. It runs for
+//! every anchor as the `anchor-asset-discovery` job.
+
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::models::AnchorCursor;
+use crate::rpc::StellarRpcClient;
+
+/// Anchors read per page when discovering for every anchor
+const ANCHOR_PAGE_SIZE: i64 = 100;
+
+/// Outcome of discovering one anchor's assets
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetDiscovery {
+    /// Assets Horizon reports the anchor's account as issuing
+    pub issued: usize,
+    /// `CODE:ISSUER` of each asset newly added to the anchor
+    pub added: Vec<String>,
+}
+
+/// Outcome of one discovery run over every anchor
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetDiscoveryStats {
+    pub checked: usize,
+    pub added: usize,
+    pub errors: usize,
+}
+
+pub struct AnchorAssetDiscovery {
+    db: Arc<Database>,
+    rpc_client: Arc<StellarRpcClient>,
+}
+
+impl AnchorAssetDiscovery {
+    pub fn new(db: Arc<Database>, rpc_client: Arc<StellarRpcClient>) -> Self {
+        Self { db, rpc_client }
+    }
+
+    /// Add the assets issued by the anchor's account that are not yet registered
+    pub async fn discover_for_anchor(&self, anchor_id: Uuid) -> Result<AssetDiscovery> {
+        let anchor = self
+            .db
+            .get_anchor_by_id(anchor_id)
+            .await?
+            .ok_or_else(|| anyhow!("Anchor {} not found", anchor_id))?;
+
+        let page = self
+            .rpc_client
+            .fetch_all_assets_by_issuer(&anchor.stellar_account, None)
+            .await?;
+        if page.capped {
+            warn!(
+                "Anchor {} issues more than {} assets; only those were discovered",
+                anchor_id, page.fetched
+            );
+        }
+        let issued = page.items;
+
+        let mut discovery = AssetDiscovery {
+            issued: issued.len(),
+            added: Vec::new(),
+        };
+        for asset in issued {
+            // Horizon filters by issuer already; guard against a stray record
+            if asset.asset_issuer != anchor.stellar_account {
+                continue;
+            }
+            if self
+                .db
+                .create_asset_if_missing(anchor_id, &asset.asset_code, &asset.asset_issuer)
+                .await?
+            {
+                discovery
+                    .added
+                    .push(format!("{}:{}", asset.asset_code, asset.asset_issuer));
+            }
+        }
+
+        if !discovery.added.is_empty() {
+            info!(
+                "Discovered {} new asset(s) for anchor {}: {}",
+                discovery.added.len(),
+                anchor_id,
+                discovery.added.join(", ")
+            );
+        }
+        Ok(discovery)
+    }
+
+    /// Discover the assets of every anchor once
+    pub async fn discover_all(&self) -> Result<AssetDiscoveryStats> {
+        let mut stats = AssetDiscoveryStats::default();
+        let mut cursor: Option<AnchorCursor> = None;
+        loop {
+            let anchors = match &cursor {
+                Some(cursor) => self.db.list_anchors_after(cursor, ANCHOR_PAGE_SIZE).await?,
+                None => self.db.list_anchors(ANCHOR_PAGE_SIZE, 0).await?,
+            };
+            for anchor in &anchors {
+                let Ok(id) = Uuid::parse_str(&anchor.id) else {
+                    warn!(
+                        "Skipping asset discovery of anchor with invalid id {}",
+                        anchor.id
+                    );
+                    continue;
+                };
+
+                stats.checked += 1;
+                match self.discover_for_anchor(id).await {
+                    Ok(discovery) => stats.added += discovery.added.len(),
+                    Err(e) => {
+                        error!("Failed to discover assets of anchor {}: {:#}", id, e);
+                        stats.errors += 1;
+                    }
+                }
+            }
+            match anchors.last() {
+                Some(last) if anchors.len() as i64 == ANCHOR_PAGE_SIZE => {
+                    cursor = Some(AnchorCursor::after(last));
+                }
+                _ => break,
+            }
+        }
+
+        info!(
+            "Anchor asset discovery: {} checked, {} added, {} errors",
+            stats.checked, stats.added, stats.errors
+        );
+        Ok(stats)
+    }
+}
@@ -0,0 +1,91 @@
+//! Anchor home domain verification.
+//!
+//! An anchor's `home_domain` is only a claim until the domain's stellar.toml
+//! lists the anchor's account, under `ACCOUNTS` or as the issuer of one of its
+//! currencies. The verifier fetches the toml and records the outcome in the
+//! anchor's `domain_verified` flag.
+
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::models::Anchor;
+use crate::services::stellar_toml::StellarTomlClient;
+
+/// Outcome of verifying one anchor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DomainVerification {
+    /// The stellar.toml lists the anchor's account
+    Verified,
+    /// The stellar.toml was fetched but does not list the account
+    AccountNotListed,
+    /// The anchor has no home domain to verify
+    NoHomeDomain,
+    /// The stellar.toml could not be fetched or parsed; the flag is left as it
+    /// was
+    FetchFailed(String),
+}
+
+impl DomainVerification {
+    pub fn is_verified(&self) -> bool {
+        matches!(self, Self::Verified)
+    }
+}
+
+pub struct AnchorDomainVerifier {
+    db: Arc<Database>,
+    toml_client: Arc<StellarTomlClient>,
+}
+
+impl AnchorDomainVerifier {
+    pub fn new(db: Arc<Database>, toml_client: Arc<StellarTomlClient>) -> Self {
+        Self { db, toml_client }
+    }
+
+    /// Verify one anchor's home domain and record the result
+    pub async fn verify_anchor(&self, anchor_id: Uuid) -> Result<DomainVerification> {
+        let anchor = self
+            .db
+            .get_anchor_by_id(anchor_id)
+            .await?
+            .ok_or_else(|| anyhow!("Anchor {} not found", anchor_id))?;
+
+        let result = self.check_anchor(&anchor).await;
+        if !matches!(result, DomainVerification::FetchFailed(_)) {
+            self.db
+                .set_anchor_domain_verified(anchor_id, result.is_verified())
+                .await?;
+        }
+        Ok(result)
+    }
+
+    /// Check an anchor's home domain without recording anything
+    pub async fn check_anchor(&self, anchor: &Anchor) -> DomainVerification {
+        let domain = match anchor.home_domain.as_deref().map(str::trim) {
+            Some(domain) if !domain.is_empty() => domain,
+            _ => return DomainVerification::NoHomeDomain,
+        };
+
+        // Verification must see the current toml, not a cached one
+        let toml = match self.toml_client.fetch_toml_no_cache(domain).await {
+            Ok(toml) => toml,
+            Err(e) => {
+                warn!(
+                    "Could not fetch stellar.toml of {} for anchor {}: {}",
+                    domain, anchor.id, e
+                );
+                return DomainVerification::FetchFailed(e.to_string());
+            }
+        };
+
+        let result = if toml.lists_account(&anchor.stellar_account) {
+            DomainVerification::Verified
+        } else {
+            DomainVerification::AccountNotListed
+        };
+        info!("Anchor {} home domain {}: {:?}", anchor.id, domain, result);
+        result
+    }
+}
@@ -2,27 +2,91 @@
 //!
 //! This service handles:
 //! - Connecting to Soroban RPC endpoints
-//! - Submitting snapshot hashes on-chain
+//! - Submitting snapshot hashes on-chain, singly or in batches
+//! - Reading the on-chain snapshot history back in pages
 //! - Retry logic with exponential backoff
+//! - Resolving a timed-out or duplicate submission against the hash already
+//!   stored on-chain
+//! - Estimating submission fees from a preflight simulation
+//! - Typed contract errors decoded from Soroban error codes
+//! - Per-network contract addresses, with a guard against submitting to a
+//!   contract on a different network than the RPC endpoint
 //! - Comprehensive error handling and logging
 
 use anyhow::{Context, Result};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::future::Future;
 use std::time::Duration;
+use stellar_xdr::curr::{ScError, ScErrorCode, ScErrorType};
 use tracing::{debug, error, info, warn};
 
+use crate::network::{NetworkConfig, StellarNetwork};
+use crate::services::fee_estimator::BASE_FEE_STROOPS;
+use crate::services::idempotency::IdempotencyStore;
+
 const MAX_RETRIES: u32 = 3;
 const INITIAL_BACKOFF_MS: u64 = 1000;
 const BACKOFF_MULTIPLIER: u64 = 2;
 const REQUEST_TIMEOUT_SECS: u64 = 30;
+/// Widest epoch range read per `get_snapshots_in_range` call; matches the
+/// contract's `MAX_SNAPSHOT_RANGE`
+const SNAPSHOT_HISTORY_PAGE_SIZE: u64 = 100;
+
+/// Snapshot contract address on each network.
+///
+/// Set by `SNAPSHOT_CONTRACT_ID_MAINNET` / `SNAPSHOT_CONTRACT_ID_TESTNET`;
+/// `SNAPSHOT_CONTRACT_ID` is used for a network without its own address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ContractAddresses {
+    pub mainnet: Option<String>,
+    pub testnet: Option<String>,
+    pub fallback: Option<String>,
+}
+
+impl ContractAddresses {
+    /// Contract address to use on `network`
+    pub fn for_network(&self, network: StellarNetwork) -> Option<&str> {
+        match network {
+            StellarNetwork::Mainnet => self.mainnet.as_deref(),
+            StellarNetwork::Testnet => self.testnet.as_deref(),
+        }
+        .or(self.fallback.as_deref())
+    }
+}
+
+/// Deployment settings the contract service is built from; without an
+/// address for the selected network or a source key there is no service
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ContractSettings {
+    pub addresses: ContractAddresses,
+    /// Soroban RPC endpoint; the selected network's RPC URL when unset
+    pub rpc_url: Option<String>,
+    /// Secret key of the account submissions are sent from
+    pub source_secret_key: Option<String>,
+}
+
+impl std::fmt::Debug for ContractSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ContractSettings")
+            .field("addresses", &self.addresses)
+            .field("rpc_url", &self.rpc_url)
+            .field(
+                "source_secret_key",
+                &self.source_secret_key.as_ref().map(|_| "[REDACTED]"),
+            )
+            .finish()
+    }
+}
 
 /// Configuration for the contract service
 #[derive(Clone, Debug)]
 pub struct ContractConfig {
     /// Soroban RPC endpoint URL
     pub rpc_url: String,
+    /// Network the contract is deployed on
+    pub network: StellarNetwork,
     /// Contract address (ID) on Stellar
     pub contract_id: String,
     /// Network passphrase (e.g., "Test SDF Network ; September 2015" for testnet)
@@ -36,6 +100,8 @@ pub struct ContractConfig {
 pub struct ContractService {
     client: Client,
     config: ContractConfig,
+    /// Records submissions so a retried submission is not sent twice
+    idempotency: Option<IdempotencyStore>,
 }
 
 /// RPC request structure for Soroban
@@ -81,8 +147,142 @@ impl std::fmt::Display for RpcError {
 
 impl std::error::Error for RpcError {}
 
+/// Failure reported by the snapshot contract or its simulation.
+///
+/// Contract failures come back from Soroban as `Error(Contract, #N)`, where
+/// `N` is the contract's `Error` code; they are returned wrapped in
+/// `anyhow::Error` and can be recovered with `downcast_ref::<ContractError>()`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ContractError {
+    #[error("Caller is not authorized to call the contract")]
+    Unauthorized,
+
+    #[error("Snapshot for this epoch already exists on-chain")]
+    DuplicateEpoch,
+
+    #[error("Invalid snapshot hash size")]
+    InvalidHashSize,
+
+    #[error("Invalid epoch")]
+    InvalidEpoch,
+
+    #[error("Contract admin is not initialized")]
+    NotInitialized,
+
+    #[error("No snapshot found for the requested epoch")]
+    SnapshotNotFound,
+
+    #[error("Contract is paused")]
+    Paused,
+
+    #[error("Epoch is not greater than the latest submitted epoch")]
+    EpochNotMonotonic,
+
+    #[error("Contract does not export the invoked function")]
+    FunctionNotFound,
+
+    #[error("Contract returned unknown error code {0}")]
+    Unknown(u32),
+
+    #[error("Transaction simulation failed: {0}")]
+    SimulationFailed(String),
+
+    #[error(
+        "Contract is configured for {expected} but the RPC endpoint serves '{rpc_passphrase}'"
+    )]
+    NetworkMismatch {
+        expected: StellarNetwork,
+        rpc_passphrase: String,
+    },
+
+    #[error("Transaction was not confirmed after {0} status polls")]
+    ConfirmationTimeout(u32),
+
+    #[error("Epoch {epoch} already holds snapshot hash {onchain_hash}, not the submitted one")]
+    EpochHashMismatch { epoch: u64, onchain_hash: String },
+}
+
+impl ContractError {
+    /// Map a snapshot contract error code to its variant
+    pub fn from_code(code: u32) -> Self {
+        match code {
+            1 => Self::Unauthorized,
+            2 => Self::DuplicateEpoch,
+            3 => Self::InvalidHashSize,
+            4 => Self::InvalidEpoch,
+            5 => Self::NotInitialized,
+            6 => Self::SnapshotNotFound,
+            7 => Self::Paused,
+            8 => Self::EpochNotMonotonic,
+            other => Self::Unknown(other),
+        }
+    }
+
+    /// Decode a simulation error message, e.g.
+    /// `HostError: Error(Contract, #2)` or `HostError: Error(Auth, InvalidAction)`
+    pub fn from_simulation_error(message: &str) -> Self {
+        match host_error(message) {
+            // The VM reports an invocation of an unexported function as a
+            // missing value
+            Some(ScError::WasmVm(ScErrorCode::MissingValue)) => Self::FunctionNotFound,
+            Some(ScError::Contract(code)) => Self::from_code(code),
+            Some(ScError::Auth(_)) => Self::Unauthorized,
+            _ => Self::SimulationFailed(message.to_string()),
+        }
+    }
+
+    /// Whether submitting again could succeed. Errors decided by the
+    /// contract will fail the same way on every attempt.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::SimulationFailed(_) | Self::ConfirmationTimeout(_)
+        )
+    }
+}
+
+/// A numeric RPC field, which Soroban RPC sends as a string or a number
+fn json_u64(value: &serde_json::Value) -> Option<u64> {
+    value
+        .as_u64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+}
+
+/// Host error named by a Soroban `Error(Type, Code)` message, e.g.
+/// `Error(Contract, #2)` or `Error(WasmVm, MissingValue)`
+fn host_error(message: &str) -> Option<ScError> {
+    let start = message.find("Error(")? + "Error(".len();
+    let end = start + message[start..].find(')')?;
+    let (kind, code) = message[start..end].split_once(',')?;
+    let (kind, code) = (kind.trim(), code.trim());
+
+    let kind = ScErrorType::VARIANTS
+        .into_iter()
+        .zip(ScErrorType::VARIANTS_STR)
+        .find_map(|(variant, name)| (name == kind).then_some(variant))?;
+    let error_code = || {
+        ScErrorCode::VARIANTS
+            .into_iter()
+            .zip(ScErrorCode::VARIANTS_STR)
+            .find_map(|(variant, name)| (name == code).then_some(variant))
+    };
+
+    Some(match kind {
+        ScErrorType::Contract => ScError::Contract(code.strip_prefix('#')?.parse().ok()?),
+        ScErrorType::WasmVm => ScError::WasmVm(error_code()?),
+        ScErrorType::Context => ScError::Context(error_code()?),
+        ScErrorType::Storage => ScError::Storage(error_code()?),
+        ScErrorType::Object => ScError::Object(error_code()?),
+        ScErrorType::Crypto => ScError::Crypto(error_code()?),
+        ScErrorType::Events => ScError::Events(error_code()?),
+        ScErrorType::Budget => ScError::Budget(error_code()?),
+        ScErrorType::Value => ScError::Value(error_code()?),
+        ScErrorType::Auth => ScError::Auth(error_code()?),
+    })
+}
+
 /// Result of a successful snapshot submission
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SubmissionResult {
     /// Transaction hash
     pub transaction_hash: String,
@@ -92,6 +292,55 @@ pub struct SubmissionResult {
     pub ledger: u64,
     /// Timestamp from the contract
     pub timestamp: u64,
+    /// Whether the snapshot was found already on-chain after a timed-out or
+    /// duplicate submission. The transaction hash, ledger and timestamp are
+    /// then unknown and left empty.
+    pub already_on_chain: bool,
+}
+
+/// Expected cost of a snapshot submission, from a preflight simulation
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct FeeEstimate {
+    /// Minimum resource fee the simulation asked for, in stroops
+    pub resource_fee_stroops: u64,
+    /// Base inclusion fee for the single invoke operation, in stroops
+    pub inclusion_fee_stroops: u64,
+    /// Resource fee plus inclusion fee
+    pub total_fee_stroops: u64,
+    /// CPU instructions the invocation consumed
+    pub cpu_instructions: u64,
+    /// Memory the invocation consumed, in bytes
+    pub memory_bytes: u64,
+    /// Ledger the simulation ran against
+    pub latest_ledger: Option<u64>,
+}
+
+/// A snapshot hash waiting to be submitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotEntry {
+    pub hash: [u8; 32],
+    pub epoch: u64,
+}
+
+/// Snapshot metadata as returned by the contract's read functions
+#[derive(Debug, Deserialize)]
+struct OnChainSnapshotMetadata {
+    epoch: u64,
+    hash: String,
+    timestamp: u64,
+}
+
+/// Result of submitting several snapshots with `submit_snapshots_batch`
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BatchSubmissionResult {
+    /// One result per submitted epoch, in epoch order
+    pub submissions: Vec<SubmissionResult>,
+    /// Epochs skipped because the contract already held them
+    pub already_submitted: Vec<u64>,
+    /// Whether the entries went out in a single batch transaction
+    pub batched: bool,
+    /// Whether every entry was found on-chain afterwards
+    pub verified: bool,
 }
 
 impl ContractService {
@@ -103,29 +352,105 @@ impl ContractService {
             .context("Failed to create HTTP client")?;
 
         info!(
-            "Initialized ContractService with RPC URL: {}, Contract ID: {}",
-            config.rpc_url, config.contract_id
+            "Initialized ContractService on {} with RPC URL: {}, Contract ID: {}",
+            config.network, config.rpc_url, config.contract_id
         );
 
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            idempotency: None,
+        })
     }
 
-    /// Create from environment variables
-    pub fn from_env() -> Result<Self> {
+    /// Create from deployment `settings`, for the contract on `network`
+    pub fn from_settings(settings: &ContractSettings, network: NetworkConfig) -> Result<Self> {
+        let contract_id = settings
+            .addresses
+            .for_network(network.network)
+            .map(str::to_string)
+            .with_context(|| {
+                format!(
+                    "No snapshot contract configured for {} (set SNAPSHOT_CONTRACT_ID_{})",
+                    network.network,
+                    network.network.to_string().to_uppercase()
+                )
+            })?;
+
         let config = ContractConfig {
-            rpc_url: std::env::var("SOROBAN_RPC_URL")
-                .unwrap_or_else(|_| "https://soroban-testnet.stellar.org".to_string()),
-            contract_id: std::env::var("SNAPSHOT_CONTRACT_ID")
-                .context("SNAPSHOT_CONTRACT_ID environment variable not set")?,
-            network_passphrase: std::env::var("STELLAR_NETWORK_PASSPHRASE")
-                .unwrap_or_else(|_| "Test SDF Network ; September 2015".to_string()),
-            source_secret_key: std::env::var("STELLAR_SOURCE_SECRET_KEY")
-                .context("STELLAR_SOURCE_SECRET_KEY environment variable not set")?,
+            rpc_url: settings.rpc_url.clone().unwrap_or(network.rpc_url),
+            network: network.network,
+            contract_id,
+            network_passphrase: network.network_passphrase,
+            source_secret_key: settings
+                .source_secret_key
+                .clone()
+                .context("STELLAR_SOURCE_SECRET_KEY is not set")?,
         };
 
         Self::new(config)
     }
 
+    /// Record snapshot submissions in `store`, so that resubmitting an epoch
+    /// whose hash already went through returns the earlier result instead of
+    /// sending another transaction
+    pub fn with_idempotency(mut self, store: IdempotencyStore) -> Self {
+        self.idempotency = Some(store);
+        self
+    }
+
+    /// Network the contract is deployed on
+    pub fn network(&self) -> StellarNetwork {
+        self.config.network
+    }
+
+    /// Fail with [`ContractError::NetworkMismatch`] unless the RPC endpoint
+    /// serves the contract's network, so a mainnet contract is never
+    /// submitted to through a testnet endpoint or vice versa
+    pub async fn ensure_rpc_network(&self) -> Result<()> {
+        let request = JsonRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: 1,
+            method: "getNetwork".to_string(),
+            params: json!({}),
+        };
+
+        let body: JsonRpcResponse<serde_json::Value> = self
+            .client
+            .post(&self.config.rpc_url)
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to send getNetwork request")?
+            .json()
+            .await
+            .context("Failed to parse getNetwork response")?;
+
+        if let Some(error) = body.error {
+            return Err(anyhow::anyhow!("getNetwork failed: {}", error));
+        }
+        let rpc_passphrase = body
+            .result
+            .as_ref()
+            .and_then(|r| r.get("passphrase"))
+            .and_then(|p| p.as_str())
+            .ok_or_else(|| anyhow::anyhow!("getNetwork returned no network passphrase"))?;
+
+        let expected = NetworkConfig::for_network(self.config.network).network_passphrase;
+        if rpc_passphrase != expected {
+            error!(
+                "Refusing to submit to the {} contract through an RPC endpoint on '{}'",
+                self.config.network, rpc_passphrase
+            );
+            return Err(ContractError::NetworkMismatch {
+                expected: self.config.network,
+                rpc_passphrase: rpc_passphrase.to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
     /// Submit a snapshot hash to the on-chain contract
     ///
     /// This function will:
@@ -165,48 +490,221 @@ impl ContractService {
         hash: [u8; 32],
         epoch: u64,
     ) -> Result<SubmissionResult> {
+        self.ensure_rpc_network().await?;
+        self.submit_single(hash, epoch).await
+    }
+
+    /// Estimate the fee for submitting `hash` at `epoch` without submitting
+    ///
+    /// Runs the same preflight simulation a submission does and reads the
+    /// resource usage and minimum resource fee from it. A simulation the
+    /// contract would reject fails with the decoded [`ContractError`].
+    pub async fn estimate_submission_cost(
+        &self,
+        hash: [u8; 32],
+        epoch: u64,
+    ) -> Result<FeeEstimate> {
+        self.ensure_rpc_network().await?;
+        let invoke_args = self.build_invoke_args(hash, epoch)?;
+        let simulated = self.simulate_transaction(&invoke_args).await?;
+
+        let resource_fee_stroops = simulated
+            .get("minResourceFee")
+            .and_then(json_u64)
+            .ok_or_else(|| anyhow::anyhow!("Simulation returned no minResourceFee"))?;
+        let cost = simulated.get("cost");
+        let inclusion_fee_stroops = BASE_FEE_STROOPS;
+
+        Ok(FeeEstimate {
+            resource_fee_stroops,
+            inclusion_fee_stroops,
+            total_fee_stroops: resource_fee_stroops.saturating_add(inclusion_fee_stroops),
+            cpu_instructions: cost
+                .and_then(|c| c.get("cpuInsns"))
+                .and_then(json_u64)
+                .unwrap_or(0),
+            memory_bytes: cost
+                .and_then(|c| c.get("memBytes"))
+                .and_then(json_u64)
+                .unwrap_or(0),
+            latest_ledger: simulated.get("latestLedger").and_then(json_u64),
+        })
+    }
+
+    /// Submit one snapshot hash, with retries
+    async fn submit_single(&self, hash: [u8; 32], epoch: u64) -> Result<SubmissionResult> {
         info!(
             "Submitting snapshot hash for epoch {}: {}",
             epoch,
             hex::encode(hash)
         );
 
-        let mut attempt = 0;
-        let mut backoff_ms = INITIAL_BACKOFF_MS;
+        let label = format!("snapshot for epoch {}", epoch);
+        let submit = || with_retries(&label, || self.try_submit_snapshot(hash, epoch));
+        let result = match &self.idempotency {
+            // The contract holds one hash per epoch, so the epoch is the key;
+            // a retry with a different hash is refused rather than answered
+            // with the earlier submission
+            Some(store) => {
+                let key = format!("snapshot:{}", epoch);
+                store
+                    .submit_idempotent_with_fingerprint(&key, &hex::encode(hash), submit)
+                    .await?
+            }
+            None => submit().await?,
+        };
+
+        info!(
+            "✓ Successfully submitted snapshot for epoch {} (tx: {}, ledger: {})",
+            epoch, result.transaction_hash, result.ledger
+        );
+        Ok(result)
+    }
+
+    /// Submit a snapshot hash, treating a submission that may already have
+    /// landed as successful when the contract holds the same hash.
+    ///
+    /// A confirmation timeout leaves it unknown whether the transaction was
+    /// included, and retrying it then fails with `DuplicateEpoch`. In either
+    /// case the epoch's stored hash is read back: if it is the submitted hash
+    /// the submission is reported as done, if it differs the call fails with
+    /// [`ContractError::EpochHashMismatch`].
+    pub async fn submit_or_fetch_existing(
+        &self,
+        hash: [u8; 32],
+        epoch: u64,
+    ) -> Result<SubmissionResult> {
+        self.ensure_rpc_network().await?;
+        match self.submit_single(hash, epoch).await {
+            Ok(result) => Ok(result),
+            Err(e) => self.resolve_unconfirmed(hash, epoch, e).await,
+        }
+    }
 
-        loop {
-            attempt += 1;
+    /// Settle a failed submission against the hash stored for `epoch`.
+    /// Errors other than a duplicate epoch or a timeout are returned as is,
+    /// as is `error` when the epoch has no snapshot on-chain.
+    async fn resolve_unconfirmed(
+        &self,
+        hash: [u8; 32],
+        epoch: u64,
+        error: anyhow::Error,
+    ) -> Result<SubmissionResult> {
+        let duplicate = error.downcast_ref() == Some(&ContractError::DuplicateEpoch);
+        if !duplicate && !is_timeout(&error) {
+            return Err(error);
+        }
 
-            match self.try_submit_snapshot(hash, epoch).await {
-                Ok(result) => {
-                    info!(
-                        "✓ Successfully submitted snapshot for epoch {} (tx: {}, ledger: {})",
-                        epoch, result.transaction_hash, result.ledger
-                    );
-                    return Ok(result);
-                }
-                Err(e) => {
-                    if attempt >= MAX_RETRIES {
-                        error!(
-                            "✗ Failed to submit snapshot for epoch {} after {} attempts: {}",
-                            epoch, MAX_RETRIES, e
-                        );
-                        return Err(e).context(format!(
-                            "Failed to submit snapshot after {} retries",
-                            MAX_RETRIES
-                        ));
-                    }
+        let expected = hex::encode(hash);
+        let onchain_hash = match self.get_snapshot_by_epoch(epoch).await {
+            Ok(Some(onchain_hash)) => onchain_hash,
+            Ok(None) => return Err(error),
+            Err(e) => {
+                warn!("Could not read back snapshot for epoch {}: {}", epoch, e);
+                return Err(error);
+            }
+        };
 
-                    warn!(
-                        "Attempt {}/{} failed for epoch {}: {}. Retrying in {}ms...",
-                        attempt, MAX_RETRIES, epoch, e, backoff_ms
-                    );
+        if !onchain_hash.eq_ignore_ascii_case(&expected) {
+            error!(
+                "Epoch {} is on-chain with hash {}, expected {}",
+                epoch, onchain_hash, expected
+            );
+            return Err(ContractError::EpochHashMismatch {
+                epoch,
+                onchain_hash,
+            }
+            .into());
+        }
+
+        info!(
+            "Snapshot for epoch {} is already on-chain with the submitted hash ({})",
+            epoch, error
+        );
+        Ok(SubmissionResult {
+            transaction_hash: String::new(),
+            epoch,
+            ledger: 0,
+            timestamp: 0,
+            already_on_chain: true,
+        })
+    }
 
-                    tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
-                    backoff_ms *= BACKOFF_MULTIPLIER;
+    /// Submit several snapshot hashes in one transaction and verify them.
+    ///
+    /// Entries are submitted in epoch order via the contract's
+    /// `submit_snapshots_batch`. If the contract does not export it, or the
+    /// batch contains an epoch that is already on-chain, each entry is
+    /// submitted on its own instead and existing epochs are skipped.
+    pub async fn submit_snapshots_batch(
+        &self,
+        entries: &[SnapshotEntry],
+    ) -> Result<BatchSubmissionResult> {
+        let mut entries = entries.to_vec();
+        entries.sort_by_key(|entry| entry.epoch);
+
+        let mut result = BatchSubmissionResult {
+            submissions: Vec::new(),
+            already_submitted: Vec::new(),
+            batched: false,
+            verified: true,
+        };
+        if entries.is_empty() {
+            return Ok(result);
+        }
+        self.ensure_rpc_network().await?;
+
+        let (first, last) = (entries[0].epoch, entries[entries.len() - 1].epoch);
+        info!(
+            "Submitting batch of {} snapshots for epochs {}-{}",
+            entries.len(),
+            first,
+            last
+        );
+
+        let batch = with_retries(
+            &format!("snapshot batch for epochs {}-{}", first, last),
+            || self.try_submit_batch(&entries),
+        )
+        .await;
+
+        match batch {
+            Ok(submissions) => {
+                result.submissions = submissions;
+                result.batched = true;
+            }
+            Err(e)
+                if matches!(
+                    e.downcast_ref(),
+                    Some(ContractError::FunctionNotFound | ContractError::DuplicateEpoch)
+                ) =>
+            {
+                warn!("Batch submission unavailable ({}), submitting singly", e);
+                for entry in &entries {
+                    match self.submit_single(entry.hash, entry.epoch).await {
+                        Ok(submission) => result.submissions.push(submission),
+                        Err(e) if e.downcast_ref() == Some(&ContractError::DuplicateEpoch) => {
+                            result.already_submitted.push(entry.epoch)
+                        }
+                        Err(e) => return Err(e),
+                    }
                 }
             }
+            Err(e) => return Err(e),
         }
+
+        for entry in &entries {
+            let verified = self
+                .verify_snapshot_exists(&hex::encode(entry.hash), entry.epoch)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!("Could not verify epoch {}: {}", entry.epoch, e);
+                    false
+                });
+            result.verified &= verified;
+        }
+
+        Ok(result)
     }
 
     /// Single attempt to submit snapshot (without retry logic)
@@ -234,6 +732,53 @@ impl ContractService {
         Ok(result)
     }
 
+    /// Single attempt to submit a batch (without retry logic)
+    async fn try_submit_batch(&self, entries: &[SnapshotEntry]) -> Result<Vec<SubmissionResult>> {
+        let invoke_args = self.build_batch_invoke_args(entries);
+        let simulated = self.simulate_transaction(&invoke_args).await?;
+        let signed_xdr = self.prepare_and_sign_transaction(&simulated)?;
+        let tx_hash = self.send_transaction(&signed_xdr).await?;
+
+        let last_epoch = entries.last().map_or(0, |entry| entry.epoch);
+        let confirmed = self.wait_for_transaction(&tx_hash, last_epoch).await?;
+
+        Ok(entries
+            .iter()
+            .map(|entry| SubmissionResult {
+                epoch: entry.epoch,
+                ..confirmed.clone()
+            })
+            .collect())
+    }
+
+    /// Build contract invocation arguments for `submit_snapshots_batch`,
+    /// a vector of `[hash_bytes, epoch_u64]` pairs
+    fn build_batch_invoke_args(&self, entries: &[SnapshotEntry]) -> serde_json::Value {
+        let pairs: Vec<serde_json::Value> = entries
+            .iter()
+            .map(|entry| {
+                json!({
+                    "type": "vec",
+                    "value": [
+                        { "type": "bytes", "value": hex::encode(entry.hash) },
+                        { "type": "u64", "value": entry.epoch.to_string() }
+                    ]
+                })
+            })
+            .collect();
+
+        json!({
+            "contractId": self.config.contract_id,
+            "function": "submit_snapshots_batch",
+            "args": [
+                {
+                    "type": "vec",
+                    "value": pairs
+                }
+            ]
+        })
+    }
+
     /// Build contract invocation arguments
     fn build_invoke_args(&self, hash: [u8; 32], epoch: u64) -> Result<serde_json::Value> {
         // Convert hash to hex for the contract call
@@ -286,15 +831,19 @@ impl ContractService {
             .context("Failed to parse simulation response")?;
 
         if let Some(error) = body.error {
-            return Err(anyhow::anyhow!(
-                "Transaction simulation failed: {} (code: {})",
-                error.message,
-                error.code
-            ));
+            return Err(ContractError::from_simulation_error(&error.message).into());
         }
 
-        body.result
-            .ok_or_else(|| anyhow::anyhow!("No simulation result returned (status: {})", status))
+        let result = body
+            .result
+            .ok_or_else(|| anyhow::anyhow!("No simulation result returned (status: {})", status))?;
+
+        // A simulation that ran but failed reports the host error in the result
+        if let Some(message) = result.get("error").and_then(|e| e.as_str()) {
+            return Err(ContractError::from_simulation_error(message).into());
+        }
+
+        Ok(result)
     }
 
     /// Prepare and sign the transaction
@@ -362,7 +911,7 @@ impl ContractService {
 
     /// Wait for transaction to be confirmed and return the result
     async fn wait_for_transaction(&self, tx_hash: &str, epoch: u64) -> Result<SubmissionResult> {
-        let max_wait_attempts = 10;
+        let max_wait_attempts: u32 = 10;
         let poll_interval = Duration::from_secs(2);
 
         for attempt in 1..=max_wait_attempts {
@@ -425,6 +974,7 @@ impl ContractService {
                             epoch,
                             ledger,
                             timestamp,
+                            already_on_chain: false,
                         });
                     }
                     "FAILED" => {
@@ -446,10 +996,7 @@ impl ContractService {
             }
         }
 
-        Err(anyhow::anyhow!(
-            "Transaction confirmation timeout after {} attempts",
-            max_wait_attempts
-        ))
+        Err(ContractError::ConfirmationTimeout(max_wait_attempts).into())
     }
 
     /// Health check for the RPC endpoint
@@ -602,6 +1149,171 @@ impl ContractService {
             Ok(None)
         }
     }
+
+    /// Read every snapshot with an epoch in `from_epoch..=to_epoch` as
+    /// `(epoch, hash, timestamp)`, in epoch order.
+    ///
+    /// The range is read in pages through the contract's
+    /// `get_snapshots_in_range` to stay within read limits. If the contract
+    /// does not export it, each epoch is read with `get_snapshot` instead.
+    /// Epochs without a snapshot are skipped.
+    pub async fn get_snapshot_history_onchain(
+        &self,
+        from_epoch: u64,
+        to_epoch: u64,
+    ) -> Result<Vec<(u64, String, u64)>> {
+        if from_epoch > to_epoch {
+            anyhow::bail!(
+                "Invalid epoch range: from_epoch {} is after to_epoch {}",
+                from_epoch,
+                to_epoch
+            );
+        }
+
+        let mut history = Vec::new();
+        let mut start = from_epoch;
+        loop {
+            let end = to_epoch.min(start.saturating_add(SNAPSHOT_HISTORY_PAGE_SIZE - 1));
+            match self.get_snapshot_page(start, end).await {
+                Ok(page) => history.extend(page),
+                Err(e) if e.downcast_ref() == Some(&ContractError::FunctionNotFound) => {
+                    warn!("Paged history unavailable ({}), reading epochs singly", e);
+                    for epoch in start..=to_epoch {
+                        history.extend(self.get_snapshot_metadata(epoch).await?);
+                    }
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
+
+            if end == to_epoch {
+                break;
+            }
+            start = end + 1;
+        }
+
+        debug!(
+            "Read {} on-chain snapshots for epochs {}-{}",
+            history.len(),
+            from_epoch,
+            to_epoch
+        );
+        Ok(history)
+    }
+
+    /// One page of `get_snapshot_history_onchain`
+    async fn get_snapshot_page(&self, start: u64, end: u64) -> Result<Vec<(u64, String, u64)>> {
+        let args = json!({
+            "contractId": self.config.contract_id,
+            "function": "get_snapshots_in_range",
+            "args": [
+                { "type": "u64", "value": start.to_string() },
+                { "type": "u64", "value": end.to_string() }
+            ]
+        });
+
+        let result = self.simulate_transaction(&args).await?;
+        let page: Vec<OnChainSnapshotMetadata> = match result.get("returnValue") {
+            Some(value) if !value.is_null() => serde_json::from_value(value.clone())
+                .context("Failed to parse snapshot range response")?,
+            _ => Vec::new(),
+        };
+
+        Ok(page
+            .into_iter()
+            .map(|s| (s.epoch, s.hash, s.timestamp))
+            .collect())
+    }
+
+    /// Snapshot metadata of a single epoch, `None` if it has none
+    async fn get_snapshot_metadata(&self, epoch: u64) -> Result<Option<(u64, String, u64)>> {
+        let args = json!({
+            "contractId": self.config.contract_id,
+            "function": "get_snapshot",
+            "args": [
+                { "type": "u64", "value": epoch.to_string() }
+            ]
+        });
+
+        let result = match self.simulate_transaction(&args).await {
+            Ok(result) => result,
+            Err(e) if e.downcast_ref() == Some(&ContractError::SnapshotNotFound) => {
+                return Ok(None)
+            }
+            Err(e) => return Err(e),
+        };
+
+        match result.get("returnValue") {
+            Some(value) if !value.is_null() => {
+                let snapshot: OnChainSnapshotMetadata = serde_json::from_value(value.clone())
+                    .context("Failed to parse snapshot response")?;
+                Ok(Some((snapshot.epoch, snapshot.hash, snapshot.timestamp)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Whether `error` means a request or confirmation timed out, leaving the
+/// outcome of the submission unknown
+fn is_timeout(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref(),
+            Some(ContractError::ConfirmationTimeout(_))
+        ) || cause
+            .downcast_ref::<reqwest::Error>()
+            .is_some_and(reqwest::Error::is_timeout)
+    })
+}
+
+/// Run `submit` until it succeeds, the contract rejects it outright, or
+/// `MAX_RETRIES` attempts have failed
+async fn with_retries<T, F, Fut>(what: &str, mut submit: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut attempt = 0;
+    let mut backoff_ms = INITIAL_BACKOFF_MS;
+
+    loop {
+        attempt += 1;
+
+        match submit().await {
+            Ok(result) => return Ok(result),
+            Err(e) => {
+                if let Some(contract_error) = e.downcast_ref::<ContractError>() {
+                    if !contract_error.is_retryable() {
+                        warn!(
+                            "Submission of {} rejected by contract: {}",
+                            what, contract_error
+                        );
+                        return Err(e);
+                    }
+                }
+
+                if attempt >= MAX_RETRIES {
+                    error!(
+                        "✗ Failed to submit {} after {} attempts: {}",
+                        what, MAX_RETRIES, e
+                    );
+                    return Err(e).context(format!(
+                        "Failed to submit snapshot after {} retries",
+                        MAX_RETRIES
+                    ));
+                }
+
+                warn!(
+                    "Attempt {}/{} failed for {}: {}. Retrying in {}ms...",
+                    attempt, MAX_RETRIES, what, e, backoff_ms
+                );
+
+                tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
+                backoff_ms *= BACKOFF_MULTIPLIER;
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -612,6 +1324,7 @@ mod tests {
     fn test_build_invoke_args() {
         let config = ContractConfig {
             rpc_url: "https://soroban-testnet.stellar.org".to_string(),
+            network: StellarNetwork::Testnet,
             contract_id: "CBGTG4JJFEQE3SPBGQFP3X5HM46N47LXZPXQACVKB7QA6X2XB2IG5CTA".to_string(),
             network_passphrase: "Test SDF Network ; September 2015".to_string(),
             source_secret_key: "S...".to_string(),
@@ -636,4 +1349,499 @@ mod tests {
         // This would require a mock server setup
         // Placeholder for integration testing
     }
+
+    #[test]
+    fn test_contract_error_codes() {
+        let expected = [
+            (1, ContractError::Unauthorized),
+            (2, ContractError::DuplicateEpoch),
+            (3, ContractError::InvalidHashSize),
+            (4, ContractError::InvalidEpoch),
+            (5, ContractError::NotInitialized),
+            (6, ContractError::SnapshotNotFound),
+            (7, ContractError::Paused),
+            (8, ContractError::EpochNotMonotonic),
+            (42, ContractError::Unknown(42)),
+        ];
+
+        for (code, variant) in expected {
+            let message = format!(
+                "HostError: Error(Contract, #{})\n\nEvent log (newest first):",
+                code
+            );
+            assert_eq!(ContractError::from_simulation_error(&message), variant);
+            assert!(!variant.is_retryable());
+        }
+    }
+
+    #[test]
+    fn test_simulation_errors_without_contract_code() {
+        assert_eq!(
+            ContractError::from_simulation_error("HostError: Error(Auth, InvalidAction)"),
+            ContractError::Unauthorized
+        );
+
+        // Other host errors are left to retry
+        assert_eq!(
+            ContractError::from_simulation_error("HostError: Error(Budget, ExceededLimit)"),
+            ContractError::SimulationFailed("HostError: Error(Budget, ExceededLimit)".to_string())
+        );
+
+        let error = ContractError::from_simulation_error("resource limit exceeded");
+        assert_eq!(
+            error,
+            ContractError::SimulationFailed("resource limit exceeded".to_string())
+        );
+        assert!(error.is_retryable());
+    }
+
+    const TESTNET_PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    /// `getNetwork` result for a testnet RPC endpoint
+    fn testnet_network() -> serde_json::Value {
+        json!({ "jsonrpc": "2.0", "id": 1, "result": { "passphrase": TESTNET_PASSPHRASE } })
+    }
+
+    /// Mock Soroban RPC on testnet that fails every simulation with `message`
+    async fn mock_rpc(
+        message: &'static str,
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use axum::{routing::post, Json, Router};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let app = Router::new().route(
+            "/",
+            post(move |Json(request): Json<serde_json::Value>| {
+                let response = if request["method"] == "getNetwork" {
+                    testnet_network()
+                } else {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "result": { "error": message }
+                    })
+                };
+                async move { Json(response) }
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (format!("http://{}/", addr), calls)
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_epoch_is_not_retried() {
+        let (rpc_url, calls) = mock_rpc("HostError: Error(Contract, #2)").await;
+        let service = ContractService::new(ContractConfig {
+            rpc_url,
+            network: StellarNetwork::Testnet,
+            contract_id: "CBGTG4JJFEQE3SPBGQFP3X5HM46N47LXZPXQACVKB7QA6X2XB2IG5CTA".to_string(),
+            network_passphrase: "Test SDF Network ; September 2015".to_string(),
+            source_secret_key: "S...".to_string(),
+        })
+        .unwrap();
+
+        let err = service.submit_snapshot([0u8; 32], 7).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ContractError>(),
+            Some(&ContractError::DuplicateEpoch)
+        );
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    /// Mock Soroban RPC on testnet answering each simulation with
+    /// `respond(function, args)`, recording the invoked function and its args
+    async fn mock_contract_rpc(
+        respond: fn(&str, &serde_json::Value) -> serde_json::Value,
+    ) -> (
+        String,
+        std::sync::Arc<std::sync::Mutex<Vec<(String, serde_json::Value)>>>,
+    ) {
+        use axum::{routing::post, Json, Router};
+        use std::sync::{Arc, Mutex};
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let recorded = calls.clone();
+        let app = Router::new().route(
+            "/",
+            post(move |Json(request): Json<serde_json::Value>| {
+                if request["method"] == "getNetwork" {
+                    return std::future::ready(Json(testnet_network()));
+                }
+                let transaction = &request["params"]["transaction"];
+                let function = transaction["function"].as_str().unwrap_or("").to_string();
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push((function.clone(), transaction["args"].clone()));
+                let result = respond(&function, &transaction["args"]);
+                std::future::ready(Json(json!({ "jsonrpc": "2.0", "id": 1, "result": result })))
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (format!("http://{}/", addr), calls)
+    }
+
+    fn service_for(rpc_url: String) -> ContractService {
+        ContractService::new(ContractConfig {
+            rpc_url,
+            network: StellarNetwork::Testnet,
+            contract_id: "CBGTG4JJFEQE3SPBGQFP3X5HM46N47LXZPXQACVKB7QA6X2XB2IG5CTA".to_string(),
+            network_passphrase: "Test SDF Network ; September 2015".to_string(),
+            source_secret_key: "S...".to_string(),
+        })
+        .unwrap()
+    }
+
+    fn entries() -> Vec<SnapshotEntry> {
+        [3u64, 1, 2]
+            .into_iter()
+            .map(|epoch| SnapshotEntry {
+                hash: [epoch as u8; 32],
+                epoch,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_batch_is_submitted_as_one_invocation() {
+        let (rpc_url, calls) =
+            mock_contract_rpc(|_, _| json!({ "error": "HostError: Error(Contract, #7)" })).await;
+        let service = service_for(rpc_url);
+
+        let err = service
+            .submit_snapshots_batch(&entries())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ContractError>(),
+            Some(&ContractError::Paused)
+        );
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, "submit_snapshots_batch");
+        let epochs: Vec<&str> = calls[0].1[0]["value"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|pair| pair["value"][1]["value"].as_str().unwrap())
+            .collect();
+        assert_eq!(epochs, ["1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn test_batch_falls_back_to_single_submission() {
+        let (rpc_url, calls) = mock_contract_rpc(|function, _| match function {
+            "submit_snapshots_batch" => json!({
+                "error": "HostError: Error(WasmVm, MissingValue)\n\
+                          trying to invoke non-existent contract function"
+            }),
+            "submit_snapshot" => json!({ "error": "HostError: Error(Contract, #2)" }),
+            _ => json!({ "returnValue": true }),
+        })
+        .await;
+        let service = service_for(rpc_url);
+
+        let result = service.submit_snapshots_batch(&entries()).await.unwrap();
+        assert!(!result.batched);
+        assert!(result.submissions.is_empty());
+        assert_eq!(result.already_submitted, vec![1, 2, 3]);
+        assert!(result.verified);
+
+        let functions: Vec<String> = calls.lock().unwrap().iter().map(|c| c.0.clone()).collect();
+        assert_eq!(
+            functions,
+            [
+                "submit_snapshots_batch",
+                "submit_snapshot",
+                "submit_snapshot",
+                "submit_snapshot",
+                "verify_snapshot",
+                "verify_snapshot",
+                "verify_snapshot",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_submission_cost_is_read_from_simulation() {
+        let (rpc_url, calls) = mock_contract_rpc(|_, _| {
+            json!({
+                "minResourceFee": "58181",
+                "cost": { "cpuInsns": "1635321", "memBytes": "1208516" },
+                "latestLedger": 51583040
+            })
+        })
+        .await;
+        let service = service_for(rpc_url);
+
+        let estimate = service
+            .estimate_submission_cost([9u8; 32], 12)
+            .await
+            .unwrap();
+        assert_eq!(
+            estimate,
+            FeeEstimate {
+                resource_fee_stroops: 58181,
+                inclusion_fee_stroops: BASE_FEE_STROOPS,
+                total_fee_stroops: 58181 + BASE_FEE_STROOPS,
+                cpu_instructions: 1635321,
+                memory_bytes: 1208516,
+                latest_ledger: Some(51583040),
+            }
+        );
+
+        // Only simulated, never sent
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, "submit_snapshot");
+        assert_eq!(calls[0].1[1]["value"], "12");
+    }
+
+    #[tokio::test]
+    async fn test_submission_cost_surfaces_simulation_failure() {
+        let (rpc_url, _) =
+            mock_contract_rpc(|_, _| json!({ "error": "HostError: Error(Contract, #8)" })).await;
+        let service = service_for(rpc_url);
+
+        let err = service
+            .estimate_submission_cost([9u8; 32], 1)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ContractError>(),
+            Some(&ContractError::EpochNotMonotonic)
+        );
+    }
+
+    fn u64_arg(args: &serde_json::Value, index: usize) -> u64 {
+        args[index]["value"].as_str().unwrap().parse().unwrap()
+    }
+
+    fn metadata(epoch: u64) -> serde_json::Value {
+        json!({ "epoch": epoch, "hash": hex::encode([epoch as u8; 32]), "timestamp": epoch * 60 })
+    }
+
+    #[tokio::test]
+    async fn test_history_is_assembled_across_pages() {
+        let (rpc_url, calls) = mock_contract_rpc(|_, args| {
+            let snapshots: Vec<serde_json::Value> = (u64_arg(args, 0)..=u64_arg(args, 1))
+                .map(metadata)
+                .collect();
+            json!({ "returnValue": snapshots })
+        })
+        .await;
+        let service = service_for(rpc_url);
+
+        let history = service.get_snapshot_history_onchain(5, 250).await.unwrap();
+        let epochs: Vec<u64> = history.iter().map(|(epoch, _, _)| *epoch).collect();
+        assert_eq!(epochs, (5..=250).collect::<Vec<_>>());
+        assert_eq!(
+            history[0],
+            (5, hex::encode([5u8; 32]), 300),
+            "hash and timestamp are carried through"
+        );
+
+        let pages: Vec<(u64, u64)> = calls
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(function, args)| {
+                assert_eq!(function, "get_snapshots_in_range");
+                (u64_arg(args, 0), u64_arg(args, 1))
+            })
+            .collect();
+        assert_eq!(pages, [(5, 104), (105, 204), (205, 250)]);
+    }
+
+    #[tokio::test]
+    async fn test_history_falls_back_to_per_epoch_reads() {
+        let (rpc_url, calls) = mock_contract_rpc(|function, args| match function {
+            "get_snapshots_in_range" => json!({
+                "error": "HostError: Error(WasmVm, MissingValue)\n\
+                          trying to invoke non-existent contract function"
+            }),
+            _ => match u64_arg(args, 0) {
+                epoch if epoch % 2 == 0 => json!({ "returnValue": metadata(epoch) }),
+                _ => json!({ "returnValue": null }),
+            },
+        })
+        .await;
+        let service = service_for(rpc_url);
+
+        let history = service.get_snapshot_history_onchain(1, 6).await.unwrap();
+        let epochs: Vec<u64> = history.iter().map(|(epoch, _, _)| *epoch).collect();
+        assert_eq!(epochs, [2, 4, 6]);
+        assert_eq!(calls.lock().unwrap().len(), 7);
+    }
+
+    #[tokio::test]
+    async fn test_history_rejects_inverted_range() {
+        let service = service_for("http://127.0.0.1:1/".to_string());
+        assert!(service.get_snapshot_history_onchain(5, 4).await.is_err());
+    }
+
+    #[test]
+    fn test_missing_function_is_not_retryable() {
+        let error = ContractError::from_simulation_error(
+            "HostError: Error(WasmVm, MissingValue) trying to invoke non-existent contract function",
+        );
+        assert_eq!(error, ContractError::FunctionNotFound);
+        assert!(!error.is_retryable());
+
+        // Only the host error decides, not the diagnostic text around it
+        assert_eq!(
+            ContractError::from_simulation_error(
+                "HostError: Error(WasmVm, InvalidAction) non-existent contract function"
+            ),
+            ContractError::SimulationFailed(
+                "HostError: Error(WasmVm, InvalidAction) non-existent contract function"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_contract_address_is_resolved_per_network() {
+        let addresses = ContractAddresses {
+            mainnet: Some("CMAINNET".to_string()),
+            testnet: Some("CTESTNET".to_string()),
+            fallback: Some("CLEGACY".to_string()),
+        };
+        assert_eq!(
+            addresses.for_network(StellarNetwork::Mainnet),
+            Some("CMAINNET")
+        );
+        assert_eq!(
+            addresses.for_network(StellarNetwork::Testnet),
+            Some("CTESTNET")
+        );
+
+        let legacy = ContractAddresses {
+            testnet: Some("CTESTNET".to_string()),
+            fallback: Some("CLEGACY".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(legacy.for_network(StellarNetwork::Mainnet), Some("CLEGACY"));
+        assert_eq!(
+            ContractAddresses::default().for_network(StellarNetwork::Testnet),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mainnet_contract_is_not_submitted_through_testnet_rpc() {
+        let (rpc_url, calls) = mock_rpc("unused").await;
+        let service = ContractService::new(ContractConfig {
+            rpc_url,
+            network: StellarNetwork::Mainnet,
+            contract_id: "CMAINNET".to_string(),
+            network_passphrase: "Public Global Stellar Network ; September 2015".to_string(),
+            source_secret_key: "S...".to_string(),
+        })
+        .unwrap();
+
+        let expected = ContractError::NetworkMismatch {
+            expected: StellarNetwork::Mainnet,
+            rpc_passphrase: TESTNET_PASSPHRASE.to_string(),
+        };
+        let err = service.submit_snapshot([0u8; 32], 1).await.unwrap_err();
+        assert_eq!(err.downcast_ref::<ContractError>(), Some(&expected));
+        let err = service
+            .submit_snapshots_batch(&entries())
+            .await
+            .unwrap_err();
+        assert_eq!(err.downcast_ref::<ContractError>(), Some(&expected));
+
+        // Nothing was simulated or sent
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+
+    /// Answers `get_snapshot` with the hex of `[7; 32]` and fails
+    /// `submit_snapshot` as a duplicate epoch
+    fn stored_snapshot(function: &str, _: &serde_json::Value) -> serde_json::Value {
+        match function {
+            "get_snapshot" => json!({ "returnValue": hex::encode([7u8; 32]) }),
+            _ => json!({ "error": "HostError: Error(Contract, #2)" }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timeout_with_matching_onchain_hash_is_success() {
+        let (rpc_url, calls) = mock_contract_rpc(stored_snapshot).await;
+        let service = service_for(rpc_url);
+
+        // As returned by `with_retries` once every attempt timed out
+        let timeout = anyhow::Error::from(ContractError::ConfirmationTimeout(10))
+            .context("Failed to submit snapshot after 3 retries");
+        let result = service
+            .resolve_unconfirmed([7u8; 32], 5, timeout)
+            .await
+            .unwrap();
+
+        assert!(result.already_on_chain);
+        assert_eq!(result.epoch, 5);
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].0, "get_snapshot");
+        assert_eq!(calls[0].1[0]["value"], "5");
+    }
+
+    #[tokio::test]
+    async fn test_timeout_with_different_onchain_hash_is_error() {
+        let (rpc_url, _) = mock_contract_rpc(stored_snapshot).await;
+        let service = service_for(rpc_url);
+
+        let err = service
+            .resolve_unconfirmed([1u8; 32], 5, ContractError::ConfirmationTimeout(10).into())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ContractError>(),
+            Some(&ContractError::EpochHashMismatch {
+                epoch: 5,
+                onchain_hash: hex::encode([7u8; 32]),
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_epoch_with_matching_hash_is_success() {
+        let (rpc_url, calls) = mock_contract_rpc(stored_snapshot).await;
+        let service = service_for(rpc_url);
+
+        let result = service
+            .submit_or_fetch_existing([7u8; 32], 5)
+            .await
+            .unwrap();
+        assert!(result.already_on_chain);
+
+        let functions: Vec<String> = calls.lock().unwrap().iter().map(|c| c.0.clone()).collect();
+        assert_eq!(functions, ["submit_snapshot", "get_snapshot"]);
+
+        // Other failures are not read back
+        let (rpc_url, calls) =
+            mock_contract_rpc(|_, _| json!({ "error": "HostError: Error(Contract, #7)" })).await;
+        let err = service_for(rpc_url)
+            .submit_or_fetch_existing([7u8; 32], 5)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ContractError>(),
+            Some(&ContractError::Paused)
+        );
+        assert_eq!(calls.lock().unwrap().len(), 1);
+    }
 }
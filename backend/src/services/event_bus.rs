@@ -0,0 +1,442 @@
+/// In-process Event Bus
+/// Producers publish typed domain events once; the webhook dispatcher and
+/// WebSocket broadcaster subscribe independently, optionally to a sampled
+/// stream of high-volume event types. Alert events are never sampled.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::broadcast;
+
+use crate::observability::metrics as obs_metrics;
+use crate::services::price_feed::StalePriceAlert;
+
+use crate::webhooks::events::{
+    AnchorStatusChangedEvent, CorridorHealthDegradedEvent, CorridorLiquidityDroppedEvent,
+    PaymentCreatedEvent, RpcHealthEvent,
+};
+use crate::webhooks::WebhookEventType;
+
+/// Default number of events buffered per subscriber before lagging
+pub const DEFAULT_EVENT_BUS_CAPACITY: usize = 256;
+
+/// Domain events emitted by analyzers and handlers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum DomainEvent {
+    AnchorStatusChanged(AnchorStatusChangedEvent),
+    CorridorHealthDegraded(CorridorHealthDegradedEvent),
+    CorridorLiquidityDropped(CorridorLiquidityDroppedEvent),
+    PaymentCreated(PaymentCreatedEvent),
+    RpcDegraded(RpcHealthEvent),
+    RpcRecovered(RpcHealthEvent),
+    PriceStale(StalePriceAlert),
+}
+
+impl DomainEvent {
+    /// Webhook event type this domain event is delivered as
+    pub fn webhook_event_type(&self) -> WebhookEventType {
+        match self {
+            Self::AnchorStatusChanged(_) => WebhookEventType::AnchorStatusChanged,
+            Self::CorridorHealthDegraded(_) => WebhookEventType::CorridorHealthDegraded,
+            Self::CorridorLiquidityDropped(_) => WebhookEventType::CorridorLiquidityDropped,
+            Self::PaymentCreated(_) => WebhookEventType::PaymentCreated,
+            Self::RpcDegraded(_) => WebhookEventType::RpcDegraded,
+            Self::RpcRecovered(_) => WebhookEventType::RpcRecovered,
+            Self::PriceStale(_) => WebhookEventType::PriceStale,
+        }
+    }
+
+    /// Event payload as JSON, without the enum tag
+    pub fn payload(&self) -> serde_json::Value {
+        let value = match self {
+            Self::AnchorStatusChanged(e) => serde_json::to_value(e),
+            Self::CorridorHealthDegraded(e) => serde_json::to_value(e),
+            Self::CorridorLiquidityDropped(e) => serde_json::to_value(e),
+            Self::PaymentCreated(e) => serde_json::to_value(e),
+            Self::RpcDegraded(e) | Self::RpcRecovered(e) => serde_json::to_value(e),
+            Self::PriceStale(e) => serde_json::to_value(e),
+        };
+        value.unwrap_or(serde_json::Value::Null)
+    }
+
+    /// Amount moved by the event, for events that carry one
+    pub fn amount(&self) -> Option<f64> {
+        match self {
+            Self::PaymentCreated(e) => Some(e.amount),
+            _ => None,
+        }
+    }
+}
+
+/// Which events of one type a sampled subscriber receives
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SamplingRule {
+    /// The first of every `n` events
+    OneIn(u64),
+    /// Only events moving at least this amount; events without an amount pass
+    MinAmount(f64),
+}
+
+impl SamplingRule {
+    /// Parse `1/N` or `min:AMOUNT`
+    pub fn parse(rule: &str) -> Option<Self> {
+        let rule = rule.trim();
+        if let Some(n) = rule.strip_prefix("1/") {
+            return n.parse().ok().filter(|n| *n > 0).map(Self::OneIn);
+        }
+        rule.strip_prefix("min:")?
+            .parse()
+            .ok()
+            .filter(|amount: &f64| amount.is_finite())
+            .map(Self::MinAmount)
+    }
+}
+
+/// Whether events of a type may be sampled at all. Only high-volume data
+/// events qualify; alerts and error reports are always delivered.
+pub fn is_samplable(event_type: &WebhookEventType) -> bool {
+    matches!(event_type, WebhookEventType::PaymentCreated)
+}
+
+/// Parse one sampling entry, e.g. `payment.created=1/10` or
+/// `payment.created=min:1000`; `None` when malformed or when the event type
+/// is never sampled
+pub fn parse_sampling_entry(entry: &str) -> Option<(WebhookEventType, SamplingRule)> {
+    let (event_type, rule) = entry.split_once('=')?;
+    let event_type = WebhookEventType::from_str(event_type.trim()).filter(is_samplable)?;
+    Some((event_type, SamplingRule::parse(rule)?))
+}
+
+/// Sampling rules per event type; types without a rule are not sampled, and
+/// only types passing [`is_samplable`] can have one
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EventSamplingConfig {
+    rules: HashMap<WebhookEventType, SamplingRule>,
+}
+
+impl EventSamplingConfig {
+    /// Sample `event_type` by `rule`; ignored, with a warning, for event
+    /// types that are never sampled
+    pub fn with_rule(mut self, event_type: WebhookEventType, rule: SamplingRule) -> Self {
+        if is_samplable(&event_type) {
+            self.rules.insert(event_type, rule);
+        } else {
+            tracing::warn!(
+                "Ignoring sampling rule for {}: alert events are never sampled",
+                event_type.as_str()
+            );
+        }
+        self
+    }
+
+    pub fn rule(&self, event_type: &WebhookEventType) -> Option<SamplingRule> {
+        self.rules.get(event_type).copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+/// Events of one type seen by a sampled subscriber, and how many it delivered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct SamplingCounts {
+    pub seen: u64,
+    pub delivered: u64,
+}
+
+/// Applies a sampling config to a stream of events, counting every event
+/// so totals stay accurate when individual events are dropped
+#[derive(Debug)]
+pub struct EventSampler {
+    subscriber: &'static str,
+    config: EventSamplingConfig,
+    counts: HashMap<WebhookEventType, SamplingCounts>,
+}
+
+impl EventSampler {
+    pub fn new(subscriber: &'static str, config: EventSamplingConfig) -> Self {
+        Self {
+            subscriber,
+            config,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Count `event` and decide whether it is delivered
+    pub fn sample(&mut self, event: &DomainEvent) -> bool {
+        let event_type = event.webhook_event_type();
+        let rule = self.config.rule(&event_type);
+        let counts = self.counts.entry(event_type.clone()).or_default();
+        counts.seen += 1;
+
+        let deliver = match rule {
+            None => true,
+            Some(SamplingRule::OneIn(n)) => (counts.seen - 1) % n == 0,
+            Some(SamplingRule::MinAmount(min)) => event.amount().is_none_or(|a| a >= min),
+        };
+
+        if deliver {
+            counts.delivered += 1;
+        } else {
+            obs_metrics::record_event_sampled_out(self.subscriber, event_type.as_str());
+        }
+        deliver
+    }
+
+    pub fn counts(&self, event_type: &WebhookEventType) -> SamplingCounts {
+        self.counts.get(event_type).copied().unwrap_or_default()
+    }
+}
+
+/// Event bus subscription that only yields events passing its sampler
+pub struct SampledReceiver {
+    rx: broadcast::Receiver<DomainEvent>,
+    sampler: EventSampler,
+}
+
+impl SampledReceiver {
+    /// Next event that passes sampling; lag and close errors are passed through
+    pub async fn recv(&mut self) -> Result<DomainEvent, broadcast::error::RecvError> {
+        loop {
+            let event = self.rx.recv().await?;
+            if self.sampler.sample(&event) {
+                return Ok(event);
+            }
+        }
+    }
+
+    pub fn counts(&self, event_type: &WebhookEventType) -> SamplingCounts {
+        self.sampler.counts(event_type)
+    }
+}
+
+/// Lightweight publish/subscribe bus backed by a tokio broadcast channel
+pub struct EventBus {
+    tx: broadcast::Sender<DomainEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_EVENT_BUS_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (tx, _rx) = broadcast::channel(capacity.max(1));
+        Self { tx }
+    }
+
+    /// Publish an event to all current subscribers.
+    /// Returns the number of subscribers that received it.
+    pub fn publish(&self, event: DomainEvent) -> usize {
+        obs_metrics::record_domain_event(event.webhook_event_type().as_str());
+        match self.tx.send(event) {
+            Ok(receivers) => receivers,
+            Err(_) => {
+                tracing::debug!("Domain event published with no subscribers");
+                0
+            }
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.tx.subscribe()
+    }
+
+    /// Subscribe to a stream sampled by `config`; `subscriber` labels the
+    /// sampled-out metrics
+    pub fn subscribe_sampled(
+        &self,
+        subscriber: &'static str,
+        config: EventSamplingConfig,
+    ) -> SampledReceiver {
+        SampledReceiver {
+            rx: self.tx.subscribe(),
+            sampler: EventSampler::new(subscriber, config),
+        }
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.tx.receiver_count()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anchor_event() -> DomainEvent {
+        DomainEvent::AnchorStatusChanged(AnchorStatusChangedEvent {
+            anchor_id: "anchor-1".to_string(),
+            name: "Test Anchor".to_string(),
+            old_status: "green".to_string(),
+            new_status: "red".to_string(),
+            reliability_score: 42.0,
+            failed_txn_count: 12,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_publish_reaches_every_subscriber() {
+        let bus = EventBus::new();
+        let mut rx1 = bus.subscribe();
+        let mut rx2 = bus.subscribe();
+
+        assert_eq!(bus.publish(anchor_event()), 2);
+
+        for rx in [&mut rx1, &mut rx2] {
+            let event = rx.recv().await.unwrap();
+            assert_eq!(
+                event.webhook_event_type(),
+                WebhookEventType::AnchorStatusChanged
+            );
+        }
+    }
+
+    #[test]
+    fn test_publish_without_subscribers() {
+        let bus = EventBus::new();
+        assert_eq!(bus.publish(anchor_event()), 0);
+    }
+
+    fn payment_event(amount: f64) -> DomainEvent {
+        DomainEvent::PaymentCreated(PaymentCreatedEvent {
+            payment_id: "payment-1".to_string(),
+            source: "GSOURCE".to_string(),
+            destination: "GDEST".to_string(),
+            asset_code: "USDC".to_string(),
+            asset_issuer: "GISSUER".to_string(),
+            amount,
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_one_in_n_ratio_holds_over_large_stream() {
+        let config = EventSamplingConfig::default()
+            .with_rule(WebhookEventType::PaymentCreated, SamplingRule::OneIn(10));
+        let mut sampler = EventSampler::new("test", config);
+
+        let mut delivered_payments = 0;
+        for i in 0..100_000 {
+            if sampler.sample(&payment_event(i as f64)) {
+                delivered_payments += 1;
+            }
+            // Types without a rule are never sampled
+            if i % 100 == 0 {
+                assert!(sampler.sample(&anchor_event()));
+            }
+        }
+
+        assert_eq!(delivered_payments, 10_000);
+        assert_eq!(
+            sampler.counts(&WebhookEventType::PaymentCreated),
+            SamplingCounts {
+                seen: 100_000,
+                delivered: 10_000
+            }
+        );
+        assert_eq!(
+            sampler.counts(&WebhookEventType::AnchorStatusChanged),
+            SamplingCounts {
+                seen: 1_000,
+                delivered: 1_000
+            }
+        );
+    }
+
+    #[test]
+    fn test_min_amount_keeps_large_payments() {
+        let config = EventSamplingConfig::default().with_rule(
+            WebhookEventType::PaymentCreated,
+            SamplingRule::MinAmount(900.0),
+        );
+        let mut sampler = EventSampler::new("test", config);
+
+        let delivered = (0..10_000)
+            .filter(|i| sampler.sample(&payment_event((i % 1_000) as f64)))
+            .count();
+
+        assert_eq!(delivered, 1_000);
+        let counts = sampler.counts(&WebhookEventType::PaymentCreated);
+        assert_eq!((counts.seen, counts.delivered), (10_000, 1_000));
+    }
+
+    #[test]
+    fn test_alert_events_are_never_sampled() {
+        let config = EventSamplingConfig::default()
+            .with_rule(
+                WebhookEventType::AnchorStatusChanged,
+                SamplingRule::OneIn(10),
+            )
+            .with_rule(WebhookEventType::PaymentCreated, SamplingRule::OneIn(10));
+        assert_eq!(config.rule(&WebhookEventType::AnchorStatusChanged), None);
+        assert_eq!(
+            config.rule(&WebhookEventType::PaymentCreated),
+            Some(SamplingRule::OneIn(10))
+        );
+
+        let mut sampler = EventSampler::new("test", config);
+        assert!((0..100).all(|_| sampler.sample(&anchor_event())));
+    }
+
+    #[test]
+    fn test_parse_sampling_rule() {
+        assert_eq!(SamplingRule::parse("1/10"), Some(SamplingRule::OneIn(10)));
+        assert_eq!(
+            SamplingRule::parse(" min:250.5 "),
+            Some(SamplingRule::MinAmount(250.5))
+        );
+        assert_eq!(SamplingRule::parse("1/0"), None);
+        assert_eq!(SamplingRule::parse("half"), None);
+
+        assert_eq!(
+            parse_sampling_entry("payment.created=1/10"),
+            Some((WebhookEventType::PaymentCreated, SamplingRule::OneIn(10)))
+        );
+        assert_eq!(parse_sampling_entry("payment.created"), None);
+        assert_eq!(parse_sampling_entry("anchor.status_changed=1/10"), None);
+    }
+
+    #[tokio::test]
+    async fn test_sampled_subscription_skips_dropped_events() {
+        let bus = EventBus::new();
+        let mut full = bus.subscribe();
+        let mut sampled = bus.subscribe_sampled(
+            "test",
+            EventSamplingConfig::default()
+                .with_rule(WebhookEventType::PaymentCreated, SamplingRule::OneIn(3)),
+        );
+
+        for amount in [1.0, 2.0, 3.0, 4.0] {
+            bus.publish(payment_event(amount));
+        }
+
+        for expected in [1.0, 4.0] {
+            let event = sampled.recv().await.unwrap();
+            assert_eq!(event.amount(), Some(expected));
+        }
+        for expected in [1.0, 2.0, 3.0, 4.0] {
+            assert_eq!(full.recv().await.unwrap().amount(), Some(expected));
+        }
+        assert_eq!(
+            sampled.counts(&WebhookEventType::PaymentCreated),
+            SamplingCounts {
+                seen: 4,
+                delivered: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_payload_is_untagged() {
+        let payload = anchor_event().payload();
+        assert_eq!(payload["anchor_id"], "anchor-1");
+        assert!(payload.get("type").is_none());
+    }
+}
@@ -1,8 +1,11 @@
 use anyhow::Result;
-use chrono::{DateTime, Utc};
-use sqlx::{Pool, Sqlite};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use sqlx::{Executor, Pool, Sqlite, SqliteConnection};
+use std::collections::HashMap;
+use stellar_xdr::curr::{Limits, ReadXdr, TransactionResult, TransactionResultResult};
 use tracing::{info, warn};
 
+use crate::models::timestamp::{format_timestamp, parse_ingested_timestamp};
 use crate::models::{FeeBumpStats, FeeBumpTransaction};
 use crate::rpc::HorizonTransaction; // Changed from StellarRpcClient as we process data structs
 
@@ -19,54 +22,14 @@ impl FeeBumpTrackerService {
     pub async fn process_transactions(&self, transactions: &[HorizonTransaction]) -> Result<u64> {
         let mut count = 0;
 
-        for tx in transactions {
-            if let Some(fee_bump) = &tx.fee_bump_transaction {
-                if let Some(inner) = &tx.inner_transaction {
-                    // Extract data safely
-                    let fee_charged = tx
-                        .fee_charged
-                        .as_ref()
-                        .and_then(|f| f.parse::<i64>().ok())
-                        .unwrap_or(0);
-
-                    let max_fee = tx
-                        .max_fee
-                        .as_ref()
-                        .and_then(|f| f.parse::<i64>().ok())
-                        .unwrap_or(0);
-
-                    let inner_max_fee = inner
-                        .max_fee
-                        .as_ref()
-                        .and_then(|f| f.parse::<i64>().ok())
-                        .unwrap_or(0);
-
-                    // Parse created_at
-                    let created_at = DateTime::parse_from_rfc3339(&tx.created_at)
-                        .map(|dt| dt.with_timezone(&Utc))
-                        .unwrap_or_else(|_| Utc::now());
-
-                    let fee_bump_tx = FeeBumpTransaction {
-                        transaction_hash: tx.hash.clone(),
-                        ledger_sequence: tx.ledger as i64,
-                        fee_source: tx
-                            .fee_account
-                            .clone()
-                            .unwrap_or_else(|| tx.source_account.clone()),
-                        fee_charged,
-                        max_fee,
-                        inner_transaction_hash: inner.hash.clone(),
-                        inner_max_fee,
-                        signatures_count: fee_bump.signatures.len() as i32,
-                        created_at,
-                    };
-
-                    if let Err(e) = self.persist_fee_bump(&fee_bump_tx).await {
-                        warn!("Failed to persist fee bump transaction {}: {}", tx.hash, e);
-                    } else {
-                        count += 1;
-                    }
-                }
+        for fee_bump_tx in Self::extract_fee_bumps(transactions) {
+            if let Err(e) = Self::insert_fee_bump(&self.pool, &fee_bump_tx).await {
+                warn!(
+                    "Failed to persist fee bump transaction {}: {}",
+                    fee_bump_tx.transaction_hash, e
+                );
+            } else {
+                count += 1;
             }
         }
 
@@ -77,15 +40,91 @@ impl FeeBumpTrackerService {
         Ok(count)
     }
 
-    /// Persist a single fee bump transaction
-    async fn persist_fee_bump(&self, tx: &FeeBumpTransaction) -> Result<()> {
-        sqlx::query(
+    /// Persist fee bump transactions as part of a caller-managed database
+    /// transaction. Errors are returned rather than logged so the caller can
+    /// roll back. Returns the number of newly inserted rows.
+    pub async fn persist_transactions_in(
+        &self,
+        conn: &mut SqliteConnection,
+        transactions: &[HorizonTransaction],
+    ) -> Result<u64> {
+        let mut inserted = 0;
+        for fee_bump_tx in Self::extract_fee_bumps(transactions) {
+            if Self::insert_fee_bump(&mut *conn, &fee_bump_tx).await? {
+                inserted += 1;
+            }
+        }
+        Ok(inserted)
+    }
+
+    /// Extract fee bump transactions from a batch of Horizon transactions,
+    /// correlating each with the outcome of its inner transaction
+    pub fn extract_fee_bumps(transactions: &[HorizonTransaction]) -> Vec<FeeBumpTransaction> {
+        let outcomes: HashMap<&str, bool> = transactions
+            .iter()
+            .map(|tx| (tx.hash.as_str(), tx.successful))
+            .collect();
+
+        transactions
+            .iter()
+            .filter_map(|tx| {
+                let fee_bump = tx.fee_bump_transaction.as_ref()?;
+                let inner = tx.inner_transaction.as_ref()?;
+
+                // Extract data safely
+                let fee_charged = tx
+                    .fee_charged
+                    .as_ref()
+                    .and_then(|f| f.parse::<i64>().ok())
+                    .unwrap_or(0);
+
+                let max_fee = tx
+                    .max_fee
+                    .as_ref()
+                    .and_then(|f| f.parse::<i64>().ok())
+                    .unwrap_or(0);
+
+                let inner_max_fee = inner
+                    .max_fee
+                    .as_ref()
+                    .and_then(|f| f.parse::<i64>().ok())
+                    .unwrap_or(0);
+
+                let created_at = parse_ingested_timestamp(&tx.created_at, "fee_bump")?;
+
+                Some(FeeBumpTransaction {
+                    transaction_hash: tx.hash.clone(),
+                    ledger_sequence: tx.ledger as i64,
+                    fee_source: tx
+                        .fee_account
+                        .clone()
+                        .unwrap_or_else(|| tx.source_account.clone()),
+                    fee_charged,
+                    max_fee,
+                    inner_transaction_hash: inner.hash.clone(),
+                    inner_max_fee,
+                    signatures_count: fee_bump.signatures.len() as i32,
+                    created_at,
+                    inner_successful: inner_transaction_succeeded(tx, &outcomes),
+                })
+            })
+            .collect()
+    }
+
+    /// Persist a single fee bump transaction using the given executor.
+    /// Returns whether a new row was inserted.
+    pub async fn insert_fee_bump<'e, E>(executor: E, tx: &FeeBumpTransaction) -> Result<bool>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let result = sqlx::query(
             r#"
             INSERT INTO fee_bump_transactions (
                 transaction_hash, ledger_sequence, fee_source, fee_charged, max_fee,
-                inner_transaction_hash, inner_max_fee, signatures_count, created_at
+                inner_transaction_hash, inner_max_fee, signatures_count, created_at,
+                inner_successful
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             ON CONFLICT (transaction_hash) DO NOTHING
             "#,
         )
@@ -97,11 +136,12 @@ impl FeeBumpTrackerService {
         .bind(&tx.inner_transaction_hash)
         .bind(tx.inner_max_fee)
         .bind(tx.signatures_count)
-        .bind(tx.created_at)
-        .execute(&self.pool)
+        .bind(format_timestamp(&tx.created_at))
+        .bind(tx.inner_successful)
+        .execute(executor)
         .await?;
 
-        Ok(())
+        Ok(result.rows_affected() > 0)
     }
 
     /// Get recent fee bump transactions
@@ -122,14 +162,15 @@ impl FeeBumpTrackerService {
 
     /// Get fee bump statistics
     pub async fn get_fee_bump_stats(&self) -> Result<FeeBumpStats> {
-        let row: (i64, f64, i64, i64, i64) = sqlx::query_as(
+        let row: (i64, f64, i64, i64, i64, i64) = sqlx::query_as(
             r#"
             SELECT 
                 COUNT(*) as total_count,
                 COALESCE(AVG(fee_charged), 0.0) as avg_fee,
                 COALESCE(MAX(fee_charged), 0) as max_fee,
                 COALESCE(MIN(fee_charged), 0) as min_fee,
-                COUNT(DISTINCT fee_source) as unique_sources
+                COUNT(DISTINCT fee_source) as unique_sources,
+                COALESCE(SUM(CASE WHEN inner_successful THEN 0 ELSE 1 END), 0) as sponsored_failed
             FROM fee_bump_transactions
             "#,
         )
@@ -142,6 +183,59 @@ impl FeeBumpTrackerService {
             max_fee_charged: row.2,
             min_fee_charged: row.3,
             unique_fee_sources: row.4,
+            sponsored_failed_count: row.5,
         })
     }
+
+    /// Get recent fee bumps whose inner transaction failed
+    pub async fn get_sponsored_failures(&self, limit: i64) -> Result<Vec<FeeBumpTransaction>> {
+        let transactions = sqlx::query_as::<_, FeeBumpTransaction>(
+            r#"
+            SELECT * FROM fee_bump_transactions
+            WHERE inner_successful = 0
+            ORDER BY created_at DESC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(transactions)
+    }
+}
+
+/// Outcome of a fee bump's inner transaction.
+///
+/// Uses the inner transaction's own record when it is in the same batch, then
+/// the wrapper's `result_xdr` (`txFeeBumpInnerSuccess`/`txFeeBumpInnerFailed`),
+/// and finally the wrapper's `successful` flag, which Horizon derives from the
+/// inner result.
+pub fn inner_transaction_succeeded(
+    tx: &HorizonTransaction,
+    outcomes: &HashMap<&str, bool>,
+) -> bool {
+    let inner_hash = tx
+        .inner_transaction
+        .as_ref()
+        .map(|inner| inner.hash.as_str());
+    if let Some(successful) = inner_hash.and_then(|hash| outcomes.get(hash)) {
+        return *successful;
+    }
+
+    tx.result_xdr
+        .as_deref()
+        .and_then(decode_inner_outcome)
+        .unwrap_or(tx.successful)
+}
+
+fn decode_inner_outcome(result_xdr: &str) -> Option<bool> {
+    let bytes = BASE64.decode(result_xdr).ok()?;
+    let result = TransactionResult::from_xdr(bytes, Limits::none()).ok()?;
+
+    match result.result {
+        TransactionResultResult::TxFeeBumpInnerSuccess(_) => Some(true),
+        TransactionResultResult::TxFeeBumpInnerFailed(_) => Some(false),
+        _ => None,
+    }
 }
@@ -0,0 +1,324 @@
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use crate::rpc::{HorizonTransaction, StellarRpcClient};
+
+/// Stellar minimum base fee per operation, in stroops
+pub const BASE_FEE_STROOPS: u64 = 100;
+
+/// Number of recent ledgers sampled for dynamic fee strategies
+const DEFAULT_SAMPLE_LEDGERS: u32 = 3;
+
+/// Percentile used by the percentile strategy when none is configured
+pub const DEFAULT_FEE_PERCENTILE: u8 = 90;
+
+/// How long a dynamic estimate is reused, about one ledger close
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// How network fees are estimated for cost calculations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeEstimationStrategy {
+    /// Always use the network base fee
+    Static,
+    /// Median per-operation fee charged in recent ledgers
+    NetworkMedian,
+    /// Given percentile (1-100) of per-operation fees charged in recent ledgers
+    Percentile(u8),
+}
+
+impl FeeEstimationStrategy {
+    /// Parse a strategy name, using `percentile` for the percentile strategy
+    pub fn parse(name: &str, percentile: u8) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "static" => Some(Self::Static),
+            "median" | "network_median" => Some(Self::NetworkMedian),
+            "percentile" => Some(Self::Percentile(percentile.clamp(1, 100))),
+            _ => None,
+        }
+    }
+
+    /// Short label, e.g. `static`, `network_median` or `p90`
+    pub fn label(&self) -> String {
+        match self {
+            Self::Static => "static".to_string(),
+            Self::NetworkMedian => "network_median".to_string(),
+            Self::Percentile(p) => format!("p{}", p),
+        }
+    }
+}
+
+/// Configuration for fee estimation
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeEstimationConfig {
+    pub strategy: FeeEstimationStrategy,
+    /// Number of most recent ledgers to sample fees from
+    pub sample_ledgers: u32,
+    /// How long an estimate derived from fee data is reused before the
+    /// recent ledgers are sampled again
+    pub cache_ttl: Duration,
+}
+
+impl Default for FeeEstimationConfig {
+    fn default() -> Self {
+        Self {
+            strategy: FeeEstimationStrategy::Static,
+            sample_ledgers: DEFAULT_SAMPLE_LEDGERS,
+            cache_ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+}
+
+/// Network fee estimate used to scale route network fees
+#[derive(Debug, Clone, Serialize)]
+pub struct FeeEstimate {
+    pub strategy: FeeEstimationStrategy,
+    /// Estimated per-operation fee in stroops
+    pub fee_per_operation_stroops: u64,
+    /// Number of fee samples the estimate was derived from
+    pub sample_size: usize,
+}
+
+impl FeeEstimate {
+    /// Ratio of the estimated fee to the network base fee (>= 1.0)
+    pub fn congestion_multiplier(&self) -> f64 {
+        (self.fee_per_operation_stroops as f64 / BASE_FEE_STROOPS as f64).max(1.0)
+    }
+}
+
+/// Derives network fee estimates from recent ledger fee data
+pub struct FeeEstimator {
+    config: FeeEstimationConfig,
+    rpc_client: Option<Arc<StellarRpcClient>>,
+    /// Last estimate derived from fee data and when it was made; held while
+    /// refreshing so concurrent requests share one round of RPC calls
+    cached: Mutex<Option<(Instant, FeeEstimate)>>,
+}
+
+impl FeeEstimator {
+    pub fn new(config: FeeEstimationConfig, rpc_client: Option<Arc<StellarRpcClient>>) -> Self {
+        Self {
+            config,
+            rpc_client,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Estimator that always returns the base fee
+    pub fn static_fee() -> Self {
+        Self::new(FeeEstimationConfig::default(), None)
+    }
+
+    pub fn strategy(&self) -> FeeEstimationStrategy {
+        self.config.strategy
+    }
+
+    /// Estimate the current per-operation fee.
+    ///
+    /// Estimates derived from fee data are reused for `cache_ttl`. Falls back
+    /// to the base fee, without caching it, when the strategy is static, no
+    /// RPC client is configured, or recent fee data cannot be fetched.
+    pub async fn estimate(&self) -> FeeEstimate {
+        let client = match (self.config.strategy, &self.rpc_client) {
+            (FeeEstimationStrategy::Static, _) | (_, None) => {
+                return estimate_from_samples(self.config.strategy, &[]);
+            }
+            (_, Some(client)) => client,
+        };
+
+        let mut cached = self.cached.lock().await;
+        if let Some((at, estimate)) = cached.as_ref() {
+            if at.elapsed() < self.config.cache_ttl {
+                return estimate.clone();
+            }
+        }
+
+        match self.fetch_fee_samples(client).await {
+            Ok(samples) => {
+                let estimate = estimate_from_samples(self.config.strategy, &samples);
+                *cached = Some((Instant::now(), estimate.clone()));
+                estimate
+            }
+            Err(e) => {
+                warn!("Failed to fetch recent fee data, using base fee: {}", e);
+                estimate_from_samples(self.config.strategy, &[])
+            }
+        }
+    }
+
+    async fn fetch_fee_samples(&self, client: &StellarRpcClient) -> Result<Vec<u64>> {
+        let latest = client
+            .fetch_latest_ledger()
+            .await
+            .context("Failed to fetch latest ledger")?;
+
+        let mut samples = Vec::new();
+        for offset in 0..u64::from(self.config.sample_ledgers) {
+            let Some(sequence) = latest.sequence.checked_sub(offset) else {
+                break;
+            };
+            let transactions = client
+                .fetch_transactions_for_ledger(sequence)
+                .await
+                .with_context(|| format!("Failed to fetch transactions for ledger {}", sequence))?;
+            samples.extend(fee_samples(&transactions));
+        }
+
+        debug!("Collected {} fee samples", samples.len());
+        Ok(samples)
+    }
+}
+
+/// Per-operation fees charged by a set of transactions, in stroops
+pub fn fee_samples(transactions: &[HorizonTransaction]) -> Vec<u64> {
+    transactions
+        .iter()
+        .filter_map(|tx| {
+            let charged = tx.fee_charged.as_deref()?.parse::<u64>().ok()?;
+            let operations = u64::from(tx.operation_count.max(1));
+            Some(charged / operations)
+        })
+        .collect()
+}
+
+/// Apply a strategy to per-operation fee samples (in stroops)
+pub fn estimate_from_samples(strategy: FeeEstimationStrategy, samples: &[u64]) -> FeeEstimate {
+    let fee = match strategy {
+        FeeEstimationStrategy::Static => BASE_FEE_STROOPS,
+        FeeEstimationStrategy::NetworkMedian => percentile(samples, 50),
+        FeeEstimationStrategy::Percentile(p) => percentile(samples, p),
+    };
+
+    FeeEstimate {
+        strategy,
+        fee_per_operation_stroops: fee.max(BASE_FEE_STROOPS),
+        sample_size: if strategy == FeeEstimationStrategy::Static {
+            0
+        } else {
+            samples.len()
+        },
+    }
+}
+
+/// Nearest-rank percentile; returns the base fee for empty input
+fn percentile(samples: &[u64], p: u8) -> u64 {
+    if samples.is_empty() {
+        return BASE_FEE_STROOPS;
+    }
+
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+
+    let p = usize::from(p.clamp(1, 100));
+    let rank = (p * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples() -> Vec<u64> {
+        vec![100, 100, 120, 150, 200, 300, 500, 1_000, 5_000, 10_000]
+    }
+
+    #[test]
+    fn test_static_strategy_ignores_samples() {
+        let estimate = estimate_from_samples(FeeEstimationStrategy::Static, &samples());
+        assert_eq!(estimate.fee_per_operation_stroops, BASE_FEE_STROOPS);
+        assert_eq!(estimate.sample_size, 0);
+        assert_eq!(estimate.congestion_multiplier(), 1.0);
+    }
+
+    #[test]
+    fn test_median_strategy() {
+        let estimate = estimate_from_samples(FeeEstimationStrategy::NetworkMedian, &samples());
+        assert_eq!(estimate.fee_per_operation_stroops, 200);
+        assert_eq!(estimate.sample_size, 10);
+        assert_eq!(estimate.congestion_multiplier(), 2.0);
+    }
+
+    #[test]
+    fn test_percentile_strategy() {
+        let p90 = estimate_from_samples(FeeEstimationStrategy::Percentile(90), &samples());
+        assert_eq!(p90.fee_per_operation_stroops, 5_000);
+
+        let p100 = estimate_from_samples(FeeEstimationStrategy::Percentile(100), &samples());
+        assert_eq!(p100.fee_per_operation_stroops, 10_000);
+    }
+
+    #[test]
+    fn test_empty_samples_fall_back_to_base_fee() {
+        let estimate = estimate_from_samples(FeeEstimationStrategy::Percentile(99), &[]);
+        assert_eq!(estimate.fee_per_operation_stroops, BASE_FEE_STROOPS);
+    }
+
+    #[test]
+    fn test_parse_strategy() {
+        assert_eq!(
+            FeeEstimationStrategy::parse("static", 90),
+            Some(FeeEstimationStrategy::Static)
+        );
+        assert_eq!(
+            FeeEstimationStrategy::parse("network_median", 90),
+            Some(FeeEstimationStrategy::NetworkMedian)
+        );
+        assert_eq!(
+            FeeEstimationStrategy::parse("percentile", 250),
+            Some(FeeEstimationStrategy::Percentile(100))
+        );
+        assert_eq!(FeeEstimationStrategy::parse("bogus", 90), None);
+    }
+
+    #[tokio::test]
+    async fn test_estimator_uses_mock_rpc_fee_data() {
+        let rpc = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let estimator = FeeEstimator::new(
+            FeeEstimationConfig {
+                strategy: FeeEstimationStrategy::NetworkMedian,
+                sample_ledgers: 2,
+                cache_ttl: Duration::ZERO,
+            },
+            Some(rpc),
+        );
+
+        let estimate = estimator.estimate().await;
+        assert_eq!(estimate.sample_size, 10);
+        assert_eq!(estimate.fee_per_operation_stroops, 100);
+    }
+
+    #[tokio::test]
+    async fn test_estimate_is_reused_within_cache_ttl() {
+        let rpc = Arc::new(StellarRpcClient::new_with_defaults(true));
+        let config = FeeEstimationConfig {
+            strategy: FeeEstimationStrategy::NetworkMedian,
+            sample_ledgers: 2,
+            cache_ttl: Duration::from_secs(60),
+        };
+        let estimator = FeeEstimator::new(config.clone(), Some(Arc::clone(&rpc)));
+        estimator.estimate().await;
+
+        // Mark the cached estimate so a cache hit is told apart from a refetch
+        let mark = |estimator: &FeeEstimator| {
+            let mut cached = estimator.cached.try_lock().unwrap();
+            cached.as_mut().unwrap().1.fee_per_operation_stroops = 777;
+        };
+        mark(&estimator);
+        assert_eq!(estimator.estimate().await.fee_per_operation_stroops, 777);
+
+        let expiring = FeeEstimator::new(
+            FeeEstimationConfig {
+                cache_ttl: Duration::ZERO,
+                ..config
+            },
+            Some(rpc),
+        );
+        expiring.estimate().await;
+        mark(&expiring);
+        assert_eq!(expiring.estimate().await.fee_per_operation_stroops, 100);
+    }
+}
@@ -0,0 +1,231 @@
+//! Idempotent execution of side-effecting operations.
+//!
+//! Snapshot submission and webhook delivery are both retried, and a retry
+//! must not repeat a side effect that already happened. [`IdempotencyStore`]
+//! records each attempt under an idempotency key: once an operation completes
+//! its result is stored, and later calls with the same key return that result
+//! without running the operation again. Keys are kept for a limited time and
+//! pruned afterwards.
+
+use anyhow::{Context, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::SqlitePool;
+use std::future::Future;
+use std::time::Duration;
+
+/// Default age after which an attempt that never finished may be claimed
+/// again, e.g. after a crash mid-operation
+pub const DEFAULT_CLAIM_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Default time a key is kept after its last attempt
+pub const DEFAULT_KEY_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+/// Another attempt holds the key and has not finished yet
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Operation '{key}' is already in progress")]
+pub struct AttemptInProgress {
+    pub key: String,
+}
+
+/// The key already completed for an operation with a different fingerprint
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Operation '{key}' already completed with different input")]
+pub struct IdempotencyConflict {
+    pub key: String,
+}
+
+#[derive(Clone)]
+pub struct IdempotencyStore {
+    pool: SqlitePool,
+    claim_timeout: Duration,
+    key_ttl: Duration,
+}
+
+impl IdempotencyStore {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self {
+            pool,
+            claim_timeout: DEFAULT_CLAIM_TIMEOUT,
+            key_ttl: DEFAULT_KEY_TTL,
+        }
+    }
+
+    /// Let unfinished attempts be reclaimed after `timeout` instead of
+    /// `DEFAULT_CLAIM_TIMEOUT`
+    pub fn with_claim_timeout(mut self, timeout: Duration) -> Self {
+        self.claim_timeout = timeout;
+        self
+    }
+
+    /// Keep keys for `ttl` after their last attempt instead of
+    /// `DEFAULT_KEY_TTL`
+    pub fn with_key_ttl(mut self, ttl: Duration) -> Self {
+        self.key_ttl = ttl;
+        self
+    }
+
+    /// Run `op` once per `key`.
+    ///
+    /// Returns the stored result when `key` already completed. A failed
+    /// attempt is recorded and may be retried with the same key. Fails with
+    /// [`AttemptInProgress`] while another attempt holds the key.
+    pub async fn submit_idempotent<T, F, Fut>(&self, key: &str, op: F) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        self.run(key, None, op).await
+    }
+
+    /// Run `op` once per `key`, like [`Self::submit_idempotent`], for input
+    /// identified by `fingerprint`.
+    ///
+    /// Fails with [`IdempotencyConflict`] when `key` already completed with
+    /// another fingerprint, rather than returning a result computed for
+    /// different input. A failed attempt may be retried with any fingerprint.
+    pub async fn submit_idempotent_with_fingerprint<T, F, Fut>(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        op: F,
+    ) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        self.run(key, Some(fingerprint), op).await
+    }
+
+    async fn run<T, F, Fut>(&self, key: &str, fingerprint: Option<&str>, op: F) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        if !self.claim(key, fingerprint).await? {
+            return self.completed_result(key, fingerprint).await;
+        }
+
+        match op().await {
+            Ok(value) => {
+                let result = serde_json::to_string(&value)?;
+                sqlx::query(
+                    r#"
+                    UPDATE idempotency_keys
+                    SET status = 'completed', result = ?, last_error = NULL,
+                        updated_at = CURRENT_TIMESTAMP
+                    WHERE key = ?
+                    "#,
+                )
+                .bind(result)
+                .bind(key)
+                .execute(&self.pool)
+                .await
+                .with_context(|| format!("Failed to record completion of '{}'", key))?;
+                Ok(value)
+            }
+            Err(e) => {
+                let recorded = sqlx::query(
+                    r#"
+                    UPDATE idempotency_keys
+                    SET status = 'failed', last_error = ?, updated_at = CURRENT_TIMESTAMP
+                    WHERE key = ?
+                    "#,
+                )
+                .bind(e.to_string())
+                .bind(key)
+                .execute(&self.pool)
+                .await;
+                if let Err(db_error) = recorded {
+                    tracing::warn!("Failed to record failure of '{}': {}", key, db_error);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// Take `key` for a new attempt. `false` when it completed or another
+    /// attempt holds it.
+    async fn claim(&self, key: &str, fingerprint: Option<&str>) -> Result<bool> {
+        let now = chrono::Utc::now().timestamp();
+        let stale_before = now - self.claim_timeout.as_secs() as i64;
+        let claimed = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (key, status, attempts, claimed_at, fingerprint)
+            VALUES (?, 'in_progress', 1, ?, ?)
+            ON CONFLICT(key) DO UPDATE SET
+                status = 'in_progress',
+                attempts = idempotency_keys.attempts + 1,
+                claimed_at = excluded.claimed_at,
+                fingerprint = excluded.fingerprint,
+                updated_at = CURRENT_TIMESTAMP
+            WHERE idempotency_keys.status = 'failed'
+               OR (idempotency_keys.status = 'in_progress'
+                   AND idempotency_keys.claimed_at < ?)
+            "#,
+        )
+        .bind(key)
+        .bind(now)
+        .bind(fingerprint)
+        .bind(stale_before)
+        .execute(&self.pool)
+        .await
+        .with_context(|| format!("Failed to claim idempotency key '{}'", key))?;
+        Ok(claimed.rows_affected() > 0)
+    }
+
+    async fn completed_result<T: DeserializeOwned>(
+        &self,
+        key: &str,
+        fingerprint: Option<&str>,
+    ) -> Result<T> {
+        let (status, result, stored_fingerprint): (String, Option<String>, Option<String>) =
+            sqlx::query_as(
+                "SELECT status, result, fingerprint FROM idempotency_keys WHERE key = ?",
+            )
+            .bind(key)
+            .fetch_one(&self.pool)
+            .await
+            .with_context(|| format!("Failed to read idempotency key '{}'", key))?;
+
+        match (status.as_str(), result) {
+            ("completed", Some(_)) if stored_fingerprint.as_deref() != fingerprint => {
+                tracing::warn!(
+                    "Operation '{}' already completed with a different fingerprint",
+                    key
+                );
+                Err(IdempotencyConflict {
+                    key: key.to_string(),
+                }
+                .into())
+            }
+            ("completed", Some(result)) => {
+                tracing::debug!("Operation '{}' already completed, reusing its result", key);
+                serde_json::from_str(&result)
+                    .with_context(|| format!("Stored result of '{}' does not match", key))
+            }
+            _ => Err(AttemptInProgress {
+                key: key.to_string(),
+            }
+            .into()),
+        }
+    }
+
+    /// Delete keys whose last attempt is older than the key TTL, returning
+    /// how many were deleted. An operation retried after its key expired
+    /// runs again.
+    pub async fn prune_expired(&self) -> Result<u64> {
+        let cutoff = chrono::Utc::now().timestamp() - self.key_ttl.as_secs() as i64;
+        // Compared as text in `CURRENT_TIMESTAMP` form so the index on
+        // `updated_at` can be used
+        let pruned =
+            sqlx::query("DELETE FROM idempotency_keys WHERE updated_at < datetime(?, 'unixepoch')")
+                .bind(cutoff)
+                .execute(&self.pool)
+                .await
+                .context("Failed to prune expired idempotency keys")?;
+        Ok(pruned.rows_affected())
+    }
+}
@@ -1,20 +1,33 @@
 use anyhow::{Context, Result};
-use chrono::DateTime;
 use std::sync::Arc;
 use tracing::info;
 
 use crate::database::Database;
+use crate::ingestion::asset_filter::AssetFilter;
+use crate::models::asset::AssetId;
+use crate::models::timestamp::parse_timestamp;
 use crate::models::PaymentRecord;
 use crate::rpc::StellarRpcClient;
 
 pub struct IndexingService {
     rpc_client: Arc<StellarRpcClient>,
     db: Arc<Database>,
+    asset_filter: AssetFilter,
 }
 
 impl IndexingService {
     pub fn new(rpc_client: Arc<StellarRpcClient>, db: Arc<Database>) -> Self {
-        Self { rpc_client, db }
+        Self {
+            rpc_client,
+            db,
+            asset_filter: AssetFilter::default(),
+        }
+    }
+
+    /// Store only the payments `filter` allows instead of every asset
+    pub fn with_asset_filter(mut self, filter: AssetFilter) -> Self {
+        self.asset_filter = filter;
+        self
     }
 
     /// Run payment ingestion starting from the last saved cursor
@@ -41,14 +54,26 @@ impl IndexingService {
 
         let last_paging_token = payments.last().map(|p| p.paging_token.clone());
 
-        // Normalize payments
+        // Normalize payments, dropping assets the filter leaves out
         let records: Vec<PaymentRecord> = payments
             .into_iter()
+            .filter(|p| {
+                self.asset_filter
+                    .allows(p.asset_code.as_deref(), p.asset_issuer.as_deref())
+            })
             .filter_map(|p| {
                 let amount = p.amount.parse::<f64>().ok()?;
-                let created_at = DateTime::parse_from_rfc3339(&p.created_at)
-                    .ok()?
-                    .with_timezone(&chrono::Utc);
+                let created_at = parse_timestamp(&p.created_at).ok()?;
+                // Only path payments send a different asset than they deliver
+                let (source_asset_code, source_asset_issuer) = match p.source_asset_type.as_deref()
+                {
+                    Some("native") => {
+                        let native = AssetId::native();
+                        (Some(native.code), Some(native.issuer))
+                    }
+                    Some(_) => (p.source_asset_code, p.source_asset_issuer),
+                    None => (p.asset_code.clone(), p.asset_issuer.clone()),
+                };
 
                 Some(PaymentRecord {
                     id: p.id,
@@ -58,8 +83,8 @@ impl IndexingService {
                     asset_type: p.asset_type.clone(),
                     asset_code: p.asset_code.clone(),
                     asset_issuer: p.asset_issuer.clone(),
-                    source_asset_code: p.asset_code.clone().unwrap_or_default(),
-                    source_asset_issuer: p.asset_issuer.clone().unwrap_or_default(),
+                    source_asset_code: source_asset_code.unwrap_or_default(),
+                    source_asset_issuer: source_asset_issuer.unwrap_or_default(),
                     destination_asset_code: p.asset_code.unwrap_or_default(),
                     destination_asset_issuer: p.asset_issuer.unwrap_or_default(),
                     amount,
@@ -4,8 +4,10 @@ use sqlx::{Pool, Sqlite};
 use std::sync::Arc;
 use tracing::info;
 
+use crate::models::asset::normalize_asset_code;
 use crate::models::{LiquidityPool, LiquidityPoolSnapshot, LiquidityPoolStats};
 use crate::rpc::StellarRpcClient;
+use crate::snapshot::normalize_stellar_amount;
 
 pub struct LiquidityPoolAnalyzer {
     pool: Pool<Sqlite>,
@@ -36,6 +38,8 @@ impl LiquidityPoolAnalyzer {
             let (asset_b_code, asset_b_issuer) = Self::parse_asset(&hp.reserves[1].asset);
             let reserve_a: f64 = hp.reserves[0].amount.parse().unwrap_or(0.0);
             let reserve_b: f64 = hp.reserves[1].amount.parse().unwrap_or(0.0);
+            let reserve_a_exact = normalize_stellar_amount(&hp.reserves[0].amount);
+            let reserve_b_exact = normalize_stellar_amount(&hp.reserves[1].amount);
 
             // Estimate total value (simplified: assume both sides equivalent for AMM)
             let total_value_usd = reserve_a + reserve_b; // Simplified valuation
@@ -81,14 +85,17 @@ impl LiquidityPoolAnalyzer {
                     reserve_a_asset_code, reserve_a_asset_issuer, reserve_a_amount,
                     reserve_b_asset_code, reserve_b_asset_issuer, reserve_b_amount,
                     total_value_usd, volume_24h_usd, fees_earned_24h_usd, apy,
-                    impermanent_loss_pct, trade_count_24h, last_synced_at, created_at, updated_at
+                    impermanent_loss_pct, trade_count_24h, last_synced_at, created_at, updated_at,
+                    reserve_a_amount_exact, reserve_b_amount_exact
                 )
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22)
                 ON CONFLICT (pool_id) DO UPDATE SET
                     total_trustlines = excluded.total_trustlines,
                     total_shares = excluded.total_shares,
                     reserve_a_amount = excluded.reserve_a_amount,
                     reserve_b_amount = excluded.reserve_b_amount,
+                    reserve_a_amount_exact = excluded.reserve_a_amount_exact,
+                    reserve_b_amount_exact = excluded.reserve_b_amount_exact,
                     total_value_usd = excluded.total_value_usd,
                     volume_24h_usd = excluded.volume_24h_usd,
                     fees_earned_24h_usd = excluded.fees_earned_24h_usd,
@@ -119,6 +126,8 @@ impl LiquidityPoolAnalyzer {
             .bind(now)
             .bind(now)
             .bind(now)
+            .bind(reserve_a_exact)
+            .bind(reserve_b_exact)
             .execute(&self.pool)
             .await?;
 
@@ -336,9 +345,9 @@ impl LiquidityPoolAnalyzer {
         if asset_str == "native" {
             ("XLM".to_string(), None)
         } else if let Some((code, issuer)) = asset_str.split_once(':') {
-            (code.to_string(), Some(issuer.to_string()))
+            (normalize_asset_code(code), Some(issuer.to_string()))
         } else {
-            (asset_str.to_string(), None)
+            (normalize_asset_code(asset_str), None)
         }
     }
 }
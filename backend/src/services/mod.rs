@@ -1,14 +1,24 @@
 pub mod account_merge_detector;
 pub mod aggregation;
 pub mod analytics;
+pub mod anchor_asset_discovery;
+pub mod anchor_domain_verifier;
 pub mod asset_verifier;
 pub mod contract;
+pub mod event_bus;
 pub mod fee_bump_tracker;
+pub mod fee_estimator;
 pub mod governance;
+pub mod idempotency;
 pub mod indexing;
 pub mod liquidity_pool_analyzer;
+pub mod pair_registry;
+pub mod path_finder;
+pub mod pending_transaction;
 pub mod price_feed;
 pub mod realtime_broadcaster;
+pub mod resource_acl;
+pub mod rpc_health_monitor;
 pub mod slack_bot;
 pub mod snapshot;
 pub mod stellar_toml;
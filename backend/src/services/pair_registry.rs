@@ -0,0 +1,208 @@
+//! Supported asset pairs.
+//!
+//! `SUPPORTED_ASSET_PAIRS` lists the pairs the order-book and cost
+//! comparison endpoints serve, as comma-separated `BASE/COUNTER` entries
+//! (e.g. `XLM/USDC,USDC:GA5Z.../NGN`). Each side is `XLM`, an asset code, or
+//! `CODE:ISSUER`; a bare code matches that code from any issuer. Codes and
+//! issuers are kept as written, since asset codes are case-sensitive. Pairs
+//! match in either direction. Without the variable every pair is served; an
+//! invalid list stops startup.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// One side of a registered pair
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AssetPattern {
+    /// `XLM` for the native asset
+    pub(crate) code: String,
+    pub(crate) issuer: Option<String>,
+}
+
+impl AssetPattern {
+    /// `XLM`, a bare code, or `CODE:ISSUER`; `None` when malformed
+    pub(crate) fn parse(asset: &str) -> Option<Self> {
+        let asset = asset.trim();
+        let (code, issuer) = match asset.split_once(':') {
+            Some((code, issuer)) => (code.trim(), Some(issuer.trim())),
+            None => (asset, None),
+        };
+        if code.is_empty() || issuer.is_some_and(str::is_empty) {
+            return None;
+        }
+        let native = match issuer {
+            Some(issuer) => issuer.eq_ignore_ascii_case("native"),
+            None => code.eq_ignore_ascii_case("XLM") || code.eq_ignore_ascii_case("native"),
+        };
+        if native {
+            return Some(Self::native());
+        }
+        if code.len() > 12 || !code.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return None;
+        }
+
+        Some(Self {
+            code: code.to_string(),
+            issuer: issuer.map(str::to_string),
+        })
+    }
+
+    pub(crate) fn native() -> Self {
+        Self {
+            code: "XLM".to_string(),
+            issuer: None,
+        }
+    }
+
+    /// Same code, and the same issuer when both name one
+    fn matches(&self, other: &Self) -> bool {
+        self.code == other.code
+            && match (&self.issuer, &other.issuer) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            }
+    }
+}
+
+impl fmt::Display for AssetPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.issuer {
+            Some(issuer) => write!(f, "{}:{}", self.code, issuer),
+            None => f.write_str(&self.code),
+        }
+    }
+}
+
+/// A request named a pair outside the registry
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Asset pair {base}/{counter} is not supported")]
+pub struct UnsupportedPair {
+    pub base: String,
+    pub counter: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PairRegistry {
+    /// `None` serves every pair
+    pairs: Option<Vec<(AssetPattern, AssetPattern)>>,
+}
+
+impl PairRegistry {
+    /// A registry serving every pair
+    pub fn unrestricted() -> Self {
+        Self::default()
+    }
+
+    /// Parse `BASE/COUNTER` entries separated by commas
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let mut pairs = Vec::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let pair = entry.split_once('/').and_then(|(base, counter)| {
+                Some((AssetPattern::parse(base)?, AssetPattern::parse(counter)?))
+            });
+            match pair {
+                Some(pair) => pairs.push(pair),
+                None => return Err(format!("invalid asset pair '{}'", entry)),
+            }
+        }
+        Ok(Self { pairs: Some(pairs) })
+    }
+
+    /// Whether only registered pairs are served
+    pub fn is_restricted(&self) -> bool {
+        self.pairs.is_some()
+    }
+
+    /// Fail with [`UnsupportedPair`] unless `base`/`counter` is registered,
+    /// in either order. Assets are `XLM`, a code, or `CODE:ISSUER`.
+    pub fn check(&self, base: &str, counter: &str) -> Result<(), UnsupportedPair> {
+        let Some(pairs) = &self.pairs else {
+            return Ok(());
+        };
+        let unsupported = || UnsupportedPair {
+            base: base.trim().to_string(),
+            counter: counter.trim().to_string(),
+        };
+        let (Some(a), Some(b)) = (AssetPattern::parse(base), AssetPattern::parse(counter)) else {
+            return Err(unsupported());
+        };
+
+        let registered = pairs
+            .iter()
+            .any(|(x, y)| (x.matches(&a) && y.matches(&b)) || (x.matches(&b) && y.matches(&a)));
+        if registered {
+            Ok(())
+        } else {
+            Err(unsupported())
+        }
+    }
+
+    /// Registered pairs as `BASE/COUNTER`
+    pub fn pairs(&self) -> Vec<String> {
+        self.pairs
+            .iter()
+            .flatten()
+            .map(|(base, counter)| format!("{}/{}", base, counter))
+            .collect()
+    }
+}
+
+impl FromStr for PairRegistry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    #[test]
+    fn test_unrestricted_registry_serves_every_pair() {
+        let registry = PairRegistry::unrestricted();
+        assert!(!registry.is_restricted());
+        assert!(registry.check("XLM", "ANY").is_ok());
+    }
+
+    #[test]
+    fn test_registered_pairs_match_in_either_direction() {
+        let registry = PairRegistry::parse(&format!("XLM/{}, USDC/NGN", USDC)).unwrap();
+        assert_eq!(
+            registry.pairs(),
+            [format!("XLM/{}", USDC), "USDC/NGN".into()]
+        );
+
+        assert!(registry.check("native", USDC).is_ok());
+        assert!(registry.check(USDC, "XLM:native").is_ok());
+        // A bare code matches a registered issuer, and vice versa
+        assert!(registry.check("XLM", "USDC").is_ok());
+        assert!(registry.check("NGN", USDC).is_ok());
+
+        assert_eq!(
+            registry.check("XLM", "USDC:GOTHERISSUER"),
+            Err(UnsupportedPair {
+                base: "XLM".to_string(),
+                counter: "USDC:GOTHERISSUER".to_string(),
+            })
+        );
+        assert!(registry.check("XLM", "NGN").is_err());
+        assert!(registry.check("XLM", "").is_err());
+        // Codes are case-sensitive
+        assert!(registry.check("usdc", "NGN").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_entries() {
+        for spec in ["XLM", "XLM/", "/USDC", "XLM/USDC:", "XLM/TOOLONGASSETCODE"] {
+            assert!(PairRegistry::parse(spec).is_err(), "{}", spec);
+        }
+        // An empty list registers no pairs at all
+        let empty = PairRegistry::parse("").unwrap();
+        assert!(empty.is_restricted());
+        assert!(empty.check("XLM", "USDC").is_err());
+    }
+}
@@ -0,0 +1,664 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
+use utoipa::ToSchema;
+
+use crate::models::asset::{validate_asset, AssetId};
+use crate::rpc::{Asset, OrderBook, StellarRpcClient};
+
+/// Default maximum number of conversions in a path
+pub const DEFAULT_MAX_HOPS: usize = 3;
+
+/// Stellar path payments allow at most 5 intermediate assets
+pub const MAX_PATH_HOPS: usize = 6;
+
+/// Number of order book levels fetched per hop
+const ORDER_BOOK_DEPTH: u32 = 50;
+
+const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+/// Circle's EURC issuer on the Stellar public network
+const EURC_ISSUER: &str = "GDHU6WRG4IEQXM5NZ4BMPKOXHW76MZM4Y2IEMFDVXBSDP6SJY4ITNPP2";
+
+/// Why no path estimate could be produced
+#[derive(Debug, thiserror::Error)]
+pub enum PathFinderError {
+    #[error("{0}")]
+    InvalidRequest(&'static str),
+    /// Every order book was fetched and none of the paths can absorb the amount
+    #[error("No path with sufficient liquidity from {from} to {to} within {max_hops} hops")]
+    NoPath {
+        from: String,
+        to: String,
+        max_hops: usize,
+    },
+    /// No path was found, but some order books could not be fetched
+    #[error("Failed to fetch order books: {0}")]
+    Upstream(anyhow::Error),
+}
+
+/// Source of order books used to price each hop
+#[async_trait::async_trait]
+pub trait OrderBookSource: Send + Sync {
+    /// Order book with `selling` as the base asset and `buying` as the counter asset
+    async fn order_book(&self, selling: &Asset, buying: &Asset) -> Result<OrderBook>;
+}
+
+#[async_trait::async_trait]
+impl OrderBookSource for StellarRpcClient {
+    async fn order_book(&self, selling: &Asset, buying: &Asset) -> Result<OrderBook> {
+        self.fetch_order_book(selling, buying, ORDER_BOOK_DEPTH)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch order book: {}", e))
+    }
+}
+
+/// Configuration for path search
+#[derive(Debug, Clone)]
+pub struct PathFinderConfig {
+    /// Maximum number of conversions in a path (1 = direct only)
+    pub max_hops: usize,
+    /// Assets considered as intermediate hops
+    pub intermediates: Vec<Asset>,
+}
+
+impl Default for PathFinderConfig {
+    fn default() -> Self {
+        Self {
+            max_hops: DEFAULT_MAX_HOPS,
+            intermediates: vec![
+                native_asset(),
+                credit_asset("USDC", USDC_ISSUER),
+                credit_asset("EURC", EURC_ISSUER),
+            ],
+        }
+    }
+}
+
+/// A single conversion along a path
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PathHop {
+    pub from_asset: String,
+    pub to_asset: String,
+    pub amount_in: f64,
+    pub amount_out: f64,
+    /// Best available price (to_asset per from_asset)
+    pub best_price: f64,
+    /// Cost of walking the book versus filling everything at the best price
+    pub slippage_bps: f64,
+    /// Network fee of the operation making this conversion
+    pub network_fee_stroops: u64,
+}
+
+/// Cheapest path found between two assets
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PathEstimate {
+    /// Assets visited, including source and destination
+    pub path: Vec<String>,
+    pub hops: Vec<PathHop>,
+    pub source_amount: f64,
+    pub destination_amount: f64,
+    /// Sum of per-hop slippage
+    pub total_slippage_bps: f64,
+    /// Sum of per-hop network fees
+    pub total_network_fee_stroops: u64,
+}
+
+/// Finds multi-hop conversion paths priced against live order books
+pub struct PathFinder {
+    source: Arc<dyn OrderBookSource>,
+    config: PathFinderConfig,
+}
+
+impl PathFinder {
+    pub fn new(source: Arc<dyn OrderBookSource>, config: PathFinderConfig) -> Self {
+        Self { source, config }
+    }
+
+    /// Find the path that delivers the most destination asset for `amount`
+    /// of the source asset, trying every route through the configured
+    /// intermediates up to the hop limit. Each hop is charged
+    /// `fee_per_hop_stroops` of network fee; see [`ranks_above`] for how the
+    /// fee enters the ranking.
+    ///
+    /// Fails with `NoPath` only when every order book involved was fetched;
+    /// if none of the reachable paths work and some books could not be
+    /// fetched, the fetch error is returned as `Upstream`.
+    pub async fn cheapest_path(
+        &self,
+        from: &Asset,
+        to: &Asset,
+        amount: f64,
+        fee_per_hop_stroops: u64,
+    ) -> Result<PathEstimate, PathFinderError> {
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(PathFinderError::InvalidRequest(
+                "Amount must be a positive number",
+            ));
+        }
+        if asset_id(from) == asset_id(to) {
+            return Err(PathFinderError::InvalidRequest(
+                "Source and destination assets must differ",
+            ));
+        }
+
+        let candidates = candidate_paths(from, to, &self.config);
+        let mut books: HashMap<(String, String), Option<OrderBook>> = HashMap::new();
+        let mut fetch_error = None;
+        let mut best: Option<PathEstimate> = None;
+
+        for path in candidates {
+            let Some(estimate) = self
+                .price_path(
+                    &path,
+                    amount,
+                    fee_per_hop_stroops,
+                    &mut books,
+                    &mut fetch_error,
+                )
+                .await
+            else {
+                continue;
+            };
+            let better = match &best {
+                Some(current) => ranks_above(&estimate, current),
+                None => true,
+            };
+            if better {
+                best = Some(estimate);
+            }
+        }
+
+        match (best, fetch_error) {
+            (Some(best), _) => Ok(best),
+            (None, Some(e)) => Err(PathFinderError::Upstream(e)),
+            (None, None) => Err(PathFinderError::NoPath {
+                from: asset_id(from),
+                to: asset_id(to),
+                max_hops: self.config.max_hops,
+            }),
+        }
+    }
+
+    /// Price `path`, fetching books not yet in `books`. The first fetch
+    /// failure is kept in `fetch_error`; paths through that book are skipped.
+    async fn price_path(
+        &self,
+        path: &[Asset],
+        amount: f64,
+        fee_per_hop_stroops: u64,
+        books: &mut HashMap<(String, String), Option<OrderBook>>,
+        fetch_error: &mut Option<anyhow::Error>,
+    ) -> Option<PathEstimate> {
+        let mut hops = Vec::with_capacity(path.len() - 1);
+        let mut amount_in = amount;
+
+        for pair in path.windows(2) {
+            let key = (asset_id(&pair[0]), asset_id(&pair[1]));
+            if !books.contains_key(&key) {
+                let book = match self.source.order_book(&pair[0], &pair[1]).await {
+                    Ok(book) => Some(book),
+                    Err(e) => {
+                        warn!("Failed to fetch order book {}->{}: {}", key.0, key.1, e);
+                        fetch_error.get_or_insert(e);
+                        None
+                    }
+                };
+                books.insert(key.clone(), book);
+            }
+
+            let book = books.get(&key)?.as_ref()?;
+            let fill = sell_into_book(book, amount_in)?;
+            hops.push(PathHop {
+                from_asset: key.0,
+                to_asset: key.1,
+                amount_in,
+                amount_out: fill.received,
+                best_price: fill.best_price,
+                slippage_bps: fill.slippage_bps,
+                network_fee_stroops: fee_per_hop_stroops,
+            });
+            amount_in = fill.received;
+        }
+
+        Some(PathEstimate {
+            path: path.iter().map(asset_id).collect(),
+            total_slippage_bps: hops.iter().map(|h| h.slippage_bps).sum(),
+            total_network_fee_stroops: hops.iter().map(|h| h.network_fee_stroops).sum(),
+            hops,
+            source_amount: amount,
+            destination_amount: amount_in,
+        })
+    }
+}
+
+/// Whether `candidate` is a better route than `current`: the one delivering
+/// more destination asset, or at equal amounts the one with the lower
+/// network fee.
+///
+/// The fee is not netted out of the delivered amount. It is paid in XLM by
+/// the sender on top of the source amount, so it is in neither the source
+/// nor (in general) the destination asset, and pricing it would take another
+/// order book per request. At the base fee a hop costs 0.00001 XLM, well
+/// below the differences in delivered amount between routes.
+fn ranks_above(candidate: &PathEstimate, current: &PathEstimate) -> bool {
+    if candidate.destination_amount != current.destination_amount {
+        return candidate.destination_amount > current.destination_amount;
+    }
+    candidate.total_network_fee_stroops < current.total_network_fee_stroops
+}
+
+/// All simple paths from `from` to `to` through the configured intermediates,
+/// bounded by the hop limit
+fn candidate_paths(from: &Asset, to: &Asset, config: &PathFinderConfig) -> Vec<Vec<Asset>> {
+    let endpoints = [asset_id(from), asset_id(to)];
+    let intermediates: Vec<&Asset> = config
+        .intermediates
+        .iter()
+        .filter(|a| !endpoints.contains(&asset_id(a)))
+        .collect();
+
+    let max_hops = config.max_hops.clamp(1, MAX_PATH_HOPS);
+    let mut paths = Vec::new();
+    let mut current = vec![from.clone()];
+    extend_paths(&mut current, to, &intermediates, max_hops, &mut paths);
+    paths
+}
+
+fn extend_paths(
+    current: &mut Vec<Asset>,
+    to: &Asset,
+    intermediates: &[&Asset],
+    max_hops: usize,
+    paths: &mut Vec<Vec<Asset>>,
+) {
+    let hops_so_far = current.len() - 1;
+    if hops_so_far >= max_hops {
+        return;
+    }
+
+    let mut direct = current.clone();
+    direct.push(to.clone());
+    paths.push(direct);
+
+    if hops_so_far + 1 >= max_hops {
+        return;
+    }
+
+    for next in intermediates {
+        let next_id = asset_id(next);
+        if current.iter().any(|a| asset_id(a) == next_id) {
+            continue;
+        }
+        current.push((*next).clone());
+        extend_paths(current, to, intermediates, max_hops, paths);
+        current.pop();
+    }
+}
+
+/// Result of selling into one order book
+struct BookFill {
+    received: f64,
+    best_price: f64,
+    slippage_bps: f64,
+}
+
+/// Sell `amount` of the book's base asset into its bids.
+///
+/// Horizon quotes bid prices as counter per base and bid amounts in the
+/// counter asset. Returns `None` if the book cannot absorb the full amount.
+fn sell_into_book(book: &OrderBook, amount: f64) -> Option<BookFill> {
+    let mut levels: Vec<(f64, f64)> = book
+        .bids
+        .iter()
+        .filter_map(|entry| {
+            let price = entry.price.parse::<f64>().ok()?;
+            let counter_amount = entry.amount.parse::<f64>().ok()?;
+            (price > 0.0 && counter_amount > 0.0).then_some((price, counter_amount))
+        })
+        .collect();
+    levels.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let best_price = levels.first()?.0;
+    let mut remaining = amount;
+    let mut received = 0.0;
+
+    for (price, counter_amount) in levels {
+        if remaining <= 0.0 {
+            break;
+        }
+        let base_capacity = counter_amount / price;
+        let filled = remaining.min(base_capacity);
+        received += filled * price;
+        remaining -= filled;
+    }
+
+    if remaining > 1e-9 {
+        return None;
+    }
+
+    let ideal = amount * best_price;
+    let slippage_bps = ((ideal - received) / ideal * 10_000.0).max(0.0);
+    Some(BookFill {
+        received,
+        best_price,
+        slippage_bps,
+    })
+}
+
+fn native_asset() -> Asset {
+    Asset {
+        asset_type: "native".to_string(),
+        asset_code: None,
+        asset_issuer: None,
+    }
+}
+
+fn credit_asset(code: &str, issuer: &str) -> Asset {
+    let asset = AssetId {
+        code: code.to_string(),
+        issuer: issuer.to_string(),
+    };
+    Asset {
+        asset_type: asset.asset_type().to_string(),
+        asset_code: Some(asset.code),
+        asset_issuer: Some(asset.issuer),
+    }
+}
+
+/// Parse `XLM`, `XLM:native`, `native` or `CODE:ISSUER` into an asset
+pub fn parse_asset(value: &str) -> Option<Asset> {
+    let asset = match value.trim().split_once(':') {
+        Some((code, issuer)) => validate_asset(code, Some(issuer)),
+        None => validate_asset(value, None),
+    }
+    .ok()?;
+
+    if asset.is_native() {
+        return Some(native_asset());
+    }
+    Some(credit_asset(&asset.code, &asset.issuer.to_uppercase()))
+}
+
+/// Canonical `CODE:ISSUER` identifier (`XLM:native` for lumens)
+pub fn asset_id(asset: &Asset) -> String {
+    if asset.asset_type == "native" {
+        return "XLM:native".to_string();
+    }
+    format!(
+        "{}:{}",
+        asset.asset_code.as_deref().unwrap_or_default(),
+        asset.asset_issuer.as_deref().unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::{OrderBookEntry, Price};
+
+    const ISSUER: &str = "GISSUER";
+
+    const FEE: u64 = 100;
+
+    struct MockBooks {
+        books: HashMap<(String, String), OrderBook>,
+        /// Pairs whose order book fetch fails
+        failing: Vec<(String, String)>,
+    }
+
+    impl MockBooks {
+        fn new() -> Self {
+            Self {
+                books: HashMap::new(),
+                failing: Vec::new(),
+            }
+        }
+
+        fn with_failing_book(mut self, from: &Asset, to: &Asset) -> Self {
+            self.failing.push((asset_id(from), asset_id(to)));
+            self
+        }
+
+        /// Add a book selling `from` for `to` with the given (price, counter amount) bids
+        fn with_book(mut self, from: &Asset, to: &Asset, bids: &[(f64, f64)]) -> Self {
+            let bids = bids
+                .iter()
+                .map(|(price, amount)| OrderBookEntry {
+                    price: price.to_string(),
+                    amount: amount.to_string(),
+                    price_r: Price { n: 1, d: 1 },
+                })
+                .collect();
+            self.books.insert(
+                (asset_id(from), asset_id(to)),
+                OrderBook {
+                    bids,
+                    asks: Vec::new(),
+                    base: from.clone(),
+                    counter: to.clone(),
+                },
+            );
+            self
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl OrderBookSource for MockBooks {
+        async fn order_book(&self, selling: &Asset, buying: &Asset) -> Result<OrderBook> {
+            let key = (asset_id(selling), asset_id(buying));
+            if self.failing.contains(&key) {
+                return Err(anyhow!("Horizon unavailable"));
+            }
+            // Like Horizon, a pair without offers has an empty book
+            Ok(self.books.get(&key).cloned().unwrap_or_else(|| OrderBook {
+                bids: Vec::new(),
+                asks: Vec::new(),
+                base: selling.clone(),
+                counter: buying.clone(),
+            }))
+        }
+    }
+
+    fn ngn() -> Asset {
+        credit_asset("NGN", ISSUER)
+    }
+
+    fn usdc() -> Asset {
+        credit_asset("USDC", ISSUER)
+    }
+
+    fn finder(books: MockBooks, max_hops: usize) -> PathFinder {
+        PathFinder::new(
+            Arc::new(books),
+            PathFinderConfig {
+                max_hops,
+                intermediates: vec![native_asset(), usdc()],
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_two_hop_path_beats_thin_direct_book() {
+        let xlm = native_asset();
+        let books = MockBooks::new()
+            // Direct XLM->NGN: poor price
+            .with_book(&xlm, &ngn(), &[(150.0, 1_000_000.0)])
+            // XLM->USDC->NGN: better combined rate
+            .with_book(&xlm, &usdc(), &[(0.12, 10_000.0)])
+            .with_book(&usdc(), &ngn(), &[(1_550.0, 10_000_000.0)]);
+
+        let estimate = finder(books, 3)
+            .cheapest_path(&xlm, &ngn(), 1_000.0, FEE)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            estimate.path,
+            vec![asset_id(&xlm), asset_id(&usdc()), asset_id(&ngn())]
+        );
+        assert_eq!(estimate.hops.len(), 2);
+        assert!((estimate.destination_amount - 1_000.0 * 0.12 * 1_550.0).abs() < 1e-6);
+        assert!(estimate.total_slippage_bps.abs() < 1e-9);
+        // Every hop pays its own network fee
+        assert!(estimate.hops.iter().all(|h| h.network_fee_stroops == FEE));
+        assert_eq!(estimate.total_network_fee_stroops, 2 * FEE);
+    }
+
+    #[tokio::test]
+    async fn test_direct_path_preferred_when_cheaper() {
+        let xlm = native_asset();
+        let books = MockBooks::new()
+            .with_book(&xlm, &ngn(), &[(190.0, 1_000_000.0)])
+            .with_book(&xlm, &usdc(), &[(0.12, 10_000.0)])
+            .with_book(&usdc(), &ngn(), &[(1_550.0, 10_000_000.0)]);
+
+        let estimate = finder(books, 3)
+            .cheapest_path(&xlm, &ngn(), 1_000.0, FEE)
+            .await
+            .unwrap();
+
+        assert_eq!(estimate.path, vec![asset_id(&xlm), asset_id(&ngn())]);
+        assert!((estimate.destination_amount - 190_000.0).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_lower_fee_breaks_tie_between_equal_paths() {
+        let xlm = native_asset();
+        let eur = credit_asset("EUR", ISSUER);
+        // NGN->XLM->USDC->EUR is tried before NGN->USDC->EUR and delivers
+        // the same amount with one more hop
+        let books = MockBooks::new()
+            .with_book(&ngn(), &xlm, &[(1.0, 1_000_000.0)])
+            .with_book(&xlm, &usdc(), &[(1.0, 1_000_000.0)])
+            .with_book(&ngn(), &usdc(), &[(1.0, 1_000_000.0)])
+            .with_book(&usdc(), &eur, &[(1.0, 1_000_000.0)]);
+
+        let estimate = finder(books, 3)
+            .cheapest_path(&ngn(), &eur, 1_000.0, FEE)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            estimate.path,
+            vec![asset_id(&ngn()), asset_id(&usdc()), asset_id(&eur)]
+        );
+        assert_eq!(estimate.total_network_fee_stroops, 2 * FEE);
+    }
+
+    #[tokio::test]
+    async fn test_slippage_accumulates_when_walking_the_book() {
+        let xlm = native_asset();
+        // First level absorbs 500 XLM at 0.12, the rest fills at 0.11
+        let books = MockBooks::new().with_book(&xlm, &usdc(), &[(0.12, 60.0), (0.11, 1_000.0)]);
+
+        let estimate = finder(books, 1)
+            .cheapest_path(&xlm, &usdc(), 1_000.0, FEE)
+            .await
+            .unwrap();
+
+        assert!((estimate.destination_amount - 115.0).abs() < 1e-9);
+        let expected_bps = (120.0 - 115.0) / 120.0 * 10_000.0;
+        assert!((estimate.total_slippage_bps - expected_bps).abs() < 1e-6);
+    }
+
+    #[tokio::test]
+    async fn test_hop_limit_excludes_longer_paths() {
+        let xlm = native_asset();
+        let books = MockBooks::new()
+            .with_book(&xlm, &usdc(), &[(0.12, 10_000.0)])
+            .with_book(&usdc(), &ngn(), &[(1_550.0, 10_000_000.0)]);
+
+        let result = finder(books, 1)
+            .cheapest_path(&xlm, &ngn(), 1_000.0, FEE)
+            .await;
+        assert!(matches!(result, Err(PathFinderError::NoPath { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_insufficient_liquidity_is_an_error() {
+        let xlm = native_asset();
+        let books = MockBooks::new().with_book(&xlm, &ngn(), &[(150.0, 1_000.0)]);
+
+        let result = finder(books, 3)
+            .cheapest_path(&xlm, &ngn(), 1_000.0, FEE)
+            .await;
+        assert!(matches!(result, Err(PathFinderError::NoPath { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_failed_book_fetch_is_an_upstream_error() {
+        let xlm = native_asset();
+        let books = MockBooks::new()
+            .with_failing_book(&xlm, &ngn())
+            .with_failing_book(&usdc(), &ngn())
+            .with_book(&xlm, &usdc(), &[(0.12, 10_000.0)]);
+
+        let result = finder(books, 3)
+            .cheapest_path(&xlm, &ngn(), 1_000.0, FEE)
+            .await;
+        assert!(matches!(result, Err(PathFinderError::Upstream(_))));
+    }
+
+    #[tokio::test]
+    async fn test_failed_book_fetch_still_returns_other_paths() {
+        let xlm = native_asset();
+        let books = MockBooks::new()
+            .with_failing_book(&xlm, &ngn())
+            .with_book(&xlm, &usdc(), &[(0.12, 10_000.0)])
+            .with_book(&usdc(), &ngn(), &[(1_550.0, 10_000_000.0)]);
+
+        let estimate = finder(books, 3)
+            .cheapest_path(&xlm, &ngn(), 1_000.0, FEE)
+            .await
+            .unwrap();
+        assert_eq!(estimate.hops.len(), 2);
+    }
+
+    #[test]
+    fn test_default_intermediates() {
+        let ids: Vec<String> = PathFinderConfig::default()
+            .intermediates
+            .iter()
+            .map(asset_id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "XLM:native".to_string(),
+                format!("USDC:{}", USDC_ISSUER),
+                format!("EURC:{}", EURC_ISSUER),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_candidate_paths_are_bounded() {
+        let config = PathFinderConfig {
+            max_hops: 2,
+            intermediates: vec![native_asset(), usdc()],
+        };
+        let paths = candidate_paths(&credit_asset("EURC", ISSUER), &ngn(), &config);
+        // direct + one path per intermediate
+        assert_eq!(paths.len(), 3);
+        assert!(paths.iter().all(|p| p.len() <= 3));
+    }
+
+    #[test]
+    fn test_parse_asset() {
+        assert_eq!(asset_id(&parse_asset("XLM").unwrap()), "XLM:native");
+        assert_eq!(asset_id(&parse_asset("xlm:native").unwrap()), "XLM:native");
+        assert_eq!(
+            asset_id(&parse_asset("USDC:gissuer").unwrap()),
+            "USDC:GISSUER"
+        );
+        // Codes keep their case
+        assert_eq!(
+            asset_id(&parse_asset("usdc:GISSUER").unwrap()),
+            "usdc:GISSUER"
+        );
+        assert!(parse_asset("USDC").is_none());
+    }
+}
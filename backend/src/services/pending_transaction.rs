@@ -0,0 +1,236 @@
+//! Multisig signing workflow for pending transactions.
+//!
+//! A pending transaction starts `pending`, becomes `ready` once it holds
+//! `required_signatures` signatures from distinct signers, and `submitted`
+//! after its envelope, with every collected signature attached, is accepted
+//! by the RPC. Submitted transactions take no further signatures.
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use std::str::FromStr;
+use std::sync::Arc;
+use stellar_xdr::curr::{
+    DecoratedSignature, Limits, PublicKey, ReadXdr, Signature, SignatureHint, TransactionEnvelope,
+    VecM, WriteXdr,
+};
+use tracing::info;
+
+use crate::database::Database;
+use crate::models::{PendingTransactionWithSignatures, TransactionResult};
+use crate::rpc::StellarRpcClient;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PendingTransactionStatus {
+    /// Collecting signatures
+    Pending,
+    /// Enough signatures to submit
+    Ready,
+    /// Accepted by the network
+    Submitted,
+}
+
+impl PendingTransactionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Ready => "ready",
+            Self::Submitted => "submitted",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "pending" => Some(Self::Pending),
+            "ready" => Some(Self::Ready),
+            "submitted" => Some(Self::Submitted),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PendingTransactionError {
+    #[error("Pending transaction {0} not found")]
+    NotFound(String),
+    #[error("{signer} has already signed transaction {transaction_id}")]
+    DuplicateSigner {
+        transaction_id: String,
+        signer: String,
+    },
+    #[error("Transaction {0} has already been submitted")]
+    AlreadySubmitted(String),
+    #[error("Transaction {id} has {collected} of {required} required signatures")]
+    NotReady {
+        id: String,
+        collected: usize,
+        required: i32,
+    },
+    #[error("Invalid signature from {signer}: {reason}")]
+    InvalidSignature { signer: String, reason: String },
+}
+
+pub struct PendingTransactionService {
+    db: Arc<Database>,
+    rpc_client: Arc<StellarRpcClient>,
+}
+
+impl PendingTransactionService {
+    pub fn new(db: Arc<Database>, rpc_client: Arc<StellarRpcClient>) -> Self {
+        Self { db, rpc_client }
+    }
+
+    /// Whether `tx` holds enough signatures to be submitted
+    pub fn is_ready(tx: &PendingTransactionWithSignatures) -> bool {
+        tx.collected_signatures.len() as i32 >= tx.transaction.required_signatures
+    }
+
+    /// Store `signer`'s base64 ed25519 signature on transaction `id`, moving it
+    /// to `ready` once enough signatures are collected. Returns the updated
+    /// transaction.
+    pub async fn add_signature(
+        &self,
+        id: &str,
+        signer: &str,
+        signature: &str,
+    ) -> Result<PendingTransactionWithSignatures> {
+        decorated_signature(signer, signature)?;
+
+        let tx = self.load(id).await?;
+        if status(&tx) == Some(PendingTransactionStatus::Submitted) {
+            return Err(PendingTransactionError::AlreadySubmitted(id.to_string()).into());
+        }
+        let duplicate = || PendingTransactionError::DuplicateSigner {
+            transaction_id: id.to_string(),
+            signer: signer.to_string(),
+        };
+        if tx.collected_signatures.iter().any(|s| s.signer == signer) {
+            return Err(duplicate().into());
+        }
+
+        // A concurrent signature from the same signer hits the unique index
+        if let Err(e) = self
+            .db
+            .add_transaction_signature(id, signer, signature)
+            .await
+        {
+            let is_duplicate = e
+                .downcast_ref::<sqlx::Error>()
+                .and_then(|e| e.as_database_error())
+                .is_some_and(|e| e.is_unique_violation());
+            return Err(if is_duplicate { duplicate().into() } else { e });
+        }
+
+        let mut tx = self.load(id).await?;
+        if Self::is_ready(&tx) && status(&tx) == Some(PendingTransactionStatus::Pending) {
+            self.set_status(&mut tx, PendingTransactionStatus::Ready)
+                .await?;
+        }
+        Ok(tx)
+    }
+
+    /// Attach the collected signatures to transaction `id` and submit it via
+    /// RPC. Fails with [`PendingTransactionError::NotReady`] while signatures
+    /// are missing; a failed submission leaves the transaction `ready`.
+    pub async fn submit_when_ready(&self, id: &str) -> Result<TransactionResult> {
+        let mut tx = self.load(id).await?;
+        if status(&tx) == Some(PendingTransactionStatus::Submitted) {
+            return Err(PendingTransactionError::AlreadySubmitted(id.to_string()).into());
+        }
+        if !Self::is_ready(&tx) {
+            return Err(PendingTransactionError::NotReady {
+                id: id.to_string(),
+                collected: tx.collected_signatures.len(),
+                required: tx.transaction.required_signatures,
+            }
+            .into());
+        }
+
+        let envelope_xdr = signed_envelope(&tx)?;
+        let hash = self
+            .rpc_client
+            .send_transaction(&envelope_xdr)
+            .await
+            .with_context(|| format!("Failed to submit transaction {}", id))?;
+
+        self.set_status(&mut tx, PendingTransactionStatus::Submitted)
+            .await?;
+        info!("Submitted pending transaction {} as {}", id, hash);
+        Ok(TransactionResult {
+            hash,
+            status: PendingTransactionStatus::Submitted.as_str().to_string(),
+        })
+    }
+
+    async fn load(&self, id: &str) -> Result<PendingTransactionWithSignatures> {
+        self.db
+            .get_pending_transaction(id)
+            .await?
+            .ok_or_else(|| PendingTransactionError::NotFound(id.to_string()).into())
+    }
+
+    async fn set_status(
+        &self,
+        tx: &mut PendingTransactionWithSignatures,
+        status: PendingTransactionStatus,
+    ) -> Result<()> {
+        self.db
+            .update_transaction_status(&tx.transaction.id, status.as_str())
+            .await?;
+        tx.transaction.status = status.as_str().to_string();
+        Ok(())
+    }
+}
+
+fn status(tx: &PendingTransactionWithSignatures) -> Option<PendingTransactionStatus> {
+    PendingTransactionStatus::parse(&tx.transaction.status)
+}
+
+/// Signature entry for a `G...` signer and a base64 64-byte signature
+fn decorated_signature(signer: &str, signature: &str) -> Result<DecoratedSignature> {
+    let invalid = |reason: &str| PendingTransactionError::InvalidSignature {
+        signer: signer.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let PublicKey::PublicKeyTypeEd25519(key) =
+        PublicKey::from_str(signer).map_err(|_| invalid("signer is not a public key"))?;
+    let bytes = BASE64
+        .decode(signature.trim())
+        .map_err(|_| invalid("signature is not base64"))?;
+    if bytes.len() != 64 {
+        return Err(invalid("signature is not 64 bytes").into());
+    }
+
+    // The hint is the last four bytes of the signer's key
+    let mut hint = [0u8; 4];
+    hint.copy_from_slice(&key.0[28..]);
+    Ok(DecoratedSignature {
+        hint: SignatureHint(hint),
+        signature: Signature(bytes.try_into()?),
+    })
+}
+
+/// The transaction's envelope with every collected signature attached
+fn signed_envelope(tx: &PendingTransactionWithSignatures) -> Result<String> {
+    let mut envelope = BASE64
+        .decode(tx.transaction.xdr.trim())
+        .ok()
+        .and_then(|bytes| TransactionEnvelope::from_xdr(bytes, Limits::none()).ok())
+        .with_context(|| format!("Transaction {} has an invalid envelope", tx.transaction.id))?;
+    let signatures = match &mut envelope {
+        TransactionEnvelope::TxV0(e) => &mut e.signatures,
+        TransactionEnvelope::Tx(e) => &mut e.signatures,
+        TransactionEnvelope::TxFeeBump(e) => &mut e.signatures,
+    };
+
+    let mut attached = signatures.to_vec();
+    for collected in &tx.collected_signatures {
+        let signature = decorated_signature(&collected.signer, &collected.signature)?;
+        if !attached.contains(&signature) {
+            attached.push(signature);
+        }
+    }
+    *signatures = VecM::try_from(attached).context("Too many signatures for one envelope")?;
+
+    Ok(BASE64.encode(envelope.to_xdr(Limits::none())?))
+}
@@ -1,12 +1,27 @@
 use anyhow::{Context, Result};
 use async_lock::RwLock;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
 use tracing::{debug, error, info, warn};
 
+use crate::services::event_bus::{DomainEvent, EventBus};
+
+/// Default age in seconds after which a price is stale (1 hour)
+const DEFAULT_STALENESS_SECONDS: u64 = 3600;
+
+/// Consecutive readings at a new level, each within the change limit of the
+/// one before, after which a move beyond the change limit is accepted
+const CONFIRMING_READINGS: u32 = 3;
+
+/// Provider names accepted in the price feed configuration
+pub const PROVIDER_NAMES: &[&str] = &["coingecko", "coincap"];
+
+const COINCAP_API_URL: &str = "https://rest.coincap.io/v3";
+
 /// Configuration for price feed service
 #[derive(Debug, Clone)]
 pub struct PriceFeedConfig {
@@ -18,6 +33,22 @@ pub struct PriceFeedConfig {
     pub cache_ttl_seconds: u64,
     /// Request timeout in seconds
     pub request_timeout_seconds: u64,
+    /// CoinCap API key, used whether CoinCap is the primary or a fallback
+    pub coincap_api_key: Option<String>,
+    /// Providers tried in order when the primary fails or is implausible
+    pub fallback_providers: Vec<String>,
+    /// Fail over when a price deviates from the next-ranked source by more
+    /// than this percentage (disabled when `None`)
+    pub max_deviation_percent: Option<f64>,
+    /// Hold on to the last known price when a fresh one moves away from it by
+    /// more than this percentage (disabled when `None`)
+    pub max_price_change_percent: Option<f64>,
+    /// Age in seconds after which a price is stale, for assets without
+    /// their own threshold
+    pub staleness_seconds: u64,
+    /// Staleness thresholds per Stellar asset, for assets priced at a
+    /// different cadence
+    pub asset_staleness_seconds: HashMap<String, u64>,
 }
 
 impl Default for PriceFeedConfig {
@@ -27,25 +58,45 @@ impl Default for PriceFeedConfig {
             api_key: None,
             cache_ttl_seconds: 900, // 15 minutes
             request_timeout_seconds: 10,
+            coincap_api_key: None,
+            fallback_providers: Vec::new(),
+            max_deviation_percent: None,
+            max_price_change_percent: None,
+            staleness_seconds: DEFAULT_STALENESS_SECONDS,
+            asset_staleness_seconds: HashMap::new(),
         }
     }
 }
 
 impl PriceFeedConfig {
-    pub fn from_env() -> Self {
-        Self {
-            provider: std::env::var("PRICE_FEED_PROVIDER")
-                .unwrap_or_else(|_| "coingecko".to_string()),
-            api_key: std::env::var("PRICE_FEED_API_KEY").ok(),
-            cache_ttl_seconds: std::env::var("PRICE_FEED_CACHE_TTL_SECONDS")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(900),
-            request_timeout_seconds: std::env::var("PRICE_FEED_REQUEST_TIMEOUT_SECONDS")
-                .ok()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(10),
+    /// Parse an `ASSET=SECONDS` staleness entry, e.g. `XLM:native=600`
+    pub fn parse_staleness_entry(entry: &str) -> Option<(String, u64)> {
+        let (asset, seconds) = entry.rsplit_once('=')?;
+        let asset = asset.trim();
+        let seconds = seconds.trim().parse().ok()?;
+        (!asset.is_empty()).then(|| (asset.to_string(), seconds))
+    }
+
+    /// API key for a provider: CoinCap has its own key, and the primary
+    /// provider falls back to the general one
+    fn api_key_for(&self, provider: &str) -> Option<String> {
+        if provider == "coincap" && self.coincap_api_key.is_some() {
+            return self.coincap_api_key.clone();
         }
+        if provider == self.provider {
+            return self.api_key.clone();
+        }
+        None
+    }
+
+    /// Staleness threshold of a Stellar asset
+    pub fn staleness_threshold(&self, stellar_asset: &str) -> Duration {
+        let seconds = self
+            .asset_staleness_seconds
+            .get(stellar_asset)
+            .copied()
+            .unwrap_or(self.staleness_seconds);
+        Duration::from_secs(seconds)
     }
 }
 
@@ -53,7 +104,64 @@ impl PriceFeedConfig {
 #[derive(Debug, Clone)]
 struct CachedPrice {
     price_usd: f64,
+    source: String,
     timestamp: Instant,
+    /// Whether a stale alert has been sent for this price
+    stale_alerted: bool,
+    /// Latest fetched price held back for moving too far from this one
+    rejected_price_usd: Option<f64>,
+    /// Consecutive held back readings consistent with `rejected_price_usd`
+    rejected_readings: u32,
+}
+
+impl CachedPrice {
+    fn new(sourced: &SourcedPrice) -> Self {
+        Self {
+            price_usd: sourced.price_usd,
+            source: sourced.source.clone(),
+            timestamp: Instant::now(),
+            stale_alerted: false,
+            rejected_price_usd: None,
+            rejected_readings: 0,
+        }
+    }
+
+    fn sourced(&self) -> SourcedPrice {
+        SourcedPrice {
+            price_usd: self.price_usd,
+            source: self.source.clone(),
+        }
+    }
+}
+
+/// A price together with the provider it came from
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourcedPrice {
+    pub price_usd: f64,
+    pub source: String,
+}
+
+/// A price with its age, checked against the asset's staleness threshold
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceStatus {
+    pub price: SourcedPrice,
+    /// Seconds since the price was fetched
+    pub age_seconds: u64,
+    pub staleness_threshold_seconds: u64,
+    pub stale: bool,
+    /// Latest fetched price, when it was rejected for moving too far from
+    /// the price served instead
+    pub rejected_price_usd: Option<f64>,
+}
+
+/// Sent once when a served price exceeds its asset's staleness threshold
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StalePriceAlert {
+    pub asset: String,
+    pub source: String,
+    pub age_seconds: u64,
+    pub staleness_threshold_seconds: u64,
+    pub timestamp: String,
 }
 
 /// Trait for price feed providers
@@ -174,12 +282,126 @@ impl PriceFeedProvider for CoinGeckoProvider {
     }
 }
 
-/// Main price feed client with caching
+/// CoinCap v3 provider implementation; its asset ids match CoinGecko's for
+/// the default asset mapping. The v3 API rejects requests without a key.
+pub struct CoinCapProvider {
+    client: Client,
+    api_key: Option<String>,
+}
+
+impl CoinCapProvider {
+    pub fn new(api_key: Option<String>, timeout: Duration) -> Self {
+        let client = Client::builder()
+            .timeout(timeout)
+            .build()
+            .expect("Failed to create HTTP client");
+
+        if api_key.is_none() {
+            warn!("CoinCap provider has no API key; set COINCAP_API_KEY");
+        }
+
+        Self { client, api_key }
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let mut request = self.client.get(url);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .context("Failed to send request to CoinCap")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("CoinCap API error: {} - {}", status, body);
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse CoinCap response")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CoinCapAsset {
+    id: String,
+    price_usd: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CoinCapResponse<T> {
+    data: T,
+}
+
+#[async_trait::async_trait]
+impl PriceFeedProvider for CoinCapProvider {
+    async fn fetch_price(&self, asset_id: &str) -> Result<f64> {
+        let url = format!("{}/assets/{}", COINCAP_API_URL, asset_id);
+        let response: CoinCapResponse<CoinCapAsset> = self.get(&url).await?;
+
+        response
+            .data
+            .price_usd
+            .and_then(|p| p.parse().ok())
+            .ok_or_else(|| anyhow::anyhow!("Price not found for asset: {}", asset_id))
+    }
+
+    async fn fetch_prices(&self, asset_ids: &[String]) -> Result<HashMap<String, f64>> {
+        if asset_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let url = format!("{}/assets?ids={}", COINCAP_API_URL, asset_ids.join(","));
+        let response: CoinCapResponse<Vec<CoinCapAsset>> = self.get(&url).await?;
+
+        Ok(response
+            .data
+            .into_iter()
+            .filter_map(|asset| Some((asset.id, asset.price_usd?.parse().ok()?)))
+            .collect())
+    }
+
+    fn name(&self) -> &str {
+        "CoinCap"
+    }
+}
+
+/// Build a provider by configuration name
+fn build_provider(
+    name: &str,
+    api_key: Option<String>,
+    timeout: Duration,
+) -> Option<Arc<dyn PriceFeedProvider>> {
+    match name {
+        "coingecko" => Some(Arc::new(CoinGeckoProvider::new(api_key, timeout))),
+        "coincap" => Some(Arc::new(CoinCapProvider::new(api_key, timeout))),
+        _ => None,
+    }
+}
+
+/// Whether a provider price can be used at all
+fn is_plausible(price: f64) -> bool {
+    price.is_finite() && price > 0.0
+}
+
+/// Percentage difference of `price` from `reference`
+fn deviation_percent(price: f64, reference: f64) -> f64 {
+    ((price - reference) / reference).abs() * 100.0
+}
+
+/// Main price feed client with caching and ranked provider failover
 pub struct PriceFeedClient {
-    provider: Arc<dyn PriceFeedProvider>,
+    providers: Vec<Arc<dyn PriceFeedProvider>>,
     cache: Arc<RwLock<HashMap<String, CachedPrice>>>,
     asset_mapping: Arc<HashMap<String, String>>,
     config: PriceFeedConfig,
+    stale_alerts: broadcast::Sender<StalePriceAlert>,
 }
 
 impl PriceFeedClient {
@@ -187,32 +409,226 @@ impl PriceFeedClient {
     pub fn new(config: PriceFeedConfig, asset_mapping: HashMap<String, String>) -> Self {
         let timeout = Duration::from_secs(config.request_timeout_seconds);
 
-        let provider: Arc<dyn PriceFeedProvider> = match config.provider.as_str() {
-            "coingecko" => Arc::new(CoinGeckoProvider::new(config.api_key.clone(), timeout)),
-            _ => {
-                warn!(
-                    "Unknown provider '{}', defaulting to CoinGecko",
-                    config.provider
-                );
-                Arc::new(CoinGeckoProvider::new(config.api_key.clone(), timeout))
+        let primary = build_provider(
+            &config.provider,
+            config.api_key_for(&config.provider),
+            timeout,
+        )
+        .unwrap_or_else(|| {
+            warn!(
+                "Unknown provider '{}', defaulting to CoinGecko",
+                config.provider
+            );
+            Arc::new(CoinGeckoProvider::new(config.api_key.clone(), timeout))
+        });
+
+        let mut providers = vec![primary];
+        for name in &config.fallback_providers {
+            match build_provider(name, config.api_key_for(name), timeout) {
+                Some(provider) => providers.push(provider),
+                None => warn!("Unknown fallback price provider '{}', skipping", name),
             }
-        };
+        }
+
+        Self::from_ranked(config, asset_mapping, providers)
+    }
+
+    /// Create a client over explicitly ranked providers, primary first
+    pub fn with_providers(
+        config: PriceFeedConfig,
+        asset_mapping: HashMap<String, String>,
+        providers: Vec<Arc<dyn PriceFeedProvider>>,
+    ) -> Result<Self> {
+        if providers.is_empty() {
+            anyhow::bail!("at least one price provider is required");
+        }
+
+        Ok(Self::from_ranked(config, asset_mapping, providers))
+    }
 
+    fn from_ranked(
+        config: PriceFeedConfig,
+        asset_mapping: HashMap<String, String>,
+        providers: Vec<Arc<dyn PriceFeedProvider>>,
+    ) -> Self {
         info!(
-            "Initialized price feed client with provider: {}",
-            provider.name()
+            "Initialized price feed client with providers: {}",
+            providers
+                .iter()
+                .map(|p| p.name())
+                .collect::<Vec<_>>()
+                .join(", ")
         );
 
         Self {
-            provider,
+            providers,
             cache: Arc::new(RwLock::new(HashMap::new())),
             asset_mapping: Arc::new(asset_mapping),
             config,
+            stale_alerts: broadcast::channel(100).0,
+        }
+    }
+
+    /// Alerts for prices served past their staleness threshold
+    pub fn subscribe_stale_alerts(&self) -> broadcast::Receiver<StalePriceAlert> {
+        self.stale_alerts.subscribe()
+    }
+
+    /// Publish stale price alerts on the event bus, where webhook
+    /// subscribers receive them, until shutdown
+    pub async fn run_stale_alert_forwarder(
+        &self,
+        event_bus: Arc<EventBus>,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) {
+        let mut alerts = self.subscribe_stale_alerts();
+        loop {
+            tokio::select! {
+                alert = alerts.recv() => match alert {
+                    Ok(alert) => {
+                        event_bus.publish(DomainEvent::PriceStale(alert));
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Stale price alert forwarder lagged, skipped {} alerts", skipped);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                _ = shutdown_rx.recv() => break,
+            }
         }
     }
 
+    /// Cache a freshly fetched price and return the price to serve. A price
+    /// that moves from the last known one by more than the configured
+    /// percentage is rejected: the last known price stays cached, keeping
+    /// its age, and is served instead. The move is accepted once
+    /// `CONFIRMING_READINGS` consecutive readings agree on the new level.
+    fn accept_price(
+        &self,
+        cache: &mut HashMap<String, CachedPrice>,
+        stellar_asset: &str,
+        sourced: SourcedPrice,
+    ) -> SourcedPrice {
+        if let (Some(max_change), Some(cached)) = (
+            self.config.max_price_change_percent,
+            cache.get_mut(stellar_asset),
+        ) {
+            let change = deviation_percent(sourced.price_usd, cached.price_usd);
+            if change > max_change {
+                cached.rejected_readings = match cached.rejected_price_usd {
+                    Some(rejected)
+                        if deviation_percent(sourced.price_usd, rejected) <= max_change =>
+                    {
+                        cached.rejected_readings + 1
+                    }
+                    _ => 1,
+                };
+                cached.rejected_price_usd = Some(sourced.price_usd);
+
+                if cached.rejected_readings < CONFIRMING_READINGS {
+                    warn!(
+                        "Rejecting price {} for {} from {}: moved {:.2}% from last known price {}",
+                        sourced.price_usd, stellar_asset, sourced.source, change, cached.price_usd
+                    );
+                    return cached.sourced();
+                }
+
+                info!(
+                    "Accepting price {} for {} from {} after {} consistent readings",
+                    sourced.price_usd, stellar_asset, sourced.source, cached.rejected_readings
+                );
+            }
+        }
+
+        cache.insert(stellar_asset.to_string(), CachedPrice::new(&sourced));
+        sourced
+    }
+
+    /// Fetch a price from the highest-ranked provider that returns a usable
+    /// one. A provider is skipped when it errors, returns a non-positive
+    /// price, or deviates from the next-ranked provider by more than the
+    /// configured threshold.
+    async fn fetch_with_failover(&self, asset_id: &str) -> Result<SourcedPrice> {
+        let mut last_error = None;
+        let mut prefetched: Option<f64> = None;
+
+        for (rank, provider) in self.providers.iter().enumerate() {
+            let price = match prefetched.take() {
+                Some(price) => price,
+                None => match provider.fetch_price(asset_id).await {
+                    Ok(price) => price,
+                    Err(e) => {
+                        warn!(
+                            "Price source {} failed for {}: {}",
+                            provider.name(),
+                            asset_id,
+                            e
+                        );
+                        last_error = Some(e);
+                        continue;
+                    }
+                },
+            };
+
+            if !is_plausible(price) {
+                warn!(
+                    "Price source {} returned implausible price {} for {}",
+                    provider.name(),
+                    price,
+                    asset_id
+                );
+                last_error = Some(anyhow::anyhow!(
+                    "Implausible price {} from {}",
+                    price,
+                    provider.name()
+                ));
+                continue;
+            }
+
+            if let (Some(max_deviation), Some(next)) = (
+                self.config.max_deviation_percent,
+                self.providers.get(rank + 1),
+            ) {
+                if let Ok(reference) = next.fetch_price(asset_id).await {
+                    let deviation = deviation_percent(price, reference);
+                    if is_plausible(reference) && deviation > max_deviation {
+                        warn!(
+                            "Price from {} for {} deviates {:.2}% from {}, failing over",
+                            provider.name(),
+                            asset_id,
+                            deviation,
+                            next.name()
+                        );
+                        last_error = Some(anyhow::anyhow!(
+                            "Price from {} deviates {:.2}% from {}",
+                            provider.name(),
+                            deviation,
+                            next.name()
+                        ));
+                        prefetched = Some(reference);
+                        continue;
+                    }
+                }
+            }
+
+            return Ok(SourcedPrice {
+                price_usd: price,
+                source: provider.name().to_string(),
+            });
+        }
+
+        Err(last_error.unwrap_or_else(|| anyhow::anyhow!("No price sources configured")))
+    }
+
     /// Get price for a Stellar asset, returns USD value
     pub async fn get_price(&self, stellar_asset: &str) -> Result<f64> {
+        self.get_sourced_price(stellar_asset)
+            .await
+            .map(|p| p.price_usd)
+    }
+
+    /// Get price for a Stellar asset along with the provider it came from
+    pub async fn get_sourced_price(&self, stellar_asset: &str) -> Result<SourcedPrice> {
         // Check cache first
         {
             let cache = self.cache.read().await;
@@ -220,7 +636,7 @@ impl PriceFeedClient {
                 let age = cached.timestamp.elapsed();
                 if age.as_secs() < self.config.cache_ttl_seconds {
                     debug!("Cache hit for {}: ${}", stellar_asset, cached.price_usd);
-                    return Ok(cached.price_usd);
+                    return Ok(cached.sourced());
                 }
             }
         }
@@ -231,21 +647,16 @@ impl PriceFeedClient {
             .get(stellar_asset)
             .ok_or_else(|| anyhow::anyhow!("No mapping found for asset: {}", stellar_asset))?;
 
-        // Fetch from provider
+        // Fetch from the ranked providers
         debug!("Fetching price for {} ({})", stellar_asset, asset_id);
-        match self.provider.fetch_price(asset_id).await {
-            Ok(price) => {
-                // Update cache
-                let mut cache = self.cache.write().await;
-                cache.insert(
-                    stellar_asset.to_string(),
-                    CachedPrice {
-                        price_usd: price,
-                        timestamp: Instant::now(),
-                    },
+        match self.fetch_with_failover(asset_id).await {
+            Ok(sourced) => {
+                info!(
+                    "Fetched price for {}: ${} from {}",
+                    stellar_asset, sourced.price_usd, sourced.source
                 );
-                info!("Fetched price for {}: ${}", stellar_asset, price);
-                Ok(price)
+                let mut cache = self.cache.write().await;
+                Ok(self.accept_price(&mut cache, stellar_asset, sourced))
             }
             Err(e) => {
                 error!("Failed to fetch price for {}: {}", stellar_asset, e);
@@ -258,7 +669,7 @@ impl PriceFeedClient {
                         stellar_asset,
                         cached.timestamp.elapsed()
                     );
-                    return Ok(cached.price_usd);
+                    return Ok(cached.sourced());
                 }
 
                 Err(e)
@@ -266,6 +677,49 @@ impl PriceFeedClient {
         }
     }
 
+    /// Get price for a Stellar asset with its age and staleness. The first
+    /// time a price is served stale an alert is sent.
+    pub async fn get_price_status(&self, stellar_asset: &str) -> Result<PriceStatus> {
+        let price = self.get_sourced_price(stellar_asset).await?;
+        let threshold = self.config.staleness_threshold(stellar_asset);
+
+        let mut cache = self.cache.write().await;
+        let (age, rejected_price_usd) = cache
+            .get(stellar_asset)
+            .map(|cached| (cached.timestamp.elapsed(), cached.rejected_price_usd))
+            .unwrap_or_default();
+        let stale = age > threshold;
+        if stale {
+            if let Some(cached) = cache.get_mut(stellar_asset) {
+                if !cached.stale_alerted {
+                    cached.stale_alerted = true;
+                    warn!(
+                        "Price for {} from {} is stale: {}s old, threshold {}s",
+                        stellar_asset,
+                        price.source,
+                        age.as_secs(),
+                        threshold.as_secs()
+                    );
+                    let _ = self.stale_alerts.send(StalePriceAlert {
+                        asset: stellar_asset.to_string(),
+                        source: price.source.clone(),
+                        age_seconds: age.as_secs(),
+                        staleness_threshold_seconds: threshold.as_secs(),
+                        timestamp: chrono::Utc::now().to_rfc3339(),
+                    });
+                }
+            }
+        }
+
+        Ok(PriceStatus {
+            price,
+            age_seconds: age.as_secs(),
+            staleness_threshold_seconds: threshold.as_secs(),
+            stale,
+            rejected_price_usd,
+        })
+    }
+
     /// Get prices for multiple Stellar assets
     pub async fn get_prices(&self, stellar_assets: &[String]) -> HashMap<String, f64> {
         let mut result = HashMap::new();
@@ -291,44 +745,83 @@ impl PriceFeedClient {
         }
 
         // Map to provider asset IDs
-        let provider_ids: Vec<String> = to_fetch
+        let mut pending: Vec<(String, String)> = to_fetch
             .iter()
-            .filter_map(|asset| self.asset_mapping.get(asset).cloned())
+            .filter_map(|asset| Some((asset.clone(), self.asset_mapping.get(asset)?.clone())))
             .collect();
 
-        if provider_ids.is_empty() {
-            return result;
-        }
-
-        // Fetch from provider
-        match self.provider.fetch_prices(&provider_ids).await {
-            Ok(prices) => {
-                let mut cache = self.cache.write().await;
+        // Fetch from the ranked providers; each asset is taken from the first
+        // provider that returns a usable price for it
+        for (rank, provider) in self.providers.iter().enumerate() {
+            if pending.is_empty() {
+                break;
+            }
 
-                // Map back to Stellar assets and update cache
-                for (stellar_asset, provider_id) in to_fetch.iter().zip(provider_ids.iter()) {
-                    if let Some(&price) = prices.get(provider_id) {
-                        cache.insert(
-                            stellar_asset.clone(),
-                            CachedPrice {
-                                price_usd: price,
-                                timestamp: Instant::now(),
-                            },
+            let ids: Vec<String> = pending.iter().map(|(_, id)| id.clone()).collect();
+            let prices = match provider.fetch_prices(&ids).await {
+                Ok(prices) => prices,
+                Err(e) => {
+                    error!("Failed to fetch prices from {}: {}", provider.name(), e);
+                    continue;
+                }
+            };
+
+            let reference = match (
+                self.config.max_deviation_percent,
+                self.providers.get(rank + 1),
+            ) {
+                (Some(_), Some(next)) => next.fetch_prices(&ids).await.unwrap_or_default(),
+                _ => HashMap::new(),
+            };
+
+            let mut cache = self.cache.write().await;
+            pending.retain(|(stellar_asset, provider_id)| {
+                let Some(&price) = prices.get(provider_id) else {
+                    return true;
+                };
+                if !is_plausible(price) {
+                    warn!(
+                        "{} returned implausible price {} for {}",
+                        provider.name(),
+                        price,
+                        stellar_asset
+                    );
+                    return true;
+                }
+                if let (Some(max_deviation), Some(&reference)) = (
+                    self.config.max_deviation_percent,
+                    reference.get(provider_id),
+                ) {
+                    if is_plausible(reference)
+                        && deviation_percent(price, reference) > max_deviation
+                    {
+                        warn!(
+                            "Price from {} for {} deviates beyond {}%, failing over",
+                            provider.name(),
+                            stellar_asset,
+                            max_deviation
                         );
-                        result.insert(stellar_asset.clone(), price);
+                        return true;
                     }
                 }
-            }
-            Err(e) => {
-                error!("Failed to fetch prices: {}", e);
 
-                // Use stale cache as fallback
-                let cache = self.cache.read().await;
-                for asset in &to_fetch {
-                    if let Some(cached) = cache.get(asset) {
-                        warn!("Using stale cache for {}", asset);
-                        result.insert(asset.clone(), cached.price_usd);
-                    }
+                let sourced = SourcedPrice {
+                    price_usd: price,
+                    source: provider.name().to_string(),
+                };
+                let served = self.accept_price(&mut cache, stellar_asset, sourced);
+                result.insert(stellar_asset.clone(), served.price_usd);
+                false
+            });
+        }
+
+        if !pending.is_empty() {
+            // Use stale cache as fallback
+            let cache = self.cache.read().await;
+            for (asset, _) in &pending {
+                if let Some(cached) = cache.get(asset) {
+                    warn!("Using stale cache for {}", asset);
+                    result.insert(asset.clone(), cached.price_usd);
                 }
             }
         }
@@ -336,6 +829,12 @@ impl PriceFeedClient {
         result
     }
 
+    /// Provider the cached price of an asset came from
+    pub async fn price_source(&self, stellar_asset: &str) -> Option<String> {
+        let cache = self.cache.read().await;
+        cache.get(stellar_asset).map(|c| c.source.clone())
+    }
+
     /// Convert an amount in a Stellar asset to USD
     pub async fn convert_to_usd(&self, stellar_asset: &str, amount: f64) -> Result<f64> {
         let price = self.get_price(stellar_asset).await?;
@@ -427,13 +926,44 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_config_from_env() {
-        std::env::set_var("PRICE_FEED_PROVIDER", "coingecko");
-        std::env::set_var("PRICE_FEED_CACHE_TTL_SECONDS", "600");
+    fn test_api_key_per_provider() {
+        let config = PriceFeedConfig {
+            api_key: Some("gecko-key".to_string()),
+            coincap_api_key: Some("coincap-key".to_string()),
+            fallback_providers: vec!["coincap".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.api_key_for("coingecko").as_deref(),
+            Some("gecko-key")
+        );
+        assert_eq!(
+            config.api_key_for("coincap").as_deref(),
+            Some("coincap-key")
+        );
 
-        let config = PriceFeedConfig::from_env();
-        assert_eq!(config.provider, "coingecko");
-        assert_eq!(config.cache_ttl_seconds, 600);
+        // A CoinCap primary without its own key uses the general one
+        let config = PriceFeedConfig {
+            provider: "coincap".to_string(),
+            api_key: Some("general-key".to_string()),
+            fallback_providers: vec!["coingecko".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.api_key_for("coincap").as_deref(),
+            Some("general-key")
+        );
+        assert_eq!(config.api_key_for("coingecko"), None);
+    }
+
+    #[test]
+    fn test_with_providers_requires_a_provider() {
+        let result = PriceFeedClient::with_providers(
+            PriceFeedConfig::default(),
+            default_asset_mapping(),
+            Vec::new(),
+        );
+        assert!(result.is_err());
     }
 
     #[test]
@@ -462,7 +992,10 @@ mod tests {
                 "XLM:native".to_string(),
                 CachedPrice {
                     price_usd: 0.10,
+                    source: "CoinGecko".to_string(),
                     timestamp: Instant::now(),
+                    stale_alerted: false,
+                    rejected_price_usd: None,
                 },
             );
         }
@@ -479,4 +1012,138 @@ mod tests {
         assert_eq!(total, 1);
         assert_eq!(fresh, 0);
     }
+
+    struct UnavailableProvider;
+
+    #[async_trait::async_trait]
+    impl PriceFeedProvider for UnavailableProvider {
+        async fn fetch_price(&self, _asset_id: &str) -> Result<f64> {
+            Err(anyhow::anyhow!("unavailable"))
+        }
+
+        async fn fetch_prices(&self, _asset_ids: &[String]) -> Result<HashMap<String, f64>> {
+            Err(anyhow::anyhow!("unavailable"))
+        }
+
+        fn name(&self) -> &str {
+            "unavailable"
+        }
+    }
+
+    #[test]
+    fn test_parse_staleness_entry() {
+        assert_eq!(
+            PriceFeedConfig::parse_staleness_entry("XLM:native=600"),
+            Some(("XLM:native".to_string(), 600))
+        );
+        assert_eq!(
+            PriceFeedConfig::parse_staleness_entry(" AQUA:GAQUA = 86400"),
+            Some(("AQUA:GAQUA".to_string(), 86400))
+        );
+        for entry in ["bad", "USDC=x", "=600"] {
+            assert_eq!(PriceFeedConfig::parse_staleness_entry(entry), None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_staleness_thresholds_per_asset() {
+        let usdc = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        let config = PriceFeedConfig {
+            cache_ttl_seconds: 30,
+            staleness_seconds: 600,
+            asset_staleness_seconds: HashMap::from([("XLM:native".to_string(), 60)]),
+            ..Default::default()
+        };
+        let client = PriceFeedClient::with_providers(
+            config,
+            default_asset_mapping(),
+            vec![Arc::new(UnavailableProvider)],
+        )
+        .unwrap();
+        let mut alerts = client.subscribe_stale_alerts();
+
+        // Both prices were fetched two minutes ago and cannot be refreshed
+        {
+            let mut cache = client.cache.write().await;
+            for asset in ["XLM:native", usdc] {
+                cache.insert(
+                    asset.to_string(),
+                    CachedPrice {
+                        price_usd: 1.0,
+                        source: "CoinGecko".to_string(),
+                        timestamp: Instant::now() - Duration::from_secs(120),
+                        stale_alerted: false,
+                        rejected_price_usd: None,
+                    },
+                );
+            }
+        }
+
+        let xlm = client.get_price_status("XLM:native").await.unwrap();
+        assert!(xlm.stale);
+        assert_eq!(xlm.staleness_threshold_seconds, 60);
+        assert!(xlm.age_seconds >= 120);
+
+        let usdc_status = client.get_price_status(usdc).await.unwrap();
+        assert!(!usdc_status.stale);
+        assert_eq!(usdc_status.staleness_threshold_seconds, 600);
+
+        // One alert, for XLM only, however often the stale price is served
+        assert!(client.get_price_status("XLM:native").await.unwrap().stale);
+        assert_eq!(alerts.try_recv().unwrap().asset, "XLM:native");
+        assert!(alerts.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stale_alerts_are_published_on_the_event_bus() {
+        let config = PriceFeedConfig {
+            staleness_seconds: 60,
+            ..Default::default()
+        };
+        let client = Arc::new(
+            PriceFeedClient::with_providers(
+                config,
+                default_asset_mapping(),
+                vec![Arc::new(UnavailableProvider)],
+            )
+            .unwrap(),
+        );
+        client.cache.write().await.insert(
+            "XLM:native".to_string(),
+            CachedPrice {
+                price_usd: 1.0,
+                source: "CoinGecko".to_string(),
+                timestamp: Instant::now() - Duration::from_secs(120),
+                stale_alerted: false,
+                rejected_price_usd: None,
+            },
+        );
+
+        let event_bus = Arc::new(EventBus::new());
+        let mut events = event_bus.subscribe();
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let forwarder = {
+            let client = Arc::clone(&client);
+            let event_bus = Arc::clone(&event_bus);
+            tokio::spawn(async move {
+                client
+                    .run_stale_alert_forwarder(event_bus, shutdown_rx)
+                    .await
+            })
+        };
+        tokio::task::yield_now().await;
+
+        assert!(client.get_price_status("XLM:native").await.unwrap().stale);
+        let event = tokio::time::timeout(Duration::from_secs(1), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        match event {
+            DomainEvent::PriceStale(alert) => assert_eq!(alert.asset, "XLM:native"),
+            other => panic!("expected PriceStale, got {:?}", other),
+        }
+
+        shutdown_tx.send(()).unwrap();
+        forwarder.await.unwrap();
+    }
 }
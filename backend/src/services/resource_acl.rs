@@ -0,0 +1,145 @@
+//! Per-resource write permissions for anchors and corridors.
+//!
+//! A write scope lets a user mutate anchors or corridors in general. A
+//! resource can additionally be restricted to particular users by granting
+//! them write access to it: once it has any grant, only its grantees and
+//! admins may mutate it. Resources without grants stay governed by the
+//! scope alone.
+
+use anyhow::{Context, Result};
+use sqlx::SqlitePool;
+use std::fmt;
+use std::str::FromStr;
+
+/// Kind of resource a permission is granted on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceType {
+    Anchor,
+    Corridor,
+}
+
+impl ResourceType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Anchor => "anchor",
+            Self::Corridor => "corridor",
+        }
+    }
+}
+
+impl FromStr for ResourceType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "anchor" => Ok(Self::Anchor),
+            "corridor" => Ok(Self::Corridor),
+            other => Err(format!("Unknown resource type '{}'", other)),
+        }
+    }
+}
+
+impl fmt::Display for ResourceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Clone)]
+pub struct ResourceAcl {
+    pool: SqlitePool,
+}
+
+impl ResourceAcl {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Grant `user_id` write access to a resource, restricting it to its
+    /// grantees
+    pub async fn grant(
+        &self,
+        resource: ResourceType,
+        resource_id: &str,
+        user_id: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO resource_permissions (resource_type, resource_id, user_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (resource_type, resource_id, user_id) DO NOTHING
+            "#,
+        )
+        .bind(resource.as_str())
+        .bind(resource_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to grant resource permission")?;
+        Ok(())
+    }
+
+    /// Revoke a grant; removing the last one lifts the restriction
+    pub async fn revoke(
+        &self,
+        resource: ResourceType,
+        resource_id: &str,
+        user_id: &str,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM resource_permissions
+            WHERE resource_type = $1 AND resource_id = $2 AND user_id = $3
+            "#,
+        )
+        .bind(resource.as_str())
+        .bind(resource_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .context("Failed to revoke resource permission")?;
+        Ok(())
+    }
+
+    /// Users granted write access to a resource, in id order; empty when it
+    /// is unrestricted
+    pub async fn grantees(&self, resource: ResourceType, resource_id: &str) -> Result<Vec<String>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT user_id FROM resource_permissions
+            WHERE resource_type = $1 AND resource_id = $2
+            ORDER BY user_id
+            "#,
+        )
+        .bind(resource.as_str())
+        .bind(resource_id)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list resource permissions")
+    }
+
+    /// Whether `user_id` may mutate the resource: true when it has no grants
+    /// or one of them is for `user_id`
+    pub async fn can_write(
+        &self,
+        resource: ResourceType,
+        resource_id: &str,
+        user_id: &str,
+    ) -> Result<bool> {
+        let (grants, granted): (i64, bool) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*), COALESCE(MAX(user_id = $3), 0)
+            FROM resource_permissions
+            WHERE resource_type = $1 AND resource_id = $2
+            "#,
+        )
+        .bind(resource.as_str())
+        .bind(resource_id)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to check resource permission")?;
+
+        Ok(grants == 0 || granted)
+    }
+}
@@ -0,0 +1,124 @@
+//! RPC connectivity alerts.
+//!
+//! Publishes `RpcDegraded` on the event bus when an endpoint group's RPC
+//! circuit breaker opens and `RpcRecovered` once it has stayed closed for the
+//! recovery debounce, so webhooks subscribed to `rpc.degraded` /
+//! `rpc.recovered` hear about each group's outage once. A breaker that
+//! reopens while half-open, or again within the debounce after closing, is
+//! still the same outage and publishes nothing.
+
+use futures::{Stream, StreamExt};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::time::Instant;
+
+use crate::rpc::circuit_breaker::{CircuitTransition, EndpointGroup, GroupTransition};
+use crate::services::event_bus::{DomainEvent, EventBus};
+use crate::webhooks::events::RpcHealthEvent;
+
+/// Default time the breaker must stay closed before recovery is announced
+pub const DEFAULT_RECOVERY_DEBOUNCE: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpcHealthMonitorConfig {
+    pub recovery_debounce: Duration,
+}
+
+impl Default for RpcHealthMonitorConfig {
+    fn default() -> Self {
+        Self {
+            recovery_debounce: DEFAULT_RECOVERY_DEBOUNCE,
+        }
+    }
+}
+
+pub struct RpcHealthMonitor {
+    event_bus: Arc<EventBus>,
+    endpoint: String,
+    config: RpcHealthMonitorConfig,
+}
+
+impl RpcHealthMonitor {
+    pub fn new(
+        event_bus: Arc<EventBus>,
+        endpoint: impl Into<String>,
+        config: RpcHealthMonitorConfig,
+    ) -> Self {
+        Self {
+            event_bus,
+            endpoint: endpoint.into(),
+            config,
+        }
+    }
+
+    /// Turn circuit transitions into events until shutdown or the breakers
+    /// are dropped
+    pub async fn run(
+        self,
+        mut transitions: impl Stream<Item = GroupTransition> + Unpin,
+        mut shutdown_rx: broadcast::Receiver<()>,
+    ) {
+        let mut degraded: HashMap<EndpointGroup, Option<Instant>> = HashMap::new();
+
+        loop {
+            // A degraded group with a due time has closed and awaits recovery
+            let recovery_due = degraded.values().flatten().min().copied();
+            let recovery_timer =
+                tokio::time::sleep_until(recovery_due.unwrap_or_else(Instant::now));
+            tokio::select! {
+                transition = transitions.next() => match transition {
+                    Some(GroupTransition { group, transition: CircuitTransition::Opened }) => {
+                        if degraded.insert(group, None).is_none() {
+                            tracing::error!(
+                                "RPC circuit breaker for '{}' ({}) opened",
+                                self.endpoint,
+                                group.label()
+                            );
+                            self.publish(DomainEvent::RpcDegraded, group, "degraded");
+                        }
+                    }
+                    Some(GroupTransition { group, transition: CircuitTransition::Closed }) => {
+                        if let Some(due) = degraded.get_mut(&group) {
+                            *due = Some(Instant::now() + self.config.recovery_debounce);
+                        }
+                    }
+                    None => break,
+                },
+                _ = recovery_timer, if recovery_due.is_some() => {
+                    let now = Instant::now();
+                    let recovered: Vec<EndpointGroup> = degraded
+                        .iter()
+                        .filter(|(_, due)| due.is_some_and(|due| due <= now))
+                        .map(|(group, _)| *group)
+                        .collect();
+                    for group in recovered {
+                        degraded.remove(&group);
+                        tracing::info!(
+                            "RPC circuit breaker for '{}' ({}) recovered",
+                            self.endpoint,
+                            group.label()
+                        );
+                        self.publish(DomainEvent::RpcRecovered, group, "recovered");
+                    }
+                }
+                _ = shutdown_rx.recv() => break,
+            }
+        }
+    }
+
+    fn publish(
+        &self,
+        event: fn(RpcHealthEvent) -> DomainEvent,
+        group: EndpointGroup,
+        status: &str,
+    ) {
+        self.event_bus.publish(event(RpcHealthEvent {
+            endpoint: self.endpoint.clone(),
+            group: group.label().to_string(),
+            status: status.to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }));
+    }
+}
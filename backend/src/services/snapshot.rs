@@ -1,19 +1,178 @@
 use crate::database::Database;
+use crate::snapshot::epoch::EpochCalendar;
 use crate::snapshot::schema::{
-    AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics, SCHEMA_VERSION,
+    stellar_amount_from_f64, AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics,
+    SnapshotPoolMetrics, SnapshotSection, CORE_SNAPSHOT_KEYS, EXACT_AMOUNTS_SCHEMA_VERSION,
+    POOL_METRICS_SECTION, SCHEMA_VERSION,
 };
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use futures::{stream, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use sha2::{Digest, Sha256};
-use sqlx::Row;
+use sha2::{Digest, Sha256, Sha512};
+use sha3::Keccak256;
+use sqlx::{Row, SqliteConnection};
 use std::collections::BTreeMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
-use super::contract::{ContractService, SubmissionResult};
+use super::contract::{
+    BatchSubmissionResult, ContractError, ContractService, SnapshotEntry, SubmissionResult,
+};
+
+/// Round every non-integer number in `value` to `decimal_places`
+fn round_floats(value: Value, decimal_places: u32) -> Value {
+    match value {
+        Value::Number(number) if number.is_f64() => {
+            let scale = 10f64.powi(decimal_places as i32);
+            let rounded = number.as_f64().map_or(0.0, |v| (v * scale).round() / scale);
+            SnapshotService::serialize_f64(rounded)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| round_floats(item, decimal_places))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, item)| (key, round_floats(item, decimal_places)))
+                .collect(),
+        ),
+        other => other,
+    }
+}
+
+/// Hash algorithm used for stored snapshot hashes.
+///
+/// The hash submitted on-chain is always SHA-256; the selected algorithm only
+/// changes the hash stored alongside the snapshot and used to verify it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Sha512,
+    Keccak256,
+}
+
+impl HashAlgorithm {
+    /// Parse an algorithm name, e.g. `sha256`, `sha-512` or `keccak256`
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().replace('-', "").as_str() {
+            "sha256" => Some(Self::Sha256),
+            "sha512" => Some(Self::Sha512),
+            "keccak256" | "keccak" => Some(Self::Keccak256),
+            _ => None,
+        }
+    }
+
+    /// Name stored in the database, e.g. `sha256`
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Sha512 => "sha512",
+            Self::Keccak256 => "keccak256",
+        }
+    }
+
+    /// Length of a digest in bytes; hex digests are twice as long
+    pub fn digest_len(&self) -> usize {
+        match self {
+            Self::Sha256 | Self::Keccak256 => 32,
+            Self::Sha512 => 64,
+        }
+    }
+
+    /// Digest of `data` with this algorithm
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Self::Sha256 => Sha256::digest(data).to_vec(),
+            Self::Sha512 => Sha512::digest(data).to_vec(),
+            Self::Keccak256 => Keccak256::digest(data).to_vec(),
+        }
+    }
+
+    /// Hex digest of `data` with this algorithm
+    pub fn hex_digest(&self, data: &[u8]) -> String {
+        hex::encode(self.digest(data))
+    }
+}
+
+impl std::str::FromStr for HashAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| format!("unknown hash algorithm '{}'", s))
+    }
+}
+
+/// A snapshot is already being generated by this service; returned wrapped
+/// in `anyhow::Error`
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Snapshot generation for epoch {epoch} is already in progress")]
+pub struct GenerationInProgress {
+    /// Epoch of the generation that is running
+    pub epoch: u64,
+}
+
+/// An epoch whose period on the epoch calendar has not started yet;
+/// returned wrapped in `anyhow::Error`
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Epoch {epoch} has not started yet")]
+pub struct EpochNotStarted {
+    pub epoch: u64,
+}
+
+/// A new snapshot was requested for an epoch that already has one stored;
+/// returned wrapped in `anyhow::Error`
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("A snapshot for epoch {epoch} already exists")]
+pub struct SnapshotExists {
+    pub epoch: u64,
+}
+
+/// A generated snapshot exceeds the configured `SnapshotSizeLimits`;
+/// returned wrapped in `anyhow::Error`
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SnapshotTooLarge {
+    #[error("Snapshot for epoch {epoch} has {count} metrics, more than the limit of {limit}")]
+    Metrics {
+        epoch: u64,
+        count: usize,
+        limit: usize,
+    },
+    #[error("Snapshot for epoch {epoch} is {size} bytes of canonical JSON, more than the limit of {limit}")]
+    Bytes {
+        epoch: u64,
+        size: usize,
+        limit: usize,
+    },
+}
+
+/// More of the snapshots sampled by the startup integrity audit are corrupted
+/// than `IntegrityAuditConfig::max_corruption_ratio` allows; returned wrapped
+/// in `anyhow::Error`
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{corrupted} of {checked} sampled snapshots are corrupted, more than the allowed ratio of {max_ratio}")]
+pub struct SnapshotCorruptionExceeded {
+    pub corrupted: usize,
+    pub checked: usize,
+    pub max_ratio: f64,
+}
+
+/// Marks a generation as running until dropped
+struct GenerationGuard<'a>(&'a Mutex<Option<u64>>);
+
+impl Drop for GenerationGuard<'_> {
+    fn drop(&mut self) {
+        *self.0.lock().unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
 
 /// Result of snapshot generation and submission process
 #[derive(Debug, Clone, Serialize)]
@@ -21,6 +180,8 @@ pub struct SnapshotGenerationResult {
     pub snapshot_id: String,
     pub epoch: u64,
     pub hash: String,
+    pub hash_algorithm: HashAlgorithm,
+    pub schema_version: u32,
     pub canonical_json: String,
     pub anchor_count: usize,
     pub corridor_count: usize,
@@ -29,17 +190,352 @@ pub struct SnapshotGenerationResult {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Self-contained evidence that lets an auditor verify a stored snapshot
+/// without database access
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProofBundle {
+    pub snapshot_id: String,
+    pub epoch: u64,
+    pub schema_version: u32,
+    pub timestamp: DateTime<Utc>,
+    /// The exact bytes that were hashed
+    pub canonical_json: String,
+    /// Hex digest of `canonical_json` under `hash_algorithm`
+    pub hash: String,
+    /// Algorithm that produced `hash`; bundles without it used SHA-256
+    #[serde(default)]
+    pub hash_algorithm: HashAlgorithm,
+    /// SHA-256 the contract holds for this epoch, if it could be queried
+    pub on_chain_hash: Option<String>,
+    /// Merkle root of the snapshot entries; not produced yet
+    pub merkle_root: Option<String>,
+    /// Hex ed25519 public key that produced `signature`; verifiers check it
+    /// against the key the service publishes
+    #[serde(default)]
+    pub signing_key: Option<String>,
+    /// Hex ed25519 signature over the bundle with this field unset
+    pub signature: Option<String>,
+}
+
+/// The exact bytes hashed for a stored snapshot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredCanonicalJson {
+    pub canonical_json: String,
+    /// Hex digest of `canonical_json` under `hash_algorithm`
+    pub hash: String,
+    pub hash_algorithm: HashAlgorithm,
+}
+
+/// One hash stored for more than one epoch. Each epoch's JSON includes the
+/// epoch number, so this means a duplicated aggregation or a hashing bug.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashCollision {
+    pub hash: String,
+    /// Epochs stored with `hash`, ascending
+    pub epochs: Vec<u64>,
+}
+
+/// Outcome of comparing a caller-supplied hash with the stored and on-chain
+/// hashes of an epoch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotHashVerification {
+    pub epoch: u64,
+    /// The hash being checked, lowercased
+    pub hash: String,
+    /// Hash stored with the latest snapshot of the epoch, if any
+    pub stored_hash: Option<String>,
+    pub matches_stored: bool,
+    /// Hash the contract holds for the epoch, if it could be queried
+    pub on_chain_hash: Option<String>,
+    /// Whether the SHA-256 of the stored canonical JSON, which is what gets
+    /// submitted, matches the on-chain hash
+    pub matches_on_chain: bool,
+    /// Whether the hash is the canonical one: it matches the stored hash and
+    /// the stored snapshot matches the chain
+    pub canonical: bool,
+}
+
+/// A stored snapshot whose hash does not match its canonical JSON
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorruptedSnapshot {
+    pub id: String,
+    pub epoch: u64,
+    pub reason: String,
+}
+
+/// Outcome of auditing a sample of stored snapshots
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrityAuditReport {
+    /// Snapshots sampled
+    pub checked: usize,
+    pub corrupted: Vec<CorruptedSnapshot>,
+}
+
+impl IntegrityAuditReport {
+    /// Share of the sampled snapshots that are corrupted, 0 when none were
+    /// sampled
+    pub fn corruption_ratio(&self) -> f64 {
+        if self.checked == 0 {
+            0.0
+        } else {
+            self.corrupted.len() as f64 / self.checked as f64
+        }
+    }
+}
+
+/// Startup self-audit of the most recent stored snapshots
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntegrityAuditConfig {
+    /// Whether the audit runs before the server starts serving
+    pub enabled: bool,
+    /// Most recent snapshots audited
+    pub sample_size: usize,
+    /// Largest share of corrupted snapshots tolerated; startup fails above it
+    pub max_corruption_ratio: f64,
+}
+
+impl Default for IntegrityAuditConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sample_size: 50,
+            max_corruption_ratio: 0.0,
+        }
+    }
+}
+
+/// Epochs checked at a time by `SnapshotService::verify_epochs`
+pub const BATCH_VERIFY_CONCURRENCY: usize = 8;
+
+/// Where the snapshot of one epoch is present, and whether the copies agree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochVerification {
+    pub epoch: u64,
+    /// A snapshot of the epoch is stored in the database
+    pub in_db: bool,
+    /// The contract holds a hash for the epoch
+    pub on_chain: bool,
+    /// Hash stored with the latest snapshot of the epoch
+    pub stored_hash: Option<String>,
+    pub on_chain_hash: Option<String>,
+    /// The on-chain hash is the SHA-256 of the stored canonical JSON, i.e.
+    /// the stored snapshot is the one that was submitted
+    pub hash_match: bool,
+    /// Why the contract could not be queried, when it could not
+    pub chain_error: Option<String>,
+}
+
+/// Outcome of serializing one epoch's data several times and comparing the
+/// outputs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeterminismCheck {
+    pub epoch: u64,
+    pub schema_version: u32,
+    /// Whether every serialization matched the first one
+    pub passed: bool,
+    /// SHA-256 of the first serialization, hex-encoded
+    pub hash: String,
+    pub anchor_count: usize,
+    pub corridor_count: usize,
+    /// Serializations that differed from the first: `repeat` or `reordered_input`
+    pub mismatches: Vec<String>,
+}
+
+impl ProofBundle {
+    /// Check the bundle is internally consistent: the hash matches the
+    /// canonical JSON under the recorded algorithm, the JSON describes this
+    /// epoch, and any on-chain hash agrees with its SHA-256
+    pub fn verify(&self) -> bool {
+        let hash_matches = self
+            .hash_algorithm
+            .hex_digest(self.canonical_json.as_bytes())
+            .eq_ignore_ascii_case(&self.hash);
+        let epoch_matches = serde_json::from_str::<Value>(&self.canonical_json)
+            .ok()
+            .and_then(|json| json.get("epoch").and_then(Value::as_u64))
+            == Some(self.epoch);
+        let on_chain_matches = match &self.on_chain_hash {
+            Some(on_chain) => on_chain.eq_ignore_ascii_case(&hex::encode(
+                SnapshotService::compute_sha256_hash_bytes(&self.canonical_json),
+            )),
+            None => true,
+        };
+
+        hash_matches && epoch_matches && on_chain_matches
+    }
+
+    /// Sign the bundle, replacing any existing signature
+    pub fn sign(&mut self, key: &SigningKey) -> Result<()> {
+        self.signing_key = Some(hex::encode(key.verifying_key().as_bytes()));
+        self.signature = Some(hex::encode(key.sign(&self.signed_bytes()?).to_bytes()));
+        Ok(())
+    }
+
+    /// Check the signature against the published `public_key`; unsigned
+    /// bundles and bundles signed by another key never verify
+    pub fn verify_signature(&self, public_key: &VerifyingKey) -> bool {
+        let (Some(signature), Ok(message)) = (&self.signature, self.signed_bytes()) else {
+            return false;
+        };
+        if self.signing_key.as_deref() != Some(hex::encode(public_key.as_bytes()).as_str()) {
+            return false;
+        }
+        hex::decode(signature)
+            .ok()
+            .and_then(|sig| Signature::from_slice(&sig).ok())
+            .is_some_and(|sig| public_key.verify(&message, &sig).is_ok())
+    }
+
+    fn signed_bytes(&self) -> Result<Vec<u8>> {
+        let unsigned = Self {
+            signature: None,
+            ..self.clone()
+        };
+        Ok(serde_json::to_vec(&unsigned)?)
+    }
+}
+
+/// Serialization settings for recomputing a stored snapshot's hash with
+/// `recompute_from_stored_json`. The default reproduces the stored hash.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SchemaOverride {
+    /// Schema version written into the JSON; `None` keeps the stored one
+    pub schema_version: Option<u32>,
+    /// Decimal places floating point metrics are rounded to; `None` keeps
+    /// full precision
+    pub float_precision: Option<u32>,
+    /// Algorithm to hash with; `None` uses the one stored with the snapshot
+    pub hash_algorithm: Option<HashAlgorithm>,
+}
+
+impl SchemaOverride {
+    pub fn with_schema_version(mut self, version: u32) -> Self {
+        self.schema_version = Some(version);
+        self
+    }
+
+    pub fn with_float_precision(mut self, decimal_places: u32) -> Self {
+        self.float_precision = Some(decimal_places);
+        self
+    }
+
+    pub fn with_hash_algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = Some(algorithm);
+        self
+    }
+}
+
+/// How long to wait for a submitted snapshot to show up on-chain. The first
+/// check runs after `min_delay`; each later one waits `backoff`, doubling
+/// every time, until `max_checks` checks have been made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfirmationConfig {
+    pub min_delay: Duration,
+    pub max_checks: u32,
+    pub backoff: Duration,
+}
+
+impl Default for ConfirmationConfig {
+    fn default() -> Self {
+        Self {
+            min_delay: Duration::from_secs(2),
+            max_checks: 5,
+            backoff: Duration::from_secs(1),
+        }
+    }
+}
+
+impl ConfirmationConfig {
+    /// Wait before check `check`, counted from 0
+    pub fn delay_before(&self, check: u32) -> Duration {
+        match check {
+            0 => self.min_delay,
+            n => self.backoff.saturating_mul(1 << (n - 1).min(16)),
+        }
+    }
+}
+
+/// Largest snapshot generated; bigger ones are neither stored nor submitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SnapshotSizeLimits {
+    /// Metric entries across anchors, corridors and sections
+    pub max_metrics: usize,
+    /// Bytes of canonical JSON
+    pub max_bytes: usize,
+}
+
+impl Default for SnapshotSizeLimits {
+    fn default() -> Self {
+        Self {
+            max_metrics: 10_000,
+            max_bytes: 4 * 1024 * 1024,
+        }
+    }
+}
+
+impl SnapshotSizeLimits {
+    /// Check `snapshot` and its canonical JSON against the limits
+    pub fn check(
+        &self,
+        snapshot: &AnalyticsSnapshot,
+        canonical_json: &str,
+    ) -> Result<(), SnapshotTooLarge> {
+        let count = snapshot.metric_count();
+        if count > self.max_metrics {
+            return Err(SnapshotTooLarge::Metrics {
+                epoch: snapshot.epoch,
+                count,
+                limit: self.max_metrics,
+            });
+        }
+        if canonical_json.len() > self.max_bytes {
+            return Err(SnapshotTooLarge::Bytes {
+                epoch: snapshot.epoch,
+                size: canonical_json.len(),
+                limit: self.max_bytes,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Liquidity pool metrics section, ordered by pool id
+pub struct PoolMetricsSection;
+
+impl SnapshotSection for PoolMetricsSection {
+    const NAME: &'static str = POOL_METRICS_SECTION;
+    type Entry = SnapshotPoolMetrics;
+
+    fn sort_key(entry: &SnapshotPoolMetrics) -> String {
+        entry.pool_id.clone()
+    }
+
+    fn serialize_entry(entry: &SnapshotPoolMetrics, schema_version: u32) -> Value {
+        SnapshotService::serialize_pool_metrics(entry, schema_version)
+    }
+}
+
 /// Service for creating cryptographically verifiable analytics snapshots
 ///
 /// This service ensures that:
 /// 1. Metrics are aggregated from all data sources
 /// 2. Snapshots are serialized deterministically (same input = same output)
-/// 3. SHA-256 hashes are computed and stored
-/// 4. Hashes are submitted to smart contracts
+/// 3. Hashes are computed with the configured algorithm and stored
+/// 4. SHA-256 hashes are submitted to smart contracts
 /// 5. Submission success is verified
 pub struct SnapshotService {
     db: Arc<Database>,
     contract_service: Option<Arc<ContractService>>,
+    proof_signing_key: Option<SigningKey>,
+    hash_algorithm: HashAlgorithm,
+    /// Schema version new snapshots are written with
+    schema_version: u32,
+    confirmation: ConfirmationConfig,
+    size_limits: SnapshotSizeLimits,
+    /// Labels epochs with the periods they cover, when configured
+    epoch_calendar: Option<EpochCalendar>,
+    /// Epoch currently being generated, if any
+    generating: Mutex<Option<u64>>,
 }
 
 impl SnapshotService {
@@ -48,6 +544,99 @@ impl SnapshotService {
         Self {
             db,
             contract_service,
+            proof_signing_key: None,
+            hash_algorithm: HashAlgorithm::default(),
+            schema_version: SCHEMA_VERSION,
+            confirmation: ConfirmationConfig::default(),
+            size_limits: SnapshotSizeLimits::default(),
+            epoch_calendar: None,
+            generating: Mutex::new(None),
+        }
+    }
+
+    /// Sign exported proof bundles with this ed25519 key
+    pub fn with_proof_signing_key(mut self, key: SigningKey) -> Self {
+        self.proof_signing_key = Some(key);
+        self
+    }
+
+    /// Public key auditors verify proof bundle signatures against, if
+    /// bundles are signed
+    pub fn proof_public_key(&self) -> Option<VerifyingKey> {
+        self.proof_signing_key
+            .as_ref()
+            .map(SigningKey::verifying_key)
+    }
+
+    /// Store snapshot hashes computed with `algorithm` instead of SHA-256
+    pub fn with_hash_algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.hash_algorithm = algorithm;
+        self
+    }
+
+    /// Algorithm new snapshot hashes are stored with
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.hash_algorithm
+    }
+
+    /// Write new snapshots under `EXACT_AMOUNTS_SCHEMA_VERSION`, with every
+    /// monetary field as a decimal string
+    pub fn with_exact_amounts(mut self) -> Self {
+        self.schema_version = EXACT_AMOUNTS_SCHEMA_VERSION;
+        self
+    }
+
+    /// Wait for submissions to confirm on-chain as `config` says
+    pub fn with_confirmation_config(mut self, config: ConfirmationConfig) -> Self {
+        self.confirmation = config;
+        self
+    }
+
+    /// Refuse to store or submit snapshots larger than `limits`
+    pub fn with_size_limits(mut self, limits: SnapshotSizeLimits) -> Self {
+        self.size_limits = limits;
+        self
+    }
+
+    /// Label epochs with the periods `calendar` maps them to
+    pub fn with_epoch_calendar(mut self, calendar: EpochCalendar) -> Self {
+        self.epoch_calendar = Some(calendar);
+        self
+    }
+
+    /// Human-readable label of `epoch`, e.g. `2026-W03`; `None` without an
+    /// epoch calendar
+    pub fn epoch_label(&self, epoch: u64) -> Option<String> {
+        self.epoch_calendar.as_ref()?.label(epoch)
+    }
+
+    /// Epoch with the given label; `None` without an epoch calendar or when
+    /// no epoch has that label
+    pub fn epoch_for_label(&self, label: &str) -> Option<u64> {
+        self.epoch_calendar.as_ref()?.epoch_for_label(label)
+    }
+
+    /// Epoch a snapshot taken now belongs to: the one covering the current
+    /// time with an epoch calendar, otherwise the one after the latest
+    /// stored epoch
+    pub async fn next_epoch(&self) -> Result<u64> {
+        match &self.epoch_calendar {
+            Some(calendar) => Ok(calendar
+                .derive_epoch(Utc::now())
+                .ok_or(EpochNotStarted { epoch: 0 })?),
+            None => Ok(self.latest_epoch().await?.map_or(1, |latest| latest + 1)),
+        }
+    }
+
+    /// Fail with [`EpochNotStarted`] for an epoch the calendar places in the
+    /// future; its snapshot would carry data from another period
+    fn check_epoch_started(&self, epoch: u64) -> Result<(), EpochNotStarted> {
+        let Some(calendar) = &self.epoch_calendar else {
+            return Ok(());
+        };
+        match calendar.epoch_start(epoch) {
+            Some(start) if start <= Utc::now() => Ok(()),
+            _ => Err(EpochNotStarted { epoch }),
         }
     }
 
@@ -55,54 +644,103 @@ impl SnapshotService {
     ///
     /// This is the main entry point that fulfills all acceptance criteria:
     /// 1. Aggregate all metrics
-    /// 2. Serialize to deterministic JSON
-    /// 3. Compute SHA-256 hash
+    /// 2. Serialize to deterministic JSON, refusing snapshots over the size
+    ///    limits
+    /// 3. Compute the snapshot hash
     /// 4. Store hash in database
     /// 5. Submit to smart contract
     /// 6. Verify submission success
+    ///
+    /// Only one generation runs at a time: while one is in progress, further
+    /// calls fail with `GenerationInProgress` instead of duplicating the work.
     pub async fn generate_and_submit_snapshot(
         &self,
         epoch: u64,
     ) -> Result<SnapshotGenerationResult> {
-        info!("Starting snapshot generation for epoch {}", epoch);
+        self.generate(epoch, false).await
+    }
 
-        // Step 1: Aggregate all metrics
-        let snapshot = self
-            .aggregate_all_metrics(epoch)
-            .await
-            .context("Failed to aggregate metrics")?;
+    /// Generate and submit a snapshot for an epoch that has none stored yet.
+    ///
+    /// Unlike [`Self::generate_and_submit_snapshot`], a stored snapshot is not
+    /// resumed: storing fails with [`SnapshotExists`] if the epoch has one,
+    /// including one stored concurrently while this snapshot was aggregated.
+    pub async fn generate_new_snapshot(&self, epoch: u64) -> Result<SnapshotGenerationResult> {
+        self.generate(epoch, true).await
+    }
 
-        info!(
-            "Aggregated {} anchor metrics and {} corridor metrics",
-            snapshot.anchor_metrics.len(),
-            snapshot.corridor_metrics.len()
-        );
+    async fn generate(&self, epoch: u64, require_new: bool) -> Result<SnapshotGenerationResult> {
+        self.check_epoch_started(epoch)?;
+        let _guard = self.begin_generation(epoch)?;
+        info!("Starting snapshot generation for epoch {}", epoch);
 
-        // Step 2: Serialize to deterministic JSON
-        let canonical_json = Self::serialize_deterministically(snapshot.clone())
-            .context("Failed to serialize snapshot deterministically")?;
+        let stored = if require_new {
+            None
+        } else {
+            self.canonical_json(epoch).await?
+        };
+        let (snapshot, canonical_json, hash_algorithm) = match stored {
+            // A retry of a stored epoch reuses the stored snapshot: a fresh
+            // aggregation carries a new timestamp, so its hash never matches
+            Some(stored) => {
+                info!(
+                    "Snapshot for epoch {} already stored, resuming with it",
+                    epoch
+                );
+                let snapshot = Self::parse_canonical_json(&stored.canonical_json)
+                    .and_then(AnalyticsSnapshot::migrate)
+                    .with_context(|| {
+                        format!("Failed to parse stored snapshot for epoch {}", epoch)
+                    })?;
+                (snapshot, stored.canonical_json, stored.hash_algorithm)
+            }
+            None => {
+                let (snapshot, canonical_json) = self.build_snapshot(epoch).await?;
+                (snapshot, canonical_json, self.hash_algorithm)
+            }
+        };
 
-        // Step 3: Compute SHA-256 hash
-        let hash = Self::compute_sha256_hash_bytes(&canonical_json);
-        let hash_hex = hex::encode(&hash);
+        // Step 3: Compute the stored hash, and the SHA-256 submitted on-chain
+        let hash_hex = hash_algorithm.hex_digest(canonical_json.as_bytes());
+        let on_chain_hash = Self::compute_sha256_hash_bytes(&canonical_json);
 
-        info!("Generated snapshot hash: {}", hash_hex);
+        info!(
+            "Generated snapshot hash ({}): {}",
+            hash_algorithm.label(),
+            hash_hex
+        );
 
         // Step 4: Store hash in database
-        let snapshot_id = self
-            .store_snapshot_in_database(&snapshot, &hash_hex, &canonical_json)
-            .await
-            .context("Failed to store snapshot in database")?;
+        let snapshot_id = if require_new {
+            self.insert_snapshot(&snapshot, &hash_hex, hash_algorithm, &canonical_json)
+                .await
+                .context("Failed to store snapshot in database")?
+                .ok_or(SnapshotExists { epoch })?
+        } else {
+            self.store_snapshot_in_database(&snapshot, &hash_hex, hash_algorithm, &canonical_json)
+                .await
+                .context("Failed to store snapshot in database")?
+        };
 
         info!("Stored snapshot in database with ID: {}", snapshot_id);
 
         // Step 5: Submit to smart contract (if configured)
         let submission_result = if let Some(contract_service) = &self.contract_service {
-            match contract_service.submit_snapshot(hash, epoch).await {
+            match contract_service
+                .submit_or_fetch_existing(on_chain_hash, epoch)
+                .await
+            {
                 Ok(result) => {
                     info!("Successfully submitted snapshot to contract: {:?}", result);
                     Some(result)
                 }
+                Err(e) if matches!(e.downcast_ref(), Some(ContractError::DuplicateEpoch)) => {
+                    warn!(
+                        "Snapshot for epoch {} is already on-chain, skipping submission",
+                        epoch
+                    );
+                    None
+                }
                 Err(e) => {
                     error!("Failed to submit snapshot to contract: {}", e);
                     return Err(e.context("Contract submission failed"));
@@ -115,7 +753,7 @@ impl SnapshotService {
 
         // Step 6: Verify submission success (if submitted)
         let verification_result = if let Some(ref submission) = submission_result {
-            self.verify_submission_success(&hash_hex, epoch, submission)
+            self.verify_submission_success(&hex::encode(on_chain_hash), epoch, submission)
                 .await
                 .context("Failed to verify submission success")?
         } else {
@@ -126,6 +764,8 @@ impl SnapshotService {
             snapshot_id,
             epoch,
             hash: hash_hex,
+            hash_algorithm,
+            schema_version: snapshot.schema_version,
             canonical_json,
             anchor_count: snapshot.anchor_metrics.len(),
             corridor_count: snapshot.corridor_metrics.len(),
@@ -135,36 +775,96 @@ impl SnapshotService {
         })
     }
 
+    /// Steps 1 and 2: aggregate a new snapshot of `epoch` and serialize it,
+    /// refusing snapshots over the size limits
+    async fn build_snapshot(&self, epoch: u64) -> Result<(AnalyticsSnapshot, String)> {
+        let snapshot = self
+            .aggregate_all_metrics(epoch)
+            .await
+            .context("Failed to aggregate metrics")?;
+
+        info!(
+            "Aggregated {} anchor metrics and {} corridor metrics",
+            snapshot.anchor_metrics.len(),
+            snapshot.corridor_metrics.len()
+        );
+
+        let canonical_json = Self::serialize_deterministically(snapshot.clone())
+            .context("Failed to serialize snapshot deterministically")?;
+
+        if let Err(e) = self.size_limits.check(&snapshot, &canonical_json) {
+            error!("{}; not storing or submitting it", e);
+            crate::observability::metrics::record_error("snapshot_too_large");
+            return Err(e.into());
+        }
+
+        Ok((snapshot, canonical_json))
+    }
+
+    /// Claim the generation slot for `epoch`, failing if it is taken
+    fn begin_generation(&self, epoch: u64) -> Result<GenerationGuard<'_>, GenerationInProgress> {
+        let mut generating = self
+            .generating
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(running) = *generating {
+            warn!(
+                "Snapshot generation for epoch {} requested while epoch {} is in progress",
+                epoch, running
+            );
+            return Err(GenerationInProgress { epoch: running });
+        }
+        *generating = Some(epoch);
+        Ok(GenerationGuard(&self.generating))
+    }
+
     /// Aggregate all metrics from the database into a snapshot
+    ///
+    /// Anchors, corridors and liquidity pools are read one after another in
+    /// one read transaction, so the snapshot reflects a single point in time
+    /// even if metrics are written while it is being aggregated.
     pub async fn aggregate_all_metrics(&self, epoch: u64) -> Result<AnalyticsSnapshot> {
         let timestamp = Utc::now();
         let mut snapshot = AnalyticsSnapshot::new(epoch, timestamp);
+        snapshot.schema_version = self.schema_version;
 
-        // Aggregate anchor metrics
-        let anchor_metrics = self
-            .aggregate_anchor_metrics()
+        let mut tx = self
+            .db
+            .pool()
+            .begin()
+            .await
+            .context("Failed to begin aggregation read transaction")?;
+
+        let anchor_metrics = Self::aggregate_anchor_metrics(&mut tx)
             .await
             .context("Failed to aggregate anchor metrics")?;
+        let corridor_metrics = Self::aggregate_corridor_metrics(&mut tx)
+            .await
+            .context("Failed to aggregate corridor metrics")?;
+        let pool_metrics = Self::aggregate_pool_metrics(&mut tx)
+            .await
+            .context("Failed to aggregate liquidity pool metrics")?;
+
+        // Nothing was written; committing just releases the read
+        tx.commit()
+            .await
+            .context("Failed to end aggregation read transaction")?;
 
         for metrics in anchor_metrics {
             snapshot.add_anchor_metrics(metrics);
         }
-
-        // Aggregate corridor metrics
-        let corridor_metrics = self
-            .aggregate_corridor_metrics()
-            .await
-            .context("Failed to aggregate corridor metrics")?;
-
         for metrics in corridor_metrics {
             snapshot.add_corridor_metrics(metrics);
         }
+        snapshot.add_section::<PoolMetricsSection>(&pool_metrics)?;
 
         Ok(snapshot)
     }
 
     /// Aggregate anchor metrics from database
-    async fn aggregate_anchor_metrics(&self) -> Result<Vec<SnapshotAnchorMetrics>> {
+    async fn aggregate_anchor_metrics(
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<SnapshotAnchorMetrics>> {
         let query = r#"
             SELECT 
                 id,
@@ -183,7 +883,7 @@ impl SnapshotService {
         "#;
 
         let rows = sqlx::query(query)
-            .fetch_all(self.db.pool())
+            .fetch_all(&mut *conn)
             .await
             .context("Failed to fetch anchor data")?;
 
@@ -230,7 +930,9 @@ impl SnapshotService {
     }
 
     /// Aggregate corridor metrics from database
-    async fn aggregate_corridor_metrics(&self) -> Result<Vec<SnapshotCorridorMetrics>> {
+    async fn aggregate_corridor_metrics(
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<SnapshotCorridorMetrics>> {
         let query = r#"
             SELECT 
                 cm.id,
@@ -248,13 +950,21 @@ impl SnapshotService {
                 cm.liquidity_depth_usd
             FROM corridor_metrics cm
             WHERE cm.date >= datetime('now', '-1 day')
+              AND NOT EXISTS (
+                  SELECT 1 FROM corridors c
+                  WHERE c.deleted_at IS NOT NULL
+                    AND c.source_asset_code = cm.asset_a_code
+                    AND c.source_asset_issuer = cm.asset_a_issuer
+                    AND c.destination_asset_code = cm.asset_b_code
+                    AND c.destination_asset_issuer = cm.asset_b_issuer
+              )
             GROUP BY cm.corridor_key
             HAVING cm.date = MAX(cm.date)
             ORDER BY cm.corridor_key
         "#;
 
         let rows = sqlx::query(query)
-            .fetch_all(self.db.pool())
+            .fetch_all(&mut *conn)
             .await
             .context("Failed to fetch corridor metrics")?;
 
@@ -285,38 +995,748 @@ impl SnapshotService {
         Ok(metrics)
     }
 
-    /// Store snapshot and hash in database
-    pub(crate) async fn store_snapshot_in_database(
-        &self,
-        snapshot: &AnalyticsSnapshot,
-        hash: &str,
-        canonical_json: &str,
-    ) -> Result<String> {
-        let snapshot_id = Uuid::new_v4().to_string();
+    /// Aggregate liquidity pool metrics from database
+    async fn aggregate_pool_metrics(
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<SnapshotPoolMetrics>> {
+        let query = r#"
+            SELECT
+                pool_id,
+                reserve_a_asset_code,
+                reserve_a_asset_issuer,
+                reserve_a_amount,
+                reserve_a_amount_exact,
+                reserve_b_asset_code,
+                reserve_b_asset_issuer,
+                reserve_b_amount,
+                reserve_b_amount_exact,
+                total_value_usd,
+                fee_bp
+            FROM liquidity_pools
+            ORDER BY pool_id
+        "#;
+
+        let rows = sqlx::query(query)
+            .fetch_all(&mut *conn)
+            .await
+            .context("Failed to fetch liquidity pools")?;
+
+        // Pools last synced before exact reserves were stored only have the
+        // floating point ones
+        let reserve = |exact: Option<String>, float: f64| {
+            exact.unwrap_or_else(|| stellar_amount_from_f64(float))
+        };
+        let metrics: Vec<SnapshotPoolMetrics> = rows
+            .into_iter()
+            .map(|row| SnapshotPoolMetrics {
+                pool_id: row.get("pool_id"),
+                reserve_a_asset_code: row.get("reserve_a_asset_code"),
+                reserve_a_asset_issuer: row.get("reserve_a_asset_issuer"),
+                reserve_a_amount: reserve(
+                    row.get("reserve_a_amount_exact"),
+                    row.get("reserve_a_amount"),
+                ),
+                reserve_b_asset_code: row.get("reserve_b_asset_code"),
+                reserve_b_asset_issuer: row.get("reserve_b_asset_issuer"),
+                reserve_b_amount: reserve(
+                    row.get("reserve_b_amount_exact"),
+                    row.get("reserve_b_amount"),
+                ),
+                total_value_usd: row.get("total_value_usd"),
+                fee_bp: row.get("fee_bp"),
+            })
+            .collect();
+
+        debug!("Aggregated {} liquidity pool metrics", metrics.len());
+        Ok(metrics)
+    }
+
+    /// Store snapshot and hash in database, once per epoch.
+    ///
+    /// Storing an epoch again with the same hash returns the existing id;
+    /// storing it with a different hash fails.
+    pub(crate) async fn store_snapshot_in_database(
+        &self,
+        snapshot: &AnalyticsSnapshot,
+        hash: &str,
+        hash_algorithm: HashAlgorithm,
+        canonical_json: &str,
+    ) -> Result<String> {
+        if let Some(snapshot_id) = self
+            .insert_snapshot(snapshot, hash, hash_algorithm, canonical_json)
+            .await?
+        {
+            return Ok(snapshot_id);
+        }
+
+        let (existing_id, existing_hash): (String, Option<String>) = sqlx::query_as(
+            "SELECT id, hash FROM snapshots WHERE entity_type = 'analytics_snapshot' AND epoch = ?",
+        )
+        .bind(snapshot.epoch as i64)
+        .fetch_one(self.db.pool())
+        .await
+        .context("Failed to fetch existing snapshot record")?;
+
+        if existing_hash.as_deref() != Some(hash) {
+            anyhow::bail!(
+                "Snapshot for epoch {} already stored with a different hash ({})",
+                snapshot.epoch,
+                existing_hash.as_deref().unwrap_or("none")
+            );
+        }
+
+        debug!(
+            "Snapshot for epoch {} already stored as {}",
+            snapshot.epoch, existing_id
+        );
+        Ok(existing_id)
+    }
+
+    /// Store a snapshot unless its epoch already has one, returning the new
+    /// id or `None` if nothing was stored. The epoch's unique index makes the
+    /// check and the insert one statement.
+    async fn insert_snapshot(
+        &self,
+        snapshot: &AnalyticsSnapshot,
+        hash: &str,
+        hash_algorithm: HashAlgorithm,
+        canonical_json: &str,
+    ) -> Result<Option<String>> {
+        let snapshot_id = Uuid::new_v4().to_string();
+
+        let query = r#"
+            INSERT INTO snapshots (
+                id, entity_id, entity_type, data, hash, hash_algorithm, epoch, timestamp,
+                created_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (epoch) WHERE entity_type = 'analytics_snapshot' DO NOTHING
+        "#;
+
+        let inserted = sqlx::query(query)
+            .bind(&snapshot_id)
+            .bind("system") // entity_id for system-wide snapshots
+            .bind("analytics_snapshot") // entity_type
+            .bind(canonical_json)
+            .bind(hash)
+            .bind(hash_algorithm.label())
+            .bind(snapshot.epoch as i64)
+            .bind(snapshot.timestamp)
+            .bind(Utc::now())
+            .execute(self.db.pool())
+            .await
+            .context("Failed to insert snapshot record")?;
+        if inserted.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        self.report_hash_collision(hash, snapshot.epoch).await;
+        Ok(Some(snapshot_id))
+    }
+
+    /// Log and count a collision when epochs other than `epoch` are stored
+    /// with `hash`. The snapshot stays stored; lookup failures only warn.
+    async fn report_hash_collision(&self, hash: &str, epoch: u64) {
+        let others: Vec<i64> = match sqlx::query_scalar(
+            r#"
+            SELECT epoch FROM snapshots
+            WHERE entity_type = 'analytics_snapshot' AND hash = ? AND epoch != ?
+            ORDER BY epoch
+            "#,
+        )
+        .bind(hash)
+        .bind(epoch as i64)
+        .fetch_all(self.db.pool())
+        .await
+        {
+            Ok(others) => others,
+            Err(e) => {
+                warn!(
+                    "Could not check snapshot hash {} for collisions: {}",
+                    hash, e
+                );
+                return;
+            }
+        };
+
+        if !others.is_empty() {
+            crate::observability::metrics::record_error("snapshot_hash_collision");
+            error!(
+                "Snapshot hash {} for epoch {} is already stored for epoch(s) {:?}; \
+                 the aggregation may have been duplicated",
+                hash, epoch, others
+            );
+        }
+    }
+
+    /// Every hash stored for more than one epoch, for auditing existing
+    /// snapshots
+    pub async fn find_hash_collisions(&self) -> Result<Vec<HashCollision>> {
+        let rows: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT hash, epoch FROM snapshots
+            WHERE entity_type = 'analytics_snapshot'
+              AND hash IN (
+                SELECT hash FROM snapshots
+                WHERE entity_type = 'analytics_snapshot' AND hash IS NOT NULL
+                GROUP BY hash
+                HAVING COUNT(DISTINCT epoch) > 1
+              )
+            ORDER BY hash, epoch
+            "#,
+        )
+        .fetch_all(self.db.pool())
+        .await
+        .context("Failed to look up snapshot hash collisions")?;
+
+        let mut collisions: Vec<HashCollision> = Vec::new();
+        for (hash, epoch) in rows {
+            match collisions.last_mut() {
+                Some(collision) if collision.hash == hash => collision.epochs.push(epoch as u64),
+                _ => collisions.push(HashCollision {
+                    hash,
+                    epochs: vec![epoch as u64],
+                }),
+            }
+        }
+        Ok(collisions)
+    }
+
+    /// Recompute the hashes of the `sample_size` most recent snapshots from
+    /// their stored data and report the ones that no longer match. Every
+    /// mismatch is logged and counted in the error metrics.
+    pub async fn audit_recent_snapshots(&self, sample_size: usize) -> Result<IntegrityAuditReport> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, epoch, data, hash, hash_algorithm FROM snapshots
+            WHERE entity_type = 'analytics_snapshot'
+            ORDER BY epoch DESC, created_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(sample_size as i64)
+        .fetch_all(self.db.pool())
+        .await
+        .context("Failed to sample snapshots for the integrity audit")?;
+
+        let mut report = IntegrityAuditReport {
+            checked: rows.len(),
+            corrupted: Vec::new(),
+        };
+        for row in rows {
+            let id: String = row.get("id");
+            let epoch = row.get::<i64, _>("epoch") as u64;
+            let stored_hash: Option<String> = row.get("hash");
+            let algorithm: String = row.get("hash_algorithm");
+            let data: String = row.get("data");
+
+            if let Err(reason) = Self::check_stored_hash(&data, stored_hash.as_deref(), &algorithm)
+            {
+                error!(
+                    "Snapshot {} for epoch {} failed the integrity audit: {}",
+                    id, epoch, reason
+                );
+                crate::observability::metrics::record_error("snapshot_integrity_mismatch");
+                report
+                    .corrupted
+                    .push(CorruptedSnapshot { id, epoch, reason });
+            }
+        }
+
+        info!(
+            "Snapshot integrity audit: {} of {} sampled snapshots corrupted",
+            report.corrupted.len(),
+            report.checked
+        );
+        Ok(report)
+    }
+
+    /// Run the startup integrity audit, failing with
+    /// `SnapshotCorruptionExceeded` when too many sampled snapshots are
+    /// corrupted
+    pub async fn run_startup_audit(
+        &self,
+        config: &IntegrityAuditConfig,
+    ) -> Result<IntegrityAuditReport> {
+        let report = self.audit_recent_snapshots(config.sample_size).await?;
+        if report.corruption_ratio() > config.max_corruption_ratio {
+            return Err(SnapshotCorruptionExceeded {
+                corrupted: report.corrupted.len(),
+                checked: report.checked,
+                max_ratio: config.max_corruption_ratio,
+            }
+            .into());
+        }
+        Ok(report)
+    }
+
+    /// Run every stored snapshot through `AnalyticsSnapshot::migrate` after
+    /// the database migrations, failing when one cannot be read by this
+    /// version. Returns how many were written under an earlier schema.
+    ///
+    /// The stored JSON is left as it is: its hash is what was submitted
+    /// on-chain, so loaded snapshots are upgraded in memory instead.
+    pub async fn migrate_stored_snapshots(&self) -> Result<usize> {
+        let rows = sqlx::query_as::<_, (i64, String)>(
+            "SELECT epoch, data FROM snapshots WHERE entity_type = 'analytics_snapshot'",
+        )
+        .fetch_all(self.db.pool())
+        .await
+        .context("Failed to fetch stored snapshots")?;
+
+        let mut outdated = 0;
+        for (epoch, data) in rows {
+            let snapshot = Self::parse_canonical_json(&data)
+                .with_context(|| format!("Failed to parse stored snapshot for epoch {}", epoch))?;
+            let stored_version = snapshot.schema_version;
+            snapshot.migrate().with_context(|| {
+                format!("Failed to migrate stored snapshot for epoch {}", epoch)
+            })?;
+            if stored_version < SCHEMA_VERSION {
+                outdated += 1;
+            }
+        }
+        Ok(outdated)
+    }
+
+    /// Check a stored hash against the hash of the stored data. The data is
+    /// hashed exactly as stored, so snapshots written before a change to the
+    /// serializer still check out.
+    fn check_stored_hash(
+        data: &str,
+        stored_hash: Option<&str>,
+        algorithm: &str,
+    ) -> std::result::Result<(), String> {
+        let algorithm = HashAlgorithm::parse(algorithm)
+            .ok_or_else(|| format!("unknown hash algorithm '{}'", algorithm))?;
+        let recomputed = algorithm.hex_digest(data.as_bytes());
+        match stored_hash {
+            Some(hash) if hash == recomputed => Ok(()),
+            Some(hash) => Err(format!(
+                "stored hash {} does not match recomputed hash {}",
+                hash, recomputed
+            )),
+            None => Err("no stored hash".to_string()),
+        }
+    }
+
+    /// Highest epoch with a stored analytics snapshot
+    pub async fn latest_epoch(&self) -> Result<Option<u64>> {
+        let epoch: Option<i64> = sqlx::query_scalar(
+            "SELECT MAX(epoch) FROM snapshots WHERE entity_type = 'analytics_snapshot'",
+        )
+        .fetch_one(self.db.pool())
+        .await
+        .context("Failed to fetch latest snapshot epoch")?;
+
+        Ok(epoch.map(|e| e as u64))
+    }
+
+    /// Whether an analytics snapshot is already stored for `epoch`
+    pub async fn snapshot_exists(&self, epoch: u64) -> Result<bool> {
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM snapshots WHERE entity_type = 'analytics_snapshot' AND epoch = ?)",
+        )
+        .bind(epoch as i64)
+        .fetch_one(self.db.pool())
+        .await
+        .context("Failed to check for existing snapshot")?;
+
+        Ok(exists)
+    }
+
+    /// Build a proof bundle for the latest stored snapshot of `epoch`.
+    ///
+    /// Fails if no snapshot exists or the stored hash no longer matches the
+    /// stored JSON under the algorithm recorded with it. The on-chain hash is included when a contract service is
+    /// configured and reachable.
+    pub async fn export_proof_bundle(&self, epoch: u64) -> Result<ProofBundle> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, data, hash, hash_algorithm, timestamp FROM snapshots
+            WHERE entity_type = 'analytics_snapshot' AND epoch = ?
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(epoch as i64)
+        .fetch_optional(self.db.pool())
+        .await
+        .context("Failed to fetch snapshot")?
+        .with_context(|| format!("No snapshot stored for epoch {}", epoch))?;
+
+        let canonical_json: String = row.get("data");
+        let stored_hash: Option<String> = row.get("hash");
+        let algorithm_name: String = row.get("hash_algorithm");
+        let hash_algorithm = HashAlgorithm::parse(&algorithm_name).with_context(|| {
+            format!(
+                "Snapshot for epoch {} uses unknown hash algorithm '{}'",
+                epoch, algorithm_name
+            )
+        })?;
+        let hash = hash_algorithm.hex_digest(canonical_json.as_bytes());
+        if stored_hash.as_deref() != Some(hash.as_str()) {
+            anyhow::bail!(
+                "Stored hash for epoch {} does not match its snapshot data",
+                epoch
+            );
+        }
+
+        let schema_version = serde_json::from_str::<Value>(&canonical_json)
+            .context("Stored snapshot is not valid JSON")?
+            .get("schema_version")
+            .and_then(Value::as_u64)
+            .map_or(SCHEMA_VERSION, |v| v as u32);
+
+        let on_chain_hash = match &self.contract_service {
+            Some(contract_service) => match contract_service.get_snapshot_by_epoch(epoch).await {
+                Ok(on_chain) => on_chain,
+                Err(e) => {
+                    warn!("Could not fetch on-chain hash for epoch {}: {}", epoch, e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let mut bundle = ProofBundle {
+            snapshot_id: row.get("id"),
+            epoch,
+            schema_version,
+            timestamp: row.get("timestamp"),
+            canonical_json,
+            hash,
+            hash_algorithm,
+            on_chain_hash,
+            merkle_root: None,
+            signing_key: None,
+            signature: None,
+        };
+
+        if let Some(key) = &self.proof_signing_key {
+            bundle.sign(key)?;
+        }
+
+        Ok(bundle)
+    }
+
+    /// Canonical JSON of the latest stored snapshot of `epoch`, `None` if
+    /// there is none. Fails if the stored hash no longer matches the JSON.
+    pub async fn canonical_json(&self, epoch: u64) -> Result<Option<StoredCanonicalJson>> {
+        let Some(row) = sqlx::query(
+            r#"
+            SELECT data, hash, hash_algorithm FROM snapshots
+            WHERE entity_type = 'analytics_snapshot' AND epoch = ?
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(epoch as i64)
+        .fetch_optional(self.db.pool())
+        .await
+        .context("Failed to fetch snapshot")?
+        else {
+            return Ok(None);
+        };
+
+        let canonical_json: String = row.get("data");
+        let stored_hash: Option<String> = row.get("hash");
+        let algorithm_name: String = row.get("hash_algorithm");
+        let hash_algorithm = HashAlgorithm::parse(&algorithm_name).with_context(|| {
+            format!(
+                "Snapshot for epoch {} uses unknown hash algorithm '{}'",
+                epoch, algorithm_name
+            )
+        })?;
+        let hash = hash_algorithm.hex_digest(canonical_json.as_bytes());
+        if stored_hash.as_deref() != Some(hash.as_str()) {
+            anyhow::bail!(
+                "Stored hash for epoch {} does not match its snapshot data",
+                epoch
+            );
+        }
+
+        Ok(Some(StoredCanonicalJson {
+            canonical_json,
+            hash,
+            hash_algorithm,
+        }))
+    }
+
+    /// Re-serialize the latest stored snapshot of `epoch` under
+    /// `schema_override` and return the resulting hash.
+    ///
+    /// The stored canonical JSON is parsed back into an `AnalyticsSnapshot`,
+    /// so the result can be compared with the stored hash to see whether a
+    /// schema or serialization change would alter it. Nothing is stored.
+    pub async fn recompute_from_stored_json(
+        &self,
+        epoch: u64,
+        schema_override: SchemaOverride,
+    ) -> Result<String> {
+        let row = sqlx::query(
+            r#"
+            SELECT data, hash_algorithm FROM snapshots
+            WHERE entity_type = 'analytics_snapshot' AND epoch = ?
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(epoch as i64)
+        .fetch_optional(self.db.pool())
+        .await
+        .context("Failed to fetch snapshot")?
+        .with_context(|| format!("No snapshot stored for epoch {}", epoch))?;
+
+        let canonical_json: String = row.get("data");
+        let algorithm = match schema_override.hash_algorithm {
+            Some(algorithm) => algorithm,
+            None => {
+                let name: String = row.get("hash_algorithm");
+                HashAlgorithm::parse(&name).with_context(|| {
+                    format!(
+                        "Snapshot for epoch {} uses unknown hash algorithm '{}'",
+                        epoch, name
+                    )
+                })?
+            }
+        };
+
+        let mut snapshot = Self::parse_canonical_json(&canonical_json)
+            .with_context(|| format!("Failed to parse stored snapshot for epoch {}", epoch))?;
+        if let Some(version) = schema_override.schema_version {
+            snapshot.schema_version = version;
+        }
+
+        let mut json = Self::serialize_deterministically(snapshot)?;
+        if let Some(decimal_places) = schema_override.float_precision {
+            let value: Value = serde_json::from_str(&json)?;
+            json = serde_json::to_string(&round_floats(value, decimal_places))?;
+        }
+
+        Ok(algorithm.hex_digest(json.as_bytes()))
+    }
+
+    /// Rebuild a snapshot from its canonical JSON. Top-level keys other than
+    /// the core ones are extension sections.
+    pub fn parse_canonical_json(canonical_json: &str) -> Result<AnalyticsSnapshot> {
+        let mut fields: Map<String, Value> =
+            serde_json::from_str(canonical_json).context("Snapshot is not a JSON object")?;
+
+        let section_names: Vec<String> = fields
+            .keys()
+            .filter(|key| !CORE_SNAPSHOT_KEYS.contains(&key.as_str()))
+            .cloned()
+            .collect();
+        let mut sections = BTreeMap::new();
+        for name in section_names {
+            match fields.remove(&name) {
+                Some(Value::Array(entries)) => {
+                    sections.insert(name, entries);
+                }
+                _ => anyhow::bail!("Snapshot section '{}' is not an array", name),
+            }
+        }
+
+        let mut snapshot: AnalyticsSnapshot = serde_json::from_value(Value::Object(fields))
+            .context("Snapshot does not match the analytics snapshot schema")?;
+        snapshot.sections = sections;
+        Ok(snapshot)
+    }
+
+    /// Compare `hash` with the stored hash of `epoch`, and the stored
+    /// snapshot with the hash the contract holds for it.
+    ///
+    /// `hash` is in the stored snapshot's algorithm, while the contract
+    /// always holds a SHA-256, so the on-chain comparison recomputes SHA-256
+    /// over the stored canonical JSON. The on-chain hash is looked up only
+    /// when a contract service is configured; lookup failures count as no
+    /// match.
+    pub async fn verify_hash(&self, epoch: u64, hash: &str) -> Result<SnapshotHashVerification> {
+        let hash = hash.trim().to_lowercase();
+
+        let stored = sqlx::query_as::<_, (String, Option<String>)>(
+            r#"
+            SELECT data, hash FROM snapshots
+            WHERE entity_type = 'analytics_snapshot' AND epoch = ?
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(epoch as i64)
+        .fetch_optional(self.db.pool())
+        .await
+        .context("Failed to fetch stored snapshot hash")?;
+
+        let on_chain_hash = match &self.contract_service {
+            Some(contract_service) => match contract_service.get_snapshot_by_epoch(epoch).await {
+                Ok(on_chain) => on_chain,
+                Err(e) => {
+                    warn!("Could not fetch on-chain hash for epoch {}: {}", epoch, e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let submitted_hash = stored
+            .as_ref()
+            .map(|(data, _)| hex::encode(Self::compute_sha256_hash_bytes(data)));
+        let stored_hash = stored.and_then(|(_, hash)| hash);
+        let matches_stored = stored_hash
+            .as_deref()
+            .is_some_and(|stored| stored.eq_ignore_ascii_case(&hash));
+        let matches_on_chain = matches!(
+            (&submitted_hash, &on_chain_hash),
+            (Some(submitted), Some(on_chain)) if submitted.eq_ignore_ascii_case(on_chain)
+        );
+
+        Ok(SnapshotHashVerification {
+            epoch,
+            hash,
+            stored_hash,
+            matches_stored,
+            on_chain_hash,
+            matches_on_chain,
+            canonical: matches_stored && matches_on_chain,
+        })
+    }
+
+    /// Check each of `epochs` against both the database and the contract.
+    /// The two are queried concurrently for an epoch, and up to
+    /// `BATCH_VERIFY_CONCURRENCY` epochs are checked at a time; results are
+    /// in the order of `epochs`.
+    pub async fn verify_epochs(&self, epochs: &[u64]) -> Result<Vec<EpochVerification>> {
+        stream::iter(epochs.iter().copied())
+            .map(|epoch| self.verify_epoch(epoch))
+            .buffered(BATCH_VERIFY_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// Submit the stored snapshots of `epochs` that the contract does not
+    /// hold yet, in one batch transaction where the contract supports it.
+    /// Epochs that are not stored, already on-chain, or whose on-chain state
+    /// could not be read are left out.
+    pub async fn submit_missing_epochs(&self, epochs: &[u64]) -> Result<BatchSubmissionResult> {
+        let contract_service = self
+            .contract_service
+            .as_ref()
+            .context("Contract service not configured")?;
+
+        let mut entries = Vec::new();
+        for verification in self.verify_epochs(epochs).await? {
+            if !verification.in_db || verification.on_chain || verification.chain_error.is_some() {
+                continue;
+            }
+            let Some(stored) = self.canonical_json(verification.epoch).await? else {
+                continue;
+            };
+            entries.push(SnapshotEntry {
+                hash: Self::compute_sha256_hash_bytes(&stored.canonical_json),
+                epoch: verification.epoch,
+            });
+        }
+
+        contract_service.submit_snapshots_batch(&entries).await
+    }
+
+    async fn verify_epoch(&self, epoch: u64) -> Result<EpochVerification> {
+        let stored = async {
+            sqlx::query_as::<_, (String, Option<String>)>(
+                r#"
+                SELECT data, hash FROM snapshots
+                WHERE entity_type = 'analytics_snapshot' AND epoch = ?
+                ORDER BY created_at DESC
+                LIMIT 1
+                "#,
+            )
+            .bind(epoch as i64)
+            .fetch_optional(self.db.pool())
+            .await
+            .with_context(|| format!("Failed to fetch stored snapshot of epoch {}", epoch))
+        };
+        let on_chain = async {
+            match &self.contract_service {
+                Some(contract_service) => contract_service
+                    .get_snapshot_by_epoch(epoch)
+                    .await
+                    .map_err(|e| e.to_string()),
+                None => Err("Contract service not configured".to_string()),
+            }
+        };
+        let (stored, on_chain) = tokio::join!(stored, on_chain);
+
+        let stored = stored?;
+        // The contract holds the SHA-256 of the canonical JSON, whichever
+        // algorithm the stored hash uses
+        let submitted_hash = stored
+            .as_ref()
+            .map(|(data, _)| hex::encode(Self::compute_sha256_hash_bytes(data)));
+        let (on_chain_hash, chain_error) = match on_chain {
+            Ok(hash) => (hash, None),
+            Err(e) => {
+                warn!("Could not fetch on-chain hash for epoch {}: {}", epoch, e);
+                (None, Some(e))
+            }
+        };
+        let hash_match = matches!(
+            (&submitted_hash, &on_chain_hash),
+            (Some(submitted), Some(on_chain)) if submitted.eq_ignore_ascii_case(on_chain)
+        );
+
+        Ok(EpochVerification {
+            epoch,
+            in_db: stored.is_some(),
+            on_chain: on_chain_hash.is_some(),
+            stored_hash: stored.and_then(|(_, hash)| hash),
+            on_chain_hash,
+            hash_match,
+            chain_error,
+        })
+    }
 
-        let query = r#"
-            INSERT INTO snapshots (
-                id, entity_id, entity_type, data, hash, epoch, timestamp, created_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-        "#;
+    /// Aggregate the current data for `epoch` and check that it serializes
+    /// deterministically. Nothing is stored or submitted.
+    pub async fn check_determinism(&self, epoch: u64) -> Result<DeterminismCheck> {
+        let snapshot = self.aggregate_all_metrics(epoch).await?;
+        Self::check_serialization(snapshot, Self::serialize_deterministically)
+            .context("Failed to serialize snapshot")
+    }
 
-        sqlx::query(query)
-            .bind(&snapshot_id)
-            .bind("system") // entity_id for system-wide snapshots
-            .bind("analytics_snapshot") // entity_type
-            .bind(canonical_json)
-            .bind(hash)
-            .bind(snapshot.epoch as i64)
-            .bind(snapshot.timestamp)
-            .bind(Utc::now())
-            .execute(self.db.pool())
-            .await
-            .context("Failed to insert snapshot record")?;
+    /// Serialize `snapshot` with `serialize` twice, then once more with its
+    /// anchor and corridor metrics in reverse order, and compare each output
+    /// with the first
+    pub fn check_serialization<F>(
+        snapshot: AnalyticsSnapshot,
+        serialize: F,
+    ) -> Result<DeterminismCheck, serde_json::Error>
+    where
+        F: Fn(AnalyticsSnapshot) -> Result<String, serde_json::Error>,
+    {
+        let mut reordered = snapshot.clone();
+        reordered.anchor_metrics.reverse();
+        reordered.corridor_metrics.reverse();
+
+        let first = serialize(snapshot.clone())?;
+        let mut mismatches = Vec::new();
+        if serialize(snapshot.clone())? != first {
+            mismatches.push("repeat".to_string());
+        }
+        if serialize(reordered)? != first {
+            mismatches.push("reordered_input".to_string());
+        }
 
-        Ok(snapshot_id)
+        Ok(DeterminismCheck {
+            epoch: snapshot.epoch,
+            schema_version: snapshot.schema_version,
+            passed: mismatches.is_empty(),
+            hash: hex::encode(Sha256::digest(first.as_bytes())),
+            anchor_count: snapshot.anchor_metrics.len(),
+            corridor_count: snapshot.corridor_metrics.len(),
+            mismatches,
+        })
     }
 
-    /// Verify that the submission was successful by querying the contract
     /// Verify that a snapshot submission was successful by checking on-chain
     ///
     /// Note: The submission parameter is intentionally unused as we verify directly
@@ -327,33 +1747,46 @@ impl SnapshotService {
         epoch: u64,
         _submission: &SubmissionResult, // Intentionally unused - we verify from contract
     ) -> Result<bool> {
-        if let Some(contract_service) = &self.contract_service {
-            // Wait a moment for the transaction to be confirmed
-            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+        self.confirm_on_chain(hash, epoch).await
+    }
+
+    /// Check the contract until it holds `hash` for `epoch`, waiting between
+    /// checks as the confirmation config says. Returns `false` if the hash
+    /// has not appeared after the last check or no contract is configured;
+    /// check errors count as not yet confirmed.
+    pub async fn confirm_on_chain(&self, hash: &str, epoch: u64) -> Result<bool> {
+        let Some(contract_service) = &self.contract_service else {
+            return Ok(false);
+        };
+
+        for check in 0..self.confirmation.max_checks {
+            tokio::time::sleep(self.confirmation.delay_before(check)).await;
 
             match contract_service.verify_snapshot_exists(hash, epoch).await {
-                Ok(exists) => {
-                    if exists {
-                        info!(
-                            "Verification successful: snapshot exists on-chain for epoch {}",
-                            epoch
-                        );
-                    } else {
-                        warn!(
-                            "Verification failed: snapshot not found on-chain for epoch {}",
-                            epoch
-                        );
-                    }
-                    Ok(exists)
-                }
-                Err(e) => {
-                    error!("Verification error: {}", e);
-                    Ok(false) // Don't fail the entire process for verification errors
+                Ok(true) => {
+                    info!(
+                        "Verification successful: snapshot exists on-chain for epoch {} after {} checks",
+                        epoch,
+                        check + 1
+                    );
+                    return Ok(true);
                 }
+                Ok(false) => debug!(
+                    "Snapshot for epoch {} not on-chain yet (check {} of {})",
+                    epoch,
+                    check + 1,
+                    self.confirmation.max_checks
+                ),
+                // Don't fail the entire process for verification errors
+                Err(e) => error!("Verification error: {}", e),
             }
-        } else {
-            Ok(false)
         }
+
+        warn!(
+            "Verification failed: snapshot not found on-chain for epoch {} after {} checks",
+            epoch, self.confirmation.max_checks
+        );
+        Ok(false)
     }
 }
 
@@ -370,10 +1803,15 @@ impl SnapshotService {
     /// * `snapshot` - The analytics snapshot to serialize
     ///
     /// # Returns
-    /// A canonical JSON string representation suitable for hashing
+    /// A canonical JSON string representation suitable for hashing, or an
+    /// error when two anchor or corridor metrics share an id
     pub fn serialize_deterministically(
         mut snapshot: AnalyticsSnapshot,
     ) -> Result<String, serde_json::Error> {
+        snapshot
+            .check_unique_ids()
+            .map_err(serde::ser::Error::custom)?;
+
         // Normalize the snapshot (sort all arrays by ID)
         snapshot.normalize();
 
@@ -394,10 +1832,11 @@ impl SnapshotService {
         );
 
         // Serialize anchor metrics array (already sorted by normalize())
+        let schema_version = snapshot.schema_version;
         let anchor_metrics: Vec<Value> = snapshot
             .anchor_metrics
             .into_iter()
-            .map(|m| Self::serialize_anchor_metrics(&m))
+            .map(|m| Self::serialize_anchor_metrics(&m, schema_version))
             .collect();
         map.insert("anchor_metrics".to_string(), Value::Array(anchor_metrics));
 
@@ -405,13 +1844,18 @@ impl SnapshotService {
         let corridor_metrics: Vec<Value> = snapshot
             .corridor_metrics
             .into_iter()
-            .map(|m| Self::serialize_corridor_metrics(&m))
+            .map(|m| Self::serialize_corridor_metrics(&m, schema_version))
             .collect();
         map.insert(
             "corridor_metrics".to_string(),
             Value::Array(corridor_metrics),
         );
 
+        // Extension sections are already canonical (sorted by add_section())
+        for (name, entries) in snapshot.sections {
+            map.insert(name, Value::Array(entries));
+        }
+
         // Convert to JSON string with no extra whitespace
         // Note: serde_json::Map uses IndexMap internally which preserves insertion order.
         // Since we iterate over BTreeMap (sorted), insertion order is sorted, ensuring determinism.
@@ -423,7 +1867,7 @@ impl SnapshotService {
     }
 
     /// Serialize anchor metrics to a deterministic JSON value
-    fn serialize_anchor_metrics(metrics: &SnapshotAnchorMetrics) -> Value {
+    fn serialize_anchor_metrics(metrics: &SnapshotAnchorMetrics, schema_version: u32) -> Value {
         let mut map = BTreeMap::new();
 
         map.insert("id".to_string(), Value::String(metrics.id.to_string()));
@@ -467,7 +1911,10 @@ impl SnapshotService {
         }
 
         if let Some(volume) = metrics.volume_usd {
-            map.insert("volume_usd".to_string(), Self::serialize_f64(volume));
+            map.insert(
+                "volume_usd".to_string(),
+                Self::serialize_amount(volume, schema_version),
+            );
         } else {
             map.insert("volume_usd".to_string(), Value::Null);
         }
@@ -484,7 +1931,7 @@ impl SnapshotService {
     }
 
     /// Serialize corridor metrics to a deterministic JSON value
-    fn serialize_corridor_metrics(metrics: &SnapshotCorridorMetrics) -> Value {
+    fn serialize_corridor_metrics(metrics: &SnapshotCorridorMetrics, schema_version: u32) -> Value {
         let mut map = BTreeMap::new();
 
         map.insert("id".to_string(), Value::String(metrics.id.to_string()));
@@ -526,7 +1973,7 @@ impl SnapshotService {
         );
         map.insert(
             "volume_usd".to_string(),
-            Self::serialize_f64(metrics.volume_usd),
+            Self::serialize_amount(metrics.volume_usd, schema_version),
         );
 
         if let Some(ms) = metrics.avg_settlement_latency_ms {
@@ -540,7 +1987,7 @@ impl SnapshotService {
 
         map.insert(
             "liquidity_depth_usd".to_string(),
-            Self::serialize_f64(metrics.liquidity_depth_usd),
+            Self::serialize_amount(metrics.liquidity_depth_usd, schema_version),
         );
 
         // serde_json::Map preserves insertion order (uses IndexMap internally)
@@ -552,12 +1999,74 @@ impl SnapshotService {
         Value::Object(json_map)
     }
 
+    /// Serialize liquidity pool metrics to a deterministic JSON value
+    ///
+    /// Under `EXACT_AMOUNTS_SCHEMA_VERSION` reserves are written as their
+    /// decimal strings, so amounts beyond `f64` precision hash exactly.
+    fn serialize_pool_metrics(metrics: &SnapshotPoolMetrics, schema_version: u32) -> Value {
+        let reserve = |amount: &str| {
+            if schema_version >= EXACT_AMOUNTS_SCHEMA_VERSION {
+                Value::String(amount.to_string())
+            } else {
+                Self::serialize_f64(amount.parse().unwrap_or(f64::NAN))
+            }
+        };
+        let optional_string = |value: &Option<String>| {
+            value
+                .as_ref()
+                .map_or(Value::Null, |s| Value::String(s.clone()))
+        };
+
+        let mut map = BTreeMap::new();
+
+        map.insert(
+            "pool_id".to_string(),
+            Value::String(metrics.pool_id.clone()),
+        );
+        map.insert(
+            "reserve_a_asset_code".to_string(),
+            Value::String(metrics.reserve_a_asset_code.clone()),
+        );
+        map.insert(
+            "reserve_a_asset_issuer".to_string(),
+            optional_string(&metrics.reserve_a_asset_issuer),
+        );
+        map.insert(
+            "reserve_a_amount".to_string(),
+            reserve(&metrics.reserve_a_amount),
+        );
+        map.insert(
+            "reserve_b_asset_code".to_string(),
+            Value::String(metrics.reserve_b_asset_code.clone()),
+        );
+        map.insert(
+            "reserve_b_asset_issuer".to_string(),
+            optional_string(&metrics.reserve_b_asset_issuer),
+        );
+        map.insert(
+            "reserve_b_amount".to_string(),
+            reserve(&metrics.reserve_b_amount),
+        );
+        map.insert(
+            "total_value_usd".to_string(),
+            Self::serialize_amount(metrics.total_value_usd, schema_version),
+        );
+        map.insert("fee_bp".to_string(), Value::Number(metrics.fee_bp.into()));
+
+        let mut json_map = Map::new();
+        for (k, v) in map {
+            json_map.insert(k, v);
+        }
+        Value::Object(json_map)
+    }
+
     /// Serialize f64 to a deterministic JSON number representation
     ///
     /// This ensures that floating point numbers are always serialized
     /// in the same way. serde_json handles this deterministically,
-    /// but we ensure special cases are handled consistently.
-    fn serialize_f64(value: f64) -> Value {
+    /// but we ensure special cases are handled consistently. Snapshot
+    /// sections should use this for their floating point fields.
+    pub fn serialize_f64(value: f64) -> Value {
         if value.is_finite() {
             // serde_json::Number::from_f64 uses ryu algorithm which is deterministic
             serde_json::Number::from_f64(value)
@@ -579,6 +2088,17 @@ impl SnapshotService {
         }
     }
 
+    /// Serialize a monetary value: a JSON number up to schema version 3, a
+    /// decimal string with `STELLAR_AMOUNT_DECIMALS` places from
+    /// `EXACT_AMOUNTS_SCHEMA_VERSION`
+    fn serialize_amount(value: f64, schema_version: u32) -> Value {
+        if schema_version >= EXACT_AMOUNTS_SCHEMA_VERSION && value.is_finite() {
+            Value::String(stellar_amount_from_f64(value))
+        } else {
+            Self::serialize_f64(value)
+        }
+    }
+
     /// Compute SHA-256 hash of a string and return the bytes
     fn compute_sha256_hash_bytes(data: &str) -> [u8; 32] {
         let mut hasher = Sha256::new();
@@ -636,10 +2156,10 @@ impl SnapshotService {
     pub fn version_and_hash(
         snapshot: AnalyticsSnapshot,
     ) -> Result<([u8; 32], String, u32), serde_json::Error> {
-        // Ensure snapshot has correct schema version
+        let schema_version = snapshot.schema_version;
         let hash = Self::hash_snapshot(snapshot)?;
         let hash_hex = hex::encode(hash);
-        Ok((hash, hash_hex, SCHEMA_VERSION))
+        Ok((hash, hash_hex, schema_version))
     }
 
     /// Create snapshot, hash it, and submit to on-chain contract
@@ -688,7 +2208,9 @@ impl SnapshotService {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::snapshot::schema::{SnapshotAnchorMetrics, SnapshotCorridorMetrics};
+    use crate::snapshot::schema::{
+        SnapshotAnchorMetrics, SnapshotCorridorMetrics, SnapshotSection, LATEST_SCHEMA_VERSION,
+    };
     use chrono::Utc;
     use uuid::Uuid;
 
@@ -727,6 +2249,278 @@ mod tests {
         }
     }
 
+    async fn insert_anchor(pool: &sqlx::SqlitePool, id: u128) {
+        sqlx::query(
+            r#"
+            INSERT INTO anchors (
+                id, name, stellar_account, total_transactions, successful_transactions,
+                failed_transactions, status
+            )
+            VALUES ($1, $2, $3, 100, 99, 1, 'green')
+            "#,
+        )
+        .bind(Uuid::from_u128(id).to_string())
+        .bind(format!("Anchor{}", id))
+        .bind(format!("GANCHOR{}", id))
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    /// Anchors already present, including those seeded by the migrations
+    async fn anchor_count(pool: &sqlx::SqlitePool) -> usize {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM anchors")
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        count as usize
+    }
+
+    async fn insert_corridor_metrics(pool: &sqlx::SqlitePool, key: &str) {
+        sqlx::query(
+            r#"
+            INSERT INTO corridor_metrics (
+                corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+                date, total_transactions, successful_transactions, failed_transactions,
+                success_rate, volume_usd
+            )
+            VALUES ($1, 'USDC', 'issuer1', 'EURC', 'issuer2', datetime('now'), 10, 9, 1, 90.0, 100.0)
+            "#,
+        )
+        .bind(key)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_interleaved_write_does_not_tear_aggregation() {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+
+        // WAL lets the writer proceed while the aggregation read is open
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}", dir.path().join("snapshot.db").display());
+        let options = SqliteConnectOptions::from_str(&url)
+            .unwrap()
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(2)
+            .connect_with(options)
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        let seeded = anchor_count(&pool).await;
+
+        insert_anchor(&pool, 1).await;
+        insert_corridor_metrics(&pool, "USDC:issuer1->EURC:issuer2").await;
+
+        let mut tx = pool.begin().await.unwrap();
+        let anchors = SnapshotService::aggregate_anchor_metrics(&mut tx)
+            .await
+            .unwrap();
+
+        // A sync lands between the anchor and corridor reads
+        insert_anchor(&pool, 2).await;
+        insert_corridor_metrics(&pool, "USDC:issuer1->GBPC:issuer3").await;
+
+        let corridors = SnapshotService::aggregate_corridor_metrics(&mut tx)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(anchors.len(), seeded + 1);
+        assert_eq!(corridors.len(), 1);
+
+        // The next snapshot sees the whole write
+        let service = SnapshotService::new(Arc::new(Database::new(pool)), None);
+        let snapshot = service.aggregate_all_metrics(2).await.unwrap();
+        assert_eq!(snapshot.anchor_metrics.len(), seeded + 2);
+        assert_eq!(snapshot.corridor_metrics.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_aggregation_reads_in_one_transaction() {
+        use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+        use std::str::FromStr;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let dir = tempfile::tempdir().unwrap();
+        let url = format!("sqlite://{}", dir.path().join("snapshot.db").display());
+        let options = SqliteConnectOptions::from_str(&url)
+            .unwrap()
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+
+        // Count every connection handed out, new or reused
+        let acquired = Arc::new(AtomicUsize::new(0));
+        let (on_connect, on_reuse) = (Arc::clone(&acquired), Arc::clone(&acquired));
+        let pool = SqlitePoolOptions::new()
+            .max_connections(4)
+            .after_connect(move |_, _| {
+                on_connect.fetch_add(1, Ordering::SeqCst);
+                Box::pin(async { Ok(()) })
+            })
+            .before_acquire(move |_, _| {
+                on_reuse.fetch_add(1, Ordering::SeqCst);
+                Box::pin(async { Ok(true) })
+            })
+            .connect_with(options)
+            .await
+            .unwrap();
+        sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+        insert_anchor(&pool, 1).await;
+        insert_corridor_metrics(&pool, "USDC:issuer1->EURC:issuer2").await;
+
+        let service = SnapshotService::new(Arc::new(Database::new(pool)), None);
+        let before = acquired.load(Ordering::SeqCst);
+        let snapshot = service.aggregate_all_metrics(1).await.unwrap();
+
+        assert!(!snapshot.anchor_metrics.is_empty());
+        assert_eq!(snapshot.corridor_metrics.len(), 1);
+        // Anchors, corridors and pools all came from one transaction
+        assert_eq!(acquired.load(Ordering::SeqCst) - before, 1);
+    }
+
+    #[sqlx::test]
+    async fn test_oversized_snapshot_is_not_stored(pool: sqlx::SqlitePool) {
+        for id in 1..=3 {
+            insert_anchor(&pool, id).await;
+        }
+        let anchors = anchor_count(&pool).await;
+        let db = Arc::new(Database::new(pool.clone()));
+
+        let service =
+            SnapshotService::new(Arc::clone(&db), None).with_size_limits(SnapshotSizeLimits {
+                max_metrics: anchors - 1,
+                ..SnapshotSizeLimits::default()
+            });
+        let err = service.generate_and_submit_snapshot(1).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SnapshotTooLarge>(),
+            Some(&SnapshotTooLarge::Metrics {
+                epoch: 1,
+                count: anchors,
+                limit: anchors - 1
+            })
+        );
+
+        let service =
+            SnapshotService::new(Arc::clone(&db), None).with_size_limits(SnapshotSizeLimits {
+                max_bytes: 256,
+                ..SnapshotSizeLimits::default()
+            });
+        let err = service.generate_and_submit_snapshot(1).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<SnapshotTooLarge>(),
+            Some(SnapshotTooLarge::Bytes { limit: 256, .. })
+        ));
+        assert_eq!(stored_count(&pool, 1).await, 0);
+
+        let service = SnapshotService::new(db, None).with_size_limits(SnapshotSizeLimits {
+            max_metrics: anchors,
+            ..SnapshotSizeLimits::default()
+        });
+        let result = service.generate_and_submit_snapshot(1).await.unwrap();
+        assert_eq!(result.anchor_count, anchors);
+        assert_eq!(stored_count(&pool, 1).await, 1);
+    }
+
+    #[sqlx::test]
+    async fn test_startup_audit_detects_corrupted_snapshot(pool: sqlx::SqlitePool) {
+        insert_anchor(&pool, 1).await;
+        let db = Arc::new(Database::new(pool.clone()));
+        let service = SnapshotService::new(db, None);
+        for epoch in 1..=4 {
+            service.generate_and_submit_snapshot(epoch).await.unwrap();
+        }
+
+        let report = service.audit_recent_snapshots(10).await.unwrap();
+        assert_eq!(report.checked, 4);
+        assert!(report.corrupted.is_empty());
+
+        // Tamper with one snapshot's data, leaving its hash as it was
+        sqlx::query(
+            "UPDATE snapshots SET data = REPLACE(data, '\"epoch\":2', '\"epoch\":20') WHERE epoch = 2",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let report = service.audit_recent_snapshots(10).await.unwrap();
+        assert_eq!(report.checked, 4);
+        assert_eq!(report.corrupted.len(), 1);
+        assert_eq!(report.corrupted[0].epoch, 2);
+        assert_eq!(report.corruption_ratio(), 0.25);
+
+        // Only the sampled, most recent snapshots are audited
+        let report = service.audit_recent_snapshots(2).await.unwrap();
+        assert_eq!(report.checked, 2);
+        assert!(report.corrupted.is_empty());
+
+        let tolerant = IntegrityAuditConfig {
+            enabled: true,
+            sample_size: 10,
+            max_corruption_ratio: 0.25,
+        };
+        assert!(service.run_startup_audit(&tolerant).await.is_ok());
+
+        let strict = IntegrityAuditConfig {
+            max_corruption_ratio: 0.2,
+            ..tolerant
+        };
+        let err = service.run_startup_audit(&strict).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<SnapshotCorruptionExceeded>(),
+            Some(&SnapshotCorruptionExceeded {
+                corrupted: 1,
+                checked: 4,
+                max_ratio: 0.2
+            })
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_migrate_stored_snapshots(pool: sqlx::SqlitePool) {
+        insert_anchor(&pool, 1).await;
+        let db = Arc::new(Database::new(pool.clone()));
+        let service = SnapshotService::new(db, None);
+        for epoch in 1..=2 {
+            service.generate_and_submit_snapshot(epoch).await.unwrap();
+        }
+        assert_eq!(service.migrate_stored_snapshots().await.unwrap(), 0);
+
+        let set_version = |epoch: i64, version: u32| {
+            sqlx::query("UPDATE snapshots SET data = REPLACE(data, ?, ?) WHERE epoch = ?")
+                .bind(format!("\"schema_version\":{}", SCHEMA_VERSION))
+                .bind(format!("\"schema_version\":{}", version))
+                .bind(epoch)
+                .execute(&pool)
+        };
+
+        set_version(1, 1).await.unwrap();
+        assert_eq!(service.migrate_stored_snapshots().await.unwrap(), 1);
+
+        set_version(2, LATEST_SCHEMA_VERSION + 1).await.unwrap();
+        assert!(service.migrate_stored_snapshots().await.is_err());
+    }
+
+    #[test]
+    fn test_stored_hash_is_checked_against_the_stored_bytes() {
+        // Not what the current serializer would write for any snapshot
+        let data = r#"{ "epoch": 7, "legacy_field": true }"#;
+        let hash = HashAlgorithm::Sha256.hex_digest(data.as_bytes());
+        assert_eq!(
+            SnapshotService::check_stored_hash(data, Some(&hash), "sha256"),
+            Ok(())
+        );
+
+        let tampered = data.replace('7', "8");
+        let err = SnapshotService::check_stored_hash(&tampered, Some(&hash), "sha256").unwrap_err();
+        assert!(err.contains("does not match"));
+    }
+
     #[test]
     fn test_deterministic_serialization() {
         let now = Utc::now();
@@ -750,6 +2544,271 @@ mod tests {
         assert_eq!(json1, json2);
     }
 
+    #[test]
+    fn test_serialization_rejects_duplicate_ids() {
+        let id = Uuid::from_u128(1);
+        let mut snapshot = AnalyticsSnapshot::new(1, Utc::now());
+        snapshot.add_anchor_metrics(create_test_anchor_metrics(id, "Anchor1"));
+        snapshot.add_anchor_metrics(create_test_anchor_metrics(id, "Anchor1 copy"));
+
+        let err = SnapshotService::serialize_deterministically(snapshot).unwrap_err();
+        assert!(err.to_string().contains("more than one anchor metric"));
+    }
+
+    struct MockFeeSection;
+
+    struct MockFeeEntry {
+        asset: String,
+        median_fee: f64,
+    }
+
+    impl SnapshotSection for MockFeeSection {
+        const NAME: &'static str = "fee_metrics";
+        type Entry = MockFeeEntry;
+
+        fn sort_key(entry: &MockFeeEntry) -> String {
+            entry.asset.clone()
+        }
+
+        fn serialize_entry(entry: &MockFeeEntry, _schema_version: u32) -> Value {
+            serde_json::json!({
+                "median_fee": SnapshotService::serialize_f64(entry.median_fee),
+                "asset": entry.asset,
+            })
+        }
+    }
+
+    fn fee_entry(asset: &str, median_fee: f64) -> MockFeeEntry {
+        MockFeeEntry {
+            asset: asset.to_string(),
+            median_fee,
+        }
+    }
+
+    #[test]
+    fn test_section_serialization_is_deterministic() {
+        let now = Utc::now();
+
+        let mut snapshot1 = AnalyticsSnapshot::new(1, now);
+        snapshot1.add_anchor_metrics(create_test_anchor_metrics(Uuid::from_u128(1), "Anchor1"));
+        snapshot1
+            .add_section::<MockFeeSection>(&[fee_entry("XLM", 100.0), fee_entry("USDC", 250.5)])
+            .unwrap();
+
+        let mut snapshot2 = AnalyticsSnapshot::new(1, now);
+        snapshot2
+            .add_section::<MockFeeSection>(&[fee_entry("USDC", 250.5), fee_entry("XLM", 100.0)])
+            .unwrap();
+        snapshot2.add_anchor_metrics(create_test_anchor_metrics(Uuid::from_u128(1), "Anchor1"));
+
+        let json1 = SnapshotService::serialize_deterministically(snapshot1).unwrap();
+        let json2 = SnapshotService::serialize_deterministically(snapshot2).unwrap();
+        assert_eq!(json1, json2);
+
+        // Sections sit among the core keys in sorted order, entries by sort key
+        assert!(json1.contains(
+            r#""epoch":1,"fee_metrics":[{"asset":"USDC","median_fee":250.5},{"asset":"XLM","median_fee":100.0}],"schema_version":3,"#
+        ));
+    }
+
+    #[test]
+    fn test_section_cannot_shadow_core_field() {
+        struct Shadowing;
+        impl SnapshotSection for Shadowing {
+            const NAME: &'static str = "anchor_metrics";
+            type Entry = ();
+            fn sort_key(_: &()) -> String {
+                String::new()
+            }
+            fn serialize_entry(_: &(), _: u32) -> Value {
+                Value::Null
+            }
+        }
+
+        let mut snapshot = AnalyticsSnapshot::new(1, Utc::now());
+        assert!(snapshot.add_section::<Shadowing>(&[()]).is_err());
+        assert!(snapshot.sections.is_empty());
+    }
+
+    fn pool_metrics(pool_id: &str, reserve_a_amount: &str) -> SnapshotPoolMetrics {
+        SnapshotPoolMetrics {
+            pool_id: pool_id.to_string(),
+            reserve_a_asset_code: "XLM".to_string(),
+            reserve_a_asset_issuer: None,
+            reserve_a_amount: reserve_a_amount.to_string(),
+            reserve_b_asset_code: "USDC".to_string(),
+            reserve_b_asset_issuer: Some("GISSUER".to_string()),
+            reserve_b_amount: "500.2500000".to_string(),
+            total_value_usd: 1500.5,
+            fee_bp: 30,
+        }
+    }
+
+    #[test]
+    fn test_pool_metrics_serialization_is_deterministic() {
+        let now = Utc::now();
+        let pools = [
+            pool_metrics("bb", "1000.0000000"),
+            pool_metrics("aa", "2000.0000000"),
+        ];
+        let reversed = [
+            pool_metrics("aa", "2000.0000000"),
+            pool_metrics("bb", "1000.0000000"),
+        ];
+
+        let mut snapshot1 = AnalyticsSnapshot::new(1, now);
+        snapshot1.add_section::<PoolMetricsSection>(&pools).unwrap();
+        let mut snapshot2 = AnalyticsSnapshot::new(1, now);
+        snapshot2
+            .add_section::<PoolMetricsSection>(&reversed)
+            .unwrap();
+
+        let json = SnapshotService::serialize_deterministically(snapshot1.clone()).unwrap();
+        assert_eq!(
+            json,
+            SnapshotService::serialize_deterministically(snapshot2.clone()).unwrap()
+        );
+        assert!(json.contains(
+            r#""pool_metrics":[{"fee_bp":30,"pool_id":"aa","reserve_a_amount":2000.0,"reserve_a_asset_code":"XLM","reserve_a_asset_issuer":null,"#
+        ));
+
+        // Same pools hash the same; a changed reserve changes the hash
+        let hash = SnapshotService::hash_snapshot(snapshot1).unwrap();
+        assert_eq!(hash, SnapshotService::hash_snapshot(snapshot2).unwrap());
+
+        let mut changed = AnalyticsSnapshot::new(1, now);
+        changed
+            .add_section::<PoolMetricsSection>(&[
+                pool_metrics("aa", "2000.0000000"),
+                pool_metrics("bb", "1000.5000000"),
+            ])
+            .unwrap();
+        assert_ne!(hash, SnapshotService::hash_snapshot(changed).unwrap());
+    }
+
+    #[test]
+    fn test_pool_reserves_beyond_f64_precision_hash_exactly() {
+        // Largest Stellar amount; as f64 it reads back as 922337203685.4775
+        let max_amount = "922337203685.4775807";
+        assert_ne!(
+            SnapshotService::serialize_f64(max_amount.parse().unwrap()).to_string(),
+            max_amount
+        );
+
+        let pool = pool_metrics("aa", max_amount);
+        let entry = SnapshotService::serialize_pool_metrics(&pool, EXACT_AMOUNTS_SCHEMA_VERSION);
+        assert_eq!(entry["reserve_a_amount"], max_amount);
+        assert_eq!(entry["total_value_usd"], "1500.5000000");
+        // The default schema version still writes numbers
+        let entry = SnapshotService::serialize_pool_metrics(&pool, SCHEMA_VERSION);
+        assert_eq!(entry["reserve_a_amount"], 922337203685.4775);
+        assert_eq!(entry["total_value_usd"], 1500.5);
+
+        // A one-stroop difference survives into the hash
+        let hash_with = |amount: &str| {
+            let mut snapshot = AnalyticsSnapshot::new(1, DateTime::UNIX_EPOCH);
+            snapshot.schema_version = EXACT_AMOUNTS_SCHEMA_VERSION;
+            snapshot
+                .add_section::<PoolMetricsSection>(&[pool_metrics("aa", amount)])
+                .unwrap();
+            SnapshotService::hash_snapshot(snapshot).unwrap()
+        };
+        assert_ne!(hash_with(max_amount), hash_with("922337203685.4775806"));
+    }
+
+    #[sqlx::test]
+    async fn test_aggregate_includes_liquidity_pools(pool: sqlx::SqlitePool) {
+        sqlx::query(
+            r#"
+            INSERT INTO liquidity_pools (
+                pool_id, fee_bp, reserve_a_asset_code, reserve_a_amount,
+                reserve_b_asset_code, reserve_b_asset_issuer, reserve_b_amount, total_value_usd
+            )
+            VALUES ('pool-1', 30, 'XLM', 1000.0, 'USDC', 'GISSUER', 500.25, 1500.5)
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let service = SnapshotService::new(Arc::new(Database::new(pool)), None);
+        let snapshot = service.aggregate_all_metrics(1).await.unwrap();
+
+        let pools = &snapshot.sections[POOL_METRICS_SECTION];
+        assert_eq!(pools.len(), 1);
+        assert_eq!(
+            pools[0],
+            SnapshotService::serialize_pool_metrics(
+                &pool_metrics("pool-1", "1000.0000000"),
+                SCHEMA_VERSION
+            )
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_exact_amounts_snapshot_keeps_stored_reserves(pool: sqlx::SqlitePool) {
+        // Not representable as f64, which reads it as 922337203685.4775
+        let max_amount = "922337203685.4775807";
+        sqlx::query(
+            r#"
+            INSERT INTO liquidity_pools (
+                pool_id, fee_bp, reserve_a_asset_code, reserve_a_amount, reserve_a_amount_exact,
+                reserve_b_asset_code, reserve_b_asset_issuer, reserve_b_amount, total_value_usd
+            )
+            VALUES ('pool-1', 30, 'XLM', ?, ?, 'USDC', 'GISSUER', 500.25, 1500.5)
+            "#,
+        )
+        .bind(max_amount.parse::<f64>().unwrap())
+        .bind(max_amount)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let service =
+            SnapshotService::new(Arc::new(Database::new(pool)), None).with_exact_amounts();
+        let (snapshot, json) = service.build_snapshot(1).await.unwrap();
+
+        assert_eq!(snapshot.schema_version, EXACT_AMOUNTS_SCHEMA_VERSION);
+        let value: Value = serde_json::from_str(&json).unwrap();
+        let entry = &value[POOL_METRICS_SECTION][0];
+        assert_eq!(entry["reserve_a_amount"], max_amount);
+        // Without an exact value the stored float is used
+        assert_eq!(entry["reserve_b_amount"], "500.2500000");
+        assert_eq!(entry["total_value_usd"], "1500.5000000");
+        for anchor in value["anchor_metrics"].as_array().unwrap() {
+            assert!(anchor["volume_usd"].is_string() || anchor["volume_usd"].is_null());
+        }
+
+        // Parsing the stored JSON back reproduces it, and so its hash
+        let parsed = SnapshotService::parse_canonical_json(&json).unwrap();
+        assert_eq!(
+            SnapshotService::serialize_deterministically(parsed).unwrap(),
+            json
+        );
+    }
+
+    #[test]
+    fn test_determinism_check_detects_order_dependence() {
+        let mut snapshot = AnalyticsSnapshot::new(1, Utc::now());
+        snapshot.add_anchor_metrics(create_test_anchor_metrics(Uuid::from_u128(1), "Anchor1"));
+        snapshot.add_anchor_metrics(create_test_anchor_metrics(Uuid::from_u128(2), "Anchor2"));
+
+        let check = SnapshotService::check_serialization(
+            snapshot.clone(),
+            SnapshotService::serialize_deterministically,
+        )
+        .unwrap();
+        assert!(check.passed);
+        assert!(check.mismatches.is_empty());
+        assert_eq!(check.anchor_count, 2);
+
+        // Plain serde output keeps the input order, so reordering changes it
+        let check =
+            SnapshotService::check_serialization(snapshot, |s| serde_json::to_string(&s)).unwrap();
+        assert!(!check.passed);
+        assert_eq!(check.mismatches, ["reordered_input"]);
+    }
+
     #[test]
     fn test_same_input_same_hash() {
         let now = Utc::now();
@@ -930,4 +2989,245 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_hash_algorithm_digests_are_stable() {
+        assert_eq!(
+            HashAlgorithm::Sha256.hex_digest(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            HashAlgorithm::Sha512.hex_digest(b"abc"),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+        assert_eq!(
+            HashAlgorithm::Keccak256.hex_digest(b"abc"),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+        );
+    }
+
+    #[test]
+    fn test_parse_hash_algorithm() {
+        assert_eq!(HashAlgorithm::default(), HashAlgorithm::Sha256);
+        assert_eq!(HashAlgorithm::parse("SHA-512"), Some(HashAlgorithm::Sha512));
+        assert_eq!(
+            HashAlgorithm::parse("keccak256"),
+            Some(HashAlgorithm::Keccak256)
+        );
+        assert_eq!(HashAlgorithm::parse("md5"), None);
+
+        for algorithm in [
+            HashAlgorithm::Sha256,
+            HashAlgorithm::Sha512,
+            HashAlgorithm::Keccak256,
+        ] {
+            assert_eq!(HashAlgorithm::parse(algorithm.label()), Some(algorithm));
+            assert_eq!(algorithm.label().parse(), Ok(algorithm));
+            assert_eq!(algorithm.digest(b"abc").len(), algorithm.digest_len());
+        }
+        assert!("md5".parse::<HashAlgorithm>().is_err());
+    }
+
+    async fn store(service: &SnapshotService, epoch: u64, json: &str) -> Result<String> {
+        let snapshot = AnalyticsSnapshot::new(epoch, Utc::now());
+        let hash = HashAlgorithm::Sha256.hex_digest(json.as_bytes());
+        service
+            .store_snapshot_in_database(&snapshot, &hash, HashAlgorithm::Sha256, json)
+            .await
+    }
+
+    async fn stored_count(pool: &sqlx::SqlitePool, epoch: i64) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM snapshots WHERE epoch = ?")
+            .bind(epoch)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test]
+    async fn test_store_snapshot_inserts_new_epoch(pool: sqlx::SqlitePool) {
+        let service = SnapshotService::new(Arc::new(Database::new(pool.clone())), None);
+
+        let first = store(&service, 1, r#"{"epoch":1}"#).await.unwrap();
+        let second = store(&service, 2, r#"{"epoch":2}"#).await.unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(stored_count(&pool, 1).await, 1);
+        assert_eq!(stored_count(&pool, 2).await, 1);
+    }
+
+    #[sqlx::test]
+    async fn test_store_snapshot_rerun_is_idempotent(pool: sqlx::SqlitePool) {
+        let service = SnapshotService::new(Arc::new(Database::new(pool.clone())), None);
+
+        let first = store(&service, 4, r#"{"epoch":4}"#).await.unwrap();
+        let rerun = store(&service, 4, r#"{"epoch":4}"#).await.unwrap();
+
+        assert_eq!(rerun, first);
+        assert_eq!(stored_count(&pool, 4).await, 1);
+    }
+
+    #[sqlx::test]
+    async fn test_regenerating_stored_epoch_reuses_snapshot(pool: sqlx::SqlitePool) {
+        insert_anchor(&pool, 1).await;
+        let service = SnapshotService::new(Arc::new(Database::new(pool.clone())), None);
+
+        let first = service.generate_and_submit_snapshot(3).await.unwrap();
+        // Later data and a later timestamp would give a fresh aggregation a
+        // different hash
+        insert_anchor(&pool, 2).await;
+        let retry = service.generate_and_submit_snapshot(3).await.unwrap();
+
+        assert_eq!(retry.snapshot_id, first.snapshot_id);
+        assert_eq!(retry.hash, first.hash);
+        assert_eq!(retry.canonical_json, first.canonical_json);
+        assert_eq!(retry.timestamp, first.timestamp);
+        assert_eq!(retry.anchor_count, first.anchor_count);
+        assert_eq!(stored_count(&pool, 3).await, 1);
+    }
+
+    #[sqlx::test]
+    async fn test_new_snapshot_refuses_stored_epoch(pool: sqlx::SqlitePool) {
+        let service = SnapshotService::new(Arc::new(Database::new(pool.clone())), None);
+
+        // Stored by someone else after any existence check would have passed
+        let stored = store(&service, 6, r#"{"epoch":6}"#).await.unwrap();
+        let err = service.generate_new_snapshot(6).await.unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<SnapshotExists>(),
+            Some(&SnapshotExists { epoch: 6 })
+        );
+        let id: String = sqlx::query_scalar("SELECT id FROM snapshots WHERE epoch = 6")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(id, stored);
+
+        assert!(service.generate_new_snapshot(7).await.is_ok());
+    }
+
+    #[sqlx::test]
+    async fn test_store_snapshot_rejects_conflicting_hash(pool: sqlx::SqlitePool) {
+        let service = SnapshotService::new(Arc::new(Database::new(pool.clone())), None);
+
+        let first = store(&service, 4, r#"{"epoch":4}"#).await.unwrap();
+        let err = store(&service, 4, r#"{"epoch":4,"changed":true}"#)
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("different hash"));
+        let stored: String = sqlx::query_scalar("SELECT id FROM snapshots WHERE epoch = 4")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stored, first);
+    }
+
+    #[sqlx::test]
+    async fn test_identical_hash_across_epochs_is_detected(pool: sqlx::SqlitePool) {
+        let service = SnapshotService::new(Arc::new(Database::new(pool.clone())), None);
+
+        // Both epochs are stored with the same content, so the same hash
+        store(&service, 5, r#"{"epoch":5}"#).await.unwrap();
+        store(&service, 6, r#"{"epoch":6}"#).await.unwrap();
+        store(&service, 7, r#"{"epoch":5}"#).await.unwrap();
+        assert_eq!(stored_count(&pool, 7).await, 1);
+
+        let collisions = service.find_hash_collisions().await.unwrap();
+        assert_eq!(
+            collisions,
+            vec![HashCollision {
+                hash: HashAlgorithm::Sha256.hex_digest(br#"{"epoch":5}"#),
+                epochs: vec![5, 7],
+            }]
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_distinct_hashes_report_no_collision(pool: sqlx::SqlitePool) {
+        let service = SnapshotService::new(Arc::new(Database::new(pool)), None);
+
+        store(&service, 1, r#"{"epoch":1}"#).await.unwrap();
+        store(&service, 2, r#"{"epoch":2}"#).await.unwrap();
+        // Re-storing an epoch is not a collision
+        store(&service, 2, r#"{"epoch":2}"#).await.unwrap();
+
+        assert!(service.find_hash_collisions().await.unwrap().is_empty());
+    }
+
+    /// Store a snapshot with anchor, corridor and pool metrics for `epoch`
+    /// and return its stored hash
+    async fn store_full_snapshot(service: &SnapshotService, epoch: u64) -> String {
+        let mut snapshot = AnalyticsSnapshot::new(epoch, Utc::now());
+        snapshot.add_anchor_metrics(create_test_anchor_metrics(Uuid::new_v4(), "anchor"));
+        snapshot.add_corridor_metrics(create_test_corridor_metrics(Uuid::new_v4(), "USDC:EURC"));
+        snapshot
+            .add_section::<PoolMetricsSection>(&[pool_metrics("pool-1", "1000.1250000")])
+            .unwrap();
+
+        let json = SnapshotService::serialize_deterministically(snapshot.clone()).unwrap();
+        let hash = HashAlgorithm::Sha256.hex_digest(json.as_bytes());
+        service
+            .store_snapshot_in_database(&snapshot, &hash, HashAlgorithm::Sha256, &json)
+            .await
+            .unwrap();
+        hash
+    }
+
+    #[sqlx::test]
+    async fn test_recompute_with_same_config_reproduces_hash(pool: sqlx::SqlitePool) {
+        let service = SnapshotService::new(Arc::new(Database::new(pool)), None);
+        let stored = store_full_snapshot(&service, 7).await;
+
+        let recomputed = service
+            .recompute_from_stored_json(7, SchemaOverride::default())
+            .await
+            .unwrap();
+        assert_eq!(recomputed, stored);
+
+        // Explicitly restating the stored settings changes nothing
+        let same = SchemaOverride::default()
+            .with_schema_version(SCHEMA_VERSION)
+            .with_hash_algorithm(HashAlgorithm::Sha256);
+        assert_eq!(
+            service.recompute_from_stored_json(7, same).await.unwrap(),
+            stored
+        );
+    }
+
+    #[sqlx::test]
+    async fn test_recompute_with_changed_config_diverges(pool: sqlx::SqlitePool) {
+        let service = SnapshotService::new(Arc::new(Database::new(pool)), None);
+        let stored = store_full_snapshot(&service, 7).await;
+
+        let overrides = [
+            SchemaOverride::default().with_float_precision(2),
+            SchemaOverride::default().with_schema_version(SCHEMA_VERSION + 1),
+            SchemaOverride::default().with_hash_algorithm(HashAlgorithm::Keccak256),
+        ];
+        for schema_override in overrides {
+            let recomputed = service
+                .recompute_from_stored_json(7, schema_override)
+                .await
+                .unwrap();
+            assert_ne!(recomputed, stored, "{:?}", schema_override);
+        }
+
+        // Rounding to more places than the data has leaves the hash unchanged
+        let precise = SchemaOverride::default().with_float_precision(6);
+        assert_eq!(
+            service
+                .recompute_from_stored_json(7, precise)
+                .await
+                .unwrap(),
+            stored
+        );
+
+        assert!(service
+            .recompute_from_stored_json(8, SchemaOverride::default())
+            .await
+            .is_err());
+    }
 }
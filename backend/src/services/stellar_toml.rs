@@ -7,6 +7,9 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::RwLock;
 use url::Url;
+use uuid::Uuid;
+
+use crate::database::Database;
 
 /// Cache TTL for successful stellar.toml fetches (24 hours)
 const SUCCESS_CACHE_TTL: u64 = 24 * 60 * 60;
@@ -20,6 +23,9 @@ const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
 /// Maximum response size (1MB)
 const MAX_RESPONSE_SIZE: usize = 1024 * 1024;
 
+/// Age after which stored anchor metadata is fetched again (24 hours)
+const METADATA_MAX_AGE: Duration = Duration::from_secs(SUCCESS_CACHE_TTL);
+
 /// Stellar.toml metadata according to SEP-1
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StellarToml {
@@ -64,6 +70,10 @@ pub struct StellarToml {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network_passphrase: Option<String>,
 
+    /// Accounts controlled by the organization, `ACCOUNTS`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accounts: Option<Vec<String>>,
+
     // Currencies
     #[serde(skip_serializing_if = "Option::is_none")]
     pub currencies: Option<Vec<CurrencyInfo>>,
@@ -164,6 +174,65 @@ pub struct Documentation {
     pub org_description: Option<String>,
 }
 
+impl StellarToml {
+    /// Whether `account` is listed under `ACCOUNTS` or issues one of the
+    /// listed currencies
+    pub fn lists_account(&self, account: &str) -> bool {
+        let account = account.trim();
+        let in_accounts = self
+            .accounts
+            .iter()
+            .flatten()
+            .any(|listed| listed.trim() == account);
+        let is_issuer = self
+            .currencies
+            .iter()
+            .flatten()
+            .any(|currency| currency.issuer.as_deref().map(str::trim) == Some(account));
+        in_accounts || is_issuer
+    }
+
+    /// Display metadata of the anchor publishing this toml. The
+    /// `[DOCUMENTATION]` section wins over the top-level organization fields.
+    pub fn anchor_metadata(&self) -> AnchorMetadata {
+        let documentation = self.documentation.as_ref();
+        let mut supported_currencies: Vec<String> = Vec::new();
+        for currency in self.currencies.iter().flatten() {
+            let code = currency.code.trim();
+            if !code.is_empty() && !supported_currencies.iter().any(|c| c == code) {
+                supported_currencies.push(code.to_string());
+            }
+        }
+
+        AnchorMetadata {
+            organization_name: documentation
+                .and_then(|d| d.org_name.clone())
+                .or_else(|| self.organization_name.clone()),
+            organization_logo: documentation
+                .and_then(|d| d.org_logo.clone())
+                .or_else(|| self.organization_logo.clone()),
+            documentation_url: documentation
+                .and_then(|d| d.org_url.clone())
+                .or_else(|| self.organization_url.clone()),
+            supported_currencies,
+            fetched_at: self.fetched_at,
+        }
+    }
+}
+
+/// Anchor display metadata taken from its stellar.toml; fields the toml
+/// leaves out stay empty
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnchorMetadata {
+    pub organization_name: Option<String>,
+    pub organization_logo: Option<String>,
+    pub documentation_url: Option<String>,
+    /// Codes listed under `CURRENCIES`, in toml order without duplicates
+    pub supported_currencies: Vec<String>,
+    /// Unix seconds of the fetch the metadata came from
+    pub fetched_at: i64,
+}
+
 /// Cached result for stellar.toml fetch
 #[derive(Debug, Clone, Serialize, Deserialize)]
 enum CachedResult {
@@ -176,6 +245,8 @@ pub struct StellarTomlClient {
     http_client: Client,
     redis_connection: Arc<RwLock<Option<MultiplexedConnection>>>,
     network_passphrase: Option<String>,
+    /// Origin every stellar.toml is fetched from instead of the domain's own
+    base_url: Option<String>,
 }
 
 impl StellarTomlClient {
@@ -194,9 +265,17 @@ impl StellarTomlClient {
             http_client,
             redis_connection,
             network_passphrase,
+            base_url: None,
         })
     }
 
+    /// Fetch every domain's stellar.toml from `base_url`, e.g. a proxy or a
+    /// test server, instead of from the domain itself
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into().trim_end_matches('/').to_string());
+        self
+    }
+
     /// Fetch stellar.toml for a domain with caching
     pub async fn fetch_toml(&self, domain: &str) -> Result<StellarToml> {
         // Validate domain
@@ -299,6 +378,12 @@ impl StellarTomlClient {
 
     /// Fetch stellar.toml from network
     async fn fetch_toml_from_network(&self, domain: &str) -> Result<StellarToml> {
+        if let Some(base_url) = &self.base_url {
+            let url = format!("{}/.well-known/stellar.toml", base_url);
+            let content = self.fetch_url(&url).await?;
+            return self.parse_toml(&content, domain);
+        }
+
         // Try HTTPS first
         let https_url = format!("https://{}/.well-known/stellar.toml", domain);
 
@@ -453,6 +538,17 @@ impl StellarTomlClient {
             }
         }
 
+        let accounts = match parsed.get("ACCOUNTS") {
+            Some(toml::Value::Array(accounts)) => Some(
+                accounts
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .collect(),
+            ),
+            _ => None,
+        };
+
         // Parse currencies
         let currencies = self.parse_currencies(&parsed)?;
 
@@ -476,6 +572,7 @@ impl StellarTomlClient {
             organization_official_email,
             organization_support_email,
             network_passphrase,
+            accounts,
             currencies,
             principals,
             documentation,
@@ -682,6 +779,130 @@ impl StellarTomlClient {
     }
 }
 
+/// Outcome of enriching one anchor
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetadataEnrichment {
+    /// The stellar.toml was fetched and its metadata stored
+    Enriched(AnchorMetadata),
+    /// The stored metadata is recent enough; nothing was fetched
+    Cached(AnchorMetadata),
+    /// The anchor has no home domain to read a toml from
+    NoHomeDomain,
+    /// The stellar.toml could not be fetched or parsed; stored metadata is
+    /// left as it was
+    FetchFailed(String),
+}
+
+/// Outcome of one enrichment pass over all anchors
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MetadataEnrichmentStats {
+    pub checked: usize,
+    pub enriched: usize,
+    pub cached: usize,
+    /// Anchors whose stellar.toml could not be fetched
+    pub failed: usize,
+    /// Anchors skipped after a database error
+    pub errors: usize,
+}
+
+/// Fills in anchor display metadata (name, logo, documentation link and
+/// currencies) from each anchor's stellar.toml
+pub struct AnchorMetadataEnricher {
+    db: Arc<Database>,
+    toml_client: Arc<StellarTomlClient>,
+    max_age: Duration,
+}
+
+impl AnchorMetadataEnricher {
+    pub fn new(db: Arc<Database>, toml_client: Arc<StellarTomlClient>) -> Self {
+        Self {
+            db,
+            toml_client,
+            max_age: METADATA_MAX_AGE,
+        }
+    }
+
+    /// Age after which stored metadata is fetched again
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Enrich one anchor unless its stored metadata is still fresh
+    pub async fn enrich_anchor(&self, anchor_id: Uuid) -> Result<MetadataEnrichment> {
+        let anchor = self
+            .db
+            .get_anchor_by_id(anchor_id)
+            .await?
+            .ok_or_else(|| anyhow!("Anchor {} not found", anchor_id))?;
+        let domain = match anchor.home_domain.as_deref().map(str::trim) {
+            Some(domain) if !domain.is_empty() => domain.to_string(),
+            _ => return Ok(MetadataEnrichment::NoHomeDomain),
+        };
+
+        if let Some(stored) = self.db.get_anchor_metadata(anchor_id).await? {
+            let age = chrono::Utc::now().timestamp() - stored.fetched_at;
+            if age >= 0 && (age as u64) < self.max_age.as_secs() {
+                return Ok(MetadataEnrichment::Cached(stored));
+            }
+        }
+
+        let toml = match self.toml_client.fetch_toml(&domain).await {
+            Ok(toml) => toml,
+            Err(e) => {
+                tracing::warn!(
+                    "Could not fetch stellar.toml of {} for anchor {} metadata: {}",
+                    domain,
+                    anchor_id,
+                    e
+                );
+                return Ok(MetadataEnrichment::FetchFailed(e.to_string()));
+            }
+        };
+
+        let metadata = toml.anchor_metadata();
+        self.db.upsert_anchor_metadata(anchor_id, &metadata).await?;
+        tracing::info!("Stored stellar.toml metadata of anchor {}", anchor_id);
+        Ok(MetadataEnrichment::Enriched(metadata))
+    }
+
+    /// Enrich every anchor with a home domain once
+    pub async fn enrich_all(&self) -> Result<MetadataEnrichmentStats> {
+        let mut stats = MetadataEnrichmentStats::default();
+        for anchor in self.db.list_anchors_with_home_domain().await? {
+            let Ok(id) = Uuid::parse_str(&anchor.id) else {
+                tracing::warn!(
+                    "Skipping metadata enrichment of anchor with invalid id {}",
+                    anchor.id
+                );
+                continue;
+            };
+
+            stats.checked += 1;
+            match self.enrich_anchor(id).await {
+                Ok(MetadataEnrichment::Enriched(_)) => stats.enriched += 1,
+                Ok(MetadataEnrichment::Cached(_)) => stats.cached += 1,
+                Ok(MetadataEnrichment::FetchFailed(_)) => stats.failed += 1,
+                Ok(MetadataEnrichment::NoHomeDomain) => {}
+                Err(e) => {
+                    tracing::error!("Failed to enrich metadata of anchor {}: {:#}", id, e);
+                    stats.errors += 1;
+                }
+            }
+        }
+
+        tracing::info!(
+            "Anchor metadata enrichment: {} checked, {} enriched, {} cached, {} failed, {} errors",
+            stats.checked,
+            stats.enriched,
+            stats.cached,
+            stats.failed,
+            stats.errors
+        );
+        Ok(stats)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -774,6 +995,42 @@ name = "Euro"
         assert_eq!(currencies[1].code, "EUR");
     }
 
+    #[test]
+    fn test_anchor_metadata_prefers_documentation() {
+        let client = StellarTomlClient::new(Arc::new(RwLock::new(None)), None).unwrap();
+        let content = r#"
+ORGANIZATION_NAME = "Top Level Name"
+ORGANIZATION_URL = "https://top.example"
+
+[DOCUMENTATION]
+ORG_NAME = "Documented Name"
+
+[[CURRENCIES]]
+code = "USDC"
+
+[[CURRENCIES]]
+code = "USDC"
+
+[[CURRENCIES]]
+code = "EURC"
+"#;
+
+        let metadata = client
+            .parse_toml(content, "example.com")
+            .unwrap()
+            .anchor_metadata();
+        assert_eq!(
+            metadata.organization_name.as_deref(),
+            Some("Documented Name")
+        );
+        assert_eq!(
+            metadata.documentation_url.as_deref(),
+            Some("https://top.example")
+        );
+        assert_eq!(metadata.organization_logo, None);
+        assert_eq!(metadata.supported_currencies, ["USDC", "EURC"]);
+    }
+
     #[test]
     fn test_parse_invalid_toml() {
         let client = StellarTomlClient::new(Arc::new(RwLock::new(None)), None).unwrap();
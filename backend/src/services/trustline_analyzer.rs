@@ -1,10 +1,94 @@
 use anyhow::Result;
+use serde::Serialize;
 use sqlx::{Pool, Sqlite};
 use std::sync::Arc;
-use tracing::info;
+use tracing::{info, warn};
 
+use crate::models::asset::validate_asset;
 use crate::models::{TrustlineMetrics, TrustlineSnapshot, TrustlineStat};
-use crate::rpc::StellarRpcClient;
+use crate::rpc::{HorizonAccount, StellarRpcClient};
+
+/// Fewer holders than this and concentration is not meaningful
+pub const MIN_CONCENTRATION_HOLDERS: usize = 20;
+
+/// Holder concentration of an asset. The metrics are `None` when they could
+/// not be computed, with `note` saying why.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TrustlineConcentration {
+    pub top10_holder_share: Option<f64>,
+    pub gini_coefficient: Option<f64>,
+    pub holders: usize,
+    pub note: Option<String>,
+    /// Only the first `holders` holders were fetched; more exist
+    pub sampled: bool,
+}
+
+impl TrustlineConcentration {
+    fn unavailable(holders: usize, note: String) -> Self {
+        Self {
+            top10_holder_share: None,
+            gini_coefficient: None,
+            holders,
+            note: Some(note),
+            sampled: false,
+        }
+    }
+}
+
+/// Top-10 holder share and Gini coefficient of a set of holder balances.
+/// Zero and negative balances are ignored.
+pub fn compute_concentration(balances: &[f64]) -> TrustlineConcentration {
+    let mut sorted: Vec<f64> = balances.iter().copied().filter(|b| *b > 0.0).collect();
+    let holders = sorted.len();
+
+    if holders < MIN_CONCENTRATION_HOLDERS {
+        return TrustlineConcentration::unavailable(
+            holders,
+            format!(
+                "too few holders ({} < {})",
+                holders, MIN_CONCENTRATION_HOLDERS
+            ),
+        );
+    }
+
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let total: f64 = sorted.iter().sum();
+    let top10: f64 = sorted.iter().rev().take(10).sum();
+
+    // Gini over ascending balances: (2 * sum(i * x_i)) / (n * sum(x)) - (n + 1) / n
+    let n = holders as f64;
+    let weighted: f64 = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, x)| (i as f64 + 1.0) * x)
+        .sum();
+    let gini = 2.0 * weighted / (n * total) - (n + 1.0) / n;
+
+    TrustlineConcentration {
+        top10_holder_share: Some(top10 / total),
+        gini_coefficient: Some(gini.clamp(0.0, 1.0)),
+        holders,
+        note: None,
+        sampled: false,
+    }
+}
+
+/// Balances of `asset_code:asset_issuer` held by the given accounts
+pub fn holder_balances(
+    accounts: &[HorizonAccount],
+    asset_code: &str,
+    asset_issuer: &str,
+) -> Vec<f64> {
+    accounts
+        .iter()
+        .flat_map(|account| account.balances.iter())
+        .filter(|b| {
+            b.asset_code.as_deref() == Some(asset_code)
+                && b.asset_issuer.as_deref() == Some(asset_issuer)
+        })
+        .filter_map(|b| b.balance.parse::<f64>().ok())
+        .collect()
+}
 
 pub struct TrustlineAnalyzer {
     pool: Pool<Sqlite>,
@@ -34,6 +118,13 @@ impl TrustlineAnalyzer {
             if asset.asset_type == "native" {
                 continue;
             }
+            let asset_id = match validate_asset(&asset.asset_code, Some(&asset.asset_issuer)) {
+                Ok(asset_id) => asset_id,
+                Err(e) => {
+                    warn!("Skipping asset from Horizon: {}", e);
+                    continue;
+                }
+            };
 
             let total_trustlines = asset.accounts.authorized
                 + asset.accounts.unauthorized
@@ -54,8 +145,8 @@ impl TrustlineAnalyzer {
                     updated_at = CURRENT_TIMESTAMP
                 "#,
             )
-            .bind(&asset.asset_code)
-            .bind(&asset.asset_issuer)
+            .bind(&asset_id.code)
+            .bind(&asset_id.issuer)
             .bind(total_trustlines)
             .bind(asset.accounts.authorized)
             .bind(asset.accounts.unauthorized)
@@ -72,6 +163,66 @@ impl TrustlineAnalyzer {
         Ok(synced_count)
     }
 
+    /// Fetch holder balances of every tracked asset, up to the RPC client's
+    /// record limit, and store its concentration. Assets with more holders
+    /// than the limit are marked as sampled.
+    pub async fn sync_concentration(&self) -> Result<u64> {
+        let assets: Vec<(String, String)> =
+            sqlx::query_as("SELECT asset_code, asset_issuer FROM trustline_stats")
+                .fetch_all(&self.pool)
+                .await?;
+
+        let mut updated = 0;
+        for (asset_code, asset_issuer) in assets {
+            let concentration = match self
+                .rpc_client
+                .fetch_all_asset_holders(&asset_code, &asset_issuer, None)
+                .await
+            {
+                Ok(holders) => {
+                    let balances = holder_balances(&holders.items, &asset_code, &asset_issuer);
+                    TrustlineConcentration {
+                        sampled: holders.capped,
+                        ..compute_concentration(&balances)
+                    }
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to fetch holders of {}:{}: {}",
+                        asset_code, asset_issuer, e
+                    );
+                    TrustlineConcentration::unavailable(0, "holder data unavailable".to_string())
+                }
+            };
+
+            sqlx::query(
+                r#"
+                UPDATE trustline_stats SET
+                    top10_holder_share = ?1,
+                    gini_coefficient = ?2,
+                    concentration_holders = ?3,
+                    concentration_note = ?4,
+                    concentration_sampled = ?5
+                WHERE asset_code = ?6 AND asset_issuer = ?7
+                "#,
+            )
+            .bind(concentration.top10_holder_share)
+            .bind(concentration.gini_coefficient)
+            .bind(concentration.holders as i64)
+            .bind(&concentration.note)
+            .bind(concentration.sampled)
+            .bind(&asset_code)
+            .bind(&asset_issuer)
+            .execute(&self.pool)
+            .await?;
+
+            updated += 1;
+        }
+
+        info!("Updated holder concentration for {} assets", updated);
+        Ok(updated)
+    }
+
     /// Take a daily snapshot of all assets for historical charting
     pub async fn take_snapshots(&self) -> Result<u64> {
         info!("Taking trustline snapshots...");
@@ -80,10 +231,14 @@ impl TrustlineAnalyzer {
         let result = sqlx::query(
             r#"
             INSERT INTO trustline_snapshots (
-                asset_code, asset_issuer, total_trustlines, authorized_trustlines, unauthorized_trustlines, total_supply, snapshot_at
+                asset_code, asset_issuer, total_trustlines, authorized_trustlines, unauthorized_trustlines, total_supply,
+                top10_holder_share, gini_coefficient, concentration_holders, concentration_note,
+                concentration_sampled, snapshot_at
             )
             SELECT 
-                asset_code, asset_issuer, total_trustlines, authorized_trustlines, unauthorized_trustlines, total_supply, CURRENT_TIMESTAMP
+                asset_code, asset_issuer, total_trustlines, authorized_trustlines, unauthorized_trustlines, total_supply,
+                top10_holder_share, gini_coefficient, concentration_holders, concentration_note,
+                concentration_sampled, CURRENT_TIMESTAMP
             FROM 
                 trustline_stats
             "#
@@ -164,3 +319,39 @@ impl TrustlineAnalyzer {
         Ok(history)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_even_distribution_is_dispersed() {
+        let concentration = compute_concentration(&[100.0; 50]);
+        assert_eq!(concentration.holders, 50);
+        assert!((concentration.top10_holder_share.unwrap() - 0.2).abs() < 1e-9);
+        assert!(concentration.gini_coefficient.unwrap() < 1e-9);
+        assert_eq!(concentration.note, None);
+    }
+
+    #[test]
+    fn test_whale_distribution_is_concentrated() {
+        let mut balances = vec![1.0; 49];
+        balances.push(1_000_000.0);
+
+        let concentration = compute_concentration(&balances);
+        assert!(concentration.top10_holder_share.unwrap() > 0.99);
+        assert!(concentration.gini_coefficient.unwrap() > 0.95);
+    }
+
+    #[test]
+    fn test_too_few_holders_returns_none_with_reason() {
+        let concentration = compute_concentration(&[10.0, 20.0, 0.0, 30.0]);
+        assert_eq!(concentration.holders, 3);
+        assert_eq!(concentration.top10_holder_share, None);
+        assert_eq!(concentration.gini_coefficient, None);
+        assert_eq!(
+            concentration.note.as_deref(),
+            Some("too few holders (3 < 20)")
+        );
+    }
+}
@@ -2,13 +2,20 @@
 //!
 //! This service handles the reward mechanism for users who verify snapshot hashes.
 //! Users earn points for successfully verifying that snapshot hashes match backend data.
+//! Several epochs can be claimed at once with a batch claim; each entry is
+//! checked and rewarded on its own.
 
 use crate::database::Database;
 use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use chrono::Utc;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
-use sqlx::Row;
+use sqlx::{Row, SqliteConnection};
+use std::collections::HashSet;
+use std::str::FromStr;
 use std::sync::Arc;
+use stellar_xdr::curr::PublicKey;
 use tracing::{debug, info};
 use uuid::Uuid;
 
@@ -24,21 +31,120 @@ const FAILED_VERIFICATION_PENALTY: i32 = 0;
 /// Maximum verifications per user per day to prevent abuse
 const MAX_VERIFICATIONS_PER_DAY: i32 = 50;
 
+/// Maximum entries in one batch claim
+pub const MAX_BATCH_CLAIM_ENTRIES: usize = MAX_VERIFICATIONS_PER_DAY as usize;
+
 /// Request to verify a snapshot hash
 #[derive(Debug, Deserialize)]
 pub struct VerifySnapshotRequest {
     pub snapshot_id: String,
     pub submitted_hash: String,
+    /// Account the reward is claimed for; must match the authenticated signer
+    #[serde(default)]
+    pub account: Option<String>,
+}
+
+/// Reason a verification attempt was not rewarded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationRejection {
+    /// Submitted hash does not match the stored snapshot hash
+    HashMismatch,
+    /// No snapshot exists for the requested id/epoch
+    EpochNotFound,
+    /// The user already earned a reward for this snapshot
+    AlreadyRewarded,
+    /// The claim is not signed by the authenticated account
+    SignatureInvalid,
+    /// The epoch appears earlier in the same batch claim
+    DuplicateEntry,
+    /// The daily verification limit was reached partway through a batch
+    DailyLimitReached,
+}
+
+impl VerificationRejection {
+    /// Human-readable explanation suitable for display
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::HashMismatch => "Verification failed. Hash does not match.",
+            Self::EpochNotFound => "Snapshot not found. Check the snapshot id and try again.",
+            Self::AlreadyRewarded => "You have already been rewarded for verifying this snapshot.",
+            Self::SignatureInvalid => "The claim is not signed by the authenticated account.",
+            Self::DuplicateEntry => "This epoch appears more than once in the batch.",
+            Self::DailyLimitReached => "Daily verification limit reached.",
+        }
+    }
 }
 
 /// Response after verification attempt
 #[derive(Debug, Serialize)]
 pub struct VerificationResponse {
-    pub verification_id: String,
+    /// Id of the recorded attempt; absent when the attempt was rejected before recording
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification_id: Option<String>,
     pub is_match: bool,
     pub reward_points: i32,
     pub total_points: i32,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<VerificationRejection>,
+}
+
+/// One `{epoch, hash, signature}` entry of a batch claim
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchClaimEntry {
+    pub epoch: i64,
+    pub hash: String,
+    /// Base64 ed25519 signature of [`claim_message`] by the claiming account
+    pub signature: String,
+}
+
+/// Claim rewards for several epochs at once
+#[derive(Debug, Deserialize)]
+pub struct BatchClaimRequest {
+    pub entries: Vec<BatchClaimEntry>,
+}
+
+/// Outcome of one batch claim entry
+#[derive(Debug, Serialize)]
+pub struct BatchClaimEntryResult {
+    pub epoch: i64,
+    #[serde(flatten)]
+    pub outcome: VerificationResponse,
+}
+
+/// Per-entry results of a batch claim, in request order
+#[derive(Debug, Serialize)]
+pub struct BatchClaimResponse {
+    pub results: Vec<BatchClaimEntryResult>,
+    /// Points earned across the batch
+    pub reward_points: i32,
+    pub total_points: i32,
+}
+
+/// Bytes a batch claim entry for `epoch` and `hash` is signed over
+pub fn claim_message(epoch: i64, hash: &str) -> Vec<u8> {
+    format!("stellar-insights:verification-claim:{}:{}", epoch, hash).into_bytes()
+}
+
+/// Whether `signature` is `account`'s ed25519 signature of the entry's
+/// [`claim_message`]; `account` is a `G...` address
+fn is_signed_by(account: &str, entry: &BatchClaimEntry) -> bool {
+    let Ok(PublicKey::PublicKeyTypeEd25519(key)) = PublicKey::from_str(account) else {
+        return false;
+    };
+    let Ok(public_key) = VerifyingKey::from_bytes(&key.0) else {
+        return false;
+    };
+    BASE64
+        .decode(entry.signature.trim())
+        .ok()
+        .and_then(|sig| Signature::from_slice(&sig).ok())
+        .is_some_and(|sig| {
+            public_key
+                .verify(&claim_message(entry.epoch, &entry.hash), &sig)
+                .is_ok()
+        })
 }
 
 /// User reward statistics
@@ -74,7 +180,10 @@ impl VerificationRewardsService {
         Self { db }
     }
 
-    /// Verify a snapshot hash and award points if successful
+    /// Verify a snapshot hash and award points if successful.
+    ///
+    /// `user_id` must be the account proven by a SEP-10 session; a claim for
+    /// any other account in `request.account` is rejected.
     pub async fn verify_and_reward(
         &self,
         user_id: &str,
@@ -88,11 +197,118 @@ impl VerificationRewardsService {
         // Check daily verification limit
         self.check_daily_limit(user_id).await?;
 
+        if let Some(ref account) = request.account {
+            if account != user_id {
+                return self
+                    .rejected(user_id, VerificationRejection::SignatureInvalid)
+                    .await;
+            }
+        }
+
         // Fetch the snapshot from database
-        let snapshot = self.fetch_snapshot(&request.snapshot_id).await?;
+        let Some(snapshot) = self.fetch_snapshot(&request.snapshot_id).await? else {
+            return self
+                .rejected(user_id, VerificationRejection::EpochNotFound)
+                .await;
+        };
+
+        self.verify_against(user_id, &snapshot, &request.submitted_hash)
+            .await
+    }
+
+    /// Claim rewards for several epochs for the SEP-10 account `user_id`.
+    ///
+    /// Each entry is checked on its own and, when valid, recorded and
+    /// rewarded in its own transaction, so one bad entry does not undo the
+    /// others. An epoch is rewarded at most once: entries for an epoch that
+    /// was already rewarded, or that repeat an earlier entry, are rejected.
+    pub async fn claim_batch(
+        &self,
+        user_id: &str,
+        request: BatchClaimRequest,
+    ) -> Result<BatchClaimResponse> {
+        if request.entries.is_empty() {
+            return Err(anyhow!("Batch claim has no entries"));
+        }
+        if request.entries.len() > MAX_BATCH_CLAIM_ENTRIES {
+            return Err(anyhow!(
+                "Batch claim has {} entries; at most {} are allowed",
+                request.entries.len(),
+                MAX_BATCH_CLAIM_ENTRIES
+            ));
+        }
+
+        info!(
+            "Processing batch claim of {} entries from user {}",
+            request.entries.len(),
+            user_id
+        );
+        self.check_daily_limit(user_id).await?;
+
+        let mut seen = HashSet::new();
+        let mut results = Vec::with_capacity(request.entries.len());
+        for entry in request.entries {
+            let outcome = self.claim_entry(user_id, &entry, &mut seen).await?;
+            results.push(BatchClaimEntryResult {
+                epoch: entry.epoch,
+                outcome,
+            });
+        }
+
+        Ok(BatchClaimResponse {
+            reward_points: results.iter().map(|r| r.outcome.reward_points).sum(),
+            total_points: self.current_total_points(user_id).await?,
+            results,
+        })
+    }
+
+    async fn claim_entry(
+        &self,
+        user_id: &str,
+        entry: &BatchClaimEntry,
+        seen: &mut HashSet<i64>,
+    ) -> Result<VerificationResponse> {
+        if !is_signed_by(user_id, entry) {
+            return self
+                .rejected(user_id, VerificationRejection::SignatureInvalid)
+                .await;
+        }
+        if !seen.insert(entry.epoch) {
+            return self
+                .rejected(user_id, VerificationRejection::DuplicateEntry)
+                .await;
+        }
+        if self.check_daily_limit(user_id).await.is_err() {
+            return self
+                .rejected(user_id, VerificationRejection::DailyLimitReached)
+                .await;
+        }
+
+        let Some(snapshot) = self.fetch_snapshot_by_epoch(entry.epoch).await? else {
+            return self
+                .rejected(user_id, VerificationRejection::EpochNotFound)
+                .await;
+        };
+
+        self.verify_against(user_id, &snapshot, &entry.hash).await
+    }
+
+    /// Compare `submitted_hash` with `snapshot` and record the attempt and
+    /// its reward in one transaction
+    async fn verify_against(
+        &self,
+        user_id: &str,
+        snapshot: &SnapshotRecord,
+        submitted_hash: &str,
+    ) -> Result<VerificationResponse> {
+        if self.has_been_rewarded(user_id, &snapshot.id).await? {
+            return self
+                .rejected(user_id, VerificationRejection::AlreadyRewarded)
+                .await;
+        }
 
         // Compare hashes
-        let is_match = snapshot.hash == request.submitted_hash;
+        let is_match = snapshot.hash == submitted_hash;
 
         // Calculate reward points
         let reward_points = if is_match {
@@ -114,12 +330,19 @@ impl VerificationRewardsService {
 
         // Record verification attempt
         let verification_id = Uuid::new_v4().to_string();
-        self.record_verification(
+        let mut tx = self
+            .db
+            .pool()
+            .begin()
+            .await
+            .context("Failed to start verification transaction")?;
+        Self::record_verification(
+            &mut tx,
             &verification_id,
             user_id,
-            &request.snapshot_id,
+            &snapshot.id,
             snapshot.epoch,
-            &request.submitted_hash,
+            submitted_hash,
             &snapshot.hash,
             is_match,
             reward_points,
@@ -127,17 +350,17 @@ impl VerificationRewardsService {
         .await?;
 
         // Update user rewards
-        let total_points = self
-            .update_user_rewards(user_id, is_match, reward_points)
-            .await?;
+        let total_points =
+            Self::update_user_rewards(&mut tx, user_id, is_match, reward_points).await?;
+        tx.commit().await.context("Failed to commit verification")?;
 
-        let message = if is_match {
-            format!(
+        let rejection = (!is_match).then_some(VerificationRejection::HashMismatch);
+        let message = match rejection {
+            None => format!(
                 "Verification successful! You earned {} points.",
                 reward_points
-            )
-        } else {
-            "Verification failed. Hash does not match.".to_string()
+            ),
+            Some(reason) => reason.message().to_string(),
         };
 
         info!(
@@ -146,11 +369,33 @@ impl VerificationRewardsService {
         );
 
         Ok(VerificationResponse {
-            verification_id,
+            verification_id: Some(verification_id),
             is_match,
             reward_points,
             total_points,
             message,
+            rejection,
+        })
+    }
+
+    /// Build a response for an attempt rejected before it was recorded
+    async fn rejected(
+        &self,
+        user_id: &str,
+        rejection: VerificationRejection,
+    ) -> Result<VerificationResponse> {
+        info!(
+            "Verification rejected: user={}, reason={:?}",
+            user_id, rejection
+        );
+
+        Ok(VerificationResponse {
+            verification_id: None,
+            is_match: false,
+            reward_points: 0,
+            total_points: self.current_total_points(user_id).await?,
+            message: rejection.message().to_string(),
+            rejection: Some(rejection),
         })
     }
 
@@ -301,7 +546,7 @@ impl VerificationRewardsService {
         Ok(())
     }
 
-    async fn fetch_snapshot(&self, snapshot_id: &str) -> Result<SnapshotRecord> {
+    async fn fetch_snapshot(&self, snapshot_id: &str) -> Result<Option<SnapshotRecord>> {
         let row = sqlx::query(
             r#"
             SELECT id, hash, epoch, created_at
@@ -310,16 +555,76 @@ impl VerificationRewardsService {
             "#,
         )
         .bind(snapshot_id)
-        .fetch_one(self.db.pool())
+        .fetch_optional(self.db.pool())
         .await
-        .context("Snapshot not found")?;
+        .context("Failed to fetch snapshot")?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
 
-        Ok(SnapshotRecord {
+        Ok(Some(SnapshotRecord {
             id: row.try_get("id")?,
             hash: row.try_get("hash")?,
             epoch: row.try_get("epoch")?,
             created_at: row.try_get("created_at")?,
-        })
+        }))
+    }
+
+    /// Latest snapshot stored for `epoch`
+    async fn fetch_snapshot_by_epoch(&self, epoch: i64) -> Result<Option<SnapshotRecord>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, hash, epoch, created_at
+            FROM snapshots
+            WHERE epoch = ?
+            ORDER BY created_at DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(epoch)
+        .fetch_optional(self.db.pool())
+        .await
+        .context("Failed to fetch snapshot")?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        Ok(Some(SnapshotRecord {
+            id: row.try_get("id")?,
+            hash: row.try_get("hash")?,
+            epoch: row.try_get("epoch")?,
+            created_at: row.try_get("created_at")?,
+        }))
+    }
+
+    async fn has_been_rewarded(&self, user_id: &str, snapshot_id: &str) -> Result<bool> {
+        let count: i32 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM snapshot_verifications
+            WHERE user_id = ? AND snapshot_id = ? AND is_match = 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(snapshot_id)
+        .fetch_one(self.db.pool())
+        .await
+        .context("Failed to check previous rewards")?;
+
+        Ok(count > 0)
+    }
+
+    async fn current_total_points(&self, user_id: &str) -> Result<i32> {
+        let total: Option<i32> =
+            sqlx::query_scalar("SELECT total_points FROM user_rewards WHERE user_id = ?")
+                .bind(user_id)
+                .fetch_optional(self.db.pool())
+                .await
+                .context("Failed to fetch total points")?;
+
+        Ok(total.unwrap_or(0))
     }
 
     async fn is_early_verification(&self, snapshot_created_at: &str) -> Result<bool> {
@@ -338,7 +643,7 @@ impl VerificationRewardsService {
     }
 
     async fn record_verification(
-        &self,
+        conn: &mut SqliteConnection,
         verification_id: &str,
         user_id: &str,
         snapshot_id: &str,
@@ -364,7 +669,7 @@ impl VerificationRewardsService {
         .bind(is_match)
         .bind(reward_points)
         .bind(Utc::now().to_rfc3339())
-        .execute(self.db.pool())
+        .execute(&mut *conn)
         .await
         .context("Failed to record verification")?;
 
@@ -372,7 +677,7 @@ impl VerificationRewardsService {
     }
 
     async fn update_user_rewards(
-        &self,
+        conn: &mut SqliteConnection,
         user_id: &str,
         is_match: bool,
         reward_points: i32,
@@ -401,7 +706,7 @@ impl VerificationRewardsService {
         .bind(if is_match { 0 } else { 1 })
         .bind(Utc::now().to_rfc3339())
         .bind(Utc::now().to_rfc3339())
-        .execute(self.db.pool())
+        .execute(&mut *conn)
         .await
         .context("Failed to update user rewards")?;
 
@@ -414,7 +719,7 @@ impl VerificationRewardsService {
             "#,
         )
         .bind(user_id)
-        .fetch_one(self.db.pool())
+        .fetch_one(&mut *conn)
         .await
         .context("Failed to fetch updated total")?;
 
@@ -3,15 +3,62 @@
 use anyhow::Result;
 use reqwest::Client;
 use sqlx::SqlitePool;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::{broadcast, Semaphore};
 use uuid::Uuid;
 
-use crate::webhooks::{WebhookEventEnvelope, WebhookService, WebhookSignature};
+use crate::services::event_bus::{DomainEvent, SampledReceiver};
+use crate::services::idempotency::IdempotencyStore;
+use crate::webhooks::{WebhookEventEnvelope, WebhookLimits, WebhookService, WebhookSignature};
+
+/// Delivery attempts, the first plus three retries, before an event is
+/// dead-lettered as `failed`
+const MAX_DELIVERY_ATTEMPTS: i32 = 4;
+
+/// Default number of webhook deliveries in flight at once
+pub const DEFAULT_MAX_CONCURRENT_DELIVERIES: usize = 8;
+
+/// Default number of pending events fetched per dispatch run
+pub const DEFAULT_DISPATCH_BATCH_SIZE: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebhookDispatcherConfig {
+    /// Deliveries in flight at once, across all webhooks
+    pub max_concurrent_deliveries: usize,
+    /// Pending events fetched per run
+    pub batch_size: usize,
+}
+
+impl Default for WebhookDispatcherConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrent_deliveries: DEFAULT_MAX_CONCURRENT_DELIVERIES,
+            batch_size: DEFAULT_DISPATCH_BATCH_SIZE,
+        }
+    }
+}
+
+/// Outcome of one delivery attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeliveryOutcome {
+    /// Delivered or dead-lettered; the webhook's next event may go out
+    Done,
+    /// Will be retried; the webhook's later events must wait
+    Retry,
+}
 
 /// Webhook dispatcher - sends events to webhooks asynchronously
 pub struct WebhookDispatcher {
     db: SqlitePool,
     http_client: Client,
+    config: WebhookDispatcherConfig,
+    /// Payload limits applied to queued events
+    limits: WebhookLimits,
+    /// Delivery slots shared by every webhook
+    delivery_slots: Arc<Semaphore>,
+    /// Records deliveries so an event is not posted again after it went out
+    idempotency: IdempotencyStore,
 }
 
 impl WebhookDispatcher {
@@ -22,7 +69,28 @@ impl WebhookDispatcher {
             .build()
             .unwrap_or_else(|_| Client::new());
 
-        Self { db, http_client }
+        let config = WebhookDispatcherConfig::default();
+        Self {
+            idempotency: IdempotencyStore::new(db.clone()),
+            db,
+            http_client,
+            config,
+            limits: WebhookLimits::default(),
+            delivery_slots: Arc::new(Semaphore::new(config.max_concurrent_deliveries.max(1))),
+        }
+    }
+
+    /// Use `config` instead of the default limits
+    pub fn with_dispatch_config(mut self, config: WebhookDispatcherConfig) -> Self {
+        self.config = config;
+        self.delivery_slots = Arc::new(Semaphore::new(config.max_concurrent_deliveries.max(1)));
+        self
+    }
+
+    /// Queue events under `limits` instead of the default payload limits
+    pub fn with_webhook_limits(mut self, limits: WebhookLimits) -> Self {
+        self.limits = limits;
+        self
     }
 
     /// Run dispatcher loop - processes pending webhook events
@@ -40,91 +108,178 @@ impl WebhookDispatcher {
         }
     }
 
-    /// Process all pending webhook events
+    /// Consume domain events from the event bus, queueing a webhook event
+    /// for every active webhook subscribed to the event type
+    pub async fn run_event_consumer(&self, mut rx: SampledReceiver) {
+        loop {
+            match rx.recv().await {
+                Ok(event) => {
+                    if let Err(e) = self.enqueue_event(&event).await {
+                        tracing::error!("Failed to enqueue webhook events: {}", e);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Webhook event consumer lagged, skipped {} events", skipped);
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// Queue a domain event for delivery; returns the number of webhooks targeted
+    pub async fn enqueue_event(&self, event: &DomainEvent) -> Result<usize> {
+        let service = WebhookService::new(self.db.clone()).with_limits(self.limits);
+        let event_type = event.webhook_event_type().as_str();
+        let payload = event.payload();
+
+        let webhooks = service.list_active_webhooks_for_event(event_type).await?;
+        for webhook in &webhooks {
+            service
+                .create_webhook_event(&webhook.id, event_type, payload.clone())
+                .await?;
+        }
+
+        Ok(webhooks.len())
+    }
+
+    /// Process pending webhook events.
+    ///
+    /// Webhooks are delivered to concurrently, at most
+    /// `max_concurrent_deliveries` at a time. Events of one webhook are
+    /// delivered in the order they were created: a failed event blocks that
+    /// webhook's later events until it is delivered or dead-lettered, while
+    /// other webhooks carry on.
     async fn process_pending_events(&self) -> Result<()> {
         let service = WebhookService::new(self.db.clone());
 
-        // Fetch pending events (max 10 per run)
-        let events = service.get_pending_events(10).await?;
-
+        // Pending events oldest first, queued per webhook in that order
+        let events = service.get_pending_events(self.config.batch_size).await?;
+        let mut queues: Vec<(String, Vec<(String, String, String)>)> = Vec::new();
         for (event_id, webhook_id, event_type, payload_str) in events {
-            // Get webhook details
-            let webhook = match service.get_webhook(&webhook_id).await? {
-                Some(w) => w,
-                None => {
-                    // Webhook was deleted, mark event as failed
-                    let _ = service
-                        .update_event_status(&event_id, "failed", Some("webhook_deleted"), 0)
-                        .await;
-                    continue;
+            let event = (event_id, event_type, payload_str);
+            match queues.iter_mut().find(|(id, _)| *id == webhook_id) {
+                Some((_, queue)) => queue.push(event),
+                None => queues.push((webhook_id, vec![event])),
+            }
+        }
+
+        let deliveries = queues.into_iter().map(|(webhook_id, queue)| {
+            let service = &service;
+            async move {
+                for (event_id, event_type, payload_str) in queue {
+                    let outcome = self
+                        .process_event(service, &webhook_id, &event_id, &event_type, &payload_str)
+                        .await?;
+                    if outcome == DeliveryOutcome::Retry {
+                        break;
+                    }
                 }
-            };
+                Ok::<_, anyhow::Error>(())
+            }
+        });
+        for result in futures::future::join_all(deliveries).await {
+            result?;
+        }
+
+        Ok(())
+    }
 
-            if !webhook.is_active {
+    /// Deliver one event once a delivery slot is free and record the outcome
+    async fn process_event(
+        &self,
+        service: &WebhookService,
+        webhook_id: &str,
+        event_id: &str,
+        event_type: &str,
+        payload_str: &str,
+    ) -> Result<DeliveryOutcome> {
+        // Get webhook details
+        let webhook = match service.get_webhook(webhook_id).await? {
+            Some(w) => w,
+            None => {
+                // Webhook was deleted, mark event as failed
                 let _ = service
-                    .update_event_status(&event_id, "failed", Some("webhook_inactive"), 0)
+                    .update_event_status(event_id, "failed", Some("webhook_deleted"), 0)
                     .await;
-                continue;
+                return Ok(DeliveryOutcome::Done);
             }
+        };
 
-            // Attempt delivery
-            match self
-                .deliver_webhook(&webhook.url, &payload_str, &webhook.secret, &event_type)
-                .await
-            {
-                Ok(_) => {
-                    // Success
+        if !webhook.is_active {
+            let _ = service
+                .update_event_status(event_id, "failed", Some("webhook_inactive"), 0)
+                .await;
+            return Ok(DeliveryOutcome::Done);
+        }
+
+        // Attempt delivery; an event already delivered whose status update
+        // was lost is not posted again
+        let _slot = self.delivery_slots.acquire().await?;
+        match self
+            .idempotency
+            .submit_idempotent(&format!("webhook_event:{}", event_id), || {
+                self.deliver_webhook(&webhook.url, payload_str, &webhook.secret, event_type)
+            })
+            .await
+        {
+            Ok(_) => {
+                // Success
+                let _ = service
+                    .update_event_status(event_id, "delivered", None, 0)
+                    .await;
+
+                // Update webhook's last_fired_at
+                let _ = service.update_last_fired(webhook_id).await;
+
+                tracing::info!(
+                    "Webhook delivered successfully: webhook_id={}, event={}",
+                    webhook_id,
+                    event_type
+                );
+                Ok(DeliveryOutcome::Done)
+            }
+            Err(e) => {
+                // Determine retry count from event
+                let current_retries = self.get_event_retries(event_id).await.unwrap_or(0);
+
+                if current_retries + 1 < MAX_DELIVERY_ATTEMPTS {
+                    // Retry later; hold back this webhook's later events
                     let _ = service
-                        .update_event_status(&event_id, "delivered", None, 0)
+                        .update_event_status(
+                            event_id,
+                            "pending",
+                            Some(&e.to_string()),
+                            current_retries + 1,
+                        )
                         .await;
 
-                    // Update webhook's last_fired_at
-                    let _ = service.update_last_fired(&webhook_id).await;
+                    tracing::warn!(
+                        "Webhook delivery failed (will retry): webhook_id={}, error={}, retries={}",
+                        webhook_id,
+                        e,
+                        current_retries + 1
+                    );
+                    Ok(DeliveryOutcome::Retry)
+                } else {
+                    // Max retries exceeded
+                    let _ = service
+                        .update_event_status(
+                            event_id,
+                            "failed",
+                            Some(&e.to_string()),
+                            current_retries,
+                        )
+                        .await;
 
-                    tracing::info!(
-                        "Webhook delivered successfully: webhook_id={}, event={}",
+                    tracing::error!(
+                        "Webhook delivery failed (max retries): webhook_id={}, error={}",
                         webhook_id,
-                        event_type
+                        e
                     );
-                }
-                Err(e) => {
-                    // Determine retry count from event
-                    let current_retries = self.get_event_retries(&event_id).await.unwrap_or(0);
-
-                    if current_retries < 3 {
-                        // Retry later
-                        let _ = service
-                            .update_event_status(
-                                &event_id,
-                                "pending",
-                                Some(&e.to_string()),
-                                current_retries + 1,
-                            )
-                            .await;
-
-                        tracing::warn!(
-                            "Webhook delivery failed (will retry): webhook_id={}, error={}, retries={}",
-                            webhook_id,
-                            e,
-                            current_retries + 1
-                        );
-                    } else {
-                        // Max retries exceeded
-                        let _ = service
-                            .update_event_status(&event_id, "failed", Some(&e.to_string()), 3)
-                            .await;
-
-                        tracing::error!(
-                            "Webhook delivery failed (max retries): webhook_id={}, error={}",
-                            webhook_id,
-                            e
-                        );
-                    }
+                    Ok(DeliveryOutcome::Done)
                 }
             }
         }
-
-        Ok(())
     }
 
     /// Deliver webhook to URL
@@ -194,10 +349,240 @@ impl WebhookDispatcher {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::webhooks::CreateWebhookRequest;
+    use axum::{extract::Path, http::StatusCode, routing::post, Json, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
 
     #[test]
     fn test_webhook_dispatcher_creation() {
         // This is a smoke test for basic creation
         // Full tests would require mocking the database and HTTP client
     }
+
+    /// Deliveries received by the mock endpoint: (path, seq, accepted)
+    type Deliveries = Arc<Mutex<Vec<(String, i64, bool)>>>;
+
+    /// Mock webhook endpoint that rejects the first delivery to `/a`
+    async fn mock_endpoint() -> (String, Deliveries) {
+        let deliveries: Deliveries = Arc::new(Mutex::new(Vec::new()));
+        let recorded = deliveries.clone();
+        let app = Router::new().route(
+            "/:name",
+            post(
+                move |Path(name): Path<String>, Json(envelope): Json<serde_json::Value>| {
+                    let mut recorded = recorded.lock().unwrap();
+                    let accepted = name != "a" || recorded.iter().any(|(p, _, _)| p == "a");
+                    let seq = envelope["data"]["seq"].as_i64().unwrap();
+                    recorded.push((name, seq, accepted));
+                    async move {
+                        if accepted {
+                            StatusCode::OK
+                        } else {
+                            StatusCode::SERVICE_UNAVAILABLE
+                        }
+                    }
+                },
+            ),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (format!("http://{}", addr), deliveries)
+    }
+
+    #[sqlx::test]
+    async fn test_failed_event_blocks_only_its_webhook(pool: SqlitePool) {
+        let (base_url, deliveries) = mock_endpoint().await;
+        sqlx::query("INSERT INTO users (id, username) VALUES ('user-1', 'user-1')")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let service = WebhookService::new(pool.clone());
+        let mut webhook_ids = Vec::new();
+        for name in ["a", "b"] {
+            let webhook = service
+                .register_webhook(
+                    "user-1",
+                    CreateWebhookRequest {
+                        url: format!("{}/{}", base_url, name),
+                        event_types: vec!["anchor.status_changed".to_string()],
+                        filters: None,
+                    },
+                )
+                .await
+                .unwrap();
+            webhook_ids.push(webhook.id);
+        }
+        for (webhook_id, seq) in [(0, 1), (1, 1), (0, 2), (1, 2)] {
+            service
+                .create_webhook_event(
+                    &webhook_ids[webhook_id],
+                    "anchor.status_changed",
+                    serde_json::json!({ "seq": seq }),
+                )
+                .await
+                .unwrap();
+        }
+
+        let dispatcher = WebhookDispatcher::new(pool);
+
+        // a:1 fails, so a:2 waits; b is unaffected. The two webhooks are
+        // delivered to concurrently, so only each one's own order is fixed.
+        dispatcher.process_pending_events().await.unwrap();
+        let mut first_run = deliveries.lock().unwrap().clone();
+        first_run.sort();
+        assert_eq!(
+            first_run,
+            [
+                ("a".to_string(), 1, false),
+                ("b".to_string(), 1, true),
+                ("b".to_string(), 2, true),
+            ]
+        );
+
+        // The retry goes out before a:2
+        dispatcher.process_pending_events().await.unwrap();
+        let delivered_to_a: Vec<i64> = deliveries
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(path, _, accepted)| path == "a" && *accepted)
+            .map(|(_, seq, _)| *seq)
+            .collect();
+        assert_eq!(delivered_to_a, [1, 2]);
+        assert!(service.get_pending_events(10).await.unwrap().is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_failing_event_is_attempted_four_times(pool: SqlitePool) {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counted = attempts.clone();
+        let app = Router::new().route(
+            "/:name",
+            post(move || {
+                counted.fetch_add(1, Ordering::SeqCst);
+                async { StatusCode::SERVICE_UNAVAILABLE }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        queue_events(&pool, &format!("http://{}", addr), 1, 1).await;
+
+        let dispatcher = WebhookDispatcher::new(pool.clone());
+        for _ in 0..6 {
+            dispatcher.process_pending_events().await.unwrap();
+        }
+
+        assert_eq!(attempts.load(Ordering::SeqCst), 4);
+        let (status, retries): (String, i64) =
+            sqlx::query_as("SELECT status, retries FROM webhook_events")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(status, "failed");
+        assert_eq!(retries, 3);
+    }
+
+    /// Mock webhook endpoint that holds each delivery for a while, recording
+    /// the most deliveries it saw in flight at once and the order of `seq`s
+    async fn slow_endpoint() -> (String, Arc<AtomicUsize>, Arc<Mutex<Vec<i64>>>) {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let (max, seen) = (max_in_flight.clone(), received.clone());
+        let app = Router::new().route(
+            "/:name",
+            post(move |Json(envelope): Json<serde_json::Value>| {
+                let (in_flight, max, seen) = (in_flight.clone(), max.clone(), seen.clone());
+                async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max.fetch_max(now, Ordering::SeqCst);
+                    seen.lock()
+                        .unwrap()
+                        .push(envelope["data"]["seq"].as_i64().unwrap());
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    StatusCode::OK
+                }
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        (format!("http://{}", addr), max_in_flight, received)
+    }
+
+    /// Register `webhooks` webhooks for user-1 and queue `events` events for
+    /// each, numbered from 1
+    async fn queue_events(pool: &SqlitePool, base_url: &str, webhooks: usize, events: i64) {
+        sqlx::query("INSERT INTO users (id, username) VALUES ('user-1', 'user-1')")
+            .execute(pool)
+            .await
+            .unwrap();
+        let service = WebhookService::new(pool.clone());
+        for n in 0..webhooks {
+            let webhook = service
+                .register_webhook(
+                    "user-1",
+                    CreateWebhookRequest {
+                        url: format!("{}/{}", base_url, n),
+                        event_types: vec!["anchor.status_changed".to_string()],
+                        filters: None,
+                    },
+                )
+                .await
+                .unwrap();
+            for seq in 1..=events {
+                service
+                    .create_webhook_event(
+                        &webhook.id,
+                        "anchor.status_changed",
+                        serde_json::json!({ "seq": seq }),
+                    )
+                    .await
+                    .unwrap();
+            }
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_deliveries_run_concurrently_up_to_the_limit(pool: SqlitePool) {
+        let (base_url, max_in_flight, received) = slow_endpoint().await;
+        queue_events(&pool, &base_url, 6, 1).await;
+
+        let dispatcher =
+            WebhookDispatcher::new(pool.clone()).with_dispatch_config(WebhookDispatcherConfig {
+                max_concurrent_deliveries: 3,
+                batch_size: 10,
+            });
+        dispatcher.process_pending_events().await.unwrap();
+
+        assert_eq!(received.lock().unwrap().len(), 6);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+        let service = WebhookService::new(pool);
+        assert!(service.get_pending_events(10).await.unwrap().is_empty());
+    }
+
+    #[sqlx::test]
+    async fn test_one_webhook_is_delivered_to_in_order(pool: SqlitePool) {
+        let (base_url, max_in_flight, received) = slow_endpoint().await;
+        queue_events(&pool, &base_url, 1, 4).await;
+
+        let dispatcher =
+            WebhookDispatcher::new(pool).with_dispatch_config(WebhookDispatcherConfig {
+                max_concurrent_deliveries: 4,
+                batch_size: 10,
+            });
+        dispatcher.process_pending_events().await.unwrap();
+
+        assert_eq!(*received.lock().unwrap(), [1, 2, 3, 4]);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 1);
+    }
 }
@@ -0,0 +1,172 @@
+//! Self-describing wrapper for exported snapshots.
+//!
+//! Exports wrap the canonical JSON as
+//! `{"format":"stellar-insights-snapshot","schema_version":N,"payload":{...}}`.
+//! Only the payload is hashed: it serializes back to exactly the canonical
+//! JSON, so the stored and on-chain hashes can be checked against it.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::schema::LATEST_SCHEMA_VERSION;
+
+/// `format` value of every snapshot envelope
+pub const SNAPSHOT_FORMAT: &str = "stellar-insights-snapshot";
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SnapshotEnvelopeError {
+    #[error("Snapshot envelope is malformed: {0}")]
+    Malformed(String),
+    #[error("Unknown snapshot format '{0}'")]
+    UnknownFormat(String),
+    #[error("Unsupported snapshot schema version {0}, expected 1 to {max}", max = LATEST_SCHEMA_VERSION)]
+    UnsupportedVersion(u32),
+    #[error("Envelope schema version {envelope} does not match payload version {payload}")]
+    VersionMismatch { envelope: u32, payload: u32 },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotEnvelope {
+    pub format: String,
+    pub schema_version: u32,
+    /// The canonical snapshot JSON
+    pub payload: Value,
+}
+
+impl SnapshotEnvelope {
+    /// Wrap a snapshot's canonical JSON
+    pub fn wrap(canonical_json: &str) -> Result<Self, SnapshotEnvelopeError> {
+        let payload: Value = serde_json::from_str(canonical_json)
+            .map_err(|e| SnapshotEnvelopeError::Malformed(e.to_string()))?;
+        let envelope = Self {
+            format: SNAPSHOT_FORMAT.to_string(),
+            schema_version: payload_version(&payload)?,
+            payload,
+        };
+
+        // Anything the hash would not survive must not be exported
+        if envelope.canonical_json()? != canonical_json {
+            return Err(SnapshotEnvelopeError::Malformed(
+                "payload is not in canonical form".to_string(),
+            ));
+        }
+        Ok(envelope)
+    }
+
+    /// Parse and validate an exported envelope
+    pub fn parse(json: &str) -> Result<Self, SnapshotEnvelopeError> {
+        let envelope: Self = serde_json::from_str(json)
+            .map_err(|e| SnapshotEnvelopeError::Malformed(e.to_string()))?;
+
+        if envelope.format != SNAPSHOT_FORMAT {
+            return Err(SnapshotEnvelopeError::UnknownFormat(envelope.format));
+        }
+        if envelope.schema_version == 0 || envelope.schema_version > LATEST_SCHEMA_VERSION {
+            return Err(SnapshotEnvelopeError::UnsupportedVersion(
+                envelope.schema_version,
+            ));
+        }
+        let payload = payload_version(&envelope.payload)?;
+        if payload != envelope.schema_version {
+            return Err(SnapshotEnvelopeError::VersionMismatch {
+                envelope: envelope.schema_version,
+                payload,
+            });
+        }
+        Ok(envelope)
+    }
+
+    /// The payload as the canonical JSON its hash covers
+    pub fn canonical_json(&self) -> Result<String, SnapshotEnvelopeError> {
+        serde_json::to_string(&self.payload)
+            .map_err(|e| SnapshotEnvelopeError::Malformed(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String, SnapshotEnvelopeError> {
+        serde_json::to_string(self).map_err(|e| SnapshotEnvelopeError::Malformed(e.to_string()))
+    }
+}
+
+fn payload_version(payload: &Value) -> Result<u32, SnapshotEnvelopeError> {
+    if !payload.is_object() {
+        return Err(SnapshotEnvelopeError::Malformed(
+            "payload is not a JSON object".to_string(),
+        ));
+    }
+    payload
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| {
+            SnapshotEnvelopeError::Malformed("payload has no schema_version".to_string())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshot::{AnalyticsSnapshot, SnapshotGenerator, SCHEMA_VERSION};
+    use chrono::{TimeZone, Utc};
+
+    fn canonical_json() -> String {
+        let timestamp = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        SnapshotGenerator::to_canonical_json(AnalyticsSnapshot::new(9, timestamp)).unwrap()
+    }
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let canonical = canonical_json();
+        let exported = SnapshotEnvelope::wrap(&canonical)
+            .unwrap()
+            .to_json()
+            .unwrap();
+        assert!(exported.contains(r#""format":"stellar-insights-snapshot""#));
+
+        let parsed = SnapshotEnvelope::parse(&exported).unwrap();
+        assert_eq!(parsed.schema_version, SCHEMA_VERSION);
+        assert_eq!(parsed.payload["epoch"], 9);
+        // The payload still hashes like the stored snapshot
+        assert_eq!(parsed.canonical_json().unwrap(), canonical);
+    }
+
+    #[test]
+    fn test_invalid_envelopes_are_rejected() {
+        let payload: Value = serde_json::from_str(&canonical_json()).unwrap();
+        let envelope = |format: &str, version: u32| {
+            serde_json::json!({ "format": format, "schema_version": version, "payload": payload })
+                .to_string()
+        };
+
+        assert_eq!(
+            SnapshotEnvelope::parse(&envelope("other-snapshot", LATEST_SCHEMA_VERSION)),
+            Err(SnapshotEnvelopeError::UnknownFormat(
+                "other-snapshot".to_string()
+            ))
+        );
+        assert_eq!(
+            SnapshotEnvelope::parse(&envelope(SNAPSHOT_FORMAT, LATEST_SCHEMA_VERSION + 1)),
+            Err(SnapshotEnvelopeError::UnsupportedVersion(
+                LATEST_SCHEMA_VERSION + 1
+            ))
+        );
+        assert_eq!(
+            SnapshotEnvelope::parse(&envelope(SNAPSHOT_FORMAT, 1)),
+            Err(SnapshotEnvelopeError::VersionMismatch {
+                envelope: 1,
+                payload: SCHEMA_VERSION
+            })
+        );
+        assert!(matches!(
+            SnapshotEnvelope::parse(r#"{"format":"stellar-insights-snapshot"}"#),
+            Err(SnapshotEnvelopeError::Malformed(_))
+        ));
+        assert!(matches!(
+            SnapshotEnvelope::parse("not json"),
+            Err(SnapshotEnvelopeError::Malformed(_))
+        ));
+        assert!(matches!(
+            SnapshotEnvelope::wrap(r#"{ "schema_version": 3 }"#),
+            Err(SnapshotEnvelopeError::Malformed(_))
+        ));
+    }
+}
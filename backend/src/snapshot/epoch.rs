@@ -0,0 +1,153 @@
+//! Human-readable labels for snapshot epochs.
+//!
+//! Epoch `n` covers `[genesis + n * duration, genesis + (n + 1) * duration)`
+//! and is labelled by the start of that period: an ISO week (`2026-W03`) for
+//! weekly epochs starting Monday at midnight UTC, a date (`2026-01-15`) for
+//! daily epochs starting at midnight, and an RFC 3339 timestamp otherwise.
+//! With a calendar configured, snapshots taken without an explicit epoch are
+//! assigned the epoch covering the current time, so labels match the data.
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, Timelike, Utc, Weekday};
+
+/// Default epoch length: one week
+pub const DEFAULT_EPOCH_DURATION_SECS: u64 = 7 * 24 * 60 * 60;
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LabelFormat {
+    IsoWeek,
+    Date,
+    Timestamp,
+}
+
+/// Maps snapshot epochs to the periods they cover
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochCalendar {
+    genesis: DateTime<Utc>,
+    duration_secs: u64,
+    format: LabelFormat,
+}
+
+impl EpochCalendar {
+    /// Epoch 0 starts at `genesis`; every epoch lasts `duration_secs`
+    pub fn new(genesis: DateTime<Utc>, duration_secs: u64) -> Result<Self, String> {
+        if duration_secs == 0 || i64::try_from(duration_secs).is_err() {
+            return Err(format!("invalid epoch duration {}s", duration_secs));
+        }
+        let midnight = genesis.time() == NaiveTime::MIN;
+        let format =
+            if duration_secs == 7 * DAY_SECS && midnight && genesis.weekday() == Weekday::Mon {
+                LabelFormat::IsoWeek
+            } else if duration_secs == DAY_SECS && midnight {
+                LabelFormat::Date
+            } else {
+                LabelFormat::Timestamp
+            };
+
+        Ok(Self {
+            genesis,
+            duration_secs,
+            format,
+        })
+    }
+
+    /// Epoch covering `at`; `None` before genesis
+    pub fn derive_epoch(&self, at: DateTime<Utc>) -> Option<u64> {
+        let elapsed = (at - self.genesis).num_seconds();
+        u64::try_from(elapsed)
+            .ok()
+            .map(|secs| secs / self.duration_secs)
+    }
+
+    /// Start of `epoch`'s period
+    pub fn epoch_start(&self, epoch: u64) -> Option<DateTime<Utc>> {
+        let offset = i64::try_from(epoch.checked_mul(self.duration_secs)?).ok()?;
+        self.genesis
+            .checked_add_signed(chrono::Duration::try_seconds(offset)?)
+    }
+
+    /// Label of `epoch`, e.g. `2026-W03`
+    pub fn label(&self, epoch: u64) -> Option<String> {
+        let start = self.epoch_start(epoch)?;
+        Some(match self.format {
+            LabelFormat::IsoWeek => {
+                let week = start.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+            LabelFormat::Date => start.format("%Y-%m-%d").to_string(),
+            LabelFormat::Timestamp => start.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+        })
+    }
+
+    /// Epoch labelled `label`; `None` unless it is the label of an epoch
+    pub fn epoch_for_label(&self, label: &str) -> Option<u64> {
+        let label = label.trim();
+        let start = match self.format {
+            LabelFormat::IsoWeek => {
+                let (year, week) = label.split_once("-W")?;
+                NaiveDate::from_isoywd_opt(year.parse().ok()?, week.parse().ok()?, Weekday::Mon)?
+                    .and_time(NaiveTime::MIN)
+                    .and_utc()
+            }
+            LabelFormat::Date => NaiveDate::parse_from_str(label, "%Y-%m-%d")
+                .ok()?
+                .and_time(NaiveTime::MIN)
+                .and_utc(),
+            LabelFormat::Timestamp => DateTime::parse_from_rfc3339(label)
+                .ok()?
+                .with_timezone(&Utc)
+                .with_nanosecond(0)?,
+        };
+
+        let epoch = self.derive_epoch(start)?;
+        (self.epoch_start(epoch)? == start).then_some(epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn calendar(genesis: &str, duration_secs: u64) -> EpochCalendar {
+        let genesis = DateTime::parse_from_rfc3339(genesis).unwrap().to_utc();
+        EpochCalendar::new(genesis, duration_secs).unwrap()
+    }
+
+    #[test]
+    fn test_weekly_epochs_use_iso_weeks() {
+        // 2025-12-29 is the Monday starting ISO week 2026-W01
+        let weekly = calendar("2025-12-29T00:00:00Z", DEFAULT_EPOCH_DURATION_SECS);
+        assert_eq!(weekly.label(0).as_deref(), Some("2026-W01"));
+        assert_eq!(weekly.label(2).as_deref(), Some("2026-W03"));
+        assert_eq!(weekly.label(53).as_deref(), Some("2027-W01"));
+
+        for epoch in [0, 2, 52, 53, 500] {
+            let label = weekly.label(epoch).unwrap();
+            assert_eq!(weekly.epoch_for_label(&label), Some(epoch), "{}", label);
+        }
+        assert_eq!(weekly.epoch_for_label("2025-W52"), None);
+        assert_eq!(weekly.epoch_for_label("2026-W54"), None);
+        assert_eq!(weekly.epoch_for_label("2026-01-12"), None);
+
+        let at = Utc.with_ymd_and_hms(2026, 1, 14, 15, 30, 0).unwrap();
+        assert_eq!(weekly.derive_epoch(at), Some(2));
+        assert_eq!(weekly.derive_epoch(at - chrono::Duration::days(30)), None);
+    }
+
+    #[test]
+    fn test_daily_and_other_epochs() {
+        let daily = calendar("2026-01-01T00:00:00Z", DAY_SECS);
+        assert_eq!(daily.label(14).as_deref(), Some("2026-01-15"));
+        assert_eq!(daily.epoch_for_label("2026-01-15"), Some(14));
+
+        let hourly = calendar("2026-01-01T06:00:00Z", 3600);
+        assert_eq!(hourly.label(7).as_deref(), Some("2026-01-01T13:00:00Z"));
+        assert_eq!(hourly.epoch_for_label("2026-01-01T13:00:00Z"), Some(7));
+        // Not the start of an epoch
+        assert_eq!(hourly.epoch_for_label("2026-01-01T13:30:00Z"), None);
+
+        assert!(EpochCalendar::new(Utc::now(), 0).is_err());
+    }
+}
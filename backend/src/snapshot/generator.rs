@@ -11,7 +11,13 @@ impl SnapshotGenerator {
     /// 1. All arrays are sorted by object identifiers
     /// 2. JSON is serialized in canonical form (no extra whitespace, sorted keys)
     /// 3. Result is suitable for hashing
+    ///
+    /// Fails when two anchor or corridor metrics share an id.
     pub fn to_canonical_json(mut snapshot: AnalyticsSnapshot) -> Result<String, serde_json::Error> {
+        snapshot
+            .check_unique_ids()
+            .map_err(serde::ser::Error::custom)?;
+
         // Normalize the snapshot (sort all arrays)
         snapshot.normalize();
 
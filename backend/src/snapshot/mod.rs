@@ -1,7 +1,14 @@
+pub mod envelope;
+pub mod epoch;
 pub mod generator;
 pub mod schema;
 
+pub use envelope::{SnapshotEnvelope, SnapshotEnvelopeError, SNAPSHOT_FORMAT};
+pub use epoch::{EpochCalendar, DEFAULT_EPOCH_DURATION_SECS};
 pub use generator::SnapshotGenerator;
 pub use schema::{
-    AnalyticsSnapshot, SnapshotAnchorMetrics, SnapshotCorridorMetrics, SCHEMA_VERSION,
+    normalize_stellar_amount, stellar_amount_from_f64, AnalyticsSnapshot, SnapshotAnchorMetrics,
+    SnapshotCorridorMetrics, SnapshotPoolMetrics, SnapshotSection, CORE_SNAPSHOT_KEYS,
+    EXACT_AMOUNTS_SCHEMA_VERSION, LATEST_SCHEMA_VERSION, POOL_METRICS_SECTION, SCHEMA_VERSION,
+    STELLAR_AMOUNT_DECIMALS,
 };
@@ -1,9 +1,56 @@
+use anyhow::{bail, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
 use uuid::Uuid;
 
-/// Snapshot schema version for backward compatibility
-pub const SCHEMA_VERSION: u32 = 1;
+/// Snapshot schema version new snapshots are written with by default.
+///
+/// Version 2 added extension metric sections, version 3 the liquidity pool
+/// section.
+pub const SCHEMA_VERSION: u32 = 3;
+
+/// Opt-in schema version writing every monetary field as a decimal string
+/// instead of a JSON number, so Stellar amounts hash exactly
+pub const EXACT_AMOUNTS_SCHEMA_VERSION: u32 = 4;
+
+/// Newest schema version snapshots can be read with
+pub const LATEST_SCHEMA_VERSION: u32 = EXACT_AMOUNTS_SCHEMA_VERSION;
+
+/// Decimal places of a Stellar amount; one stroop is 0.0000001
+pub const STELLAR_AMOUNT_DECIMALS: usize = 7;
+
+/// Section holding liquidity pool metrics, from schema version 3
+pub const POOL_METRICS_SECTION: &str = "pool_metrics";
+
+/// Top-level keys of the canonical snapshot JSON owned by the core schema
+pub const CORE_SNAPSHOT_KEYS: &[&str] = &[
+    "schema_version",
+    "epoch",
+    "timestamp",
+    "anchor_metrics",
+    "corridor_metrics",
+];
+
+/// A metric type carried in a snapshot alongside the core anchor and
+/// corridor metrics.
+///
+/// Each section appears in the canonical JSON as a top-level array under
+/// `NAME`, with entries ordered by `sort_key` and object keys sorted.
+pub trait SnapshotSection {
+    /// Top-level key of the section; must not clash with a core key
+    const NAME: &'static str;
+
+    type Entry;
+
+    /// Key entries are ordered by; should be unique within the section
+    fn sort_key(entry: &Self::Entry) -> String;
+
+    /// Serialize one entry for a snapshot of `schema_version`. Must depend
+    /// only on its arguments so that the output is reproducible.
+    fn serialize_entry(entry: &Self::Entry, schema_version: u32) -> Value;
+}
 
 /// Individual anchor metrics within a snapshot
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -18,6 +65,7 @@ pub struct SnapshotAnchorMetrics {
     pub successful_transactions: i64,
     pub failed_transactions: i64,
     pub avg_settlement_time_ms: Option<i32>,
+    #[serde(default, deserialize_with = "amount::optional")]
     pub volume_usd: Option<f64>,
     pub status: String,
 }
@@ -35,11 +83,28 @@ pub struct SnapshotCorridorMetrics {
     pub successful_transactions: i64,
     pub failed_transactions: i64,
     pub success_rate: f64,
+    #[serde(deserialize_with = "amount::required")]
     pub volume_usd: f64,
     pub avg_settlement_latency_ms: Option<i32>,
+    #[serde(deserialize_with = "amount::required")]
     pub liquidity_depth_usd: f64,
 }
 
+/// Individual liquidity pool metrics within a snapshot
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotPoolMetrics {
+    pub pool_id: String,
+    pub reserve_a_asset_code: String,
+    pub reserve_a_asset_issuer: Option<String>,
+    /// Stellar amount with seven decimal places, see [`normalize_stellar_amount`]
+    pub reserve_a_amount: String,
+    pub reserve_b_asset_code: String,
+    pub reserve_b_asset_issuer: Option<String>,
+    pub reserve_b_amount: String,
+    pub total_value_usd: f64,
+    pub fee_bp: i32,
+}
+
 /// Complete snapshot containing all metrics at a specific epoch
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalyticsSnapshot {
@@ -53,6 +118,9 @@ pub struct AnalyticsSnapshot {
     pub anchor_metrics: Vec<SnapshotAnchorMetrics>,
     /// All corridor metrics at this epoch
     pub corridor_metrics: Vec<SnapshotCorridorMetrics>,
+    /// Extension metric sections, keyed by section name, entries in canonical form
+    #[serde(default)]
+    pub sections: BTreeMap<String, Vec<Value>>,
 }
 
 impl AnalyticsSnapshot {
@@ -64,6 +132,7 @@ impl AnalyticsSnapshot {
             timestamp,
             anchor_metrics: Vec::new(),
             corridor_metrics: Vec::new(),
+            sections: BTreeMap::new(),
         }
     }
 
@@ -77,15 +146,193 @@ impl AnalyticsSnapshot {
         self.corridor_metrics.push(metrics);
     }
 
+    /// Number of metric entries across anchors, corridors and sections
+    pub fn metric_count(&self) -> usize {
+        self.anchor_metrics.len()
+            + self.corridor_metrics.len()
+            + self.sections.values().map(Vec::len).sum::<usize>()
+    }
+
+    /// Set the entries of an extension section, replacing any previous ones
+    pub fn add_section<S: SnapshotSection>(&mut self, entries: &[S::Entry]) -> Result<()> {
+        if CORE_SNAPSHOT_KEYS.contains(&S::NAME) {
+            bail!("Snapshot section '{}' clashes with a core field", S::NAME);
+        }
+
+        let mut keyed: Vec<(String, Value)> = entries
+            .iter()
+            .map(|entry| {
+                let value = S::serialize_entry(entry, self.schema_version);
+                (S::sort_key(entry), canonicalize(value))
+            })
+            .collect();
+        keyed.sort_by(|a, b| a.0.cmp(&b.0));
+
+        self.sections.insert(
+            S::NAME.to_string(),
+            keyed.into_iter().map(|(_, value)| value).collect(),
+        );
+        Ok(())
+    }
+
+    /// Upgrade a snapshot written under an earlier schema version to
+    /// `SCHEMA_VERSION`. Sections added since are present but empty.
+    pub fn migrate(mut self) -> Result<Self> {
+        if self.schema_version > LATEST_SCHEMA_VERSION {
+            bail!(
+                "Snapshot schema version {} is newer than supported version {}",
+                self.schema_version,
+                LATEST_SCHEMA_VERSION
+            );
+        }
+
+        // 1 -> 2 only introduced `sections`, which deserializes as empty
+        if self.schema_version < 3 {
+            self.sections
+                .entry(POOL_METRICS_SECTION.to_string())
+                .or_default();
+        }
+        // 4 only changes how amounts are written and is opt-in, so neither
+        // direction between 3 and 4 is a migration
+        self.schema_version = self.schema_version.max(SCHEMA_VERSION);
+        Ok(self)
+    }
+
     /// Sort all arrays deterministically for consistent serialization
+    ///
+    /// Metrics are ordered by id, then by name or corridor key, so metrics
+    /// wrongly sharing an id still come out in the same order whatever
+    /// order they were added in.
     pub fn normalize(&mut self) {
-        // Sort anchor metrics by id for deterministic ordering
-        self.anchor_metrics
-            .sort_by(|a, b| a.id.as_bytes().cmp(b.id.as_bytes()));
+        self.anchor_metrics.sort_by(|a, b| {
+            a.id.as_bytes()
+                .cmp(b.id.as_bytes())
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.stellar_account.cmp(&b.stellar_account))
+        });
+
+        self.corridor_metrics.sort_by(|a, b| {
+            a.id.as_bytes()
+                .cmp(b.id.as_bytes())
+                .then_with(|| a.corridor_key.cmp(&b.corridor_key))
+        });
+    }
+
+    /// Fail if two anchor metrics, or two corridor metrics, share an id
+    pub fn check_unique_ids(&self) -> Result<(), DuplicateMetricId> {
+        fn check(
+            kind: &'static str,
+            ids: impl Iterator<Item = Uuid>,
+        ) -> Result<(), DuplicateMetricId> {
+            let mut seen = HashSet::new();
+            for id in ids {
+                if !seen.insert(id) {
+                    return Err(DuplicateMetricId { kind, id });
+                }
+            }
+            Ok(())
+        }
+
+        check("anchor", self.anchor_metrics.iter().map(|m| m.id))?;
+        check("corridor", self.corridor_metrics.iter().map(|m| m.id))
+    }
+}
+
+/// Two metrics of a snapshot share an id, so it has no canonical form
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Snapshot has more than one {kind} metric with id {id}")]
+pub struct DuplicateMetricId {
+    /// `anchor` or `corridor`
+    pub kind: &'static str,
+    pub id: Uuid,
+}
+
+/// Write a decimal amount string with exactly `STELLAR_AMOUNT_DECIMALS`
+/// decimal places, without passing it through `f64`
+///
+/// Horizon reports amounts such as `"922337203685.4775807"`, which `f64`
+/// cannot hold exactly. Returns `None` for anything but a plain decimal with
+/// at most seven decimal places.
+pub fn normalize_stellar_amount(amount: &str) -> Option<String> {
+    let amount = amount.trim();
+    let (negative, digits) = match amount.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, amount),
+    };
+    let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if whole.is_empty()
+        || !all_digits(whole)
+        || !all_digits(fraction)
+        || fraction.len() > STELLAR_AMOUNT_DECIMALS
+    {
+        return None;
+    }
+
+    let whole = match whole.trim_start_matches('0') {
+        "" => "0",
+        trimmed => trimmed,
+    };
+    let zero = whole == "0" && fraction.bytes().all(|b| b == b'0');
+    Some(format!(
+        "{}{}.{:0<width$}",
+        if negative && !zero { "-" } else { "" },
+        whole,
+        fraction,
+        width = STELLAR_AMOUNT_DECIMALS
+    ))
+}
+
+/// Write `value` as a Stellar amount string, for amounts only available as
+/// floating point
+pub fn stellar_amount_from_f64(value: f64) -> String {
+    format!("{:.*}", STELLAR_AMOUNT_DECIMALS, value)
+}
+
+/// Deserializers for monetary fields, which are JSON numbers up to schema
+/// version 3 and decimal strings from `EXACT_AMOUNTS_SCHEMA_VERSION`
+mod amount {
+    use serde::{de, Deserialize, Deserializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Amount {
+        Number(f64),
+        Decimal(String),
+    }
+
+    impl Amount {
+        fn into_f64<E: de::Error>(self) -> Result<f64, E> {
+            match self {
+                Amount::Number(value) => Ok(value),
+                Amount::Decimal(decimal) => decimal
+                    .parse()
+                    .map_err(|_| E::custom(format!("invalid amount '{}'", decimal))),
+            }
+        }
+    }
+
+    pub fn required<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        Amount::deserialize(deserializer)?.into_f64()
+    }
+
+    pub fn optional<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<f64>, D::Error> {
+        Option::<Amount>::deserialize(deserializer)?
+            .map(Amount::into_f64)
+            .transpose()
+    }
+}
 
-        // Sort corridor metrics by id for deterministic ordering
-        self.corridor_metrics
-            .sort_by(|a, b| a.id.as_bytes().cmp(b.id.as_bytes()));
+/// Rebuild objects with their keys in sorted order, recursively
+fn canonicalize(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let sorted: BTreeMap<String, Value> =
+                map.into_iter().map(|(k, v)| (k, canonicalize(v))).collect();
+            Value::Object(sorted.into_iter().collect::<Map<String, Value>>())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(canonicalize).collect()),
+        other => other,
     }
 }
 
@@ -193,4 +440,174 @@ mod tests {
         assert_eq!(snapshot.anchor_metrics[1].id, id1);
         assert_eq!(snapshot.anchor_metrics[2].id, id3);
     }
+
+    fn anchor(id: u128, name: &str) -> SnapshotAnchorMetrics {
+        SnapshotAnchorMetrics {
+            id: Uuid::from_u128(id),
+            name: name.to_string(),
+            stellar_account: format!("G{}", name.to_uppercase()),
+            success_rate: 99.0,
+            failure_rate: 1.0,
+            reliability_score: 0.99,
+            total_transactions: 100,
+            successful_transactions: 99,
+            failed_transactions: 1,
+            avg_settlement_time_ms: None,
+            volume_usd: None,
+            status: "green".to_string(),
+        }
+    }
+
+    fn corridor(id: u128, key: &str) -> SnapshotCorridorMetrics {
+        SnapshotCorridorMetrics {
+            id: Uuid::from_u128(id),
+            corridor_key: key.to_string(),
+            asset_a_code: "USDC".to_string(),
+            asset_a_issuer: "issuer1".to_string(),
+            asset_b_code: "EURC".to_string(),
+            asset_b_issuer: "issuer2".to_string(),
+            total_transactions: 10,
+            successful_transactions: 9,
+            failed_transactions: 1,
+            success_rate: 90.0,
+            volume_usd: 100.0,
+            avg_settlement_latency_ms: None,
+            liquidity_depth_usd: 1000.0,
+        }
+    }
+
+    #[test]
+    fn test_duplicate_ids_are_rejected() {
+        let mut snapshot = AnalyticsSnapshot::new(1, Utc::now());
+        snapshot.add_anchor_metrics(anchor(1, "alpha"));
+        snapshot.add_anchor_metrics(anchor(2, "beta"));
+        snapshot.add_corridor_metrics(corridor(1, "a->b"));
+        assert_eq!(snapshot.check_unique_ids(), Ok(()));
+
+        snapshot.add_anchor_metrics(anchor(1, "gamma"));
+        let err = snapshot.check_unique_ids().unwrap_err();
+        assert_eq!(
+            err,
+            DuplicateMetricId {
+                kind: "anchor",
+                id: Uuid::from_u128(1)
+            }
+        );
+
+        let mut snapshot = AnalyticsSnapshot::new(1, Utc::now());
+        snapshot.add_corridor_metrics(corridor(7, "a->b"));
+        snapshot.add_corridor_metrics(corridor(7, "a->c"));
+        assert_eq!(snapshot.check_unique_ids().unwrap_err().kind, "corridor");
+    }
+
+    #[test]
+    fn test_normalize_orders_shared_ids_by_name() {
+        let normalized = |anchors: &[(u128, &str)], corridors: &[(u128, &str)]| {
+            let mut snapshot = AnalyticsSnapshot::new(1, Utc::now());
+            for (id, name) in anchors {
+                snapshot.add_anchor_metrics(anchor(*id, name));
+            }
+            for (id, key) in corridors {
+                snapshot.add_corridor_metrics(corridor(*id, key));
+            }
+            snapshot.normalize();
+            let names: Vec<String> = snapshot
+                .anchor_metrics
+                .into_iter()
+                .map(|m| m.name)
+                .collect();
+            let keys: Vec<String> = snapshot
+                .corridor_metrics
+                .into_iter()
+                .map(|m| m.corridor_key)
+                .collect();
+            (names, keys)
+        };
+
+        let forward = normalized(
+            &[(2, "beta"), (1, "zeta"), (1, "alpha")],
+            &[(3, "x->y"), (3, "a->b")],
+        );
+        let reverse = normalized(
+            &[(1, "alpha"), (1, "zeta"), (2, "beta")],
+            &[(3, "a->b"), (3, "x->y")],
+        );
+        assert_eq!(forward, reverse);
+        assert_eq!(forward.0, ["alpha", "zeta", "beta"]);
+        assert_eq!(forward.1, ["a->b", "x->y"]);
+    }
+
+    #[test]
+    fn test_migrate_from_version_1() {
+        let v1 = serde_json::json!({
+            "schema_version": 1,
+            "epoch": 7,
+            "timestamp": "2024-01-01T00:00:00Z",
+            "anchor_metrics": [],
+            "corridor_metrics": []
+        });
+        let snapshot: AnalyticsSnapshot = serde_json::from_value(v1).unwrap();
+
+        let migrated = snapshot.migrate().unwrap();
+        assert_eq!(migrated.schema_version, SCHEMA_VERSION);
+        assert_eq!(migrated.epoch, 7);
+        assert_eq!(
+            migrated.sections.get(POOL_METRICS_SECTION),
+            Some(&Vec::new())
+        );
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_version() {
+        let mut snapshot = AnalyticsSnapshot::new(1, Utc::now());
+        snapshot.schema_version = LATEST_SCHEMA_VERSION + 1;
+        assert!(snapshot.migrate().is_err());
+    }
+
+    #[test]
+    fn test_migrate_keeps_exact_amounts_version() {
+        let mut snapshot = AnalyticsSnapshot::new(1, Utc::now());
+        snapshot.schema_version = EXACT_AMOUNTS_SCHEMA_VERSION;
+        assert_eq!(
+            snapshot.migrate().unwrap().schema_version,
+            EXACT_AMOUNTS_SCHEMA_VERSION
+        );
+    }
+
+    #[test]
+    fn test_monetary_fields_read_as_numbers_or_decimal_strings() {
+        let mut anchor = serde_json::to_value(anchor(1, "a")).unwrap();
+        anchor["volume_usd"] = Value::String("10000.5000000".to_string());
+        let anchor: SnapshotAnchorMetrics = serde_json::from_value(anchor).unwrap();
+        assert_eq!(anchor.volume_usd, Some(10000.5));
+
+        let mut corridor = serde_json::to_value(corridor(2, "a->b")).unwrap();
+        corridor["liquidity_depth_usd"] = Value::String("abc".to_string());
+        assert!(serde_json::from_value::<SnapshotCorridorMetrics>(corridor).is_err());
+    }
+
+    #[test]
+    fn test_normalize_stellar_amount() {
+        let max = "922337203685.4775807";
+        assert_eq!(normalize_stellar_amount(max).as_deref(), Some(max));
+        assert_ne!(stellar_amount_from_f64(max.parse().unwrap()), max);
+
+        assert_eq!(
+            normalize_stellar_amount("00100.5").as_deref(),
+            Some("100.5000000")
+        );
+        assert_eq!(normalize_stellar_amount("7").as_deref(), Some("7.0000000"));
+        assert_eq!(
+            normalize_stellar_amount("-0.25").as_deref(),
+            Some("-0.2500000")
+        );
+        assert_eq!(
+            normalize_stellar_amount("-0.0").as_deref(),
+            Some("0.0000000")
+        );
+        for invalid in ["", ".5", "1.23456789", "1e5", "abc", "1.2.3"] {
+            assert_eq!(normalize_stellar_amount(invalid), None, "{}", invalid);
+        }
+        assert_eq!(stellar_amount_from_f64(500.25), "500.2500000");
+    }
 }
@@ -1,19 +1,37 @@
 //! HTTP handlers for snapshot generation and submission
 
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
-use chrono::Utc;
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, HeaderName, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{error, info};
 
+use crate::admin_audit_log::AuditContext;
+use crate::api::pagination::{PagedResource, PaginationConfig};
 use crate::database::Database;
-use crate::services::contract::ContractService;
-use crate::services::snapshot::SnapshotService;
+use crate::error::{ApiError, ApiResult};
+use crate::models::{SnapshotCursor, SnapshotRecord};
+use crate::services::contract::{BatchSubmissionResult, ContractService};
+use crate::services::idempotency::IdempotencyConflict;
+use crate::services::snapshot::{
+    DeterminismCheck, EpochNotStarted, EpochVerification, GenerationInProgress, HashAlgorithm,
+    ProofBundle, SnapshotExists, SnapshotGenerationResult, SnapshotHashVerification,
+    SnapshotService,
+};
+use crate::snapshot::SnapshotEnvelope;
 
 /// Response for snapshot generation
 #[derive(Debug, Serialize)]
 pub struct SnapshotResponse {
     pub epoch: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epoch_label: Option<String>,
     pub timestamp: String,
     pub hash: String,
     pub schema_version: u32,
@@ -39,6 +57,173 @@ pub struct GenerateSnapshotRequest {
     pub submit_to_contract: bool,
 }
 
+/// Request for a manually triggered snapshot
+#[derive(Debug, Default, Deserialize)]
+pub struct ManualSnapshotRequest {
+    /// Epoch to snapshot; defaults to the epoch after the latest stored one
+    #[serde(default)]
+    pub epoch: Option<u64>,
+}
+
+/// Query for listing snapshots, newest epoch first
+#[derive(Debug, Deserialize)]
+pub struct ListSnapshotsQuery {
+    #[serde(default)]
+    pub limit: Option<i64>,
+    #[serde(default)]
+    pub offset: i64,
+    /// `next_cursor` of the previous page; takes precedence over `offset`
+    #[serde(default)]
+    pub cursor: Option<String>,
+    /// Only list snapshots of this entity type, e.g. `analytics_snapshot`
+    #[serde(default)]
+    pub entity_type: Option<String>,
+}
+
+/// A stored snapshot with the label of its epoch
+#[derive(Debug, Serialize)]
+pub struct LabeledSnapshot {
+    #[serde(flatten)]
+    pub record: SnapshotRecord,
+    /// Period the epoch covers, e.g. `2026-W03`; absent without an epoch
+    /// calendar
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epoch_label: Option<String>,
+}
+
+/// A page of snapshots
+#[derive(Debug, Serialize)]
+pub struct SnapshotListResponse {
+    pub snapshots: Vec<LabeledSnapshot>,
+    /// Cursor for the next page, absent on the last page
+    pub next_cursor: Option<String>,
+}
+
+/// Snapshots returned by `/api/snapshots/recent` when `n` is not given
+const DEFAULT_RECENT_SNAPSHOTS: i64 = 5;
+
+/// Query for the most recent snapshots
+#[derive(Debug, Default, Deserialize)]
+pub struct RecentSnapshotsQuery {
+    /// Number of snapshots, at most the snapshot page size maximum
+    #[serde(default)]
+    pub n: Option<i64>,
+}
+
+/// A stored snapshot with its changes since the previous epoch
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecentSnapshot {
+    pub epoch: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epoch_label: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    pub hash: Option<String>,
+    pub anchor_count: usize,
+    pub corridor_count: usize,
+    /// Epoch of the snapshot these deltas are against; `None` for the first
+    /// stored snapshot
+    pub previous_epoch: Option<i64>,
+    pub anchor_count_delta: Option<i64>,
+    pub corridor_count_delta: Option<i64>,
+    pub hash_changed: Option<bool>,
+}
+
+/// The most recent snapshots, newest first
+#[derive(Debug, Serialize)]
+pub struct RecentSnapshotsResponse {
+    pub snapshots: Vec<RecentSnapshot>,
+}
+
+/// Anchor and corridor counts of a stored snapshot's data
+fn snapshot_counts(record: &SnapshotRecord) -> (usize, usize) {
+    let data: serde_json::Value = serde_json::from_str(&record.data).unwrap_or_default();
+    let count = |key: &str| data[key].as_array().map_or(0, Vec::len);
+    (count("anchor_metrics"), count("corridor_metrics"))
+}
+
+/// Annotate snapshots, newest first, with deltas against the next older one.
+/// The oldest record only serves as the baseline for the one before it.
+pub fn recent_snapshots_with_deltas(records: &[SnapshotRecord], n: usize) -> Vec<RecentSnapshot> {
+    records
+        .iter()
+        .enumerate()
+        .take(n)
+        .filter_map(|(i, record)| {
+            let epoch = record.epoch?;
+            let (anchor_count, corridor_count) = snapshot_counts(record);
+            let previous = records.get(i + 1);
+            let previous_counts = previous.map(snapshot_counts);
+
+            Some(RecentSnapshot {
+                epoch,
+                epoch_label: None,
+                timestamp: record.timestamp,
+                hash: record.hash.clone(),
+                anchor_count,
+                corridor_count,
+                previous_epoch: previous.and_then(|p| p.epoch),
+                anchor_count_delta: previous_counts
+                    .map(|(anchors, _)| anchor_count as i64 - anchors as i64),
+                corridor_count_delta: previous_counts
+                    .map(|(_, corridors)| corridor_count as i64 - corridors as i64),
+                hash_changed: previous.map(|p| p.hash != record.hash),
+            })
+        })
+        .collect()
+}
+
+/// Label of a stored epoch, if the service has an epoch calendar
+fn stored_epoch_label(service: &SnapshotService, epoch: Option<i64>) -> Option<String> {
+    service.epoch_label(u64::try_from(epoch?).ok()?)
+}
+
+fn labeled(service: &SnapshotService, record: SnapshotRecord) -> LabeledSnapshot {
+    LabeledSnapshot {
+        epoch_label: stored_epoch_label(service, record.epoch),
+        record,
+    }
+}
+
+/// Query for a serialization determinism check
+#[derive(Debug, Default, Deserialize)]
+pub struct DeterminismCheckQuery {
+    /// Epoch to check; defaults to the epoch after the latest stored one
+    #[serde(default)]
+    pub epoch: Option<u64>,
+}
+
+/// Query for verifying a snapshot hash
+#[derive(Debug, Deserialize)]
+pub struct VerifyHashQuery {
+    /// Hex-encoded 32-byte hash to check
+    pub hash: String,
+}
+
+/// Most epochs checked by one batch verification
+pub const MAX_BATCH_VERIFY_EPOCHS: usize = 100;
+
+/// Query for verifying several epochs at once
+#[derive(Debug, Deserialize)]
+pub struct BatchVerifyQuery {
+    /// Comma-separated epochs, e.g. `3,4,5`
+    pub epochs: String,
+}
+
+/// Request to submit stored snapshots that are missing on-chain
+#[derive(Debug, Deserialize)]
+pub struct SubmitMissingRequest {
+    /// Epochs to check, at most `MAX_BATCH_VERIFY_EPOCHS`
+    pub epochs: Vec<u64>,
+}
+
+/// Database and on-chain presence of each requested epoch
+#[derive(Debug, Serialize)]
+pub struct BatchVerifyResponse {
+    pub results: Vec<EpochVerification>,
+    /// Every epoch is stored and on-chain with matching hashes
+    pub all_match: bool,
+}
+
 /// Shared application state for snapshot handlers
 #[derive(Clone)]
 pub struct SnapshotAppState {
@@ -69,9 +254,10 @@ pub async fn generate_snapshot(
             let hash = result.hash.clone();
             let response = SnapshotResponse {
                 epoch: result.epoch,
+                epoch_label: state.snapshot_service.epoch_label(result.epoch),
                 timestamp: result.timestamp.to_rfc3339(),
                 hash: result.hash,
-                schema_version: 1, // From SCHEMA_VERSION
+                schema_version: result.schema_version,
                 anchor_count: result.anchor_count,
                 corridor_count: result.corridor_count,
                 submission: result.submission_result.map(|sr| SubmissionInfo {
@@ -97,9 +283,431 @@ pub async fn generate_snapshot(
                 "Failed to generate snapshot for epoch {}: {}",
                 request.epoch, e
             );
-            Err(SnapshotError::GenerationFailed(e.to_string()))
+            Err(SnapshotError::from_generation(e))
+        }
+    }
+}
+
+/// Force a snapshot, e.g. ahead of a known event
+///
+/// POST /api/admin/snapshots
+pub async fn trigger_manual_snapshot(
+    State(state): State<SnapshotAppState>,
+    audit: AuditContext,
+    request: Option<Json<ManualSnapshotRequest>>,
+) -> Result<(StatusCode, Json<SnapshotGenerationResult>), SnapshotError> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    let service = &state.snapshot_service;
+
+    let epoch = match request.epoch {
+        Some(epoch) => epoch,
+        None => service
+            .next_epoch()
+            .await
+            .map_err(SnapshotError::from_generation)?,
+    };
+
+    info!("Manually triggered snapshot for epoch {}", epoch);
+    let result = service.generate_new_snapshot(epoch).await.map_err(|e| {
+        error!("Manual snapshot for epoch {} failed: {}", epoch, e);
+        SnapshotError::from_generation(e)
+    })?;
+    state
+        .db
+        .admin_audit_logger
+        .record(
+            &audit,
+            "snapshot.trigger",
+            &format!("snapshot:{}", epoch),
+            serde_json::json!({ "hash": result.hash }),
+        )
+        .await;
+
+    Ok((StatusCode::CREATED, Json(result)))
+}
+
+/// Submit the stored snapshots of the requested epochs that the contract
+/// does not hold yet, batched into one transaction where the contract
+/// supports it
+///
+/// POST /api/admin/snapshots/submit-missing
+pub async fn submit_missing_snapshots(
+    State(state): State<SnapshotAppState>,
+    audit: AuditContext,
+    Json(request): Json<SubmitMissingRequest>,
+) -> Result<Json<BatchSubmissionResult>, SnapshotError> {
+    let mut epochs = request.epochs;
+    epochs.sort_unstable();
+    epochs.dedup();
+    if epochs.is_empty() || epochs.len() > MAX_BATCH_VERIFY_EPOCHS {
+        return Err(SnapshotError::InvalidEpochs(format!(
+            "expected between 1 and {} epochs",
+            MAX_BATCH_VERIFY_EPOCHS
+        )));
+    }
+    if state.contract_service.is_none() {
+        return Err(SnapshotError::ConfigError(
+            "Contract service not configured".to_string(),
+        ));
+    }
+
+    let result = state
+        .snapshot_service
+        .submit_missing_epochs(&epochs)
+        .await
+        .map_err(|e| {
+            error!("Submitting missing snapshots failed: {}", e);
+            SnapshotError::SubmissionError(e.to_string())
+        })?;
+    let submitted: Vec<u64> = result.submissions.iter().map(|s| s.epoch).collect();
+    info!(
+        "Submitted missing snapshots for epochs {:?} (batched: {})",
+        submitted, result.batched
+    );
+    state
+        .db
+        .admin_audit_logger
+        .record(
+            &audit,
+            "snapshot.submit_missing",
+            "snapshots",
+            serde_json::json!({
+                "submitted": submitted,
+                "already_submitted": result.already_submitted,
+            }),
+        )
+        .await;
+
+    Ok(Json(result))
+}
+
+/// Dry run: serialize the current data for an epoch repeatedly and report
+/// whether the output is stable, without storing or submitting anything
+///
+/// GET /api/admin/snapshots/determinism-check?epoch=...
+pub async fn check_snapshot_determinism(
+    State(state): State<SnapshotAppState>,
+    Query(query): Query<DeterminismCheckQuery>,
+) -> Result<Json<DeterminismCheck>, SnapshotError> {
+    let service = &state.snapshot_service;
+
+    let epoch = match query.epoch {
+        Some(epoch) => epoch,
+        None => service
+            .next_epoch()
+            .await
+            .map_err(SnapshotError::from_generation)?,
+    };
+
+    let check = service
+        .check_determinism(epoch)
+        .await
+        .map_err(|e| SnapshotError::HashingError(e.to_string()))?;
+    if !check.passed {
+        error!(
+            "Snapshot serialization is not deterministic for epoch {}: {:?}",
+            epoch, check.mismatches
+        );
+    }
+
+    Ok(Json(check))
+}
+
+/// List stored snapshots, newest epoch first
+///
+/// GET /api/snapshots?limit=&offset=&cursor=&entity_type=
+pub async fn list_snapshots(
+    State(state): State<SnapshotAppState>,
+    Extension(pagination): Extension<PaginationConfig>,
+    Query(query): Query<ListSnapshotsQuery>,
+) -> ApiResult<Json<SnapshotListResponse>> {
+    let limit = pagination.page_limit(PagedResource::Snapshots, query.limit);
+    let entity_type = query.entity_type.as_deref();
+    let snapshots = match query.cursor.as_deref() {
+        Some(raw) => {
+            let cursor = SnapshotCursor::decode(raw).ok_or_else(|| {
+                ApiError::bad_request(
+                    "INVALID_CURSOR",
+                    format!("Invalid snapshot cursor '{}'", raw),
+                )
+            })?;
+            state
+                .db
+                .list_snapshots_after(entity_type, &cursor, limit)
+                .await?
         }
+        None => {
+            pagination.check_offset(query.offset, Some("cursor"))?;
+            state
+                .db
+                .list_snapshots(entity_type, limit, query.offset.max(0))
+                .await?
+        }
+    };
+
+    let next_cursor = if snapshots.len() as i64 == limit {
+        snapshots
+            .last()
+            .and_then(SnapshotCursor::after)
+            .map(|cursor| cursor.encode())
+    } else {
+        None
+    };
+
+    Ok(Json(SnapshotListResponse {
+        snapshots: snapshots
+            .into_iter()
+            .map(|record| labeled(&state.snapshot_service, record))
+            .collect(),
+        next_cursor,
+    }))
+}
+
+/// The latest `n` snapshots with count deltas versus the prior epoch
+///
+/// GET /api/snapshots/recent?n=5
+pub async fn recent_snapshots(
+    State(state): State<SnapshotAppState>,
+    Extension(pagination): Extension<PaginationConfig>,
+    Query(query): Query<RecentSnapshotsQuery>,
+) -> ApiResult<Json<RecentSnapshotsResponse>> {
+    let n = pagination.page_limit(
+        PagedResource::Snapshots,
+        Some(query.n.unwrap_or(DEFAULT_RECENT_SNAPSHOTS)),
+    );
+    // One extra snapshot is the baseline for the oldest one returned
+    let records = state.db.list_snapshots(None, n + 1, 0).await?;
+
+    let mut snapshots = recent_snapshots_with_deltas(&records, n as usize);
+    for snapshot in &mut snapshots {
+        snapshot.epoch_label = stored_epoch_label(&state.snapshot_service, Some(snapshot.epoch));
+    }
+    Ok(Json(RecentSnapshotsResponse { snapshots }))
+}
+
+/// The stored snapshot of the epoch with a human-readable label
+///
+/// GET /api/snapshots/by-label/:label
+pub async fn snapshot_by_label(
+    State(state): State<SnapshotAppState>,
+    Path(label): Path<String>,
+) -> Result<Json<LabeledSnapshot>, SnapshotError> {
+    let epoch = state
+        .snapshot_service
+        .epoch_for_label(&label)
+        .ok_or(SnapshotError::UnknownLabel(label))?;
+    let stored_epoch = i64::try_from(epoch).map_err(|_| SnapshotError::NotFound(epoch))?;
+    let record = state
+        .db
+        .get_snapshot_by_epoch(stored_epoch)
+        .await
+        .map_err(SnapshotError::database)?
+        .ok_or(SnapshotError::NotFound(epoch))?;
+
+    Ok(Json(labeled(&state.snapshot_service, record)))
+}
+
+/// Compare a locally computed hash with the stored and on-chain hashes
+///
+/// GET /api/snapshots/:epoch/verify?hash=...
+pub async fn verify_snapshot_hash(
+    State(state): State<SnapshotAppState>,
+    Path(epoch): Path<u64>,
+    Query(query): Query<VerifyHashQuery>,
+) -> Result<Json<SnapshotHashVerification>, SnapshotError> {
+    let hash = query.hash.trim();
+    let algorithm = state.snapshot_service.hash_algorithm();
+    if hash.len() != algorithm.digest_len() * 2 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(SnapshotError::InvalidHash(query.hash, algorithm));
+    }
+
+    let verification = state
+        .snapshot_service
+        .verify_hash(epoch, hash)
+        .await
+        .map_err(|e| SnapshotError::GenerationError(e.to_string()))?;
+
+    Ok(Json(verification))
+}
+
+/// Check several epochs against the database and the contract at once
+///
+/// GET /api/snapshots/verify?epochs=3,4,5
+pub async fn verify_snapshot_epochs(
+    State(state): State<SnapshotAppState>,
+    Query(query): Query<BatchVerifyQuery>,
+) -> Result<Json<BatchVerifyResponse>, SnapshotError> {
+    let requested = query.epochs.split(',').map(str::trim);
+    let mut epochs = Vec::new();
+    for raw in requested.filter(|e| !e.is_empty()) {
+        let epoch = raw
+            .parse::<u64>()
+            .map_err(|_| SnapshotError::InvalidEpochs(format!("'{}' is not an epoch", raw)))?;
+        if !epochs.contains(&epoch) {
+            epochs.push(epoch);
+        }
+    }
+    if epochs.is_empty() || epochs.len() > MAX_BATCH_VERIFY_EPOCHS {
+        return Err(SnapshotError::InvalidEpochs(format!(
+            "expected between 1 and {} epochs",
+            MAX_BATCH_VERIFY_EPOCHS
+        )));
+    }
+
+    let results = state
+        .snapshot_service
+        .verify_epochs(&epochs)
+        .await
+        .map_err(|e| SnapshotError::GenerationError(e.to_string()))?;
+    let all_match = results.iter().all(|r| r.hash_match);
+
+    Ok(Json(BatchVerifyResponse { results, all_match }))
+}
+
+/// Header carrying the stored hash of a snapshot's canonical JSON
+pub const SNAPSHOT_HASH_HEADER: HeaderName = HeaderName::from_static("x-snapshot-hash");
+
+/// Header naming the algorithm that produced [`SNAPSHOT_HASH_HEADER`]
+pub const SNAPSHOT_HASH_ALGORITHM_HEADER: HeaderName =
+    HeaderName::from_static("x-snapshot-hash-algorithm");
+
+/// The exact canonical JSON bytes that were hashed for an epoch, so external
+/// verifiers can recompute the hash themselves
+///
+/// GET /api/snapshots/:epoch/canonical
+pub async fn canonical_snapshot_json(
+    State(state): State<SnapshotAppState>,
+    Path(epoch): Path<u64>,
+) -> Result<Response, SnapshotError> {
+    let stored = state
+        .snapshot_service
+        .canonical_json(epoch)
+        .await
+        .map_err(|e| SnapshotError::HashingError(e.to_string()))?
+        .ok_or(SnapshotError::NotFound(epoch))?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (SNAPSHOT_HASH_HEADER, stored.hash),
+            (
+                SNAPSHOT_HASH_ALGORITHM_HEADER,
+                stored.hash_algorithm.label().to_string(),
+            ),
+        ],
+        stored.canonical_json,
+    )
+        .into_response())
+}
+
+/// A snapshot wrapped in a self-describing envelope; the stored hash covers
+/// its `payload`
+///
+/// GET /api/snapshots/:epoch/export
+pub async fn export_snapshot(
+    State(state): State<SnapshotAppState>,
+    Path(epoch): Path<u64>,
+) -> Result<Response, SnapshotError> {
+    let stored = state
+        .snapshot_service
+        .canonical_json(epoch)
+        .await
+        .map_err(|e| SnapshotError::HashingError(e.to_string()))?
+        .ok_or(SnapshotError::NotFound(epoch))?;
+    let envelope = SnapshotEnvelope::wrap(&stored.canonical_json)
+        .map_err(|e| SnapshotError::HashingError(e.to_string()))?;
+
+    Ok((
+        [
+            (SNAPSHOT_HASH_HEADER, stored.hash),
+            (
+                SNAPSHOT_HASH_ALGORITHM_HEADER,
+                stored.hash_algorithm.label().to_string(),
+            ),
+        ],
+        Json(envelope),
+    )
+        .into_response())
+}
+
+/// Self-contained proof of an epoch's stored snapshot, signed when a proof
+/// signing key is configured
+///
+/// GET /api/snapshots/:epoch/proof
+pub async fn snapshot_proof_bundle(
+    State(state): State<SnapshotAppState>,
+    Path(epoch): Path<u64>,
+) -> Result<Json<ProofBundle>, SnapshotError> {
+    let exists = state
+        .snapshot_service
+        .snapshot_exists(epoch)
+        .await
+        .map_err(|e| SnapshotError::HashingError(e.to_string()))?;
+    if !exists {
+        return Err(SnapshotError::NotFound(epoch));
     }
+
+    let bundle = state
+        .snapshot_service
+        .export_proof_bundle(epoch)
+        .await
+        .map_err(|e| SnapshotError::HashingError(e.to_string()))?;
+    Ok(Json(bundle))
+}
+
+/// Public key that proof bundle signatures verify against
+#[derive(Debug, Serialize)]
+pub struct ProofKeyResponse {
+    pub algorithm: &'static str,
+    /// Hex-encoded ed25519 public key
+    pub public_key: String,
+}
+
+/// GET /api/snapshots/proof-key
+pub async fn snapshot_proof_key(
+    State(state): State<SnapshotAppState>,
+) -> Result<Json<ProofKeyResponse>, SnapshotError> {
+    let public_key = state.snapshot_service.proof_public_key().ok_or_else(|| {
+        SnapshotError::ConfigError("Proof bundle signing is not configured".to_string())
+    })?;
+
+    Ok(Json(ProofKeyResponse {
+        algorithm: "ed25519",
+        public_key: hex::encode(public_key.as_bytes()),
+    }))
+}
+
+/// Public snapshot routes
+pub fn routes(state: SnapshotAppState) -> Router {
+    Router::new()
+        .route("/api/snapshots", get(list_snapshots))
+        .route("/api/snapshots/recent", get(recent_snapshots))
+        .route("/api/snapshots/by-label/:label", get(snapshot_by_label))
+        .route("/api/snapshots/verify", get(verify_snapshot_epochs))
+        .route("/api/snapshots/:epoch/verify", get(verify_snapshot_hash))
+        .route(
+            "/api/snapshots/:epoch/canonical",
+            get(canonical_snapshot_json),
+        )
+        .route("/api/snapshots/:epoch/export", get(export_snapshot))
+        .route("/api/snapshots/:epoch/proof", get(snapshot_proof_bundle))
+        .route("/api/snapshots/proof-key", get(snapshot_proof_key))
+        .with_state(state)
+}
+
+/// Admin snapshot routes; callers must add the admin access layers
+pub fn admin_routes(state: SnapshotAppState) -> Router {
+    Router::new()
+        .route("/api/admin/snapshots", post(trigger_manual_snapshot))
+        .route(
+            "/api/admin/snapshots/submit-missing",
+            post(submit_missing_snapshots),
+        )
+        .route(
+            "/api/admin/snapshots/determinism-check",
+            get(check_snapshot_determinism),
+        )
+        .with_state(state)
 }
 
 /// Health check for contract service
@@ -139,6 +747,39 @@ pub enum SnapshotError {
     SubmissionError(String),
     ConnectionError(String),
     ConfigError(String),
+    EpochExists(u64),
+    SubmissionConflict(String),
+    InvalidHash(String, HashAlgorithm),
+    InvalidEpochs(String),
+    GenerationInProgress(u64),
+    NotFound(u64),
+    UnknownLabel(String),
+    DatabaseError(String),
+}
+
+impl SnapshotError {
+    /// Error for a failed `generate_and_submit_snapshot` call
+    fn from_generation(e: anyhow::Error) -> Self {
+        if let Some(running) = e.downcast_ref::<GenerationInProgress>() {
+            return SnapshotError::GenerationInProgress(running.epoch);
+        }
+        if let Some(not_started) = e.downcast_ref::<EpochNotStarted>() {
+            return SnapshotError::InvalidEpochs(not_started.to_string());
+        }
+        if let Some(exists) = e.downcast_ref::<SnapshotExists>() {
+            return SnapshotError::EpochExists(exists.epoch);
+        }
+        if let Some(conflict) = e.downcast_ref::<IdempotencyConflict>() {
+            return SnapshotError::SubmissionConflict(conflict.to_string());
+        }
+        SnapshotError::GenerationFailed(e.to_string())
+    }
+
+    /// Error for a failed snapshot lookup; the cause is logged, not returned
+    fn database(e: anyhow::Error) -> Self {
+        error!("Snapshot lookup failed: {:#}", e);
+        SnapshotError::DatabaseError("Failed to read stored snapshots".to_string())
+    }
 }
 
 impl IntoResponse for SnapshotError {
@@ -150,6 +791,40 @@ impl IntoResponse for SnapshotError {
             SnapshotError::SubmissionError(msg) => (StatusCode::BAD_GATEWAY, msg),
             SnapshotError::ConnectionError(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg),
             SnapshotError::ConfigError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
+            SnapshotError::EpochExists(epoch) => (
+                StatusCode::CONFLICT,
+                format!("A snapshot for epoch {} already exists", epoch),
+            ),
+            SnapshotError::SubmissionConflict(msg) => (StatusCode::CONFLICT, msg),
+            SnapshotError::InvalidHash(hash, algorithm) => (
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Invalid hash '{}': expected {} hex characters of {}",
+                    hash,
+                    algorithm.digest_len() * 2,
+                    algorithm.label()
+                ),
+            ),
+            SnapshotError::InvalidEpochs(reason) => (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid epochs: {}", reason),
+            ),
+            SnapshotError::GenerationInProgress(epoch) => (
+                StatusCode::CONFLICT,
+                format!(
+                    "Snapshot generation for epoch {} is already in progress",
+                    epoch
+                ),
+            ),
+            SnapshotError::NotFound(epoch) => (
+                StatusCode::NOT_FOUND,
+                format!("No snapshot stored for epoch {}", epoch),
+            ),
+            SnapshotError::UnknownLabel(label) => (
+                StatusCode::NOT_FOUND,
+                format!("No snapshot epoch is labelled '{}'", label),
+            ),
+            SnapshotError::DatabaseError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
         };
 
         (
@@ -1,5 +1,6 @@
 use crate::database::Database;
 use crate::ingestion::DataIngestionService;
+use crate::services::event_bus::EventBus;
 use crate::websocket::WsState;
 use std::sync::Arc;
 
@@ -9,6 +10,7 @@ pub struct AppState {
     pub db: Arc<Database>,
     pub ws_state: Arc<WsState>,
     pub ingestion: Arc<DataIngestionService>,
+    pub event_bus: Arc<EventBus>,
 }
 
 impl AppState {
@@ -16,11 +18,13 @@ impl AppState {
         db: Arc<Database>,
         ws_state: Arc<WsState>,
         ingestion: Arc<DataIngestionService>,
+        event_bus: Arc<EventBus>,
     ) -> Self {
         Self {
             db,
             ws_state,
             ingestion,
+            event_bus,
         }
     }
 }
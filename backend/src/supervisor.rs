@@ -0,0 +1,162 @@
+//! Restart supervision for background tasks.
+//!
+//! A supervised task is spawned from a factory so that it can be spawned
+//! again if it panics. Restarts back off exponentially and stop once
+//! shutdown is signalled; a task that returns normally is not restarted.
+
+use std::any::Any;
+use std::future::Future;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+use crate::observability::metrics as obs_metrics;
+
+/// Backoff between restarts of a panicking task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RestartPolicy {
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(300),
+        }
+    }
+}
+
+impl RestartPolicy {
+    pub fn with_initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    pub fn with_max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Delay before the restart following `consecutive_panics` earlier ones
+    pub fn backoff(&self, consecutive_panics: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(consecutive_panics))
+            .min(self.max_backoff)
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+/// Spawn `make_task` and respawn it whenever it panics.
+///
+/// Each run is handed its own shutdown receiver. A run that lasted longer
+/// than `max_backoff` resets the backoff, so a task that panics rarely is
+/// restarted quickly.
+pub fn supervise<F, Fut>(
+    name: &'static str,
+    policy: RestartPolicy,
+    mut shutdown_rx: broadcast::Receiver<()>,
+    mut make_task: F,
+) -> JoinHandle<()>
+where
+    F: FnMut(broadcast::Receiver<()>) -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut consecutive_panics = 0;
+        loop {
+            let started = Instant::now();
+            let error = match tokio::spawn(make_task(shutdown_rx.resubscribe())).await {
+                Ok(()) => return,
+                Err(e) if e.is_panic() => e.into_panic(),
+                Err(_) => return,
+            };
+
+            if started.elapsed() > policy.max_backoff {
+                consecutive_panics = 0;
+            }
+            let delay = policy.backoff(consecutive_panics);
+            consecutive_panics = consecutive_panics.saturating_add(1);
+            obs_metrics::record_task_restart(name);
+            tracing::error!(
+                task = name,
+                restart_in_ms = delay.as_millis() as u64,
+                "Background task panicked: {}; restarting",
+                panic_message(error.as_ref())
+            );
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = shutdown_rx.recv() => {
+                    tracing::info!(task = name, "Shutdown during restart backoff");
+                    return;
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RestartPolicy::default()
+            .with_initial_backoff(Duration::from_secs(1))
+            .with_max_backoff(Duration::from_secs(10));
+        assert_eq!(policy.backoff(0), Duration::from_secs(1));
+        assert_eq!(policy.backoff(1), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(8));
+        assert_eq!(policy.backoff(4), Duration::from_secs(10));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_panicking_task_is_restarted() {
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+        let (ran_tx, mut ran_rx) = mpsc::unbounded_channel();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let policy = RestartPolicy::default().with_initial_backoff(Duration::from_millis(10));
+
+        let runs_clone = Arc::clone(&runs);
+        let supervisor = supervise("flaky", policy, shutdown_rx, move |mut shutdown_rx| {
+            let run = runs_clone.fetch_add(1, Ordering::SeqCst);
+            let ran_tx = ran_tx.clone();
+            async move {
+                if run == 0 {
+                    panic!("first run fails");
+                }
+                ran_tx.send(run).unwrap();
+                let _ = shutdown_rx.recv().await;
+            }
+        });
+
+        let run = tokio::time::timeout(Duration::from_secs(5), ran_rx.recv())
+            .await
+            .expect("task was not restarted");
+        assert_eq!(run, Some(1));
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        // A task that exits on shutdown is not restarted
+        shutdown_tx.send(()).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), supervisor)
+            .await
+            .expect("supervisor did not stop")
+            .unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,247 @@
+//! Status of the long-running background tasks.
+//!
+//! Each task registers itself with the shared [`TaskRegistry`] and reports
+//! every run. Periodic tasks are unhealthy once they have missed
+//! `MISSED_TICKS_BEFORE_UNHEALTHY` of their expected ticks; every task is
+//! unhealthy once it has stopped.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Expected ticks a periodic task may miss before it counts as stale
+pub const MISSED_TICKS_BEFORE_UNHEALTHY: u32 = 2;
+
+#[derive(Debug, Clone)]
+struct TaskEntry {
+    expected_interval: Option<Duration>,
+    registered_at: DateTime<Utc>,
+    last_run: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+    last_error_at: Option<DateTime<Utc>>,
+    stopped_at: Option<DateTime<Utc>>,
+}
+
+/// Status of one background task
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaskStatus {
+    pub name: String,
+    pub healthy: bool,
+    /// Why the task is unhealthy, if it is
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// How often the task should tick; `None` for event-driven tasks
+    pub expected_interval_secs: Option<u64>,
+    pub last_run: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+}
+
+/// Shared record of background task runs, errors and liveness
+#[derive(Debug, Default)]
+pub struct TaskRegistry {
+    tasks: Mutex<BTreeMap<String, TaskEntry>>,
+}
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a task ticking every `expected_interval`, or an event-driven
+    /// task when `None`. Registering again resets the task's status.
+    pub fn register(
+        self: &Arc<Self>,
+        name: &str,
+        expected_interval: Option<Duration>,
+    ) -> TaskHandle {
+        self.register_at(name, expected_interval, Utc::now())
+    }
+
+    fn register_at(
+        self: &Arc<Self>,
+        name: &str,
+        expected_interval: Option<Duration>,
+        now: DateTime<Utc>,
+    ) -> TaskHandle {
+        self.tasks.lock().unwrap().insert(
+            name.to_string(),
+            TaskEntry {
+                expected_interval,
+                registered_at: now,
+                last_run: None,
+                last_error: None,
+                last_error_at: None,
+                stopped_at: None,
+            },
+        );
+        TaskHandle {
+            registry: Arc::clone(self),
+            name: name.to_string(),
+        }
+    }
+
+    fn update(&self, name: &str, apply: impl FnOnce(&mut TaskEntry)) {
+        if let Some(entry) = self.tasks.lock().unwrap().get_mut(name) {
+            apply(entry);
+        }
+    }
+
+    /// Status of every registered task, by name
+    pub fn statuses(&self) -> Vec<TaskStatus> {
+        self.statuses_at(Utc::now())
+    }
+
+    /// Status of every registered task as of `now`
+    pub fn statuses_at(&self, now: DateTime<Utc>) -> Vec<TaskStatus> {
+        self.tasks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, entry)| {
+                let reason = unhealthy_reason(entry, now);
+                TaskStatus {
+                    name: name.clone(),
+                    healthy: reason.is_none(),
+                    reason,
+                    expected_interval_secs: entry.expected_interval.map(|i| i.as_secs()),
+                    last_run: entry.last_run,
+                    last_error: entry.last_error.clone(),
+                    last_error_at: entry.last_error_at,
+                }
+            })
+            .collect()
+    }
+}
+
+fn unhealthy_reason(entry: &TaskEntry, now: DateTime<Utc>) -> Option<String> {
+    if let Some(stopped_at) = entry.stopped_at {
+        return Some(format!("stopped at {}", stopped_at.to_rfc3339()));
+    }
+    let interval = entry.expected_interval?;
+    let since = entry.last_run.unwrap_or(entry.registered_at);
+    let elapsed = (now - since).to_std().unwrap_or_default();
+    (elapsed > interval * MISSED_TICKS_BEFORE_UNHEALTHY).then(|| {
+        format!(
+            "no run for {}s, expected every {}s",
+            elapsed.as_secs(),
+            interval.as_secs()
+        )
+    })
+}
+
+/// Handle a task uses to report its runs
+#[derive(Debug, Clone)]
+pub struct TaskHandle {
+    registry: Arc<TaskRegistry>,
+    name: String,
+}
+
+impl TaskHandle {
+    /// Record a completed run
+    pub fn success(&self) {
+        self.registry.update(&self.name, |entry| {
+            entry.last_run = Some(Utc::now());
+        });
+    }
+
+    /// Record a run that failed with `error`
+    pub fn error(&self, error: impl std::fmt::Display) {
+        let now = Utc::now();
+        self.registry.update(&self.name, |entry| {
+            entry.last_run = Some(now);
+            entry.last_error = Some(error.to_string());
+            entry.last_error_at = Some(now);
+        });
+    }
+
+    /// Record a run, failed or not
+    pub fn record<T, E: std::fmt::Display>(&self, result: &Result<T, E>) {
+        match result {
+            Ok(_) => self.success(),
+            Err(e) => self.error(e),
+        }
+    }
+
+    /// Record that the task has exited
+    pub fn stopped(&self) {
+        self.registry.update(&self.name, |entry| {
+            entry.stopped_at = Some(Utc::now());
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status<'a>(statuses: &'a [TaskStatus], name: &str) -> &'a TaskStatus {
+        statuses.iter().find(|s| s.name == name).unwrap()
+    }
+
+    #[test]
+    fn test_stale_periodic_task_is_unhealthy() {
+        let registry = Arc::new(TaskRegistry::new());
+        let start = Utc::now();
+        let interval = Duration::from_secs(60);
+        registry.register_at("fresh", Some(interval), start);
+        registry.register_at("stale", Some(interval), start);
+        registry.register_at("never_ran", Some(interval), start);
+        registry.register_at("events", None, start);
+
+        let later = start + chrono::Duration::seconds(150);
+        registry.update("fresh", |e| e.last_run = Some(later));
+        registry.update("stale", |e| {
+            e.last_run = Some(start + chrono::Duration::seconds(10))
+        });
+
+        let statuses = registry.statuses_at(later + chrono::Duration::seconds(5));
+        assert!(status(&statuses, "fresh").healthy);
+        assert!(status(&statuses, "events").healthy);
+
+        let stale = status(&statuses, "stale");
+        assert!(!stale.healthy);
+        assert_eq!(
+            stale.reason.as_deref(),
+            Some("no run for 145s, expected every 60s")
+        );
+        assert!(!status(&statuses, "never_ran").healthy);
+    }
+
+    #[test]
+    fn test_errors_and_exits_are_reported() {
+        let registry = Arc::new(TaskRegistry::new());
+        let periodic = registry.register("periodic", Some(Duration::from_secs(60)));
+        let consumer = registry.register("consumer", None);
+
+        periodic.record(&Err::<(), _>("rpc unavailable"));
+        consumer.stopped();
+
+        let statuses = registry.statuses();
+        let periodic_status = status(&statuses, "periodic");
+        assert!(periodic_status.healthy);
+        assert_eq!(
+            periodic_status.last_error.as_deref(),
+            Some("rpc unavailable")
+        );
+        assert!(periodic_status.last_run.is_some());
+
+        let consumer_status = status(&statuses, "consumer");
+        assert!(!consumer_status.healthy);
+        assert!(consumer_status
+            .reason
+            .as_ref()
+            .unwrap()
+            .starts_with("stopped"));
+
+        // A later success keeps the last error for inspection
+        periodic.success();
+        let statuses = registry.statuses();
+        assert_eq!(
+            status(&statuses, "periodic").last_error.as_deref(),
+            Some("rpc unavailable")
+        );
+    }
+}
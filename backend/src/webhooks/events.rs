@@ -44,6 +44,17 @@ pub struct CorridorLiquidityDroppedEvent {
     pub severity: String,        // "warning" | "critical"
 }
 
+/// RPC Connectivity Event, sent when an endpoint group's circuit breaker
+/// opens and again when it recovers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcHealthEvent {
+    pub endpoint: String,
+    /// Endpoint group whose breaker changed: `core`, `accounts` or `markets`
+    pub group: String,
+    pub status: String, // "degraded" | "recovered"
+    pub timestamp: String,
+}
+
 /// Corridor Metrics snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CorridorMetrics {
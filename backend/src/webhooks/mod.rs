@@ -4,12 +4,83 @@ pub mod events;
 
 use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use sha2::Sha256;
 use sqlx::SqlitePool;
+use tracing::warn;
 use uuid::Uuid;
 
 type HmacSha256 = Hmac<Sha256>;
 
+/// Active webhooks a user may have by default
+pub const DEFAULT_MAX_ACTIVE_WEBHOOKS_PER_USER: i64 = 10;
+
+/// Largest event payload queued for delivery by default
+pub const DEFAULT_MAX_WEBHOOK_PAYLOAD_BYTES: usize = 256 * 1024;
+
+/// What happens to an event payload over the size limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OversizedPayloadPolicy {
+    /// Drop the largest top-level fields and mark the payload as truncated
+    #[default]
+    Truncate,
+    /// Refuse to queue the event
+    Reject,
+}
+
+impl OversizedPayloadPolicy {
+    /// `truncate` or `reject`, as set in `WEBHOOK_OVERSIZED_PAYLOAD_POLICY`
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "truncate" => Some(Self::Truncate),
+            "reject" => Some(Self::Reject),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for OversizedPayloadPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| format!("unknown oversized payload policy '{}'", s))
+    }
+}
+
+/// Limits `WebhookService` enforces on registrations and queued payloads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebhookLimits {
+    /// Active webhooks a user may have; deleted webhooks do not count
+    pub max_active_per_user: i64,
+    /// Largest event payload queued for delivery, in bytes
+    pub max_payload_bytes: usize,
+    /// What happens to a payload over `max_payload_bytes`
+    pub oversized_payload_policy: OversizedPayloadPolicy,
+}
+
+impl Default for WebhookLimits {
+    fn default() -> Self {
+        Self {
+            max_active_per_user: DEFAULT_MAX_ACTIVE_WEBHOOKS_PER_USER,
+            max_payload_bytes: DEFAULT_MAX_WEBHOOK_PAYLOAD_BYTES,
+            oversized_payload_policy: OversizedPayloadPolicy::default(),
+        }
+    }
+}
+
+/// Key of the marker added to truncated payloads
+pub const TRUNCATION_MARKER_KEY: &str = "_truncated";
+
+/// Request refused by `WebhookService`; returned wrapped in `anyhow::Error`
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum WebhookError {
+    #[error("Active webhook limit of {limit} reached; delete a webhook to register another")]
+    LimitExceeded { limit: i64 },
+
+    #[error("Webhook payload of {size} bytes exceeds the limit of {limit} bytes")]
+    PayloadTooLarge { size: usize, limit: usize },
+}
+
 /// Webhook signature - for verifying webhook requests
 pub struct WebhookSignature;
 
@@ -78,6 +149,9 @@ pub enum WebhookEventType {
     AnchorStatusChanged,
     PaymentCreated,
     CorridorLiquidityDropped,
+    RpcDegraded,
+    RpcRecovered,
+    PriceStale,
 }
 
 impl WebhookEventType {
@@ -87,6 +161,9 @@ impl WebhookEventType {
             Self::AnchorStatusChanged => "anchor.status_changed",
             Self::PaymentCreated => "payment.created",
             Self::CorridorLiquidityDropped => "corridor.liquidity_dropped",
+            Self::RpcDegraded => "rpc.degraded",
+            Self::RpcRecovered => "rpc.recovered",
+            Self::PriceStale => "price.stale",
         }
     }
 
@@ -96,6 +173,9 @@ impl WebhookEventType {
             "anchor.status_changed" => Some(Self::AnchorStatusChanged),
             "payment.created" => Some(Self::PaymentCreated),
             "corridor.liquidity_dropped" => Some(Self::CorridorLiquidityDropped),
+            "rpc.degraded" => Some(Self::RpcDegraded),
+            "rpc.recovered" => Some(Self::RpcRecovered),
+            "price.stale" => Some(Self::PriceStale),
             _ => None,
         }
     }
@@ -105,6 +185,9 @@ impl WebhookEventType {
 pub struct WebhookService {
     db: SqlitePool,
     encryption_key: String,
+    max_active_per_user: i64,
+    max_payload_bytes: usize,
+    oversized_payload_policy: OversizedPayloadPolicy,
 }
 
 impl WebhookService {
@@ -112,10 +195,38 @@ impl WebhookService {
         let encryption_key = std::env::var("ENCRYPTION_KEY").unwrap_or_else(|_| {
             "0000000000000000000000000000000000000000000000000000000000000000".to_string()
         });
-        Self { db, encryption_key }
+        Self {
+            db,
+            encryption_key,
+            max_active_per_user: DEFAULT_MAX_ACTIVE_WEBHOOKS_PER_USER,
+            max_payload_bytes: DEFAULT_MAX_WEBHOOK_PAYLOAD_BYTES,
+            oversized_payload_policy: OversizedPayloadPolicy::default(),
+        }
+    }
+
+    /// Enforce `limits` instead of the defaults
+    pub fn with_limits(self, limits: WebhookLimits) -> Self {
+        self.with_max_active_per_user(limits.max_active_per_user)
+            .with_payload_limit(limits.max_payload_bytes, limits.oversized_payload_policy)
+    }
+
+    /// Override the number of active webhooks a user may have
+    pub fn with_max_active_per_user(mut self, limit: i64) -> Self {
+        self.max_active_per_user = limit;
+        self
+    }
+
+    /// Override the event payload size limit and what happens past it
+    pub fn with_payload_limit(mut self, max_bytes: usize, policy: OversizedPayloadPolicy) -> Self {
+        self.max_payload_bytes = max_bytes;
+        self.oversized_payload_policy = policy;
+        self
     }
 
-    /// Register a new webhook
+    /// Register a new webhook.
+    ///
+    /// Fails with `WebhookError::LimitExceeded` when the user already has the
+    /// maximum number of active webhooks; deleted webhooks do not count.
     pub async fn register_webhook(
         &self,
         user_id: &str,
@@ -130,10 +241,13 @@ impl WebhookService {
         let encrypted_secret = crate::crypto::encrypt_data(&secret, &self.encryption_key)
             .unwrap_or_else(|_| secret.clone());
 
-        sqlx::query(
+        // Count and insert in one statement so concurrent registrations
+        // cannot overshoot the limit
+        let result = sqlx::query(
             r#"
             INSERT INTO webhooks (id, user_id, url, event_types, filters, secret, is_active, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            SELECT ?, ?, ?, ?, ?, ?, ?, ?
+            WHERE (SELECT COUNT(*) FROM webhooks WHERE user_id = ? AND is_active = 1) < ?
             "#,
         )
         .bind(&id)
@@ -144,9 +258,18 @@ impl WebhookService {
         .bind(&encrypted_secret)
         .bind(true)
         .bind(&now)
+        .bind(user_id)
+        .bind(self.max_active_per_user)
         .execute(&self.db)
         .await?;
 
+        if result.rows_affected() == 0 {
+            return Err(WebhookError::LimitExceeded {
+                limit: self.max_active_per_user,
+            }
+            .into());
+        }
+
         Ok(WebhookResponse {
             id,
             url: request.url,
@@ -191,6 +314,23 @@ impl WebhookService {
         Ok(webhooks)
     }
 
+    /// List active webhooks subscribed to an event type
+    pub async fn list_active_webhooks_for_event(
+        &self,
+        event_type: &str,
+    ) -> anyhow::Result<Vec<Webhook>> {
+        let webhooks = sqlx::query_as::<_, Webhook>(
+            "SELECT id, user_id, url, event_types, filters, secret, is_active, created_at, last_fired_at FROM webhooks WHERE is_active = 1"
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        Ok(webhooks
+            .into_iter()
+            .filter(|w| w.event_types.split(',').any(|t| t.trim() == event_type))
+            .collect())
+    }
+
     /// Delete/deactivate webhook
     pub async fn delete_webhook(&self, webhook_id: &str, user_id: &str) -> anyhow::Result<bool> {
         let result = sqlx::query("UPDATE webhooks SET is_active = 0 WHERE id = ? AND user_id = ?")
@@ -202,7 +342,11 @@ impl WebhookService {
         Ok(result.rows_affected() > 0)
     }
 
-    /// Record webhook event for delivery
+    /// Record webhook event for delivery.
+    ///
+    /// Payloads over the size limit are truncated or, under
+    /// `OversizedPayloadPolicy::Reject`, refused with
+    /// `WebhookError::PayloadTooLarge`.
     pub async fn create_webhook_event(
         &self,
         webhook_id: &str,
@@ -210,7 +354,7 @@ impl WebhookService {
         payload: serde_json::Value,
     ) -> anyhow::Result<String> {
         let id = Uuid::new_v4().to_string();
-        let payload_str = payload.to_string();
+        let payload_str = self.limit_payload(event_type, payload)?;
         let now = chrono::Utc::now().to_rfc3339();
 
         sqlx::query(
@@ -230,7 +374,31 @@ impl WebhookService {
         Ok(id)
     }
 
-    /// Get pending webhook events
+    /// Serialized payload within the size limit
+    fn limit_payload(&self, event_type: &str, payload: Value) -> Result<String, WebhookError> {
+        let payload_str = payload.to_string();
+        let size = payload_str.len();
+        if size <= self.max_payload_bytes {
+            return Ok(payload_str);
+        }
+
+        let limit = self.max_payload_bytes;
+        if self.oversized_payload_policy == OversizedPayloadPolicy::Reject {
+            warn!(
+                "Rejecting {} webhook payload of {} bytes (limit {})",
+                event_type, size, limit
+            );
+            return Err(WebhookError::PayloadTooLarge { size, limit });
+        }
+
+        warn!(
+            "Truncating {} webhook payload of {} bytes (limit {})",
+            event_type, size, limit
+        );
+        Ok(truncate_payload(payload, size, limit).to_string())
+    }
+
+    /// Get pending webhook events, oldest first
     pub async fn get_pending_events(
         &self,
         limit: usize,
@@ -241,7 +409,7 @@ impl WebhookService {
             "SELECT we.id, we.webhook_id, we.event_type, we.payload
              FROM webhook_events we
              WHERE we.status = 'pending' AND we.retries < 3
-             ORDER BY we.created_at ASC
+             ORDER BY we.created_at ASC, we.rowid ASC
              LIMIT ?",
         )
         .bind(query_limit)
@@ -298,6 +466,43 @@ impl WebhookService {
     }
 }
 
+/// Drop the largest top-level fields of `payload` until it fits in `limit`
+/// bytes, recording the original size and the dropped fields under
+/// `TRUNCATION_MARKER_KEY`. Payloads that are not objects, or that do not fit
+/// even without their fields, are replaced by the marker alone.
+fn truncate_payload(payload: Value, original_size: usize, limit: usize) -> Value {
+    let marker = |omitted: &[String]| {
+        json!({
+            "original_size_bytes": original_size,
+            "max_size_bytes": limit,
+            "omitted_fields": omitted,
+        })
+    };
+
+    let Value::Object(mut fields) = payload else {
+        return json!({ TRUNCATION_MARKER_KEY: marker(&[]) });
+    };
+
+    let mut by_size: Vec<(String, usize)> = fields
+        .iter()
+        .map(|(key, value)| (key.clone(), value.to_string().len()))
+        .collect();
+    by_size.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut omitted = Vec::new();
+    for (key, _) in by_size {
+        fields.remove(&key);
+        omitted.push(key);
+        fields.insert(TRUNCATION_MARKER_KEY.to_string(), marker(&omitted));
+        let size = serde_json::to_vec(&fields).map_or(usize::MAX, |bytes| bytes.len());
+        if size <= limit {
+            return Value::Object(fields);
+        }
+    }
+
+    json!({ TRUNCATION_MARKER_KEY: marker(&omitted) })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -320,4 +525,138 @@ mod tests {
             Some(WebhookEventType::CorridorHealthDegraded)
         );
     }
+
+    fn request() -> CreateWebhookRequest {
+        CreateWebhookRequest {
+            url: "https://example.com/hook".to_string(),
+            event_types: vec!["anchor.status_changed".to_string()],
+            filters: None,
+        }
+    }
+
+    #[sqlx::test]
+    async fn test_active_webhook_limit(pool: SqlitePool) {
+        for user in ["user-1", "user-2"] {
+            sqlx::query("INSERT INTO users (id, username) VALUES (?, ?)")
+                .bind(user)
+                .bind(user)
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+        let service = WebhookService::new(pool).with_max_active_per_user(2);
+
+        let first = service.register_webhook("user-1", request()).await.unwrap();
+        service.register_webhook("user-1", request()).await.unwrap();
+
+        let err = service
+            .register_webhook("user-1", request())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<WebhookError>(),
+            Some(&WebhookError::LimitExceeded { limit: 2 })
+        );
+        assert_eq!(service.list_webhooks("user-1").await.unwrap().len(), 2);
+
+        // The limit is per user
+        service.register_webhook("user-2", request()).await.unwrap();
+
+        // Deleting a webhook frees its slot
+        assert!(service.delete_webhook(&first.id, "user-1").await.unwrap());
+        service.register_webhook("user-1", request()).await.unwrap();
+        assert_eq!(service.list_webhooks("user-1").await.unwrap().len(), 2);
+    }
+
+    async fn insert_webhook(pool: &SqlitePool) -> (WebhookService, String) {
+        sqlx::query("INSERT INTO users (id, username) VALUES ('user-1', 'user-1')")
+            .execute(pool)
+            .await
+            .unwrap();
+        let service = WebhookService::new(pool.clone());
+        let webhook = service.register_webhook("user-1", request()).await.unwrap();
+        (service, webhook.id)
+    }
+
+    fn oversized_payload() -> Value {
+        json!({
+            "anchor_id": "anchor-1",
+            "status": "red",
+            "history": "x".repeat(2_000),
+        })
+    }
+
+    #[sqlx::test]
+    async fn test_payload_under_limit_is_stored_intact(pool: SqlitePool) {
+        let (service, webhook_id) = insert_webhook(&pool).await;
+        let service = service.with_payload_limit(1_024, OversizedPayloadPolicy::Reject);
+
+        let payload = json!({ "anchor_id": "anchor-1", "history": "x".repeat(500) });
+        service
+            .create_webhook_event(&webhook_id, "anchor.status_changed", payload.clone())
+            .await
+            .unwrap();
+
+        let events = service.get_pending_events(10).await.unwrap();
+        let stored: Value = serde_json::from_str(&events[0].3).unwrap();
+        assert_eq!(stored, payload);
+    }
+
+    #[sqlx::test]
+    async fn test_payload_over_limit_is_truncated(pool: SqlitePool) {
+        let (service, webhook_id) = insert_webhook(&pool).await;
+        let service = service.with_payload_limit(1_024, OversizedPayloadPolicy::Truncate);
+
+        service
+            .create_webhook_event(&webhook_id, "anchor.status_changed", oversized_payload())
+            .await
+            .unwrap();
+
+        let events = service.get_pending_events(10).await.unwrap();
+        let stored_str = &events[0].3;
+        assert!(stored_str.len() <= 1_024);
+
+        // Only the field that made it too large is dropped
+        let stored: Value = serde_json::from_str(stored_str).unwrap();
+        assert_eq!(stored["anchor_id"], "anchor-1");
+        assert_eq!(stored["status"], "red");
+        assert!(stored.get("history").is_none());
+        let marker = &stored[TRUNCATION_MARKER_KEY];
+        assert_eq!(
+            marker["original_size_bytes"],
+            oversized_payload().to_string().len()
+        );
+        assert_eq!(marker["max_size_bytes"], 1_024);
+        assert_eq!(marker["omitted_fields"], json!(["history"]));
+
+        // A limit too small for any field leaves the marker alone
+        let truncated = truncate_payload(oversized_payload(), 2_060, 10);
+        assert_eq!(
+            truncated[TRUNCATION_MARKER_KEY]["omitted_fields"]
+                .as_array()
+                .unwrap()
+                .len(),
+            3
+        );
+        assert_eq!(truncated.as_object().unwrap().len(), 1);
+    }
+
+    #[sqlx::test]
+    async fn test_payload_over_limit_is_rejected(pool: SqlitePool) {
+        let (service, webhook_id) = insert_webhook(&pool).await;
+        let service = service.with_payload_limit(1_024, OversizedPayloadPolicy::Reject);
+
+        let err = service
+            .create_webhook_event(&webhook_id, "anchor.status_changed", oversized_payload())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<WebhookError>(),
+            Some(&WebhookError::PayloadTooLarge {
+                size: oversized_payload().to_string().len(),
+                limit: 1_024,
+            })
+        );
+        assert!(service.get_pending_events(10).await.unwrap().is_empty());
+    }
 }
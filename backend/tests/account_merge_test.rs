@@ -2,9 +2,16 @@ use sqlx::SqlitePool;
 use std::sync::Arc;
 
 use axum::body::{to_bytes, Body};
+use axum::extract::Path;
 use axum::http::{Request, StatusCode};
-use stellar_insights_backend::rpc::StellarRpcClient;
-use stellar_insights_backend::services::account_merge_detector::AccountMergeDetector;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::{TimeZone, Utc};
+use serde_json::{json, Value};
+use stellar_insights_backend::rpc::{HorizonOperation, StellarRpcClient};
+use stellar_insights_backend::services::account_merge_detector::{
+    AccountMergeDetector, AccountMergeEvent,
+};
 use tower::util::ServiceExt;
 
 #[sqlx::test]
@@ -124,3 +131,244 @@ async fn test_account_merge_routes(pool: SqlitePool) {
     let destinations: Vec<serde_json::Value> = serde_json::from_slice(&destinations_body).unwrap();
     assert_eq!(destinations.len(), 2);
 }
+
+const CHURNED: &str = "GCHURNAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+const FUNDER: &str = "GFUNDERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+fn operation(id: &str, operation_type: &str, account: &str) -> HorizonOperation {
+    HorizonOperation {
+        id: id.to_string(),
+        paging_token: id.to_string(),
+        transaction_hash: format!("tx_{}", id),
+        source_account: FUNDER.to_string(),
+        operation_type: operation_type.to_string(),
+        created_at: "2026-01-23T09:00:00Z".to_string(),
+        account: Some(account.to_string()),
+        into: None,
+        amount: None,
+        funder: Some(FUNDER.to_string()),
+        transaction_successful: None,
+    }
+}
+
+async fn insert_ledger(pool: &SqlitePool, sequence: i64) {
+    sqlx::query(
+        "INSERT INTO ledgers (sequence, hash, close_time, transaction_count, operation_count) VALUES ($1, $2, '2026-01-23T09:00:00Z', 0, 0)",
+    )
+    .bind(sequence)
+    .bind(format!("ledger_hash_{}", sequence))
+    .execute(pool)
+    .await
+    .expect("failed to insert ledger row");
+}
+
+async fn insert_merge(pool: &SqlitePool, operation_id: &str, ledger_sequence: i64) {
+    let event = AccountMergeEvent {
+        operation_id: operation_id.to_string(),
+        transaction_hash: format!("tx_{}", operation_id),
+        ledger_sequence,
+        source_account: CHURNED.to_string(),
+        destination_account: FUNDER.to_string(),
+        merged_balance: 10.0,
+        created_at: Utc.with_ymd_and_hms(2026, 1, 22, 9, 0, 0).unwrap(),
+    };
+    assert!(AccountMergeDetector::insert_merge_event(pool, &event)
+        .await
+        .unwrap());
+}
+
+#[sqlx::test]
+async fn test_merge_then_recreate_is_flagged_as_churn(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let detector = Arc::new(AccountMergeDetector::new(pool.clone(), rpc_client));
+    for sequence in [300, 301] {
+        insert_ledger(&pool, sequence).await;
+    }
+
+    insert_merge(&pool, "300_1", 300).await;
+
+    // Ledger 301 re-creates the merged account and opens an unrelated one
+    let operations = vec![
+        operation("301_1", "create_account", CHURNED),
+        operation(
+            "301_2",
+            "create_account",
+            "GFRESHAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+        ),
+        operation("301_3", "payment", CHURNED),
+    ];
+    let creations = AccountMergeDetector::account_creations(301, &operations);
+    assert_eq!(creations.len(), 2);
+
+    let mut flagged = Vec::new();
+    for creation in &creations {
+        flagged.push(
+            AccountMergeDetector::record_churn(&pool, creation)
+                .await
+                .unwrap(),
+        );
+    }
+    assert_eq!(flagged, vec![true, false]);
+
+    // Recording the same creation again adds nothing
+    assert!(!AccountMergeDetector::record_churn(&pool, &creations[0])
+        .await
+        .unwrap());
+
+    let churn = detector.get_recent_churn(10).await.unwrap();
+    assert_eq!(churn.len(), 1);
+    assert_eq!(churn[0].account, CHURNED);
+    assert_eq!(churn[0].merge_operation_id, "300_1");
+    assert_eq!(churn[0].merge_ledger_sequence, 300);
+    assert_eq!(churn[0].merged_into, FUNDER);
+    assert_eq!(churn[0].recreate_operation_id, "301_1");
+    assert_eq!(churn[0].recreate_ledger_sequence, 301);
+    assert_eq!(churn[0].funder.as_deref(), Some(FUNDER));
+
+    let app = stellar_insights_backend::api::account_merges::routes(detector);
+    let res = app
+        .oneshot(
+            Request::builder()
+                .uri("/churn?limit=5")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let churn: Vec<serde_json::Value> = serde_json::from_slice(&body).unwrap();
+    assert_eq!(churn.len(), 1);
+    assert_eq!(churn[0]["account"], CHURNED);
+}
+
+#[sqlx::test]
+async fn test_creation_before_merge_is_not_churn(pool: SqlitePool) {
+    for sequence in [300, 301] {
+        insert_ledger(&pool, sequence).await;
+    }
+
+    // Created in ledger 300 and merged afterwards: no re-creation happened
+    insert_merge(&pool, "301_1", 301).await;
+    let creations = AccountMergeDetector::account_creations(
+        300,
+        &[operation("300_1", "create_account", CHURNED)],
+    );
+    assert!(!AccountMergeDetector::record_churn(&pool, &creations[0])
+        .await
+        .unwrap());
+
+    // Merged and re-created within the same ledger, in that order
+    insert_merge(&pool, "300_0", 300).await;
+    let creations = AccountMergeDetector::account_creations(
+        300,
+        &[operation("300_2", "create_account", CHURNED)],
+    );
+    assert!(AccountMergeDetector::record_churn(&pool, &creations[0])
+        .await
+        .unwrap());
+}
+
+fn merge_record(id: &str, transaction_hash: &str, successful: Option<bool>) -> Value {
+    let mut record = json!({
+        "id": id,
+        "paging_token": id,
+        "transaction_hash": transaction_hash,
+        "source_account": CHURNED,
+        "type": "account_merge",
+        "created_at": "2026-01-24T09:00:00Z",
+        "account": CHURNED,
+        "into": FUNDER
+    });
+    if let Some(successful) = successful {
+        record["transaction_successful"] = json!(successful);
+    }
+    record
+}
+
+fn transaction_record(hash: &str, successful: bool) -> Value {
+    json!({
+        "id": hash,
+        "hash": hash,
+        "ledger": 400,
+        "created_at": "2026-01-24T09:00:00Z",
+        "source_account": CHURNED,
+        "operation_count": 1,
+        "successful": successful,
+        "paging_token": hash
+    })
+}
+
+/// Horizon serving ledger 400: one merge in a successful transaction, one in
+/// a transaction flagged as failed, and one (plus an account creation) in a
+/// transaction only the ledger's transaction list shows as failed
+async fn horizon_with_failed_merges() -> StellarRpcClient {
+    let app = Router::new()
+        .route(
+            "/ledgers/:sequence/operations",
+            get(|| async {
+                let mut creation = merge_record("400_4", "tx_failed", None);
+                creation["type"] = json!("create_account");
+                creation["funder"] = json!(FUNDER);
+                Json(json!({ "_embedded": { "records": [
+                    merge_record("400_1", "tx_ok", None),
+                    merge_record("400_2", "tx_flagged", Some(false)),
+                    merge_record("400_3", "tx_failed", None),
+                    creation,
+                ] } }))
+            }),
+        )
+        .route(
+            "/ledgers/:sequence/transactions",
+            get(|| async {
+                Json(json!({ "_embedded": { "records": [
+                    transaction_record("tx_ok", true),
+                    transaction_record("tx_flagged", false),
+                    transaction_record("tx_failed", false),
+                ] } }))
+            }),
+        )
+        .route(
+            "/operations/:id/effects",
+            get(|Path(id): Path<String>| async move {
+                Json(json!({ "_embedded": { "records": [{
+                    "id": format!("{}-1", id),
+                    "type": "account_credited",
+                    "account": FUNDER,
+                    "amount": "25.0000000",
+                    "asset_type": "native"
+                }] } }))
+            }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    StellarRpcClient::new(
+        "http://127.0.0.1:1/rpc".to_string(),
+        format!("http://{}", addr),
+        false,
+    )
+}
+
+#[sqlx::test]
+async fn test_merges_in_failed_transactions_are_excluded(pool: SqlitePool) {
+    let rpc_client = Arc::new(horizon_with_failed_merges().await);
+    let detector = AccountMergeDetector::new(pool.clone(), rpc_client);
+    insert_ledger(&pool, 400).await;
+
+    let activity = detector.detect_ledger_activity(400).await.unwrap();
+    assert!(activity.creations.is_empty());
+
+    let detected = detector.process_ledger_operations(400).await.unwrap();
+    assert_eq!(detected, 1);
+
+    let recent = detector.get_recent_merges(10).await.unwrap();
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].operation_id, "400_1");
+    assert_eq!(recent[0].transaction_hash, "tx_ok");
+
+    let stats = detector.get_merge_stats().await.unwrap();
+    assert_eq!(stats.total_merges, 1);
+    assert!((stats.total_merged_balance - 25.0).abs() < f64::EPSILON);
+}
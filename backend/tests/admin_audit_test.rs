@@ -0,0 +1,97 @@
+mod common;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::{routing::post, Extension, Router};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::api::admin_audit;
+use stellar_insights_backend::api::pagination::PaginationConfig;
+use stellar_insights_backend::auth_middleware::AuthUser;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::handlers::create_anchor;
+use stellar_insights_backend::request_id::RequestId;
+use tower::util::ServiceExt;
+
+fn anchor_app(db: Arc<Database>) -> Router {
+    let state = common::app_state(db);
+    Router::new()
+        .route("/api/anchors", post(create_anchor))
+        .with_state(state)
+        .layer(Extension(AuthUser {
+            user_id: "admin-1".to_string(),
+            username: "ops".to_string(),
+            scopes: vec!["admin".to_string()],
+        }))
+        .layer(Extension(RequestId("req-123".to_string())))
+}
+
+async fn send(app: Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+async fn audit_entries(db: Arc<Database>, query: &str) -> Vec<Value> {
+    let request = Request::builder()
+        .uri(format!("/api/admin/audit{}", query))
+        .body(Body::empty())
+        .unwrap();
+    let app = admin_audit::routes(db).layer(Extension(PaginationConfig::default()));
+    let (status, body) = send(app, request).await;
+    assert_eq!(status, StatusCode::OK);
+    body["entries"].as_array().unwrap().clone()
+}
+
+#[sqlx::test]
+async fn test_create_anchor_is_audited(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool.clone()));
+
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/anchors")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({"name": "Audited", "stellar_account": "GAUDITED", "home_domain": null})
+                .to_string(),
+        ))
+        .unwrap();
+    let (status, anchor) = send(anchor_app(Arc::clone(&db)), request).await;
+    assert_eq!(status, StatusCode::OK);
+    let anchor_id = anchor["id"].as_str().unwrap();
+
+    let entries = audit_entries(Arc::clone(&db), "?actor=admin-1").await;
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry["action"], "anchor.create");
+    assert_eq!(entry["resource"], format!("anchor:{}", anchor_id));
+    assert_eq!(entry["user_id"], "admin-1");
+    assert_eq!(entry["request_id"], "req-123");
+    assert_eq!(entry["status"], "success");
+    let details: Value = serde_json::from_str(entry["details"].as_str().unwrap()).unwrap();
+    assert_eq!(details["stellar_account"], "GAUDITED");
+
+    assert!(audit_entries(Arc::clone(&db), "?action=snapshot.trigger")
+        .await
+        .is_empty());
+    assert_eq!(
+        audit_entries(db, &format!("?target=anchor:{}", anchor_id))
+            .await
+            .len(),
+        1
+    );
+
+    // The log is append-only
+    assert!(
+        sqlx::query("UPDATE admin_audit_log SET user_id = 'someone-else'")
+            .execute(&pool)
+            .await
+            .is_err()
+    );
+    assert!(sqlx::query("DELETE FROM admin_audit_log")
+        .execute(&pool)
+        .await
+        .is_err());
+}
@@ -0,0 +1,232 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::{routing::post, Json, Router};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::sync::{Arc, Mutex};
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::network::StellarNetwork;
+use stellar_insights_backend::services::contract::{ContractConfig, ContractService};
+use stellar_insights_backend::services::snapshot::SnapshotService;
+use stellar_insights_backend::snapshot_handlers::{admin_routes, SnapshotAppState};
+use tower::util::ServiceExt;
+
+fn app(pool: SqlitePool) -> axum::Router {
+    let db = Arc::new(Database::new(pool));
+    admin_routes(SnapshotAppState {
+        db: Arc::clone(&db),
+        contract_service: None,
+        snapshot_service: Arc::new(SnapshotService::new(db, None)),
+    })
+}
+
+async fn trigger(app: &axum::Router, body: Option<&str>) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/snapshots")
+        .header("content-type", "application/json")
+        .body(body.map_or_else(Body::empty, |b| Body::from(b.to_string())))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test]
+async fn test_manual_snapshot_for_explicit_epoch(pool: SqlitePool) {
+    let app = app(pool.clone());
+
+    let (status, body) = trigger(&app, Some(r#"{"epoch": 42}"#)).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["epoch"], 42);
+    assert_eq!(body["hash"].as_str().unwrap().len(), 64);
+    assert_eq!(body["submission_result"], serde_json::Value::Null);
+
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM snapshots WHERE epoch = 42")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 1);
+
+    // Without an epoch the next one after the latest is used
+    let (status, body) = trigger(&app, None).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["epoch"], 43);
+}
+
+#[sqlx::test]
+async fn test_manual_snapshot_rejects_existing_epoch(pool: SqlitePool) {
+    let app = app(pool.clone());
+
+    let (status, _) = trigger(&app, Some(r#"{"epoch": 7}"#)).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    let (status, body) = trigger(&app, Some(r#"{"epoch": 7}"#)).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(body["error"].as_str().unwrap().contains("epoch 7"));
+
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM snapshots WHERE epoch = 7")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 1);
+}
+
+async fn determinism_check(app: &axum::Router, query: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .uri(format!("/api/admin/snapshots/determinism-check{}", query))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test]
+async fn test_determinism_check_passes_on_seeded_data(pool: SqlitePool) {
+    sqlx::query("DELETE FROM anchors")
+        .execute(&pool)
+        .await
+        .unwrap();
+    for i in 1..=3 {
+        sqlx::query(
+            r#"
+            INSERT INTO anchors (
+                id, name, stellar_account, total_transactions, successful_transactions,
+                failed_transactions, status
+            )
+            VALUES ($1, $2, $3, 100, 97, 3, 'yellow')
+            "#,
+        )
+        .bind(uuid::Uuid::from_u128(i).to_string())
+        .bind(format!("Anchor{}", i))
+        .bind(format!("GANCHOR{}", i))
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    let app = app(pool.clone());
+
+    let (status, body) = determinism_check(&app, "?epoch=9").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["epoch"], 9);
+    assert_eq!(body["passed"], true);
+    assert_eq!(body["mismatches"], serde_json::json!([]));
+    assert_eq!(body["anchor_count"], 3);
+    assert_eq!(body["hash"].as_str().unwrap().len(), 64);
+
+    // A dry run stores nothing
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM snapshots")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 0);
+
+    let (status, body) = determinism_check(&app, "").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["epoch"], 1);
+}
+
+/// Mock testnet Soroban RPC holding `on_chain` epochs that rejects every
+/// submission as paused, recording the epochs of each batch it is sent
+async fn mock_contract(on_chain: Vec<u64>) -> (Arc<ContractService>, Arc<Mutex<Vec<Vec<u64>>>>) {
+    let batches = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&batches);
+    let app = Router::new().route(
+        "/",
+        post(move |Json(request): Json<Value>| {
+            let (on_chain, batches) = (on_chain.clone(), Arc::clone(&recorded));
+            async move {
+                let result = |value: Value| json!({ "jsonrpc": "2.0", "id": 1, "result": value });
+                if request["method"] == "getNetwork" {
+                    return Json(result(
+                        json!({ "passphrase": "Test SDF Network ; September 2015" }),
+                    ));
+                }
+                let invocation = &request["params"]["transaction"];
+                let epoch = |value: &Value| value.as_str().unwrap().parse::<u64>().unwrap();
+                Json(match invocation["function"].as_str().unwrap() {
+                    "get_snapshot"
+                        if on_chain.contains(&epoch(&invocation["args"][0]["value"])) =>
+                    {
+                        result(json!({ "returnValue": "ab".repeat(32) }))
+                    }
+                    "submit_snapshots_batch" => {
+                        let epochs = invocation["args"][0]["value"]
+                            .as_array()
+                            .unwrap()
+                            .iter()
+                            .map(|pair| epoch(&pair["value"][1]["value"]))
+                            .collect();
+                        batches.lock().unwrap().push(epochs);
+                        result(json!({ "error": "HostError: Error(Contract, #7)" }))
+                    }
+                    _ => json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "error": { "code": -32000, "message": "Snapshot not found" }
+                    }),
+                })
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let service = ContractService::new(ContractConfig {
+        rpc_url: format!("http://{}/", addr),
+        network: StellarNetwork::Testnet,
+        contract_id: "CBGTG4JJFEQE3SPBGQFP3X5HM46N47LXZPXQACVKB7QA6X2XB2IG5CTA".to_string(),
+        network_passphrase: "Test SDF Network ; September 2015".to_string(),
+        source_secret_key: "S...".to_string(),
+    })
+    .unwrap();
+    (Arc::new(service), batches)
+}
+
+async fn submit_missing(app: &axum::Router, body: &str) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/admin/snapshots/submit-missing")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test]
+async fn test_submit_missing_batches_only_epochs_missing_on_chain(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let service = SnapshotService::new(Arc::clone(&db), None);
+    for epoch in [1, 2, 3] {
+        service.generate_and_submit_snapshot(epoch).await.unwrap();
+    }
+    let (contract, batches) = mock_contract(vec![2]).await;
+    let app = admin_routes(SnapshotAppState {
+        db: Arc::clone(&db),
+        contract_service: Some(Arc::clone(&contract)),
+        snapshot_service: Arc::new(SnapshotService::new(db, Some(contract))),
+    });
+
+    // Epoch 2 is on-chain and epoch 4 is not stored, so 1 and 3 are sent
+    let (status, body) = submit_missing(&app, r#"{"epochs": [4, 3, 2, 1, 3]}"#).await;
+    assert_eq!(status, StatusCode::BAD_GATEWAY);
+    assert!(body["error"].as_str().unwrap().contains("paused"));
+    assert_eq!(*batches.lock().unwrap(), [vec![1, 3]]);
+
+    let (status, _) = submit_missing(&app, r#"{"epochs": []}"#).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_submit_missing_requires_contract(pool: SqlitePool) {
+    let (status, body) = submit_missing(&app(pool), r#"{"epochs": [1]}"#).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(body["error"].as_str().unwrap().contains("not configured"));
+}
@@ -0,0 +1,184 @@
+use axum::{extract::Query, routing::get, Json, Router};
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::CreateAnchorRequest;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::anchor_asset_discovery::AnchorAssetDiscovery;
+
+/// Issuer of the mock AQUA asset
+const AQUA_ISSUER: &str = "GBNZILSTVQZ4R7IKQDGHYGY2QXL5QOFJYQMXPKWRRM5PAV7Y4M67AQUA";
+
+/// Seeded Circle anchor, which already lists USDC and EURC
+const CIRCLE_ID: &str = "c1b1f1a1-1111-4111-a111-111111111111";
+
+fn discovery(db: &Arc<Database>) -> AnchorAssetDiscovery {
+    AnchorAssetDiscovery::new(
+        Arc::clone(db),
+        Arc::new(StellarRpcClient::new_with_defaults(true)),
+    )
+}
+
+/// Issuer of the 201 assets served by [`paged_horizon`]
+const PAGED_ISSUER: &str = "GDZ6PAGEDISSUERXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX";
+
+fn horizon_asset(code: &str) -> serde_json::Value {
+    json!({
+        "asset_type": "credit_alphanum12",
+        "asset_code": code,
+        "asset_issuer": PAGED_ISSUER,
+        "num_claimable_balances": 0,
+        "num_liquidity_pools": 0,
+        "num_contracts": 0,
+        "accounts": {
+            "authorized": 1,
+            "authorized_to_maintain_liabilities": 0,
+            "unauthorized": 0
+        },
+        "claimable_balances_amount": "0",
+        "liquidity_pools_amount": "0",
+        "contracts_amount": "0",
+        "balances": {
+            "authorized": "1",
+            "authorized_to_maintain_liabilities": "0",
+            "unauthorized": "0"
+        },
+        "flags": {
+            "auth_required": false,
+            "auth_revocable": false,
+            "auth_immutable": false,
+            "auth_clawback_enabled": false
+        },
+        "paging_token": format!("{}_{}_credit_alphanum12", code, PAGED_ISSUER)
+    })
+}
+
+/// Horizon mock whose `/assets` endpoint returns a full page of 200 assets,
+/// then one more asset once the request carries a cursor
+async fn paged_horizon() -> String {
+    let app = Router::new().route(
+        "/assets",
+        get(|Query(params): Query<HashMap<String, String>>| async move {
+            let records: Vec<_> = match params.get("cursor") {
+                None => (0..200)
+                    .map(|i| horizon_asset(&format!("A{:03}", i)))
+                    .collect(),
+                Some(_) => vec![horizon_asset("A200")],
+            };
+            Json(json!({ "_embedded": { "records": records } }))
+        }),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+fn asset_codes(assets: &[stellar_insights_backend::models::Asset]) -> Vec<&str> {
+    assets.iter().map(|a| a.asset_code.as_str()).collect()
+}
+
+#[sqlx::test]
+async fn test_discovered_assets_are_added_once(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: "Aquarius".to_string(),
+            stellar_account: AQUA_ISSUER.to_string(),
+            home_domain: None,
+        })
+        .await
+        .unwrap();
+    let anchor_id = Uuid::parse_str(&anchor.id).unwrap();
+    let discovery = discovery(&db);
+
+    let first = discovery.discover_for_anchor(anchor_id).await.unwrap();
+    assert_eq!(first.issued, 1);
+    assert_eq!(first.added, vec![format!("AQUA:{}", AQUA_ISSUER)]);
+
+    let second = discovery.discover_for_anchor(anchor_id).await.unwrap();
+    assert_eq!(second.issued, 1);
+    assert!(second.added.is_empty());
+
+    let assets = db.get_assets_by_anchor(anchor_id).await.unwrap();
+    assert_eq!(asset_codes(&assets), ["AQUA"]);
+}
+
+#[sqlx::test]
+async fn test_manually_added_assets_are_not_duplicated(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let circle = Uuid::parse_str(CIRCLE_ID).unwrap();
+    let before = db.get_assets_by_anchor(circle).await.unwrap();
+    assert_eq!(asset_codes(&before), ["EURC", "USDC"]);
+
+    // Horizon reports USDC, which Circle already lists
+    let result = discovery(&db).discover_for_anchor(circle).await.unwrap();
+    assert_eq!(result.issued, 1);
+    assert!(result.added.is_empty());
+
+    let after = db.get_assets_by_anchor(circle).await.unwrap();
+    assert_eq!(asset_codes(&after), ["EURC", "USDC"]);
+    let ids: Vec<&str> = after.iter().map(|a| a.id.as_str()).collect();
+    let before_ids: Vec<&str> = before.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(ids, before_ids);
+}
+
+#[sqlx::test]
+async fn test_unknown_anchor_is_an_error(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    assert!(discovery(&db)
+        .discover_for_anchor(Uuid::new_v4())
+        .await
+        .is_err());
+}
+
+#[sqlx::test]
+async fn test_discovery_follows_the_paging_cursor(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: "Paged".to_string(),
+            stellar_account: PAGED_ISSUER.to_string(),
+            home_domain: None,
+        })
+        .await
+        .unwrap();
+    let anchor_id = Uuid::parse_str(&anchor.id).unwrap();
+    let rpc_client = StellarRpcClient::new(
+        "http://127.0.0.1:1/rpc".to_string(),
+        paged_horizon().await,
+        false,
+    );
+    let discovery = AnchorAssetDiscovery::new(Arc::clone(&db), Arc::new(rpc_client));
+
+    let result = discovery.discover_for_anchor(anchor_id).await.unwrap();
+    assert_eq!(result.issued, 201);
+    assert_eq!(result.added.len(), 201);
+
+    let assets = db.get_assets_by_anchor(anchor_id).await.unwrap();
+    assert_eq!(assets.len(), 201);
+    assert!(assets.iter().any(|a| a.asset_code == "A200"));
+}
+
+#[sqlx::test]
+async fn test_discover_all_checks_every_anchor(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    db.create_anchor(CreateAnchorRequest {
+        name: "Aquarius".to_string(),
+        stellar_account: AQUA_ISSUER.to_string(),
+        home_domain: None,
+    })
+    .await
+    .unwrap();
+    let anchors = db.list_anchors(1000, 0).await.unwrap().len();
+
+    let stats = discovery(&db).discover_all().await.unwrap();
+    assert_eq!(stats.checked, anchors);
+    assert_eq!(stats.errors, 0);
+    assert!(stats.added >= 1);
+}
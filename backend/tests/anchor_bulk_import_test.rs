@@ -0,0 +1,97 @@
+mod common;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::{routing::post, Router};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::handlers::create_anchors_bulk;
+use stellar_insights_backend::models::CreateAnchorRequest;
+use tower::util::ServiceExt;
+
+fn app(db: Arc<Database>) -> Router {
+    let state = common::app_state(db);
+    Router::new()
+        .route("/api/anchors/bulk", post(create_anchors_bulk))
+        .with_state(state)
+}
+
+async fn import(app: Router, body: Value) -> (StatusCode, Value) {
+    let request = Request::builder()
+        .method("POST")
+        .uri("/api/anchors/bulk")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test]
+async fn test_mixed_validity_batch_reports_each_item(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool.clone()));
+    db.create_anchor(CreateAnchorRequest {
+        name: "Existing".to_string(),
+        stellar_account: "GEXISTING".to_string(),
+        home_domain: None,
+    })
+    .await
+    .unwrap();
+
+    let (status, body) = import(
+        app(db),
+        json!([
+            {"name": "Anchor A", "stellar_account": "GBULKA", "home_domain": "a.example"},
+            {"name": "", "stellar_account": "GBULKB", "home_domain": null},
+            {"name": "Anchor C", "stellar_account": "GEXISTING", "home_domain": null},
+            {"name": "Anchor D", "stellar_account": "", "home_domain": null},
+            {"name": "Anchor A again", "stellar_account": "GBULKA", "home_domain": null},
+            {"name": "Anchor F", "stellar_account": "GBULKF", "home_domain": null}
+        ]),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total"], 6);
+    assert_eq!(body["created"], 2);
+    assert_eq!(body["failed"], 4);
+
+    let results = body["results"].as_array().unwrap();
+    let statuses: Vec<&str> = results
+        .iter()
+        .map(|r| r["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![
+            "created",
+            "invalid",
+            "duplicate",
+            "invalid",
+            "duplicate",
+            "created"
+        ]
+    );
+    assert_eq!(results[1]["error"], "Name cannot be empty");
+    assert_eq!(results[2]["error"], "Stellar account is already registered");
+    assert_eq!(results[3]["error"], "Stellar account cannot be empty");
+    assert!(results[0]["id"].is_string());
+
+    let imported: Vec<String> = sqlx::query_scalar(
+        "SELECT stellar_account FROM anchors WHERE stellar_account LIKE 'GBULK%' ORDER BY stellar_account",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(imported, vec!["GBULKA", "GBULKF"]);
+}
+
+#[sqlx::test]
+async fn test_empty_batch_is_rejected(pool: SqlitePool) {
+    let (status, _) = import(app(Arc::new(Database::new(pool))), json!([])).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
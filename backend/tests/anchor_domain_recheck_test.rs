@@ -0,0 +1,157 @@
+use axum::{extract::State, http::StatusCode, routing::get, Router};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::jobs::anchor_domain_recheck::{
+    AnchorDomainRecheckJob, DOMAIN_UNVERIFIED_STATUS, DOMAIN_VERIFIED_STATUS,
+};
+use stellar_insights_backend::services::anchor_domain_verifier::AnchorDomainVerifier;
+use stellar_insights_backend::services::event_bus::{DomainEvent, EventBus};
+use stellar_insights_backend::services::stellar_toml::StellarTomlClient;
+
+const ANCHOR: &str = "GANCHORAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+type Toml = Arc<RwLock<(StatusCode, String)>>;
+
+fn listing_toml() -> (StatusCode, String) {
+    (StatusCode::OK, format!("ACCOUNTS = [\"{}\"]\n", ANCHOR))
+}
+
+/// Server answering stellar.toml requests with whatever `toml` holds
+async fn toml_server(toml: Toml) -> String {
+    let app = Router::new()
+        .route(
+            "/.well-known/stellar.toml",
+            get(|State(toml): State<Toml>| async move { toml.read().await.clone() }),
+        )
+        .with_state(toml);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+async fn setup(
+    pool: &SqlitePool,
+    toml: Toml,
+    event_bus: Arc<EventBus>,
+) -> (Uuid, AnchorDomainRecheckJob) {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO anchors (id, name, stellar_account, home_domain) VALUES (?, ?, ?, ?)")
+        .bind(id.to_string())
+        .bind("Anchor")
+        .bind(ANCHOR)
+        .bind("anchor.example")
+        .execute(pool)
+        .await
+        .unwrap();
+
+    let db = Arc::new(Database::new(pool.clone()));
+    let toml_client = StellarTomlClient::new(Arc::new(RwLock::new(None)), None)
+        .unwrap()
+        .with_base_url(toml_server(toml).await);
+    let verifier = Arc::new(AnchorDomainVerifier::new(
+        Arc::clone(&db),
+        Arc::new(toml_client),
+    ));
+    let job = AnchorDomainRecheckJob::new(db, verifier, event_bus).with_failure_threshold(3);
+    (id, job)
+}
+
+async fn check_failures(pool: &SqlitePool, id: Uuid) -> i64 {
+    let (failures,): (i64,) =
+        sqlx::query_as("SELECT domain_check_failures FROM anchors WHERE id = ?")
+            .bind(id.to_string())
+            .fetch_one(pool)
+            .await
+            .unwrap();
+    failures
+}
+
+async fn domain_verified(pool: &SqlitePool, id: Uuid) -> bool {
+    let (verified,): (bool,) = sqlx::query_as("SELECT domain_verified FROM anchors WHERE id = ?")
+        .bind(id.to_string())
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    verified
+}
+
+fn status_change(event: DomainEvent) -> (String, String) {
+    match event {
+        DomainEvent::AnchorStatusChanged(e) => (e.old_status, e.new_status),
+        other => panic!("unexpected event {:?}", other),
+    }
+}
+
+#[sqlx::test]
+async fn test_dropped_account_flips_after_failure_threshold(pool: SqlitePool) {
+    let toml: Toml = Arc::new(RwLock::new(listing_toml()));
+    let event_bus = Arc::new(EventBus::new());
+    let mut events = event_bus.subscribe();
+    let (id, job) = setup(&pool, Arc::clone(&toml), event_bus).await;
+
+    job.run_once().await.unwrap();
+    assert!(domain_verified(&pool, id).await);
+    assert_eq!(
+        status_change(events.try_recv().unwrap()),
+        (
+            DOMAIN_UNVERIFIED_STATUS.to_string(),
+            DOMAIN_VERIFIED_STATUS.to_string()
+        )
+    );
+
+    // The toml stops listing the anchor
+    *toml.write().await = (StatusCode::OK, "ACCOUNTS = []\n".to_string());
+    for failures in 1..=2 {
+        job.run_once().await.unwrap();
+        assert_eq!(check_failures(&pool, id).await, failures);
+        assert!(domain_verified(&pool, id).await);
+        assert!(events.try_recv().is_err());
+    }
+
+    let stats = job.run_once().await.unwrap();
+    assert_eq!(stats.unverified, 1);
+    assert!(!domain_verified(&pool, id).await);
+    assert_eq!(
+        status_change(events.try_recv().unwrap()),
+        (
+            DOMAIN_VERIFIED_STATUS.to_string(),
+            DOMAIN_UNVERIFIED_STATUS.to_string()
+        )
+    );
+
+    // Further failures do not publish again
+    job.run_once().await.unwrap();
+    assert!(events.try_recv().is_err());
+}
+
+#[sqlx::test]
+async fn test_transient_outage_does_not_flap(pool: SqlitePool) {
+    let toml: Toml = Arc::new(RwLock::new(listing_toml()));
+    let event_bus = Arc::new(EventBus::new());
+    let (id, job) = setup(&pool, Arc::clone(&toml), Arc::clone(&event_bus)).await;
+    job.run_once().await.unwrap();
+    let mut events = event_bus.subscribe();
+
+    // Two failures, a recovery, then two more failures never reach three in a
+    // row
+    for round in [
+        (StatusCode::SERVICE_UNAVAILABLE, String::new()),
+        (StatusCode::SERVICE_UNAVAILABLE, String::new()),
+        listing_toml(),
+        (StatusCode::SERVICE_UNAVAILABLE, String::new()),
+        (StatusCode::SERVICE_UNAVAILABLE, String::new()),
+    ] {
+        *toml.write().await = round;
+        job.run_once().await.unwrap();
+        assert!(domain_verified(&pool, id).await);
+    }
+    assert!(events.try_recv().is_err());
+
+    assert_eq!(check_failures(&pool, id).await, 2);
+}
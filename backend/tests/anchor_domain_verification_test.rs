@@ -0,0 +1,149 @@
+use axum::{http::StatusCode, routing::get, Router};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::anchor_domain_verifier::{
+    AnchorDomainVerifier, DomainVerification,
+};
+use stellar_insights_backend::services::stellar_toml::StellarTomlClient;
+
+const ANCHOR: &str = "GANCHORAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+const OTHER: &str = "GOTHERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+/// Server answering every stellar.toml request with `status` and `body`
+async fn toml_server(status: StatusCode, body: String) -> String {
+    let app = Router::new().route(
+        "/.well-known/stellar.toml",
+        get(move || {
+            let body = body.clone();
+            async move { (status, body) }
+        }),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+async fn insert_anchor(pool: &SqlitePool, home_domain: Option<&str>) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO anchors (id, name, stellar_account, home_domain) VALUES (?, ?, ?, ?)")
+        .bind(id.to_string())
+        .bind("Anchor")
+        .bind(ANCHOR)
+        .bind(home_domain)
+        .execute(pool)
+        .await
+        .unwrap();
+    id
+}
+
+async fn set_verified(pool: &SqlitePool, id: Uuid, verified: bool) {
+    sqlx::query("UPDATE anchors SET domain_verified = ? WHERE id = ?")
+        .bind(verified)
+        .bind(id.to_string())
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+async fn domain_verified(pool: &SqlitePool, id: Uuid) -> bool {
+    let (verified,): (bool,) = sqlx::query_as("SELECT domain_verified FROM anchors WHERE id = ?")
+        .bind(id.to_string())
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    verified
+}
+
+fn verifier(pool: &SqlitePool, base_url: String) -> AnchorDomainVerifier {
+    let toml_client = StellarTomlClient::new(Arc::new(RwLock::new(None)), None)
+        .unwrap()
+        .with_base_url(base_url);
+    AnchorDomainVerifier::new(Arc::new(Database::new(pool.clone())), Arc::new(toml_client))
+}
+
+#[sqlx::test]
+async fn test_listed_account_is_verified(pool: SqlitePool) {
+    let id = insert_anchor(&pool, Some("anchor.example")).await;
+    let body = format!(
+        "ACCOUNTS = [\"{}\"]\n\n[DOCUMENTATION]\nORG_NAME = \"Anchor\"\n",
+        ANCHOR
+    );
+    let verifier = verifier(&pool, toml_server(StatusCode::OK, body).await);
+
+    assert_eq!(
+        verifier.verify_anchor(id).await.unwrap(),
+        DomainVerification::Verified
+    );
+    assert!(domain_verified(&pool, id).await);
+}
+
+#[sqlx::test]
+async fn test_currency_issuer_is_verified(pool: SqlitePool) {
+    let id = insert_anchor(&pool, Some("anchor.example")).await;
+    let body = format!("[[CURRENCIES]]\ncode = \"USDC\"\nissuer = \"{}\"\n", ANCHOR);
+    let verifier = verifier(&pool, toml_server(StatusCode::OK, body).await);
+
+    assert_eq!(
+        verifier.verify_anchor(id).await.unwrap(),
+        DomainVerification::Verified
+    );
+    assert!(domain_verified(&pool, id).await);
+}
+
+#[sqlx::test]
+async fn test_unlisted_account_is_not_verified(pool: SqlitePool) {
+    let id = insert_anchor(&pool, Some("anchor.example")).await;
+    set_verified(&pool, id, true).await;
+    let body = format!("ACCOUNTS = [\"{}\"]\n", OTHER);
+    let verifier = verifier(&pool, toml_server(StatusCode::OK, body).await);
+
+    assert_eq!(
+        verifier.verify_anchor(id).await.unwrap(),
+        DomainVerification::AccountNotListed
+    );
+    assert!(!domain_verified(&pool, id).await);
+}
+
+#[sqlx::test]
+async fn test_fetch_failure_keeps_previous_result(pool: SqlitePool) {
+    let id = insert_anchor(&pool, Some("anchor.example")).await;
+    set_verified(&pool, id, true).await;
+
+    let failing = verifier(
+        &pool,
+        toml_server(StatusCode::NOT_FOUND, String::new()).await,
+    );
+    assert!(matches!(
+        failing.verify_anchor(id).await.unwrap(),
+        DomainVerification::FetchFailed(_)
+    ));
+    assert!(domain_verified(&pool, id).await);
+
+    let malformed = verifier(
+        &pool,
+        toml_server(StatusCode::OK, "ACCOUNTS = [\"unterminated".to_string()).await,
+    );
+    assert!(matches!(
+        malformed.verify_anchor(id).await.unwrap(),
+        DomainVerification::FetchFailed(_)
+    ));
+    assert!(domain_verified(&pool, id).await);
+}
+
+#[sqlx::test]
+async fn test_anchor_without_home_domain(pool: SqlitePool) {
+    let id = insert_anchor(&pool, None).await;
+    let verifier = verifier(&pool, "http://127.0.0.1:1".to_string());
+
+    assert_eq!(
+        verifier.verify_anchor(id).await.unwrap(),
+        DomainVerification::NoHomeDomain
+    );
+    assert!(!domain_verified(&pool, id).await);
+}
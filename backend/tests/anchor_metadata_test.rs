@@ -0,0 +1,189 @@
+use axum::{http::StatusCode, routing::get, Router};
+use sqlx::SqlitePool;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::stellar_toml::{
+    AnchorMetadataEnricher, MetadataEnrichment, StellarTomlClient,
+};
+
+const ANCHOR: &str = "GANCHORAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+const OTHER_ANCHOR: &str = "GOTHERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+const COMPLETE_TOML: &str = r#"
+ACCOUNTS = ["GANCHORAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"]
+
+[DOCUMENTATION]
+ORG_NAME = "Example Anchor"
+ORG_URL = "https://docs.anchor.example"
+ORG_LOGO = "https://anchor.example/logo.png"
+
+[[CURRENCIES]]
+code = "USDC"
+issuer = "GANCHORAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"
+
+[[CURRENCIES]]
+code = "NGNT"
+issuer = "GANCHORAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"
+"#;
+
+/// Server answering every stellar.toml request with `status` and `body`,
+/// counting the requests it serves
+async fn toml_server(status: StatusCode, body: &'static str) -> (String, Arc<AtomicUsize>) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&hits);
+    let app = Router::new().route(
+        "/.well-known/stellar.toml",
+        get(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async move { (status, body) }
+        }),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{}", addr), hits)
+}
+
+async fn insert_anchor(pool: &SqlitePool, account: &str, home_domain: Option<&str>) -> Uuid {
+    let id = Uuid::new_v4();
+    sqlx::query("INSERT INTO anchors (id, name, stellar_account, home_domain) VALUES (?, ?, ?, ?)")
+        .bind(id.to_string())
+        .bind("Anchor")
+        .bind(account)
+        .bind(home_domain)
+        .execute(pool)
+        .await
+        .unwrap();
+    id
+}
+
+fn enricher(db: &Arc<Database>, base_url: String) -> AnchorMetadataEnricher {
+    let toml_client = StellarTomlClient::new(Arc::new(RwLock::new(None)), None)
+        .unwrap()
+        .with_base_url(base_url);
+    AnchorMetadataEnricher::new(Arc::clone(db), Arc::new(toml_client))
+}
+
+#[sqlx::test]
+async fn test_complete_toml_is_stored(pool: SqlitePool) {
+    let id = insert_anchor(&pool, ANCHOR, Some("anchor.example")).await;
+    let db = Arc::new(Database::new(pool));
+    let (url, _) = toml_server(StatusCode::OK, COMPLETE_TOML).await;
+
+    let MetadataEnrichment::Enriched(metadata) =
+        enricher(&db, url).enrich_anchor(id).await.unwrap()
+    else {
+        panic!("expected the anchor to be enriched");
+    };
+    assert_eq!(
+        metadata.organization_name.as_deref(),
+        Some("Example Anchor")
+    );
+    assert_eq!(
+        metadata.documentation_url.as_deref(),
+        Some("https://docs.anchor.example")
+    );
+    assert_eq!(
+        metadata.organization_logo.as_deref(),
+        Some("https://anchor.example/logo.png")
+    );
+    assert_eq!(metadata.supported_currencies, ["USDC", "NGNT"]);
+
+    let stored = db.get_anchor_metadata(id).await.unwrap().unwrap();
+    assert_eq!(stored, metadata);
+}
+
+#[sqlx::test]
+async fn test_partial_toml_leaves_missing_fields_empty(pool: SqlitePool) {
+    let id = insert_anchor(&pool, ANCHOR, Some("anchor.example")).await;
+    let db = Arc::new(Database::new(pool));
+    let (url, _) = toml_server(StatusCode::OK, "ORGANIZATION_NAME = \"Partial Anchor\"\n").await;
+
+    let result = enricher(&db, url).enrich_anchor(id).await.unwrap();
+    assert!(matches!(result, MetadataEnrichment::Enriched(_)));
+
+    let stored = db.get_anchor_metadata(id).await.unwrap().unwrap();
+    assert_eq!(stored.organization_name.as_deref(), Some("Partial Anchor"));
+    assert_eq!(stored.documentation_url, None);
+    assert_eq!(stored.organization_logo, None);
+    assert!(stored.supported_currencies.is_empty());
+}
+
+#[sqlx::test]
+async fn test_fresh_metadata_is_not_fetched_again(pool: SqlitePool) {
+    let id = insert_anchor(&pool, ANCHOR, Some("anchor.example")).await;
+    let db = Arc::new(Database::new(pool));
+    let (url, hits) = toml_server(StatusCode::OK, COMPLETE_TOML).await;
+
+    let enricher = enricher(&db, url);
+    assert!(matches!(
+        enricher.enrich_anchor(id).await.unwrap(),
+        MetadataEnrichment::Enriched(_)
+    ));
+    assert!(matches!(
+        enricher.enrich_anchor(id).await.unwrap(),
+        MetadataEnrichment::Cached(_)
+    ));
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+    // Once stale the toml is read again
+    let enricher = enricher.with_max_age(Duration::ZERO);
+    assert!(matches!(
+        enricher.enrich_anchor(id).await.unwrap(),
+        MetadataEnrichment::Enriched(_)
+    ));
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+}
+
+#[sqlx::test]
+async fn test_missing_toml_keeps_stored_metadata(pool: SqlitePool) {
+    let id = insert_anchor(&pool, ANCHOR, Some("anchor.example")).await;
+    let no_domain = insert_anchor(&pool, OTHER_ANCHOR, None).await;
+    let db = Arc::new(Database::new(pool));
+
+    let (url, _) = toml_server(StatusCode::OK, COMPLETE_TOML).await;
+    enricher(&db, url).enrich_anchor(id).await.unwrap();
+    let before = db.get_anchor_metadata(id).await.unwrap().unwrap();
+
+    let (url, _) = toml_server(StatusCode::NOT_FOUND, "").await;
+    let enricher = enricher(&db, url).with_max_age(Duration::ZERO);
+    assert!(matches!(
+        enricher.enrich_anchor(id).await.unwrap(),
+        MetadataEnrichment::FetchFailed(_)
+    ));
+    assert_eq!(db.get_anchor_metadata(id).await.unwrap().unwrap(), before);
+
+    assert_eq!(
+        enricher.enrich_anchor(no_domain).await.unwrap(),
+        MetadataEnrichment::NoHomeDomain
+    );
+    assert!(db.get_anchor_metadata(no_domain).await.unwrap().is_none());
+}
+
+#[sqlx::test]
+async fn test_enrich_all_continues_past_database_errors(pool: SqlitePool) {
+    let failing = insert_anchor(&pool, ANCHOR, Some("anchor.example")).await;
+    let id = insert_anchor(&pool, OTHER_ANCHOR, Some("other.example")).await;
+    sqlx::query(&format!(
+        "CREATE TRIGGER fail_metadata BEFORE INSERT ON anchor_metadata \
+         WHEN NEW.anchor_id = '{}' BEGIN SELECT RAISE(ABORT, 'injected failure'); END",
+        failing
+    ))
+    .execute(&pool)
+    .await
+    .unwrap();
+    let db = Arc::new(Database::new(pool));
+    let (url, _) = toml_server(StatusCode::OK, COMPLETE_TOML).await;
+
+    let stats = enricher(&db, url).enrich_all().await.unwrap();
+
+    assert_eq!(stats.errors, 1);
+    assert!(db.get_anchor_metadata(failing).await.unwrap().is_none());
+    assert!(db.get_anchor_metadata(id).await.unwrap().is_some());
+}
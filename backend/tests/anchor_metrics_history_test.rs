@@ -0,0 +1,57 @@
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::{AnchorMetricsParams, Database};
+use stellar_insights_backend::models::CreateAnchorRequest;
+use uuid::Uuid;
+
+const LIMIT: i64 = 10;
+
+fn params(anchor_id: Uuid, total_transactions: i64) -> AnchorMetricsParams {
+    AnchorMetricsParams {
+        anchor_id,
+        success_rate: 99.0,
+        failure_rate: 1.0,
+        reliability_score: 95.0,
+        total_transactions,
+        successful_transactions: total_transactions,
+        failed_transactions: 0,
+        avg_settlement_time_ms: Some(1500),
+        volume_usd: None,
+    }
+}
+
+async fn create_anchor(db: &Database, account: &str) -> Uuid {
+    let anchor = db
+        .create_anchor(CreateAnchorRequest {
+            name: account.to_string(),
+            stellar_account: account.to_string(),
+            home_domain: None,
+        })
+        .await
+        .unwrap();
+    Uuid::parse_str(&anchor.id).unwrap()
+}
+
+#[sqlx::test]
+async fn test_history_is_trimmed_to_newest_rows(pool: SqlitePool) {
+    let db = Database::new(pool).with_metrics_history_limit(LIMIT);
+    let anchor_id = create_anchor(&db, "GTRIMMED").await;
+    let other_id = create_anchor(&db, "GOTHER").await;
+    db.record_anchor_metrics_history(params(other_id, 1))
+        .await
+        .unwrap();
+
+    for i in 1..=LIMIT + 5 {
+        db.record_anchor_metrics_history(params(anchor_id, i))
+            .await
+            .unwrap();
+    }
+
+    let history = db.get_anchor_metrics_history(anchor_id, 100).await.unwrap();
+    let totals: Vec<i64> = history.iter().map(|h| h.total_transactions).collect();
+    let expected: Vec<i64> = (6..=LIMIT + 5).rev().collect();
+    assert_eq!(totals, expected);
+
+    // Other anchors' history is untouched
+    let other = db.get_anchor_metrics_history(other_id, 100).await.unwrap();
+    assert_eq!(other.len(), 1);
+}
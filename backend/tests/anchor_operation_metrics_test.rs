@@ -0,0 +1,109 @@
+use axum::{routing::get, Json, Router};
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::ingestion::anchor_activity::AnchorMetricsSource;
+use stellar_insights_backend::ingestion::{DataIngestionService, ReliabilityScoreConfig};
+use stellar_insights_backend::rpc::StellarRpcClient;
+
+const ANCHOR: &str = "GANCHORAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+fn operation(
+    id: u32,
+    operation_type: &str,
+    amount: Option<&str>,
+    successful: bool,
+) -> serde_json::Value {
+    json!({
+        "id": id.to_string(),
+        "paging_token": id.to_string(),
+        "transaction_hash": format!("tx{}", id),
+        "transaction_successful": successful,
+        "source_account": ANCHOR,
+        "type": operation_type,
+        "created_at": "2026-01-22T10:30:00Z",
+        "amount": amount,
+    })
+}
+
+/// Horizon mock serving the anchor's operations: payments, a path payment,
+/// trustline changes, offers and account configuration, one of each kind
+/// failing
+async fn horizon_with_operations() -> String {
+    let records = json!({ "_embedded": { "records": [
+        operation(1, "payment", Some("100.0000000"), true),
+        operation(2, "payment", Some("50.0000000"), false),
+        operation(3, "path_payment_strict_send", Some("25.5000000"), true),
+        operation(4, "change_trust", None, true),
+        operation(5, "set_trust_line_flags", None, true),
+        operation(6, "change_trust", None, false),
+        operation(7, "manage_sell_offer", None, true),
+        operation(8, "manage_buy_offer", None, true),
+        operation(9, "set_options", None, true),
+        operation(10, "manage_data", None, false),
+    ] } });
+
+    let app = Router::new().route(
+        "/accounts/:account/operations",
+        get(move || {
+            let records = records.clone();
+            async move { Json(records) }
+        }),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+#[sqlx::test]
+async fn test_anchor_metrics_from_operations(pool: SqlitePool) {
+    // The Horizon mock answers for any account, so drop the seeded anchors
+    sqlx::query("DELETE FROM anchors")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO anchors (id, name, stellar_account) VALUES (?, ?, ?)")
+        .bind("a-1")
+        .bind("Anchor")
+        .bind(ANCHOR)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let rpc = StellarRpcClient::new(
+        "http://127.0.0.1:1/rpc".to_string(),
+        horizon_with_operations().await,
+        false,
+    );
+    let service = DataIngestionService::new(Arc::new(rpc), Arc::new(Database::new(pool.clone())))
+        .with_reliability_config(ReliabilityScoreConfig::default())
+        .with_metrics_source(AnchorMetricsSource::Operations);
+
+    let report = service.sync_all_metrics().await.unwrap();
+    assert_eq!(report.succeeded, 1, "{:?}", report.failed);
+
+    let (total, successful, failed, volume): (i64, i64, i64, f64) = sqlx::query_as(
+        "SELECT total_transactions, successful_transactions, failed_transactions, total_volume_usd FROM anchors WHERE id = 'a-1'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(total, 10);
+    assert_eq!(successful, 7);
+    assert_eq!(failed, 3);
+    // Only successful payments count towards volume
+    assert!((volume - 125.5).abs() < 1e-9, "{}", volume);
+
+    // Weighted: 3 payments, 2 offers, 3 trustlines, 2 other => 6.0 in total,
+    // of which a payment (1.0), a trustline (0.5) and manage_data (0.25) failed
+    let (reliability,): (f64,) =
+        sqlx::query_as("SELECT reliability_score FROM anchors WHERE id = 'a-1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    let expected = 4.25 / 6.0 - 3.0 * 0.01;
+    assert!((reliability - expected).abs() < 1e-9, "{}", reliability);
+}
@@ -0,0 +1,86 @@
+use sqlx::SqlitePool;
+use std::collections::HashSet;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::AnchorCursor;
+
+async fn insert_anchor(pool: &SqlitePool, id: &str, reliability: f64, updated_at: &str) {
+    sqlx::query(
+        r#"
+        INSERT INTO anchors (id, name, stellar_account, reliability_score, updated_at)
+        VALUES ($1, $1, $2, $3, $4)
+        "#,
+    )
+    .bind(id)
+    .bind(format!("G{}", id.to_uppercase()))
+    .bind(reliability)
+    .bind(updated_at)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+/// Seven anchors, in place of the seeded ones, five of them tied on
+/// reliability and update time; one of those stores its timestamp in RFC 3339
+/// form
+async fn seed_tied_anchors(pool: &SqlitePool) {
+    sqlx::query("DELETE FROM anchors")
+        .execute(pool)
+        .await
+        .unwrap();
+    insert_anchor(pool, "top", 99.0, "2024-01-01 00:00:00").await;
+    for id in ["tie-c", "tie-a", "tie-e", "tie-b"] {
+        insert_anchor(pool, id, 90.0, "2024-01-01 00:00:00").await;
+    }
+    insert_anchor(pool, "tie-d", 90.0, "2024-01-01T00:00:00+00:00").await;
+    insert_anchor(pool, "low", 50.0, "2024-01-02 00:00:00").await;
+}
+
+const EXPECTED_ORDER: [&str; 7] = ["top", "tie-e", "tie-d", "tie-c", "tie-b", "tie-a", "low"];
+
+fn ids(anchors: &[stellar_insights_backend::models::Anchor]) -> Vec<String> {
+    anchors.iter().map(|a| a.id.clone()).collect()
+}
+
+#[sqlx::test]
+async fn test_offset_pages_are_stable_and_gap_free(pool: SqlitePool) {
+    seed_tied_anchors(&pool).await;
+    let db = Database::new(pool);
+
+    let mut paged = Vec::new();
+    for offset in (0..7).step_by(3) {
+        paged.extend(ids(&db.list_anchors(3, offset).await.unwrap()));
+    }
+    assert_eq!(paged, EXPECTED_ORDER);
+
+    // Repeating a page returns the same rows
+    assert_eq!(
+        ids(&db.list_anchors(3, 3).await.unwrap()),
+        ids(&db.list_anchors(3, 3).await.unwrap())
+    );
+}
+
+#[sqlx::test]
+async fn test_cursor_pages_follow_the_full_sort_key(pool: SqlitePool) {
+    seed_tied_anchors(&pool).await;
+    let db = Database::new(pool);
+
+    let mut page = db.list_anchors(2, 0).await.unwrap();
+    let mut paged = ids(&page);
+    while let Some(last) = page.last() {
+        // The cursor goes through its client-facing form
+        let cursor = AnchorCursor::decode(&AnchorCursor::after(last).encode()).unwrap();
+        assert_eq!(cursor, AnchorCursor::after(last));
+
+        page = db.list_anchors_after(&cursor, 2).await.unwrap();
+        paged.extend(ids(&page));
+    }
+
+    assert_eq!(paged, EXPECTED_ORDER);
+    assert_eq!(paged.iter().collect::<HashSet<_>>().len(), paged.len());
+}
+
+#[test]
+fn test_invalid_cursor_is_rejected() {
+    assert_eq!(AnchorCursor::decode("not a cursor"), None);
+    assert_eq!(AnchorCursor::decode(""), None);
+}
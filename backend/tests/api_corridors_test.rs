@@ -13,6 +13,7 @@ use stellar_insights_backend::api::corridors::{get_corridor_detail, list_corrido
 use stellar_insights_backend::database::Database;
 use stellar_insights_backend::ingestion::DataIngestionService;
 use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::event_bus::EventBus;
 use stellar_insights_backend::state::AppState;
 use stellar_insights_backend::websocket::WsState;
 
@@ -33,6 +34,7 @@ fn create_test_router(db: Arc<Database>) -> Router {
         db,
         ws_state,
         ingestion,
+        event_bus: Arc::new(EventBus::new()),
     };
     Router::new()
         .route("/api/corridors", axum::routing::get(list_corridors))
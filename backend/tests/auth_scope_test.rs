@@ -0,0 +1,132 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Request, StatusCode};
+use axum::{middleware, Extension, Router};
+use chrono::Utc;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::api::pagination::PaginationConfig;
+use stellar_insights_backend::api::{admin_audit, webhooks};
+use stellar_insights_backend::auth::Claims;
+use stellar_insights_backend::auth_middleware::{
+    auth_middleware, require_scope, scopes, JwtSecret, RequiredScope,
+};
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::webhooks::WebhookLimits;
+use tower::util::ServiceExt;
+
+const SECRET: &str = "test-secret-that-is-at-least-32-characters";
+
+/// Webhook routes layered the way main.rs mounts them
+fn app(pool: SqlitePool) -> Router {
+    webhooks::routes(pool, WebhookLimits::default())
+        .layer(middleware::from_fn_with_state(
+            RequiredScope(scopes::WEBHOOK_MANAGE),
+            require_scope,
+        ))
+        .layer(middleware::from_fn(auth_middleware))
+        .layer(Extension(JwtSecret(Arc::from(SECRET))))
+}
+
+/// Admin audit routes layered the way main.rs mounts them, minus the IP
+/// whitelist
+fn admin_audit_app(pool: SqlitePool) -> Router {
+    admin_audit::routes(Arc::new(Database::new(pool)))
+        .layer(Extension(PaginationConfig::default()))
+        .layer(middleware::from_fn_with_state(
+            RequiredScope(scopes::ADMIN),
+            require_scope,
+        ))
+        .layer(middleware::from_fn(auth_middleware))
+        .layer(Extension(JwtSecret(Arc::from(SECRET))))
+}
+
+fn token(scopes: &[&str]) -> String {
+    let now = Utc::now().timestamp();
+    let claims = Claims {
+        sub: "user-1".to_string(),
+        username: "alice".to_string(),
+        exp: now + 3600,
+        iat: now,
+        token_type: "access".to_string(),
+        scopes: scopes.iter().map(|s| s.to_string()).collect(),
+    };
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(SECRET.as_bytes()),
+    )
+    .unwrap()
+}
+
+async fn list_webhooks(app: &Router, token: Option<&str>) -> (StatusCode, serde_json::Value) {
+    get(app, "/api/webhooks", token).await
+}
+
+async fn get(app: &Router, uri: &str, token: Option<&str>) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::builder().method("GET").uri(uri);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    let response = app
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test]
+async fn test_under_scoped_token_is_rejected(pool: SqlitePool) {
+    let app = app(pool);
+
+    let (status, body) = list_webhooks(&app, Some(&token(&[scopes::ANCHOR_WRITE]))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error"], "Insufficient scope");
+    assert_eq!(body["reason"], "missing required scope 'webhook:manage'");
+
+    // Tokens issued before scopes existed carry none
+    let (status, _) = list_webhooks(&app, Some(&token(&[]))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+}
+
+#[sqlx::test]
+async fn test_scoped_token_is_accepted(pool: SqlitePool) {
+    let app = app(pool);
+
+    let (status, body) = list_webhooks(&app, Some(&token(&[scopes::WEBHOOK_MANAGE]))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["webhooks"], serde_json::json!([]));
+
+    let (status, _) = list_webhooks(&app, Some(&token(&[scopes::ADMIN]))).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_missing_token_is_unauthorized(pool: SqlitePool) {
+    let (status, _) = list_webhooks(&app(pool), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test]
+async fn test_admin_audit_requires_admin_scope(pool: SqlitePool) {
+    let app = admin_audit_app(pool);
+
+    let (status, _) = get(&app, "/api/admin/audit", None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, body) = get(
+        &app,
+        "/api/admin/audit",
+        Some(&token(&[scopes::WEBHOOK_MANAGE])),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["reason"], "missing required scope 'admin'");
+
+    let (status, body) = get(&app, "/api/admin/audit", Some(&token(&[scopes::ADMIN]))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["entries"], serde_json::json!([]));
+}
@@ -0,0 +1,148 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use stellar_insights_backend::api::metrics_cached;
+use stellar_insights_backend::cache::{CacheConfig, CacheManager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tower::util::ServiceExt;
+
+/// Commands received by the mock Redis, by name
+type CommandLog = Arc<Mutex<Vec<String>>>;
+
+/// Parse one RESP array of bulk strings from the front of `buf`
+fn parse_command(buf: &mut Vec<u8>) -> Option<Vec<String>> {
+    fn line(buf: &[u8], at: usize) -> Option<(&[u8], usize)> {
+        let end = buf[at..].windows(2).position(|w| w == b"\r\n")? + at;
+        Some((&buf[at..end], end + 2))
+    }
+    let (header, mut at) = line(buf, 0)?;
+    let count: usize = std::str::from_utf8(header.strip_prefix(b"*")?)
+        .ok()?
+        .parse()
+        .ok()?;
+    let mut args = Vec::with_capacity(count);
+    for _ in 0..count {
+        let (len, start) = line(buf, at)?;
+        let len: usize = std::str::from_utf8(len.strip_prefix(b"$")?)
+            .ok()?
+            .parse()
+            .ok()?;
+        if buf.len() < start + len + 2 {
+            return None;
+        }
+        args.push(String::from_utf8_lossy(&buf[start..start + len]).into_owned());
+        at = start + len + 2;
+    }
+    buf.drain(..at);
+    Some(args)
+}
+
+/// A Redis speaking just enough RESP for the cache. With `fail_reads`,
+/// every GET answers with an error.
+async fn mock_redis(fail_reads: bool) -> (String, CommandLog) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("redis://{}", listener.local_addr().unwrap());
+    let log = CommandLog::default();
+    let commands = Arc::clone(&log);
+
+    tokio::spawn(async move {
+        let store: Arc<Mutex<HashMap<String, String>>> = Arc::default();
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let commands = Arc::clone(&commands);
+            let store = Arc::clone(&store);
+            tokio::spawn(async move {
+                let mut buf = Vec::new();
+                let mut chunk = [0u8; 4096];
+                loop {
+                    let n = match socket.read(&mut chunk).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => n,
+                    };
+                    buf.extend_from_slice(&chunk[..n]);
+                    while let Some(args) = parse_command(&mut buf) {
+                        let name = args[0].to_ascii_uppercase();
+                        commands.lock().unwrap().push(name.clone());
+                        let reply = match name.as_str() {
+                            "GET" if fail_reads => "-ERR injected read failure\r\n".to_string(),
+                            "GET" => match store.lock().unwrap().get(&args[1]) {
+                                Some(value) => format!("${}\r\n{}\r\n", value.len(), value),
+                                None => "$-1\r\n".to_string(),
+                            },
+                            "SETEX" => {
+                                store
+                                    .lock()
+                                    .unwrap()
+                                    .insert(args[1].clone(), args[3].clone());
+                                "+OK\r\n".to_string()
+                            }
+                            _ => "+OK\r\n".to_string(),
+                        };
+                        if socket.write_all(reply.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    (url, log)
+}
+
+async fn get_overview(cache: &Arc<CacheManager>) -> (String, serde_json::Value) {
+    let response = metrics_cached::routes(Arc::clone(cache))
+        .oneshot(
+            Request::builder()
+                .uri("/api/metrics/overview")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let x_cache = response.headers()["x-cache"].to_str().unwrap().to_string();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (x_cache, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_cache_read_error_falls_through_with_bypass_header() {
+    let (url, commands) = mock_redis(true).await;
+    let cache = Arc::new(
+        CacheManager::connect(CacheConfig::default(), &url)
+            .await
+            .unwrap(),
+    );
+    assert!(cache.is_connected().await);
+
+    let (x_cache, overview) = get_overview(&cache).await;
+
+    assert_eq!(x_cache, "BYPASS");
+    assert_eq!(overview["total_transactions"], 98765);
+    assert_eq!(overview["corridor_count"], 12);
+    // The failing cache is not written to
+    let commands = commands.lock().unwrap();
+    assert!(commands.contains(&"GET".to_string()));
+    assert!(!commands.contains(&"SETEX".to_string()));
+}
+
+#[tokio::test]
+async fn test_healthy_cache_reports_miss_then_hit() {
+    let (url, _) = mock_redis(false).await;
+    let cache = Arc::new(
+        CacheManager::connect(CacheConfig::default(), &url)
+            .await
+            .unwrap(),
+    );
+
+    let (first, fetched) = get_overview(&cache).await;
+    let (second, cached) = get_overview(&cache).await;
+
+    assert_eq!(first, "MISS");
+    assert_eq!(second, "HIT");
+    assert_eq!(cached["total_transactions"], fetched["total_transactions"]);
+}
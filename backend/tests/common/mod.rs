@@ -0,0 +1,19 @@
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::ingestion::DataIngestionService;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::event_bus::EventBus;
+use stellar_insights_backend::state::AppState;
+use stellar_insights_backend::websocket::WsState;
+
+/// Handler state over `db`, backed by the mock RPC client
+pub fn app_state(db: Arc<Database>) -> AppState {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let ingestion = Arc::new(DataIngestionService::new(rpc_client, Arc::clone(&db)));
+    AppState::new(
+        db,
+        Arc::new(WsState::new()),
+        ingestion,
+        Arc::new(EventBus::new()),
+    )
+}
@@ -0,0 +1,75 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    middleware,
+    routing::get,
+    Router,
+};
+use std::sync::Arc;
+use std::time::Duration;
+use stellar_insights_backend::concurrency_limit::{
+    concurrency_limit_middleware, ConcurrencyLimiter,
+};
+use tokio::sync::watch;
+use tower::util::ServiceExt;
+
+/// Router whose `/slow` handler holds its slot until `release` is set
+fn app(limiter: Arc<ConcurrencyLimiter>, release: watch::Receiver<bool>) -> Router {
+    Router::new()
+        .route(
+            "/slow",
+            get(move || {
+                let mut release = release.clone();
+                async move {
+                    release.wait_for(|released| *released).await.unwrap();
+                    "done"
+                }
+            }),
+        )
+        .layer(middleware::from_fn_with_state(
+            limiter,
+            concurrency_limit_middleware,
+        ))
+}
+
+fn request() -> Request<Body> {
+    Request::builder().uri("/slow").body(Body::empty()).unwrap()
+}
+
+async fn wait_for_in_flight(limiter: &ConcurrencyLimiter, expected: usize) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while limiter.in_flight() < expected {
+            tokio::task::yield_now().await;
+        }
+    })
+    .await
+    .expect("requests never became in-flight");
+}
+
+#[tokio::test]
+async fn test_excess_requests_are_shed_while_in_flight_complete() {
+    let limiter = Arc::new(ConcurrencyLimiter::new("test", 2, 5));
+    let (release_tx, release_rx) = watch::channel(false);
+    let app = app(Arc::clone(&limiter), release_rx);
+
+    let in_flight: Vec<_> = (0..2)
+        .map(|_| tokio::spawn(app.clone().oneshot(request())))
+        .collect();
+    wait_for_in_flight(&limiter, 2).await;
+
+    let shed = app.clone().oneshot(request()).await.unwrap();
+    assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(shed.headers()[header::RETRY_AFTER], "5");
+    assert_eq!(limiter.in_flight(), 2);
+
+    release_tx.send(true).unwrap();
+    for handle in in_flight {
+        let response = handle.await.unwrap().unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    // Slots are returned once the in-flight requests finish
+    assert_eq!(limiter.in_flight(), 0);
+    let response = app.oneshot(request()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::{routing::delete, Extension, Router};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::auth_middleware::AuthUser;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::handlers::delete_corridor;
+use stellar_insights_backend::services::snapshot::SnapshotService;
+use tower::util::ServiceExt;
+use uuid::Uuid;
+
+const USDC_EURC: &str = "00000000-0000-0000-0000-000000000001";
+const USDC_XLM: &str = "00000000-0000-0000-0000-000000000002";
+
+async fn insert_corridor(pool: &SqlitePool, id: &str, dest: &str, dest_issuer: &str) {
+    sqlx::query(
+        r#"
+        INSERT INTO corridors (
+            id, source_asset_code, source_asset_issuer,
+            destination_asset_code, destination_asset_issuer, reliability_score
+        )
+        VALUES ($1, 'USDC', 'issuer1', $2, $3, 90.0)
+        "#,
+    )
+    .bind(id)
+    .bind(dest)
+    .bind(dest_issuer)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn insert_corridor_metrics(pool: &SqlitePool, dest: &str, dest_issuer: &str) {
+    sqlx::query(
+        r#"
+        INSERT INTO corridor_metrics (
+            corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+            date, total_transactions, successful_transactions, failed_transactions,
+            success_rate, volume_usd
+        )
+        VALUES ($1, 'USDC', 'issuer1', $2, $3, datetime('now'), 10, 9, 1, 90.0, 100.0)
+        "#,
+    )
+    .bind(format!("USDC:issuer1->{}:{}", dest, dest_issuer))
+    .bind(dest)
+    .bind(dest_issuer)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+fn app(db: Arc<Database>) -> Router {
+    let state = common::app_state(db);
+    Router::new()
+        .route("/api/corridors/:id", delete(delete_corridor))
+        .with_state(state)
+        .layer(Extension(AuthUser {
+            user_id: "ops-1".to_string(),
+            username: "ops".to_string(),
+            scopes: vec!["corridor:write".to_string()],
+        }))
+}
+
+async fn delete_request(app: &Router, id: &str) -> StatusCode {
+    let request = Request::builder()
+        .method("DELETE")
+        .uri(format!("/api/corridors/{}", id))
+        .body(Body::empty())
+        .unwrap();
+    app.clone().oneshot(request).await.unwrap().status()
+}
+
+#[sqlx::test]
+async fn test_deleted_corridor_is_hidden(pool: SqlitePool) {
+    sqlx::query("DELETE FROM corridors")
+        .execute(&pool)
+        .await
+        .unwrap();
+    insert_corridor(&pool, USDC_EURC, "EURC", "issuer2").await;
+    insert_corridor(&pool, USDC_XLM, "XLM", "native").await;
+    let db = Arc::new(Database::new(pool.clone()));
+    let app = app(Arc::clone(&db));
+
+    assert_eq!(db.list_corridors(50, 0).await.unwrap().len(), 2);
+
+    assert_eq!(
+        delete_request(&app, USDC_EURC).await,
+        StatusCode::NO_CONTENT
+    );
+    let corridors = db.list_corridors(50, 0).await.unwrap();
+    assert_eq!(corridors.len(), 1);
+    assert_eq!(corridors[0].asset_b_code, "XLM");
+    assert!(db
+        .get_corridor_by_id(Uuid::parse_str(USDC_EURC).unwrap())
+        .await
+        .unwrap()
+        .is_none());
+
+    // The row is kept, marked deleted
+    let (status, deleted_at): (String, Option<String>) =
+        sqlx::query_as("SELECT status, deleted_at FROM corridors WHERE id = $1")
+            .bind(USDC_EURC)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(status, "deleted");
+    assert!(deleted_at.is_some());
+
+    // Deleting again, or an unknown corridor, is a 404
+    assert_eq!(delete_request(&app, USDC_EURC).await, StatusCode::NOT_FOUND);
+    assert_eq!(
+        delete_request(&app, &Uuid::new_v4().to_string()).await,
+        StatusCode::NOT_FOUND
+    );
+}
+
+#[sqlx::test]
+async fn test_deleted_corridor_is_left_out_of_snapshots(pool: SqlitePool) {
+    insert_corridor(&pool, USDC_EURC, "EURC", "issuer2").await;
+    insert_corridor(&pool, USDC_XLM, "XLM", "native").await;
+    insert_corridor_metrics(&pool, "EURC", "issuer2").await;
+    insert_corridor_metrics(&pool, "XLM", "native").await;
+    let db = Arc::new(Database::new(pool));
+    let snapshots = SnapshotService::new(Arc::clone(&db), None);
+
+    let snapshot = snapshots.aggregate_all_metrics(1).await.unwrap();
+    assert_eq!(snapshot.corridor_metrics.len(), 2);
+
+    db.delete_corridor(Uuid::parse_str(USDC_EURC).unwrap())
+        .await
+        .unwrap()
+        .unwrap();
+    let snapshot = snapshots.aggregate_all_metrics(2).await.unwrap();
+    assert_eq!(snapshot.corridor_metrics.len(), 1);
+    assert_eq!(snapshot.corridor_metrics[0].asset_b_code, "XLM");
+}
@@ -0,0 +1,168 @@
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::CreateCorridorRequest;
+
+fn request(source: &str, dest: &str, directional: bool) -> CreateCorridorRequest {
+    CreateCorridorRequest {
+        source_asset_code: source.to_string(),
+        source_asset_issuer: format!("{}_ISSUER", source),
+        dest_asset_code: dest.to_string(),
+        dest_asset_issuer: format!("{}_ISSUER", dest),
+        directional,
+    }
+}
+
+async fn insert_raw(pool: &SqlitePool, id: &str, source: &str, dest: &str, score: f64) {
+    sqlx::query(
+        r#"
+        INSERT INTO corridors (
+            id, source_asset_code, source_asset_issuer,
+            destination_asset_code, destination_asset_issuer, reliability_score
+        )
+        VALUES ($1, $2, $3, $4, $5, $6)
+        "#,
+    )
+    .bind(id)
+    .bind(source)
+    .bind(format!("{}_ISSUER", source))
+    .bind(dest)
+    .bind(format!("{}_ISSUER", dest))
+    .bind(score)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+/// Drop the corridors seeded by the migrations so counts cover the test's own
+async fn clear_corridors(pool: &SqlitePool) {
+    sqlx::query("DELETE FROM corridors")
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+/// Re-run the migration that merges corridors stored in reversed order
+async fn merge_duplicate_corridors(pool: &SqlitePool) {
+    sqlx::raw_sql(include_str!(
+        "../migrations/047_merge_duplicate_corridors.sql"
+    ))
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn corridor_count(pool: &SqlitePool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM corridors")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_normalized_corridors_dedupe_both_orders(pool: SqlitePool) {
+    clear_corridors(&pool).await;
+    let db = Database::new(pool.clone());
+
+    let usdc_eurc = db
+        .create_corridor(request("USDC", "EURC", false))
+        .await
+        .unwrap();
+    let eurc_usdc = db
+        .create_corridor(request("EURC", "USDC", false))
+        .await
+        .unwrap();
+
+    assert_eq!(usdc_eurc, eurc_usdc);
+    assert!(!usdc_eurc.directional);
+    assert_eq!(corridor_count(&pool).await, 1);
+}
+
+#[sqlx::test]
+async fn test_directional_corridors_keep_their_own_metrics(pool: SqlitePool) {
+    clear_corridors(&pool).await;
+    let db = Database::new(pool.clone());
+
+    let usdc_to_eurc = db
+        .create_corridor(request("USDC", "EURC", true))
+        .await
+        .unwrap();
+    let eurc_to_usdc = db
+        .create_corridor(request("EURC", "USDC", true))
+        .await
+        .unwrap();
+
+    assert_ne!(usdc_to_eurc, eurc_to_usdc);
+    assert_eq!(usdc_to_eurc.pair_key(), eurc_to_usdc.pair_key());
+    assert_eq!(corridor_count(&pool).await, 2);
+
+    sqlx::query("UPDATE corridors SET reliability_score = 90 WHERE source_asset_code = 'USDC'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE corridors SET reliability_score = 40 WHERE source_asset_code = 'EURC'")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    merge_duplicate_corridors(&pool).await;
+    assert_eq!(corridor_count(&pool).await, 2);
+
+    let corridors = db.list_corridors(10, 0).await.unwrap();
+    assert_eq!(corridors, vec![usdc_to_eurc, eurc_to_usdc]);
+    assert!(corridors.iter().all(|c| c.directional));
+}
+
+#[sqlx::test]
+async fn test_merge_duplicate_corridors_migration(pool: SqlitePool) {
+    clear_corridors(&pool).await;
+
+    // Legacy rows keyed on the ordered tuple: USDC->EURC duplicates EURC->USDC,
+    // and XLM->BTC has no normalized counterpart
+    insert_raw(&pool, "canonical", "EURC", "USDC", 70.0).await;
+    insert_raw(&pool, "reversed", "USDC", "EURC", 85.0).await;
+    insert_raw(&pool, "lone", "XLM", "BTC", 50.0).await;
+
+    sqlx::query(
+        r#"
+        INSERT INTO metrics (id, name, value, entity_id, entity_type, timestamp)
+        VALUES ('m1', 'success_rate', 85.0, 'reversed', 'corridor', '2024-01-01T00:00:00Z')
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    merge_duplicate_corridors(&pool).await;
+    assert_eq!(corridor_count(&pool).await, 2);
+
+    let score: f64 =
+        sqlx::query_scalar("SELECT reliability_score FROM corridors WHERE id = 'canonical'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(score, 85.0);
+
+    let entity: String = sqlx::query_scalar("SELECT entity_id FROM metrics WHERE id = 'm1'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(entity, "canonical");
+
+    let lone: (String, String) = sqlx::query_as(
+        "SELECT source_asset_code, destination_asset_code FROM corridors WHERE id = 'lone'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(lone, ("BTC".to_string(), "XLM".to_string()));
+
+    // Nothing left to merge
+    merge_duplicate_corridors(&pool).await;
+    assert_eq!(corridor_count(&pool).await, 2);
+    let lone_source: String =
+        sqlx::query_scalar("SELECT source_asset_code FROM corridors WHERE id = 'lone'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(lone_source, "BTC");
+}
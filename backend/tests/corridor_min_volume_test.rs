@@ -0,0 +1,161 @@
+mod common;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::{routing::get, Extension, Router};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::api::pagination::PaginationConfig;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::handlers::list_corridors;
+use tower::util::ServiceExt;
+
+async fn insert_corridor(pool: &SqlitePool, id: &str, source: (&str, &str), dest: (&str, &str)) {
+    sqlx::query(
+        r#"
+        INSERT INTO corridors (
+            id, source_asset_code, source_asset_issuer,
+            destination_asset_code, destination_asset_issuer, reliability_score
+        )
+        VALUES ($1, $2, $3, $4, $5, 90.0)
+        "#,
+    )
+    .bind(id)
+    .bind(source.0)
+    .bind(source.1)
+    .bind(dest.0)
+    .bind(dest.1)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn insert_volume(pool: &SqlitePool, corridor_key: &str, date: &str, volume_usd: f64) {
+    let (a, b) = corridor_key.split_once("->").unwrap();
+    let (a_code, a_issuer) = a.split_once(':').unwrap();
+    let (b_code, b_issuer) = b.split_once(':').unwrap();
+    sqlx::query(
+        r#"
+        INSERT INTO corridor_metrics (
+            corridor_key, asset_a_code, asset_a_issuer, asset_b_code, asset_b_issuer,
+            date, total_transactions, successful_transactions, failed_transactions,
+            success_rate, volume_usd
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, 10, 10, 0, 100.0, $7)
+        "#,
+    )
+    .bind(corridor_key)
+    .bind(a_code)
+    .bind(a_issuer)
+    .bind(b_code)
+    .bind(b_issuer)
+    .bind(date)
+    .bind(volume_usd)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+/// Busy, dust and metric-less corridors, in place of the seeded ones; the busy
+/// one is stored in the opposite order to its metrics key
+async fn seed(pool: &SqlitePool) {
+    sqlx::query("DELETE FROM corridors")
+        .execute(pool)
+        .await
+        .unwrap();
+    insert_corridor(pool, "busy", ("USDC", "issuer1"), ("EURC", "issuer2")).await;
+    insert_volume(pool, "EURC:issuer2->USDC:issuer1", "2024-01-01", 0.5).await;
+    insert_volume(pool, "EURC:issuer2->USDC:issuer1", "2024-01-02", 5000.0).await;
+
+    insert_corridor(pool, "dust", ("USDC", "issuer1"), ("XLM", "native")).await;
+    insert_volume(pool, "USDC:issuer1->XLM:native", "2024-01-01", 9000.0).await;
+    insert_volume(pool, "USDC:issuer1->XLM:native", "2024-01-02", 0.25).await;
+
+    insert_corridor(pool, "silent", ("BRL", "issuer3"), ("USDC", "issuer1")).await;
+}
+
+fn app(pool: SqlitePool) -> Router {
+    app_with_db(Database::new(pool))
+}
+
+fn app_with_db(db: Database) -> Router {
+    let db = Arc::new(db);
+    let state = common::app_state(db);
+    Router::new()
+        .route("/api/corridors", get(list_corridors))
+        .with_state(state)
+        .layer(Extension(PaginationConfig::default()))
+}
+
+async fn list(app: &Router, query: &str) -> (StatusCode, serde_json::Value) {
+    let response = app
+        .clone()
+        .oneshot(
+            Request::get(format!("/api/corridors?{}", query))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn keys(body: &serde_json::Value) -> Vec<String> {
+    let mut keys: Vec<String> = body["corridors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| format!("{}->{}", c["asset_a_code"], c["asset_b_code"]).replace('"', ""))
+        .collect();
+    keys.sort();
+    keys
+}
+
+#[sqlx::test]
+async fn test_dust_corridors_are_hidden_by_default(pool: SqlitePool) {
+    seed(&pool).await;
+    let app = app(pool);
+
+    // Only the latest day's volume counts
+    let (status, body) = list(&app, "").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(keys(&body), ["EURC->USDC"]);
+
+    let (_, body) = list(&app, "include_all=true").await;
+    assert_eq!(keys(&body), ["BRL->USDC", "EURC->USDC", "USDC->XLM"]);
+}
+
+#[sqlx::test]
+async fn test_min_volume_threshold(pool: SqlitePool) {
+    seed(&pool).await;
+    let app = app(pool);
+
+    let (_, body) = list(&app, "min_volume_usd=0.1").await;
+    assert_eq!(keys(&body), ["EURC->USDC", "USDC->XLM"]);
+
+    let (_, body) = list(&app, "min_volume_usd=10000").await;
+    assert!(keys(&body).is_empty());
+
+    // A zero threshold lists every corridor
+    let (_, body) = list(&app, "min_volume_usd=0").await;
+    assert_eq!(keys(&body).len(), 3);
+
+    let (status, body) = list(&app, "min_volume_usd=-1").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "INVALID_MIN_VOLUME");
+}
+
+#[sqlx::test]
+async fn test_configured_default_threshold(pool: SqlitePool) {
+    seed(&pool).await;
+    let app = app_with_db(Database::new(pool).with_min_corridor_volume_usd(0.1));
+
+    let (_, body) = list(&app, "").await;
+    assert_eq!(keys(&body), ["EURC->USDC", "USDC->XLM"]);
+
+    // An explicit threshold still wins over the configured one
+    let (_, body) = list(&app, "min_volume_usd=10000").await;
+    assert!(keys(&body).is_empty());
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::{routing::get, Extension, Router};
+use chrono::{Duration, Utc};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::api::pagination::PaginationConfig;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::handlers::get_corridor_payments;
+use tower::util::ServiceExt;
+
+const CIRCLE: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+const OTHER: &str = "GOTHERXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX";
+
+type Asset<'a> = Option<(&'a str, &'a str)>;
+
+/// Insert a payment delivering `asset` (native when `None`), sent in the
+/// same asset
+async fn insert_payment(pool: &SqlitePool, id: &str, asset: Asset<'_>, hours_ago: i64) {
+    insert_path_payment(pool, id, asset, asset, hours_ago).await;
+}
+
+async fn insert_path_payment(
+    pool: &SqlitePool,
+    id: &str,
+    source: Asset<'_>,
+    asset: Asset<'_>,
+    hours_ago: i64,
+) {
+    let created_at = (Utc::now() - Duration::hours(hours_ago)).to_rfc3339();
+    sqlx::query(
+        r#"
+        INSERT INTO payments (
+            id, transaction_hash, source_account, destination_account,
+            source_asset_code, source_asset_issuer,
+            asset_type, asset_code, asset_issuer, amount, created_at
+        )
+        VALUES ($1, $1, 'GSOURCE', 'GDEST', $2, $3, $4, $5, $6, 10.0, $7)
+        "#,
+    )
+    .bind(id)
+    .bind(source.map(|(code, _)| code))
+    .bind(source.map(|(_, issuer)| issuer))
+    .bind(if asset.is_some() {
+        "credit_alphanum4"
+    } else {
+        "native"
+    })
+    .bind(asset.map(|(code, _)| code))
+    .bind(asset.map(|(_, issuer)| issuer))
+    .bind(created_at)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn seed(pool: &SqlitePool) {
+    let usdc = Some(("USDC", CIRCLE));
+    let eurc = Some(("EURC", CIRCLE));
+    insert_payment(pool, "usdc-1h", usdc, 1).await;
+    insert_payment(pool, "usdc-2h", usdc, 2).await;
+    insert_payment(pool, "usdc-3h", usdc, 3).await;
+    insert_payment(pool, "usdc-30h", usdc, 30).await;
+    insert_payment(pool, "usdc-other-issuer", Some(("USDC", OTHER)), 1).await;
+    insert_payment(pool, "eurc", eurc, 1).await;
+    insert_payment(pool, "xlm", None, 1).await;
+    insert_path_payment(pool, "usdc-to-eurc", usdc, eurc, 1).await;
+    insert_path_payment(pool, "xlm-to-usdc", None, usdc, 2).await;
+}
+
+fn app(pool: SqlitePool) -> Router {
+    let state = common::app_state(Arc::new(Database::new(pool)));
+    Router::new()
+        .route(
+            "/api/corridors/:corridor_key/payments",
+            get(get_corridor_payments),
+        )
+        .with_state(state)
+        .layer(Extension(PaginationConfig::default()))
+}
+
+async fn get_payments(
+    app: &Router,
+    corridor: &str,
+    query: &str,
+) -> (StatusCode, serde_json::Value) {
+    // `>` is not allowed in a URI path
+    let uri = format!(
+        "/api/corridors/{}/payments?{}",
+        corridor.replace('>', "%3E"),
+        query
+    );
+    let response = app
+        .clone()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn ids(body: &serde_json::Value) -> Vec<String> {
+    body["payments"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_payments_are_filtered_by_pair_and_window(pool: SqlitePool) {
+    seed(&pool).await;
+    let app = app(pool);
+    let usdc = format!("USDC:{0}->USDC:{0}", CIRCLE);
+
+    let (status, body) = get_payments(&app, &usdc, "window=24h").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&body), ["usdc-1h", "usdc-2h", "usdc-3h"]);
+    assert_eq!(body["window"], "24h");
+    assert_eq!(body["next_cursor"], serde_json::Value::Null);
+
+    let (_, body) = get_payments(&app, &usdc, "window=7d").await;
+    assert_eq!(ids(&body), ["usdc-1h", "usdc-2h", "usdc-3h", "usdc-30h"]);
+
+    // The window defaults to 24 hours
+    let (_, body) = get_payments(&app, &usdc, "").await;
+    assert_eq!(ids(&body).len(), 3);
+
+    let (_, body) = get_payments(&app, "XLM:native->XLM:native", "window=24h").await;
+    assert_eq!(ids(&body), ["xlm"]);
+
+    // Path payments make up the corridor from the asset sent to the one
+    // delivered
+    let (status, body) = get_payments(&app, &format!("USDC:{0}->EURC:{0}", CIRCLE), "").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&body), ["usdc-to-eurc"]);
+
+    let (_, body) = get_payments(&app, &format!("EURC:{0}->USDC:{0}", CIRCLE), "").await;
+    assert!(ids(&body).is_empty());
+
+    let (_, body) = get_payments(&app, &format!("XLM:native->USDC:{}", CIRCLE), "").await;
+    assert_eq!(ids(&body), ["xlm-to-usdc"]);
+
+    // Asset codes are case-sensitive
+    let (_, body) = get_payments(&app, &format!("usdc:{0}->usdc:{0}", CIRCLE), "").await;
+    assert!(ids(&body).is_empty());
+}
+
+#[sqlx::test]
+async fn test_payments_page_with_cursor(pool: SqlitePool) {
+    seed(&pool).await;
+    let app = app(pool);
+    let usdc = format!("USDC:{0}->USDC:{0}", CIRCLE);
+
+    let (_, first) = get_payments(&app, &usdc, "window=7d&limit=3").await;
+    assert_eq!(ids(&first), ["usdc-1h", "usdc-2h", "usdc-3h"]);
+    let cursor = first["next_cursor"].as_str().unwrap();
+
+    let (_, second) =
+        get_payments(&app, &usdc, &format!("window=7d&limit=3&cursor={}", cursor)).await;
+    assert_eq!(ids(&second), ["usdc-30h"]);
+    assert_eq!(second["next_cursor"], serde_json::Value::Null);
+
+    let (status, body) = get_payments(&app, &usdc, "cursor=bogus").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "INVALID_CURSOR");
+}
+
+#[sqlx::test]
+async fn test_invalid_requests_are_rejected(pool: SqlitePool) {
+    let app = app(pool);
+    let usdc = format!("USDC:{0}->USDC:{0}", CIRCLE);
+
+    for window in ["0h", "24", "1w", "91d", "h"] {
+        let (status, body) = get_payments(&app, &usdc, &format!("window={}", window)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{window}");
+        assert_eq!(body["error"]["code"], "INVALID_WINDOW");
+    }
+
+    let (status, body) = get_payments(&app, "USDC", "").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "INVALID_CORRIDOR_FORMAT");
+}
@@ -5,7 +5,8 @@
 /// - Preflight (OPTIONS) requests return the expected headers and 200/204 status
 /// - Non-matching origin does NOT receive Access-Control-Allow-Origin
 /// - Wildcard "*" origin configuration reflects properly
-/// - max-age header is present on preflight responses
+/// - max-age header is present on preflight responses and follows configuration
+/// - Vary: Origin is set on simple and preflight responses
 use axum::{
     body::Body,
     http::{header, Method, Request, StatusCode},
@@ -13,8 +14,9 @@ use axum::{
     Router,
 };
 use std::time::Duration;
+use stellar_insights_backend::cors::{cors_layer, DEFAULT_CORS_MAX_AGE};
 use tower::util::ServiceExt;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::CorsLayer;
 
 // ---------------------------------------------------------------------------
 // Helpers
@@ -27,38 +29,20 @@ fn build_router_with_cors(cors: CorsLayer) -> Router {
         .layer(cors)
 }
 
-/// Build a CorsLayer that mirrors the real application logic for a given
-/// `CORS_ALLOWED_ORIGINS` value (mirrors `main.rs`).
+/// Build the application's CorsLayer for a given `CORS_ALLOWED_ORIGINS` value.
 fn cors_layer_from_origins(cors_allowed_origins: &str) -> CorsLayer {
-    let methods = [
-        Method::GET,
-        Method::POST,
-        Method::PUT,
-        Method::DELETE,
-        Method::OPTIONS,
-        Method::PATCH,
-        Method::HEAD,
-    ];
-
-    let base = CorsLayer::new()
-        .allow_methods(methods)
-        .allow_headers(Any)
-        .max_age(Duration::from_secs(3600));
-
-    if cors_allowed_origins.trim() == "*" {
-        base.allow_origin(Any)
-    } else {
-        let origins: Vec<axum::http::HeaderValue> = cors_allowed_origins
-            .split(',')
-            .filter_map(|o| o.trim().parse::<axum::http::HeaderValue>().ok())
-            .collect();
-
-        if origins.is_empty() {
-            base.allow_origin(Any)
-        } else {
-            base.allow_origin(origins)
-        }
-    }
+    cors_layer(cors_allowed_origins, DEFAULT_CORS_MAX_AGE).unwrap()
+}
+
+/// Whether the response's `Vary` header lists `name`.
+fn varies_on(response: &axum::response::Response, name: &str) -> bool {
+    response
+        .headers()
+        .get_all(header::VARY)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|v| v.trim().eq_ignore_ascii_case(name))
 }
 
 // ---------------------------------------------------------------------------
@@ -342,3 +326,65 @@ async fn test_cors_production_origin_receives_acao_header() {
 
     assert_eq!(acao, "https://stellar-insights.com");
 }
+
+// ---------------------------------------------------------------------------
+// Tests – Configured max-age and Vary
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn test_cors_preflight_uses_configured_max_age() {
+    let cors = cors_layer("http://localhost:3000", Duration::from_secs(600)).unwrap();
+    let app = build_router_with_cors(cors);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/health")
+                .header(header::ORIGIN, "http://localhost:3000")
+                .header("access-control-request-method", "GET")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response.headers().get("access-control-max-age").unwrap(),
+        "600"
+    );
+    assert!(varies_on(&response, "origin"));
+    assert!(varies_on(&response, "access-control-request-method"));
+    assert!(varies_on(&response, "access-control-request-headers"));
+}
+
+#[tokio::test]
+async fn test_cors_simple_response_varies_on_origin() {
+    for origin in ["http://localhost:3000", "http://evil.example.com"] {
+        let app = build_router_with_cors(cors_layer_from_origins("http://localhost:3000"));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::GET)
+                    .uri("/health")
+                    .header(header::ORIGIN, origin)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(
+            varies_on(&response, "origin"),
+            "no Vary: Origin for {}",
+            origin
+        );
+    }
+}
+
+#[test]
+fn test_cors_rejects_origin_list_without_valid_entries() {
+    assert!(cors_layer("bad\norigin", DEFAULT_CORS_MAX_AGE).is_err());
+}
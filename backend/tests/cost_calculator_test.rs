@@ -3,15 +3,56 @@ use std::sync::Arc;
 
 use axum::body::{to_bytes, Body};
 use axum::http::{header::IF_NONE_MATCH, HeaderValue, Request, StatusCode};
+use stellar_insights_backend::api::cost_calculator::{routes_with_state, CostCalculatorState};
+use stellar_insights_backend::services::fee_estimator::FeeEstimator;
+use stellar_insights_backend::services::pair_registry::PairRegistry;
 use stellar_insights_backend::services::price_feed::{PriceFeedClient, PriceFeedConfig};
 use tower::util::ServiceExt;
 
-fn test_app() -> axum::Router {
-    let price_feed = Arc::new(PriceFeedClient::new(
+fn price_feed() -> Arc<PriceFeedClient> {
+    Arc::new(PriceFeedClient::new(
         PriceFeedConfig::default(),
         HashMap::new(),
-    ));
-    stellar_insights_backend::api::cost_calculator::routes(price_feed)
+    ))
+}
+
+fn test_app() -> axum::Router {
+    stellar_insights_backend::api::cost_calculator::routes(price_feed())
+}
+
+fn app_with_pairs(pairs: &str) -> axum::Router {
+    routes_with_state(CostCalculatorState {
+        price_feed: price_feed(),
+        fee_estimator: Arc::new(FeeEstimator::static_fee()),
+        path_finder: None,
+        pair_registry: Arc::new(PairRegistry::parse(pairs).unwrap()),
+    })
+}
+
+async fn post_estimate(
+    app: axum::Router,
+    source: &str,
+    destination: &str,
+) -> (StatusCode, serde_json::Value) {
+    let request_body = serde_json::json!({
+        "source_currency": source,
+        "destination_currency": destination,
+        "source_amount": 100.0
+    });
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/estimate")
+                .header("content-type", "application/json")
+                .body(Body::from(request_body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
 }
 
 #[tokio::test]
@@ -101,3 +142,17 @@ async fn estimate_supports_conditional_etag_requests() {
 
     assert_eq!(second_response.status(), StatusCode::NOT_MODIFIED);
 }
+
+#[tokio::test]
+async fn estimate_serves_registered_pairs_only() {
+    let app = app_with_pairs("USDC/NGN");
+
+    let (status, _) = post_estimate(app.clone(), "USDC", "NGN").await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = post_estimate(app.clone(), "ngn", "usdc").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = post_estimate(app, "USDC", "PHP").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"], "Asset pair USDC/PHP is not supported");
+}
@@ -0,0 +1,26 @@
+use sqlx::sqlite::SqlitePoolOptions;
+use stellar_insights_backend::database::Database;
+
+#[tokio::test]
+async fn test_health_check_returns_latency() {
+    let pool = SqlitePoolOptions::new()
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let db = Database::new(pool);
+
+    let latency = db.health_check().await.unwrap();
+    assert!(latency.as_secs() < 2);
+}
+
+#[tokio::test]
+async fn test_health_check_fails_on_closed_pool() {
+    let pool = SqlitePoolOptions::new()
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    let db = Database::new(pool.clone());
+    pool.close().await;
+
+    assert!(db.health_check().await.is_err());
+}
@@ -0,0 +1,92 @@
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::{Connection, SqliteConnection, SqlitePool};
+use std::str::FromStr;
+use std::time::Duration;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::retry::{is_transient, retry_transient, RetryPolicy};
+use stellar_insights_backend::models::CreateAnchorRequest;
+
+fn anchor(account: &str) -> CreateAnchorRequest {
+    CreateAnchorRequest {
+        name: "Anchor".to_string(),
+        stellar_account: account.to_string(),
+        home_domain: None,
+    }
+}
+
+#[tokio::test]
+async fn test_busy_write_succeeds_on_retry() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = format!("sqlite://{}", dir.path().join("busy.db").display());
+    let options = SqliteConnectOptions::from_str(&url)
+        .unwrap()
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Delete);
+
+    // No busy wait inside SQLite, so a held lock fails the write at once
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options.clone().busy_timeout(Duration::ZERO))
+        .await
+        .unwrap();
+    sqlx::migrate!("./migrations").run(&pool).await.unwrap();
+
+    let mut locker = SqliteConnection::connect_with(&options).await.unwrap();
+    sqlx::query("BEGIN EXCLUSIVE")
+        .execute(&mut locker)
+        .await
+        .unwrap();
+
+    let no_retry = Database::new(pool.clone()).with_retry_policy(RetryPolicy::none());
+    let err = no_retry.create_anchor(anchor("GBUSY1")).await.unwrap_err();
+    assert!(is_transient(&err), "{:?}", err);
+
+    // Release the lock while the retries are backing off
+    let release = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        sqlx::query("ROLLBACK").execute(&mut locker).await.unwrap();
+    });
+    let db = Database::new(pool).with_retry_policy(RetryPolicy {
+        max_retries: 5,
+        backoff: Duration::from_millis(100),
+    });
+    let created = db.create_anchor(anchor("GBUSY2")).await.unwrap();
+    assert_eq!(created.stellar_account, "GBUSY2");
+    release.await.unwrap();
+}
+
+#[sqlx::test]
+async fn test_constraint_violation_is_not_retried(pool: SqlitePool) {
+    let policy = RetryPolicy {
+        max_retries: 5,
+        backoff: Duration::from_millis(10),
+    };
+    let insert = || {
+        sqlx::query("INSERT INTO anchors (id, name, stellar_account) VALUES ('dup', 'Dup', 'GDUP')")
+            .execute(&pool)
+    };
+    retry_transient(&policy, "insert_anchor", insert)
+        .await
+        .unwrap();
+
+    let mut attempts = 0;
+    let err = retry_transient(&policy, "insert_anchor", || {
+        attempts += 1;
+        insert()
+    })
+    .await
+    .unwrap_err();
+    assert_eq!(attempts, 1);
+    assert!(!is_transient(&err));
+
+    // The same holds through the Database layer
+    let db = Database::new(pool).with_retry_policy(RetryPolicy {
+        max_retries: 5,
+        backoff: Duration::from_secs(10),
+    });
+    let err = tokio::time::timeout(Duration::from_secs(5), db.create_anchor(anchor("GDUP")))
+        .await
+        .expect("constraint violation was retried")
+        .unwrap_err();
+    assert!(!is_transient(&err));
+}
@@ -0,0 +1,84 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::broadcast::broadcast_domain_event;
+use stellar_insights_backend::services::event_bus::{DomainEvent, EventBus};
+use stellar_insights_backend::services::webhook_dispatcher::WebhookDispatcher;
+use stellar_insights_backend::webhooks::events::AnchorStatusChangedEvent;
+use stellar_insights_backend::websocket::{WsMessage, WsState};
+
+async fn seed_webhook(pool: &SqlitePool, id: &str, event_types: &str, is_active: bool) {
+    sqlx::query("INSERT OR IGNORE INTO users (id, username) VALUES ('user-1', 'tester')")
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query(
+        "INSERT INTO webhooks (id, user_id, url, event_types, secret, is_active) VALUES (?, 'user-1', 'https://example.com/hook', ?, 'secret', ?)",
+    )
+    .bind(id)
+    .bind(event_types)
+    .bind(is_active)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+fn anchor_status_event() -> DomainEvent {
+    DomainEvent::AnchorStatusChanged(AnchorStatusChangedEvent {
+        anchor_id: "anchor-1".to_string(),
+        name: "Test Anchor".to_string(),
+        old_status: "green".to_string(),
+        new_status: "red".to_string(),
+        reliability_score: 40.0,
+        failed_txn_count: 25,
+    })
+}
+
+#[sqlx::test]
+async fn test_event_produces_webhook_row_and_ws_broadcast(pool: SqlitePool) {
+    seed_webhook(&pool, "wh-anchor", "anchor.status_changed", true).await;
+    seed_webhook(&pool, "wh-payment", "payment.created", true).await;
+    seed_webhook(&pool, "wh-inactive", "anchor.status_changed", false).await;
+
+    let bus = EventBus::new();
+    let mut webhook_rx = bus.subscribe();
+    let mut ws_rx = bus.subscribe();
+
+    let ws_state = Arc::new(WsState::new());
+    let mut client_rx = ws_state.tx.subscribe();
+    let dispatcher = WebhookDispatcher::new(pool.clone());
+
+    assert_eq!(bus.publish(anchor_status_event()), 2);
+
+    // Webhook consumer
+    let event = webhook_rx.recv().await.unwrap();
+    let queued = dispatcher.enqueue_event(&event).await.unwrap();
+    assert_eq!(queued, 1);
+
+    let rows: Vec<(String, String, String)> =
+        sqlx::query_as("SELECT webhook_id, event_type, status FROM webhook_events")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        rows,
+        vec![(
+            "wh-anchor".to_string(),
+            "anchor.status_changed".to_string(),
+            "pending".to_string()
+        )]
+    );
+
+    // WebSocket consumer
+    let event = ws_rx.recv().await.unwrap();
+    broadcast_domain_event(&ws_state, &event);
+
+    match client_rx.recv().await.unwrap() {
+        WsMessage::AnchorUpdate {
+            anchor_id, status, ..
+        } => {
+            assert_eq!(anchor_id, "anchor-1");
+            assert_eq!(status, "red");
+        }
+        other => panic!("unexpected message: {:?}", other),
+    }
+}
@@ -1,7 +1,13 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use chrono::Utc;
 use sqlx::SqlitePool;
 use stellar_insights_backend::rpc::{FeeBumpTransactionInfo, HorizonTransaction, InnerTransaction};
 use stellar_insights_backend::services::fee_bump_tracker::FeeBumpTrackerService;
+use stellar_xdr::curr::{
+    Hash, InnerTransactionResult, InnerTransactionResultExt, InnerTransactionResultPair,
+    InnerTransactionResultResult, Limits, TransactionResult, TransactionResultExt,
+    TransactionResultResult, WriteXdr,
+};
 
 #[sqlx::test]
 async fn test_fee_bump_tracker_process_transactions(pool: SqlitePool) {
@@ -30,6 +36,7 @@ async fn test_fee_bump_tracker_process_transactions(pool: SqlitePool) {
             max_fee: Some("500".to_string()),
             signatures: vec!["sig1".to_string()],
         }),
+        result_xdr: None,
     };
 
     let tx2 = HorizonTransaction {
@@ -46,6 +53,7 @@ async fn test_fee_bump_tracker_process_transactions(pool: SqlitePool) {
         paging_token: "pt2".to_string(),
         fee_bump_transaction: None,
         inner_transaction: None,
+        result_xdr: None,
     };
 
     let transactions = vec![tx1, tx2];
@@ -76,4 +84,117 @@ async fn test_fee_bump_tracker_process_transactions(pool: SqlitePool) {
     assert_eq!(stats.max_fee_charged, 100);
     assert_eq!(stats.min_fee_charged, 100);
     assert_eq!(stats.unique_fee_sources, 1);
+    assert_eq!(stats.sponsored_failed_count, 0);
+}
+
+fn fee_bump_tx(hash: &str, inner_hash: &str, successful: bool) -> HorizonTransaction {
+    HorizonTransaction {
+        id: hash.to_string(),
+        hash: hash.to_string(),
+        ledger: 100,
+        created_at: Utc::now().to_rfc3339(),
+        source_account: "src".to_string(),
+        fee_account: Some("sponsor".to_string()),
+        fee_charged: Some("200".to_string()),
+        max_fee: Some("1000".to_string()),
+        operation_count: 1,
+        successful,
+        paging_token: hash.to_string(),
+        fee_bump_transaction: Some(FeeBumpTransactionInfo {
+            hash: hash.to_string(),
+            signatures: vec!["sig".to_string()],
+        }),
+        inner_transaction: Some(InnerTransaction {
+            hash: inner_hash.to_string(),
+            max_fee: Some("100".to_string()),
+            signatures: vec!["sig".to_string()],
+        }),
+        result_xdr: None,
+    }
+}
+
+/// `txFeeBumpInnerFailed` result whose inner transaction failed with `txBadSeq`
+fn inner_failed_result_xdr() -> String {
+    let result = TransactionResult {
+        fee_charged: 200,
+        result: TransactionResultResult::TxFeeBumpInnerFailed(InnerTransactionResultPair {
+            transaction_hash: Hash([0; 32]),
+            result: InnerTransactionResult {
+                fee_charged: 100,
+                result: InnerTransactionResultResult::TxBadSeq,
+                ext: InnerTransactionResultExt::V0,
+            },
+        }),
+        ext: TransactionResultExt::V0,
+    };
+    BASE64.encode(result.to_xdr(Limits::none()).unwrap())
+}
+
+#[test]
+fn test_inner_failure_is_correlated() {
+    // Wrapper reported successful but its result says the inner tx failed
+    let mut decoded = fee_bump_tx("fb1", "inner1", true);
+    decoded.result_xdr = Some(inner_failed_result_xdr());
+
+    // No result XDR: fall back to the wrapper's successful flag
+    let flagged = fee_bump_tx("fb2", "inner2", false);
+
+    // The inner transaction's own record in the batch takes precedence
+    let joined = fee_bump_tx("fb3", "inner3", true);
+    let mut inner = fee_bump_tx("inner3", "unused", false);
+    inner.fee_bump_transaction = None;
+    inner.inner_transaction = None;
+
+    let succeeded = fee_bump_tx("fb4", "inner4", true);
+
+    let fee_bumps =
+        FeeBumpTrackerService::extract_fee_bumps(&[decoded, flagged, joined, inner, succeeded]);
+    let outcomes: Vec<(&str, bool)> = fee_bumps
+        .iter()
+        .map(|fb| (fb.transaction_hash.as_str(), fb.inner_successful))
+        .collect();
+    assert_eq!(
+        outcomes,
+        vec![
+            ("fb1", false),
+            ("fb2", false),
+            ("fb3", false),
+            ("fb4", true)
+        ]
+    );
+}
+
+#[sqlx::test]
+async fn test_sponsored_but_failed_count(pool: SqlitePool) {
+    let service = FeeBumpTrackerService::new(pool.clone());
+
+    sqlx::query("INSERT INTO ledgers (sequence, hash, close_time, transaction_count, operation_count) VALUES (100, 'ledger_hash', '2026-01-01T00:00:00Z', 0, 0)")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let mut failed = fee_bump_tx("fb_failed", "inner_failed", true);
+    failed.result_xdr = Some(inner_failed_result_xdr());
+    let transactions = vec![
+        failed,
+        fee_bump_tx("fb_ok", "inner_ok", true),
+        fee_bump_tx("fb_bad_seq", "inner_bad_seq", false),
+    ];
+
+    assert_eq!(
+        service.process_transactions(&transactions).await.unwrap(),
+        3
+    );
+
+    let stats = service.get_fee_bump_stats().await.unwrap();
+    assert_eq!(stats.total_fee_bumps, 3);
+    assert_eq!(stats.sponsored_failed_count, 2);
+
+    let failures = service.get_sponsored_failures(10).await.unwrap();
+    let mut hashes: Vec<&str> = failures
+        .iter()
+        .map(|fb| fb.transaction_hash.as_str())
+        .collect();
+    hashes.sort_unstable();
+    assert_eq!(hashes, vec!["fb_bad_seq", "fb_failed"]);
 }
@@ -81,7 +81,7 @@ async fn test_snapshot_storage_with_hash_and_epoch() {
         .unwrap();
 
     // Test 4: List snapshots (should be ordered by epoch DESC)
-    let snapshots: Vec<SnapshotRecord> = db.list_snapshots(10, 0).await.unwrap();
+    let snapshots: Vec<SnapshotRecord> = db.list_snapshots(None, 10, 0).await.unwrap();
     assert_eq!(snapshots.len(), 3);
     assert_eq!(snapshots[0].epoch, Some(200)); // Most recent
     assert_eq!(snapshots[1].epoch, Some(150));
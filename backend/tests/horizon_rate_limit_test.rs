@@ -0,0 +1,94 @@
+use axum::{http::StatusCode, response::IntoResponse, routing::get, Json, Router};
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use stellar_insights_backend::rpc::StellarRpcClient;
+
+/// Horizon mock whose ledgers and payments endpoints answer 429 with a
+/// problem+json body carrying `reset` on the first call, then succeed
+async fn rate_limited_horizon() -> String {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let respond = move |success: serde_json::Value| {
+        let calls = Arc::clone(&calls);
+        move || {
+            let calls = Arc::clone(&calls);
+            let success = success.clone();
+            async move {
+                if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    let problem = json!({
+                        "type": "https://stellar.org/horizon-errors/rate_limit_exceeded",
+                        "title": "Rate Limit Exceeded",
+                        "status": 429,
+                        "detail": "The rate limit for the requesting IP address is over its allotted limit.",
+                        "extras": { "reset": 1 }
+                    });
+                    (StatusCode::TOO_MANY_REQUESTS, Json(problem)).into_response()
+                } else {
+                    Json(success).into_response()
+                }
+            }
+        }
+    };
+
+    let app = Router::new()
+        .route(
+            "/ledgers",
+            get(respond(json!({ "_embedded": { "records": [{
+                "sequence": 100,
+                "hash": "ledger",
+                "previous_hash": "prev",
+                "transaction_count": 1,
+                "operation_count": 1,
+                "closed_at": "2026-01-01T00:00:00Z",
+                "total_coins": "1",
+                "fee_pool": "1",
+                "base_fee": 100,
+                "base_reserve": "0.5"
+            }] } }))),
+        )
+        .route(
+            "/accounts/:account/payments",
+            get(respond(json!({ "_embedded": { "records": [] } }))),
+        );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+fn client(horizon_url: String) -> StellarRpcClient {
+    StellarRpcClient::new("http://127.0.0.1:1/rpc".to_string(), horizon_url, false)
+}
+
+/// The retry waits for the reset from the body: longer than the fixed
+/// backoff, shorter than the default rate-limit wait
+fn assert_waited_for_reset(elapsed: Duration) {
+    assert!(elapsed >= Duration::from_millis(950), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(4), "{:?}", elapsed);
+}
+
+#[tokio::test]
+async fn test_retry_waits_for_horizon_reset() {
+    let client = client(rate_limited_horizon().await);
+
+    let start = Instant::now();
+    let ledger = client.fetch_latest_ledger().await.unwrap();
+    assert_waited_for_reset(start.elapsed());
+    assert_eq!(ledger.sequence, 100);
+}
+
+#[tokio::test]
+async fn test_rate_limiter_backs_off_until_horizon_reset() {
+    let client = client(rate_limited_horizon().await);
+
+    let start = Instant::now();
+    let payments = client
+        .fetch_all_account_payments("GACCOUNT", Some(10))
+        .await
+        .unwrap();
+    assert_waited_for_reset(start.elapsed());
+    assert!(payments.is_empty());
+    assert_eq!(client.rate_limit_metrics().rate_limited_responses, 1);
+}
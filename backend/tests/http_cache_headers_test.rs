@@ -0,0 +1,83 @@
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::response::Response;
+use axum::{middleware, Extension, Router};
+use chrono::Utc;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::api::{metrics_cached, webhooks};
+use stellar_insights_backend::auth::Claims;
+use stellar_insights_backend::auth_middleware::{auth_middleware, JwtSecret};
+use stellar_insights_backend::cache::{CacheConfig, CacheManager};
+use stellar_insights_backend::webhooks::WebhookLimits;
+use tower::util::ServiceExt;
+
+const SECRET: &str = "test-secret-that-is-at-least-32-characters";
+
+async fn get(app: Router, uri: &str, token: Option<&str>) -> Response {
+    let mut request = Request::get(uri);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    app.oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+fn cache_control(response: &Response) -> Option<&str> {
+    response
+        .headers()
+        .get(header::CACHE_CONTROL)
+        .map(|value| value.to_str().unwrap())
+}
+
+#[tokio::test]
+async fn test_cached_endpoint_sends_configured_max_age() {
+    let config = CacheConfig {
+        dashboard_http_max_age: Some(45),
+        ..CacheConfig::default()
+    };
+    let cache = Arc::new(CacheManager::new(config).await.unwrap());
+
+    let response = get(metrics_cached::routes(cache), "/api/metrics/overview", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(cache_control(&response), Some("public, max-age=45"));
+}
+
+#[tokio::test]
+async fn test_max_age_defaults_to_cache_ttl() {
+    let config = CacheConfig::default();
+    assert_eq!(config.http_max_age("dashboard"), config.dashboard_stats_ttl);
+    let cache = Arc::new(CacheManager::new(config).await.unwrap());
+
+    let response = get(metrics_cached::routes(cache), "/api/metrics/overview", None).await;
+    assert_eq!(cache_control(&response), Some("public, max-age=60"));
+}
+
+#[sqlx::test]
+async fn test_authenticated_endpoint_has_no_cache_control(pool: SqlitePool) {
+    let app = webhooks::routes(pool, WebhookLimits::default())
+        .layer(middleware::from_fn(auth_middleware))
+        .layer(Extension(JwtSecret(Arc::from(SECRET))));
+
+    let now = Utc::now().timestamp();
+    let claims = Claims {
+        sub: "user-1".to_string(),
+        username: "alice".to_string(),
+        exp: now + 3600,
+        iat: now,
+        token_type: "access".to_string(),
+        scopes: vec!["admin".to_string()],
+    };
+    let token = encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(SECRET.as_bytes()),
+    )
+    .unwrap();
+
+    let response = get(app, "/api/webhooks", Some(&token)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(cache_control(&response), None);
+}
@@ -0,0 +1,175 @@
+use sqlx::SqlitePool;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use stellar_insights_backend::services::idempotency::{
+    AttemptInProgress, IdempotencyConflict, IdempotencyStore,
+};
+
+async fn attempts(pool: &SqlitePool, key: &str) -> (String, i64) {
+    sqlx::query_as("SELECT status, attempts FROM idempotency_keys WHERE key = ?")
+        .bind(key)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn test_completed_operation_runs_once(pool: SqlitePool) {
+    let store = IdempotencyStore::new(pool.clone());
+    let side_effects = &AtomicUsize::new(0);
+    let op = || async move {
+        let n = side_effects.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(format!("tx-{}", n))
+    };
+
+    let first: String = store.submit_idempotent("snapshot:7", op).await.unwrap();
+    let retried: String = store.submit_idempotent("snapshot:7", op).await.unwrap();
+
+    assert_eq!(side_effects.load(Ordering::SeqCst), 1);
+    assert_eq!(first, "tx-1");
+    assert_eq!(retried, first);
+    assert_eq!(
+        attempts(&pool, "snapshot:7").await,
+        ("completed".to_string(), 1)
+    );
+
+    // Another key is a separate operation
+    let other: String = store.submit_idempotent("snapshot:8", op).await.unwrap();
+    assert_eq!(other, "tx-2");
+}
+
+#[sqlx::test]
+async fn test_failed_attempt_is_retried(pool: SqlitePool) {
+    let store = IdempotencyStore::new(pool.clone());
+    let calls = &AtomicUsize::new(0);
+    let op = || async move {
+        if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+            anyhow::bail!("connection reset");
+        }
+        Ok(())
+    };
+
+    let err = store
+        .submit_idempotent::<(), _, _>("webhook_event:1", op)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("connection reset"));
+    assert_eq!(attempts(&pool, "webhook_event:1").await.0, "failed");
+
+    store
+        .submit_idempotent("webhook_event:1", op)
+        .await
+        .unwrap();
+    store
+        .submit_idempotent("webhook_event:1", op)
+        .await
+        .unwrap();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        attempts(&pool, "webhook_event:1").await,
+        ("completed".to_string(), 2)
+    );
+}
+
+#[sqlx::test]
+async fn test_concurrent_attempt_is_refused_until_stale(pool: SqlitePool) {
+    let store = IdempotencyStore::new(pool.clone());
+
+    // A nested call with the key finds the outer attempt in progress
+    let nested = store
+        .submit_idempotent("snapshot:9", || async {
+            let inner = store
+                .submit_idempotent("snapshot:9", || async { Ok(2) })
+                .await;
+            let err = inner.unwrap_err();
+            assert_eq!(
+                err.downcast_ref::<AttemptInProgress>(),
+                Some(&AttemptInProgress {
+                    key: "snapshot:9".to_string()
+                })
+            );
+            Ok(1)
+        })
+        .await
+        .unwrap();
+    assert_eq!(nested, 1);
+
+    // An attempt that never finished is reclaimed once it is stale
+    sqlx::query(
+        "INSERT INTO idempotency_keys (key, status, attempts, claimed_at) VALUES ('snapshot:10', 'in_progress', 1, 0)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let reclaimed = store
+        .clone()
+        .with_claim_timeout(Duration::from_secs(60))
+        .submit_idempotent("snapshot:10", || async { Ok(3) })
+        .await
+        .unwrap();
+    assert_eq!(reclaimed, 3);
+    assert_eq!(
+        attempts(&pool, "snapshot:10").await,
+        ("completed".to_string(), 2)
+    );
+}
+
+#[sqlx::test]
+async fn test_completed_key_refuses_different_fingerprint(pool: SqlitePool) {
+    let store = IdempotencyStore::new(pool.clone());
+    let calls = &AtomicUsize::new(0);
+    let op = || async move {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Ok("tx-1".to_string())
+    };
+
+    store
+        .submit_idempotent_with_fingerprint("snapshot:7", "aa", op)
+        .await
+        .unwrap();
+    let retried: String = store
+        .submit_idempotent_with_fingerprint("snapshot:7", "aa", op)
+        .await
+        .unwrap();
+    assert_eq!(retried, "tx-1");
+
+    let err = store
+        .submit_idempotent_with_fingerprint::<String, _, _>("snapshot:7", "bb", op)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<IdempotencyConflict>(),
+        Some(&IdempotencyConflict {
+            key: "snapshot:7".to_string()
+        })
+    );
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[sqlx::test]
+async fn test_prune_expired_removes_old_keys(pool: SqlitePool) {
+    let store = IdempotencyStore::new(pool.clone()).with_key_ttl(Duration::from_secs(3600));
+    store
+        .submit_idempotent("webhook_event:old", || async { Ok(()) })
+        .await
+        .unwrap();
+    store
+        .submit_idempotent("webhook_event:new", || async { Ok(()) })
+        .await
+        .unwrap();
+    sqlx::query(
+        "UPDATE idempotency_keys SET updated_at = datetime('now', '-2 hours') WHERE key = ?",
+    )
+    .bind("webhook_event:old")
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    assert_eq!(store.prune_expired().await.unwrap(), 1);
+    let remaining: Vec<(String,)> = sqlx::query_as("SELECT key FROM idempotency_keys")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, vec![("webhook_event:new".to_string(),)]);
+}
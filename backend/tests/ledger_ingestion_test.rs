@@ -0,0 +1,297 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use std::time::Duration;
+use stellar_insights_backend::ingestion::asset_filter::AssetFilter;
+use stellar_insights_backend::ingestion::ledger::{
+    AdaptiveBatchSize, BatchSizeConfig, LedgerIngestionService,
+};
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::account_merge_detector::AccountMergeDetector;
+use stellar_insights_backend::services::fee_bump_tracker::FeeBumpTrackerService;
+
+fn ingestion_service(
+    pool: &SqlitePool,
+    rpc_client: Arc<StellarRpcClient>,
+) -> LedgerIngestionService {
+    LedgerIngestionService::new(
+        Arc::clone(&rpc_client),
+        Arc::new(FeeBumpTrackerService::new(pool.clone())),
+        Arc::new(AccountMergeDetector::new(pool.clone(), rpc_client)),
+        pool.clone(),
+    )
+}
+
+#[sqlx::test]
+async fn test_follow_advances_to_latest_ledger(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let health = rpc_client.check_health().await.unwrap();
+    let service = ingestion_service(&pool, Arc::clone(&rpc_client));
+
+    // First batch starts at the oldest retained ledger
+    let first = service.ingest_batch(20).await.unwrap();
+    assert_eq!(first.ingested, 20);
+    assert_eq!(first.last_ledger, Some(health.oldest_ledger + 19));
+    assert_eq!(first.latest_ledger, health.latest_ledger);
+    assert_eq!(
+        first.lag,
+        health.latest_ledger - (health.oldest_ledger + 19)
+    );
+    assert_eq!(service.lag_behind_head(), first.lag);
+
+    let mut progress = first;
+    for _ in 0..10 {
+        if progress.caught_up() {
+            break;
+        }
+        progress = service.ingest_batch(20).await.unwrap();
+    }
+
+    assert!(progress.caught_up());
+    assert_eq!(progress.last_ledger, Some(health.latest_ledger));
+    assert_eq!(service.lag_behind_head(), 0);
+
+    let ingested: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM ledgers")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        ingested.0 as u64,
+        health.latest_ledger - health.oldest_ledger + 1
+    );
+
+    // Polling at the head ingests nothing and keeps reporting zero lag
+    let idle = service.ingest_batch(20).await.unwrap();
+    assert_eq!(idle.ingested, 0);
+    assert_eq!(idle.last_ledger, Some(health.latest_ledger));
+    assert!(idle.caught_up());
+}
+
+async fn count_rows(pool: &SqlitePool, table: &str) -> i64 {
+    let row: (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM {}", table))
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    row.0
+}
+
+#[sqlx::test]
+async fn test_rerun_over_same_range_writes_no_duplicates(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let health = rpc_client.check_health().await.unwrap();
+    let service = ingestion_service(&pool, Arc::clone(&rpc_client));
+
+    let first = service.ingest_batch(10).await.unwrap();
+    assert_eq!(first.ingested, 10);
+    assert_eq!(first.skipped, 0);
+    assert_eq!(
+        service.last_processed_ledger().await.unwrap(),
+        Some(health.oldest_ledger + 9)
+    );
+
+    let payments = count_rows(&pool, "ledger_payments").await;
+    let fee_bumps = count_rows(&pool, "fee_bump_transactions").await;
+    let merges = count_rows(&pool, "account_merges").await;
+    assert_eq!(payments, 50);
+    assert!(fee_bumps > 0);
+    assert_eq!(merges, 20);
+
+    // Simulate a restart that lost the cursor and re-fetches the same ledgers
+    sqlx::query("DELETE FROM ingestion_cursor")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let rerun = service.ingest_batch(10).await.unwrap();
+    assert_eq!(rerun.ingested, 0);
+    assert_eq!(rerun.skipped, 10);
+    assert!(rerun.advanced());
+    assert!(service
+        .is_ledger_processed(health.oldest_ledger)
+        .await
+        .unwrap());
+
+    assert_eq!(count_rows(&pool, "ledger_payments").await, payments);
+    assert_eq!(count_rows(&pool, "fee_bump_transactions").await, fee_bumps);
+    assert_eq!(count_rows(&pool, "account_merges").await, merges);
+    assert_eq!(count_rows(&pool, "processed_ledgers").await, 10);
+}
+
+#[sqlx::test]
+async fn test_failed_ledger_stops_batch_and_holds_cursor(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let health = rpc_client.check_health().await.unwrap();
+    let service = ingestion_service(&pool, Arc::clone(&rpc_client));
+    let failing = health.oldest_ledger + 3;
+
+    // Make committing one ledger fail
+    sqlx::query(&format!(
+        "CREATE TRIGGER fail_ledger BEFORE INSERT ON processed_ledgers \
+         WHEN NEW.sequence = {} BEGIN SELECT RAISE(ABORT, 'injected failure'); END",
+        failing
+    ))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    assert!(service.ingest_batch(10).await.is_err());
+    assert_eq!(
+        service.last_processed_ledger().await.unwrap(),
+        Some(failing - 1)
+    );
+    assert!(!service.is_ledger_processed(failing + 1).await.unwrap());
+
+    let cursor: (i64,) =
+        sqlx::query_as("SELECT last_ledger_sequence FROM ingestion_cursor WHERE id = 1")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(cursor.0 as u64, failing - 1);
+
+    // Once the failure clears, ingestion resumes at the failed ledger
+    sqlx::query("DROP TRIGGER fail_ledger")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let retry = service.ingest_batch(10).await.unwrap();
+    assert_eq!(retry.ingested, 10);
+    assert_eq!(retry.last_ledger, Some(failing + 9));
+    assert!(service.is_ledger_processed(failing).await.unwrap());
+    assert_eq!(count_rows(&pool, "processed_ledgers").await, 13);
+}
+
+#[sqlx::test]
+async fn test_backfill_range_reports_counts_and_is_idempotent(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let health = rpc_client.check_health().await.unwrap();
+    let service = ingestion_service(&pool, Arc::clone(&rpc_client));
+    let start = health.oldest_ledger;
+
+    let report = service.backfill_range(start, start + 4).await.unwrap();
+    assert_eq!(report.ledgers_processed, 5);
+    assert_eq!(report.ledgers_skipped, 0);
+    assert_eq!(report.ledgers_failed, 0);
+    assert_eq!(report.payments, 5 * 5);
+    assert_eq!(report.merges, 5 * 2);
+    assert_eq!(
+        report.fee_bumps as i64,
+        count_rows(&pool, "fee_bump_transactions").await
+    );
+    assert!(report.fee_bumps > 0);
+
+    // Re-running the same range writes nothing
+    let rerun = service.backfill_range(start, start + 4).await.unwrap();
+    assert_eq!(rerun.ledgers_processed, 0);
+    assert_eq!(rerun.ledgers_skipped, 5);
+    assert_eq!((rerun.payments, rerun.fee_bumps, rerun.merges), (0, 0, 0));
+    assert_eq!(count_rows(&pool, "ledger_payments").await, 25);
+
+    // An overlapping range only processes the new ledgers
+    let overlap = service.backfill_range(start + 3, start + 6).await.unwrap();
+    assert_eq!(overlap.ledgers_processed, 2);
+    assert_eq!(overlap.ledgers_skipped, 2);
+    assert_eq!(overlap.payments, 10);
+
+    // Backfill leaves the live ingestion cursor untouched
+    assert_eq!(count_rows(&pool, "ingestion_cursor").await, 0);
+}
+
+async fn ingested_asset_codes(pool: &SqlitePool) -> Vec<(Option<String>, i64)> {
+    sqlx::query_as(
+        "SELECT asset_code, COUNT(*) FROM ledger_payments GROUP BY asset_code ORDER BY asset_code",
+    )
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+#[sqlx::test]
+async fn test_asset_allowlist_limits_stored_payments(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    // Each mock ledger pays in XLM twice, BRL once and LONGASSETCODE twice
+    let service =
+        ingestion_service(&pool, rpc_client).with_asset_filter(AssetFilter::new("brl", ""));
+
+    assert_eq!(service.ingest_batch(4).await.unwrap().ingested, 4);
+    assert_eq!(
+        ingested_asset_codes(&pool).await,
+        [(None, 8), (Some("BRL".to_string()), 4)]
+    );
+}
+
+#[sqlx::test]
+async fn test_asset_denylist_drops_one_issuer(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let service = ingestion_service(&pool, rpc_client).with_asset_filter(AssetFilter::new(
+        "",
+        "LONGASSETCODE:GISSUER01XXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX",
+    ));
+
+    assert_eq!(service.ingest_batch(4).await.unwrap().ingested, 4);
+    assert_eq!(
+        ingested_asset_codes(&pool).await,
+        [
+            (None, 8),
+            (Some("BRL".to_string()), 4),
+            (Some("LONGASSETCODE".to_string()), 4),
+        ]
+    );
+}
+
+#[sqlx::test]
+async fn test_backfill_rejects_inverted_range(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let service = ingestion_service(&pool, rpc_client);
+
+    assert!(service.backfill_range(10, 5).await.is_err());
+}
+
+#[sqlx::test]
+async fn test_adaptive_batch_size_grows_and_shrinks(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let service = ingestion_service(&pool, rpc_client);
+    let config = BatchSizeConfig {
+        initial: 2,
+        min: 1,
+        max: 16,
+        timeout: Duration::from_secs(30),
+    };
+    let mut sizer = AdaptiveBatchSize::new(&config);
+    assert_eq!(sizer.current(), 2);
+
+    // Successful batches that leave us behind the head double the size up to max
+    for expected in [4, 8, 16, 16] {
+        let progress = service
+            .ingest_adaptive(&mut sizer, config.timeout)
+            .await
+            .unwrap();
+        assert!(!progress.caught_up());
+        assert_eq!(sizer.current(), expected);
+    }
+
+    // Errors halve it
+    pool.close().await;
+    for expected in [8, 4] {
+        assert!(service
+            .ingest_adaptive(&mut sizer, config.timeout)
+            .await
+            .is_err());
+        assert_eq!(sizer.current(), expected);
+    }
+}
+
+#[test]
+fn test_adaptive_batch_size_respects_bounds() {
+    let mut sizer = AdaptiveBatchSize::new(&BatchSizeConfig {
+        initial: 100,
+        min: 2,
+        max: 8,
+        timeout: Duration::from_secs(1),
+    });
+    assert_eq!(sizer.current(), 8);
+
+    for _ in 0..5 {
+        sizer.record_failure();
+    }
+    assert_eq!(sizer.current(), 2);
+}
@@ -0,0 +1,126 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use std::time::Duration;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::db::metrics_buffer::{BufferedMetricsWriter, MetricsBufferConfig};
+use stellar_insights_backend::observability::metrics as obs_metrics;
+use stellar_insights_backend::shutdown::{ShutdownConfig, ShutdownCoordinator};
+
+const METRIC: &str = "buffered.latency_ms";
+
+async fn stored(pool: &SqlitePool) -> i64 {
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM metrics WHERE name = ?")
+        .bind(METRIC)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    count
+}
+
+fn writer(
+    pool: &SqlitePool,
+    max_batch_size: usize,
+    flush_interval: Duration,
+) -> BufferedMetricsWriter {
+    BufferedMetricsWriter::new(
+        Arc::new(Database::new(pool.clone())),
+        MetricsBufferConfig {
+            max_batch_size,
+            flush_interval,
+            ..MetricsBufferConfig::default()
+        },
+    )
+}
+
+#[sqlx::test]
+async fn test_metrics_flush_at_batch_size(pool: SqlitePool) {
+    let writer = writer(&pool, 3, Duration::from_secs(3600));
+
+    for value in [1.0, 2.0] {
+        writer.record(METRIC, value, None, None).await.unwrap();
+    }
+    assert_eq!(writer.pending_len().await, 2);
+    assert_eq!(stored(&pool).await, 0);
+
+    writer
+        .record(
+            METRIC,
+            3.0,
+            Some("a-1".to_string()),
+            Some("anchor".to_string()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(writer.pending_len().await, 0);
+    assert_eq!(stored(&pool).await, 3);
+
+    let (entity_id,): (Option<String>,) =
+        sqlx::query_as("SELECT entity_id FROM metrics WHERE name = ? AND value = 3.0")
+            .bind(METRIC)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(entity_id.as_deref(), Some("a-1"));
+}
+
+#[sqlx::test]
+async fn test_metrics_flush_on_interval(pool: SqlitePool) {
+    let writer = Arc::new(writer(&pool, 100, Duration::from_millis(50)));
+    let coordinator = ShutdownCoordinator::new(ShutdownConfig::default());
+    let flusher = writer.spawn_flusher(coordinator.subscribe());
+
+    writer.record(METRIC, 1.0, None, None).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(stored(&pool).await, 1);
+
+    coordinator.trigger_shutdown();
+    flusher.await.unwrap();
+}
+
+#[sqlx::test]
+async fn test_metrics_flush_on_shutdown(pool: SqlitePool) {
+    let writer = Arc::new(writer(&pool, 100, Duration::from_secs(3600)));
+    let coordinator = ShutdownCoordinator::new(ShutdownConfig::default());
+    let flusher = writer.spawn_flusher(coordinator.subscribe());
+
+    for value in [1.0, 2.0] {
+        writer.record(METRIC, value, None, None).await.unwrap();
+    }
+    assert_eq!(stored(&pool).await, 0);
+
+    coordinator.trigger_shutdown();
+    tokio::time::timeout(Duration::from_secs(5), flusher)
+        .await
+        .expect("flusher did not stop on shutdown")
+        .unwrap();
+    assert_eq!(writer.pending_len().await, 0);
+    assert_eq!(stored(&pool).await, 2);
+}
+
+#[sqlx::test]
+async fn test_full_buffer_drops_oldest_metrics(pool: SqlitePool) {
+    let writer = BufferedMetricsWriter::new(
+        Arc::new(Database::new(pool.clone())),
+        MetricsBufferConfig {
+            max_batch_size: 100,
+            flush_interval: Duration::from_secs(3600),
+            max_pending: 3,
+        },
+    );
+    let dropped = obs_metrics::buffered_metrics_dropped();
+
+    for value in [1.0, 2.0, 3.0, 4.0, 5.0] {
+        writer.record(METRIC, value, None, None).await.unwrap();
+    }
+    assert_eq!(writer.pending_len().await, 3);
+    assert!(obs_metrics::buffered_metrics_dropped() >= dropped + 2);
+
+    assert_eq!(writer.flush().await.unwrap(), 3);
+    let values: Vec<f64> =
+        sqlx::query_scalar("SELECT value FROM metrics WHERE name = ? ORDER BY value")
+            .bind(METRIC)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(values, [3.0, 4.0, 5.0]);
+}
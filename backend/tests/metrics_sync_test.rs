@@ -0,0 +1,69 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::ingestion::DataIngestionService;
+use stellar_insights_backend::rpc::StellarRpcClient;
+
+async fn seed_anchor(pool: &SqlitePool, id: &str, name: &str, account: &str) {
+    sqlx::query("INSERT INTO anchors (id, name, stellar_account) VALUES (?, ?, ?)")
+        .bind(id)
+        .bind(name)
+        .bind(account)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[sqlx::test]
+async fn test_sync_report_lists_failed_anchors(pool: SqlitePool) {
+    // Only the anchors below should be synced, not the seeded sample ones
+    sqlx::query("DELETE FROM anchors")
+        .execute(&pool)
+        .await
+        .unwrap();
+    seed_anchor(&pool, "a-1", "Healthy One", "GHEALTHY1").await;
+    seed_anchor(&pool, "a-2", "Broken", "GBROKEN").await;
+    seed_anchor(&pool, "a-3", "Healthy Two", "GHEALTHY2").await;
+
+    // Make the metrics update fail for one anchor only
+    sqlx::query(
+        r#"
+        CREATE TRIGGER fail_broken_anchor BEFORE UPDATE ON anchors
+        WHEN OLD.stellar_account = 'GBROKEN'
+        BEGIN
+            SELECT RAISE(ABORT, 'anchor update rejected');
+        END
+        "#,
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let service = DataIngestionService::new(
+        Arc::new(StellarRpcClient::new_with_defaults(true)),
+        Arc::new(Database::new(pool.clone())),
+    );
+
+    let report = service.sync_all_metrics().await.unwrap();
+    assert_eq!(report.succeeded, 2);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0, "Broken");
+    assert!(report.failed[0].1.contains("anchor update rejected"));
+    assert_eq!(report.total(), 3);
+    assert!((report.failure_ratio() - 1.0 / 3.0).abs() < f64::EPSILON);
+
+    let updated: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT stellar_account, total_transactions FROM anchors ORDER BY stellar_account",
+    )
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        updated,
+        vec![
+            ("GBROKEN".to_string(), 0),
+            ("GHEALTHY1".to_string(), 100),
+            ("GHEALTHY2".to_string(), 100),
+        ]
+    );
+}
@@ -0,0 +1,135 @@
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::muxed::DustThresholds;
+
+const BASE: &str = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+
+/// A 69 character address the analytics queries treat as muxed
+fn muxed(tag: char) -> String {
+    format!("M{}", tag.to_string().repeat(68))
+}
+
+const USDC_ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+async fn insert_payment(pool: &SqlitePool, id: &str, source: &str, destination: &str, amount: f64) {
+    insert_asset_payment(pool, id, source, destination, amount, None).await;
+}
+
+/// Insert a lumen payment, or a credit payment of `(code, issuer)`
+async fn insert_asset_payment(
+    pool: &SqlitePool,
+    id: &str,
+    source: &str,
+    destination: &str,
+    amount: f64,
+    asset: Option<(&str, &str)>,
+) {
+    let asset_type = if asset.is_some() {
+        "credit_alphanum4"
+    } else {
+        "native"
+    };
+    sqlx::query(
+        r#"
+        INSERT INTO payments (
+            id, transaction_hash, source_account, destination_account,
+            asset_type, asset_code, asset_issuer, amount, created_at
+        )
+        VALUES ($1, $1, $2, $3, $4, $5, $6, $7, '2026-01-01T00:00:00Z')
+        "#,
+    )
+    .bind(id)
+    .bind(source)
+    .bind(destination)
+    .bind(asset_type)
+    .bind(asset.map(|(code, _)| code))
+    .bind(asset.map(|(_, issuer)| issuer))
+    .bind(amount)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+fn thresholds(spec: &str) -> DustThresholds {
+    spec.parse().unwrap()
+}
+
+/// One dust spammer with many tiny payments and one account with a few
+/// real ones
+async fn seed(pool: &SqlitePool) -> (String, String) {
+    let spammer = muxed('A');
+    let real = muxed('B');
+    for i in 0..10 {
+        insert_payment(pool, &format!("dust-{}", i), &spammer, BASE, 0.0000001).await;
+    }
+    for i in 0..3 {
+        insert_payment(pool, &format!("real-{}", i), BASE, &real, 25.0).await;
+    }
+    (spammer, real)
+}
+
+#[sqlx::test]
+async fn test_dust_excluded_from_muxed_ranking(pool: SqlitePool) {
+    let (_, real) = seed(&pool).await;
+    let db = Database::new(pool).with_muxed_dust_thresholds(thresholds("XLM=1"));
+
+    let analytics = db.get_muxed_analytics(10).await.unwrap();
+    let top = analytics.top_muxed_by_activity.unwrap();
+    assert_eq!(top.len(), 1);
+    assert_eq!(top[0].account_address, real);
+    assert_eq!(top[0].payment_count_as_destination, 3);
+    assert_eq!(analytics.total_muxed_payments, Some(3));
+    assert_eq!(analytics.unique_muxed_addresses, Some(1));
+}
+
+#[sqlx::test]
+async fn test_no_threshold_counts_every_payment(pool: SqlitePool) {
+    let (spammer, real) = seed(&pool).await;
+    let db = Database::new(pool);
+
+    let analytics = db.get_muxed_analytics(10).await.unwrap();
+    let top = analytics.top_muxed_by_activity.unwrap();
+    let ranked: Vec<&str> = top.iter().map(|u| u.account_address.as_str()).collect();
+    assert_eq!(ranked, [spammer.as_str(), real.as_str()]);
+    assert_eq!(analytics.total_muxed_payments, Some(13));
+    assert_eq!(analytics.unique_muxed_addresses, Some(2));
+}
+
+#[sqlx::test]
+async fn test_thresholds_apply_per_asset(pool: SqlitePool) {
+    let (spammer, real) = seed(&pool).await;
+    let usdc = Some(("USDC", USDC_ISSUER));
+    let other_usdc = Some(("USDC", BASE));
+    // Half a USDC is a real payment even though it is below the XLM threshold
+    insert_asset_payment(&pool, "usdc-0", BASE, &spammer, 0.5, usdc).await;
+    insert_asset_payment(&pool, "usdc-1", BASE, &spammer, 0.001, usdc).await;
+    // Any issuer's USDC falls under the bare code entry
+    insert_asset_payment(&pool, "usdc-2", BASE, &spammer, 0.001, other_usdc).await;
+
+    let db = Database::new(pool).with_muxed_dust_thresholds(thresholds("XLM=1,USDC=0.1"));
+    let analytics = db.get_muxed_analytics(10).await.unwrap();
+    assert_eq!(analytics.total_muxed_payments, Some(4));
+    assert_eq!(analytics.unique_muxed_addresses, Some(2));
+    let top = analytics.top_muxed_by_activity.unwrap();
+    let ranked: Vec<(&str, i64)> = top
+        .iter()
+        .map(|u| (u.account_address.as_str(), u.total_payments))
+        .collect();
+    assert_eq!(ranked, [(real.as_str(), 3), (spammer.as_str(), 1)]);
+}
+
+#[sqlx::test]
+async fn test_issuer_threshold_wins_over_code_threshold(pool: SqlitePool) {
+    let (spammer, _) = seed(&pool).await;
+    let usdc = Some(("USDC", USDC_ISSUER));
+    insert_asset_payment(&pool, "usdc-0", BASE, &spammer, 0.5, usdc).await;
+
+    let spec = format!("USDC=0.1,USDC:{}=1", USDC_ISSUER);
+    let db = Database::new(pool).with_muxed_dust_thresholds(thresholds(&spec));
+    let analytics = db.get_muxed_analytics(10).await.unwrap();
+    // Ten lumen payments with no lumen threshold, the USDC one is dust
+    assert_eq!(analytics.total_muxed_payments, Some(13));
+    let top = analytics.top_muxed_by_activity.unwrap();
+    assert_eq!(top[0].account_address, spammer);
+    assert_eq!(top[0].payment_count_as_destination, 0);
+}
@@ -0,0 +1,128 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::api::overview::{routes, OverviewResponse};
+use stellar_insights_backend::cache::{keys, CacheConfig, CacheManager};
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use tower::util::ServiceExt;
+
+async fn get_overview(app: &Router) -> OverviewResponse {
+    let request = Request::get("/api/overview").body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn insert_anchor(pool: &SqlitePool, id: &str, account: &str, score: f64) {
+    sqlx::query(
+        "INSERT INTO anchors (id, name, stellar_account, reliability_score) VALUES (?, ?, ?, ?)",
+    )
+    .bind(id)
+    .bind(format!("Anchor {}", id))
+    .bind(account)
+    .bind(score)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn seed(pool: &SqlitePool, db: &Database) {
+    // Scores above the seeded rows so the inserted anchors and corridor lead
+    insert_anchor(pool, "a-1", "GANCHOR1", 100.0).await;
+    insert_anchor(pool, "a-2", "GANCHOR2", 99.5).await;
+    sqlx::query(
+        "INSERT INTO assets (id, anchor_id, asset_code, asset_issuer) VALUES ('s-1', 'a-1', 'USDC', 'GANCHOR1')",
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO corridors (id, source_asset_code, source_asset_issuer, destination_asset_code, destination_asset_issuer, reliability_score) VALUES ('c-1', 'USDC', 'GANCHOR1', 'EURC', 'GANCHOR2', 100.0)",
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+    db.create_snapshot(
+        "all",
+        "analytics",
+        serde_json::json!({}),
+        Some("abc".to_string()),
+        Some(7),
+    )
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn test_overview_sections_and_caching(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool.clone()));
+    let baseline = db.count_entities().await.unwrap();
+    seed(&pool, &db).await;
+
+    let cache = Arc::new(CacheManager::new(CacheConfig::default()).await.unwrap());
+    cache.delete(&keys::dashboard_overview()).await.unwrap();
+    let rpc = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let app = routes(Arc::clone(&db), Arc::clone(&cache), rpc);
+
+    let overview = get_overview(&app).await;
+    let anchors: Vec<_> = overview.top_anchors.iter().map(|a| a.id.as_str()).collect();
+    assert_eq!(anchors[..2], ["a-1", "a-2"]);
+    assert_eq!(overview.top_corridors[0].id, "c-1");
+    assert_eq!(overview.top_corridors[0].source_asset, "USDC:GANCHOR1");
+    assert!(overview.network_health.is_some());
+    assert_eq!(overview.latest_snapshot.as_ref().map(|s| s.epoch), Some(7));
+    assert_eq!(overview.counts.anchors, baseline.anchors + 2);
+    assert_eq!(overview.counts.corridors, baseline.corridors + 1);
+    assert_eq!(overview.counts.assets, baseline.assets + 1);
+    assert_eq!(overview.counts.snapshots, baseline.snapshots + 1);
+    // Nothing ingested yet, so the age of the data is unknown
+    assert_eq!(overview.data_as_of, None);
+    assert!(overview.stale);
+
+    // Without Redis every request is assembled afresh
+    if !cache.is_connected().await {
+        return;
+    }
+
+    // Within the TTL the cached overview is served, new rows and all
+    insert_anchor(&pool, "a-3", "GANCHOR3", 98.0).await;
+    let hits = cache.get_stats().hits;
+    let cached = get_overview(&app).await;
+    assert_eq!(cache.get_stats().hits, hits + 1);
+    assert_eq!(cached.counts.anchors, baseline.anchors + 2);
+    assert_eq!(cached.data_as_of, overview.data_as_of);
+
+    cache.delete(&keys::dashboard_overview()).await.unwrap();
+    assert_eq!(
+        get_overview(&app).await.counts.anchors,
+        baseline.anchors + 3
+    );
+}
+
+#[sqlx::test]
+async fn test_overview_data_as_of_is_latest_ledger_close(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool.clone()));
+    for (sequence, close_time) in [(100, "2026-01-15T10:00:00Z"), (101, "2026-01-15T10:00:05Z")] {
+        sqlx::query("INSERT INTO ledgers (sequence, hash, close_time) VALUES (?, ?, ?)")
+            .bind(sequence)
+            .bind(format!("hash-{}", sequence))
+            .bind(close_time)
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    let cache = Arc::new(CacheManager::new(CacheConfig::default()).await.unwrap());
+    cache.delete(&keys::dashboard_overview()).await.unwrap();
+    let rpc = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let app = routes(db, Arc::clone(&cache), rpc);
+
+    let overview = get_overview(&app).await;
+    assert_eq!(overview.data_as_of, "2026-01-15T10:00:05Z".parse().ok());
+    // Ledgers closed well in the past are stale however recently they were read
+    assert!(overview.stale);
+}
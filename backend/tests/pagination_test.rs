@@ -1,4 +1,74 @@
-use stellar_insights_backend::rpc::StellarRpcClient;
+use axum::{extract::Query, routing::get, Json, Router};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use stellar_insights_backend::rpc::{MaxRecordsExceeded, StellarRpcClient};
+
+/// Records the mock Horizon holds for each of payments and trades
+const HORIZON_RECORDS: u32 = 5;
+
+/// Page of `HORIZON_RECORDS` records numbered from 1, after `cursor`
+fn horizon_page(params: &HashMap<String, String>, record: fn(u32) -> Value) -> Json<Value> {
+    let after: u32 = params.get("cursor").map_or(0, |c| c.parse().unwrap());
+    let limit: u32 = params["limit"].parse().unwrap();
+    let records: Vec<Value> = (after + 1..=HORIZON_RECORDS)
+        .take(limit as usize)
+        .map(record)
+        .collect();
+    Json(json!({ "_embedded": { "records": records } }))
+}
+
+fn payment_record(n: u32) -> Value {
+    json!({
+        "id": n.to_string(),
+        "paging_token": n.to_string(),
+        "transaction_hash": format!("tx{}", n),
+        "source_account": "GSOURCE",
+        "destination": "GDEST",
+        "asset_type": "native",
+        "amount": "1.0000000",
+        "created_at": "2026-01-01T00:00:00Z"
+    })
+}
+
+fn trade_record(n: u32) -> Value {
+    json!({
+        "id": n.to_string(),
+        "ledger_close_time": "2026-01-01T00:00:00Z",
+        "base_account": "GBASE",
+        "base_amount": "1.0000000",
+        "base_asset_type": "native",
+        "counter_account": "GCOUNTER",
+        "counter_amount": "2.0000000",
+        "counter_asset_type": "native",
+        "price": { "n": 2, "d": 1 },
+        "trade_type": "orderbook"
+    })
+}
+
+async fn paged_horizon_client() -> StellarRpcClient {
+    let app = Router::new()
+        .route(
+            "/payments",
+            get(|Query(params): Query<HashMap<String, String>>| async move {
+                horizon_page(&params, payment_record)
+            }),
+        )
+        .route(
+            "/trades",
+            get(|Query(params): Query<HashMap<String, String>>| async move {
+                horizon_page(&params, trade_record)
+            }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    StellarRpcClient::new(
+        "http://127.0.0.1:1/rpc".to_string(),
+        format!("http://{}", addr),
+        false,
+    )
+}
 
 #[tokio::test]
 async fn test_fetch_all_payments_mock() {
@@ -58,3 +128,92 @@ async fn test_pagination_with_default_limit() {
     // Should get a large number (default is 10000)
     assert!(payments.len() >= 1000);
 }
+
+#[tokio::test]
+async fn test_max_records_override_is_honored_up_to_ceiling() {
+    let client = StellarRpcClient::new_with_defaults(true).with_max_records_ceiling(12_000);
+
+    // Above the 10000 default, at the ceiling
+    let payments = client.fetch_all_payments(Some(12_000)).await.unwrap();
+    assert_eq!(payments.len(), 12_000);
+    let trades = client.fetch_all_trades(Some(11_000)).await.unwrap();
+    assert_eq!(trades.len(), 11_000);
+}
+
+#[tokio::test]
+async fn test_max_records_override_beyond_ceiling_is_rejected() {
+    let client = StellarRpcClient::new_with_defaults(true).with_max_records_ceiling(12_000);
+    let expected = MaxRecordsExceeded {
+        requested: 12_001,
+        ceiling: 12_000,
+    };
+
+    let err = client.fetch_all_payments(Some(12_001)).await.unwrap_err();
+    assert_eq!(err.downcast_ref::<MaxRecordsExceeded>(), Some(&expected));
+    assert!(err.to_string().contains("12000"), "{}", err);
+
+    let err = client.fetch_all_trades(Some(12_001)).await.unwrap_err();
+    assert_eq!(err.downcast_ref::<MaxRecordsExceeded>(), Some(&expected));
+
+    let err = client
+        .fetch_all_account_payments("GACCOUNT", Some(12_001))
+        .await
+        .unwrap_err();
+    assert_eq!(err.downcast_ref::<MaxRecordsExceeded>(), Some(&expected));
+}
+
+#[tokio::test]
+async fn test_paginated_fetch_reports_cap() {
+    let client = paged_horizon_client().await;
+
+    let payments = client
+        .fetch_all_payments_paginated(Some(3), None)
+        .await
+        .unwrap();
+    assert_eq!(payments.items.len(), 3);
+    assert_eq!(payments.fetched, 3);
+    assert!(payments.capped);
+    assert_eq!(payments.last_cursor.as_deref(), Some("3"));
+
+    // Resuming picks up after the cursor and runs out of data
+    let rest = client
+        .fetch_all_payments_paginated(Some(3), payments.last_cursor.as_deref())
+        .await
+        .unwrap();
+    assert_eq!(rest.items[0].id, "4");
+    assert_eq!(rest.fetched, 2);
+    assert!(!rest.capped);
+    assert_eq!(rest.last_cursor.as_deref(), Some("5"));
+
+    let trades = client
+        .fetch_all_trades_paginated(Some(2), None)
+        .await
+        .unwrap();
+    assert_eq!(trades.fetched, 2);
+    assert!(trades.capped);
+    assert_eq!(trades.last_cursor.as_deref(), Some("2"));
+}
+
+#[tokio::test]
+async fn test_paginated_fetch_reports_exhausted_data() {
+    let client = paged_horizon_client().await;
+
+    let payments = client
+        .fetch_all_payments_paginated(Some(10), None)
+        .await
+        .unwrap();
+    assert_eq!(payments.fetched, HORIZON_RECORDS);
+    assert!(!payments.capped);
+    assert_eq!(payments.last_cursor.as_deref(), Some("5"));
+
+    let trades = client
+        .fetch_all_trades_paginated(Some(10), None)
+        .await
+        .unwrap();
+    assert_eq!(trades.items.len(), HORIZON_RECORDS as usize);
+    assert!(!trades.capped);
+
+    // The bare-Vec methods return the same records
+    let bare = client.fetch_all_payments(Some(10)).await.unwrap();
+    assert_eq!(bare.len(), HORIZON_RECORDS as usize);
+}
@@ -0,0 +1,136 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{Duration, Utc};
+use serde_json::json;
+use sqlx::SqlitePool;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::ingestion::path_payment_failures::{
+    detect_path_payment_failures, failures_by_corridor, insert_path_payment_failure,
+};
+use stellar_insights_backend::models::corridor::Corridor;
+use stellar_insights_backend::rpc::{HorizonTransaction, Payment};
+use stellar_insights_backend::services::analytics::compute_metrics_from_payments;
+use stellar_xdr::curr::{
+    Limits, OperationResult, OperationResultTr, PathPaymentStrictSendResult, TransactionResult,
+    TransactionResultExt, TransactionResultResult, VecM, WriteXdr,
+};
+
+const ISSUER: &str = "GAUSDCISSUERXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXXX";
+
+fn too_few_offers_xdr() -> String {
+    let result = TransactionResult {
+        fee_charged: 100,
+        result: TransactionResultResult::TxFailed(
+            VecM::try_from(vec![OperationResult::OpInner(
+                OperationResultTr::PathPaymentStrictSend(PathPaymentStrictSendResult::TooFewOffers),
+            )])
+            .unwrap(),
+        ),
+        ext: TransactionResultExt::V0,
+    };
+    BASE64.encode(result.to_xdr(Limits::none()).unwrap())
+}
+
+fn transaction(hash: &str, successful: bool, result_xdr: Option<String>) -> HorizonTransaction {
+    serde_json::from_value(json!({
+        "id": hash,
+        "hash": hash,
+        "ledger": 300,
+        "created_at": Utc::now().to_rfc3339(),
+        "source_account": "GSOURCE",
+        "fee_charged": "100",
+        "max_fee": "1000",
+        "operation_count": 1,
+        "successful": successful,
+        "paging_token": hash,
+        "result_xdr": result_xdr,
+    }))
+    .unwrap()
+}
+
+fn payment(id: &str, tx_hash: &str, operation_type: &str) -> Payment {
+    serde_json::from_value(json!({
+        "id": id,
+        "paging_token": id,
+        "transaction_hash": tx_hash,
+        "source_account": "GSOURCE",
+        "destination": "GDEST",
+        "asset_type": "credit_alphanum4",
+        "asset_code": "USDC",
+        "asset_issuer": ISSUER,
+        "amount": "50.0000000",
+        "created_at": Utc::now().to_rfc3339(),
+        "type": operation_type,
+        "source_asset_type": "native",
+        "source_amount": "400.0000000",
+    }))
+    .unwrap()
+}
+
+#[sqlx::test]
+async fn test_failed_path_payment_is_recorded_against_corridor(pool: SqlitePool) {
+    sqlx::query(
+        "INSERT INTO ledgers (sequence, hash, close_time, transaction_count, operation_count) VALUES (300, 'ledger_hash_300', '2026-01-22T10:30:00Z', 0, 0)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let transactions = vec![
+        transaction("tx_failed", false, Some(too_few_offers_xdr())),
+        transaction("tx_ok", true, None),
+        transaction("tx_failed_plain", false, None),
+    ];
+    let payments = vec![
+        payment("op_failed", "tx_failed", "path_payment_strict_send"),
+        payment("op_ok", "tx_ok", "path_payment_strict_send"),
+        // Plain payments are not path payments even when their transaction fails
+        payment("op_plain", "tx_failed_plain", "payment"),
+    ];
+
+    let failures = detect_path_payment_failures(300, &payments, &transactions);
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].operation_id, "op_failed");
+    assert_eq!(
+        failures[0].failure_reason,
+        "path_payment_strict_send:TooFewOffers"
+    );
+
+    let mut conn = pool.acquire().await.unwrap();
+    assert!(insert_path_payment_failure(&mut conn, &failures[0])
+        .await
+        .unwrap());
+    // Re-recording the same operation is a no-op
+    assert!(!insert_path_payment_failure(&mut conn, &failures[0])
+        .await
+        .unwrap());
+    drop(conn);
+
+    let corridor_key = Corridor::new(
+        "XLM".to_string(),
+        "native".to_string(),
+        "USDC".to_string(),
+        ISSUER.to_string(),
+    )
+    .to_string_key();
+
+    let by_corridor = failures_by_corridor(&pool).await.unwrap();
+    assert_eq!(by_corridor.len(), 1);
+    assert_eq!(by_corridor[0].corridor_key, corridor_key);
+    assert_eq!(by_corridor[0].failure_count, 1);
+
+    // Failures feed the corridor failure rate used by aggregation
+    let db = Database::new(pool.clone());
+    let now = Utc::now();
+    let records = db
+        .fetch_path_payment_failures_by_timerange(now - Duration::hours(1), now, 100)
+        .await
+        .unwrap();
+    assert_eq!(records.len(), 1);
+    assert!(!records[0].successful);
+
+    let metrics = compute_metrics_from_payments(&records);
+    assert_eq!(metrics.len(), 1);
+    assert_eq!(metrics[0].corridor_key, corridor_key);
+    assert_eq!(metrics[0].failed_transactions, 1);
+    assert_eq!(metrics[0].success_rate, 0.0);
+}
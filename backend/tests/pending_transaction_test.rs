@@ -0,0 +1,191 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::models::transaction_envelope::EnvelopeError;
+use stellar_insights_backend::rpc::StellarRpcClient;
+use stellar_insights_backend::services::pending_transaction::{
+    PendingTransactionError, PendingTransactionService,
+};
+use stellar_xdr::curr::{
+    BumpSequenceOp, Limits, Memo, MuxedAccount, Operation, OperationBody, Preconditions, PublicKey,
+    SequenceNumber, Transaction, TransactionEnvelope, TransactionExt, TransactionV1Envelope,
+    Uint256, VecM, WriteXdr,
+};
+
+fn signer(seed: u8) -> String {
+    PublicKey::PublicKeyTypeEd25519(Uint256([seed; 32])).to_string()
+}
+
+fn signature(seed: u8) -> String {
+    BASE64.encode([seed; 64])
+}
+
+fn envelope_xdr() -> String {
+    let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([7; 32])),
+            fee: 200,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![Operation {
+                source_account: None,
+                body: OperationBody::BumpSequence(BumpSequenceOp {
+                    bump_to: SequenceNumber(2),
+                }),
+            }]
+            .try_into()
+            .unwrap(),
+            ext: TransactionExt::V0,
+        },
+        signatures: VecM::default(),
+    });
+    BASE64.encode(envelope.to_xdr(Limits::none()).unwrap())
+}
+
+fn service(db: Arc<Database>) -> PendingTransactionService {
+    PendingTransactionService::new(db, Arc::new(StellarRpcClient::new_with_defaults(true)))
+}
+
+#[sqlx::test]
+async fn test_collect_signatures_and_submit(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let service = service(Arc::clone(&db));
+    let tx = db
+        .create_pending_transaction(&signer(7), &envelope_xdr(), 2)
+        .await
+        .unwrap();
+
+    let pending = service
+        .add_signature(&tx.id, &signer(1), &signature(1))
+        .await
+        .unwrap();
+    assert_eq!(pending.transaction.status, "pending");
+    assert!(!PendingTransactionService::is_ready(&pending));
+
+    let err = service.submit_when_ready(&tx.id).await.unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<PendingTransactionError>(),
+        Some(&PendingTransactionError::NotReady {
+            id: tx.id.clone(),
+            collected: 1,
+            required: 2,
+        })
+    );
+
+    let ready = service
+        .add_signature(&tx.id, &signer(2), &signature(2))
+        .await
+        .unwrap();
+    assert_eq!(ready.transaction.status, "ready");
+    assert!(PendingTransactionService::is_ready(&ready));
+
+    let result = service.submit_when_ready(&tx.id).await.unwrap();
+    assert_eq!(result.status, "submitted");
+    assert_eq!(result.hash.len(), 64);
+
+    let stored = db.get_pending_transaction(&tx.id).await.unwrap().unwrap();
+    assert_eq!(stored.transaction.status, "submitted");
+    assert_eq!(stored.collected_signatures.len(), 2);
+
+    // Submitted transactions are closed to further signatures and submissions
+    let err = service
+        .add_signature(&tx.id, &signer(3), &signature(3))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<PendingTransactionError>(),
+        Some(&PendingTransactionError::AlreadySubmitted(tx.id.clone()))
+    );
+    assert!(service.submit_when_ready(&tx.id).await.is_err());
+}
+
+#[sqlx::test]
+async fn test_duplicate_signer_is_rejected(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let service = service(Arc::clone(&db));
+    let tx = db
+        .create_pending_transaction(&signer(7), &envelope_xdr(), 2)
+        .await
+        .unwrap();
+
+    service
+        .add_signature(&tx.id, &signer(1), &signature(1))
+        .await
+        .unwrap();
+    let err = service
+        .add_signature(&tx.id, &signer(1), &signature(9))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<PendingTransactionError>(),
+        Some(&PendingTransactionError::DuplicateSigner {
+            transaction_id: tx.id.clone(),
+            signer: signer(1),
+        })
+    );
+
+    // The duplicate does not count towards the threshold
+    let stored = db.get_pending_transaction(&tx.id).await.unwrap().unwrap();
+    assert_eq!(stored.collected_signatures.len(), 1);
+    assert_eq!(stored.transaction.status, "pending");
+}
+
+#[sqlx::test]
+async fn test_malformed_envelope_is_not_stored(pool: SqlitePool) {
+    let db = Database::new(pool.clone());
+
+    let err = db
+        .create_pending_transaction(&signer(7), "definitely not xdr", 1)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<EnvelopeError>(),
+        Some(&EnvelopeError::NotBase64)
+    );
+
+    // The envelope names its own source account
+    let err = db
+        .create_pending_transaction(&signer(8), &envelope_xdr(), 1)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<EnvelopeError>(),
+        Some(&EnvelopeError::SourceMismatch {
+            expected: signer(8),
+            actual: signer(7),
+        })
+    );
+
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pending_transactions")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 0);
+}
+
+#[sqlx::test]
+async fn test_invalid_signature_and_unknown_transaction(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let service = service(Arc::clone(&db));
+    let tx = db
+        .create_pending_transaction(&signer(7), &envelope_xdr(), 1)
+        .await
+        .unwrap();
+
+    let err = service
+        .add_signature(&tx.id, "not-a-key", &signature(1))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<PendingTransactionError>(),
+        Some(PendingTransactionError::InvalidSignature { .. })
+    ));
+
+    let err = service.submit_when_ready("missing").await.unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<PendingTransactionError>(),
+        Some(&PendingTransactionError::NotFound("missing".to_string()))
+    );
+}
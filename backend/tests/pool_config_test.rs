@@ -1,9 +1,5 @@
-use std::env;
-use std::sync::Mutex;
 use stellar_insights_backend::database::PoolConfig;
 
-static ENV_MUTEX: Mutex<()> = Mutex::new(());
-
 #[test]
 fn test_pool_config_defaults() {
     let config = PoolConfig::default();
@@ -15,73 +11,6 @@ fn test_pool_config_defaults() {
     assert_eq!(config.max_lifetime_seconds, 1800);
 }
 
-#[test]
-fn test_pool_config_from_env() {
-    let _lock = ENV_MUTEX.lock().unwrap();
-    // Set environment variables
-    env::set_var("DB_POOL_MAX_CONNECTIONS", "20");
-    env::set_var("DB_POOL_MIN_CONNECTIONS", "5");
-    env::set_var("DB_POOL_CONNECT_TIMEOUT_SECONDS", "60");
-    env::set_var("DB_POOL_IDLE_TIMEOUT_SECONDS", "300");
-    env::set_var("DB_POOL_MAX_LIFETIME_SECONDS", "3600");
-
-    let config = PoolConfig::from_env();
-
-    assert_eq!(config.max_connections, 20);
-    assert_eq!(config.min_connections, 5);
-    assert_eq!(config.connect_timeout_seconds, 60);
-    assert_eq!(config.idle_timeout_seconds, 300);
-    assert_eq!(config.max_lifetime_seconds, 3600);
-
-    // Clean up
-    env::remove_var("DB_POOL_MAX_CONNECTIONS");
-    env::remove_var("DB_POOL_MIN_CONNECTIONS");
-    env::remove_var("DB_POOL_CONNECT_TIMEOUT_SECONDS");
-    env::remove_var("DB_POOL_IDLE_TIMEOUT_SECONDS");
-    env::remove_var("DB_POOL_MAX_LIFETIME_SECONDS");
-}
-
-#[test]
-fn test_pool_config_from_env_with_defaults() {
-    let _lock = ENV_MUTEX.lock().unwrap();
-    // Ensure no env vars are set
-    env::remove_var("DB_POOL_MAX_CONNECTIONS");
-    env::remove_var("DB_POOL_MIN_CONNECTIONS");
-    env::remove_var("DB_POOL_CONNECT_TIMEOUT_SECONDS");
-    env::remove_var("DB_POOL_IDLE_TIMEOUT_SECONDS");
-    env::remove_var("DB_POOL_MAX_LIFETIME_SECONDS");
-
-    let config = PoolConfig::from_env();
-
-    // Should use defaults
-    assert_eq!(config.max_connections, 10);
-    assert_eq!(config.min_connections, 2);
-    assert_eq!(config.connect_timeout_seconds, 30);
-    assert_eq!(config.idle_timeout_seconds, 600);
-    assert_eq!(config.max_lifetime_seconds, 1800);
-}
-
-#[test]
-fn test_pool_config_from_env_partial() {
-    let _lock = ENV_MUTEX.lock().unwrap();
-    // Set only some environment variables
-    env::set_var("DB_POOL_MAX_CONNECTIONS", "15");
-    env::remove_var("DB_POOL_MIN_CONNECTIONS");
-    env::set_var("DB_POOL_CONNECT_TIMEOUT_SECONDS", "45");
-
-    let config = PoolConfig::from_env();
-
-    assert_eq!(config.max_connections, 15);
-    assert_eq!(config.min_connections, 2); // default
-    assert_eq!(config.connect_timeout_seconds, 45);
-    assert_eq!(config.idle_timeout_seconds, 600); // default
-    assert_eq!(config.max_lifetime_seconds, 1800); // default
-
-    // Clean up
-    env::remove_var("DB_POOL_MAX_CONNECTIONS");
-    env::remove_var("DB_POOL_CONNECT_TIMEOUT_SECONDS");
-}
-
 #[tokio::test]
 async fn test_pool_creation() {
     let config = PoolConfig {
@@ -90,6 +19,7 @@ async fn test_pool_creation() {
         connect_timeout_seconds: 10,
         idle_timeout_seconds: 300,
         max_lifetime_seconds: 900,
+        query_timeout_seconds: 5,
     };
 
     // Use in-memory SQLite for testing
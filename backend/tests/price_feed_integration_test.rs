@@ -1,7 +1,62 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use stellar_insights_backend::services::price_feed::{
-    default_asset_mapping, PriceFeedClient, PriceFeedConfig,
+    default_asset_mapping, PriceFeedClient, PriceFeedConfig, PriceFeedProvider, SourcedPrice,
 };
 
+/// Provider returning a set price, or an error when `price` is `None`
+struct MockProvider {
+    name: &'static str,
+    price: Mutex<Option<f64>>,
+    calls: AtomicUsize,
+}
+
+impl MockProvider {
+    fn new(name: &'static str, price: Option<f64>) -> Arc<Self> {
+        Arc::new(Self {
+            name,
+            price: Mutex::new(price),
+            calls: AtomicUsize::new(0),
+        })
+    }
+
+    fn set_price(&self, price: f64) {
+        *self.price.lock().unwrap() = Some(price);
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceFeedProvider for MockProvider {
+    async fn fetch_price(&self, _asset_id: &str) -> anyhow::Result<f64> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.price
+            .lock()
+            .unwrap()
+            .ok_or_else(|| anyhow::anyhow!("{} unavailable", self.name))
+    }
+
+    async fn fetch_prices(&self, asset_ids: &[String]) -> anyhow::Result<HashMap<String, f64>> {
+        let price = self.fetch_price("").await?;
+        Ok(asset_ids.iter().map(|id| (id.clone(), price)).collect())
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+}
+
+fn client(
+    max_deviation_percent: Option<f64>,
+    providers: Vec<Arc<dyn PriceFeedProvider>>,
+) -> PriceFeedClient {
+    let config = PriceFeedConfig {
+        max_deviation_percent,
+        ..Default::default()
+    };
+    PriceFeedClient::with_providers(config, default_asset_mapping(), providers).unwrap()
+}
+
 #[tokio::test]
 async fn test_price_feed_client_creation() {
     let config = PriceFeedConfig::default();
@@ -49,25 +104,6 @@ async fn test_cache_clear() {
     assert_eq!(fresh, 0);
 }
 
-#[tokio::test]
-async fn test_config_from_env() {
-    // Set environment variables
-    std::env::set_var("PRICE_FEED_PROVIDER", "coingecko");
-    std::env::set_var("PRICE_FEED_CACHE_TTL_SECONDS", "600");
-    std::env::set_var("PRICE_FEED_REQUEST_TIMEOUT_SECONDS", "15");
-
-    let config = PriceFeedConfig::from_env();
-
-    assert_eq!(config.provider, "coingecko");
-    assert_eq!(config.cache_ttl_seconds, 600);
-    assert_eq!(config.request_timeout_seconds, 15);
-
-    // Clean up
-    std::env::remove_var("PRICE_FEED_PROVIDER");
-    std::env::remove_var("PRICE_FEED_CACHE_TTL_SECONDS");
-    std::env::remove_var("PRICE_FEED_REQUEST_TIMEOUT_SECONDS");
-}
-
 #[tokio::test]
 async fn test_multiple_asset_mappings() {
     let mapping = default_asset_mapping();
@@ -82,6 +118,180 @@ async fn test_multiple_asset_mappings() {
     // Euro stablecoin
 }
 
+#[tokio::test]
+async fn test_failing_primary_falls_back_to_secondary() {
+    let primary = MockProvider::new("primary", None);
+    let secondary = MockProvider::new("secondary", Some(0.12));
+    let client = client(None, vec![primary.clone(), secondary.clone()]);
+
+    let price = client.get_sourced_price("XLM:native").await.unwrap();
+    assert_eq!(
+        price,
+        SourcedPrice {
+            price_usd: 0.12,
+            source: "secondary".to_string()
+        }
+    );
+    assert_eq!(primary.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        client.price_source("XLM:native").await.as_deref(),
+        Some("secondary")
+    );
+
+    let usdc = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN".to_string();
+    let prices = client.get_prices(&[usdc.clone()]).await;
+    assert_eq!(prices.get(&usdc), Some(&0.12));
+    assert_eq!(
+        client.price_source(&usdc).await.as_deref(),
+        Some("secondary")
+    );
+}
+
+#[tokio::test]
+async fn test_deviating_primary_fails_over() {
+    // Primary is 50% above the secondary, beyond the 5% threshold
+    let primary = MockProvider::new("primary", Some(0.18));
+    let secondary = MockProvider::new("secondary", Some(0.12));
+    let client = client(Some(5.0), vec![primary, secondary.clone()]);
+
+    let price = client.get_sourced_price("XLM:native").await.unwrap();
+    assert_eq!(price.price_usd, 0.12);
+    assert_eq!(price.source, "secondary");
+    // The secondary's cross-check price is reused rather than refetched
+    assert_eq!(secondary.calls.load(Ordering::SeqCst), 1);
+
+    let usdc = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN".to_string();
+    let prices = client.get_prices(&[usdc.clone()]).await;
+    assert_eq!(prices.get(&usdc), Some(&0.12));
+    assert_eq!(
+        client.price_source(&usdc).await.as_deref(),
+        Some("secondary")
+    );
+}
+
+#[tokio::test]
+async fn test_primary_within_deviation_is_kept() {
+    let primary = MockProvider::new("primary", Some(0.121));
+    let secondary = MockProvider::new("secondary", Some(0.12));
+    let client = client(Some(5.0), vec![primary, secondary]);
+
+    let price = client.get_sourced_price("XLM:native").await.unwrap();
+    assert_eq!(price.price_usd, 0.121);
+    assert_eq!(price.source, "primary");
+}
+
+#[tokio::test]
+async fn test_implausible_price_fails_over() {
+    let primary = MockProvider::new("primary", Some(0.0));
+    let secondary = MockProvider::new("secondary", Some(0.12));
+    let client = client(None, vec![primary, secondary]);
+
+    let price = client.get_sourced_price("XLM:native").await.unwrap();
+    assert_eq!(price.source, "secondary");
+}
+
+#[tokio::test]
+async fn test_all_sources_failing_is_an_error() {
+    let client = client(
+        None,
+        vec![
+            MockProvider::new("primary", None),
+            MockProvider::new("secondary", None),
+        ],
+    );
+
+    assert!(client.get_price("XLM:native").await.is_err());
+}
+
+#[tokio::test]
+async fn test_price_spike_is_rejected() {
+    let provider = MockProvider::new("primary", Some(0.12));
+    let config = PriceFeedConfig {
+        // Refetch on every request
+        cache_ttl_seconds: 0,
+        max_price_change_percent: Some(50.0),
+        ..Default::default()
+    };
+    let client =
+        PriceFeedClient::with_providers(config, default_asset_mapping(), vec![provider.clone()])
+            .unwrap();
+
+    assert_eq!(client.get_price("XLM:native").await.unwrap(), 0.12);
+
+    // A normal move replaces the last known price
+    provider.set_price(0.15);
+    assert_eq!(client.get_price("XLM:native").await.unwrap(), 0.15);
+    let status = client.get_price_status("XLM:native").await.unwrap();
+    assert_eq!(status.rejected_price_usd, None);
+
+    // A 100x jump is held back and flagged
+    provider.set_price(15.0);
+    assert_eq!(client.get_price("XLM:native").await.unwrap(), 0.15);
+    let status = client.get_price_status("XLM:native").await.unwrap();
+    assert_eq!(status.price.price_usd, 0.15);
+    assert_eq!(status.rejected_price_usd, Some(15.0));
+
+    // A spike at another level starts over rather than confirming
+    provider.set_price(0.5);
+    let xlm = vec!["XLM:native".to_string()];
+    assert_eq!(client.get_prices(&xlm).await.get("XLM:native"), Some(&0.15));
+
+    // Once the feed is back in range the flag clears
+    provider.set_price(0.16);
+    let status = client.get_price_status("XLM:native").await.unwrap();
+    assert_eq!(status.price.price_usd, 0.16);
+    assert_eq!(status.rejected_price_usd, None);
+}
+
+#[tokio::test]
+async fn test_sustained_price_move_is_accepted() {
+    let provider = MockProvider::new("primary", Some(0.12));
+    let config = PriceFeedConfig {
+        cache_ttl_seconds: 0,
+        max_price_change_percent: Some(50.0),
+        ..Default::default()
+    };
+    let client =
+        PriceFeedClient::with_providers(config, default_asset_mapping(), vec![provider.clone()])
+            .unwrap();
+
+    assert_eq!(client.get_price("XLM:native").await.unwrap(), 0.12);
+
+    // The first two readings at the new level are held back
+    provider.set_price(0.30);
+    assert_eq!(client.get_price("XLM:native").await.unwrap(), 0.12);
+    provider.set_price(0.31);
+    assert_eq!(client.get_price("XLM:native").await.unwrap(), 0.12);
+
+    // The third consistent reading confirms the move
+    provider.set_price(0.30);
+    assert_eq!(client.get_price("XLM:native").await.unwrap(), 0.30);
+    let status = client.get_price_status("XLM:native").await.unwrap();
+    assert_eq!(status.price.price_usd, 0.30);
+    assert_eq!(status.rejected_price_usd, None);
+}
+
+#[tokio::test]
+async fn test_erratic_price_spikes_stay_rejected() {
+    let provider = MockProvider::new("primary", Some(0.12));
+    let config = PriceFeedConfig {
+        cache_ttl_seconds: 0,
+        max_price_change_percent: Some(50.0),
+        ..Default::default()
+    };
+    let client =
+        PriceFeedClient::with_providers(config, default_asset_mapping(), vec![provider.clone()])
+            .unwrap();
+
+    assert_eq!(client.get_price("XLM:native").await.unwrap(), 0.12);
+
+    // Spikes that disagree with each other never confirm a new level
+    for spike in [15.0, 0.5, 15.0, 0.5] {
+        provider.set_price(spike);
+        assert_eq!(client.get_price("XLM:native").await.unwrap(), 0.12);
+    }
+}
+
 // Note: The following tests require actual API calls and should only be run with network access
 // They are commented out to avoid CI failures
 
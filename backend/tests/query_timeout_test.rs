@@ -0,0 +1,53 @@
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::{Connection, SqliteConnection};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use stellar_insights_backend::database::{Database, PoolConfig};
+use stellar_insights_backend::db::query_timeout::QueryTimeout;
+
+#[tokio::test]
+async fn test_query_blocked_by_lock_times_out() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = format!("sqlite://{}", dir.path().join("locked.db").display());
+    let options = SqliteConnectOptions::from_str(&url)
+        .unwrap()
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Delete);
+
+    let setup = SqlitePoolOptions::new()
+        .connect_with(options.clone())
+        .await
+        .unwrap();
+    sqlx::migrate!("./migrations").run(&setup).await.unwrap();
+    setup.close().await;
+
+    // Hold an exclusive lock so every read has to wait on it
+    let mut locker = SqliteConnection::connect_with(&options).await.unwrap();
+    sqlx::query("BEGIN EXCLUSIVE")
+        .execute(&mut locker)
+        .await
+        .unwrap();
+
+    let pool_config = PoolConfig {
+        min_connections: 0,
+        query_timeout_seconds: 1,
+        ..PoolConfig::default()
+    };
+    let pool = pool_config.create_pool(&url).await.unwrap();
+    let db = Database::new(pool).with_query_timeout(pool_config.query_timeout());
+
+    let start = Instant::now();
+    let err = db.list_corridors(10, 0).await.unwrap_err();
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(
+        err.downcast_ref::<QueryTimeout>(),
+        Some(&QueryTimeout {
+            query: "list_corridors",
+            timeout: Duration::from_secs(1),
+        })
+    );
+
+    // Once the lock is released the same read succeeds
+    sqlx::query("ROLLBACK").execute(&mut locker).await.unwrap();
+    assert!(db.list_corridors(10, 0).await.is_ok());
+}
@@ -0,0 +1,102 @@
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{Request, StatusCode};
+use axum::{middleware, routing::get, Json, Router};
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use sqlx::{Connection, SqliteConnection, SqlitePool};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use stellar_insights_backend::database::{Database, PoolConfig};
+use stellar_insights_backend::error::ApiResult;
+use stellar_insights_backend::models::corridor::Corridor;
+use stellar_insights_backend::request_deadline::{
+    request_deadline_middleware, with_deadline, DeadlineExceeded, RequestDeadline,
+    RequestDeadlineConfig,
+};
+use tower::util::ServiceExt;
+
+async fn list_corridors(State(db): State<Arc<Database>>) -> ApiResult<Json<Vec<Corridor>>> {
+    Ok(Json(db.list_corridors(10, 0).await?))
+}
+
+fn app(db: Arc<Database>, budget: Duration) -> Router {
+    Router::new()
+        .route("/corridors", get(list_corridors))
+        .with_state(db)
+        .layer(middleware::from_fn_with_state(
+            RequestDeadlineConfig {
+                max_request_duration: budget,
+            },
+            request_deadline_middleware,
+        ))
+}
+
+#[tokio::test]
+async fn test_short_budget_aborts_slow_query() {
+    let dir = tempfile::tempdir().unwrap();
+    let url = format!("sqlite://{}", dir.path().join("locked.db").display());
+    let options = SqliteConnectOptions::from_str(&url)
+        .unwrap()
+        .create_if_missing(true)
+        .journal_mode(SqliteJournalMode::Delete);
+
+    let setup = SqlitePoolOptions::new()
+        .connect_with(options.clone())
+        .await
+        .unwrap();
+    sqlx::migrate!("./migrations").run(&setup).await.unwrap();
+    setup.close().await;
+
+    // Hold an exclusive lock so the read has to wait on it
+    let mut locker = SqliteConnection::connect_with(&options).await.unwrap();
+    sqlx::query("BEGIN EXCLUSIVE")
+        .execute(&mut locker)
+        .await
+        .unwrap();
+
+    // The query timeout alone would let the read wait for 10 seconds
+    let pool_config = PoolConfig {
+        min_connections: 0,
+        ..PoolConfig::default()
+    };
+    let pool = pool_config.create_pool(&url).await.unwrap();
+    let db = Arc::new(Database::new(pool).with_query_timeout(pool_config.query_timeout()));
+    let app = app(Arc::clone(&db), Duration::from_millis(200));
+
+    let start = Instant::now();
+    let response = app
+        .clone()
+        .oneshot(Request::get("/corridors").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    // Once the lock is released the same request fits in its budget
+    sqlx::query("ROLLBACK").execute(&mut locker).await.unwrap();
+    let response = app
+        .oneshot(Request::get("/corridors").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_spent_budget_skips_query(pool: SqlitePool) {
+    let db = Database::new(pool);
+
+    let spent = RequestDeadline::after(Duration::ZERO);
+    let err = with_deadline(spent, db.list_corridors(10, 0))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<DeadlineExceeded>(),
+        Some(&DeadlineExceeded {
+            query: "list_corridors",
+        })
+    );
+
+    // Outside a request the query timeout is the only limit
+    assert!(db.list_corridors(10, 0).await.is_ok());
+}
@@ -0,0 +1,86 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use flate2::{write::GzEncoder, Compression};
+use std::io::Write;
+use stellar_insights_backend::request_decompression::with_request_decompression;
+use tower::util::ServiceExt;
+
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+fn app() -> Router {
+    let router = Router::new().route(
+        "/echo",
+        post(|Json(body): Json<serde_json::Value>| async move { Json(body) }),
+    );
+    with_request_decompression(router, MAX_BODY_BYTES)
+}
+
+fn gzip(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn gzip_request(body: Vec<u8>) -> Request<Body> {
+    Request::builder()
+        .method("POST")
+        .uri("/echo")
+        .header(header::CONTENT_TYPE, "application/json")
+        .header(header::CONTENT_ENCODING, "gzip")
+        .body(Body::from(body))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_gzip_body_is_decoded() {
+    let payload = serde_json::json!({
+        "url": "https://example.com/hook",
+        "event_types": ["anchor.status_changed", "corridor.updated"]
+    });
+
+    let response = app()
+        .oneshot(gzip_request(gzip(payload.to_string().as_bytes())))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let echoed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(echoed, payload);
+}
+
+#[tokio::test]
+async fn test_plain_body_still_accepted() {
+    let response = app()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/echo")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(r#"{"ok":true}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_decompression_bomb_is_rejected() {
+    // ~16 MiB of JSON that compresses to a few KiB
+    let mut bomb = Vec::with_capacity(16 * 1024 * 1024 + 16);
+    bomb.extend_from_slice(br#"{"padding":""#);
+    bomb.resize(16 * 1024 * 1024, b'a');
+    bomb.extend_from_slice(br#""}"#);
+    let compressed = gzip(&bomb);
+    assert!(compressed.len() < MAX_BODY_BYTES);
+
+    let response = app().oneshot(gzip_request(compressed)).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
@@ -0,0 +1,201 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Request, StatusCode};
+use axum::routing::{post, put};
+use axum::{middleware, Extension, Router};
+use chrono::Utc;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::api::resource_permissions;
+use stellar_insights_backend::auth::Claims;
+use stellar_insights_backend::auth_middleware::{
+    auth_middleware, require_resource_permission, require_scope, scopes, JwtSecret, RequiredScope,
+    ResourcePermission,
+};
+use stellar_insights_backend::services::resource_acl::{ResourceAcl, ResourceType};
+use tower::util::ServiceExt;
+
+const SECRET: &str = "test-secret-that-is-at-least-32-characters";
+
+/// Anchor write routes layered the way main.rs mounts them, with stub
+/// handlers
+fn app(acl: ResourceAcl) -> Router {
+    Router::new()
+        .route("/api/anchors", post(|| async { "created" }))
+        .route("/api/anchors/:id/metrics", put(|| async { "updated" }))
+        .route_layer(middleware::from_fn_with_state(
+            ResourcePermission {
+                acl,
+                resource: ResourceType::Anchor,
+            },
+            require_resource_permission,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            RequiredScope(scopes::ANCHOR_WRITE),
+            require_scope,
+        ))
+        .layer(middleware::from_fn(auth_middleware))
+        .layer(Extension(JwtSecret(Arc::from(SECRET))))
+}
+
+fn token(user_id: &str, scopes: &[&str]) -> String {
+    let now = Utc::now().timestamp();
+    let claims = Claims {
+        sub: user_id.to_string(),
+        username: user_id.to_string(),
+        exp: now + 3600,
+        iat: now,
+        token_type: "access".to_string(),
+        scopes: scopes.iter().map(|s| s.to_string()).collect(),
+    };
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(SECRET.as_bytes()),
+    )
+    .unwrap()
+}
+
+async fn send(app: &Router, method: &str, uri: &str, token: &str) -> (StatusCode, Vec<u8>) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {}", token))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, body.to_vec())
+}
+
+async fn update_metrics(app: &Router, anchor: &str, token: &str) -> StatusCode {
+    let uri = format!("/api/anchors/{}/metrics", anchor);
+    send(app, "PUT", &uri, token).await.0
+}
+
+#[sqlx::test]
+async fn test_user_permitted_on_one_anchor_is_denied_on_another(pool: SqlitePool) {
+    let acl = ResourceAcl::new(pool);
+    acl.grant(ResourceType::Anchor, "anchor-a", "alice")
+        .await
+        .unwrap();
+    acl.grant(ResourceType::Anchor, "anchor-b", "bob")
+        .await
+        .unwrap();
+    let app = app(acl);
+    let alice = token("alice", &[scopes::ANCHOR_WRITE]);
+
+    assert_eq!(
+        update_metrics(&app, "anchor-a", &alice).await,
+        StatusCode::OK
+    );
+
+    let (status, body) = send(&app, "PUT", "/api/anchors/anchor-b/metrics", &alice).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["error"], "No write permission on anchor 'anchor-b'");
+
+    // Anchors without grants are governed by the scope alone
+    assert_eq!(
+        update_metrics(&app, "anchor-c", &alice).await,
+        StatusCode::OK
+    );
+    let (status, _) = send(&app, "POST", "/api/anchors", &alice).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Admins may mutate any anchor
+    let admin = token("carol", &[scopes::ADMIN]);
+    assert_eq!(
+        update_metrics(&app, "anchor-b", &admin).await,
+        StatusCode::OK
+    );
+}
+
+#[sqlx::test]
+async fn test_scope_is_still_required_and_revoke_lifts_restriction(pool: SqlitePool) {
+    let acl = ResourceAcl::new(pool);
+    acl.grant(ResourceType::Anchor, "anchor-a", "alice")
+        .await
+        .unwrap();
+    let app = app(acl.clone());
+
+    // A grant does not stand in for the write scope
+    assert_eq!(
+        update_metrics(&app, "anchor-a", &token("alice", &[])).await,
+        StatusCode::FORBIDDEN
+    );
+
+    let bob = token("bob", &[scopes::ANCHOR_WRITE]);
+    assert_eq!(
+        update_metrics(&app, "anchor-a", &bob).await,
+        StatusCode::FORBIDDEN
+    );
+    acl.revoke(ResourceType::Anchor, "anchor-a", "alice")
+        .await
+        .unwrap();
+    assert_eq!(update_metrics(&app, "anchor-a", &bob).await, StatusCode::OK);
+}
+
+/// Admin permission routes layered the way main.rs mounts them
+fn admin_app(pool: SqlitePool) -> Router {
+    resource_permissions::routes(pool)
+        .layer(middleware::from_fn_with_state(
+            RequiredScope(scopes::ADMIN),
+            require_scope,
+        ))
+        .layer(middleware::from_fn(auth_middleware))
+        .layer(Extension(JwtSecret(Arc::from(SECRET))))
+}
+
+#[sqlx::test]
+async fn test_admin_grants_and_revokes_through_the_api(pool: SqlitePool) {
+    let acl = ResourceAcl::new(pool.clone());
+    let admin_app = admin_app(pool);
+    let admin = token("carol", &[scopes::ADMIN]);
+    let uri = "/api/admin/permissions/anchor/anchor-a/alice";
+
+    // Granting requires the admin scope
+    let alice = token("alice", &[scopes::ANCHOR_WRITE]);
+    assert_eq!(
+        send(&admin_app, "PUT", uri, &alice).await.0,
+        StatusCode::FORBIDDEN
+    );
+
+    assert_eq!(
+        send(&admin_app, "PUT", uri, &admin).await.0,
+        StatusCode::NO_CONTENT
+    );
+    assert!(!acl
+        .can_write(ResourceType::Anchor, "anchor-a", "bob")
+        .await
+        .unwrap());
+    let (status, body) = send(
+        &admin_app,
+        "GET",
+        "/api/admin/permissions/anchor/anchor-a",
+        &admin,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["grantees"], serde_json::json!(["alice"]));
+
+    assert_eq!(
+        send(&admin_app, "DELETE", uri, &admin).await.0,
+        StatusCode::NO_CONTENT
+    );
+    assert!(acl
+        .can_write(ResourceType::Anchor, "anchor-a", "bob")
+        .await
+        .unwrap());
+
+    let (status, _) = send(
+        &admin_app,
+        "PUT",
+        "/api/admin/permissions/webhook/w-1/alice",
+        &admin,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
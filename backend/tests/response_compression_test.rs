@@ -0,0 +1,93 @@
+use axum::{
+    body::Body,
+    http::{header, Request, StatusCode},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use stellar_insights_backend::response_compression::{
+    compression_layer, CompressionAlgorithms, ResponseCompressionConfig,
+};
+use tower::util::ServiceExt;
+
+fn large_body() -> String {
+    "stellar-insights ".repeat(512)
+}
+
+fn app(config: &ResponseCompressionConfig) -> Router {
+    Router::new()
+        .route("/json", get(|| async { large_body() }))
+        .route(
+            "/image",
+            get(|| async { ([(header::CONTENT_TYPE, "image/png")], large_body()).into_response() }),
+        )
+        .layer(compression_layer(config))
+}
+
+async fn content_encoding(app: Router, uri: &str, accept_encoding: &str) -> Option<String> {
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header(header::ACCEPT_ENCODING, accept_encoding)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    response
+        .headers()
+        .get(header::CONTENT_ENCODING)
+        .map(|v| v.to_str().unwrap().to_string())
+}
+
+#[tokio::test]
+async fn test_negotiated_encoding_matches_configured_set() {
+    let default = ResponseCompressionConfig::default();
+    assert_eq!(
+        content_encoding(app(&default), "/json", "gzip, br").await,
+        Some("br".to_string())
+    );
+
+    let gzip_only = ResponseCompressionConfig {
+        algorithms: "gzip".parse().unwrap(),
+        ..ResponseCompressionConfig::default()
+    };
+    assert_eq!(
+        content_encoding(app(&gzip_only), "/json", "gzip, br").await,
+        Some("gzip".to_string())
+    );
+    // A client accepting only a disabled encoding gets the body as-is
+    assert_eq!(content_encoding(app(&gzip_only), "/json", "br").await, None);
+
+    let disabled = ResponseCompressionConfig {
+        algorithms: CompressionAlgorithms {
+            gzip: false,
+            br: false,
+        },
+        ..ResponseCompressionConfig::default()
+    };
+    assert_eq!(
+        content_encoding(app(&disabled), "/json", "gzip, br").await,
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_excluded_content_type_is_sent_uncompressed() {
+    let config = ResponseCompressionConfig::default();
+    assert_eq!(
+        content_encoding(app(&config), "/image", "gzip, br").await,
+        None
+    );
+
+    // Removing the exclusion compresses it again
+    let config = ResponseCompressionConfig {
+        excluded_content_types: vec!["application/zip".to_string()],
+        ..ResponseCompressionConfig::default()
+    };
+    assert!(content_encoding(app(&config), "/image", "gzip")
+        .await
+        .is_some());
+}
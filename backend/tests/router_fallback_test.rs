@@ -0,0 +1,54 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Method, Request, StatusCode};
+use axum::{middleware, routing::get, Router};
+use stellar_insights_backend::error::with_json_fallbacks;
+use stellar_insights_backend::request_id::request_id_middleware;
+use tower::util::ServiceExt;
+
+fn app() -> Router {
+    let router = Router::new().route("/api/anchors", get(|| async { "anchors" }));
+    with_json_fallbacks(router).layer(middleware::from_fn(request_id_middleware))
+}
+
+async fn send(method: Method, uri: &str) -> (StatusCode, header::HeaderMap, serde_json::Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("X-Request-ID", "req-123")
+        .body(Body::empty())
+        .unwrap();
+    let response = app().oneshot(request).await.unwrap();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, headers, serde_json::from_slice(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_unknown_path_returns_json_404() {
+    let (status, headers, body) = send(Method::GET, "/api/does-not-exist").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(headers[header::CONTENT_TYPE], "application/json");
+    assert_eq!(body["error"]["code"], "ROUTE_NOT_FOUND");
+    assert_eq!(
+        body["error"]["message"],
+        "No route for GET /api/does-not-exist"
+    );
+    assert_eq!(body["error"]["request_id"], "req-123");
+}
+
+#[tokio::test]
+async fn test_disallowed_method_returns_json_405() {
+    let (status, headers, body) = send(Method::DELETE, "/api/anchors").await;
+
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(headers[header::CONTENT_TYPE], "application/json");
+    assert!(headers[header::ALLOW].to_str().unwrap().contains("GET"));
+    assert_eq!(body["error"]["code"], "METHOD_NOT_ALLOWED");
+    assert_eq!(
+        body["error"]["message"],
+        "Method DELETE is not allowed for /api/anchors"
+    );
+    assert_eq!(body["error"]["request_id"], "req-123");
+}
@@ -0,0 +1,192 @@
+use axum::{
+    extract::Path,
+    routing::{get, post},
+    Json, Router,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use serde_json::{json, Value};
+use stellar_insights_backend::rpc::{
+    DataOperation, DataSource, DataSourceConfig, StellarRpcClient,
+};
+use stellar_xdr::curr::{
+    BumpSequenceOp, Limits, Memo, MuxedAccount, Operation, OperationBody, Preconditions,
+    SequenceNumber, Transaction, TransactionEnvelope, TransactionExt, TransactionResult,
+    TransactionResultExt, TransactionResultResult, TransactionV1Envelope, Uint256, VecM, WriteXdr,
+};
+
+const LEDGER: u64 = 1_000;
+
+fn source_account() -> MuxedAccount {
+    MuxedAccount::Ed25519(Uint256([7; 32]))
+}
+
+fn envelope_xdr() -> String {
+    let envelope = TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: Transaction {
+            source_account: source_account(),
+            fee: 200,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![Operation {
+                source_account: None,
+                body: OperationBody::BumpSequence(BumpSequenceOp {
+                    bump_to: SequenceNumber(2),
+                }),
+            }]
+            .try_into()
+            .unwrap(),
+            ext: TransactionExt::V0,
+        },
+        signatures: VecM::default(),
+    });
+    BASE64.encode(envelope.to_xdr(Limits::none()).unwrap())
+}
+
+fn result_xdr() -> String {
+    let result = TransactionResult {
+        fee_charged: 150,
+        result: TransactionResultResult::TxSuccess(VecM::default()),
+        ext: TransactionResultExt::V0,
+    };
+    BASE64.encode(result.to_xdr(Limits::none()).unwrap())
+}
+
+fn rpc_transaction(hash: &str, ledger: u64) -> Value {
+    json!({
+        "status": "SUCCESS",
+        "txHash": hash,
+        "applicationOrder": 1,
+        "feeBump": false,
+        "envelopeXdr": envelope_xdr(),
+        "resultXdr": result_xdr(),
+        "ledger": ledger,
+        "createdAt": 1_767_225_600
+    })
+}
+
+/// Horizon and RPC mocks on one server that answer with distinct data
+async fn mock_network() -> (String, String) {
+    let app = Router::new()
+        .route(
+            "/horizon/ledgers",
+            get(|| async {
+                Json(json!({ "_embedded": { "records": [{
+                    "sequence": 100,
+                    "hash": "horizon-ledger",
+                    "previous_hash": "prev",
+                    "transaction_count": 1,
+                    "operation_count": 1,
+                    "closed_at": "2026-01-01T00:00:00Z",
+                    "total_coins": "1",
+                    "fee_pool": "1",
+                    "base_fee": 100,
+                    "base_reserve": "0.5"
+                }] } }))
+            }),
+        )
+        .route(
+            "/horizon/ledgers/:sequence/transactions",
+            get(|Path(sequence): Path<u64>| async move {
+                Json(json!({ "_embedded": { "records": [{
+                    "id": "horizon-tx",
+                    "hash": "horizon-tx",
+                    "ledger": sequence,
+                    "created_at": "2026-01-01T00:00:00Z",
+                    "source_account": "GHORIZON",
+                    "fee_charged": "100",
+                    "operation_count": 1,
+                    "successful": true,
+                    "paging_token": "1"
+                }] } }))
+            }),
+        )
+        .route(
+            "/rpc",
+            post(|Json(request): Json<Value>| async move {
+                let result = match request["method"].as_str() {
+                    Some("getLatestLedger") => json!({
+                        "id": "rpc-ledger",
+                        "protocolVersion": 22,
+                        "sequence": 200
+                    }),
+                    Some("getTransactions") => json!({
+                        "transactions": [
+                            rpc_transaction("rpc-tx", LEDGER),
+                            rpc_transaction("next-ledger-tx", LEDGER + 1)
+                        ],
+                        "cursor": "1"
+                    }),
+                    _ => Value::Null,
+                };
+                Json(json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+            }),
+        );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (
+        format!("http://{}/rpc", addr),
+        format!("http://{}/horizon", addr),
+    )
+}
+
+async fn client(config: DataSourceConfig) -> StellarRpcClient {
+    let (rpc_url, horizon_url) = mock_network().await;
+    StellarRpcClient::new(rpc_url, horizon_url, false).with_data_sources(config)
+}
+
+#[tokio::test]
+async fn test_horizon_is_the_default_source() {
+    let client = client(DataSourceConfig::default()).await;
+
+    let latest = client.fetch_latest_ledger().await.unwrap();
+    assert_eq!(latest.sequence, 100);
+    assert_eq!(latest.hash, "horizon-ledger");
+
+    let transactions = client.fetch_transactions_for_ledger(LEDGER).await.unwrap();
+    assert_eq!(transactions.len(), 1);
+    assert_eq!(transactions[0].hash, "horizon-tx");
+}
+
+#[tokio::test]
+async fn test_latest_ledger_from_rpc() {
+    let config =
+        DataSourceConfig::default().with_source(DataOperation::LatestLedger, DataSource::Rpc);
+    let client = client(config).await;
+
+    let latest = client.fetch_latest_ledger().await.unwrap();
+    assert_eq!(latest.sequence, 200);
+    assert_eq!(latest.hash, "rpc-ledger");
+
+    // Other operations still use Horizon
+    let transactions = client.fetch_transactions_for_ledger(LEDGER).await.unwrap();
+    assert_eq!(transactions[0].hash, "horizon-tx");
+}
+
+#[tokio::test]
+async fn test_ledger_transactions_from_rpc() {
+    let config =
+        DataSourceConfig::default().with_source(DataOperation::LedgerTransactions, DataSource::Rpc);
+    let client = client(config).await;
+
+    let transactions = client.fetch_transactions_for_ledger(LEDGER).await.unwrap();
+    assert_eq!(transactions.len(), 1);
+
+    let tx = &transactions[0];
+    assert_eq!(tx.hash, "rpc-tx");
+    assert_eq!(tx.ledger, LEDGER);
+    assert_eq!(tx.source_account, source_account().to_string());
+    assert!(tx.source_account.starts_with('G'));
+    assert_eq!(tx.fee_charged.as_deref(), Some("150"));
+    assert_eq!(tx.max_fee.as_deref(), Some("200"));
+    assert_eq!(tx.operation_count, 1);
+    assert!(tx.successful);
+    assert!(tx.fee_bump_transaction.is_none());
+    assert_eq!(tx.created_at, "2026-01-01T00:00:00+00:00");
+
+    let latest = client.fetch_latest_ledger().await.unwrap();
+    assert_eq!(latest.hash, "horizon-ledger");
+}
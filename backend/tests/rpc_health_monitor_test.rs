@@ -0,0 +1,189 @@
+use std::sync::Arc;
+use std::time::Duration;
+use stellar_insights_backend::rpc::circuit_breaker::{
+    CircuitBreaker, CircuitBreakerConfig, CircuitBreakers, EndpointGroup,
+};
+use stellar_insights_backend::rpc::error::RpcError;
+use stellar_insights_backend::services::event_bus::{DomainEvent, EventBus};
+use stellar_insights_backend::services::rpc_health_monitor::{
+    RpcHealthMonitor, RpcHealthMonitorConfig,
+};
+use tokio::sync::broadcast;
+
+const BREAKER_TIMEOUT: Duration = Duration::from_millis(20);
+
+fn breakers() -> CircuitBreakers {
+    CircuitBreakers::uniform(CircuitBreakerConfig {
+        failure_threshold: 2,
+        success_threshold: 1,
+        timeout_duration: BREAKER_TIMEOUT,
+        half_open_max_calls: 1,
+    })
+}
+
+async fn fail(breaker: &CircuitBreaker) {
+    let _ = breaker
+        .call(|| async { Err::<(), _>(RpcError::NetworkError("down".to_string())) })
+        .await;
+}
+
+async fn succeed(breaker: &CircuitBreaker) {
+    breaker
+        .call(|| async { Ok::<_, RpcError>(()) })
+        .await
+        .unwrap();
+}
+
+/// Let the monitor task handle the transitions published so far
+async fn settle() {
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+}
+
+/// Move the paused clock forward and let the monitor act on what came due
+async fn advance(duration: Duration) {
+    settle().await;
+    tokio::time::advance(duration).await;
+    settle().await;
+}
+
+/// Let the open circuit time out into half-open
+async fn wait_half_open() {
+    advance(BREAKER_TIMEOUT + Duration::from_millis(10)).await;
+}
+
+#[tokio::test]
+async fn test_flapping_breaker_emits_one_degraded_and_one_recovered() {
+    tokio::time::pause();
+    let breakers = breakers();
+    let breaker = breakers.get(EndpointGroup::Core);
+    let event_bus = Arc::new(EventBus::new());
+    let mut events = event_bus.subscribe();
+    let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+    let monitor = RpcHealthMonitor::new(
+        Arc::clone(&event_bus),
+        "horizon",
+        RpcHealthMonitorConfig {
+            recovery_debounce: Duration::from_millis(100),
+        },
+    );
+    let handle = tokio::spawn(monitor.run(breakers.subscribe(), shutdown_rx));
+
+    // Open, then reopen from half-open
+    fail(breaker).await;
+    fail(breaker).await;
+    wait_half_open().await;
+    fail(breaker).await;
+
+    // Close, then open again inside the recovery debounce
+    wait_half_open().await;
+    succeed(breaker).await;
+    fail(breaker).await;
+    fail(breaker).await;
+
+    // Close for good
+    wait_half_open().await;
+    succeed(breaker).await;
+    settle().await;
+
+    match events.try_recv().unwrap() {
+        DomainEvent::RpcDegraded(event) => {
+            assert_eq!(event.endpoint, "horizon");
+            assert_eq!(event.group, "core");
+            assert_eq!(event.status, "degraded");
+        }
+        other => panic!("expected RpcDegraded, got {:?}", other),
+    }
+
+    // Recovery waits out the full debounce; timers fire on the next
+    // millisecond tick after their deadline
+    advance(Duration::from_millis(99)).await;
+    assert!(events.try_recv().is_err());
+    advance(Duration::from_millis(2)).await;
+    match events.try_recv().unwrap() {
+        DomainEvent::RpcRecovered(event) => {
+            assert_eq!(event.endpoint, "horizon");
+            assert_eq!(event.status, "recovered");
+        }
+        other => panic!("expected RpcRecovered, got {:?}", other),
+    }
+    assert!(events.try_recv().is_err());
+
+    shutdown_tx.send(()).unwrap();
+    tokio::time::timeout(Duration::from_secs(5), handle)
+        .await
+        .expect("monitor did not stop on shutdown")
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_failures_below_threshold_emit_nothing() {
+    tokio::time::pause();
+    let breakers = breakers();
+    let breaker = breakers.get(EndpointGroup::Core);
+    let event_bus = Arc::new(EventBus::new());
+    let mut events = event_bus.subscribe();
+    let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+    let monitor = RpcHealthMonitor::new(
+        Arc::clone(&event_bus),
+        "horizon",
+        RpcHealthMonitorConfig {
+            recovery_debounce: Duration::from_millis(10),
+        },
+    );
+    tokio::spawn(monitor.run(breakers.subscribe(), shutdown_rx));
+
+    fail(breaker).await;
+    succeed(breaker).await;
+    fail(breaker).await;
+    advance(Duration::from_millis(50)).await;
+
+    assert!(events.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_each_group_reports_its_own_outage() {
+    tokio::time::pause();
+    let breakers = breakers();
+    let event_bus = Arc::new(EventBus::new());
+    let mut events = event_bus.subscribe();
+    let (_shutdown_tx, shutdown_rx) = broadcast::channel(1);
+    let monitor = RpcHealthMonitor::new(
+        Arc::clone(&event_bus),
+        "horizon",
+        RpcHealthMonitorConfig {
+            recovery_debounce: Duration::from_millis(10),
+        },
+    );
+    tokio::spawn(monitor.run(breakers.subscribe(), shutdown_rx));
+
+    let markets = breakers.get(EndpointGroup::Markets);
+    fail(markets).await;
+    fail(markets).await;
+    settle().await;
+
+    match events.try_recv().unwrap() {
+        DomainEvent::RpcDegraded(event) => assert_eq!(event.group, "markets"),
+        other => panic!("expected RpcDegraded, got {:?}", other),
+    }
+
+    // Another group opening is a separate outage
+    let accounts = breakers.get(EndpointGroup::Accounts);
+    fail(accounts).await;
+    fail(accounts).await;
+    settle().await;
+    match events.try_recv().unwrap() {
+        DomainEvent::RpcDegraded(event) => assert_eq!(event.group, "accounts"),
+        other => panic!("expected RpcDegraded, got {:?}", other),
+    }
+
+    wait_half_open().await;
+    succeed(markets).await;
+    advance(Duration::from_millis(20)).await;
+    match events.try_recv().unwrap() {
+        DomainEvent::RpcRecovered(event) => assert_eq!(event.group, "markets"),
+        other => panic!("expected RpcRecovered, got {:?}", other),
+    }
+    assert!(events.try_recv().is_err());
+}
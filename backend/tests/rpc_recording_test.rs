@@ -0,0 +1,128 @@
+use axum::{
+    routing::{get, post},
+    Json, Router,
+};
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use stellar_insights_backend::rpc::{RecordingConfig, RecordingMode, StellarRpcClient};
+
+/// Horizon and RPC mocks counting the requests they serve
+async fn mock_network(hits: Arc<AtomicUsize>) -> (String, String) {
+    let ledger_hits = Arc::clone(&hits);
+    let app = Router::new()
+        .route(
+            "/horizon/ledgers",
+            get(move || {
+                let sequence = 100 + ledger_hits.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    Json(json!({ "_embedded": { "records": [{
+                        "sequence": sequence,
+                        "hash": format!("ledger-{}", sequence),
+                        "previous_hash": "prev",
+                        "transaction_count": 1,
+                        "operation_count": 1,
+                        "closed_at": "2026-01-01T00:00:00Z",
+                        "total_coins": "1",
+                        "fee_pool": "1",
+                        "base_fee": 100,
+                        "base_reserve": "0.5"
+                    }] } }))
+                }
+            }),
+        )
+        .route(
+            "/rpc",
+            post(move |Json(request): Json<Value>| {
+                hits.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    assert_eq!(request["method"], "getHealth");
+                    Json(json!({ "jsonrpc": "2.0", "id": 1, "result": {
+                        "status": "healthy",
+                        "latestLedger": 200,
+                        "oldestLedger": 100,
+                        "ledgerRetentionWindow": 100
+                    } }))
+                }
+            }),
+        );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    (
+        format!("http://{}/rpc", addr),
+        format!("http://{}/horizon", addr),
+    )
+}
+
+fn client(urls: &(String, String), mode: RecordingMode, dir: &std::path::Path) -> StellarRpcClient {
+    StellarRpcClient::new(urls.0.clone(), urls.1.clone(), false)
+        .with_recording(RecordingConfig::new(mode, dir))
+}
+
+fn fixture_count(dir: &std::path::Path) -> usize {
+    std::fs::read_dir(dir)
+        .map(|entries| entries.count())
+        .unwrap_or(0)
+}
+
+#[tokio::test]
+async fn test_record_then_replay() {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let urls = mock_network(Arc::clone(&hits)).await;
+    let dir = tempfile::tempdir().unwrap();
+
+    let recorder = client(&urls, RecordingMode::Record, dir.path());
+    let ledger = recorder.fetch_latest_ledger().await.unwrap();
+    let health = recorder.check_health().await.unwrap();
+    assert_eq!(ledger.sequence, 100);
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+    assert_eq!(fixture_count(dir.path()), 2);
+
+    // Replay answers from the fixtures without touching the network; a live
+    // call would now see ledger 101
+    let replayer = client(&urls, RecordingMode::Replay, dir.path());
+    let replayed_ledger = replayer.fetch_latest_ledger().await.unwrap();
+    let replayed_health = replayer.check_health().await.unwrap();
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        serde_json::to_value(&replayed_ledger).unwrap(),
+        serde_json::to_value(&ledger).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_value(&replayed_health).unwrap(),
+        serde_json::to_value(&health).unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_replay_is_keyed_by_request_parameters() {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let urls = mock_network(Arc::clone(&hits)).await;
+    let dir = tempfile::tempdir().unwrap();
+
+    client(&urls, RecordingMode::Record, dir.path())
+        .fetch_latest_ledger()
+        .await
+        .unwrap();
+
+    // Nothing was recorded for this request, and replay does not go live
+    let err = client(&urls, RecordingMode::Replay, dir.path())
+        .check_health()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("No recorded fixture"), "{}", err);
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+    // Another base URL is another request
+    let other_urls = (
+        urls.0.replace("/rpc", "/rpc2"),
+        urls.1.replace("/horizon", "/h2"),
+    );
+    assert!(client(&other_urls, RecordingMode::Replay, dir.path())
+        .fetch_latest_ledger()
+        .await
+        .is_err());
+}
@@ -0,0 +1,166 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::{routing::post, Json, Router};
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::network::StellarNetwork;
+use stellar_insights_backend::services::contract::{ContractConfig, ContractService};
+use stellar_insights_backend::services::snapshot::SnapshotService;
+use stellar_insights_backend::snapshot_handlers::{routes, SnapshotAppState};
+use tower::util::ServiceExt;
+
+/// What the mock contract answers for an epoch's `get_snapshot`
+#[derive(Clone)]
+enum OnChain {
+    Hash(String),
+    Failure,
+}
+
+/// Mock Soroban RPC answering `get_snapshot` per epoch; epochs it has no
+/// entry for are not found
+async fn mock_contract(snapshots: HashMap<u64, OnChain>) -> Arc<ContractService> {
+    let app = Router::new().route(
+        "/",
+        post(move |Json(request): Json<Value>| {
+            let snapshots = snapshots.clone();
+            async move {
+                let epoch: u64 = request["params"]["transaction"]["args"][0]["value"]
+                    .as_str()
+                    .unwrap()
+                    .parse()
+                    .unwrap();
+                let error = |message: &str| {
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "error": { "code": -32000, "message": message }
+                    })
+                };
+                Json(match snapshots.get(&epoch) {
+                    Some(OnChain::Hash(hash)) => {
+                        json!({ "jsonrpc": "2.0", "id": 1, "result": { "returnValue": hash } })
+                    }
+                    Some(OnChain::Failure) => error("internal failure"),
+                    None => error("Snapshot not found"),
+                })
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    Arc::new(
+        ContractService::new(ContractConfig {
+            rpc_url: format!("http://{}/", addr),
+            network: StellarNetwork::Testnet,
+            contract_id: "CBGTG4JJFEQE3SPBGQFP3X5HM46N47LXZPXQACVKB7QA6X2XB2IG5CTA".to_string(),
+            network_passphrase: "Test SDF Network ; September 2015".to_string(),
+            source_secret_key: "S...".to_string(),
+        })
+        .unwrap(),
+    )
+}
+
+/// Store a snapshot for `epoch` and return its SHA-256 hash
+async fn store_snapshot(db: &Arc<Database>, epoch: u64) -> String {
+    SnapshotService::new(Arc::clone(db), None)
+        .generate_and_submit_snapshot(epoch)
+        .await
+        .unwrap()
+        .hash
+}
+
+fn router(db: Arc<Database>, contract_service: Arc<ContractService>) -> Router {
+    routes(SnapshotAppState {
+        db: Arc::clone(&db),
+        contract_service: Some(Arc::clone(&contract_service)),
+        snapshot_service: Arc::new(SnapshotService::new(db, Some(contract_service))),
+    })
+}
+
+async fn get(app: &Router, uri: &str) -> (StatusCode, Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test]
+async fn test_batch_verify_reports_mixed_presence(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let matching = store_snapshot(&db, 1).await;
+    let stored = store_snapshot(&db, 2).await;
+    store_snapshot(&db, 3).await;
+    store_snapshot(&db, 5).await;
+    let other = "ab".repeat(32);
+
+    let contract = mock_contract(HashMap::from([
+        (1, OnChain::Hash(matching.to_uppercase())),
+        (2, OnChain::Hash(other.clone())),
+        (4, OnChain::Hash(other.clone())),
+        (5, OnChain::Failure),
+    ]))
+    .await;
+    let app = router(db, contract);
+
+    let (status, body) = get(&app, "/api/snapshots/verify?epochs=1,2,3,4,5,1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["all_match"], false);
+    let results = body["results"].as_array().unwrap();
+    let report: Vec<(u64, bool, bool, bool)> = results
+        .iter()
+        .map(|r| {
+            (
+                r["epoch"].as_u64().unwrap(),
+                r["in_db"].as_bool().unwrap(),
+                r["on_chain"].as_bool().unwrap(),
+                r["hash_match"].as_bool().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        report,
+        [
+            (1, true, true, true),
+            (2, true, true, false),
+            (3, true, false, false),
+            (4, false, true, false),
+            (5, true, false, false),
+        ]
+    );
+
+    assert_eq!(results[1]["stored_hash"], stored);
+    assert_eq!(results[1]["on_chain_hash"], other);
+    assert!(results[2]["chain_error"].is_null());
+    assert!(results[3]["stored_hash"].is_null());
+    assert!(results[4]["chain_error"]
+        .as_str()
+        .unwrap()
+        .contains("internal failure"));
+
+    let (_, body) = get(&app, "/api/snapshots/verify?epochs=1").await;
+    assert_eq!(body["all_match"], true);
+}
+
+#[sqlx::test]
+async fn test_batch_verify_rejects_bad_epoch_lists(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let app = router(db, mock_contract(HashMap::new()).await);
+
+    let too_many: Vec<String> = (0..=100).map(|e| e.to_string()).collect();
+    let too_many = too_many.join(",");
+
+    for epochs in ["", "1,two", too_many.as_str()] {
+        let (status, body) = get(&app, &format!("/api/snapshots/verify?epochs={}", epochs)).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", epochs);
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Invalid epochs"));
+    }
+}
@@ -0,0 +1,77 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{header, Request, StatusCode};
+use axum::Router;
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::snapshot::SnapshotService;
+use stellar_insights_backend::snapshot::SnapshotEnvelope;
+use stellar_insights_backend::snapshot_handlers::{routes, SnapshotAppState};
+use tower::util::ServiceExt;
+
+fn router(db: Arc<Database>) -> Router {
+    routes(SnapshotAppState {
+        db: Arc::clone(&db),
+        contract_service: None,
+        snapshot_service: Arc::new(SnapshotService::new(db, None)),
+    })
+}
+
+async fn get(app: &Router, uri: &str) -> axum::response::Response {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    app.clone().oneshot(request).await.unwrap()
+}
+
+#[sqlx::test]
+async fn test_canonical_json_hashes_to_stored_hash(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let stored_hash = SnapshotService::new(Arc::clone(&db), None)
+        .generate_and_submit_snapshot(3)
+        .await
+        .unwrap()
+        .hash;
+    let app = router(db);
+
+    let response = get(&app, "/api/snapshots/3/canonical").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    assert_eq!(response.headers()["x-snapshot-hash"], stored_hash.as_str());
+    assert_eq!(response.headers()["x-snapshot-hash-algorithm"], "sha256");
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    assert_eq!(hex::encode(Sha256::digest(&body)), stored_hash);
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["epoch"], 3);
+}
+
+#[sqlx::test]
+async fn test_export_envelope_payload_hashes_to_stored_hash(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let stored_hash = SnapshotService::new(Arc::clone(&db), None)
+        .generate_and_submit_snapshot(4)
+        .await
+        .unwrap()
+        .hash;
+    let app = router(db);
+
+    let response = get(&app, "/api/snapshots/4/export").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-snapshot-hash"], stored_hash.as_str());
+
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let envelope = SnapshotEnvelope::parse(std::str::from_utf8(&body).unwrap()).unwrap();
+    assert_eq!(envelope.format, "stellar-insights-snapshot");
+    assert_eq!(envelope.payload["epoch"], 4);
+    let payload = envelope.canonical_json().unwrap();
+    assert_eq!(hex::encode(Sha256::digest(payload.as_bytes())), stored_hash);
+}
+
+#[sqlx::test]
+async fn test_missing_epoch_is_not_found(pool: SqlitePool) {
+    let app = router(Arc::new(Database::new(pool)));
+
+    for uri in ["/api/snapshots/99/canonical", "/api/snapshots/99/export"] {
+        assert_eq!(get(&app, uri).await.status(), StatusCode::NOT_FOUND);
+    }
+}
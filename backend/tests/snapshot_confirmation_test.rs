@@ -0,0 +1,85 @@
+use axum::{routing::post, Json, Router};
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::network::StellarNetwork;
+use stellar_insights_backend::services::contract::{ContractConfig, ContractService};
+use stellar_insights_backend::services::snapshot::{ConfirmationConfig, SnapshotService};
+
+const HASH: &str = "0707070707070707070707070707070707070707070707070707070707070707";
+
+/// Mock Soroban RPC whose `verify_snapshot` simulation only finds the hash
+/// from check `visible_from` (counted from 1) on. Returns the service and
+/// the number of checks made.
+async fn mock_contract(visible_from: usize) -> (Arc<ContractService>, Arc<AtomicUsize>) {
+    let checks = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&checks);
+    let app = Router::new().route(
+        "/",
+        post(move || {
+            let check = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            async move {
+                Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": { "returnValue": check >= visible_from }
+                }))
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let service = ContractService::new(ContractConfig {
+        rpc_url: format!("http://{}/", addr),
+        network: StellarNetwork::Testnet,
+        contract_id: "CBGTG4JJFEQE3SPBGQFP3X5HM46N47LXZPXQACVKB7QA6X2XB2IG5CTA".to_string(),
+        network_passphrase: "Test SDF Network ; September 2015".to_string(),
+        source_secret_key: "S...".to_string(),
+    })
+    .unwrap();
+    (Arc::new(service), checks)
+}
+
+fn service(pool: SqlitePool, contract: Arc<ContractService>) -> SnapshotService {
+    SnapshotService::new(Arc::new(Database::new(pool)), Some(contract)).with_confirmation_config(
+        ConfirmationConfig {
+            min_delay: Duration::ZERO,
+            max_checks: 4,
+            backoff: Duration::from_millis(10),
+        },
+    )
+}
+
+#[sqlx::test]
+async fn test_hash_appearing_on_second_check_is_confirmed(pool: SqlitePool) {
+    let (contract, checks) = mock_contract(2).await;
+
+    let start = Instant::now();
+    assert!(service(pool, contract)
+        .confirm_on_chain(HASH, 7)
+        .await
+        .unwrap());
+    assert_eq!(checks.load(Ordering::SeqCst), 2);
+    // Only the 10ms backoff was waited, not a fixed confirmation sleep
+    assert!(
+        start.elapsed() < Duration::from_secs(1),
+        "{:?}",
+        start.elapsed()
+    );
+}
+
+#[sqlx::test]
+async fn test_hash_never_appearing_is_not_confirmed(pool: SqlitePool) {
+    let (contract, checks) = mock_contract(usize::MAX).await;
+
+    assert!(!service(pool, contract)
+        .confirm_on_chain(HASH, 7)
+        .await
+        .unwrap());
+    assert_eq!(checks.load(Ordering::SeqCst), 4);
+}
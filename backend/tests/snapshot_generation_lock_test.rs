@@ -0,0 +1,33 @@
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::snapshot::{GenerationInProgress, SnapshotService};
+
+#[sqlx::test]
+async fn test_concurrent_generation_is_rejected(pool: SqlitePool) {
+    let service = SnapshotService::new(Arc::new(Database::new(pool.clone())), None);
+
+    let (first, second) = tokio::join!(
+        service.generate_and_submit_snapshot(1),
+        service.generate_and_submit_snapshot(2)
+    );
+
+    // The first call holds the slot while it waits on the database
+    let first = first.unwrap();
+    assert_eq!(first.epoch, 1);
+    let err = second.unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<GenerationInProgress>(),
+        Some(&GenerationInProgress { epoch: 1 })
+    );
+
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM snapshots")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 1);
+
+    // The slot is released once the generation finishes
+    let next = service.generate_and_submit_snapshot(2).await.unwrap();
+    assert_eq!(next.epoch, 2);
+}
@@ -63,6 +63,44 @@ async fn setup_test_database() -> Arc<Database> {
     .await
     .unwrap();
 
+    let _: sqlx::sqlite::SqliteQueryResult = sqlx::query(
+        r#"
+        CREATE TABLE liquidity_pools (
+            pool_id TEXT PRIMARY KEY,
+            fee_bp INTEGER NOT NULL DEFAULT 30,
+            reserve_a_asset_code TEXT NOT NULL,
+            reserve_a_asset_issuer TEXT,
+            reserve_a_amount REAL NOT NULL DEFAULT 0.0,
+            reserve_b_asset_code TEXT NOT NULL,
+            reserve_b_asset_issuer TEXT,
+            reserve_b_amount REAL NOT NULL DEFAULT 0.0,
+            reserve_a_amount_exact TEXT,
+            reserve_b_amount_exact TEXT,
+            total_value_usd REAL NOT NULL DEFAULT 0.0
+        )
+    "#,
+    )
+    .execute(db.pool())
+    .await
+    .unwrap();
+
+    let _: sqlx::sqlite::SqliteQueryResult = sqlx::query(
+        r#"
+        CREATE TABLE corridors (
+            id TEXT PRIMARY KEY,
+            source_asset_code TEXT NOT NULL,
+            source_asset_issuer TEXT NOT NULL,
+            destination_asset_code TEXT NOT NULL,
+            destination_asset_issuer TEXT NOT NULL,
+            status TEXT DEFAULT 'active',
+            deleted_at TEXT
+        )
+    "#,
+    )
+    .execute(db.pool())
+    .await
+    .unwrap();
+
     let _: sqlx::sqlite::SqliteQueryResult = sqlx::query(
         r#"
         CREATE TABLE snapshots (
@@ -71,6 +109,7 @@ async fn setup_test_database() -> Arc<Database> {
             entity_type TEXT NOT NULL,
             data TEXT NOT NULL,
             hash TEXT,
+            hash_algorithm TEXT NOT NULL DEFAULT 'sha256',
             epoch INTEGER,
             timestamp TEXT NOT NULL,
             created_at TEXT DEFAULT CURRENT_TIMESTAMP
@@ -81,6 +120,16 @@ async fn setup_test_database() -> Arc<Database> {
     .await
     .unwrap();
 
+    let _: sqlx::sqlite::SqliteQueryResult = sqlx::query(
+        r#"
+        CREATE UNIQUE INDEX idx_snapshots_analytics_epoch
+            ON snapshots(epoch) WHERE entity_type = 'analytics_snapshot'
+    "#,
+    )
+    .execute(db.pool())
+    .await
+    .unwrap();
+
     // Insert test data
     let _: sqlx::sqlite::SqliteQueryResult = sqlx::query(r#"
         INSERT INTO anchors (id, name, stellar_account, total_transactions, successful_transactions, failed_transactions, total_volume_usd, avg_settlement_time_ms, reliability_score, status)
@@ -127,6 +176,30 @@ async fn test_acceptance_criteria_1_aggregate_all_metrics() {
     );
 }
 
+#[tokio::test]
+async fn test_aggregation_skips_soft_deleted_corridors() {
+    let db = setup_test_database().await;
+    sqlx::query(
+        r#"
+        INSERT INTO corridors (id, source_asset_code, source_asset_issuer, destination_asset_code, destination_asset_issuer, status, deleted_at)
+        VALUES ('corridor-1', 'USDC', 'ISSUER1', 'GBPC', 'ISSUER3', 'deleted', datetime('now'))
+    "#,
+    )
+    .execute(db.pool())
+    .await
+    .unwrap();
+    let service = SnapshotService::new(db, None);
+
+    let snapshot = service.aggregate_all_metrics(1).await.unwrap();
+
+    let keys: Vec<&str> = snapshot
+        .corridor_metrics
+        .iter()
+        .map(|c| c.corridor_key.as_str())
+        .collect();
+    assert_eq!(keys, ["USDC:ISSUER1->EURC:ISSUER2"]);
+}
+
 #[tokio::test]
 async fn test_acceptance_criteria_2_serialize_deterministic_json() {
     println!("🧪 Testing Acceptance Criteria 2: Serialize to deterministic JSON");
@@ -0,0 +1,133 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use chrono::{DateTime, Utc};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::snapshot::{EpochNotStarted, SnapshotService};
+use stellar_insights_backend::snapshot::{EpochCalendar, DEFAULT_EPOCH_DURATION_SECS};
+use stellar_insights_backend::snapshot_handlers::{routes, SnapshotAppState};
+use tower::util::ServiceExt;
+
+async fn seed_snapshots(pool: &SqlitePool, epochs: &[i64]) {
+    for epoch in epochs {
+        sqlx::query(
+            r#"
+            INSERT INTO snapshots (id, entity_id, entity_type, data, hash, epoch, timestamp, created_at)
+            VALUES ($1, 'analytics_snapshot', 'analytics_snapshot', '{}', $2, $3, $4, $4)
+            "#,
+        )
+        .bind(format!("snapshot-{}", epoch))
+        .bind(format!("{:064x}", epoch))
+        .bind(epoch)
+        .bind("2026-01-01T00:00:00+00:00")
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+}
+
+/// Weekly epochs, epoch 0 being ISO week 2026-W01
+fn weekly_calendar() -> EpochCalendar {
+    let genesis: DateTime<Utc> = "2025-12-29T00:00:00Z".parse().unwrap();
+    EpochCalendar::new(genesis, DEFAULT_EPOCH_DURATION_SECS).unwrap()
+}
+
+fn router(pool: SqlitePool, calendar: Option<EpochCalendar>) -> axum::Router {
+    let db = Arc::new(Database::new(pool));
+    let mut snapshot_service = SnapshotService::new(Arc::clone(&db), None);
+    if let Some(calendar) = calendar {
+        snapshot_service = snapshot_service.with_epoch_calendar(calendar);
+    }
+    routes(SnapshotAppState {
+        db,
+        contract_service: None,
+        snapshot_service: Arc::new(snapshot_service),
+    })
+}
+
+async fn get(app: &axum::Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test]
+async fn test_lookup_by_label(pool: SqlitePool) {
+    seed_snapshots(&pool, &[1, 2]).await;
+    let app = router(pool, Some(weekly_calendar()));
+
+    let (status, body) = get(&app, "/api/snapshots/by-label/2026-W03").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["epoch"], 2);
+    assert_eq!(body["id"], "snapshot-2");
+    assert_eq!(body["epoch_label"], "2026-W03");
+
+    // A valid label of an epoch without a snapshot
+    let (status, body) = get(&app, "/api/snapshots/by-label/2026-W05").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(body["error"].as_str().unwrap().contains("epoch 4"));
+
+    let (status, _) = get(&app, "/api/snapshots/by-label/not-a-week").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_snapshot_responses_carry_labels(pool: SqlitePool) {
+    seed_snapshots(&pool, &[1, 2]).await;
+    let app = router(pool.clone(), Some(weekly_calendar()));
+
+    let (_, list) = get(&app, "/api/snapshots").await;
+    let labels: Vec<&str> = list["snapshots"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["epoch_label"].as_str().unwrap())
+        .collect();
+    assert_eq!(labels, ["2026-W03", "2026-W02"]);
+
+    let (_, recent) = get(&app, "/api/snapshots/recent?n=1").await;
+    assert_eq!(recent["snapshots"][0]["epoch_label"], "2026-W03");
+
+    // Without a calendar epochs stay unlabelled and no label resolves
+    let app = router(pool, None);
+    let (_, list) = get(&app, "/api/snapshots").await;
+    assert!(list["snapshots"][0].get("epoch_label").is_none());
+    let (status, _) = get(&app, "/api/snapshots/by-label/2026-W03").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_calendar_assigns_the_current_epoch(pool: SqlitePool) {
+    seed_snapshots(&pool, &[1, 2]).await;
+    let db = Arc::new(Database::new(pool));
+    let current = weekly_calendar().derive_epoch(Utc::now()).unwrap();
+
+    let service =
+        SnapshotService::new(Arc::clone(&db), None).with_epoch_calendar(weekly_calendar());
+    assert_eq!(service.next_epoch().await.unwrap(), current);
+    let err = service
+        .generate_and_submit_snapshot(current + 1)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<EpochNotStarted>(),
+        Some(&EpochNotStarted { epoch: current + 1 })
+    );
+
+    // Without a calendar epochs are numbered after the latest stored one
+    let service = SnapshotService::new(db, None);
+    assert_eq!(service.next_epoch().await.unwrap(), 3);
+}
+
+#[sqlx::test]
+async fn test_label_lookup_database_failure_is_internal(pool: SqlitePool) {
+    let app = router(pool.clone(), Some(weekly_calendar()));
+    pool.close().await;
+
+    let (status, body) = get(&app, "/api/snapshots/by-label/2026-W03").await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(body["error"], "Failed to read stored snapshots");
+}
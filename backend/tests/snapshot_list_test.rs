@@ -0,0 +1,182 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::Extension;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::api::pagination::{PaginationConfig, DEFAULT_MAX_OFFSET};
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::snapshot::SnapshotService;
+use stellar_insights_backend::snapshot_handlers::{routes, SnapshotAppState};
+use tower::util::ServiceExt;
+
+async fn seed_snapshot(pool: &SqlitePool, id: &str, entity_type: &str, epoch: i64) {
+    sqlx::query(
+        r#"
+        INSERT INTO snapshots (id, entity_id, entity_type, data, hash, epoch, timestamp, created_at)
+        VALUES ($1, $2, $2, '{}', $3, $4, $5, $5)
+        "#,
+    )
+    .bind(id)
+    .bind(entity_type)
+    .bind(format!("{:064x}", epoch))
+    .bind(epoch)
+    .bind("2024-01-01T00:00:00+00:00")
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn seed_snapshots(pool: &SqlitePool, epochs: std::ops::RangeInclusive<i64>) {
+    for epoch in epochs {
+        let id = format!("snapshot-{}", epoch);
+        seed_snapshot(pool, &id, "analytics_snapshot", epoch).await;
+    }
+}
+
+fn router(pool: SqlitePool) -> axum::Router {
+    router_with_pagination(pool, PaginationConfig::default())
+}
+
+fn router_with_pagination(pool: SqlitePool, pagination: PaginationConfig) -> axum::Router {
+    let db = Arc::new(Database::new(pool));
+    routes(SnapshotAppState {
+        db: Arc::clone(&db),
+        contract_service: None,
+        snapshot_service: Arc::new(SnapshotService::new(db, None)),
+    })
+    .layer(Extension(pagination))
+}
+
+async fn list(app: &axum::Router, query: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .uri(format!("/api/snapshots{}", query))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn cursor(body: &serde_json::Value) -> &str {
+    body["next_cursor"].as_str().unwrap()
+}
+
+fn epochs(body: &serde_json::Value) -> Vec<i64> {
+    body["snapshots"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["epoch"].as_i64().unwrap())
+        .collect()
+}
+
+#[sqlx::test]
+async fn test_offset_within_limit(pool: SqlitePool) {
+    seed_snapshots(&pool, 1..=5).await;
+    let app = router(pool);
+
+    let (status, body) = list(&app, "?limit=2&offset=1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(epochs(&body), [4, 3]);
+    assert!(body["next_cursor"].is_string());
+
+    let (status, body) = list(&app, &format!("?offset={}", DEFAULT_MAX_OFFSET)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(epochs(&body), Vec::<i64>::new());
+}
+
+#[sqlx::test]
+async fn test_excessive_offset_suggests_cursor(pool: SqlitePool) {
+    let app = router(pool);
+
+    let (status, body) = list(&app, &format!("?offset={}", DEFAULT_MAX_OFFSET + 1)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "OFFSET_TOO_LARGE");
+    assert!(body["error"]["message"]
+        .as_str()
+        .unwrap()
+        .contains("`cursor`"));
+    assert_eq!(body["error"]["details"]["suggestion"], "cursor");
+    assert_eq!(body["error"]["details"]["max_offset"], DEFAULT_MAX_OFFSET);
+}
+
+#[sqlx::test]
+async fn test_configured_max_offset(pool: SqlitePool) {
+    seed_snapshots(&pool, 1..=5).await;
+    let app = router_with_pagination(pool, PaginationConfig { max_offset: 2 });
+
+    let (status, body) = list(&app, "?offset=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(epochs(&body), [3, 2, 1]);
+
+    let (status, body) = list(&app, "?offset=3").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["details"]["max_offset"], 2);
+}
+
+#[sqlx::test]
+async fn test_cursor_pages_through_all_snapshots(pool: SqlitePool) {
+    seed_snapshots(&pool, 1..=5).await;
+    let app = router(pool);
+
+    let (_, first) = list(&app, "?limit=2").await;
+    assert_eq!(epochs(&first), [5, 4]);
+
+    let (_, second) = list(&app, &format!("?limit=2&cursor={}", cursor(&first))).await;
+    assert_eq!(epochs(&second), [3, 2]);
+
+    let (_, last) = list(&app, &format!("?limit=2&cursor={}", cursor(&second))).await;
+    assert_eq!(epochs(&last), [1]);
+    assert_eq!(last["next_cursor"], serde_json::Value::Null);
+}
+
+#[sqlx::test]
+async fn test_cursor_pages_through_snapshots_sharing_an_epoch(pool: SqlitePool) {
+    for id in ["a", "b", "c"] {
+        seed_snapshot(&pool, id, "anchor", 7).await;
+    }
+    seed_snapshot(&pool, "d", "anchor", 6).await;
+    let app = router(pool);
+
+    let (_, first) = list(&app, "?limit=2").await;
+    let (_, second) = list(&app, &format!("?limit=2&cursor={}", cursor(&first))).await;
+
+    let ids = |body: &serde_json::Value| -> Vec<String> {
+        body["snapshots"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(ids(&first), ["c", "b"]);
+    assert_eq!(ids(&second), ["a", "d"]);
+}
+
+#[sqlx::test]
+async fn test_entity_type_filter(pool: SqlitePool) {
+    seed_snapshots(&pool, 1..=3).await;
+    seed_snapshot(&pool, "anchor-2", "anchor", 2).await;
+    let app = router(pool);
+
+    let (status, body) = list(&app, "?entity_type=anchor").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(epochs(&body), [2]);
+
+    let (_, first) = list(&app, "?limit=1&entity_type=analytics_snapshot").await;
+    let query = format!(
+        "?limit=2&entity_type=analytics_snapshot&cursor={}",
+        cursor(&first)
+    );
+    let (_, rest) = list(&app, &query).await;
+    assert_eq!(epochs(&first), [3]);
+    assert_eq!(epochs(&rest), [2, 1]);
+}
+
+#[sqlx::test]
+async fn test_invalid_cursor_is_rejected(pool: SqlitePool) {
+    let (status, body) = list(&router(pool), "?cursor=4").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "INVALID_CURSOR");
+}
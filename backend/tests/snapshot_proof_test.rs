@@ -0,0 +1,180 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::snapshot::{HashAlgorithm, ProofBundle, SnapshotService};
+use stellar_insights_backend::snapshot_handlers::{routes, SnapshotAppState};
+use tower::util::ServiceExt;
+
+fn signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[7u8; 32])
+}
+
+async fn seed_anchor(pool: &SqlitePool) {
+    sqlx::query(
+        r#"
+        INSERT INTO anchors (
+            id, name, stellar_account, total_transactions, successful_transactions,
+            failed_transactions, total_volume_usd, reliability_score, status
+        )
+        VALUES (
+            '550e8400-e29b-41d4-a716-446655440000', 'Anchor', 'GANCHOR', 100, 98, 2,
+            5000.0, 0.97, 'green'
+        )
+        "#,
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[sqlx::test]
+async fn test_proof_bundle_verifies_its_own_hash(pool: SqlitePool) {
+    seed_anchor(&pool).await;
+    let service = SnapshotService::new(Arc::new(Database::new(pool)), None)
+        .with_proof_signing_key(signing_key());
+    let public_key = service.proof_public_key().unwrap();
+    assert_eq!(public_key, signing_key().verifying_key());
+
+    let generated = service.generate_and_submit_snapshot(7).await.unwrap();
+    let bundle = service.export_proof_bundle(7).await.unwrap();
+
+    assert_eq!(bundle.epoch, 7);
+    assert_eq!(bundle.snapshot_id, generated.snapshot_id);
+    assert_eq!(bundle.hash, generated.hash);
+    assert_eq!(bundle.canonical_json, generated.canonical_json);
+    assert_eq!(bundle.on_chain_hash, None);
+    assert!(bundle.verify());
+    assert!(bundle.verify_signature(&public_key));
+    assert!(!bundle.verify_signature(&SigningKey::from_bytes(&[8u8; 32]).verifying_key()));
+
+    // The bundle round-trips through its JSON file form
+    let file = serde_json::to_string_pretty(&bundle).unwrap();
+    let restored: stellar_insights_backend::services::snapshot::ProofBundle =
+        serde_json::from_str(&file).unwrap();
+    assert_eq!(restored, bundle);
+    assert!(restored.verify());
+    assert!(restored.verify_signature(&public_key));
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(app: &axum::Router, uri: &str) -> T {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[sqlx::test]
+async fn test_proof_bundle_served_with_published_key(pool: SqlitePool) {
+    seed_anchor(&pool).await;
+    let db = Arc::new(Database::new(pool));
+    let service = SnapshotService::new(Arc::clone(&db), None).with_proof_signing_key(signing_key());
+    service.generate_and_submit_snapshot(5).await.unwrap();
+    let app = routes(SnapshotAppState {
+        db,
+        contract_service: None,
+        snapshot_service: Arc::new(service),
+    });
+
+    let key: serde_json::Value = get_json(&app, "/api/snapshots/proof-key").await;
+    assert_eq!(key["algorithm"], "ed25519");
+    let key_bytes: [u8; 32] = hex::decode(key["public_key"].as_str().unwrap())
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let public_key = VerifyingKey::from_bytes(&key_bytes).unwrap();
+
+    let bundle: ProofBundle = get_json(&app, "/api/snapshots/5/proof").await;
+    assert_eq!(bundle.epoch, 5);
+    assert!(bundle.verify());
+    assert!(bundle.verify_signature(&public_key));
+
+    let request = Request::builder()
+        .uri("/api/snapshots/99/proof")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_tampered_proof_bundle_fails_verification(pool: SqlitePool) {
+    seed_anchor(&pool).await;
+    let service = SnapshotService::new(Arc::new(Database::new(pool)), None)
+        .with_proof_signing_key(signing_key());
+    service.generate_and_submit_snapshot(3).await.unwrap();
+
+    let mut bundle = service.export_proof_bundle(3).await.unwrap();
+    bundle.canonical_json = bundle
+        .canonical_json
+        .replace("\"total_transactions\":100", "\"total_transactions\":1000");
+
+    assert!(!bundle.verify());
+    assert!(!bundle.verify_signature(&signing_key().verifying_key()));
+
+    // Re-signing with another key does not verify against the published one
+    bundle.sign(&SigningKey::from_bytes(&[8u8; 32])).unwrap();
+    assert!(!bundle.verify_signature(&signing_key().verifying_key()));
+}
+
+#[sqlx::test]
+async fn test_missing_epoch_has_no_bundle(pool: SqlitePool) {
+    let service = SnapshotService::new(Arc::new(Database::new(pool)), None);
+    assert!(service.export_proof_bundle(42).await.is_err());
+}
+
+#[sqlx::test]
+async fn test_stored_hash_algorithm_drives_verification(pool: SqlitePool) {
+    seed_anchor(&pool).await;
+    let service = SnapshotService::new(Arc::new(Database::new(pool.clone())), None)
+        .with_hash_algorithm(HashAlgorithm::Keccak256);
+
+    let generated = service.generate_and_submit_snapshot(9).await.unwrap();
+    assert_eq!(generated.hash_algorithm, HashAlgorithm::Keccak256);
+    assert_eq!(
+        generated.hash,
+        HashAlgorithm::Keccak256.hex_digest(generated.canonical_json.as_bytes())
+    );
+
+    let stored: String = sqlx::query_scalar("SELECT hash_algorithm FROM snapshots WHERE id = ?")
+        .bind(&generated.snapshot_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, "keccak256");
+
+    // A default (SHA-256) service still exports the snapshot with the
+    // algorithm it was stored with
+    let reader = SnapshotService::new(Arc::new(Database::new(pool)), None);
+    let mut bundle = reader.export_proof_bundle(9).await.unwrap();
+    assert_eq!(bundle.hash_algorithm, HashAlgorithm::Keccak256);
+    assert_eq!(bundle.hash, generated.hash);
+    assert!(bundle.verify());
+
+    bundle.hash_algorithm = HashAlgorithm::Sha256;
+    assert!(!bundle.verify());
+}
+
+#[test]
+fn test_bundle_without_algorithm_defaults_to_sha256() {
+    let canonical_json = r#"{"epoch":1}"#;
+    let bundle: stellar_insights_backend::services::snapshot::ProofBundle =
+        serde_json::from_value(serde_json::json!({
+            "snapshot_id": "s1",
+            "epoch": 1,
+            "schema_version": 1,
+            "timestamp": "2024-01-01T00:00:00Z",
+            "canonical_json": canonical_json,
+            "hash": HashAlgorithm::Sha256.hex_digest(canonical_json.as_bytes()),
+            "on_chain_hash": null,
+            "merkle_root": null,
+            "signature": null
+        }))
+        .unwrap();
+
+    assert_eq!(bundle.hash_algorithm, HashAlgorithm::Sha256);
+    assert!(bundle.verify());
+}
@@ -0,0 +1,105 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::Extension;
+use serde_json::{json, Value};
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::api::pagination::PaginationConfig;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::services::snapshot::SnapshotService;
+use stellar_insights_backend::snapshot_handlers::{routes, SnapshotAppState};
+use tower::util::ServiceExt;
+
+/// Store a snapshot with `anchors` anchor entries and `corridors` corridor entries
+async fn seed_snapshot(
+    pool: &SqlitePool,
+    epoch: i64,
+    anchors: usize,
+    corridors: usize,
+    hash: &str,
+) {
+    let data = json!({
+        "epoch": epoch,
+        "anchor_metrics": vec![json!({}); anchors],
+        "corridor_metrics": vec![json!({}); corridors],
+    });
+    sqlx::query(
+        r#"
+        INSERT INTO snapshots (id, entity_id, entity_type, data, hash, epoch, timestamp, created_at)
+        VALUES ($1, 'analytics_snapshot', 'analytics_snapshot', $2, $3, $4, $5, $5)
+        "#,
+    )
+    .bind(format!("snapshot-{}", epoch))
+    .bind(data.to_string())
+    .bind(hash)
+    .bind(epoch)
+    .bind("2024-01-01T00:00:00+00:00")
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn recent(pool: SqlitePool, query: &str) -> Vec<Value> {
+    let db = Arc::new(Database::new(pool));
+    let app = routes(SnapshotAppState {
+        db: Arc::clone(&db),
+        contract_service: None,
+        snapshot_service: Arc::new(SnapshotService::new(db, None)),
+    })
+    .layer(Extension(PaginationConfig::default()));
+    let request = Request::builder()
+        .uri(format!("/api/snapshots/recent{}", query))
+        .body(Body::empty())
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    body["snapshots"].as_array().unwrap().clone()
+}
+
+#[sqlx::test]
+async fn test_recent_snapshots_with_deltas(pool: SqlitePool) {
+    seed_snapshot(&pool, 1, 2, 1, "aa").await;
+    seed_snapshot(&pool, 2, 4, 1, "bb").await;
+    seed_snapshot(&pool, 3, 3, 5, "bb").await;
+    seed_snapshot(&pool, 4, 6, 2, "cc").await;
+
+    let snapshots = recent(pool, "?n=3").await;
+    let epochs: Vec<i64> = snapshots
+        .iter()
+        .map(|s| s["epoch"].as_i64().unwrap())
+        .collect();
+    assert_eq!(epochs, [4, 3, 2]);
+
+    assert_eq!(snapshots[0]["anchor_count"], 6);
+    assert_eq!(snapshots[0]["anchor_count_delta"], 3);
+    assert_eq!(snapshots[0]["corridor_count_delta"], -3);
+    assert_eq!(snapshots[0]["hash"], "cc");
+    assert_eq!(snapshots[0]["hash_changed"], true);
+
+    assert_eq!(snapshots[1]["anchor_count_delta"], -1);
+    assert_eq!(snapshots[1]["corridor_count_delta"], 4);
+    assert_eq!(snapshots[1]["hash_changed"], false);
+
+    // Epoch 2 is diffed against epoch 1 even though epoch 1 is not returned
+    assert_eq!(snapshots[2]["previous_epoch"], 1);
+    assert_eq!(snapshots[2]["anchor_count_delta"], 2);
+    assert_eq!(snapshots[2]["corridor_count_delta"], 0);
+}
+
+#[sqlx::test]
+async fn test_first_snapshot_has_no_deltas(pool: SqlitePool) {
+    seed_snapshot(&pool, 1, 2, 1, "aa").await;
+    seed_snapshot(&pool, 2, 2, 2, "bb").await;
+
+    // Default n covers every stored snapshot
+    let snapshots = recent(pool, "").await;
+    assert_eq!(snapshots.len(), 2);
+    assert_eq!(snapshots[0]["corridor_count_delta"], 1);
+    let first = &snapshots[1];
+    assert_eq!(first["epoch"], 1);
+    assert_eq!(first["previous_epoch"], Value::Null);
+    assert_eq!(first["anchor_count_delta"], Value::Null);
+    assert_eq!(first["hash_changed"], Value::Null);
+}
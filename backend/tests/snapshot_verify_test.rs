@@ -0,0 +1,179 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::{routing::post, Json, Router};
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::sync::Arc;
+use stellar_insights_backend::database::Database;
+use stellar_insights_backend::network::StellarNetwork;
+use stellar_insights_backend::services::contract::{ContractConfig, ContractService};
+use stellar_insights_backend::services::snapshot::{HashAlgorithm, SnapshotService};
+use stellar_insights_backend::snapshot_handlers::{routes, SnapshotAppState};
+use tower::util::ServiceExt;
+
+/// Mock Soroban RPC whose `get_snapshot` simulation returns `on_chain_hash`
+async fn mock_contract(on_chain_hash: String) -> Arc<ContractService> {
+    let app =
+        Router::new().route(
+            "/",
+            post(move || {
+                let hash = on_chain_hash.clone();
+                async move {
+                    Json(json!({ "jsonrpc": "2.0", "id": 1, "result": { "returnValue": hash } }))
+                }
+            }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    Arc::new(
+        ContractService::new(ContractConfig {
+            rpc_url: format!("http://{}/", addr),
+            network: StellarNetwork::Testnet,
+            contract_id: "CBGTG4JJFEQE3SPBGQFP3X5HM46N47LXZPXQACVKB7QA6X2XB2IG5CTA".to_string(),
+            network_passphrase: "Test SDF Network ; September 2015".to_string(),
+            source_secret_key: "S...".to_string(),
+        })
+        .unwrap(),
+    )
+}
+
+/// Store a snapshot for `epoch` and return its hash
+async fn store_snapshot(db: &Arc<Database>, epoch: u64) -> String {
+    SnapshotService::new(Arc::clone(db), None)
+        .generate_and_submit_snapshot(epoch)
+        .await
+        .unwrap()
+        .hash
+}
+
+fn router(db: Arc<Database>, contract_service: Arc<ContractService>) -> Router {
+    routes(SnapshotAppState {
+        db: Arc::clone(&db),
+        contract_service: Some(Arc::clone(&contract_service)),
+        snapshot_service: Arc::new(SnapshotService::new(db, Some(contract_service))),
+    })
+}
+
+async fn verify(app: &Router, uri: &str) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+#[sqlx::test]
+async fn test_matching_hash_is_canonical(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let hash = store_snapshot(&db, 5).await;
+    let app = router(Arc::clone(&db), mock_contract(hash.clone()).await);
+
+    let (status, body) = verify(
+        &app,
+        &format!("/api/snapshots/5/verify?hash={}", hash.to_uppercase()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["epoch"], 5);
+    assert_eq!(body["hash"], hash);
+    assert_eq!(body["stored_hash"], hash);
+    assert_eq!(body["on_chain_hash"], hash);
+    assert_eq!(body["matches_stored"], true);
+    assert_eq!(body["matches_on_chain"], true);
+    assert_eq!(body["canonical"], true);
+}
+
+#[sqlx::test]
+async fn test_mismatched_hash_is_not_canonical(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let stored = store_snapshot(&db, 5).await;
+    let app = router(Arc::clone(&db), mock_contract(stored.clone()).await);
+
+    let local = "ab".repeat(32);
+    let (status, body) = verify(&app, &format!("/api/snapshots/5/verify?hash={}", local)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["stored_hash"], stored);
+    assert_eq!(body["matches_stored"], false);
+    assert_eq!(body["matches_on_chain"], false);
+    assert_eq!(body["canonical"], false);
+
+    // Matching only the stored hash is not canonical
+    let app = router(Arc::clone(&db), mock_contract(local.clone()).await);
+    let (_, body) = verify(&app, &format!("/api/snapshots/5/verify?hash={}", stored)).await;
+    assert_eq!(body["matches_stored"], true);
+    assert_eq!(body["matches_on_chain"], false);
+    assert_eq!(body["canonical"], false);
+}
+
+#[sqlx::test]
+async fn test_non_sha256_hash_verifies_against_chain(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let generated = SnapshotService::new(Arc::clone(&db), None)
+        .with_hash_algorithm(HashAlgorithm::Keccak256)
+        .generate_and_submit_snapshot(5)
+        .await
+        .unwrap();
+    // The contract holds the SHA-256 of the canonical JSON
+    let on_chain = HashAlgorithm::Sha256.hex_digest(generated.canonical_json.as_bytes());
+    assert_ne!(generated.hash, on_chain);
+    let app = router(Arc::clone(&db), mock_contract(on_chain.clone()).await);
+
+    let (status, body) = verify(
+        &app,
+        &format!("/api/snapshots/5/verify?hash={}", generated.hash),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["stored_hash"], generated.hash);
+    assert_eq!(body["on_chain_hash"], on_chain);
+    assert_eq!(body["matches_stored"], true);
+    assert_eq!(body["matches_on_chain"], true);
+    assert_eq!(body["canonical"], true);
+}
+
+#[sqlx::test]
+async fn test_malformed_hash_is_rejected(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let app = router(Arc::clone(&db), mock_contract(String::new()).await);
+
+    let (status, body) = verify(&app, "/api/snapshots/5/verify?hash=not-a-hash").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .contains("64 hex characters"));
+}
+
+#[sqlx::test]
+async fn test_hash_length_follows_configured_algorithm(pool: SqlitePool) {
+    let db = Arc::new(Database::new(pool));
+    let contract_service = mock_contract(String::new()).await;
+    let app = routes(SnapshotAppState {
+        db: Arc::clone(&db),
+        contract_service: Some(Arc::clone(&contract_service)),
+        snapshot_service: Arc::new(
+            SnapshotService::new(db, Some(contract_service))
+                .with_hash_algorithm(HashAlgorithm::Sha512),
+        ),
+    });
+
+    let (status, body) = verify(
+        &app,
+        &format!("/api/snapshots/5/verify?hash={}", "ab".repeat(32)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .contains("128 hex characters"));
+
+    let (status, _) = verify(
+        &app,
+        &format!("/api/snapshots/5/verify?hash={}", "ab".repeat(64)),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
@@ -45,3 +45,63 @@ async fn test_trustlines_snapshots(pool: SqlitePool) {
     assert_eq!(history[0].asset_code, asset.asset_code);
     assert_eq!(history[0].total_trustlines, asset.total_trustlines);
 }
+
+#[sqlx::test]
+async fn test_trustline_concentration(pool: SqlitePool) {
+    let rpc_client = Arc::new(StellarRpcClient::new_with_defaults(true));
+    let analyzer = TrustlineAnalyzer::new(pool.clone(), rpc_client);
+
+    analyzer.sync_assets().await.unwrap();
+    assert_eq!(analyzer.sync_concentration().await.unwrap(), 4);
+    analyzer.take_snapshots().await.unwrap();
+
+    let rankings = analyzer.get_trustline_rankings(5).await.unwrap();
+    let by_code = |code: &str| rankings.iter().find(|s| s.asset_code == code).unwrap();
+
+    // Mock USDC holders all hold the same balance
+    let usdc = by_code("USDC");
+    assert_eq!(usdc.concentration_holders, Some(50));
+    assert_eq!(usdc.concentration_sampled, Some(false));
+    assert!(usdc.gini_coefficient.unwrap() < 0.01);
+    assert!((usdc.top10_holder_share.unwrap() - 0.2).abs() < 1e-9);
+
+    // Mock AQUA is held almost entirely by one whale
+    let aqua = by_code("AQUA");
+    assert!(aqua.gini_coefficient.unwrap() > 0.95);
+    assert!(aqua.top10_holder_share.unwrap() > 0.99);
+
+    // Mock yXLM has too few holders to say anything
+    let yxlm = by_code("yXLM");
+    assert_eq!(yxlm.gini_coefficient, None);
+    assert_eq!(yxlm.top10_holder_share, None);
+    assert_eq!(
+        yxlm.concentration_note.as_deref(),
+        Some("too few holders (5 < 20)")
+    );
+
+    let history = analyzer
+        .get_asset_history(&aqua.asset_code, &aqua.asset_issuer, 10)
+        .await
+        .unwrap();
+    assert_eq!(history[0].gini_coefficient, aqua.gini_coefficient);
+    assert_eq!(history[0].concentration_holders, Some(50));
+}
+
+#[tokio::test]
+async fn test_asset_holders_past_the_record_limit_are_a_sample() {
+    let rpc_client = StellarRpcClient::new_with_defaults(true);
+
+    let holders = rpc_client
+        .fetch_all_asset_holders("USDC", "GISSUER", Some(10))
+        .await
+        .unwrap();
+    assert_eq!(holders.items.len(), 10);
+    assert!(holders.capped);
+
+    let holders = rpc_client
+        .fetch_all_asset_holders("USDC", "GISSUER", None)
+        .await
+        .unwrap();
+    assert_eq!(holders.items.len(), 50);
+    assert!(!holders.capped);
+}
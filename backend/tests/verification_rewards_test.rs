@@ -1,10 +1,23 @@
 use anyhow::Result;
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    routing::post,
+    Extension, Router,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use ed25519_dalek::{Signer, SigningKey};
 use sqlx::SqlitePool;
 use std::sync::Arc;
+use stellar_insights_backend::api::verification_rewards::verify_snapshot;
+use stellar_insights_backend::auth::sep10_middleware::Sep10User;
 use stellar_insights_backend::database::Database;
 use stellar_insights_backend::services::verification_rewards::{
+    claim_message, BatchClaimEntry, BatchClaimRequest, VerificationRejection,
     VerificationRewardsService, VerifySnapshotRequest,
 };
+use stellar_xdr::curr::{PublicKey, Uint256};
+use tower::util::ServiceExt;
 use uuid::Uuid;
 
 async fn setup_test_db() -> Result<SqlitePool> {
@@ -60,6 +73,7 @@ async fn test_successful_verification() -> Result<()> {
     let request = VerifySnapshotRequest {
         snapshot_id: snapshot_id.clone(),
         submitted_hash: hash.to_string(),
+        account: None,
     };
 
     let response = service.verify_and_reward(user_id, request).await?;
@@ -88,6 +102,7 @@ async fn test_failed_verification() -> Result<()> {
     let request = VerifySnapshotRequest {
         snapshot_id: snapshot_id.clone(),
         submitted_hash: wrong_hash.to_string(),
+        account: None,
     };
 
     let response = service.verify_and_reward(user_id, request).await?;
@@ -116,6 +131,7 @@ async fn test_user_stats() -> Result<()> {
     let request = VerifySnapshotRequest {
         snapshot_id: snapshot_id.clone(),
         submitted_hash: hash.to_string(),
+        account: None,
     };
     service.verify_and_reward(user_id, request).await?;
 
@@ -149,6 +165,7 @@ async fn test_leaderboard() -> Result<()> {
         let request = VerifySnapshotRequest {
             snapshot_id: snapshot_id.clone(),
             submitted_hash: hash.clone(),
+            account: None,
         };
         service.verify_and_reward(&user_id, request).await?;
     }
@@ -179,6 +196,7 @@ async fn test_daily_limit() -> Result<()> {
         let request = VerifySnapshotRequest {
             snapshot_id: snapshot_id.clone(),
             submitted_hash: hash.clone(),
+            account: None,
         };
 
         let result = service.verify_and_reward(user_id, request).await;
@@ -215,6 +233,7 @@ async fn test_verification_history() -> Result<()> {
         let request = VerifySnapshotRequest {
             snapshot_id: snapshot_id.clone(),
             submitted_hash: hash.clone(),
+            account: None,
         };
         service.verify_and_reward(user_id, request).await?;
     }
@@ -227,3 +246,359 @@ async fn test_verification_history() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_rejection_hash_mismatch() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let db = Arc::new(Database::new(pool.clone()));
+    let service = VerificationRewardsService::new(db);
+
+    let user_id = "test-user-mismatch";
+    let snapshot_id = Uuid::new_v4().to_string();
+    create_test_user(&pool, user_id).await?;
+    create_test_snapshot(&pool, &snapshot_id, "expected_hash", 1).await?;
+
+    let request = VerifySnapshotRequest {
+        snapshot_id,
+        submitted_hash: "other_hash".to_string(),
+        account: None,
+    };
+    let response = service.verify_and_reward(user_id, request).await?;
+
+    assert_eq!(
+        response.rejection,
+        Some(VerificationRejection::HashMismatch)
+    );
+    assert!(response.verification_id.is_some());
+    assert_eq!(response.reward_points, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rejection_epoch_not_found() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let db = Arc::new(Database::new(pool.clone()));
+    let service = VerificationRewardsService::new(db);
+
+    let user_id = "test-user-missing";
+    create_test_user(&pool, user_id).await?;
+
+    let request = VerifySnapshotRequest {
+        snapshot_id: Uuid::new_v4().to_string(),
+        submitted_hash: "any_hash".to_string(),
+        account: None,
+    };
+    let response = service.verify_and_reward(user_id, request).await?;
+
+    assert_eq!(
+        response.rejection,
+        Some(VerificationRejection::EpochNotFound)
+    );
+    assert!(response.verification_id.is_none());
+    assert!(!response.is_match);
+
+    let history = service.get_user_verifications(user_id, 10).await?;
+    assert!(history.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rejection_already_rewarded() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let db = Arc::new(Database::new(pool.clone()));
+    let service = VerificationRewardsService::new(db);
+
+    let user_id = "test-user-repeat";
+    let snapshot_id = Uuid::new_v4().to_string();
+    let hash = "repeat_hash";
+    create_test_user(&pool, user_id).await?;
+    create_test_snapshot(&pool, &snapshot_id, hash, 1).await?;
+
+    let first = service
+        .verify_and_reward(
+            user_id,
+            VerifySnapshotRequest {
+                snapshot_id: snapshot_id.clone(),
+                submitted_hash: hash.to_string(),
+                account: None,
+            },
+        )
+        .await?;
+    assert!(first.rejection.is_none());
+
+    let second = service
+        .verify_and_reward(
+            user_id,
+            VerifySnapshotRequest {
+                snapshot_id,
+                submitted_hash: hash.to_string(),
+                account: None,
+            },
+        )
+        .await?;
+
+    assert_eq!(
+        second.rejection,
+        Some(VerificationRejection::AlreadyRewarded)
+    );
+    assert_eq!(second.reward_points, 0);
+    assert_eq!(second.total_points, first.total_points);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rejection_signature_invalid() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let db = Arc::new(Database::new(pool.clone()));
+    let service = VerificationRewardsService::new(db);
+
+    let user_id = "test-user-signer";
+    let snapshot_id = Uuid::new_v4().to_string();
+    let hash = "signed_hash";
+    create_test_user(&pool, user_id).await?;
+    create_test_snapshot(&pool, &snapshot_id, hash, 1).await?;
+
+    let request = VerifySnapshotRequest {
+        snapshot_id,
+        submitted_hash: hash.to_string(),
+        account: Some("someone-else".to_string()),
+    };
+    let response = service.verify_and_reward(user_id, request).await?;
+
+    assert_eq!(
+        response.rejection,
+        Some(VerificationRejection::SignatureInvalid)
+    );
+    assert_eq!(response.reward_points, 0);
+
+    Ok(())
+}
+
+const SESSION_ACCOUNT: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+fn verify_request(snapshot_id: &str, hash: &str) -> Request<Body> {
+    let body = serde_json::json!({
+        "snapshot_id": snapshot_id,
+        "submitted_hash": hash,
+    });
+    Request::builder()
+        .method("POST")
+        .uri("/verify")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_verify_with_sep10_session_succeeds() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let db = Arc::new(Database::new(pool.clone()));
+    let service = Arc::new(VerificationRewardsService::new(db));
+
+    let snapshot_id = Uuid::new_v4().to_string();
+    create_test_user(&pool, SESSION_ACCOUNT).await?;
+    create_test_snapshot(&pool, &snapshot_id, "session_hash", 1).await?;
+
+    // Mock the extension the SEP-10 middleware attaches for a valid session
+    let app = Router::new()
+        .route("/verify", post(verify_snapshot))
+        .layer(Extension(Sep10User {
+            account: SESSION_ACCOUNT.to_string(),
+            client_domain: None,
+        }))
+        .with_state(service);
+
+    let response = app
+        .oneshot(verify_request(&snapshot_id, "session_hash"))
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await?;
+    let json: serde_json::Value = serde_json::from_slice(&body)?;
+    assert_eq!(json["is_match"], true);
+    assert!(json["reward_points"].as_i64().unwrap_or(0) >= 10);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_verify_without_sep10_session_is_rejected() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let db = Arc::new(Database::new(pool.clone()));
+    let service = Arc::new(VerificationRewardsService::new(db));
+
+    let snapshot_id = Uuid::new_v4().to_string();
+    create_test_user(&pool, SESSION_ACCOUNT).await?;
+    create_test_snapshot(&pool, &snapshot_id, "session_hash", 1).await?;
+
+    let app = Router::new()
+        .route("/verify", post(verify_snapshot))
+        .with_state(service);
+
+    let response = app
+        .oneshot(verify_request(&snapshot_id, "session_hash"))
+        .await?;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let rewarded: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM snapshot_verifications")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(rewarded, 0);
+
+    Ok(())
+}
+
+/// Signing key and `G...` account of a claimant
+fn claimant(seed: u8) -> (SigningKey, String) {
+    let key = SigningKey::from_bytes(&[seed; 32]);
+    let account =
+        PublicKey::PublicKeyTypeEd25519(Uint256(key.verifying_key().to_bytes())).to_string();
+    (key, account)
+}
+
+fn claim_entry(key: &SigningKey, epoch: i64, hash: &str) -> BatchClaimEntry {
+    BatchClaimEntry {
+        epoch,
+        hash: hash.to_string(),
+        signature: BASE64.encode(key.sign(&claim_message(epoch, hash)).to_bytes()),
+    }
+}
+
+#[tokio::test]
+async fn test_batch_claim_with_valid_and_invalid_entries() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let db = Arc::new(Database::new(pool.clone()));
+    let service = VerificationRewardsService::new(db);
+
+    let (key, user_id) = claimant(1);
+    let (other_key, _) = claimant(2);
+    create_test_user(&pool, &user_id).await?;
+    for epoch in 1..=3 {
+        let hash = format!("batch_hash_{}", epoch);
+        create_test_snapshot(&pool, &Uuid::new_v4().to_string(), &hash, epoch).await?;
+    }
+
+    let request = BatchClaimRequest {
+        entries: vec![
+            claim_entry(&key, 1, "batch_hash_1"),
+            claim_entry(&key, 2, "wrong_hash"),
+            claim_entry(&other_key, 3, "batch_hash_3"),
+            claim_entry(&key, 9, "batch_hash_9"),
+            claim_entry(&key, 1, "batch_hash_1"),
+            claim_entry(&key, 3, "batch_hash_3"),
+        ],
+    };
+    let response = service.claim_batch(&user_id, request).await?;
+
+    let outcomes: Vec<(i64, Option<VerificationRejection>)> = response
+        .results
+        .iter()
+        .map(|r| (r.epoch, r.outcome.rejection))
+        .collect();
+    assert_eq!(
+        outcomes,
+        [
+            (1, None),
+            (2, Some(VerificationRejection::HashMismatch)),
+            (3, Some(VerificationRejection::SignatureInvalid)),
+            (9, Some(VerificationRejection::EpochNotFound)),
+            (1, Some(VerificationRejection::DuplicateEntry)),
+            (3, None),
+        ]
+    );
+    assert!(response.results[0].outcome.verification_id.is_some());
+    assert!(response.results[2].outcome.verification_id.is_none());
+
+    let rewarded =
+        response.results[0].outcome.reward_points + response.results[5].outcome.reward_points;
+    assert!(rewarded >= 20);
+    assert_eq!(response.reward_points, rewarded);
+    assert_eq!(response.total_points, rewarded);
+
+    // Two rewarded entries and one failed attempt were recorded
+    let stats = service.get_user_stats(&user_id).await?;
+    assert_eq!(stats.successful_verifications, 2);
+    assert_eq!(stats.failed_verifications, 1);
+    assert_eq!(service.get_user_verifications(&user_id, 10).await?.len(), 3);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_claim_is_idempotent() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let db = Arc::new(Database::new(pool.clone()));
+    let service = VerificationRewardsService::new(db);
+
+    let (key, user_id) = claimant(3);
+    create_test_user(&pool, &user_id).await?;
+    create_test_snapshot(&pool, &Uuid::new_v4().to_string(), "repeat_1", 1).await?;
+    create_test_snapshot(&pool, &Uuid::new_v4().to_string(), "repeat_2", 2).await?;
+
+    let request = || BatchClaimRequest {
+        entries: vec![
+            claim_entry(&key, 1, "repeat_1"),
+            claim_entry(&key, 2, "repeat_2"),
+        ],
+    };
+    let first = service.claim_batch(&user_id, request()).await?;
+    assert!(first.results.iter().all(|r| r.outcome.rejection.is_none()));
+
+    let second = service.claim_batch(&user_id, request()).await?;
+    assert!(second
+        .results
+        .iter()
+        .all(|r| r.outcome.rejection == Some(VerificationRejection::AlreadyRewarded)));
+    assert_eq!(second.reward_points, 0);
+    assert_eq!(second.total_points, first.total_points);
+
+    let empty = service
+        .claim_batch(&user_id, BatchClaimRequest { entries: vec![] })
+        .await;
+    assert!(empty.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_batch_claim_rejects_unsigned_or_altered_entries() -> Result<()> {
+    let pool = setup_test_db().await?;
+    let db = Arc::new(Database::new(pool.clone()));
+    let service = VerificationRewardsService::new(db);
+
+    let (key, user_id) = claimant(4);
+    let (other_key, _) = claimant(5);
+    create_test_user(&pool, &user_id).await?;
+    create_test_snapshot(&pool, &Uuid::new_v4().to_string(), "signed_1", 1).await?;
+
+    // Knowing the account, epoch and hash is not enough to claim
+    let mut unsigned = claim_entry(&key, 1, "signed_1");
+    unsigned.signature = BASE64.encode([0u8; 64]);
+    // A signature over another hash does not cover this one
+    let mut altered = claim_entry(&key, 1, "other_hash");
+    altered.hash = "signed_1".to_string();
+    let request = BatchClaimRequest {
+        entries: vec![
+            claim_entry(&other_key, 1, "signed_1"),
+            unsigned,
+            altered,
+            BatchClaimEntry {
+                signature: "not base64!".to_string(),
+                ..claim_entry(&key, 1, "signed_1")
+            },
+        ],
+    };
+    let response = service.claim_batch(&user_id, request).await?;
+
+    assert!(response
+        .results
+        .iter()
+        .all(|r| r.outcome.rejection == Some(VerificationRejection::SignatureInvalid)));
+    assert_eq!(response.reward_points, 0);
+    assert_eq!(service.get_user_verifications(&user_id, 10).await?.len(), 0);
+
+    Ok(())
+}
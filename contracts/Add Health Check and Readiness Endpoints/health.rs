@@ -1,7 +1,12 @@
 use axum::{extract::State, http::StatusCode, response::Json};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc, time::Instant};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 /// Shared application state containing dependency clients.
 /// Adjust field types to match your actual application state.
@@ -9,29 +14,95 @@ pub struct AppState {
     pub db_pool: sqlx::PgPool,
     pub redis_client: redis::Client,
     pub rpc_url: String,
+    /// Price feed endpoint. Optional: when it fails the service is degraded
+    /// but keeps serving.
+    pub price_feed_url: Option<String>,
     pub version: String,
+    pub check_timeouts: CheckTimeouts,
+}
+
+/// Upper bound on each dependency check, so a hung dependency cannot hang the
+/// readiness probe. Read from `HEALTH_CHECK_<DEPENDENCY>_TIMEOUT_MS`.
+#[derive(Debug, Clone, Copy)]
+pub struct CheckTimeouts {
+    pub database: Duration,
+    pub redis: Duration,
+    pub rpc: Duration,
+    pub price_feed: Duration,
+}
+
+impl Default for CheckTimeouts {
+    fn default() -> Self {
+        Self {
+            database: Duration::from_secs(2),
+            redis: Duration::from_secs(2),
+            rpc: Duration::from_secs(5),
+            price_feed: Duration::from_secs(3),
+        }
+    }
+}
+
+impl CheckTimeouts {
+    /// Timeouts from the environment; fails on a value that is not a positive
+    /// number of milliseconds.
+    pub fn from_env() -> anyhow::Result<Self> {
+        let defaults = Self::default();
+        Ok(Self {
+            database: timeout_from_env("HEALTH_CHECK_DATABASE_TIMEOUT_MS", defaults.database)?,
+            redis: timeout_from_env("HEALTH_CHECK_REDIS_TIMEOUT_MS", defaults.redis)?,
+            rpc: timeout_from_env("HEALTH_CHECK_RPC_TIMEOUT_MS", defaults.rpc)?,
+            price_feed: timeout_from_env(
+                "HEALTH_CHECK_PRICE_FEED_TIMEOUT_MS",
+                defaults.price_feed,
+            )?,
+        })
+    }
+}
+
+fn timeout_from_env(var: &str, default: Duration) -> anyhow::Result<Duration> {
+    match std::env::var(var) {
+        Ok(raw) => match raw.trim().parse::<u64>() {
+            Ok(ms) if ms > 0 => Ok(Duration::from_millis(ms)),
+            _ => anyhow::bail!("Invalid {var} '{raw}': expected a positive number of milliseconds"),
+        },
+        Err(_) => Ok(default),
+    }
 }
 
 // ── Response types ────────────────────────────────────────────────────────────
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ServiceStatus {
     Healthy,
+    /// Serving, but an optional dependency is failing
     Degraded,
+    /// A critical dependency is failing
     Unhealthy,
 }
 
+impl ServiceStatus {
+    /// Only an unhealthy service is taken out of rotation
+    pub fn http_status(self) -> StatusCode {
+        match self {
+            Self::Healthy | Self::Degraded => StatusCode::OK,
+            Self::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CheckResult {
     pub status: CheckStatus,
+    /// Whether the service cannot serve without this dependency
+    pub critical: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub latency_ms: Option<u128>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum CheckStatus {
     Up,
@@ -72,104 +143,111 @@ pub async fn health_check(
 
 /// GET /ready
 ///
-/// Readiness probe — confirms all downstream dependencies (database, Redis, RPC)
-/// are reachable.  Returns 200 only when every check passes; 503 otherwise.
+/// Readiness probe — checks the downstream dependencies concurrently, each
+/// under its own timeout.  The database, Redis and RPC are critical: if any
+/// fails the service is unhealthy and 503 is returned.  The price feed is
+/// optional: its failure reports the service as degraded with a 200.
 pub async fn readiness_check(
     State(state): State<Arc<AppState>>,
 ) -> (StatusCode, Json<ReadinessResponse>) {
-    let mut checks: HashMap<String, CheckResult> = HashMap::new();
-    let mut all_up = true;
-
-    // ── Database ──────────────────────────────────────────────────────────────
-    let db_result = check_database(&state.db_pool).await;
-    if matches!(db_result.status, CheckStatus::Down) {
-        all_up = false;
-    }
-    checks.insert("database".to_string(), db_result);
-
-    // ── Redis ─────────────────────────────────────────────────────────────────
-    let redis_result = check_redis(&state.redis_client).await;
-    if matches!(redis_result.status, CheckStatus::Down) {
-        all_up = false;
-    }
-    checks.insert("redis".to_string(), redis_result);
+    let timeouts = state.check_timeouts;
+    let (database, redis, rpc, price_feed) = tokio::join!(
+        run_check(true, timeouts.database, check_database(&state.db_pool)),
+        run_check(true, timeouts.redis, check_redis(&state.redis_client)),
+        run_check(true, timeouts.rpc, check_rpc(&state.rpc_url)),
+        async {
+            match &state.price_feed_url {
+                Some(url) => {
+                    Some(run_check(false, timeouts.price_feed, check_price_feed(url)).await)
+                }
+                None => None,
+            }
+        },
+    );
 
-    // ── RPC endpoint ──────────────────────────────────────────────────────────
-    let rpc_result = check_rpc(&state.rpc_url).await;
-    if matches!(rpc_result.status, CheckStatus::Down) {
-        all_up = false;
+    let mut checks: HashMap<String, CheckResult> = HashMap::new();
+    checks.insert("database".to_string(), database);
+    checks.insert("redis".to_string(), redis);
+    checks.insert("rpc".to_string(), rpc);
+    if let Some(price_feed) = price_feed {
+        checks.insert("price_feed".to_string(), price_feed);
     }
-    checks.insert("rpc".to_string(), rpc_result);
-
-    let (http_status, service_status) = if all_up {
-        (StatusCode::OK, ServiceStatus::Healthy)
-    } else {
-        (StatusCode::SERVICE_UNAVAILABLE, ServiceStatus::Unhealthy)
-    };
 
+    let status = overall_status(&checks);
     let response = ReadinessResponse {
-        status: service_status,
+        status,
         timestamp: Utc::now().to_rfc3339(),
         version: state.version.clone(),
         checks,
     };
 
-    (http_status, Json(response))
+    (status.http_status(), Json(response))
+}
+
+/// Unhealthy if a critical check is down, degraded if only optional ones are
+pub fn overall_status(checks: &HashMap<String, CheckResult>) -> ServiceStatus {
+    let mut status = ServiceStatus::Healthy;
+    for check in checks.values() {
+        if check.status == CheckStatus::Up {
+            continue;
+        }
+        if check.critical {
+            return ServiceStatus::Unhealthy;
+        }
+        status = ServiceStatus::Degraded;
+    }
+    status
 }
 
 // ── Dependency checks ─────────────────────────────────────────────────────────
 
-async fn check_database(pool: &sqlx::PgPool) -> CheckResult {
+/// Run one dependency check, reporting it down if it does not finish in
+/// `timeout`
+pub async fn run_check<F>(critical: bool, timeout: Duration, check: F) -> CheckResult
+where
+    F: Future<Output = Result<(), String>>,
+{
     let start = Instant::now();
-    match sqlx::query("SELECT 1").execute(pool).await {
-        Ok(_) => CheckResult {
-            status: CheckStatus::Up,
-            latency_ms: Some(start.elapsed().as_millis()),
-            error: None,
-        },
-        Err(e) => CheckResult {
-            status: CheckStatus::Down,
-            latency_ms: Some(start.elapsed().as_millis()),
-            error: Some(e.to_string()),
+    let error = match tokio::time::timeout(timeout, check).await {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e),
+        Err(_) => Some(format!("timed out after {}ms", timeout.as_millis())),
+    };
+    CheckResult {
+        status: if error.is_none() {
+            CheckStatus::Up
+        } else {
+            CheckStatus::Down
         },
+        critical,
+        latency_ms: Some(start.elapsed().as_millis()),
+        error,
     }
 }
 
-async fn check_redis(client: &redis::Client) -> CheckResult {
-    let start = Instant::now();
-    match client.get_async_connection().await {
-        Ok(mut conn) => {
-            use redis::AsyncCommands;
-            match redis::cmd("PING").query_async::<_, String>(&mut conn).await {
-                Ok(_) => CheckResult {
-                    status: CheckStatus::Up,
-                    latency_ms: Some(start.elapsed().as_millis()),
-                    error: None,
-                },
-                Err(e) => CheckResult {
-                    status: CheckStatus::Down,
-                    latency_ms: Some(start.elapsed().as_millis()),
-                    error: Some(e.to_string()),
-                },
-            }
-        }
-        Err(e) => CheckResult {
-            status: CheckStatus::Down,
-            latency_ms: Some(start.elapsed().as_millis()),
-            error: Some(e.to_string()),
-        },
-    }
+async fn check_database(pool: &sqlx::PgPool) -> Result<(), String> {
+    sqlx::query("SELECT 1")
+        .execute(pool)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
 }
 
-async fn check_rpc(rpc_url: &str) -> CheckResult {
-    let start = Instant::now();
+async fn check_redis(client: &redis::Client) -> Result<(), String> {
+    let mut conn = client
+        .get_async_connection()
+        .await
+        .map_err(|e| e.to_string())?;
+    redis::cmd("PING")
+        .query_async::<_, String>(&mut conn)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+async fn check_rpc(rpc_url: &str) -> Result<(), String> {
     // A lightweight JSON-RPC call (`net_version`) is used to probe the node
     // without causing any state changes.
-    let client = reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(5))
-        .build()
-        .unwrap_or_default();
-
     let payload = serde_json::json!({
         "jsonrpc": "2.0",
         "method": "net_version",
@@ -177,22 +255,25 @@ async fn check_rpc(rpc_url: &str) -> CheckResult {
         "id": 1
     });
 
-    match client.post(rpc_url).json(&payload).send().await {
-        Ok(resp) if resp.status().is_success() => CheckResult {
-            status: CheckStatus::Up,
-            latency_ms: Some(start.elapsed().as_millis()),
-            error: None,
-        },
-        Ok(resp) => CheckResult {
-            status: CheckStatus::Down,
-            latency_ms: Some(start.elapsed().as_millis()),
-            error: Some(format!("unexpected status: {}", resp.status())),
-        },
-        Err(e) => CheckResult {
-            status: CheckStatus::Down,
-            latency_ms: Some(start.elapsed().as_millis()),
-            error: Some(e.to_string()),
-        },
+    let resp = reqwest::Client::new()
+        .post(rpc_url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if resp.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("unexpected status: {}", resp.status()))
+    }
+}
+
+async fn check_price_feed(url: &str) -> Result<(), String> {
+    let resp = reqwest::get(url).await.map_err(|e| e.to_string())?;
+    if resp.status().is_success() {
+        Ok(())
+    } else {
+        Err(format!("unexpected status: {}", resp.status()))
     }
 }
 
@@ -224,7 +305,9 @@ mod tests {
             db_pool: create_test_pool().await,
             redis_client: create_test_redis(),
             rpc_url: std::env::var("RPC_URL").unwrap_or_else(|_| "http://localhost:8545".into()),
+            price_feed_url: None,
             version: "0.1.0-test".into(),
+            check_timeouts: CheckTimeouts::default(),
         });
 
         let app = test_router(state);
@@ -248,7 +331,9 @@ mod tests {
             db_pool: create_test_pool().await,
             redis_client: create_test_redis(),
             rpc_url: std::env::var("RPC_URL").unwrap_or_else(|_| "http://localhost:8545".into()),
+            price_feed_url: None,
             version: "0.1.0-test".into(),
+            check_timeouts: CheckTimeouts::default(),
         });
 
         let app = test_router(state);
@@ -268,6 +353,78 @@ mod tests {
         assert!(json.checks.contains_key("rpc"));
     }
 
+    fn checks(results: Vec<(&str, CheckResult)>) -> HashMap<String, CheckResult> {
+        results
+            .into_iter()
+            .map(|(name, result)| (name.to_string(), result))
+            .collect()
+    }
+
+    async fn up(critical: bool) -> CheckResult {
+        run_check(critical, Duration::from_secs(1), async { Ok(()) }).await
+    }
+
+    async fn down(critical: bool) -> CheckResult {
+        run_check(critical, Duration::from_secs(1), async {
+            Err("connection refused".to_string())
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn all_dependencies_up_is_healthy() {
+        let checks = checks(vec![
+            ("database", up(true).await),
+            ("rpc", up(true).await),
+            ("price_feed", up(false).await),
+        ]);
+
+        let status = overall_status(&checks);
+        assert_eq!(status, ServiceStatus::Healthy);
+        assert_eq!(status.http_status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn failing_optional_dependency_is_degraded_but_serving() {
+        let checks = checks(vec![
+            ("database", up(true).await),
+            ("rpc", up(true).await),
+            ("price_feed", down(false).await),
+        ]);
+
+        let status = overall_status(&checks);
+        assert_eq!(status, ServiceStatus::Degraded);
+        assert_eq!(status.http_status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn failing_critical_dependency_is_unhealthy() {
+        let checks = checks(vec![
+            ("database", down(true).await),
+            ("rpc", up(true).await),
+            ("price_feed", down(false).await),
+        ]);
+
+        let status = overall_status(&checks);
+        assert_eq!(status, ServiceStatus::Unhealthy);
+        assert_eq!(status.http_status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn hung_check_times_out() {
+        let start = Instant::now();
+        let result = run_check(
+            true,
+            Duration::from_millis(50),
+            std::future::pending::<Result<(), String>>(),
+        )
+        .await;
+
+        assert_eq!(result.status, CheckStatus::Down);
+        assert_eq!(result.error.as_deref(), Some("timed out after 50ms"));
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
     // ── Helpers ───────────────────────────────────────────────────────────────
 
     async fn create_test_pool() -> sqlx::PgPool {
@@ -5,7 +5,7 @@ use std::sync::Arc;
 use axum::{routing::get, Router};
 use tokio::net::TcpListener;
 
-use crate::api::health::{health_check, readiness_check, AppState};
+use crate::api::health::{health_check, readiness_check, AppState, CheckTimeouts};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -13,6 +13,7 @@ async fn main() -> anyhow::Result<()> {
     let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
     let redis_url = std::env::var("REDIS_URL").expect("REDIS_URL must be set");
     let rpc_url = std::env::var("RPC_URL").expect("RPC_URL must be set");
+    let price_feed_url = std::env::var("PRICE_FEED_URL").ok();
 
     let db_pool = sqlx::PgPool::connect(&database_url).await?;
     let redis_client = redis::Client::open(redis_url)?;
@@ -21,7 +22,9 @@ async fn main() -> anyhow::Result<()> {
         db_pool,
         redis_client,
         rpc_url,
+        price_feed_url,
         version: env!("CARGO_PKG_VERSION").to_string(),
+        check_timeouts: CheckTimeouts::from_env()?,
     });
 
     // ── Router ────────────────────────────────────────────────────────────────
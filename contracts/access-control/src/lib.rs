@@ -148,7 +148,7 @@ impl AccessControl {
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Env};
+    use soroban_sdk::{symbol_short, testutils::Address as _, Env};
 
     #[test]
     fn test_acl() {
@@ -0,0 +1,24 @@
+use soroban_sdk::contracterror;
+
+/// Contract-specific errors for the Analytics Contract
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// `initialize` has not been called yet
+    NotInitialized = 1,
+    /// `get_snapshots_in_range` was given a start epoch after its end epoch
+    InvalidEpochRange = 2,
+    /// `get_snapshots_in_range` was asked for more than `MAX_SNAPSHOT_RANGE`
+    /// epochs
+    EpochRangeTooLarge = 3,
+    /// The ledger timestamp is earlier than the latest snapshot's
+    TimestampNotMonotonic = 4,
+    /// The caller is not allowed to make this call
+    Unauthorized = 5,
+    /// `initialize_with_max_epoch_gap` was given a gap of 0
+    InvalidMaxEpochGap = 6,
+    /// A submitted epoch jumps past the latest one by more than the
+    /// configured max epoch gap
+    EpochGapExceeded = 7,
+}
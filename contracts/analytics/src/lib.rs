@@ -1,5 +1,11 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Map};
+
+mod errors;
+
+pub use errors::Error;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, panic_with_error, Address, BytesN, Env, Map,
+};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -22,11 +28,48 @@ pub enum DataKey {
     Paused,
     /// Governance contract address (only it can call set_admin_by_governance / set_paused_by_governance)
     Governance,
+    /// Largest allowed jump between consecutive epochs (unlimited when unset)
+    MaxEpochGap,
 }
 
+/// Largest epoch range `get_snapshots_in_range` will scan in one call
+pub const MAX_SNAPSHOT_RANGE: u64 = 100;
+
+/// Ledgers closed per day at ~5s per ledger
+const DAY_IN_LEDGERS: u32 = 17_280;
+
+/// The snapshot history and contract instance TTLs are extended once no more
+/// than this many ledgers remain
+pub const TTL_EXTEND_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+
+/// Ledgers the snapshot history and contract instance are kept live for
+/// when extended
+pub const TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
+
 #[contract]
 pub struct AnalyticsContract;
 
+/// Panic with `Error::NotInitialized` unless `initialize` has been called.
+///
+/// Keyed on the admin, which every `initialize` has set, so contracts
+/// initialized by earlier versions keep accepting writes after an upgrade.
+fn require_initialized(env: &Env) {
+    if !env.storage().instance().has(&DataKey::Admin) {
+        panic_with_error!(env, Error::NotInitialized);
+    }
+}
+
+/// Keep the snapshot history and contract instance from being archived
+fn extend_storage_ttl(env: &Env) {
+    let persistent = env.storage().persistent();
+    if persistent.has(&DataKey::Snapshots) {
+        persistent.extend_ttl(&DataKey::Snapshots, TTL_EXTEND_THRESHOLD, TTL_EXTEND_TO);
+    }
+    env.storage()
+        .instance()
+        .extend_ttl(TTL_EXTEND_THRESHOLD, TTL_EXTEND_TO);
+}
+
 #[contractimpl]
 impl AnalyticsContract {
     /// Initialize contract storage with an authorized admin address
@@ -59,6 +102,31 @@ impl AnalyticsContract {
         let persistent_storage = env.storage().persistent();
         let empty_snapshots = Map::<u64, SnapshotMetadata>::new(&env);
         persistent_storage.set(&DataKey::Snapshots, &empty_snapshots);
+
+        extend_storage_ttl(&env);
+    }
+
+    /// Initialize like `initialize`, additionally limiting how far each
+    /// submitted epoch may jump past the latest one, so skipped epochs are
+    /// caught at submission
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `admin` - Address authorized to submit snapshots
+    /// * `max_epoch_gap` - Largest allowed `epoch - latest` for a submission
+    ///
+    /// # Panics
+    /// * If contract is already initialized (admin already set)
+    /// * With `Error::InvalidMaxEpochGap` if `max_epoch_gap` is 0
+    pub fn initialize_with_max_epoch_gap(env: Env, admin: Address, max_epoch_gap: u64) {
+        if max_epoch_gap == 0 {
+            panic_with_error!(&env, Error::InvalidMaxEpochGap);
+        }
+
+        Self::initialize(env.clone(), admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxEpochGap, &max_epoch_gap);
     }
 
     /// Submit a new snapshot for a specific epoch.
@@ -72,15 +140,22 @@ impl AnalyticsContract {
     /// * `caller` - Address attempting to submit (must be the authorized admin)
     ///
     /// # Panics
+    /// * With `Error::NotInitialized` if `initialize` has not been called
     /// * If contract is paused for emergency maintenance
-    /// * If admin is not set (contract not initialized)
     /// * If caller is not the authorized admin
     /// * If epoch is 0 (invalid)
     /// * If epoch <= latest (monotonicity violated: out-of-order or duplicate)
+    /// * With `Error::EpochGapExceeded` if a max epoch gap is configured and
+    ///   epoch jumps past latest by more than it (the first snapshot may
+    ///   start at any epoch)
+    /// * With `Error::TimestampNotMonotonic` if the ledger timestamp is
+    ///   earlier than the latest snapshot's timestamp
     ///
     /// # Returns
     /// * Ledger timestamp when snapshot was recorded
     pub fn submit_snapshot(env: Env, epoch: u64, hash: BytesN<32>, caller: Address) -> u64 {
+        require_initialized(&env);
+
         // Check if contract is paused
         let is_paused: bool = env
             .storage()
@@ -126,12 +201,17 @@ impl AnalyticsContract {
             }
         }
 
-        let timestamp = env.ledger().timestamp();
-        let metadata = SnapshotMetadata {
-            epoch,
-            timestamp,
-            hash,
-        };
+        if latest > 0 {
+            if let Some(max_gap) = env
+                .storage()
+                .instance()
+                .get::<_, u64>(&DataKey::MaxEpochGap)
+            {
+                if epoch - latest > max_gap {
+                    panic_with_error!(&env, Error::EpochGapExceeded);
+                }
+            }
+        }
 
         let mut snapshots: Map<u64, SnapshotMetadata> = env
             .storage()
@@ -139,12 +219,29 @@ impl AnalyticsContract {
             .get(&DataKey::Snapshots)
             .unwrap_or_else(|| Map::new(&env));
 
+        // Keep the time series coherent: a later epoch may not be recorded
+        // at an earlier ledger time than the latest stored snapshot
+        let timestamp = env.ledger().timestamp();
+        if let Some(previous) = snapshots.get(latest) {
+            if timestamp < previous.timestamp {
+                panic_with_error!(&env, Error::TimestampNotMonotonic);
+            }
+        }
+
+        let metadata = SnapshotMetadata {
+            epoch,
+            timestamp,
+            hash,
+        };
+
         snapshots.set(epoch, metadata);
         env.storage()
             .persistent()
             .set(&DataKey::Snapshots, &snapshots);
         env.storage().instance().set(&DataKey::LatestEpoch, &epoch);
 
+        extend_storage_ttl(&env);
+
         timestamp
     }
 
@@ -166,6 +263,31 @@ impl AnalyticsContract {
         snapshots.get(epoch)
     }
 
+    /// Get the snapshot for an epoch or, when that epoch has none, the
+    /// nearest earlier one, for "state as of epoch X" queries over sparse
+    /// epochs
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `epoch` - Epoch to retrieve
+    ///
+    /// # Returns
+    /// * Snapshot metadata for the highest stored epoch <= `epoch`, or None
+    ///   if there is no such snapshot
+    pub fn get_snapshot_or_nearest(env: Env, epoch: u64) -> Option<SnapshotMetadata> {
+        let snapshots = Self::get_snapshot_history(env);
+        if let Some(exact) = snapshots.get(epoch) {
+            return Some(exact);
+        }
+
+        let nearest = snapshots
+            .keys()
+            .iter()
+            .filter(|stored| *stored <= epoch)
+            .max()?;
+        snapshots.get(nearest)
+    }
+
     /// Get the latest snapshot metadata
     ///
     /// # Arguments
@@ -215,22 +337,68 @@ impl AnalyticsContract {
             .unwrap_or(0)
     }
 
+    /// Get the largest allowed jump between consecutive epochs
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    ///
+    /// # Returns
+    /// * The configured gap, or None when gaps are unlimited
+    pub fn get_max_epoch_gap(env: Env) -> Option<u64> {
+        env.storage().instance().get(&DataKey::MaxEpochGap)
+    }
+
     /// Get all epochs that have snapshots (for iteration purposes)
     ///
+    /// Epochs are always returned in ascending order, the key order of the
+    /// underlying snapshot map.
+    ///
     /// # Arguments
     /// * `env` - Contract environment
     ///
     /// # Returns
-    /// * Vector of all epochs with stored snapshots
+    /// * Vector of all epochs with stored snapshots, sorted ascending
     pub fn get_all_epochs(env: Env) -> soroban_sdk::Vec<u64> {
+        Self::get_snapshot_history(env).keys()
+    }
+
+    /// Get the snapshots whose epoch lies in `[start_epoch, end_epoch]`
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `start_epoch` - First epoch of the range (inclusive)
+    /// * `end_epoch` - Last epoch of the range (inclusive)
+    ///
+    /// # Panics
+    /// * With `Error::InvalidEpochRange` if `start_epoch` is greater than
+    ///   `end_epoch`
+    /// * With `Error::EpochRangeTooLarge` if the range spans more than
+    ///   `MAX_SNAPSHOT_RANGE` epochs
+    ///
+    /// # Returns
+    /// * Snapshot metadata for every stored epoch in the range, sorted ascending
+    pub fn get_snapshots_in_range(
+        env: Env,
+        start_epoch: u64,
+        end_epoch: u64,
+    ) -> soroban_sdk::Vec<SnapshotMetadata> {
+        if start_epoch > end_epoch {
+            panic_with_error!(&env, Error::InvalidEpochRange);
+        }
+        if end_epoch - start_epoch >= MAX_SNAPSHOT_RANGE {
+            panic_with_error!(&env, Error::EpochRangeTooLarge);
+        }
+
         let snapshots = Self::get_snapshot_history(env.clone());
-        let mut epochs = soroban_sdk::Vec::new(&env);
+        let mut in_range = soroban_sdk::Vec::new(&env);
 
-        for (epoch, _) in snapshots.iter() {
-            epochs.push_back(epoch);
+        for epoch in start_epoch..=end_epoch {
+            if let Some(metadata) = snapshots.get(epoch) {
+                in_range.push_back(metadata);
+            }
         }
 
-        epochs
+        in_range
     }
 
     /// Get the current authorized admin address
@@ -253,9 +421,11 @@ impl AnalyticsContract {
     /// * `new_admin` - New address to set as admin
     ///
     /// # Panics
-    /// * If contract is not initialized (admin not set)
+    /// * With `Error::NotInitialized` if `initialize` has not been called
     /// * If caller is not the current admin
     pub fn set_admin(env: Env, current_admin: Address, new_admin: Address) {
+        require_initialized(&env);
+
         // Require authentication from the current admin
         current_admin.require_auth();
 
@@ -284,9 +454,11 @@ impl AnalyticsContract {
     /// * `caller` - Address attempting to pause (must be admin)
     ///
     /// # Panics
-    /// * If contract is not initialized (admin not set)
+    /// * With `Error::NotInitialized` if `initialize` has not been called
     /// * If caller is not the admin
     pub fn pause(env: Env, caller: Address) {
+        require_initialized(&env);
+
         caller.require_auth();
 
         let admin: Address = env
@@ -311,9 +483,11 @@ impl AnalyticsContract {
     /// * `caller` - Address attempting to unpause (must be admin)
     ///
     /// # Panics
-    /// * If contract is not initialized (admin not set)
+    /// * With `Error::NotInitialized` if `initialize` has not been called
     /// * If caller is not the admin
     pub fn unpause(env: Env, caller: Address) {
+        require_initialized(&env);
+
         caller.require_auth();
 
         let admin: Address = env
@@ -332,6 +506,8 @@ impl AnalyticsContract {
     /// Set the governance contract address. Only the admin can set this.
     /// The governance contract can then update admin or pause state via voting.
     pub fn set_governance(env: Env, caller: Address, governance: Address) {
+        require_initialized(&env);
+
         caller.require_auth();
 
         let admin: Address = env
@@ -349,6 +525,30 @@ impl AnalyticsContract {
             .set(&DataKey::Governance, &governance);
     }
 
+    /// Extend the TTL of the snapshot history and contract instance ahead of
+    /// their expiry. Entries with more than `TTL_EXTEND_THRESHOLD` ledgers
+    /// left are not touched; the rest are extended to `TTL_EXTEND_TO`.
+    ///
+    /// # Arguments
+    /// * `env` - Contract environment
+    /// * `caller` - Address requesting the extension (must be admin)
+    ///
+    /// # Panics
+    /// * With `Error::NotInitialized` if `initialize` has not been called
+    /// * With `Error::Unauthorized` if caller is not the admin
+    pub fn bump_ttl(env: Env, caller: Address) {
+        require_initialized(&env);
+
+        caller.require_auth();
+
+        let admin: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if admin.as_ref() != Some(&caller) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        extend_storage_ttl(&env);
+    }
+
     /// Get the current governance contract address (if any).
     pub fn get_governance(env: Env) -> Option<Address> {
         env.storage().instance().get(&DataKey::Governance)
@@ -356,6 +556,8 @@ impl AnalyticsContract {
 
     /// Set the admin address. Only the governance contract may call this (after a passed proposal).
     pub fn set_admin_by_governance(env: Env, caller: Address, new_admin: Address) {
+        require_initialized(&env);
+
         let governance: Address = env
             .storage()
             .instance()
@@ -371,6 +573,8 @@ impl AnalyticsContract {
 
     /// Set the paused state. Only the governance contract may call this (after a passed proposal).
     pub fn set_paused_by_governance(env: Env, caller: Address, paused: bool) {
+        require_initialized(&env);
+
         let governance: Address = env
             .storage()
             .instance()
@@ -1,6 +1,9 @@
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
+    testutils::{
+        storage::{Instance as _, Persistent as _},
+        Address as _, Ledger,
+    },
     Address, BytesN, Env,
 };
 
@@ -40,6 +43,129 @@ fn test_initialize_cannot_reinitialize() {
     client.initialize(&admin);
 }
 
+#[test]
+fn test_submit_before_initialize_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let hash = create_test_hash(&env, 1);
+
+    let not_initialized: soroban_sdk::Error = Error::NotInitialized.into();
+    assert_eq!(
+        client.try_submit_snapshot(&1, &hash, &admin),
+        Err(Ok(not_initialized))
+    );
+    assert_eq!(client.try_pause(&admin), Err(Ok(not_initialized)));
+    assert_eq!(client.get_latest_epoch(), 0);
+
+    client.initialize(&admin);
+    client.submit_snapshot(&1, &hash, &admin);
+    assert_eq!(client.get_latest_epoch(), 1);
+}
+
+#[test]
+fn test_contract_initialized_before_upgrade_accepts_writes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    // Storage as left by an `initialize` that predates the initialization check
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.set(&DataKey::Admin, &admin);
+        storage.set(&DataKey::LatestEpoch, &0u64);
+        storage.set(&DataKey::Paused, &false);
+        env.storage().persistent().set(
+            &DataKey::Snapshots,
+            &Map::<u64, SnapshotMetadata>::new(&env),
+        );
+    });
+
+    client.submit_snapshot(&1, &create_test_hash(&env, 1), &admin);
+    assert_eq!(client.get_latest_epoch(), 1);
+    client.pause(&admin);
+    assert!(client.is_paused());
+
+    // Still cannot be re-initialized
+    assert!(client.try_initialize(&admin).is_err());
+}
+
+#[test]
+fn test_epoch_gaps_are_unlimited_by_default() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    assert_eq!(client.get_max_epoch_gap(), None);
+
+    client.submit_snapshot(&1, &create_test_hash(&env, 1), &admin);
+    client.submit_snapshot(&1000, &create_test_hash(&env, 2), &admin);
+    assert_eq!(client.get_latest_epoch(), 1000);
+}
+
+#[test]
+fn test_epoch_jump_within_max_gap_is_accepted() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize_with_max_epoch_gap(&admin, &5);
+    assert_eq!(client.get_max_epoch_gap(), Some(5));
+
+    // The first snapshot may start anywhere
+    client.submit_snapshot(&100, &create_test_hash(&env, 1), &admin);
+    client.submit_snapshot(&101, &create_test_hash(&env, 2), &admin);
+    client.submit_snapshot(&106, &create_test_hash(&env, 3), &admin);
+    assert_eq!(client.get_latest_epoch(), 106);
+}
+
+#[test]
+fn test_epoch_jump_over_max_gap_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize_with_max_epoch_gap(&admin, &5);
+    client.submit_snapshot(&1, &create_test_hash(&env, 1), &admin);
+
+    let gap_exceeded: soroban_sdk::Error = Error::EpochGapExceeded.into();
+    assert_eq!(
+        client.try_submit_snapshot(&7, &create_test_hash(&env, 2), &admin),
+        Err(Ok(gap_exceeded))
+    );
+    assert_eq!(client.get_latest_epoch(), 1);
+}
+
+#[test]
+fn test_zero_max_epoch_gap_is_rejected() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+
+    let invalid_gap: soroban_sdk::Error = Error::InvalidMaxEpochGap.into();
+    assert_eq!(
+        client.try_initialize_with_max_epoch_gap(&Address::generate(&env), &0),
+        Err(Ok(invalid_gap))
+    );
+    assert_eq!(client.get_admin(), None);
+}
+
 #[test]
 fn test_submit_single_snapshot() {
     let env = Env::default();
@@ -418,3 +544,286 @@ fn test_old_admin_cannot_submit_after_transfer() {
     let hash = create_test_hash(&env, 1);
     client.submit_snapshot(&epoch, &hash, &admin);
 }
+
+#[test]
+fn test_get_all_epochs_sorted_ascending() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    // Seed the history out of order, bypassing submit_snapshot's monotonicity check
+    env.as_contract(&contract_id, || {
+        let mut snapshots = Map::<u64, SnapshotMetadata>::new(&env);
+        for (epoch, value) in [(1u64, 1u8), (10, 10), (5, 5)] {
+            snapshots.set(
+                epoch,
+                SnapshotMetadata {
+                    epoch,
+                    timestamp: 0,
+                    hash: create_test_hash(&env, value),
+                },
+            );
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Snapshots, &snapshots);
+    });
+
+    assert_eq!(
+        client.get_all_epochs(),
+        soroban_sdk::vec![&env, 1u64, 5, 10]
+    );
+}
+
+/// Contract with snapshots at epochs 2, 4, 6, 8 and 10
+fn setup_even_epochs(env: &Env) -> AnalyticsContractClient<'_> {
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+
+    client.initialize(&admin);
+    for epoch in (2u64..=10).step_by(2) {
+        client.submit_snapshot(&epoch, &create_test_hash(env, epoch as u8), &admin);
+    }
+
+    client
+}
+
+fn epochs_of(env: &Env, snapshots: &soroban_sdk::Vec<SnapshotMetadata>) -> soroban_sdk::Vec<u64> {
+    let mut epochs = soroban_sdk::Vec::new(env);
+    for snapshot in snapshots.iter() {
+        epochs.push_back(snapshot.epoch);
+    }
+    epochs
+}
+
+#[test]
+fn test_get_snapshots_in_range_boundaries() {
+    let env = Env::default();
+    let client = setup_even_epochs(&env);
+
+    // Inclusive at both ends
+    let snapshots = client.get_snapshots_in_range(&4, &8);
+    assert_eq!(
+        epochs_of(&env, &snapshots),
+        soroban_sdk::vec![&env, 4u64, 6, 8]
+    );
+    assert_eq!(
+        snapshots.get(0).map(|snapshot| snapshot.hash),
+        Some(create_test_hash(&env, 4))
+    );
+
+    // Boundaries that fall between stored epochs
+    assert_eq!(
+        epochs_of(&env, &client.get_snapshots_in_range(&3, &7)),
+        soroban_sdk::vec![&env, 4u64, 6]
+    );
+
+    // Single-epoch ranges
+    assert_eq!(
+        epochs_of(&env, &client.get_snapshots_in_range(&10, &10)),
+        soroban_sdk::vec![&env, 10u64]
+    );
+    assert!(client.get_snapshots_in_range(&5, &5).is_empty());
+
+    // Ranges outside the stored history
+    assert!(client.get_snapshots_in_range(&11, &50).is_empty());
+    assert_eq!(
+        epochs_of(&env, &client.get_snapshots_in_range(&0, &99)),
+        soroban_sdk::vec![&env, 2u64, 4, 6, 8, 10]
+    );
+}
+
+#[test]
+fn test_get_snapshot_or_nearest_with_sparse_epochs() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    for epoch in [1u64, 5, 10] {
+        client.submit_snapshot(&epoch, &create_test_hash(&env, epoch as u8), &admin);
+    }
+
+    assert_eq!(client.get_snapshot_or_nearest(&7), client.get_snapshot(&5));
+    assert!(client.get_snapshot(&5).is_some());
+
+    let nearest_epoch = |epoch: u64| {
+        client
+            .get_snapshot_or_nearest(&epoch)
+            .map(|snapshot| snapshot.epoch)
+    };
+    assert_eq!(nearest_epoch(5), Some(5));
+    assert_eq!(nearest_epoch(4), Some(1));
+    assert_eq!(nearest_epoch(1000), Some(10));
+    assert_eq!(nearest_epoch(0), None);
+}
+
+#[test]
+fn test_get_snapshots_in_range_too_large() {
+    let env = Env::default();
+    let client = setup_even_epochs(&env);
+
+    let too_large: soroban_sdk::Error = Error::EpochRangeTooLarge.into();
+    assert_eq!(
+        client.try_get_snapshots_in_range(&1, &(MAX_SNAPSHOT_RANGE + 1)),
+        Err(Ok(too_large))
+    );
+    assert_eq!(
+        client.get_snapshots_in_range(&1, &MAX_SNAPSHOT_RANGE).len(),
+        5
+    );
+}
+
+#[test]
+fn test_get_snapshots_in_range_inverted() {
+    let env = Env::default();
+    let client = setup_even_epochs(&env);
+
+    let inverted: soroban_sdk::Error = Error::InvalidEpochRange.into();
+    assert_eq!(client.try_get_snapshots_in_range(&8, &4), Err(Ok(inverted)));
+}
+
+#[test]
+fn test_submit_snapshot_accepts_monotonic_timestamps() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    env.ledger().set_timestamp(100);
+    assert_eq!(
+        client.submit_snapshot(&1, &create_test_hash(&env, 1), &admin),
+        100
+    );
+
+    // Equal timestamps are allowed (several epochs within one ledger second)
+    assert_eq!(
+        client.submit_snapshot(&2, &create_test_hash(&env, 2), &admin),
+        100
+    );
+
+    env.ledger().set_timestamp(250);
+    assert_eq!(
+        client.submit_snapshot(&3, &create_test_hash(&env, 3), &admin),
+        250
+    );
+    assert_eq!(
+        client
+            .get_latest_snapshot()
+            .map(|snapshot| snapshot.timestamp),
+        Some(250)
+    );
+}
+
+#[test]
+fn test_submit_snapshot_rejects_earlier_timestamp() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+
+    env.ledger().set_timestamp(500);
+    client.submit_snapshot(&1, &create_test_hash(&env, 1), &admin);
+
+    // Force the ledger clock backwards
+    env.ledger().set_timestamp(400);
+    let not_monotonic: soroban_sdk::Error = Error::TimestampNotMonotonic.into();
+    assert_eq!(
+        client.try_submit_snapshot(&2, &create_test_hash(&env, 2), &admin),
+        Err(Ok(not_monotonic))
+    );
+    assert_eq!(client.get_latest_epoch(), 1);
+}
+
+fn snapshots_ttl(env: &Env, contract_id: &Address) -> u32 {
+    env.as_contract(contract_id, || {
+        env.storage().persistent().get_ttl(&DataKey::Snapshots)
+    })
+}
+
+#[test]
+fn test_submit_snapshot_extends_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    assert_eq!(snapshots_ttl(&env, &contract_id), TTL_EXTEND_TO);
+
+    // Let the history get close to expiry, then submit
+    env.ledger()
+        .with_mut(|l| l.sequence_number += TTL_EXTEND_TO - TTL_EXTEND_THRESHOLD);
+    assert_eq!(snapshots_ttl(&env, &contract_id), TTL_EXTEND_THRESHOLD);
+
+    client.submit_snapshot(&1, &create_test_hash(&env, 1), &admin);
+    assert_eq!(snapshots_ttl(&env, &contract_id), TTL_EXTEND_TO);
+    env.as_contract(&contract_id, || {
+        assert_eq!(env.storage().instance().get_ttl(), TTL_EXTEND_TO);
+    });
+}
+
+#[test]
+fn test_bump_ttl() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    client.submit_snapshot(&1, &create_test_hash(&env, 1), &admin);
+
+    // With more than the threshold left nothing is extended
+    env.ledger()
+        .with_mut(|l| l.sequence_number += TTL_EXTEND_TO - TTL_EXTEND_THRESHOLD - 1);
+    client.bump_ttl(&admin);
+    assert_eq!(snapshots_ttl(&env, &contract_id), TTL_EXTEND_THRESHOLD + 1);
+
+    env.ledger().with_mut(|l| l.sequence_number += 1);
+    client.bump_ttl(&admin);
+    assert_eq!(snapshots_ttl(&env, &contract_id), TTL_EXTEND_TO);
+    assert_eq!(
+        client.get_snapshot(&1).map(|snapshot| snapshot.epoch),
+        Some(1)
+    );
+}
+
+#[test]
+fn test_bump_ttl_rejects_non_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, AnalyticsContract);
+    let client = AnalyticsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    client.initialize(&admin);
+    let unauthorized: soroban_sdk::Error = Error::Unauthorized.into();
+    assert_eq!(
+        client.try_bump_ttl(&Address::generate(&env)),
+        Err(Ok(unauthorized))
+    );
+}
@@ -23,4 +23,6 @@ pub enum Error {
     ProposalNotPassed = 9,
     /// Invalid proposal title
     InvalidTitle = 10,
+    /// Quorum or voting period must be greater than zero
+    InvalidConfig = 11,
 }
@@ -13,6 +13,9 @@ pub const VOTE_CAST: Symbol = symbol_short!("VOTE_CST");
 /// Topic for proposal finalized events
 pub const PROP_FINALIZED: Symbol = symbol_short!("PROP_FIN");
 
+/// Topic for quorum / voting period change events
+pub const CONFIG_CHANGED: Symbol = symbol_short!("CFG_CHG");
+
 /// Topic for governance lifecycle events (for filtering)
 pub const GOV_LIFECYCLE: Symbol = symbol_short!("GOV_LFE");
 
@@ -100,6 +103,27 @@ impl ProposalFinalizedEvent {
     }
 }
 
+/// Event emitted when the admin changes a governance parameter.
+/// `parameter` is `quorum` or `vote_prd`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfigChangedEvent {
+    pub parameter: Symbol,
+    pub old_value: u64,
+    pub new_value: u64,
+}
+
+impl ConfigChangedEvent {
+    pub fn publish(env: &Env, parameter: Symbol, old_value: u64, new_value: u64) {
+        let event = ConfigChangedEvent {
+            parameter,
+            old_value,
+            new_value,
+        };
+        env.events().publish((CONFIG_CHANGED, GOV_LIFECYCLE), event);
+    }
+}
+
 // ============================================================================
 // Event Helper Functions
 // ============================================================================
@@ -135,3 +159,7 @@ pub fn emit_proposal_finalized(
         total_voters,
     );
 }
+
+pub fn emit_config_changed(env: &Env, parameter: Symbol, old_value: u64, new_value: u64) {
+    ConfigChangedEvent::publish(env, parameter, old_value, new_value);
+}
@@ -5,8 +5,10 @@ mod events;
 
 use analytics::AnalyticsContractClient;
 use errors::Error;
-use events::{emit_proposal_created, emit_proposal_finalized, emit_vote_cast};
-use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Map, String};
+use events::{emit_config_changed, emit_proposal_created, emit_proposal_finalized, emit_vote_cast};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map, String,
+};
 
 // ============================================================================
 // Data Types
@@ -78,6 +80,8 @@ pub enum DataKey {
     VoteTally(u64),
     /// Parameter-update action for a proposal (when present, proposal is parameter type).
     ParameterAction(u64),
+    /// Quorum in force when the proposal was created.
+    ProposalQuorum(u64),
 }
 
 // ============================================================================
@@ -103,6 +107,65 @@ impl GovernanceContract {
             .set(&DataKey::VotingPeriod, &voting_period);
     }
 
+    /// Set the quorum for proposals created from now on. Only the admin can call this.
+    pub fn set_quorum(env: Env, caller: Address, quorum: u64) -> Result<(), Error> {
+        caller.require_auth();
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+
+        if caller != admin {
+            return Err(Error::UnauthorizedCaller);
+        }
+
+        if quorum == 0 {
+            return Err(Error::InvalidConfig);
+        }
+
+        let old_quorum: u64 = env.storage().instance().get(&DataKey::Quorum).unwrap_or(0);
+        env.storage().instance().set(&DataKey::Quorum, &quorum);
+
+        emit_config_changed(&env, symbol_short!("quorum"), old_quorum, quorum);
+
+        Ok(())
+    }
+
+    /// Set the voting period for proposals created from now on. Existing proposals
+    /// keep their deadlines. Only the admin can call this.
+    pub fn set_voting_period(env: Env, caller: Address, voting_period: u64) -> Result<(), Error> {
+        caller.require_auth();
+
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+
+        if caller != admin {
+            return Err(Error::UnauthorizedCaller);
+        }
+
+        if voting_period == 0 {
+            return Err(Error::InvalidConfig);
+        }
+
+        let old_period: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::VotingPeriod)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::VotingPeriod, &voting_period);
+
+        emit_config_changed(&env, symbol_short!("vote_prd"), old_period, voting_period);
+
+        Ok(())
+    }
+
     /// Create a new governance proposal. Only the admin can create proposals.
     pub fn create_proposal(
         env: Env,
@@ -176,6 +239,11 @@ impl GovernanceContract {
             .persistent()
             .set(&DataKey::VoteTally(count), &tally);
 
+        let quorum: u64 = env.storage().instance().get(&DataKey::Quorum).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProposalQuorum(count), &quorum);
+
         // Initialize votes map for this proposal
         let votes: Map<Address, VoteChoice> = Map::new(&env);
         env.storage()
@@ -268,6 +336,11 @@ impl GovernanceContract {
             .persistent()
             .set(&DataKey::VoteTally(count), &tally);
 
+        let quorum: u64 = env.storage().instance().get(&DataKey::Quorum).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProposalQuorum(count), &quorum);
+
         let votes: Map<Address, VoteChoice> = Map::new(&env);
         env.storage()
             .persistent()
@@ -390,7 +463,13 @@ impl GovernanceContract {
                 total_voters: 0,
             });
 
-        let quorum: u64 = env.storage().instance().get(&DataKey::Quorum).unwrap_or(0);
+        // Proposals keep the quorum they were created under; those created
+        // before quorums were recorded per proposal use the current one
+        let quorum: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProposalQuorum(proposal_id))
+            .unwrap_or_else(|| env.storage().instance().get(&DataKey::Quorum).unwrap_or(0));
 
         // Determine outcome: passes if quorum met AND more for than against
         let new_status = if tally.total_voters >= quorum && tally.votes_for > tally.votes_against {
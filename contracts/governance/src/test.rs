@@ -3,8 +3,9 @@
 use super::*;
 use analytics::AnalyticsContractClient;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
-    Address, BytesN, Env, String,
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    Address, BytesN, Env, IntoVal, String,
 };
 
 /// Helper function to create a 32-byte hash for testing
@@ -290,7 +291,7 @@ fn test_create_parameter_proposal() {
         &admin,
         &title,
         &target,
-        &ParameterAction::SetAdmin(new_admin),
+        &ParameterAction::SetAdmin(new_admin.clone()),
     );
     assert_eq!(proposal_id, 1);
 
@@ -307,3 +308,121 @@ fn test_create_parameter_proposal() {
         _ => panic!("expected SetAdmin"),
     }
 }
+
+#[test]
+fn test_set_voting_period_applies_to_new_proposals() {
+    let (env, client, admin) = setup();
+
+    let target = Address::generate(&env);
+    let wasm_hash = create_test_hash(&env, 11111);
+    client.create_proposal(
+        &admin,
+        &String::from_str(&env, "Before"),
+        &target,
+        &wasm_hash,
+    );
+
+    client.set_voting_period(&admin, &5000);
+    let (_, _, voting_period, _) = client.get_config();
+    assert_eq!(voting_period, 5000);
+
+    let event = env.events().all().last().unwrap();
+    assert_eq!(
+        event.1,
+        (events::CONFIG_CHANGED, events::GOV_LIFECYCLE).into_val(&env)
+    );
+    let changed: events::ConfigChangedEvent = event.2.into_val(&env);
+    assert_eq!(changed.parameter, symbol_short!("vote_prd"));
+    assert_eq!(changed.old_value, 1000);
+    assert_eq!(changed.new_value, 5000);
+
+    client.create_proposal(
+        &admin,
+        &String::from_str(&env, "After"),
+        &target,
+        &wasm_hash,
+    );
+
+    // The existing proposal keeps its original deadline
+    assert_eq!(client.get_proposal(&1).voting_ends_at, 1000);
+    assert_eq!(client.get_proposal(&2).voting_ends_at, 5000);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp = 2000;
+    });
+    let voter = Address::generate(&env);
+    assert_eq!(
+        client.try_vote(&voter, &1, &VoteChoice::For),
+        Err(Ok(Error::VotingNotActive))
+    );
+    client.vote(&voter, &2, &VoteChoice::For);
+}
+
+#[test]
+fn test_set_quorum_applies_to_new_proposals() {
+    let (env, client, admin) = setup();
+
+    let target = Address::generate(&env);
+    let wasm_hash = create_test_hash(&env, 11111);
+    client.create_proposal(
+        &admin,
+        &String::from_str(&env, "Before"),
+        &target,
+        &wasm_hash,
+    );
+
+    client.set_quorum(&admin, &3);
+    let (_, quorum, _, _) = client.get_config();
+    assert_eq!(quorum, 3);
+
+    let changed: events::ConfigChangedEvent = env.events().all().last().unwrap().2.into_val(&env);
+    assert_eq!(changed.parameter, symbol_short!("quorum"));
+    assert_eq!(changed.old_value, 2);
+    assert_eq!(changed.new_value, 3);
+
+    client.create_proposal(
+        &admin,
+        &String::from_str(&env, "After"),
+        &target,
+        &wasm_hash,
+    );
+
+    // Two votes on each: enough for the old quorum of 2, not the new one of 3
+    for proposal_id in [1, 2] {
+        for _ in 0..2 {
+            client.vote(&Address::generate(&env), &proposal_id, &VoteChoice::For);
+        }
+    }
+    env.ledger().with_mut(|li| {
+        li.timestamp = 2000;
+    });
+
+    assert_eq!(client.finalize(&1), ProposalStatus::Passed);
+    assert_eq!(client.finalize(&2), ProposalStatus::Failed);
+}
+
+#[test]
+fn test_config_updates_are_admin_only() {
+    let (env, client, admin) = setup();
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_set_quorum(&outsider, &5),
+        Err(Ok(Error::UnauthorizedCaller))
+    );
+    assert_eq!(
+        client.try_set_voting_period(&outsider, &5),
+        Err(Ok(Error::UnauthorizedCaller))
+    );
+    assert_eq!(
+        client.try_set_quorum(&admin, &0),
+        Err(Ok(Error::InvalidConfig))
+    );
+    assert_eq!(
+        client.try_set_voting_period(&admin, &0),
+        Err(Ok(Error::InvalidConfig))
+    );
+
+    let (_, quorum, voting_period, _) = client.get_config();
+    assert_eq!((quorum, voting_period), (2, 1000));
+}
@@ -1,10 +1,22 @@
 #![no_std]
 use soroban_sdk::{
     contract, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env, Map, Symbol,
+    Vec,
 };
 
 const HASH_SIZE: u32 = 32;
 const CONTRACT_VERSION: u32 = 1;
+/// Most `(epoch, hash)` pairs accepted by one `verify_snapshot_range` call
+const MAX_VERIFY_BATCH_SIZE: u32 = 100;
+
+/// Ledgers closed per day at ~5s per ledger
+const DAY_IN_LEDGERS: u32 = 17_280;
+/// Snapshot data and contract instance TTLs are extended once no more than
+/// this many ledgers remain
+pub const TTL_EXTEND_THRESHOLD: u32 = 30 * DAY_IN_LEDGERS;
+/// Ledgers snapshot data and the contract instance are kept live for when
+/// extended
+pub const TTL_EXTEND_TO: u32 = 120 * DAY_IN_LEDGERS;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -34,6 +46,19 @@ pub enum DataKey {
 #[contract]
 pub struct SnapshotContract;
 
+/// Keep the snapshot history and contract instance from being archived
+fn extend_storage_ttl(env: &Env) {
+    let persistent = env.storage().persistent();
+    for key in [DataKey::Snapshots, DataKey::LatestEpoch] {
+        if persistent.has(&key) {
+            persistent.extend_ttl(&key, TTL_EXTEND_THRESHOLD, TTL_EXTEND_TO);
+        }
+    }
+    env.storage()
+        .instance()
+        .extend_ttl(TTL_EXTEND_THRESHOLD, TTL_EXTEND_TO);
+}
+
 #[contractimpl]
 impl SnapshotContract {
     /// Internal: check if contract is stopped
@@ -71,6 +96,27 @@ impl SnapshotContract {
         env.storage().instance().set(&DataKey::Stopped, &false);
         env.events().publish((symbol_short!("RESUMED"),), (admin,));
     }
+    /// Admin-only: extend the TTL of the snapshot history and contract
+    /// instance ahead of their expiry
+    ///
+    /// Entries with more than `TTL_EXTEND_THRESHOLD` ledgers left are not
+    /// touched; the rest are extended to `TTL_EXTEND_TO` ledgers.
+    ///
+    /// # Panics
+    /// * If contract is not initialized
+    /// * If caller is not the admin
+    pub fn bump_ttl(env: Env) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+        admin.require_auth();
+        extend_storage_ttl(&env);
+        env.events()
+            .publish((symbol_short!("TTL_BUMP"),), (admin, TTL_EXTEND_TO));
+    }
+
     /// Initialize the contract with an admin address
     ///
     /// # Panics
@@ -317,6 +363,8 @@ impl SnapshotContract {
             .persistent()
             .set(&DataKey::LatestEpoch, &epoch);
 
+        extend_storage_ttl(&env);
+
         env.events()
             .publish((symbol_short!("SNAP_SUB"),), (hash, epoch, timestamp));
 
@@ -357,6 +405,28 @@ impl SnapshotContract {
         }
     }
 
+    /// Summary of stored snapshot data, for deciding when to prune or bump TTL
+    ///
+    /// # Returns
+    /// * `(snapshot_count, latest_epoch, oldest_epoch)`; epochs are 0 when no
+    ///   snapshot is stored
+    pub fn storage_stats(env: Env) -> (u32, u64, u64) {
+        Self::require_not_stopped(&env);
+        let snapshots: Map<u64, Snapshot> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Snapshots)
+            .unwrap_or(Map::new(&env));
+
+        // Map keys are kept in ascending order
+        let epochs = snapshots.keys();
+        (
+            snapshots.len(),
+            epochs.last().unwrap_or(0),
+            epochs.first().unwrap_or(0),
+        )
+    }
+
     /// Verify if a hash matches any stored snapshot
     pub fn verify_snapshot(env: Env, hash: Bytes) -> bool {
         Self::require_not_stopped(&env);
@@ -390,6 +460,59 @@ impl SnapshotContract {
         }
     }
 
+    /// Whether `epoch` is finalized, i.e. has a stored snapshot
+    ///
+    /// Submissions for an epoch that already has a snapshot, or for any
+    /// epoch not after the latest, are rejected, so a finalized epoch's
+    /// snapshot can never change. Removing the snapshot is the only way
+    /// an epoch could become unfinalized again.
+    pub fn is_finalized(env: Env, epoch: u64) -> bool {
+        Self::require_not_stopped(&env);
+        let snapshots: Map<u64, Snapshot> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Snapshots)
+            .unwrap_or(Map::new(&env));
+
+        snapshots.contains_key(epoch)
+    }
+
+    /// Verify many `(epoch, hash)` pairs against a single read of the
+    /// snapshot map
+    ///
+    /// # Panics
+    /// * If more than `MAX_VERIFY_BATCH_SIZE` pairs are given
+    ///
+    /// # Returns
+    /// * For each pair in order, whether the hash matches the snapshot stored
+    ///   at that epoch; `false` when the epoch has none
+    pub fn verify_snapshot_range(env: Env, entries: Vec<(u64, Bytes)>) -> Vec<bool> {
+        Self::require_not_stopped(&env);
+        if entries.len() > MAX_VERIFY_BATCH_SIZE {
+            panic!(
+                "Batch too large: at most {} entries, got {}",
+                MAX_VERIFY_BATCH_SIZE,
+                entries.len()
+            );
+        }
+
+        let snapshots: Map<u64, Snapshot> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Snapshots)
+            .unwrap_or(Map::new(&env));
+
+        let mut results = Vec::new(&env);
+        for (epoch, hash) in entries.iter() {
+            let matches = match snapshots.get(epoch) {
+                Some(snapshot) => snapshot.hash == hash,
+                None => false,
+            };
+            results.push_back(matches);
+        }
+        results
+    }
+
     /// Verify if a hash matches the latest snapshot
     pub fn verify_latest_snapshot(env: Env, hash: Bytes) -> bool {
         Self::require_not_stopped(&env);
@@ -474,8 +597,11 @@ mod test {
     use super::*;
     use soroban_sdk::{
         bytes,
-        testutils::{Address as _, Events},
-        Env, TryIntoVal,
+        testutils::{
+            storage::{Instance as _, Persistent as _},
+            Address as _, Events, Ledger,
+        },
+        vec, Env, TryIntoVal,
     };
 
     #[test]
@@ -911,6 +1037,188 @@ mod test {
         assert!(client.verify_snapshot_at_epoch(&hash2, &2));
     }
 
+    #[test]
+    fn test_is_finalized() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client =
+            SnapshotContractClient::new(&env, &env.register_contract(None, SnapshotContract));
+
+        assert!(!client.is_finalized(&5));
+
+        client.submit_snapshot(
+            &bytes!(
+                &env,
+                0x1111111111111111111111111111111111111111111111111111111111111111
+            ),
+            &5,
+        );
+
+        assert!(client.is_finalized(&5));
+        // Skipped and future epochs stay open
+        assert!(!client.is_finalized(&4));
+        assert!(!client.is_finalized(&6));
+    }
+
+    fn snapshots_ttl(env: &Env, contract_id: &Address) -> u32 {
+        env.as_contract(contract_id, || {
+            env.storage().persistent().get_ttl(&DataKey::Snapshots)
+        })
+    }
+
+    #[test]
+    fn test_submit_extends_ttl() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, SnapshotContract);
+        let client = SnapshotContractClient::new(&env, &contract_id);
+
+        client.submit_snapshot(
+            &bytes!(
+                &env,
+                0x1111111111111111111111111111111111111111111111111111111111111111
+            ),
+            &1,
+        );
+
+        assert_eq!(snapshots_ttl(&env, &contract_id), TTL_EXTEND_TO);
+        env.as_contract(&contract_id, || {
+            assert_eq!(
+                env.storage().persistent().get_ttl(&DataKey::LatestEpoch),
+                TTL_EXTEND_TO
+            );
+            assert_eq!(env.storage().instance().get_ttl(), TTL_EXTEND_TO);
+        });
+    }
+
+    #[test]
+    fn test_bump_ttl_extends_expiring_snapshots() {
+        let env = Env::default();
+        let admin = Address::generate(&env);
+        env.mock_all_auths();
+
+        let contract_id = env.register_contract(None, SnapshotContract);
+        let client = SnapshotContractClient::new(&env, &contract_id);
+        client.initialize(&admin);
+        client.submit_snapshot(
+            &bytes!(
+                &env,
+                0x1111111111111111111111111111111111111111111111111111111111111111
+            ),
+            &1,
+        );
+
+        // Above the threshold nothing is extended
+        let elapsed = TTL_EXTEND_TO - TTL_EXTEND_THRESHOLD - 1;
+        env.ledger().with_mut(|l| l.sequence_number += elapsed);
+        client.bump_ttl();
+        assert_eq!(snapshots_ttl(&env, &contract_id), TTL_EXTEND_THRESHOLD + 1);
+
+        env.ledger().with_mut(|l| l.sequence_number += 1);
+        assert_eq!(snapshots_ttl(&env, &contract_id), TTL_EXTEND_THRESHOLD);
+        client.bump_ttl();
+        assert_eq!(snapshots_ttl(&env, &contract_id), TTL_EXTEND_TO);
+        assert_eq!(client.get_snapshot(&1).len(), HASH_SIZE);
+    }
+
+    #[test]
+    fn test_storage_stats() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client =
+            SnapshotContractClient::new(&env, &env.register_contract(None, SnapshotContract));
+
+        assert_eq!(client.storage_stats(), (0, 0, 0));
+
+        client.submit_snapshot(
+            &bytes!(
+                &env,
+                0x1111111111111111111111111111111111111111111111111111111111111111
+            ),
+            &3,
+        );
+        assert_eq!(client.storage_stats(), (1, 3, 3));
+
+        client.submit_snapshot(
+            &bytes!(
+                &env,
+                0x2222222222222222222222222222222222222222222222222222222222222222
+            ),
+            &8,
+        );
+        client.submit_snapshot(
+            &bytes!(
+                &env,
+                0x3333333333333333333333333333333333333333333333333333333333333333
+            ),
+            &21,
+        );
+        assert_eq!(client.storage_stats(), (3, 21, 3));
+    }
+
+    #[test]
+    fn test_verify_snapshot_range() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client =
+            SnapshotContractClient::new(&env, &env.register_contract(None, SnapshotContract));
+
+        let hash1 = bytes!(
+            &env,
+            0x1111111111111111111111111111111111111111111111111111111111111111
+        );
+        let hash2 = bytes!(
+            &env,
+            0x2222222222222222222222222222222222222222222222222222222222222222
+        );
+        let unknown = bytes!(
+            &env,
+            0x9999999999999999999999999999999999999999999999999999999999999999
+        );
+
+        client.submit_snapshot(&hash1, &1);
+        client.submit_snapshot(&hash2, &2);
+
+        let entries = vec![
+            &env,
+            (1u64, hash1.clone()),
+            (2u64, hash1.clone()),
+            (2u64, hash2.clone()),
+            (1u64, unknown.clone()),
+            (3u64, hash2.clone()),
+        ];
+        assert_eq!(
+            client.verify_snapshot_range(&entries),
+            vec![&env, true, false, true, false, false]
+        );
+
+        assert_eq!(client.verify_snapshot_range(&vec![&env]), vec![&env]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Batch too large")]
+    fn test_verify_snapshot_range_rejects_oversized_batch() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let client =
+            SnapshotContractClient::new(&env, &env.register_contract(None, SnapshotContract));
+
+        let hash = bytes!(
+            &env,
+            0x1111111111111111111111111111111111111111111111111111111111111111
+        );
+        let mut entries = Vec::new(&env);
+        for epoch in 0..=u64::from(MAX_VERIFY_BATCH_SIZE) {
+            entries.push_back((epoch, hash.clone()));
+        }
+        client.verify_snapshot_range(&entries);
+    }
+
     #[test]
     fn test_verify_latest_snapshot() {
         let env = Env::default();